@@ -1,5 +1,7 @@
 use thiserror::Error;
 
+use crate::runner::RunResult;
+
 /// Provider-neutral alias for the agent error type.
 pub type AgentError = ClaudeAgentError;
 
@@ -15,12 +17,86 @@ pub enum ClaudeAgentError {
         source: serde_json::Error,
     },
 
+    #[error("stream-json line exceeded the limit after {bytes} bytes")]
+    LineTooLong { bytes: usize },
+
     #[error("Process error: {0}")]
     Process(String),
 
+    #[error(
+        "claude CLI {found} does not support the stream-json protocol; \
+         upgrade to {required} or newer (npm install -g @anthropic-ai/claude-code)"
+    )]
+    UnsupportedCliVersion { found: String, required: String },
+
+    #[error("Invalid query options: {0}")]
+    InvalidOptions(String),
+
     #[error("MCP error: {0}")]
     Mcp(String),
 
+    #[error("MCP server '{server}' is misconfigured: {detail}")]
+    McpConfigInvalid { server: String, detail: String },
+
+    #[error("run budget exceeded: spent {spent} {unit} of the {limit} {unit} limit")]
+    BudgetExceeded {
+        spent: f64,
+        limit: f64,
+        /// `"USD"` for a cost ceiling, `"tokens"` for a token ceiling.
+        unit: &'static str,
+        /// The run up to the message that crossed the ceiling.
+        partial: Box<RunResult>,
+    },
+
+    #[error("run stalled: '{action}' failed {attempts} times in a row")]
+    RunStalled {
+        action: String,
+        attempts: u32,
+        /// The run up to the result of the last failed attempt.
+        partial: Box<RunResult>,
+    },
+
     #[error("Session not found for slug: {0}")]
     SessionNotFound(String),
 }
+
+impl ClaudeAgentError {
+    /// What the run did before this error stopped it, for errors that abort
+    /// a run partway through.
+    pub fn partial_result(&self) -> Option<&RunResult> {
+        match self {
+            ClaudeAgentError::BudgetExceeded { partial, .. }
+            | ClaudeAgentError::RunStalled { partial, .. } => Some(partial),
+            _ => None,
+        }
+    }
+
+    fn partial_result_mut(&mut self) -> Option<&mut RunResult> {
+        match self {
+            ClaudeAgentError::BudgetExceeded { partial, .. }
+            | ClaudeAgentError::RunStalled { partial, .. } => Some(partial),
+            _ => None,
+        }
+    }
+
+    /// Fold the earlier attempts of a compacted run into the partial result.
+    pub(crate) fn with_earlier_attempts(
+        mut self,
+        tool_calls: &[crate::runner::ToolCallRecord],
+        tool_timings: &[crate::runner::ToolTiming],
+        cost_usd: f64,
+        turns: u32,
+        compactions: u32,
+    ) -> Self {
+        if let Some(partial) = self.partial_result_mut() {
+            partial.tool_calls.splice(0..0, tool_calls.iter().cloned());
+            partial
+                .tool_timings
+                .splice(0..0, tool_timings.iter().cloned());
+            partial.total_cost_usd += cost_usd;
+            partial.num_turns += turns;
+            partial.compactions = compactions;
+        }
+        self
+    }
+}
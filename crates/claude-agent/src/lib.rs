@@ -51,6 +51,7 @@
 //! - Agent runner (`runner.rs`): ✅ Week 4
 
 pub mod error;
+pub mod pricing;
 pub mod provider;
 pub mod runner;
 pub mod types;
@@ -58,22 +59,34 @@ pub mod types;
 pub(crate) mod process;
 pub mod session;
 pub mod stream;
+pub mod transport;
 
 #[cfg(test)]
 mod tests;
 
 pub use error::{AgentError, ClaudeAgentError};
+pub use pricing::{ModelPricing, PricingTable};
 pub use provider::claude::ClaudeProvider;
 pub use provider::codex::CodexProvider;
 pub use provider::opencode::OpenCodeProvider;
 pub use provider::AgentProvider;
-pub use runner::{run as agent_run, RunConfig, RunResult};
-pub use session::SessionStore;
-pub use stream::{AgentStream, QueryStream};
+pub use runner::{
+    query_with_fallbacks, run as agent_run, RunConfig, RunResult, RunnerBuilder, ToolCallRecord,
+    ToolTiming,
+};
+pub use session::{Checkpoint, RecordedMessage, SessionStore, TranscriptTail, TranscriptWriter};
+pub use stream::{
+    user_input_channel, AgentStream, QueryStream, Sequenced, SequencedStream, StreamEnd, UserInput,
+    UserInputSender,
+};
+pub use transport::{MockTransport, Transport};
 pub use types::{
-    AgentEvent, AssistantContent, AssistantMessage, ContentBlock, Effort, McpServerConfig, Message,
-    PermissionMode, QueryOptions, ResultError, ResultMessage, ResultSuccess, SystemMessage,
-    SystemPayload, ThinkingBlock, TokenUsage, ToolCall, ToolResultEvent, UserMessage,
+    AgentEvent, AssistantContent, AssistantDeltaMessage, AssistantMessage, ContentBlock,
+    ControlRequest, ControlRequestMessage, Effort, ImageSource, McpServerConfig, McpTransport,
+    Message, ModelFallbackPayload, PermissionDecision, PermissionMode, PermissionPolicy,
+    QueryOptions, ResultError, ResultMessage, ResultSuccess, SystemInit, SystemMessage,
+    SystemPayload, ThinkingBlock, TokenUsage, ToolCall, ToolResultContent, ToolResultEvent,
+    UserMessage, DEFAULT_MAX_LINE_BYTES, DEFAULT_MAX_THINKING_TOKENS, MIN_CLI_VERSION,
 };
 
 /// Convenience `Result` alias for this crate.
@@ -0,0 +1,159 @@
+//! Per-model token prices, used to estimate what a query cost when the
+//! provider does not report it.
+//!
+//! [`PricingTable::default`] holds built-in rates for the common Claude and
+//! OpenAI models; callers layer project overrides on top with
+//! [`PricingTable::with_override`]. Rates are USD per million tokens.
+
+use crate::types::TokenUsage;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+// ─── ModelPricing ─────────────────────────────────────────────────────────
+
+/// Rates for one model, in USD per million tokens.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Deserialize, Serialize)]
+pub struct ModelPricing {
+    pub input: f64,
+    pub output: f64,
+    /// Tokens written to the prompt cache.
+    #[serde(default)]
+    pub cache_write: f64,
+    /// Tokens read back from the prompt cache.
+    #[serde(default)]
+    pub cache_read: f64,
+}
+
+impl ModelPricing {
+    pub const fn new(input: f64, output: f64, cache_write: f64, cache_read: f64) -> Self {
+        Self {
+            input,
+            output,
+            cache_write,
+            cache_read,
+        }
+    }
+}
+
+/// Built-in rates, keyed by model name prefix.
+const BUILTIN_PRICING: &[(&str, ModelPricing)] = &[
+    ("claude-opus-4", ModelPricing::new(15.0, 75.0, 18.75, 1.5)),
+    ("claude-opus-4-5", ModelPricing::new(5.0, 25.0, 6.25, 0.5)),
+    ("claude-opus-4-6", ModelPricing::new(5.0, 25.0, 6.25, 0.5)),
+    ("claude-sonnet-4", ModelPricing::new(3.0, 15.0, 3.75, 0.3)),
+    ("claude-haiku-4", ModelPricing::new(1.0, 5.0, 1.25, 0.1)),
+    ("claude-3-5-haiku", ModelPricing::new(0.8, 4.0, 1.0, 0.08)),
+    ("opus", ModelPricing::new(5.0, 25.0, 6.25, 0.5)),
+    ("sonnet", ModelPricing::new(3.0, 15.0, 3.75, 0.3)),
+    ("haiku", ModelPricing::new(1.0, 5.0, 1.25, 0.1)),
+    ("gpt-4.1", ModelPricing::new(2.0, 8.0, 0.0, 0.5)),
+    ("gpt-5", ModelPricing::new(1.25, 10.0, 0.0, 0.125)),
+];
+
+// ─── PricingTable ─────────────────────────────────────────────────────────
+
+/// Model name → [`ModelPricing`].
+///
+/// A model matches its exact entry first, then the longest entry that is a
+/// prefix of its name, so `claude-sonnet-4` prices `claude-sonnet-4-6`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PricingTable {
+    models: BTreeMap<String, ModelPricing>,
+}
+
+impl Default for PricingTable {
+    fn default() -> Self {
+        Self {
+            models: BUILTIN_PRICING
+                .iter()
+                .map(|(model, pricing)| (model.to_string(), *pricing))
+                .collect(),
+        }
+    }
+}
+
+impl PricingTable {
+    /// A table with no entries at all, not even the built-ins.
+    pub fn empty() -> Self {
+        Self {
+            models: BTreeMap::new(),
+        }
+    }
+
+    /// Set the rates for `model`, replacing any built-in entry.
+    pub fn with_override(mut self, model: impl Into<String>, pricing: ModelPricing) -> Self {
+        self.models.insert(model.into(), pricing);
+        self
+    }
+
+    /// Rates for `model`, if any entry matches it.
+    pub fn get(&self, model: &str) -> Option<&ModelPricing> {
+        self.models.get(model).or_else(|| {
+            self.models
+                .iter()
+                .filter(|(key, _)| model.starts_with(key.as_str()))
+                .max_by_key(|(key, _)| key.len())
+                .map(|(_, pricing)| pricing)
+        })
+    }
+}
+
+// ─── TokenUsage ───────────────────────────────────────────────────────────
+
+impl TokenUsage {
+    /// USD cost of this usage at `model`'s rates in `table`; `None` when the
+    /// table has no entry for the model.
+    pub fn billable_cost(&self, model: &str, table: &PricingTable) -> Option<f64> {
+        let rates = table.get(model)?;
+        let cost = self.input_tokens as f64 * rates.input
+            + self.output_tokens as f64 * rates.output
+            + self.cache_creation_input_tokens.unwrap_or(0) as f64 * rates.cache_write
+            + self.cache_read_input_tokens.unwrap_or(0) as f64 * rates.cache_read;
+        Some(cost / 1_000_000.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn usage() -> TokenUsage {
+        TokenUsage {
+            input_tokens: 1_000_000,
+            output_tokens: 100_000,
+            cache_creation_input_tokens: Some(200_000),
+            cache_read_input_tokens: Some(1_000_000),
+        }
+    }
+
+    fn assert_cost(actual: Option<f64>, expected: f64) {
+        let actual = actual.expect("model should be priced");
+        assert!((actual - expected).abs() < 1e-9, "{actual} != {expected}");
+    }
+
+    #[test]
+    fn billable_cost_uses_builtin_rates_by_default() {
+        let table = PricingTable::default();
+        // 1M × $3 + 0.1M × $15 + 0.2M × $3.75 + 1M × $0.30
+        assert_cost(usage().billable_cost("claude-sonnet-4-6", &table), 5.55);
+        // The longer `claude-opus-4-6` entry wins over `claude-opus-4`.
+        assert_cost(usage().billable_cost("claude-opus-4-6", &table), 9.25);
+        assert_cost(usage().billable_cost("claude-opus-4-1", &table), 27.75);
+        assert_eq!(usage().billable_cost("mystery-model", &table), None);
+    }
+
+    #[test]
+    fn billable_cost_prefers_overrides() {
+        let table = PricingTable::default()
+            .with_override("claude-sonnet-4-6", ModelPricing::new(1.0, 2.0, 0.0, 0.0))
+            .with_override("mystery-model", ModelPricing::new(10.0, 0.0, 0.0, 0.0));
+        assert_cost(usage().billable_cost("claude-sonnet-4-6", &table), 1.2);
+        // Other Sonnet versions still fall back to the built-in prefix entry.
+        assert_cost(usage().billable_cost("claude-sonnet-4-5", &table), 5.55);
+        assert_cost(usage().billable_cost("mystery-model", &table), 10.0);
+        assert_eq!(
+            usage().billable_cost("claude-sonnet-4-6", &PricingTable::empty()),
+            None
+        );
+    }
+}
@@ -1,10 +1,15 @@
 use std::process::Stdio;
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
-use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader, Lines};
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncWriteExt, BufReader};
 use tokio::process::{Child, ChildStdin, ChildStdout, Command};
 
-use crate::types::{Message, PermissionMode, QueryOptions};
+use crate::stream::user_message;
+use crate::types::{
+    Message, PermissionMode, QueryOptions, DEFAULT_MAX_LINE_BYTES, DEFAULT_MAX_THINKING_TOKENS,
+    MIN_CLI_VERSION,
+};
 use crate::{ClaudeAgentError, Result};
 
 // ─── ClaudeProcess ────────────────────────────────────────────────────────
@@ -17,10 +22,24 @@ use crate::{ClaudeAgentError, Result};
 /// in a background task and surfaced on process exit errors.
 pub(crate) struct ClaudeProcess {
     child: Child,
-    lines: Lines<BufReader<ChildStdout>>,
+    stdout: BufReader<ChildStdout>,
+    /// Longest stdout line accepted before `next_message` fails.
+    max_line_bytes: usize,
+    /// Partial stdout line, kept across cancelled reads.
+    line_buf: Vec<u8>,
     stdin: Option<ChildStdin>,
     /// Stderr output collected by a background reader task.
     stderr_buf: Arc<Mutex<String>>,
+    /// The stderr reader; awaited on exit so the buffer is complete.
+    stderr_task: Option<tokio::task::JoinHandle<()>>,
+    /// The `claude` binary, asked for its version when the output suggests
+    /// a CLI too old for stream-json. `None` for mock commands.
+    executable: Option<String>,
+    /// Whether any message has parsed; only a failure before the first one
+    /// can be an unsupported CLI.
+    seen_message: bool,
+    /// How the child exited, once `wait_exit_error` has reaped it.
+    exit_status: Option<std::process::ExitStatus>,
 }
 
 impl ClaudeProcess {
@@ -32,7 +51,8 @@ impl ClaudeProcess {
     /// `CLAUDECODE` is removed from the environment so this works both from a
     /// terminal and from inside a running Claude session (e.g., during `sdlc run`).
     pub(crate) async fn spawn(prompt: &str, opts: &QueryOptions) -> Result<Self> {
-        let mut cmd = build_command(opts);
+        opts.validate()?;
+        let mut cmd = build_command(opts)?;
         cmd.env_remove("CLAUDECODE");
 
         // Apply additional env vars from options
@@ -40,18 +60,20 @@ impl ClaudeProcess {
             cmd.env(k, v);
         }
 
-        let mut process = Self::from_command(cmd)?;
+        let mut process = Self::from_command(cmd)?
+            .with_max_line_bytes(opts.max_line_bytes.unwrap_or(DEFAULT_MAX_LINE_BYTES))
+            .with_executable(opts.path_to_executable.as_deref().unwrap_or("claude"));
 
-        // Send the initial prompt as a user message via stdin
-        let user_msg = serde_json::json!({
-            "type": "user",
-            "message": {
-                "role": "user",
-                "content": [{"type": "text", "text": prompt}]
-            }
-        });
-        process.send_message(&user_msg).await?;
-        process.close_stdin();
+        // Send the initial prompt as a user message via stdin. A CLI that
+        // rejected its flags has already exited; report why, not EPIPE.
+        if let Err(e) = process.send_message(&user_message(prompt)).await {
+            return Err(process.wait_exit_error().await.unwrap_or(e));
+        }
+        // A policy answers permission prompts and injected user input is
+        // written over stdin, so it stays open until the process is terminated.
+        if opts.user_input.is_none() && !matches!(opts.permission_mode, PermissionMode::Policy(_)) {
+            process.close_stdin();
+        }
 
         Ok(process)
     }
@@ -66,7 +88,9 @@ impl ClaudeProcess {
     fn from_command(mut cmd: Command) -> Result<Self> {
         cmd.stdin(Stdio::piped())
             .stdout(Stdio::piped())
-            .stderr(Stdio::piped());
+            .stderr(Stdio::piped())
+            // Last resort if the owning task is aborted before `terminate`.
+            .kill_on_drop(true);
 
         let mut child = cmd.spawn().map_err(ClaudeAgentError::Io)?;
 
@@ -81,7 +105,7 @@ impl ClaudeProcess {
         // This matches the TS SDK pattern: stderr is captured and surfaced
         // when the process exits with an error.
         let stderr_buf = Arc::new(Mutex::new(String::new()));
-        if let Some(stderr) = child.stderr.take() {
+        let stderr_task = child.stderr.take().map(|stderr| {
             let buf = Arc::clone(&stderr_buf);
             tokio::spawn(async move {
                 let mut reader = BufReader::new(stderr).lines();
@@ -93,18 +117,41 @@ impl ClaudeProcess {
                         b.push_str(&line);
                     }
                 }
-            });
-        }
+            })
+        });
 
-        let lines = BufReader::new(stdout).lines();
         Ok(Self {
             child,
-            lines,
+            stdout: BufReader::new(stdout),
+            max_line_bytes: DEFAULT_MAX_LINE_BYTES,
+            line_buf: Vec::new(),
             stdin,
             stderr_buf,
+            stderr_task,
+            executable: None,
+            seen_message: false,
+            exit_status: None,
         })
     }
 
+    /// Cap the length of a single stdout line (default
+    /// [`DEFAULT_MAX_LINE_BYTES`]).
+    pub(crate) fn with_max_line_bytes(mut self, max: usize) -> Self {
+        self.max_line_bytes = max;
+        self
+    }
+
+    /// Record the binary this process runs, for version checks on failure.
+    pub(crate) fn with_executable(mut self, executable: impl Into<String>) -> Self {
+        self.executable = Some(executable.into());
+        self
+    }
+
+    /// OS process id, until the child has been reaped.
+    pub(crate) fn id(&self) -> Option<u32> {
+        self.child.id()
+    }
+
     /// Write a JSON message to the subprocess stdin.
     pub(crate) async fn send_message(&mut self, msg: &serde_json::Value) -> Result<()> {
         let stdin = self
@@ -117,8 +164,17 @@ impl ClaudeProcess {
         })?;
         buf.push(b'\n');
 
-        stdin.write_all(&buf).await.map_err(ClaudeAgentError::Io)?;
-        stdin.flush().await.map_err(ClaudeAgentError::Io)?;
+        let written = match stdin.write_all(&buf).await {
+            Ok(()) => stdin.flush().await,
+            Err(e) => Err(e),
+        };
+        if let Err(e) = written {
+            // The child stopped reading; nothing more can reach it.
+            if e.kind() == std::io::ErrorKind::BrokenPipe {
+                self.stdin = None;
+            }
+            return Err(ClaudeAgentError::Io(e));
+        }
 
         Ok(())
     }
@@ -128,30 +184,67 @@ impl ClaudeProcess {
         self.stdin.take();
     }
 
+    /// Whether stdin was closed, by [`close_stdin`](Self::close_stdin) or
+    /// because the child stopped reading it.
+    pub(crate) fn stdin_closed(&self) -> bool {
+        self.stdin.is_none()
+    }
+
+    /// The child's exit status, once [`wait_exit_error`](Self::wait_exit_error)
+    /// has reaped it.
+    pub(crate) fn exit_status(&self) -> Option<std::process::ExitStatus> {
+        self.exit_status
+    }
+
     /// Read the next non-empty JSONL line from stdout and deserialize it.
     ///
     /// Unknown message types (e.g. `rate_limit_event`) are silently skipped,
     /// matching the TS SDK's behaviour of ignoring types it doesn't recognise.
     ///
-    /// Returns `Ok(None)` on EOF (process exited normally).
+    /// Returns `Ok(None)` on EOF (process exited normally), and
+    /// `ClaudeAgentError::LineTooLong` once a line outgrows `max_line_bytes`
+    /// without buffering the rest of it.
     pub(crate) async fn next_message(&mut self) -> Result<Option<Message>> {
         loop {
-            match self.lines.next_line().await {
-                Err(e) => return Err(ClaudeAgentError::Io(e)),
-                Ok(None) => return Ok(None),
-                Ok(Some(line)) => {
+            let read =
+                read_bounded_line(&mut self.stdout, &mut self.line_buf, self.max_line_bytes).await;
+            let buf = std::mem::take(&mut self.line_buf);
+            match read {
+                Err(e) => return Err(e),
+                Ok(false) => return Ok(None),
+                Ok(true) => {
+                    let line = std::str::from_utf8(&buf).map_err(|e| {
+                        ClaudeAgentError::Io(std::io::Error::new(
+                            std::io::ErrorKind::InvalidData,
+                            e,
+                        ))
+                    })?;
                     let trimmed = line.trim();
                     if trimmed.is_empty() {
                         continue;
                     }
                     match serde_json::from_str::<Message>(trimmed) {
-                        Ok(msg) => return Ok(Some(msg)),
+                        Ok(msg) => {
+                            self.seen_message = true;
+                            return Ok(Some(msg));
+                        }
                         Err(e) => {
                             // If the line is valid JSON with an unknown "type",
                             // skip it rather than failing the stream.
                             if is_unknown_message_type(trimmed) {
                                 continue;
                             }
+                            // Plain text up front: a CLI that predates
+                            // stream-json printing its usual output.
+                            if !self.seen_message
+                                && serde_json::from_str::<serde_json::Value>(trimmed).is_err()
+                            {
+                                if let Some(found) = self.cli_version().await {
+                                    if version_lt(&found, MIN_CLI_VERSION) {
+                                        return Err(unsupported_cli(found));
+                                    }
+                                }
+                            }
                             return Err(ClaudeAgentError::Parse {
                                 line: trimmed.to_owned(),
                                 source: e,
@@ -173,11 +266,18 @@ impl ClaudeProcess {
             Ok(s) => s,
             Err(e) => return Some(ClaudeAgentError::Io(e)),
         };
+        self.exit_status = Some(status);
 
         if status.success() {
             return None;
         }
 
+        // The child has exited, so its stderr pipe closes unless a grandchild
+        // still holds it; bound the wait for that case.
+        if let Some(task) = self.stderr_task.take() {
+            let _ = tokio::time::timeout(STDERR_DRAIN_TIMEOUT, task).await;
+        }
+
         let stderr = self
             .stderr_buf
             .lock()
@@ -185,6 +285,11 @@ impl ClaudeProcess {
             .map(|b| b.clone())
             .unwrap_or_default();
 
+        if !self.seen_message && rejects_stream_json(&stderr) {
+            let found = self.cli_version().await;
+            return Some(unsupported_cli(found.unwrap_or_else(|| "unknown".into())));
+        }
+
         let msg = if let Some(code) = status.code() {
             if stderr.is_empty() {
                 format!("Claude Code process exited with code {code}")
@@ -203,12 +308,84 @@ impl ClaudeProcess {
         Some(ClaudeAgentError::Process(msg))
     }
 
-    /// Kill the subprocess (best-effort; errors are silently ignored).
-    pub(crate) async fn kill(&mut self) {
+    /// `<executable> --version`, e.g. `0.2.9` from `0.2.9 (Claude Code)`.
+    async fn cli_version(&self) -> Option<String> {
+        let mut cmd = Command::new(self.executable.as_deref()?);
+        cmd.arg("--version")
+            .stdin(Stdio::null())
+            .stderr(Stdio::null())
+            .kill_on_drop(true);
+        let output = tokio::time::timeout(VERSION_PROBE_TIMEOUT, cmd.output())
+            .await
+            .ok()?
+            .ok()?;
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        stdout.split_whitespace().next().map(str::to_string)
+    }
+
+    /// Stop the child and reap it: SIGTERM, up to `grace` to exit, then
+    /// SIGKILL. Windows has no polite equivalent, so it goes straight to
+    /// `TerminateProcess`. A child that already exited is just reaped.
+    pub(crate) async fn terminate(&mut self, grace: Duration) {
+        if let Ok(Some(_)) = self.child.try_wait() {
+            return;
+        }
+        #[cfg(unix)]
+        if let Some(pid) = self.child.id() {
+            // SAFETY: kill(2) with a pid we own; the child is not yet reaped,
+            // so the pid cannot have been recycled.
+            unsafe {
+                libc::kill(pid as libc::pid_t, libc::SIGTERM);
+            }
+            if tokio::time::timeout(grace, self.child.wait()).await.is_ok() {
+                return;
+            }
+        }
+        #[cfg(not(unix))]
+        let _ = grace;
+        // Sends SIGKILL / TerminateProcess and waits for the exit.
         let _ = self.child.kill().await;
     }
 }
 
+/// How long an exited child's stderr reader gets to finish.
+const STDERR_DRAIN_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// How long `claude --version` gets when diagnosing a failed start.
+const VERSION_PROBE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Stderr fragments (lowercased) of a CLI rejecting the stream-json flags.
+const UNSUPPORTED_FLAG_MARKERS: &[&str] = &["unknown option", "unrecognized option"];
+
+fn rejects_stream_json(stderr: &str) -> bool {
+    let stderr = stderr.to_lowercase();
+    UNSUPPORTED_FLAG_MARKERS.iter().any(|m| stderr.contains(m))
+        && ["--output-format", "--input-format", "stream-json"]
+            .iter()
+            .any(|flag| stderr.contains(flag))
+}
+
+fn unsupported_cli(found: String) -> ClaudeAgentError {
+    ClaudeAgentError::UnsupportedCliVersion {
+        found,
+        required: MIN_CLI_VERSION.to_string(),
+    }
+}
+
+/// `found < required` for dotted numeric versions; false if either is not one.
+fn version_lt(found: &str, required: &str) -> bool {
+    let parse = |v: &str| -> Option<Vec<u64>> {
+        v.trim_start_matches('v')
+            .split('.')
+            .map(|part| part.parse().ok())
+            .collect()
+    };
+    match (parse(found), parse(required)) {
+        (Some(found), Some(required)) => found < required,
+        _ => false,
+    }
+}
+
 /// Check if a JSON line has a `"type"` field with a value we don't recognise.
 /// If it's valid JSON with a type field, it's an unknown message type and
 /// should be skipped. If it's not valid JSON, it's a genuine parse error.
@@ -222,9 +399,41 @@ fn is_unknown_message_type(line: &str) -> bool {
     }
 }
 
+/// Append the rest of one `\n`-terminated line to `buf` (terminator
+/// stripped), failing with `LineTooLong` as soon as it would exceed `max`
+/// bytes. Returns `Ok(false)` at EOF when nothing was read.
+///
+/// Cancel-safe like `Lines::next_line`: bytes are only consumed once they
+/// are in `buf`, so a dropped call resumes from the same line.
+async fn read_bounded_line<R: AsyncBufRead + Unpin>(
+    reader: &mut R,
+    buf: &mut Vec<u8>,
+    max: usize,
+) -> Result<bool> {
+    loop {
+        let available = reader.fill_buf().await.map_err(ClaudeAgentError::Io)?;
+        if available.is_empty() {
+            return Ok(!buf.is_empty());
+        }
+        let newline = available.iter().position(|&b| b == b'\n');
+        let chunk = &available[..newline.unwrap_or(available.len())];
+        if buf.len() + chunk.len() > max {
+            return Err(ClaudeAgentError::LineTooLong {
+                bytes: buf.len() + chunk.len(),
+            });
+        }
+        buf.extend_from_slice(chunk);
+        let used = chunk.len() + usize::from(newline.is_some());
+        reader.consume(used);
+        if newline.is_some() {
+            return Ok(true);
+        }
+    }
+}
+
 // ─── Command builder ──────────────────────────────────────────────────────
 
-fn build_command(opts: &QueryOptions) -> Command {
+pub(crate) fn build_command(opts: &QueryOptions) -> Result<Command> {
     let exe = opts.path_to_executable.as_deref().unwrap_or("claude");
     let mut cmd = Command::new(exe);
 
@@ -239,6 +448,11 @@ fn build_command(opts: &QueryOptions) -> Command {
         cmd.arg("--model").arg(model);
     }
 
+    if opts.include_thinking {
+        cmd.arg("--max-thinking-tokens")
+            .arg(DEFAULT_MAX_THINKING_TOKENS.to_string());
+    }
+
     if let Some(max_turns) = opts.max_turns {
         cmd.arg("--max-turns").arg(max_turns.to_string());
     }
@@ -252,16 +466,22 @@ fn build_command(opts: &QueryOptions) -> Command {
     }
 
     if !opts.allowed_tools.is_empty() {
-        cmd.arg("--allowed-tools").args(&opts.allowed_tools);
+        cmd.arg("--allowedTools").arg(opts.allowed_tools.join(","));
     }
 
     if !opts.disallowed_tools.is_empty() {
-        cmd.arg("--disallowed-tools").args(&opts.disallowed_tools);
+        cmd.arg("--disallowedTools")
+            .arg(opts.disallowed_tools.join(","));
     }
 
-    if opts.permission_mode != PermissionMode::Default {
-        cmd.arg("--permission-mode")
-            .arg(opts.permission_mode.as_str());
+    match &opts.permission_mode {
+        PermissionMode::Default => {}
+        PermissionMode::Policy(_) => {
+            cmd.arg("--permission-prompt-tool").arg("stdio");
+        }
+        mode => {
+            cmd.arg("--permission-mode").arg(mode.as_str());
+        }
     }
 
     if let Some(sp) = &opts.system_prompt {
@@ -274,6 +494,12 @@ fn build_command(opts: &QueryOptions) -> Command {
 
     if let Some(resume) = &opts.resume {
         cmd.arg("--resume").arg(resume);
+        if let Some(uuid) = &opts.resume_session_at {
+            cmd.arg("--resume-session-at").arg(uuid);
+        }
+        if opts.fork_session {
+            cmd.arg("--fork-session");
+        }
     }
 
     if opts.continue_conversation {
@@ -290,7 +516,7 @@ fn build_command(opts: &QueryOptions) -> Command {
         }
     }
 
-    for dir in &opts.additional_directories {
+    for dir in opts.additional_dir_paths()? {
         cmd.arg("--add-dir").arg(dir);
     }
 
@@ -298,7 +524,7 @@ fn build_command(opts: &QueryOptions) -> Command {
         cmd.arg("--debug");
     }
 
-    if opts.include_partial_messages {
+    if opts.include_partial_messages || opts.stream_deltas {
         cmd.arg("--include-partial-messages");
     }
 
@@ -312,47 +538,18 @@ fn build_command(opts: &QueryOptions) -> Command {
 
     // NOTE: prompt is NOT a positional arg — it's sent via stdin
 
-    cmd
+    Ok(cmd)
 }
 
 /// Serialise `McpServerConfig` entries into the JSON string expected by
 /// `claude --mcp-config '...'`.
 ///
-/// Format: `{"mcpServers":{"<name>":{"type":"stdio","command":"...","args":[...],"env":{...}}}}`
+/// Format: `{"mcpServers":{"<name>":{"type":"stdio","command":"...","args":[...],"env":{...}}}}`,
+/// or `{"type":"http","url":"..."}` for HTTP servers.
 fn build_mcp_config_json(servers: &[crate::types::McpServerConfig]) -> Result<String> {
     let mut mcp_servers = serde_json::Map::new();
-
     for srv in servers {
-        let mut cfg = serde_json::Map::new();
-        cfg.insert("type".into(), serde_json::Value::String("stdio".into()));
-        cfg.insert(
-            "command".into(),
-            serde_json::Value::String(srv.command.clone()),
-        );
-
-        if !srv.args.is_empty() {
-            cfg.insert(
-                "args".into(),
-                serde_json::Value::Array(
-                    srv.args
-                        .iter()
-                        .map(|a| serde_json::Value::String(a.clone()))
-                        .collect(),
-                ),
-            );
-        }
-
-        if !srv.env.is_empty() {
-            let env: serde_json::Map<String, serde_json::Value> = srv
-                .env
-                .iter()
-                .map(|(k, v)| (k.clone(), serde_json::Value::String(v.clone())))
-                .collect();
-            cfg.insert("env".into(), serde_json::Value::Object(env));
-        }
-
-        mcp_servers.insert(srv.name.clone(), serde_json::Value::Object(cfg));
+        mcp_servers.insert(srv.name.clone(), srv.config_json());
     }
-
     Ok(serde_json::json!({ "mcpServers": mcp_servers }).to_string())
 }
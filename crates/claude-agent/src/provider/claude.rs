@@ -1,14 +1,15 @@
 use std::future::Future;
 use std::pin::Pin;
 
+use futures::StreamExt;
 use tokio::sync::mpsc;
 
 use super::AgentProvider;
 use crate::error::AgentError;
-use crate::process::ClaudeProcess;
+use crate::stream::QueryStream;
 use crate::types::{
-    AgentEvent, ContentBlock, Message, QueryOptions, ResultMessage, SystemPayload, ThinkingBlock,
-    ToolCall, ToolResultContent, ToolResultEvent, UserContentBlock,
+    AgentEvent, ContentBlock, ControlRequest, Message, QueryOptions, ResultMessage, SystemPayload,
+    ThinkingBlock, ToolCall, ToolResultContent, ToolResultEvent, UserContentBlock,
 };
 
 /// Maximum characters for tool result content in events.
@@ -26,43 +27,34 @@ impl AgentProvider for ClaudeProvider {
         tx: mpsc::Sender<Result<AgentEvent, AgentError>>,
     ) -> Pin<Box<dyn Future<Output = Result<(), AgentError>> + Send>> {
         Box::pin(async move {
-            let mut process = ClaudeProcess::spawn(&prompt, &opts).await?;
-
-            let mut got_result = false;
-            loop {
-                match process.next_message().await {
-                    Err(e) => {
-                        let _ = tx.send(Err(e)).await;
-                        break;
-                    }
-                    Ok(None) => break,
-                    Ok(Some(msg)) => {
-                        let is_terminal = matches!(msg, Message::Result(_));
-                        if is_terminal {
-                            got_result = true;
-                        }
-                        let event = claude_message_to_event(&msg);
-                        if tx.send(Ok(event)).await.is_err() {
-                            break;
-                        }
-                        if is_terminal {
-                            break;
-                        }
+            // Same pump as `query()`, so partial-message shaping, deny-list
+            // warnings, and exit-error reporting behave identically.
+            let mut stream = QueryStream::new(prompt, opts);
+            while let Some(item) = stream.next().await {
+                // Nobody can approve interactively behind this interface, so
+                // tools a policy leaves to the caller are refused.
+                if let Ok(Message::ControlRequest(request)) = &item {
+                    if let Err(e) = stream.answer_permission(request, false).await {
+                        tracing::warn!(error = %e, "could not refuse permission prompt");
                     }
                 }
-            }
-
-            if !got_result {
-                if let Some(exit_err) = process.wait_exit_error().await {
-                    let _ = tx.send(Err(exit_err)).await;
+                let event = item.map(|msg| claude_message_to_event(&msg));
+                if tx.send(event).await.is_err() {
+                    break;
                 }
             }
-
-            process.kill().await;
             Ok(())
         })
     }
 
+    fn accepts_user_input(&self) -> bool {
+        true
+    }
+
+    fn preflight(&self) -> Result<(), AgentError> {
+        crate::types::require_executable("claude")
+    }
+
     fn name(&self) -> &'static str {
         "claude"
     }
@@ -123,19 +115,7 @@ pub fn claude_message_to_event(msg: &Message) -> AgentEvent {
             _ => AgentEvent::System { timestamp: ts },
         },
         Message::Assistant(asst) => {
-            let text: String = asst
-                .message
-                .content
-                .iter()
-                .filter_map(|c| {
-                    if let ContentBlock::Text { text } = c {
-                        Some(text.as_str())
-                    } else {
-                        None
-                    }
-                })
-                .collect::<Vec<_>>()
-                .join("");
+            let text = asst.message.visible_text();
             let tools: Vec<ToolCall> = asst
                 .message
                 .content
@@ -153,17 +133,11 @@ pub fn claude_message_to_event(msg: &Message) -> AgentEvent {
                 .collect();
             let thinking: Vec<ThinkingBlock> = asst
                 .message
-                .content
-                .iter()
-                .filter_map(|c| {
-                    if let ContentBlock::Thinking { thinking } = c {
-                        Some(ThinkingBlock {
-                            block_type: "thinking".to_string(),
-                            thinking: thinking.clone(),
-                        })
-                    } else {
-                        None
-                    }
+                .thinking()
+                .into_iter()
+                .map(|thinking| ThinkingBlock {
+                    block_type: "thinking".to_string(),
+                    thinking: thinking.to_string(),
                 })
                 .collect();
             AgentEvent::Assistant {
@@ -187,17 +161,10 @@ pub fn claude_message_to_event(msg: &Message) -> AgentEvent {
                     {
                         let text = content
                             .as_ref()
-                            .and_then(|blocks| {
-                                blocks
-                                    .iter()
-                                    .map(|b| {
-                                        let ToolResultContent::Text { text } = b;
-                                        text.as_str()
-                                    })
-                                    .next()
-                            })
-                            .unwrap_or("");
-                        let truncated = truncate_chars(text, DISPLAY_TRUNCATE_CHARS);
+                            .and_then(|blocks| blocks.first())
+                            .map(ToolResultContent::text)
+                            .unwrap_or_default();
+                        let truncated = truncate_chars(&text, DISPLAY_TRUNCATE_CHARS);
                         Some(ToolResultEvent {
                             event_type: "tool_result".to_string(),
                             tool_use_id: tool_use_id.clone(),
@@ -222,6 +189,7 @@ pub fn claude_message_to_event(msg: &Message) -> AgentEvent {
             turns: r.num_turns(),
             session_id: Some(r.session_id().to_string()),
             stop_reason: r.stop_reason().map(|s| s.to_string()),
+            usage: Some(r.usage().into()),
             timestamp: ts,
         },
         Message::ToolProgress(tp) => AgentEvent::ToolProgress {
@@ -234,6 +202,18 @@ pub fn claude_message_to_event(msg: &Message) -> AgentEvent {
             timestamp: ts,
         },
         Message::StreamEvent(_) => AgentEvent::StreamEvent { timestamp: ts },
+        Message::AssistantDelta(delta) => AgentEvent::AssistantDelta {
+            text: delta.text.clone(),
+            timestamp: ts,
+        },
+        Message::ControlRequest(request) => match &request.request {
+            ControlRequest::CanUseTool { tool_name, .. } => AgentEvent::PermissionRequest {
+                request_id: request.request_id.clone(),
+                tool: tool_name.clone(),
+                timestamp: ts,
+            },
+            ControlRequest::Other => AgentEvent::System { timestamp: ts },
+        },
         Message::AuthStatus(auth) => AgentEvent::AuthStatus {
             is_authenticating: auth.is_authenticating,
             timestamp: ts,
@@ -299,7 +279,8 @@ mod tests {
                         input: serde_json::json!({"file": "foo.txt"}),
                     },
                     ContentBlock::Thinking {
-                        thinking: "hmm".into(),
+                        text: "hmm".into(),
+                        signature: None,
                     },
                 ],
                 model: "claude-sonnet-4-6".into(),
@@ -348,5 +329,7 @@ mod tests {
         let json = serde_json::to_value(&event).unwrap();
         assert_eq!(json["is_max_turns"], true);
         assert_eq!(json["is_error"], true);
+        assert_eq!(json["usage"]["input_tokens"], 100);
+        assert_eq!(json["usage"]["output_tokens"], 50);
     }
 }
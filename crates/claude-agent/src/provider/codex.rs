@@ -9,7 +9,7 @@ use tokio::sync::mpsc;
 use super::codex_types::CodexEvent;
 use super::AgentProvider;
 use crate::error::AgentError;
-use crate::types::{AgentEvent, PermissionMode, QueryOptions};
+use crate::types::{AgentEvent, PermissionMode, QueryOptions, TokenUsage};
 
 /// OpenAI Codex CLI provider.
 #[derive(Debug, Clone, Default)]
@@ -121,12 +121,23 @@ impl AgentProvider for CodexProvider {
             }
 
             // Synthesize a Result event from accumulated state
+            let usage = TokenUsage {
+                input_tokens: total_input_tokens,
+                output_tokens: total_output_tokens,
+                cache_creation_input_tokens: None,
+                cache_read_input_tokens: None,
+            };
             let ts = chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Millis, true);
             let result_event = AgentEvent::Result {
                 is_error: got_failure,
                 is_max_turns: false,
                 text: accumulated_text,
-                cost_usd: estimate_cost(total_input_tokens, total_output_tokens),
+                cost_usd: usage
+                    .billable_cost(
+                        opts.model.as_deref().unwrap_or(DEFAULT_PRICING_MODEL),
+                        &opts.pricing,
+                    )
+                    .unwrap_or(0.0),
                 turns: turn_count,
                 session_id: None,
                 stop_reason: if got_failure {
@@ -134,6 +145,7 @@ impl AgentProvider for CodexProvider {
                 } else {
                     Some("end_turn".to_string())
                 },
+                usage: Some(usage),
                 timestamp: ts,
             };
             let _ = tx.send(Ok(result_event)).await;
@@ -159,6 +171,10 @@ impl AgentProvider for CodexProvider {
         })
     }
 
+    fn preflight(&self) -> Result<(), AgentError> {
+        crate::types::require_executable("codex")
+    }
+
     fn name(&self) -> &'static str {
         "codex"
     }
@@ -217,32 +233,7 @@ fn build_codex_command(prompt: &str, opts: &QueryOptions) -> Command {
 fn build_mcp_config_json(servers: &[crate::types::McpServerConfig]) -> crate::Result<String> {
     let mut mcp_servers = serde_json::Map::new();
     for srv in servers {
-        let mut cfg = serde_json::Map::new();
-        cfg.insert("type".into(), serde_json::Value::String("stdio".into()));
-        cfg.insert(
-            "command".into(),
-            serde_json::Value::String(srv.command.clone()),
-        );
-        if !srv.args.is_empty() {
-            cfg.insert(
-                "args".into(),
-                serde_json::Value::Array(
-                    srv.args
-                        .iter()
-                        .map(|a| serde_json::Value::String(a.clone()))
-                        .collect(),
-                ),
-            );
-        }
-        if !srv.env.is_empty() {
-            let env: serde_json::Map<String, serde_json::Value> = srv
-                .env
-                .iter()
-                .map(|(k, v)| (k.clone(), serde_json::Value::String(v.clone())))
-                .collect();
-            cfg.insert("env".into(), serde_json::Value::Object(env));
-        }
-        mcp_servers.insert(srv.name.clone(), serde_json::Value::Object(cfg));
+        mcp_servers.insert(srv.name.clone(), srv.config_json());
     }
     Ok(serde_json::json!({ "mcpServers": mcp_servers }).to_string())
 }
@@ -333,6 +324,7 @@ fn codex_event_to_agent_event(
             turns: *turn_count,
             session_id: None,
             stop_reason: Some("turn_failed".to_string()),
+            usage: None,
             timestamp: ts,
         }),
         // Accumulated events — don't emit directly
@@ -345,11 +337,8 @@ fn codex_event_to_agent_event(
 
 use crate::types::ToolResultEvent;
 
-/// Rough cost estimate from token counts (GPT-4.1 pricing).
-fn estimate_cost(input_tokens: u64, output_tokens: u64) -> f64 {
-    // Approximate GPT-4.1 pricing: $2/M input, $8/M output
-    (input_tokens as f64 * 2.0 / 1_000_000.0) + (output_tokens as f64 * 8.0 / 1_000_000.0)
-}
+/// Model whose rates price a run when `opts.model` is unset.
+const DEFAULT_PRICING_MODEL: &str = "gpt-4.1";
 
 #[cfg(test)]
 mod tests {
@@ -28,6 +28,19 @@ pub trait AgentProvider: Send + Sync + 'static {
         tx: mpsc::Sender<Result<AgentEvent, AgentError>>,
     ) -> Pin<Box<dyn Future<Output = Result<(), AgentError>> + Send>>;
 
+    /// Whether [`QueryOptions::user_input`] reaches the agent mid-run.
+    /// Providers that cannot take input after the prompt ignore it.
+    fn accepts_user_input(&self) -> bool {
+        false
+    }
+
+    /// Cheap check that a run could start, without spawning anything.
+    /// CLI-backed providers verify their executable is on `PATH`; the
+    /// default passes.
+    fn preflight(&self) -> Result<(), AgentError> {
+        Ok(())
+    }
+
     /// Human-readable provider name (e.g. `"claude"`, `"codex"`).
     fn name(&self) -> &'static str;
 
@@ -231,6 +231,7 @@ impl AgentProvider for OpenCodeProvider {
                 } else {
                     Some("end_turn".to_string())
                 },
+                usage: None, // OpenCode doesn't expose token counts either
                 timestamp: ts(),
             };
             let _ = tx.send(Ok(result_event)).await;
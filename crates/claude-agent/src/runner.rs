@@ -1,7 +1,20 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
 use futures::StreamExt;
+use tracing::field::Empty;
+use tracing::Instrument;
 
-use crate::stream::QueryStream;
-use crate::{query, ClaudeAgentError, Message, QueryOptions, Result};
+use crate::pricing::PricingTable;
+use crate::session::{RecordedMessage, SessionStore, TranscriptWriter};
+use crate::stream::{spliced_channel, QueryStream};
+use crate::transport::{MockTransport, Transport};
+use crate::types::{
+    AssistantContent, AssistantMessage, ContentBlock, ControlRequest, ModelFallbackPayload,
+    SystemMessage, SystemPayload, UserContentBlock,
+};
+use crate::{query, ClaudeAgentError, Message, QueryOptions, Result, ResultMessage};
 
 // ─── RunConfig ────────────────────────────────────────────────────────────
 
@@ -16,12 +29,41 @@ pub struct RunConfig {
     pub prompt: String,
     /// Query options: model, MCP servers, permission mode, allowed tools, etc.
     pub opts: QueryOptions,
+    /// On a context-window overflow, compact the session and retry the
+    /// prompt on the compacted context, up to [`MAX_COMPACTIONS`] times.
+    pub auto_compact: bool,
+    /// Open a `claude.message` span for every message, not just the
+    /// `claude.run` span around the whole run. Verbose; meant for
+    /// performance debugging.
+    pub trace_messages: bool,
+    /// Abort the run once its estimated cost (priced with
+    /// `opts.pricing`) passes this many USD.
+    pub max_cost_usd: Option<f64>,
+    /// Abort the run once input, output and cache tokens together pass
+    /// this count.
+    pub max_total_tokens: Option<u64>,
+    /// Abort the run once the same tool call (name and input) has failed
+    /// this many times in a row, instead of letting the agent retry a
+    /// deterministic failure forever. `0` disables the guard; see
+    /// [`DEFAULT_MAX_CONSECUTIVE_FAILURES`].
+    pub max_consecutive_failures: u32,
 }
 
+/// The usual [`RunConfig::max_consecutive_failures`].
+pub const DEFAULT_MAX_CONSECUTIVE_FAILURES: u32 = 3;
+
+/// Upper bound on compact-and-retry cycles for one run with `auto_compact`.
+pub const MAX_COMPACTIONS: u32 = 2;
+
+/// Sent on `--resume` of the overflowing session. The CLI's `/compact`
+/// summarizes the conversation and replaces it with the summary.
+const COMPACT_PROMPT: &str = "/compact Summarize the task, the decisions made, the files \
+     changed, and what remains to be done, so the work can continue from the summary alone.";
+
 // ─── RunResult ────────────────────────────────────────────────────────────
 
 /// The terminal result of a completed agentic run.
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
 pub struct RunResult {
     pub session_id: String,
     /// The final text Claude produced (empty string for error subtypes).
@@ -30,15 +72,104 @@ pub struct RunResult {
     pub num_turns: u32,
     /// `true` if the run ended with any error subtype (max_turns, budget, etc.).
     pub is_error: bool,
+    /// Model fallbacks taken before the final attempt, oldest first.
+    pub fallbacks: Vec<ModelFallbackPayload>,
+    /// Error strings reported by error subtypes (empty on success).
+    pub errors: Vec<String>,
+    /// Compact-and-retry cycles performed (see `RunConfig::auto_compact`).
+    pub compactions: u32,
+    /// Every tool call the agent made, in the order it made them (across
+    /// compactions and retries).
+    pub tool_calls: Vec<ToolCallRecord>,
+    /// Wall-clock time of every tool call that got a result, in the order
+    /// the results arrived.
+    pub tool_timings: Vec<ToolTiming>,
+}
+
+/// Longest [`ToolCallRecord::input_summary`], in characters.
+pub const TOOL_INPUT_SUMMARY_CHARS: usize = 200;
+
+/// One `tool_use` block and the outcome of its matching `tool_result`.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct ToolCallRecord {
+    /// The `tool_use` id that pairs the call with its result.
+    pub id: String,
+    pub name: String,
+    /// The input as compact JSON, cut to [`TOOL_INPUT_SUMMARY_CHARS`].
+    pub input_summary: String,
+    /// `true` if the tool result was an error. A call whose result never
+    /// arrived (the run ended first) counts as an error.
+    pub is_error: bool,
+}
+
+impl ToolCallRecord {
+    fn new(id: &str, name: &str, input: &serde_json::Value) -> Self {
+        let input = input.to_string();
+        let input_summary = match input.char_indices().nth(TOOL_INPUT_SUMMARY_CHARS) {
+            Some((idx, _)) => format!("{}…", &input[..idx]),
+            None => input,
+        };
+        Self {
+            id: id.to_string(),
+            name: name.to_string(),
+            input_summary,
+            // Cleared when a successful result arrives.
+            is_error: true,
+        }
+    }
+}
+
+/// How long one tool call took: from the assistant message carrying its
+/// `tool_use` to the user message carrying the matching `tool_result`.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct ToolTiming {
+    /// The `tool_use` id, as in [`ToolCallRecord::id`].
+    pub id: String,
+    pub name: String,
+    pub duration_ms: u64,
+}
+
+impl RunResult {
+    /// Total tool time per tool name, slowest first — where the run spent
+    /// its time outside the model.
+    pub fn tool_time_by_name(&self) -> Vec<(String, u64)> {
+        let mut totals: Vec<(String, u64)> = Vec::new();
+        for timing in &self.tool_timings {
+            match totals.iter_mut().find(|(name, _)| *name == timing.name) {
+                Some((_, total)) => *total += timing.duration_ms,
+                None => totals.push((timing.name.clone(), timing.duration_ms)),
+            }
+        }
+        totals.sort_by(|a, b| b.1.cmp(&a.1));
+        totals
+    }
+
+    /// True if the run failed because the conversation outgrew the model's
+    /// context window.
+    pub fn is_context_overflow(&self) -> bool {
+        const MARKERS: &[&str] = &[
+            "prompt is too long",
+            "context window",
+            "context_length_exceeded",
+            "context limit",
+            "maximum context length",
+        ];
+        self.is_error
+            && std::iter::once(&self.result_text)
+                .chain(&self.errors)
+                .map(|text| text.to_lowercase())
+                .any(|text| MARKERS.iter().any(|m| text.contains(m)))
+    }
 }
 
 // ─── Public API ───────────────────────────────────────────────────────────
 
 /// Drive a single agentic Claude query to completion.
 ///
-/// Merges `config.system_prompt` into `config.opts`, starts a [`QueryStream`],
-/// consumes all messages, and returns the terminal result message as a
-/// [`RunResult`].
+/// Merges `config.system_prompt` into `config.opts`, starts a [`QueryStream`]
+/// via [`query_with_fallbacks`], consumes all messages, and returns the
+/// terminal result message as a [`RunResult`]. With `config.auto_compact`, a
+/// context-window overflow triggers a compact-and-retry cycle.
 ///
 /// Returns `Err` if the stream ends without a `Result` message (e.g., process
 /// crashed) or if any message fails to parse.
@@ -46,50 +177,1078 @@ pub struct RunResult {
 /// # Example
 ///
 /// ```rust,ignore
-/// use claude_agent::runner::{RunConfig, run};
+/// use claude_agent::runner::{RunConfig, run, DEFAULT_MAX_CONSECUTIVE_FAILURES};
 /// use claude_agent::QueryOptions;
 ///
 /// let result = run(RunConfig {
 ///     system_prompt: None,
 ///     prompt: "say hello".into(),
 ///     opts: QueryOptions::default(),
+///     auto_compact: false,
+///     trace_messages: false,
+///     max_cost_usd: Some(5.0),
+///     max_total_tokens: None,
+///     max_consecutive_failures: DEFAULT_MAX_CONSECUTIVE_FAILURES,
 /// }).await?;
 /// println!("{}", result.result_text);
 /// ```
 pub async fn run(config: RunConfig) -> Result<RunResult> {
-    let mut opts = config.opts;
-    if let Some(sp) = config.system_prompt {
-        opts.system_prompt = Some(sp);
+    RunnerBuilder::new(config).run().await
+}
+
+// ─── RunnerBuilder ────────────────────────────────────────────────────────
+
+type MessageHook = Box<dyn FnMut(&Message) + Send>;
+type ToolUseHook = Box<dyn FnMut(&str, &serde_json::Value) + Send>;
+type ResultHook = Box<dyn FnMut(&RunResult) + Send>;
+type ToolTimingHook = Box<dyn FnMut(&ToolTiming) + Send>;
+type BoundaryFn = Box<dyn Fn(&str) -> bool + Send>;
+
+/// [`run`] with hooks: callbacks invoked as the stream is driven, for
+/// logging, metrics, or transcript capture without a hand-written loop.
+///
+/// Hooks of the same kind fire in registration order. The returned
+/// [`RunResult`] is exactly what [`run`] would produce.
+///
+/// ```rust,ignore
+/// let result = RunnerBuilder::new(config)
+///     .on_tool_use(|name, _input| tracing::info!(tool = name, "tool call"))
+///     .with_store(SessionStore::new(&root), "my-feature")
+///     .run()
+///     .await?;
+/// ```
+pub struct RunnerBuilder {
+    config: RunConfig,
+    on_message: Vec<MessageHook>,
+    on_tool_use: Vec<ToolUseHook>,
+    on_result: Vec<ResultHook>,
+    on_tool_timing: Vec<ToolTimingHook>,
+    store: Option<(SessionStore, String)>,
+    checkpoints: Option<Checkpointer>,
+    recorder: Option<Recorder>,
+    replay_clock: Option<ReplayClock>,
+}
+
+impl RunnerBuilder {
+    pub fn new(config: RunConfig) -> Self {
+        Self {
+            config,
+            on_message: Vec::new(),
+            on_tool_use: Vec::new(),
+            on_result: Vec::new(),
+            on_tool_timing: Vec::new(),
+            store: None,
+            checkpoints: None,
+            recorder: None,
+            replay_clock: None,
+        }
+    }
+
+    /// Called for every message, including the terminal `Result`.
+    pub fn on_message(mut self, hook: impl FnMut(&Message) + Send + 'static) -> Self {
+        self.on_message.push(Box::new(hook));
+        self
+    }
+
+    /// Called with the tool name and input of every `tool_use` block in an
+    /// assistant message.
+    pub fn on_tool_use(
+        mut self,
+        hook: impl FnMut(&str, &serde_json::Value) + Send + 'static,
+    ) -> Self {
+        self.on_tool_use.push(Box::new(hook));
+        self
+    }
+
+    /// Called with the timing of each tool call as its result arrives.
+    pub fn on_tool_timing(mut self, hook: impl FnMut(&ToolTiming) + Send + 'static) -> Self {
+        self.on_tool_timing.push(Box::new(hook));
+        self
+    }
+
+    /// Called once with the final result, before [`RunnerBuilder::run`]
+    /// returns it.
+    pub fn on_result(mut self, hook: impl FnMut(&RunResult) + Send + 'static) -> Self {
+        self.on_result.push(Box::new(hook));
+        self
+    }
+
+    /// Save the run's session ID under `slug` once the result arrives, so
+    /// the next run can resume it via `store.load(slug)`.
+    pub fn with_store(mut self, store: SessionStore, slug: impl Into<String>) -> Self {
+        self.store = Some((store, slug.into()));
+        self
+    }
+
+    /// Checkpoint the session under `run_id` after every successful call of
+    /// a tool `is_boundary` accepts (e.g. an artifact approval). If `run_id`
+    /// already has checkpoints — a retried run — the run resumes the latest
+    /// one's session at the message that made the call, forked, instead of
+    /// starting a fresh session.
+    pub fn with_checkpoints(
+        mut self,
+        store: SessionStore,
+        run_id: impl Into<String>,
+        is_boundary: impl Fn(&str) -> bool + Send + 'static,
+    ) -> Self {
+        self.checkpoints = Some(Checkpointer {
+            store,
+            run_id: run_id.into(),
+            is_boundary: Box::new(is_boundary),
+            turn: 0,
+            last_message_id: None,
+            tool_use_messages: HashMap::new(),
+        });
+        self
+    }
+
+    /// Record every message the run sees under `run_id`, with when it
+    /// arrived, replacing any earlier recording, so
+    /// [`RunnerBuilder::replay`] can re-drive it. A recording that fails
+    /// mid-run is abandoned; the run carries on.
+    pub fn with_recording(mut self, store: &SessionStore, run_id: &str) -> Result<Self> {
+        self.recorder = Some(Recorder {
+            writer: store.recording_writer(run_id)?,
+            run_id: run_id.to_string(),
+            started: Instant::now(),
+        });
+        Ok(self)
+    }
+
+    /// Merge the system prompt into the options, start the query (with model
+    /// fallbacks), and drive it to completion.
+    pub async fn run(mut self) -> Result<RunResult> {
+        let (prompt, opts) = self.take_query();
+        self.run_with(prompt, opts, query_with_fallbacks).await
+    }
+
+    /// Drive a recorded run (see [`RunnerBuilder::with_recording`]) through
+    /// the same hooks, store and result assembly as [`RunnerBuilder::run`],
+    /// without spawning Claude.
+    ///
+    /// Each query the run starts plays the next recorded stream — everything
+    /// up to and including the next `Result` — so a run that compacted and
+    /// retried replays those queries too. Fallback notices were recorded as
+    /// messages and are replayed as such. Each message arrives at its
+    /// recorded offset, so tool calls are timed as they were in the run.
+    pub async fn replay(
+        mut self,
+        recording: impl IntoIterator<Item = impl Into<RecordedMessage>>,
+    ) -> Result<RunResult> {
+        let mut streams = VecDeque::new();
+        let mut current = Vec::new();
+        let mut offsets = VecDeque::new();
+        for recorded in recording {
+            let RecordedMessage { offset_ns, message } = recorded.into();
+            let terminal = matches!(message, Message::Result(_));
+            offsets.push_back(offset_ns);
+            current.push(message);
+            if terminal {
+                streams.push_back(std::mem::take(&mut current));
+            }
+        }
+        if !current.is_empty() {
+            streams.push_back(current);
+        }
+        let streams = Mutex::new(streams);
+        self.replay_clock = Some(ReplayClock {
+            base: Instant::now(),
+            offsets,
+        });
+
+        let (prompt, opts) = self.take_query();
+        self.run_with(prompt, opts, move |prompt, mut opts| {
+            let script = streams
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .pop_front()
+                .unwrap_or_default();
+            opts.transport = Transport::Mock(MockTransport::new().on("", script));
+            query(prompt, opts)
+        })
+        .await
+    }
+
+    /// The prompt and options to start the run with, the system prompt
+    /// merged into the options.
+    fn take_query(&mut self) -> (String, QueryOptions) {
+        let mut opts = std::mem::take(&mut self.config.opts);
+        if let Some(sp) = self.config.system_prompt.take() {
+            opts.system_prompt = Some(sp);
+        }
+        (std::mem::take(&mut self.config.prompt), opts)
+    }
+
+    /// When the message being driven arrived: now, or its recorded time
+    /// when replaying.
+    fn arrival(&mut self) -> Instant {
+        match &mut self.replay_clock {
+            Some(clock) => {
+                let offset = clock.offsets.pop_front().flatten().unwrap_or(0);
+                clock.base + Duration::from_nanos(offset)
+            }
+            None => Instant::now(),
+        }
+    }
+
+    /// The body of [`RunnerBuilder::run`]. `start` launches each query so
+    /// tests can replay fixtures instead of spawning Claude.
+    ///
+    /// Runs inside a `claude.run` span; the counters are recorded on it once
+    /// the run finishes.
+    pub(crate) async fn run_with<F>(
+        self,
+        prompt: String,
+        opts: QueryOptions,
+        start: F,
+    ) -> Result<RunResult>
+    where
+        F: Fn(String, QueryOptions) -> QueryStream,
+    {
+        let span = tracing::info_span!(
+            "claude.run",
+            model = opts.model.as_deref().unwrap_or("default"),
+            turns = Empty,
+            messages = Empty,
+            bytes = Empty,
+            first_byte_ms = Empty,
+            duration_ms = Empty,
+            cost_usd = Empty,
+            compactions = Empty,
+            is_error = Empty,
+        );
+        self.run_traced(prompt, opts, start).instrument(span).await
+    }
+
+    async fn run_traced<F>(
+        mut self,
+        prompt: String,
+        mut opts: QueryOptions,
+        start: F,
+    ) -> Result<RunResult>
+    where
+        F: Fn(String, QueryOptions) -> QueryStream,
+    {
+        // A bad server would otherwise surface only as missing tools, mid-run.
+        for server in &opts.mcp_servers {
+            server.validate()?;
+        }
+        if let Some(cp) = &mut self.checkpoints {
+            cp.resume_latest(&mut opts)?;
+        }
+        let mut trace = RunTrace::new();
+        let mut budget = RunBudget::new(&self.config, opts.pricing.clone());
+        let mut failures = FailureGuard::new(self.config.max_consecutive_failures);
+        let result = self
+            .drive(
+                start(prompt.clone(), opts.clone()),
+                &mut trace,
+                &mut budget,
+                &mut failures,
+            )
+            .await;
+        let mut result = trace.finish(result)?;
+        let mut tool_calls = std::mem::take(&mut result.tool_calls);
+        let mut tool_timings = std::mem::take(&mut result.tool_timings);
+        let mut compactions = 0;
+        let (mut cost, mut turns) = (result.total_cost_usd, result.num_turns);
+
+        while self.config.auto_compact
+            && compactions < MAX_COMPACTIONS
+            && result.is_context_overflow()
+        {
+            compactions += 1;
+            tracing::info!(
+                session_id = %result.session_id,
+                compactions,
+                "context window exceeded; compacting and retrying"
+            );
+            opts.resume = Some(result.session_id.clone());
+            opts.resume_session_at = None;
+            opts.fork_session = false;
+            opts.session_id = None;
+            opts.continue_conversation = false;
+            let compacted = self
+                .drive(
+                    start(COMPACT_PROMPT.to_string(), opts.clone()),
+                    &mut trace,
+                    &mut budget,
+                    &mut failures,
+                )
+                .await;
+            let mut compacted = trace.finish(compacted).map_err(|e| {
+                e.with_earlier_attempts(&tool_calls, &tool_timings, cost, turns, compactions)
+            })?;
+            tool_calls.append(&mut compacted.tool_calls);
+            tool_timings.append(&mut compacted.tool_timings);
+            cost += compacted.total_cost_usd;
+            if compacted.is_error {
+                // Report the original overflow, not the failed compaction.
+                break;
+            }
+
+            opts.resume = Some(compacted.session_id.clone());
+            let retried = self
+                .drive(
+                    start(prompt.clone(), opts.clone()),
+                    &mut trace,
+                    &mut budget,
+                    &mut failures,
+                )
+                .await;
+            result = trace.finish(retried).map_err(|e| {
+                e.with_earlier_attempts(&tool_calls, &tool_timings, cost, turns, compactions)
+            })?;
+            tool_calls.append(&mut result.tool_calls);
+            tool_timings.append(&mut result.tool_timings);
+            cost += result.total_cost_usd;
+            turns += result.num_turns;
+        }
+
+        result.total_cost_usd = cost;
+        result.num_turns = turns;
+        result.compactions = compactions;
+        result.tool_calls = tool_calls;
+        result.tool_timings = tool_timings;
+        trace.record(&result);
+        if let Some((store, slug)) = &self.store {
+            store.save(slug, &result.session_id)?;
+        }
+        for hook in &mut self.on_result {
+            hook(&result);
+        }
+        Ok(result)
+    }
+
+    /// Drive one started stream through the message and tool-use hooks.
+    ///
+    /// Returning early drops `stream`, which terminates the agent process, so
+    /// a budget overrun stops the run right after the message that crossed
+    /// the ceiling. The hooks have seen every message up to and including
+    /// that one. A stalled tool call stops it the same way.
+    async fn drive(
+        &mut self,
+        mut stream: QueryStream,
+        trace: &mut RunTrace,
+        budget: &mut RunBudget,
+        failures: &mut FailureGuard,
+    ) -> Result<RunResult> {
+        let mut run_result: Option<RunResult> = None;
+        let mut transcript = Transcript::new(budget.cost_usd);
+        let mut timer = ToolTimer::default();
+
+        while let Some(msg) = stream.next().await {
+            let msg = msg?;
+            let span = trace.message(&msg, self.config.trace_messages);
+            let entered = span.enter();
+            let mut refuse = None;
+            let at = self.arrival();
+            if let Some(recorder) = &mut self.recorder {
+                if let Err(e) = recorder.record(&msg, at) {
+                    tracing::warn!(run_id = %recorder.run_id, error = %e, "abandoning run recording");
+                    self.recorder = None;
+                }
+            }
+            for hook in &mut self.on_message {
+                hook(&msg);
+            }
+            for timing in timer.observe(&msg, at) {
+                for hook in &mut self.on_tool_timing {
+                    hook(&timing);
+                }
+                transcript.tool_timings.push(timing);
+            }
+            if let Message::Assistant(asst) = &msg {
+                transcript.observe(&asst.message.id, &asst.session_id);
+                if let Err(overrun) = budget.charge(&asst.message) {
+                    return Err(overrun.into_error(transcript.into_partial(budget)));
+                }
+                if let Some(cp) = &mut self.checkpoints {
+                    cp.observe(asst);
+                }
+            }
+            match msg {
+                Message::System(SystemMessage {
+                    payload: SystemPayload::ModelFallback(fallback),
+                    ..
+                }) => transcript.fallbacks.push(fallback),
+                Message::Assistant(asst) => {
+                    for block in &asst.message.content {
+                        if let ContentBlock::ToolUse { id, name, input } = block {
+                            transcript
+                                .tool_calls
+                                .push(ToolCallRecord::new(id, name, input));
+                            failures.track(id, name, input);
+                            for hook in &mut self.on_tool_use {
+                                hook(name, input);
+                            }
+                        }
+                    }
+                }
+                Message::User(user) => {
+                    for block in &user.message.content {
+                        if let UserContentBlock::ToolResult {
+                            tool_use_id,
+                            is_error,
+                            ..
+                        } = block
+                        {
+                            if let Some(call) = transcript
+                                .tool_calls
+                                .iter_mut()
+                                .rev()
+                                .find(|c| &c.id == tool_use_id)
+                            {
+                                call.is_error = is_error.unwrap_or(false);
+                                if let Some(cp) = &mut self.checkpoints {
+                                    cp.after_tool(call, &user.session_id);
+                                }
+                            }
+                            if let Err(stall) =
+                                failures.record(tool_use_id, is_error.unwrap_or(false))
+                            {
+                                return Err(stall.into_error(transcript.into_partial(budget)));
+                            }
+                        }
+                    }
+                }
+                Message::ControlRequest(request)
+                    if matches!(request.request, ControlRequest::CanUseTool { .. }) =>
+                {
+                    // Nobody can approve interactively behind the runner, so
+                    // tools a policy leaves to the caller are refused —
+                    // otherwise the CLI would wait on stdin forever.
+                    refuse = Some(request);
+                }
+                Message::Result(r) => {
+                    run_result = Some(RunResult {
+                        session_id: r.session_id().to_string(),
+                        result_text: r.result_text().unwrap_or("").to_string(),
+                        total_cost_usd: r.total_cost_usd(),
+                        num_turns: r.num_turns(),
+                        is_error: r.is_error(),
+                        fallbacks: transcript.fallbacks,
+                        errors: match r {
+                            ResultMessage::Success(_) => Vec::new(),
+                            ResultMessage::ErrorDuringExecution(e)
+                            | ResultMessage::ErrorMaxTurns(e)
+                            | ResultMessage::ErrorMaxBudgetUsd(e)
+                            | ResultMessage::ErrorMaxStructuredOutputRetries(e) => e.errors,
+                        },
+                        compactions: 0,
+                        tool_calls: transcript.tool_calls,
+                        tool_timings: transcript.tool_timings,
+                    });
+                    // Result is the terminal message — no need to consume further.
+                    break;
+                }
+                _ => {}
+            }
+            // A span guard must not be held across an await.
+            drop(entered);
+            if let Some(request) = refuse {
+                let answer = stream.answer_permission(&request, false).instrument(span);
+                if let Err(e) = answer.await {
+                    tracing::warn!(error = %e, "could not refuse permission prompt");
+                }
+            }
+        }
+
+        run_result.ok_or_else(|| {
+            ClaudeAgentError::Process("stream ended without a result message".into())
+        })
     }
-    collect(query(config.prompt, opts)).await
 }
 
-// ─── Internal ─────────────────────────────────────────────────────────────
+/// What one [`RunnerBuilder::drive`] has seen so far, so a run stopped
+/// before its result message can still be reported.
+struct Transcript {
+    session_id: String,
+    /// Assistant turns, counted once per API message id.
+    turns: u32,
+    last_message_id: Option<String>,
+    /// [`RunBudget::cost_usd`] when the drive started.
+    cost_at_start: f64,
+    fallbacks: Vec<ModelFallbackPayload>,
+    tool_calls: Vec<ToolCallRecord>,
+    tool_timings: Vec<ToolTiming>,
+}
+
+impl Transcript {
+    fn new(cost_at_start: f64) -> Self {
+        Self {
+            session_id: String::new(),
+            turns: 0,
+            last_message_id: None,
+            cost_at_start,
+            fallbacks: Vec::new(),
+            tool_calls: Vec::new(),
+            tool_timings: Vec::new(),
+        }
+    }
+
+    fn observe(&mut self, message_id: &str, session_id: &str) {
+        if self.last_message_id.as_deref() != Some(message_id) {
+            self.last_message_id = Some(message_id.to_owned());
+            self.turns += 1;
+        }
+        if self.session_id != session_id {
+            self.session_id = session_id.to_owned();
+        }
+    }
+
+    /// The run so far as an errored [`RunResult`]. Its cost is estimated
+    /// from usage, since no result message reported one.
+    fn into_partial(self, budget: &RunBudget) -> Box<RunResult> {
+        Box::new(RunResult {
+            session_id: self.session_id,
+            result_text: String::new(),
+            total_cost_usd: budget.cost_usd - self.cost_at_start,
+            num_turns: self.turns,
+            is_error: true,
+            fallbacks: self.fallbacks,
+            errors: Vec::new(),
+            compactions: 0,
+            tool_calls: self.tool_calls,
+            tool_timings: self.tool_timings,
+        })
+    }
+}
+
+/// Writes the run's messages for [`RunnerBuilder::with_recording`].
+struct Recorder {
+    writer: TranscriptWriter,
+    run_id: String,
+    started: Instant,
+}
+
+impl Recorder {
+    /// Append `msg`, which arrived at `at`.
+    fn record(&mut self, msg: &Message, at: Instant) -> Result<()> {
+        let offset = at.saturating_duration_since(self.started).as_nanos();
+        let recorded = RecordedMessage {
+            offset_ns: Some(u64::try_from(offset).unwrap_or(u64::MAX)),
+            message: msg.clone(),
+        };
+        let event = serde_json::to_value(&recorded)
+            .map_err(|e| ClaudeAgentError::Process(e.to_string()))?;
+        self.writer.append(&event)
+    }
+}
+
+/// Arrival times of the messages [`RunnerBuilder::replay`] plays, in order.
+struct ReplayClock {
+    base: Instant,
+    /// Recorded offsets from `base`; `None` where the recording has none.
+    offsets: VecDeque<Option<u64>>,
+}
+
+/// Pairs `tool_use` blocks with their `tool_result`s to time each call.
+#[derive(Default)]
+struct ToolTimer {
+    /// `tool_use` id → (tool name, when the call was seen).
+    started: HashMap<String, (String, Instant)>,
+}
+
+impl ToolTimer {
+    /// Note the calls `msg` starts and return the timings of those it
+    /// finishes, `at` being when `msg` arrived.
+    fn observe(&mut self, msg: &Message, at: Instant) -> Vec<ToolTiming> {
+        match msg {
+            Message::Assistant(asst) => {
+                for block in &asst.message.content {
+                    if let ContentBlock::ToolUse { id, name, .. } = block {
+                        self.started.insert(id.clone(), (name.clone(), at));
+                    }
+                }
+                Vec::new()
+            }
+            Message::User(user) => user
+                .message
+                .content
+                .iter()
+                .filter_map(|block| match block {
+                    UserContentBlock::ToolResult { tool_use_id, .. } => {
+                        let (name, started) = self.started.remove(tool_use_id)?;
+                        Some(ToolTiming {
+                            id: tool_use_id.clone(),
+                            name,
+                            duration_ms: millis(at.saturating_duration_since(started)),
+                        })
+                    }
+                    _ => None,
+                })
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+}
+
+/// Records checkpoints for [`RunnerBuilder::with_checkpoints`].
+struct Checkpointer {
+    store: SessionStore,
+    run_id: String,
+    is_boundary: BoundaryFn,
+    /// Assistant turns so far, counted once per API message id and carried
+    /// over from the checkpoint a retried run resumes.
+    turn: u32,
+    last_message_id: Option<String>,
+    /// `tool_use` id → `uuid` of the assistant message that made the call.
+    tool_use_messages: HashMap<String, String>,
+}
+
+impl Checkpointer {
+    /// Point `opts` at the latest checkpoint, unless the caller already
+    /// chose a session to resume.
+    fn resume_latest(&mut self, opts: &mut QueryOptions) -> Result<()> {
+        if opts.resume.is_some() {
+            return Ok(());
+        }
+        if let Some(latest) = self.store.latest_checkpoint(&self.run_id)? {
+            tracing::info!(
+                run_id = %self.run_id,
+                session_id = %latest.session_id,
+                turn = latest.turn,
+                "resuming from checkpoint"
+            );
+            self.turn = latest.turn;
+            // Forked, so the failed session stays intact for inspection.
+            opts.fork_session = latest.message_uuid.is_some();
+            opts.resume_session_at = latest.message_uuid;
+            opts.resume = Some(latest.session_id);
+            opts.session_id = None;
+            opts.continue_conversation = false;
+        }
+        Ok(())
+    }
+
+    /// Count the turn `asst` belongs to and note which message made each of
+    /// its tool calls.
+    fn observe(&mut self, asst: &AssistantMessage) {
+        if self.last_message_id.as_deref() != Some(asst.message.id.as_str()) {
+            self.last_message_id = Some(asst.message.id.clone());
+            self.turn += 1;
+        }
+        let Some(uuid) = &asst.uuid else { return };
+        for block in &asst.message.content {
+            if let ContentBlock::ToolUse { id, .. } = block {
+                self.tool_use_messages.insert(id.clone(), uuid.clone());
+            }
+        }
+    }
+
+    /// A failed checkpoint write only costs resumability, so it is logged
+    /// rather than failing the run.
+    fn after_tool(&mut self, call: &ToolCallRecord, session_id: &str) {
+        let message_uuid = self.tool_use_messages.remove(&call.id);
+        if call.is_error || !(self.is_boundary)(&call.name) {
+            return;
+        }
+        if let Err(e) =
+            self.store
+                .checkpoint(&self.run_id, session_id, message_uuid.as_deref(), self.turn)
+        {
+            tracing::warn!(run_id = %self.run_id, error = %e, "failed to record checkpoint");
+        }
+    }
+}
+
+/// Cumulative usage of one run, checked against the [`RunConfig`] ceilings
+/// after every assistant message.
+struct RunBudget {
+    max_cost_usd: Option<f64>,
+    max_total_tokens: Option<u64>,
+    pricing: PricingTable,
+    cost_usd: f64,
+    total_tokens: u64,
+    /// The CLI repeats an API message on each of its content-block messages,
+    /// with usage that grows as output streams. Only the latest usage of a
+    /// message id counts, so its earlier charge is replaced.
+    last_message_id: Option<String>,
+    last_charge: (u64, f64),
+}
+
+/// A ceiling [`RunBudget::charge`] found crossed.
+struct BudgetOverrun {
+    spent: f64,
+    limit: f64,
+    unit: &'static str,
+}
+
+impl BudgetOverrun {
+    fn into_error(self, partial: Box<RunResult>) -> ClaudeAgentError {
+        tracing::warn!(
+            spent = self.spent,
+            limit = self.limit,
+            unit = self.unit,
+            "run budget exceeded; aborting"
+        );
+        ClaudeAgentError::BudgetExceeded {
+            spent: self.spent,
+            limit: self.limit,
+            unit: self.unit,
+            partial,
+        }
+    }
+}
+
+impl RunBudget {
+    fn new(config: &RunConfig, pricing: PricingTable) -> Self {
+        Self {
+            max_cost_usd: config.max_cost_usd,
+            max_total_tokens: config.max_total_tokens,
+            pricing,
+            cost_usd: 0.0,
+            total_tokens: 0,
+            last_message_id: None,
+            last_charge: (0, 0.0),
+        }
+    }
+
+    /// Add `message`'s usage and report the ceiling it passes, if any.
+    fn charge(&mut self, message: &AssistantContent) -> std::result::Result<(), BudgetOverrun> {
+        if self.last_message_id.as_deref() == Some(message.id.as_str()) {
+            let (tokens, cost) = self.last_charge;
+            self.total_tokens -= tokens;
+            self.cost_usd -= cost;
+        } else {
+            self.last_message_id = Some(message.id.clone());
+        }
+
+        let usage = &message.usage;
+        let tokens = usage.input_tokens
+            + usage.output_tokens
+            + usage.cache_creation_input_tokens.unwrap_or(0)
+            + usage.cache_read_input_tokens.unwrap_or(0);
+        // An unpriced model cannot count against the cost ceiling.
+        let cost = usage
+            .billable_cost(&message.model, &self.pricing)
+            .unwrap_or(0.0);
+        self.last_charge = (tokens, cost);
+        self.total_tokens += tokens;
+        self.cost_usd += cost;
 
-/// Consume a [`QueryStream`] and extract the terminal [`RunResult`].
+        if let Some(limit) = self.max_total_tokens {
+            if self.total_tokens > limit {
+                return Err(BudgetOverrun {
+                    spent: self.total_tokens as f64,
+                    limit: limit as f64,
+                    unit: "tokens",
+                });
+            }
+        }
+        if let Some(limit) = self.max_cost_usd {
+            if self.cost_usd > limit {
+                return Err(BudgetOverrun {
+                    spent: self.cost_usd,
+                    limit,
+                    unit: "USD",
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Consecutive failures per tool call, checked against
+/// [`RunConfig::max_consecutive_failures`] after every tool result.
 ///
-/// Exposed as `pub(crate)` so tests can inject mock streams directly without
-/// spawning a real Claude subprocess.
-pub(crate) async fn collect(stream: QueryStream) -> Result<RunResult> {
-    let mut stream = stream;
-    let mut run_result: Option<RunResult> = None;
-
-    while let Some(msg) = stream.next().await {
-        if let Message::Result(r) = msg? {
-            run_result = Some(RunResult {
-                session_id: r.session_id().to_string(),
-                result_text: r.result_text().unwrap_or("").to_string(),
-                total_cost_usd: r.total_cost_usd(),
-                num_turns: r.num_turns(),
-                is_error: r.is_error(),
+/// A call is identified by its tool name and input, so retrying the same
+/// command counts against the same action while a different command does
+/// not. A success of that action resets its count.
+struct FailureGuard {
+    max: u32,
+    /// `tool_use` id → (tool name, action key) for calls awaiting a result.
+    pending: HashMap<String, (String, String)>,
+    /// Consecutive failures per action key.
+    counts: HashMap<String, u32>,
+}
+
+impl FailureGuard {
+    fn new(max: u32) -> Self {
+        Self {
+            max,
+            pending: HashMap::new(),
+            counts: HashMap::new(),
+        }
+    }
+
+    fn track(&mut self, id: &str, name: &str, input: &serde_json::Value) {
+        if self.max == 0 {
+            return;
+        }
+        self.pending.insert(
+            id.to_string(),
+            (name.to_string(), format!("{name} {input}")),
+        );
+    }
+
+    /// Count the result of call `id` and report the stall once its action
+    /// has failed `max` times in a row.
+    fn record(&mut self, id: &str, is_error: bool) -> std::result::Result<(), Stall> {
+        let Some((name, key)) = self.pending.remove(id) else {
+            return Ok(());
+        };
+        if !is_error {
+            self.counts.remove(&key);
+            return Ok(());
+        }
+        let attempts = self.counts.entry(key).or_insert(0);
+        *attempts += 1;
+        if *attempts >= self.max {
+            return Err(Stall {
+                action: name,
+                attempts: *attempts,
             });
-            // Result is the terminal message — no need to consume further.
-            break;
+        }
+        Ok(())
+    }
+}
+
+/// A tool call [`FailureGuard::record`] found failing over and over.
+struct Stall {
+    action: String,
+    attempts: u32,
+}
+
+impl Stall {
+    fn into_error(self, partial: Box<RunResult>) -> ClaudeAgentError {
+        tracing::warn!(action = %self.action, attempts = self.attempts, "tool call keeps failing; aborting run");
+        ClaudeAgentError::RunStalled {
+            action: self.action,
+            attempts: self.attempts,
+            partial,
         }
     }
+}
 
-    run_result
-        .ok_or_else(|| ClaudeAgentError::Process("stream ended without a result message".into()))
+/// Timing and volume counters for one `claude.run` span.
+struct RunTrace {
+    started: Instant,
+    last: Instant,
+    messages: u64,
+    bytes: u64,
+    first_byte_ms: Option<u64>,
+}
+
+impl RunTrace {
+    fn new() -> Self {
+        let now = Instant::now();
+        Self {
+            started: now,
+            last: now,
+            messages: 0,
+            bytes: 0,
+            first_byte_ms: None,
+        }
+    }
+
+    /// Count `msg` and, with `detailed`, return a `claude.message` span for
+    /// it (a disabled span otherwise).
+    fn message(&mut self, msg: &Message, detailed: bool) -> tracing::Span {
+        let now = Instant::now();
+        let wait_ms = millis(now - self.last);
+        self.last = now;
+        self.messages += 1;
+        self.first_byte_ms
+            .get_or_insert_with(|| millis(now - self.started));
+        if !detailed {
+            return tracing::Span::none();
+        }
+        let bytes = serde_json::to_vec(msg).map_or(0, |v| v.len() as u64);
+        self.bytes += bytes;
+        tracing::info_span!(
+            "claude.message",
+            kind = message_kind(msg),
+            seq = self.messages,
+            bytes,
+            wait_ms,
+        )
+    }
+
+    /// Record the counters on the current span when a drive fails, so an
+    /// aborted run still reports how far it got.
+    fn finish(&self, result: Result<RunResult>) -> Result<RunResult> {
+        if result.is_err() {
+            self.record_counters();
+            tracing::Span::current().record("is_error", true);
+        }
+        result
+    }
+
+    fn record(&self, result: &RunResult) {
+        self.record_counters();
+        let span = tracing::Span::current();
+        span.record("turns", result.num_turns);
+        span.record("cost_usd", result.total_cost_usd);
+        span.record("compactions", result.compactions);
+        span.record("is_error", result.is_error);
+    }
+
+    fn record_counters(&self) {
+        let span = tracing::Span::current();
+        span.record("messages", self.messages);
+        if self.bytes > 0 {
+            span.record("bytes", self.bytes);
+        }
+        if let Some(ms) = self.first_byte_ms {
+            span.record("first_byte_ms", ms);
+        }
+        span.record("duration_ms", millis(self.started.elapsed()));
+    }
+}
+
+fn millis(d: std::time::Duration) -> u64 {
+    u64::try_from(d.as_millis()).unwrap_or(u64::MAX)
+}
+
+/// The stream-json `type` of a message.
+fn message_kind(msg: &Message) -> &'static str {
+    match msg {
+        Message::System(_) => "system",
+        Message::Assistant(_) => "assistant",
+        Message::User(_) => "user",
+        Message::Result(_) => "result",
+        Message::StreamEvent(_) => "stream_event",
+        Message::AssistantDelta(_) => "assistant_delta",
+        Message::ToolProgress(_) => "tool_progress",
+        Message::ToolUseSummary(_) => "tool_use_summary",
+        Message::AuthStatus(_) => "auth_status",
+        Message::ControlRequest(_) => "control_request",
+    }
+}
+
+/// Like [`query`], but retries the whole query on the next entry in
+/// `opts.model_fallbacks` when the current model is rate-limited or
+/// unavailable.
+///
+/// Each retry reuses the original prompt, session and resume options. Before
+/// a retry the stream yields a synthetic [`SystemPayload::ModelFallback`]
+/// message so consumers can see which model took over and why. The failing
+/// attempt's terminal error is swallowed; anything it emitted before failing
+/// is passed through unchanged.
+pub fn query_with_fallbacks(prompt: impl Into<String>, opts: QueryOptions) -> QueryStream {
+    fallback_stream(prompt.into(), opts, query)
+}
+
+// ─── Internal ─────────────────────────────────────────────────────────────
+
+/// Error fragments (lowercased) that mean "try another model" rather than
+/// "the task failed".
+const FALLBACK_MARKERS: &[&str] = &[
+    "rate_limit",
+    "rate limit",
+    "rate-limited",
+    "overloaded",
+    "model_not_found",
+    "model not found",
+    "not_found_error",
+    "model unavailable",
+    "model is not available",
+];
+
+fn is_fallback_error(text: &str) -> bool {
+    let text = text.to_lowercase();
+    FALLBACK_MARKERS.iter().any(|m| text.contains(m))
+}
+
+/// If `item` ends the attempt with a failure that a different model could
+/// avoid, return the error text.
+fn fallback_reason(item: &Result<Message>) -> Option<String> {
+    let text = match item {
+        Err(ClaudeAgentError::Process(msg)) => msg.clone(),
+        Ok(Message::Result(ResultMessage::Success(r))) if r.is_error => r.result.clone(),
+        Ok(Message::Result(
+            ResultMessage::ErrorDuringExecution(r)
+            | ResultMessage::ErrorMaxTurns(r)
+            | ResultMessage::ErrorMaxBudgetUsd(r)
+            | ResultMessage::ErrorMaxStructuredOutputRetries(r),
+        )) => r.errors.join("; "),
+        _ => return None,
+    };
+    is_fallback_error(&text).then_some(text)
+}
+
+/// Drive attempts started by `start` through the primary model and then each
+/// fallback, forwarding messages into a single [`QueryStream`].
+///
+/// `start` is injected so tests can substitute mock streams for the Claude
+/// subprocess.
+pub(crate) fn fallback_stream<F>(prompt: String, opts: QueryOptions, start: F) -> QueryStream
+where
+    F: Fn(String, QueryOptions) -> QueryStream + Send + 'static,
+{
+    let (mut tx, mut answers, spliced) = spliced_channel(32);
+    // The attempts it starts inherit the caller's span (e.g. `claude.run`).
+    let task = async move {
+        let models: Vec<Option<String>> = std::iter::once(opts.model.clone())
+            .chain(opts.model_fallbacks.iter().cloned().map(Some))
+            .collect();
+        let mut session_id = opts
+            .session_id
+            .clone()
+            .or_else(|| opts.resume.clone())
+            .unwrap_or_default();
+
+        for (i, model) in models.iter().enumerate() {
+            let mut attempt = opts.clone();
+            attempt.model = model.clone();
+            let mut stream = start(prompt.clone(), attempt);
+            let next = models.get(i + 1).cloned().flatten();
+
+            let mut reason = None;
+            loop {
+                // Permission answers given on the spliced stream go to the
+                // attempt that is running now.
+                let item = tokio::select! {
+                    item = stream.next() => item,
+                    Some(answer) = answers.recv() => {
+                        if let Err(e) = stream.send_answer(answer).await {
+                            tracing::warn!(error = %e, "could not forward permission answer");
+                        }
+                        continue;
+                    }
+                };
+                let Some(item) = item else {
+                    break;
+                };
+                if next.is_some() {
+                    reason = fallback_reason(&item);
+                    if reason.is_some() {
+                        break;
+                    }
+                }
+                if let Ok(Message::System(sys)) = &item {
+                    session_id = sys.session_id.clone();
+                }
+                if tx.send(item).await.is_err() {
+                    return; // Receiver dropped
+                }
+            }
+            if reason.is_none() {
+                if let Some(end) = stream.end_reason() {
+                    tx.set_end(end);
+                }
+            }
+
+            let (Some(reason), Some(to_model)) = (reason, next) else {
+                return;
+            };
+            let notice = Message::System(SystemMessage {
+                session_id: session_id.clone(),
+                payload: SystemPayload::ModelFallback(ModelFallbackPayload {
+                    from_model: model.clone(),
+                    to_model,
+                    reason,
+                }),
+            });
+            if tx.send(Ok(notice)).await.is_err() {
+                return;
+            }
+        }
+    };
+    tokio::spawn(task.in_current_span());
+    spliced
 }
 
 // ─── Tests ────────────────────────────────────────────────────────────────
@@ -97,12 +1256,10 @@ pub(crate) async fn collect(stream: QueryStream) -> Result<RunResult> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use tokio::sync::mpsc;
+    use crate::stream::stream_channel;
+    use std::sync::{Arc, Mutex};
 
-    use crate::types::{
-        ResultError, ResultMessage, ResultSuccess, ResultUsage, SystemInit, SystemMessage,
-        SystemPayload,
-    };
+    use crate::types::{ResultError, ResultSuccess, ResultUsage, SystemInit};
 
     fn success_msg(text: &str) -> Message {
         Message::Result(ResultMessage::Success(ResultSuccess {
@@ -166,8 +1323,31 @@ mod tests {
         })
     }
 
+    fn config(auto_compact: bool) -> RunConfig {
+        RunConfig {
+            system_prompt: None,
+            prompt: String::new(),
+            opts: QueryOptions::default(),
+            auto_compact,
+            trace_messages: false,
+            max_cost_usd: None,
+            max_total_tokens: None,
+            max_consecutive_failures: 0,
+        }
+    }
+
+    /// Drive a mock stream with no hooks — what [`run`] does after spawning.
+    async fn collect(stream: QueryStream) -> Result<RunResult> {
+        let stream = Mutex::new(Some(stream));
+        RunnerBuilder::new(config(false))
+            .run_with(String::new(), QueryOptions::default(), move |_, _| {
+                stream.lock().unwrap().take().expect("started once")
+            })
+            .await
+    }
+
     fn mock_stream(messages: Vec<Result<Message>>) -> QueryStream {
-        let (tx, rx) = mpsc::channel(32);
+        let (mut tx, stream) = stream_channel(32);
         tokio::spawn(async move {
             for msg in messages {
                 if tx.send(msg).await.is_err() {
@@ -175,7 +1355,7 @@ mod tests {
                 }
             }
         });
-        QueryStream::from_channel(rx)
+        stream
     }
 
     #[tokio::test]
@@ -201,9 +1381,8 @@ mod tests {
 
     #[tokio::test]
     async fn collect_no_result_message_returns_err() {
-        let (tx, rx) = mpsc::channel::<Result<Message>>(1);
+        let (tx, stream) = stream_channel(1);
         drop(tx); // drop sender immediately so the stream closes with no messages
-        let stream = QueryStream::from_channel(rx);
         let err = collect(stream).await;
         assert!(err.is_err());
         let msg = err.unwrap_err().to_string();
@@ -225,4 +1404,822 @@ mod tests {
         let err = collect(stream).await;
         assert!(err.is_err());
     }
+
+    fn rate_limited_msg() -> Message {
+        Message::Result(ResultMessage::ErrorDuringExecution(ResultError {
+            session_id: "s1".into(),
+            duration_ms: 10,
+            duration_api_ms: 8,
+            is_error: true,
+            num_turns: 0,
+            stop_reason: None,
+            total_cost_usd: 0.0,
+            usage: ResultUsage {
+                input_tokens: 0,
+                output_tokens: 0,
+                cache_creation_input_tokens: None,
+                cache_read_input_tokens: None,
+            },
+            errors: vec!["API Error: 429 rate_limit_error".into()],
+            uuid: None,
+        }))
+    }
+
+    #[tokio::test]
+    async fn fallback_retries_with_next_model_and_reports_it() {
+        let started: Arc<Mutex<Vec<(String, QueryOptions)>>> = Arc::default();
+        let seen = started.clone();
+        let opts = QueryOptions {
+            model: Some("primary".into()),
+            model_fallbacks: vec!["secondary".into()],
+            resume: Some("s1".into()),
+            ..Default::default()
+        };
+        let stream = fallback_stream("do it".into(), opts, move |prompt, opts| {
+            seen.lock().unwrap().push((prompt, opts.clone()));
+            if opts.model.as_deref() == Some("primary") {
+                mock_stream(vec![Ok(system_init_msg()), Ok(rate_limited_msg())])
+            } else {
+                mock_stream(vec![Ok(system_init_msg()), Ok(success_msg("done"))])
+            }
+        });
+
+        let result = collect(stream).await.unwrap();
+        assert_eq!(result.result_text, "done");
+        assert!(!result.is_error);
+        assert_eq!(
+            result.fallbacks,
+            vec![ModelFallbackPayload {
+                from_model: Some("primary".into()),
+                to_model: "secondary".into(),
+                reason: "API Error: 429 rate_limit_error".into(),
+            }]
+        );
+        let started = started.lock().unwrap();
+        let models: Vec<_> = started.iter().map(|(_, o)| o.model.as_deref()).collect();
+        assert_eq!(models, vec![Some("primary"), Some("secondary")]);
+        for (prompt, opts) in started.iter() {
+            assert_eq!(prompt, "do it");
+            assert_eq!(opts.resume.as_deref(), Some("s1"));
+        }
+    }
+
+    /// A policy `Ask` behind the runner is refused rather than left for a
+    /// caller who cannot see the spliced stream — the CLI would otherwise
+    /// block on stdin and the run would never finish.
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn policy_ask_is_refused_through_the_fallback_splice() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let ask = r#"{"type":"control_request","request_id":"r-write","request":{"subtype":"can_use_tool","tool_name":"Write","input":{"path":"x"}}}"#;
+        std::fs::write(dir.path().join("ask"), format!("{ask}\n")).unwrap();
+        std::fs::write(
+            dir.path().join("result"),
+            serde_json::to_string(&success_msg("done")).unwrap() + "\n",
+        )
+        .unwrap();
+        let script = format!(
+            "cd {} && cat ask && read a && printf '%s\\n' \"$a\" > answers && cat result",
+            dir.path().display()
+        );
+        let opts = QueryOptions {
+            permission_mode: crate::PermissionMode::Policy(
+                crate::PermissionPolicy::default().allow("Read*"),
+            ),
+            ..Default::default()
+        };
+
+        let run = RunnerBuilder::new(config(false)).run_with(String::new(), opts, move |p, o| {
+            let script = script.clone();
+            fallback_stream(p, o, move |_, o| {
+                let script = script.clone();
+                QueryStream::pump(
+                    async move {
+                        let mut cmd = tokio::process::Command::new("sh");
+                        cmd.arg("-c").arg(script);
+                        crate::process::ClaudeProcess::spawn_command(cmd)
+                    },
+                    o,
+                )
+            })
+        });
+        let result = tokio::time::timeout(std::time::Duration::from_secs(10), run)
+            .await
+            .expect("run must not hang on the Ask prompt")
+            .unwrap();
+        assert_eq!(result.result_text, "done");
+
+        let answer: serde_json::Value = serde_json::from_str(
+            std::fs::read_to_string(dir.path().join("answers"))
+                .unwrap()
+                .trim(),
+        )
+        .unwrap();
+        assert_eq!(answer["response"]["request_id"], "r-write");
+        assert_eq!(answer["response"]["response"]["behavior"], "deny");
+    }
+
+    #[tokio::test]
+    async fn fallback_passes_through_unrelated_errors() {
+        let opts = QueryOptions {
+            model_fallbacks: vec!["secondary".into()],
+            ..Default::default()
+        };
+        let stream = fallback_stream("do it".into(), opts, |_, _| {
+            mock_stream(vec![Ok(error_msg())])
+        });
+        let result = collect(stream).await.unwrap();
+        assert!(result.is_error);
+        assert!(result.fallbacks.is_empty());
+    }
+
+    #[tokio::test]
+    async fn builder_hooks_fire_for_replayed_fixture() {
+        let tool_use: Message = serde_json::from_str(
+            r#"{"type":"assistant","message":{"id":"msg_1","role":"assistant","model":"m","content":[{"type":"text","text":"Looking."},{"type":"tool_use","id":"tu_1","name":"Read","input":{"file_path":"a.rs"}},{"type":"tool_use","id":"tu_2","name":"Grep","input":{"pattern":"fn"}}],"usage":{"input_tokens":1,"output_tokens":1}},"parent_tool_use_id":null,"session_id":"s1"}"#,
+        )
+        .unwrap();
+        let stream = mock_stream(vec![
+            Ok(system_init_msg()),
+            Ok(tool_use),
+            Ok(success_msg("done")),
+        ]);
+        let stream = Mutex::new(Some(stream));
+
+        let dir = tempfile::TempDir::new().unwrap();
+        let messages = Arc::new(Mutex::new(0));
+        let tools = Arc::new(Mutex::new(Vec::new()));
+        let results = Arc::new(Mutex::new(0));
+        let (m, t, r) = (messages.clone(), tools.clone(), results.clone());
+
+        let result = RunnerBuilder::new(config(false))
+            .on_message(move |_| *m.lock().unwrap() += 1)
+            .on_tool_use(move |name, _| t.lock().unwrap().push(name.to_string()))
+            .on_result(move |_| *r.lock().unwrap() += 1)
+            .with_store(SessionStore::new(dir.path()), "auth")
+            .run_with(String::new(), QueryOptions::default(), move |_, _| {
+                stream.lock().unwrap().take().expect("started once")
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(result.result_text, "done");
+        assert_eq!(*messages.lock().unwrap(), 3);
+        assert_eq!(*tools.lock().unwrap(), vec!["Read", "Grep"]);
+        assert_eq!(*results.lock().unwrap(), 1);
+        assert_eq!(
+            SessionStore::new(dir.path()).load("auth").as_deref(),
+            Some("s1")
+        );
+    }
+
+    fn tool_turn(msg_id: &str, tool_id: &str, name: &str, is_error: bool) -> [Message; 2] {
+        let use_line = format!(
+            r#"{{"type":"assistant","message":{{"id":"{msg_id}","role":"assistant","model":"m","content":[{{"type":"tool_use","id":"{tool_id}","name":"{name}","input":{{}}}}],"usage":{{"input_tokens":1,"output_tokens":1}}}},"parent_tool_use_id":null,"session_id":"s1","uuid":"u-{msg_id}"}}"#
+        );
+        let result_line = format!(
+            r#"{{"type":"user","message":{{"role":"user","content":[{{"type":"tool_result","tool_use_id":"{tool_id}","is_error":{is_error}}}]}},"parent_tool_use_id":null,"session_id":"s1"}}"#
+        );
+        [
+            serde_json::from_str(&use_line).unwrap(),
+            serde_json::from_str(&result_line).unwrap(),
+        ]
+    }
+
+    #[tokio::test]
+    async fn checkpoints_at_artifact_boundaries_and_retry_resumes_latest() {
+        let approve = "mcp__sdlc__sdlc_approve_artifact";
+        let dir = tempfile::TempDir::new().unwrap();
+        let checkpointed = |messages: Vec<Message>| {
+            let stream = Mutex::new(Some(mock_stream(messages.into_iter().map(Ok).collect())));
+            let started = Arc::new(Mutex::new(Vec::new()));
+            let seen = started.clone();
+            let builder = RunnerBuilder::new(config(false)).with_checkpoints(
+                SessionStore::new(dir.path()),
+                "run-1",
+                move |name| name == approve,
+            );
+            let run = builder.run_with(String::new(), QueryOptions::default(), move |_, opts| {
+                seen.lock().unwrap().push((
+                    opts.resume.clone(),
+                    opts.resume_session_at.clone(),
+                    opts.fork_session,
+                ));
+                stream.lock().unwrap().take().expect("started once")
+            });
+            (run, started)
+        };
+
+        let mut first = vec![system_init_msg()];
+        first.extend(tool_turn("msg_1", "tu_1", approve, false));
+        first.extend(tool_turn("msg_2", "tu_2", "Read", false));
+        first.extend(tool_turn("msg_3", "tu_3", approve, true));
+        first.extend(tool_turn("msg_4", "tu_4", approve, false));
+        first.push(error_msg());
+        let (run, started) = checkpointed(first);
+        assert!(run.await.unwrap().is_error);
+        assert_eq!(*started.lock().unwrap(), vec![(None, None, false)]);
+
+        let store = SessionStore::new(dir.path());
+        let turns: Vec<u32> = store
+            .checkpoints("run-1")
+            .unwrap()
+            .iter()
+            .map(|c| c.turn)
+            .collect();
+        assert_eq!(turns, vec![1, 4]);
+        let latest = store.latest_checkpoint("run-1").unwrap().unwrap();
+        assert_eq!(latest.message_uuid.as_deref(), Some("u-msg_4"));
+
+        let mut retry = vec![system_init_msg()];
+        retry.extend(tool_turn("msg_5", "tu_5", approve, false));
+        retry.push(success_msg("done"));
+        let (run, started) = checkpointed(retry);
+        assert!(!run.await.unwrap().is_error);
+        // The retry resumes at the checkpointed message, in a forked session.
+        assert_eq!(
+            *started.lock().unwrap(),
+            vec![(Some("s1".to_string()), Some("u-msg_4".to_string()), true)]
+        );
+        let latest = store.latest_checkpoint("run-1").unwrap().unwrap();
+        assert_eq!(latest.turn, 5);
+    }
+
+    #[tokio::test]
+    async fn tool_calls_recorded_with_their_outcomes() {
+        let fixture = [
+            r#"{"type":"assistant","message":{"id":"msg_1","role":"assistant","model":"m","content":[{"type":"tool_use","id":"tu_1","name":"Read","input":{"file_path":"a.rs"}},{"type":"tool_use","id":"tu_2","name":"Bash","input":{"command":"cargo test"}}],"usage":{"input_tokens":1,"output_tokens":1}},"parent_tool_use_id":null,"session_id":"s1"}"#,
+            r#"{"type":"user","message":{"role":"user","content":[{"type":"tool_result","tool_use_id":"tu_1","content":[{"type":"text","text":"fn main() {}"}]},{"type":"tool_result","tool_use_id":"tu_2","content":[{"type":"text","text":"1 failed"}],"is_error":true}]},"parent_tool_use_id":null,"session_id":"s1"}"#,
+            r#"{"type":"assistant","message":{"id":"msg_2","role":"assistant","model":"m","content":[{"type":"tool_use","id":"tu_3","name":"Edit","input":{"file_path":"a.rs","old_string":"x","new_string":"y"}}],"usage":{"input_tokens":1,"output_tokens":1}},"parent_tool_use_id":null,"session_id":"s1"}"#,
+            r#"{"type":"user","message":{"role":"user","content":[{"type":"tool_result","tool_use_id":"tu_3","is_error":false}]},"parent_tool_use_id":null,"session_id":"s1"}"#,
+            r#"{"type":"assistant","message":{"id":"msg_3","role":"assistant","model":"m","content":[{"type":"tool_use","id":"tu_4","name":"Bash","input":{"command":"x"}}],"usage":{"input_tokens":1,"output_tokens":1}},"parent_tool_use_id":null,"session_id":"s1"}"#,
+        ];
+        let mut messages = vec![Ok(system_init_msg())];
+        messages.extend(
+            fixture
+                .iter()
+                .map(|line| Ok(serde_json::from_str::<Message>(line).unwrap())),
+        );
+        messages.push(Ok(success_msg("done")));
+
+        let result = collect(mock_stream(messages)).await.unwrap();
+
+        let calls: Vec<(&str, &str, bool)> = result
+            .tool_calls
+            .iter()
+            .map(|c| (c.id.as_str(), c.name.as_str(), c.is_error))
+            .collect();
+        assert_eq!(
+            calls,
+            vec![
+                ("tu_1", "Read", false),
+                ("tu_2", "Bash", true),
+                ("tu_3", "Edit", false),
+                // No result before the run ended.
+                ("tu_4", "Bash", true),
+            ]
+        );
+        assert_eq!(
+            result.tool_calls[0].input_summary,
+            r#"{"file_path":"a.rs"}"#
+        );
+    }
+
+    #[test]
+    fn tool_timings_span_tool_use_to_matching_result() {
+        let message = |line: serde_json::Value| serde_json::from_value::<Message>(line).unwrap();
+        let tool_use = |msg_id: &str, calls: &[(&str, &str)]| {
+            let content: Vec<_> = calls
+                .iter()
+                .map(|(id, name)| serde_json::json!({"type": "tool_use", "id": id, "name": name, "input": {}}))
+                .collect();
+            message(serde_json::json!({
+                "type": "assistant",
+                "message": {"id": msg_id, "role": "assistant", "model": "m", "content": content,
+                            "usage": {"input_tokens": 1, "output_tokens": 1}},
+                "parent_tool_use_id": null,
+                "session_id": "s1",
+            }))
+        };
+        let tool_result = |id: &str| {
+            message(serde_json::json!({
+                "type": "user",
+                "message": {"role": "user", "content": [{"type": "tool_result", "tool_use_id": id}]},
+                "parent_tool_use_id": null,
+                "session_id": "s1",
+            }))
+        };
+        // (milliseconds since the run started, message)
+        let fixture = [
+            (0, system_init_msg()),
+            (
+                100,
+                tool_use(
+                    "msg_1",
+                    &[("tu_1", "mcp__sdlc__sdlc_get_directive"), ("tu_2", "Read")],
+                ),
+            ),
+            (130, tool_result("tu_2")),
+            (1_600, tool_result("tu_1")),
+            (1_700, tool_use("msg_2", &[("tu_3", "Read")])),
+            (1_750, tool_result("tu_3")),
+            // A result for a call never seen is ignored.
+            (1_800, tool_result("tu_9")),
+        ];
+
+        let start = Instant::now();
+        let mut timer = ToolTimer::default();
+        let tool_timings: Vec<ToolTiming> = fixture
+            .iter()
+            .flat_map(|(ms, msg)| timer.observe(msg, start + std::time::Duration::from_millis(*ms)))
+            .collect();
+
+        let timed: Vec<(&str, &str, u64)> = tool_timings
+            .iter()
+            .map(|t| (t.id.as_str(), t.name.as_str(), t.duration_ms))
+            .collect();
+        assert_eq!(
+            timed,
+            vec![
+                ("tu_2", "Read", 30),
+                ("tu_1", "mcp__sdlc__sdlc_get_directive", 1_500),
+                ("tu_3", "Read", 50),
+            ]
+        );
+
+        let result = RunResult {
+            session_id: "s1".into(),
+            result_text: String::new(),
+            total_cost_usd: 0.0,
+            num_turns: 2,
+            is_error: false,
+            fallbacks: vec![],
+            errors: vec![],
+            compactions: 0,
+            tool_calls: vec![],
+            tool_timings,
+        };
+        assert_eq!(
+            result.tool_time_by_name(),
+            vec![
+                ("mcp__sdlc__sdlc_get_directive".to_string(), 1_500),
+                ("Read".to_string(), 80),
+            ]
+        );
+    }
+
+    #[test]
+    fn partial_result_includes_earlier_compaction_attempts() {
+        let partial = RunResult {
+            session_id: "s2".into(),
+            result_text: String::new(),
+            total_cost_usd: 0.5,
+            num_turns: 2,
+            is_error: true,
+            fallbacks: Vec::new(),
+            errors: Vec::new(),
+            compactions: 0,
+            tool_calls: vec![ToolCallRecord::new("tu_2", "Bash", &serde_json::json!({}))],
+            tool_timings: Vec::new(),
+        };
+        let err = ClaudeAgentError::BudgetExceeded {
+            spent: 2.0,
+            limit: 1.5,
+            unit: "USD",
+            partial: Box::new(partial),
+        }
+        .with_earlier_attempts(
+            &[ToolCallRecord::new("tu_1", "Read", &serde_json::json!({}))],
+            &[],
+            1.5,
+            40,
+            1,
+        );
+        let partial = err.partial_result().unwrap();
+        let ids: Vec<_> = partial.tool_calls.iter().map(|c| c.id.as_str()).collect();
+        assert_eq!(ids, ["tu_1", "tu_2"]);
+        assert_eq!(partial.total_cost_usd, 2.0);
+        assert_eq!(partial.num_turns, 42);
+        assert_eq!(partial.compactions, 1);
+    }
+
+    #[test]
+    fn tool_input_summary_is_truncated() {
+        let input = serde_json::json!({ "content": "é".repeat(500) });
+        let call = ToolCallRecord::new("tu_1", "Write", &input);
+        assert_eq!(
+            call.input_summary.chars().count(),
+            TOOL_INPUT_SUMMARY_CHARS + 1
+        );
+        assert!(call.input_summary.ends_with('…'));
+    }
+
+    #[tokio::test]
+    async fn invalid_mcp_server_fails_before_spawning() {
+        let opts = QueryOptions {
+            mcp_servers: vec![crate::McpServerConfig::http("docs", "ftp://example.com")],
+            ..Default::default()
+        };
+        let err = RunnerBuilder::new(config(false))
+            .run_with(String::new(), opts, |_, _| panic!("must not start a query"))
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(err, ClaudeAgentError::McpConfigInvalid { ref server, .. } if server == "docs"),
+            "{err}"
+        );
+    }
+
+    fn assistant_msg(id: &str, input_tokens: u64, output_tokens: u64) -> Message {
+        serde_json::from_value(serde_json::json!({
+            "type": "assistant",
+            "message": {
+                "id": id, "role": "assistant", "model": "claude-sonnet-4-6",
+                "content": [{"type": "text", "text": id}],
+                "usage": {"input_tokens": input_tokens, "output_tokens": output_tokens},
+            },
+            "parent_tool_use_id": null,
+            "session_id": "s1",
+        }))
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn budget_ceiling_aborts_at_the_crossing_message() {
+        let stream = mock_stream(vec![
+            Ok(system_init_msg()),
+            Ok(assistant_msg("msg_1", 400, 100)),
+            // Same API message, next content block: not counted again.
+            Ok(assistant_msg("msg_1", 400, 100)),
+            Ok(assistant_msg("msg_2", 400, 200)),
+            Ok(assistant_msg("msg_3", 100, 100)),
+            Ok(success_msg("done")),
+        ]);
+        let stream = Mutex::new(Some(stream));
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let s = seen.clone();
+        let mut cfg = config(false);
+        cfg.max_total_tokens = Some(1_000);
+
+        let err = RunnerBuilder::new(cfg)
+            .on_message(move |msg| {
+                if let Message::Assistant(a) = msg {
+                    s.lock().unwrap().push(a.message.id.clone());
+                }
+            })
+            .run_with(String::new(), QueryOptions::default(), move |_, _| {
+                stream.lock().unwrap().take().expect("started once")
+            })
+            .await
+            .unwrap_err();
+
+        assert!(
+            matches!(
+                err,
+                ClaudeAgentError::BudgetExceeded { spent, limit, unit: "tokens", .. }
+                    if spent == 1_100.0 && limit == 1_000.0
+            ),
+            "{err}"
+        );
+        // The transcript runs up to and including the crossing message.
+        assert_eq!(*seen.lock().unwrap(), vec!["msg_1", "msg_1", "msg_2"]);
+        let partial = err.partial_result().expect("partial result");
+        assert_eq!(partial.session_id, "s1");
+        assert_eq!(partial.num_turns, 2);
+        assert!(partial.is_error);
+    }
+
+    #[tokio::test]
+    async fn repeated_message_usage_counts_its_latest_value() {
+        // The CLI repeats msg_1 per content block; output_tokens grows as
+        // the message streams, so the last copy carries the real total.
+        let stream = mock_stream(vec![
+            Ok(assistant_msg("msg_1", 400, 1)),
+            Ok(assistant_msg("msg_1", 400, 700)),
+            Ok(success_msg("done")),
+        ]);
+        let stream = Mutex::new(Some(stream));
+        let mut cfg = config(false);
+        cfg.max_total_tokens = Some(1_000);
+        let err = RunnerBuilder::new(cfg)
+            .run_with(String::new(), QueryOptions::default(), move |_, _| {
+                stream.lock().unwrap().take().expect("started once")
+            })
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(err, ClaudeAgentError::BudgetExceeded { spent, .. } if spent == 1_100.0),
+            "{err}"
+        );
+    }
+
+    #[tokio::test]
+    async fn cost_ceiling_prices_usage_with_the_pricing_table() {
+        // 1M input tokens of Sonnet at $3/MTok.
+        let stream = mock_stream(vec![
+            Ok(assistant_msg("msg_1", 1_000_000, 0)),
+            Ok(success_msg("done")),
+        ]);
+        let stream = Mutex::new(Some(stream));
+        let mut cfg = config(false);
+        cfg.max_cost_usd = Some(2.5);
+        let err = RunnerBuilder::new(cfg)
+            .run_with(String::new(), QueryOptions::default(), move |_, _| {
+                stream.lock().unwrap().take().expect("started once")
+            })
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(err, ClaudeAgentError::BudgetExceeded { unit: "USD", .. }),
+            "{err}"
+        );
+    }
+
+    fn overflow_msg() -> Message {
+        Message::Result(ResultMessage::ErrorDuringExecution(ResultError {
+            session_id: "s1".into(),
+            duration_ms: 10,
+            duration_api_ms: 8,
+            is_error: true,
+            num_turns: 40,
+            stop_reason: None,
+            total_cost_usd: 0.5,
+            usage: ResultUsage {
+                input_tokens: 0,
+                output_tokens: 0,
+                cache_creation_input_tokens: None,
+                cache_read_input_tokens: None,
+            },
+            errors: vec!["Prompt is too long".into()],
+            uuid: None,
+        }))
+    }
+
+    #[tokio::test]
+    async fn repeatedly_failing_tool_call_stalls_the_run() {
+        let mut recording = vec![system_init_msg()];
+        for n in 1..=5 {
+            recording.extend(tool_turn(
+                &format!("msg_r{n}"),
+                &format!("tu_r{n}"),
+                "Read",
+                false,
+            ));
+            recording.extend(tool_turn(
+                &format!("msg_b{n}"),
+                &format!("tu_b{n}"),
+                "Bash",
+                true,
+            ));
+        }
+        recording.push(success_msg("done"));
+        let bash_calls = Arc::new(Mutex::new(0));
+        let seen = bash_calls.clone();
+        let mut cfg = config(false);
+        cfg.max_consecutive_failures = 3;
+
+        let err = RunnerBuilder::new(cfg)
+            .on_tool_use(move |name, _| {
+                if name == "Bash" {
+                    *seen.lock().unwrap() += 1;
+                }
+            })
+            .replay(recording)
+            .await
+            .unwrap_err();
+
+        assert!(
+            matches!(
+                err,
+                ClaudeAgentError::RunStalled { ref action, attempts: 3, .. } if action == "Bash"
+            ),
+            "{err}"
+        );
+        assert_eq!(*bash_calls.lock().unwrap(), 3);
+        let partial = err.partial_result().expect("partial result");
+        let failed: Vec<&str> = partial
+            .tool_calls
+            .iter()
+            .filter(|c| c.is_error)
+            .map(|c| c.name.as_str())
+            .collect();
+        assert_eq!(failed, ["Bash", "Bash", "Bash"]);
+        assert_eq!(partial.tool_calls.len(), 6);
+    }
+
+    #[tokio::test]
+    async fn overflow_compacts_once_then_retries() {
+        // (prompt, resume) per started query
+        type Calls = Vec<(String, Option<String>)>;
+        let calls: Arc<Mutex<Calls>> = Arc::default();
+        let seen = calls.clone();
+        let result = RunnerBuilder::new(config(true))
+            .run_with(
+                "finish the feature".into(),
+                QueryOptions::default(),
+                move |prompt, opts| {
+                    let mut calls = seen.lock().unwrap();
+                    calls.push((prompt, opts.resume.clone()));
+                    match calls.len() {
+                        1 => mock_stream(vec![Ok(overflow_msg())]),
+                        2 => mock_stream(vec![Ok(success_msg("summary"))]),
+                        _ => mock_stream(vec![Ok(success_msg("done"))]),
+                    }
+                },
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(result.result_text, "done");
+        assert_eq!(result.compactions, 1);
+        assert!((result.total_cost_usd - (0.5 + 0.012 + 0.012)).abs() < 1e-9);
+        let calls = calls.lock().unwrap();
+        assert_eq!(calls.len(), 3);
+        assert_eq!(calls[0], ("finish the feature".to_string(), None));
+        assert!(calls[1].0.starts_with("/compact"));
+        assert_eq!(calls[1].1.as_deref(), Some("s1"));
+        assert_eq!(
+            calls[2],
+            ("finish the feature".to_string(), Some("s1".into()))
+        );
+    }
+
+    #[tokio::test]
+    async fn recorded_run_replays_to_an_identical_result() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let store = SessionStore::new(dir.path());
+        let tool_use = r#"{"type":"assistant","message":{"id":"msg_1","role":"assistant","model":"m","content":[{"type":"tool_use","id":"tu_1","name":"Bash","input":{"command":"cargo test"}}],"usage":{"input_tokens":1,"output_tokens":1}},"parent_tool_use_id":null,"session_id":"s1"}"#;
+        let tool_result = r#"{"type":"user","message":{"role":"user","content":[{"type":"tool_result","tool_use_id":"tu_1","is_error":true}]},"parent_tool_use_id":null,"session_id":"s1"}"#;
+        let counter = |count: &Arc<Mutex<usize>>| {
+            let count = count.clone();
+            move |_: &Message| *count.lock().unwrap() += 1
+        };
+
+        // A run that overflows, compacts, and retries: three queries.
+        let live_count = Arc::default();
+        let calls = Mutex::new(0);
+        let live = RunnerBuilder::new(config(true))
+            .on_message(counter(&live_count))
+            .with_recording(&store, "run-1")
+            .unwrap()
+            .run_with(String::new(), QueryOptions::default(), move |_, _| {
+                let mut calls = calls.lock().unwrap();
+                *calls += 1;
+                match *calls {
+                    1 => mock_stream(vec![
+                        Ok(system_init_msg()),
+                        Ok(serde_json::from_str(tool_use).unwrap()),
+                        Ok(serde_json::from_str(tool_result).unwrap()),
+                        Ok(overflow_msg()),
+                    ]),
+                    2 => mock_stream(vec![Ok(success_msg("summary"))]),
+                    _ => mock_stream(vec![Ok(system_init_msg()), Ok(success_msg("done"))]),
+                }
+            })
+            .await
+            .unwrap();
+
+        let replay_count = Arc::default();
+        let replayed = RunnerBuilder::new(config(true))
+            .on_message(counter(&replay_count))
+            .replay(store.recording("run-1").unwrap())
+            .await
+            .unwrap();
+
+        assert_eq!(replayed, live);
+        assert_eq!(replayed.compactions, 1);
+        assert_eq!(replayed.tool_calls.len(), 1);
+        assert_eq!(*replay_count.lock().unwrap(), 7);
+        assert_eq!(*replay_count.lock().unwrap(), *live_count.lock().unwrap());
+    }
+
+    #[tokio::test]
+    async fn overflow_without_auto_compact_is_returned() {
+        let result = collect(mock_stream(vec![Ok(overflow_msg())]))
+            .await
+            .unwrap();
+        assert!(result.is_context_overflow());
+        assert_eq!(result.compactions, 0);
+    }
+
+    /// `(span, field, value)` for every field a span is opened or recorded with.
+    #[derive(Clone, Default)]
+    struct SpanLog(Arc<Mutex<Vec<(String, String, String)>>>);
+
+    impl SpanLog {
+        fn values(&self, span: &str, field: &str) -> Vec<String> {
+            self.0
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|(s, f, _)| s == span && f == field)
+                .map(|(_, _, v)| v.clone())
+                .collect()
+        }
+    }
+
+    struct FieldVisitor<'a> {
+        span: &'static str,
+        log: &'a Mutex<Vec<(String, String, String)>>,
+    }
+
+    impl tracing::field::Visit for FieldVisitor<'_> {
+        fn record_str(&mut self, field: &tracing::field::Field, value: &str) {
+            self.log.lock().unwrap().push((
+                self.span.to_string(),
+                field.name().to_string(),
+                value.to_string(),
+            ));
+        }
+
+        fn record_debug(&mut self, field: &tracing::field::Field, value: &dyn std::fmt::Debug) {
+            self.record_str(field, &format!("{value:?}"));
+        }
+    }
+
+    impl<S> tracing_subscriber::Layer<S> for SpanLog
+    where
+        S: tracing::Subscriber + for<'a> tracing_subscriber::registry::LookupSpan<'a>,
+    {
+        fn on_new_span(
+            &self,
+            attrs: &tracing::span::Attributes<'_>,
+            _id: &tracing::span::Id,
+            _ctx: tracing_subscriber::layer::Context<'_, S>,
+        ) {
+            attrs.record(&mut FieldVisitor {
+                span: attrs.metadata().name(),
+                log: &self.0,
+            });
+        }
+
+        fn on_record(
+            &self,
+            id: &tracing::span::Id,
+            values: &tracing::span::Record<'_>,
+            ctx: tracing_subscriber::layer::Context<'_, S>,
+        ) {
+            let span = ctx.span(id).expect("recorded span exists").name();
+            values.record(&mut FieldVisitor { span, log: &self.0 });
+        }
+    }
+
+    async fn traced_run(trace_messages: bool) -> SpanLog {
+        use tracing_subscriber::layer::SubscriberExt;
+
+        let log = SpanLog::default();
+        let _guard =
+            tracing::subscriber::set_default(tracing_subscriber::registry().with(log.clone()));
+        let stream = mock_stream(vec![Ok(system_init_msg()), Ok(success_msg("done"))]);
+        let stream = Mutex::new(Some(stream));
+        let opts = QueryOptions {
+            model: Some("claude-sonnet-4-6".into()),
+            ..Default::default()
+        };
+        let mut cfg = config(false);
+        cfg.trace_messages = trace_messages;
+        RunnerBuilder::new(cfg)
+            .run_with(String::new(), opts, move |_, _| {
+                stream.lock().unwrap().take().expect("started once")
+            })
+            .await
+            .unwrap();
+        log
+    }
+
+    #[tokio::test]
+    async fn replayed_run_records_run_and_message_spans() {
+        let log = traced_run(true).await;
+
+        assert_eq!(log.values("claude.run", "model"), vec!["claude-sonnet-4-6"]);
+        assert_eq!(log.values("claude.run", "turns"), vec!["3"]);
+        assert_eq!(log.values("claude.run", "messages"), vec!["2"]);
+        assert_eq!(log.values("claude.run", "is_error"), vec!["false"]);
+        assert_eq!(log.values("claude.run", "compactions"), vec!["0"]);
+        for field in ["first_byte_ms", "duration_ms", "cost_usd", "bytes"] {
+            assert_eq!(log.values("claude.run", field).len(), 1, "{field}");
+        }
+
+        assert_eq!(
+            log.values("claude.message", "kind"),
+            vec!["system", "result"]
+        );
+        assert_eq!(log.values("claude.message", "seq"), vec!["1", "2"]);
+        let bytes: Vec<u64> = log
+            .values("claude.message", "bytes")
+            .iter()
+            .map(|b| b.parse().unwrap())
+            .collect();
+        assert_eq!(bytes.len(), 2);
+        assert!(bytes.iter().all(|&b| b > 0));
+        assert_eq!(log.values("claude.message", "wait_ms").len(), 2);
+    }
+
+    #[tokio::test]
+    async fn message_spans_only_with_trace_messages() {
+        let log = traced_run(false).await;
+        assert_eq!(log.values("claude.run", "messages"), vec!["2"]);
+        assert!(log.values("claude.run", "bytes").is_empty());
+        assert!(log.values("claude.message", "kind").is_empty());
+    }
 }
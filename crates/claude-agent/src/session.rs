@@ -1,6 +1,12 @@
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
 use std::path::{Path, PathBuf};
+use std::time::Duration;
 
-use crate::{ClaudeAgentError, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::{ClaudeAgentError, Message, Result};
 
 // ─── SessionStore ─────────────────────────────────────────────────────────
 
@@ -29,6 +35,15 @@ use crate::{ClaudeAgentError, Result};
 /// // On explicit reset:
 /// store.clear("my-feature")?;
 /// ```
+///
+/// Runs can also keep a transcript: one JSON event per line in
+/// `<project_root>/.sdlc/sessions/<run-id>.jsonl`, appended as the run
+/// progresses so another process can [`tail`](SessionStore::tail) it, and
+/// checkpoints: resumable session IDs recorded at meaningful points of the
+/// run in `<project_root>/.sdlc/sessions/<run-id>.checkpoints.jsonl`, and a
+/// recording: every raw [`Message`] the runner saw, in
+/// `<project_root>/.sdlc/sessions/<run-id>.messages.jsonl`, for
+/// [`RunnerBuilder::replay`](crate::RunnerBuilder::replay).
 pub struct SessionStore {
     sessions_dir: PathBuf,
 }
@@ -73,6 +88,246 @@ impl SessionStore {
     fn path(&self, slug: &str) -> PathBuf {
         self.sessions_dir.join(format!("{slug}.session"))
     }
+
+    /// Path of the transcript for `run_id` (which may not exist yet).
+    pub fn transcript_path(&self, run_id: &str) -> PathBuf {
+        self.sessions_dir.join(format!("{run_id}.jsonl"))
+    }
+
+    /// Open the transcript for `run_id` for appending, creating it if needed.
+    pub fn transcript_writer(&self, run_id: &str) -> Result<TranscriptWriter> {
+        std::fs::create_dir_all(&self.sessions_dir).map_err(ClaudeAgentError::Io)?;
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(self.transcript_path(run_id))
+            .map_err(ClaudeAgentError::Io)?;
+        Ok(TranscriptWriter { file })
+    }
+
+    /// Read the transcript for `run_id` event by event. With `follow`, the
+    /// reader waits at end of file for more lines until the run's terminal
+    /// `result` (or `error`) event, like `tail -f`.
+    pub fn tail(&self, run_id: &str, follow: bool) -> Result<TranscriptTail> {
+        let file = File::open(self.transcript_path(run_id)).map_err(ClaudeAgentError::Io)?;
+        Ok(TranscriptTail {
+            reader: BufReader::new(file),
+            pending: String::new(),
+            follow,
+            finished: false,
+            poll_interval: TAIL_POLL_INTERVAL,
+        })
+    }
+
+    /// Delete the transcript for `run_id` (no-op if none exists).
+    pub fn clear_transcript(&self, run_id: &str) -> Result<()> {
+        let p = self.transcript_path(run_id);
+        if p.exists() {
+            std::fs::remove_file(&p).map_err(ClaudeAgentError::Io)?;
+        }
+        Ok(())
+    }
+
+    /// Path of the message recording for `run_id` (which may not exist yet).
+    pub fn recording_path(&self, run_id: &str) -> PathBuf {
+        self.sessions_dir.join(format!("{run_id}.messages.jsonl"))
+    }
+
+    /// Start a fresh recording for `run_id`, replacing any earlier one.
+    pub fn recording_writer(&self, run_id: &str) -> Result<TranscriptWriter> {
+        std::fs::create_dir_all(&self.sessions_dir).map_err(ClaudeAgentError::Io)?;
+        let file = File::create(self.recording_path(run_id)).map_err(ClaudeAgentError::Io)?;
+        Ok(TranscriptWriter { file })
+    }
+
+    /// The messages recorded for `run_id`, in the order the runner saw them.
+    /// Lines holding a bare message, without an arrival offset, are read
+    /// with `offset_ns: None`.
+    pub fn recording(&self, run_id: &str) -> Result<Vec<RecordedMessage>> {
+        let file = File::open(self.recording_path(run_id)).map_err(ClaudeAgentError::Io)?;
+        let mut messages = Vec::new();
+        for line in BufReader::new(file).lines() {
+            let line = line.map_err(ClaudeAgentError::Io)?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let parse_error = |source| ClaudeAgentError::Parse {
+                line: line.trim().to_owned(),
+                source,
+            };
+            let event: serde_json::Value = serde_json::from_str(&line).map_err(parse_error)?;
+            let recorded = if event.get("type").is_some() {
+                serde_json::from_value::<Message>(event).map(RecordedMessage::from)
+            } else {
+                serde_json::from_value(event)
+            };
+            messages.push(recorded.map_err(parse_error)?);
+        }
+        Ok(messages)
+    }
+
+    /// Path of the checkpoint log for `run_id` (which may not exist yet).
+    pub fn checkpoints_path(&self, run_id: &str) -> PathBuf {
+        self.sessions_dir
+            .join(format!("{run_id}.checkpoints.jsonl"))
+    }
+
+    /// Record that `run_id` can be resumed from `session_id` as it stood
+    /// after `turn`, whose last assistant message is `message_uuid`.
+    pub fn checkpoint(
+        &self,
+        run_id: &str,
+        session_id: &str,
+        message_uuid: Option<&str>,
+        turn: u32,
+    ) -> Result<()> {
+        std::fs::create_dir_all(&self.sessions_dir).map_err(ClaudeAgentError::Io)?;
+        let checkpoint = Checkpoint {
+            session_id: session_id.to_owned(),
+            message_uuid: message_uuid.map(str::to_owned),
+            turn,
+            created_at: Utc::now(),
+        };
+        let mut line = serde_json::to_vec(&checkpoint).map_err(|e| {
+            ClaudeAgentError::Process(format!("failed to serialize checkpoint: {e}"))
+        })?;
+        line.push(b'\n');
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(self.checkpoints_path(run_id))
+            .and_then(|mut f| f.write_all(&line))
+            .map_err(ClaudeAgentError::Io)
+    }
+
+    /// Every checkpoint of `run_id`, oldest first. Empty if none were recorded.
+    pub fn checkpoints(&self, run_id: &str) -> Result<Vec<Checkpoint>> {
+        let data = match std::fs::read_to_string(self.checkpoints_path(run_id)) {
+            Ok(data) => data,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(ClaudeAgentError::Io(e)),
+        };
+        data.lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| {
+                serde_json::from_str(line).map_err(|source| ClaudeAgentError::Parse {
+                    line: line.to_owned(),
+                    source,
+                })
+            })
+            .collect()
+    }
+
+    /// The most recent checkpoint of `run_id`, if any.
+    pub fn latest_checkpoint(&self, run_id: &str) -> Result<Option<Checkpoint>> {
+        Ok(self.checkpoints(run_id)?.pop())
+    }
+}
+
+// ─── Checkpoints ──────────────────────────────────────────────────────────
+
+/// A point a failed run can be resumed from; see [`SessionStore::checkpoint`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Checkpoint {
+    pub session_id: String,
+    /// The assistant message to resume at, so a retry continues from the
+    /// checkpoint rather than from wherever the session ended. Older
+    /// checkpoints without one resume the whole session.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message_uuid: Option<String>,
+    /// Assistant turns completed when the checkpoint was taken.
+    pub turn: u32,
+    pub created_at: DateTime<Utc>,
+}
+
+// ─── Recordings ───────────────────────────────────────────────────────────
+
+/// One line of a run recording: a message and when the runner saw it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordedMessage {
+    /// Nanoseconds from the start of the recording to the message's
+    /// arrival, so a replay times tool calls as the run did.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub offset_ns: Option<u64>,
+    pub message: Message,
+}
+
+impl From<Message> for RecordedMessage {
+    fn from(message: Message) -> Self {
+        Self {
+            offset_ns: None,
+            message,
+        }
+    }
+}
+
+// ─── Transcripts ──────────────────────────────────────────────────────────
+
+/// How often a following [`TranscriptTail`] checks for new lines.
+const TAIL_POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+/// Appends events to a run transcript, one JSON object per line.
+pub struct TranscriptWriter {
+    file: File,
+}
+
+impl TranscriptWriter {
+    /// Append one event. The line is written with a single `write_all` so a
+    /// concurrent reader never sees half of it followed by another event.
+    pub fn append(&mut self, event: &serde_json::Value) -> Result<()> {
+        let mut line = serde_json::to_vec(event).map_err(|e| {
+            ClaudeAgentError::Process(format!("failed to serialize transcript event: {e}"))
+        })?;
+        line.push(b'\n');
+        self.file.write_all(&line).map_err(ClaudeAgentError::Io)
+    }
+}
+
+/// Iterator over the events of a transcript; see [`SessionStore::tail`].
+pub struct TranscriptTail {
+    reader: BufReader<File>,
+    /// A line whose newline has not been written yet.
+    pending: String,
+    follow: bool,
+    finished: bool,
+    poll_interval: Duration,
+}
+
+impl Iterator for TranscriptTail {
+    type Item = Result<serde_json::Value>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if self.finished {
+                return None;
+            }
+            match self.reader.read_line(&mut self.pending) {
+                Err(e) => return Some(Err(ClaudeAgentError::Io(e))),
+                Ok(_) if self.pending.ends_with('\n') => {
+                    let line = std::mem::take(&mut self.pending);
+                    if line.trim().is_empty() {
+                        continue;
+                    }
+                    let event: serde_json::Value = match serde_json::from_str(&line) {
+                        Ok(v) => v,
+                        Err(source) => {
+                            return Some(Err(ClaudeAgentError::Parse {
+                                line: line.trim().to_owned(),
+                                source,
+                            }))
+                        }
+                    };
+                    if matches!(event["type"].as_str(), Some("result" | "error")) {
+                        self.finished = true;
+                    }
+                    return Some(Ok(event));
+                }
+                // End of file, possibly mid-line: wait for the writer.
+                Ok(_) if self.follow => std::thread::sleep(self.poll_interval),
+                Ok(_) => return None,
+            }
+        }
+    }
 }
 
 // ─── Tests ────────────────────────────────────────────────────────────────
@@ -142,4 +397,95 @@ mod tests {
         assert_eq!(store.load("feat-a"), None);
         assert_eq!(store.load("feat-b"), Some("bbb".into()));
     }
+
+    fn event(kind: &str, n: u32) -> serde_json::Value {
+        serde_json::json!({ "type": kind, "n": n })
+    }
+
+    #[test]
+    fn tail_without_follow_stops_at_end_of_file() {
+        let (store, _dir) = store();
+        let mut writer = store.transcript_writer("run-1").unwrap();
+        writer.append(&event("assistant", 1)).unwrap();
+        writer.append(&event("user", 2)).unwrap();
+
+        let events: Vec<_> = store
+            .tail("run-1", false)
+            .unwrap()
+            .map(|e| e.unwrap()["n"].as_u64().unwrap())
+            .collect();
+        assert_eq!(events, vec![1, 2]);
+    }
+
+    #[test]
+    fn follow_delivers_appended_events_in_order_until_result() {
+        let (store, _dir) = store();
+        let mut writer = store.transcript_writer("run-1").unwrap();
+        writer.append(&event("init", 0)).unwrap();
+        let mut tail = store.tail("run-1", true).unwrap();
+        tail.poll_interval = Duration::from_millis(10);
+
+        let appender = std::thread::spawn(move || {
+            for n in 1..=5 {
+                std::thread::sleep(Duration::from_millis(30));
+                writer.append(&event("assistant", n)).unwrap();
+            }
+            writer.append(&event("result", 6)).unwrap();
+            // Anything after the terminal event is not part of the run.
+            writer.append(&event("assistant", 7)).unwrap();
+        });
+
+        let seen: Vec<u64> = tail.map(|e| e.unwrap()["n"].as_u64().unwrap()).collect();
+        appender.join().unwrap();
+        assert_eq!(seen, vec![0, 1, 2, 3, 4, 5, 6]);
+    }
+
+    #[test]
+    fn follow_waits_for_the_rest_of_a_partial_line() {
+        let (store, _dir) = store();
+        std::fs::create_dir_all(&store.sessions_dir).unwrap();
+        let path = store.transcript_path("run-1");
+        std::fs::write(&path, r#"{"type":"res"#).unwrap();
+        let mut tail = store.tail("run-1", true).unwrap();
+        tail.poll_interval = Duration::from_millis(10);
+
+        let finisher = std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(50));
+            let mut f = OpenOptions::new().append(true).open(path).unwrap();
+            f.write_all(b"ult\"}\n").unwrap();
+        });
+
+        let first = tail.next().unwrap().unwrap();
+        finisher.join().unwrap();
+        assert_eq!(first["type"], "result");
+        assert!(tail.next().is_none());
+    }
+
+    #[test]
+    fn checkpoints_accumulate_and_latest_is_the_last_recorded() {
+        let (store, _dir) = store();
+        assert!(store.checkpoints("run-1").unwrap().is_empty());
+        assert_eq!(store.latest_checkpoint("run-1").unwrap(), None);
+
+        store.checkpoint("run-1", "sess-a", Some("u-3"), 3).unwrap();
+        store.checkpoint("run-1", "sess-b", None, 7).unwrap();
+        store.checkpoint("run-2", "sess-c", None, 1).unwrap();
+
+        let turns: Vec<(String, u32)> = store
+            .checkpoints("run-1")
+            .unwrap()
+            .into_iter()
+            .map(|c| (c.session_id, c.turn))
+            .collect();
+        assert_eq!(turns, vec![("sess-a".into(), 3), ("sess-b".into(), 7)]);
+        let latest = store.latest_checkpoint("run-1").unwrap().unwrap();
+        assert_eq!((latest.session_id.as_str(), latest.turn), ("sess-b", 7));
+    }
+
+    #[test]
+    fn tail_of_missing_transcript_is_an_error() {
+        let (store, _dir) = store();
+        assert!(store.tail("nope", false).is_err());
+        store.clear_transcript("nope").unwrap();
+    }
 }
@@ -1,14 +1,23 @@
+use std::future::Future;
 use std::pin::Pin;
-use std::task::{Context, Poll};
+use std::sync::{Arc, OnceLock};
+use std::task::{ready, Context, Poll};
+use std::time::Duration;
 
 use futures::Stream;
 use tokio::sync::mpsc;
+use tracing::Instrument;
 
 use crate::error::AgentError;
 use crate::process::ClaudeProcess;
 use crate::provider::AgentProvider;
-use crate::types::{AgentEvent, Message, QueryOptions};
-use crate::Result;
+use crate::transport::{MockTransport, Transport};
+use crate::types::{
+    AgentEvent, AssistantDeltaMessage, ContentBlock, ControlRequest, ControlRequestMessage,
+    Message, PermissionDecision, PermissionMode, QueryOptions, SystemInit, SystemMessage,
+    SystemPayload,
+};
+use crate::{ClaudeAgentError, Result};
 
 // ─── QueryStream (Claude-specific, backward-compatible) ──────────────────
 
@@ -19,6 +28,20 @@ use crate::Result;
 /// the process exits. Dropping `QueryStream` closes the receiver, which
 /// causes the background task to exit on the next send attempt.
 ///
+/// # Ordering
+///
+/// Items arrive strictly in the order the CLI emitted them, with nothing
+/// skipped: a single task reads stdout and sends every message, and any
+/// error (a parse failure, a non-zero exit) through the same channel after
+/// the messages read before it. Reading a line is cancel-safe, so answering
+/// a permission prompt or forwarding user input mid-line loses no output.
+/// Each item is stamped with a sequence number when it is sent — `0` for the
+/// first, then `+1` per item — which [`QueryStream::sequenced`] exposes so
+/// consumers can check the guarantee.
+///
+/// Once the stream is exhausted, [`QueryStream::end_reason`] says whether the
+/// CLI finished, ran out of input, or crashed.
+///
 /// ```rust,ignore
 /// use claude_agent::{query, Message, QueryOptions};
 /// use futures::StreamExt;
@@ -31,31 +54,250 @@ use crate::Result;
 /// }
 /// ```
 pub struct QueryStream {
-    rx: mpsc::Receiver<Result<Message>>,
+    rx: mpsc::Receiver<Sequenced<Result<Message>>>,
+    /// Sequence number of the last item yielded.
+    last_seq: Option<u64>,
+    /// The first `system/init` payload seen by `poll_next`.
+    init: Option<SystemInit>,
+    /// Caller answers to permission prompts, written to the child's stdin.
+    /// `None` when no process sits directly behind the stream.
+    answers: Option<mpsc::Sender<serde_json::Value>>,
+    /// Set by the sending side before it lets go of the channel.
+    end: Arc<OnceLock<StreamEnd>>,
+    /// Whether the channel has yielded `None`.
+    exhausted: bool,
+}
+
+/// How the process behind a [`QueryStream`] ended; see
+/// [`QueryStream::end_reason`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StreamEnd {
+    /// The CLI finished the turn: it sent its terminal `result` message, or
+    /// closed stdout and exited cleanly while still accepting input.
+    Completed,
+    /// The CLI exited cleanly without a `result` after its stdin was closed
+    /// (or stopped being read) — the input ran out before the turn finished.
+    StdinClosed,
+    /// The CLI exited with a non-zero status; `code` is `None` when a signal
+    /// killed it.
+    ProcessCrashed { code: Option<i32> },
+}
+
+/// A stream item with its position in the stream; see
+/// [`QueryStream::sequenced`].
+#[derive(Debug)]
+pub struct Sequenced<T> {
+    pub seq: u64,
+    pub item: T,
+}
+
+/// Sending half of a [`QueryStream`]'s channel. Stamps every item with the
+/// next sequence number, so numbering follows send order.
+pub(crate) struct StreamSender {
+    tx: mpsc::Sender<Sequenced<Result<Message>>>,
+    next_seq: u64,
+    end: Arc<OnceLock<StreamEnd>>,
+}
+
+impl StreamSender {
+    /// Send `item`; fails once the stream has been dropped.
+    pub(crate) async fn send(&mut self, item: Result<Message>) -> std::result::Result<(), ()> {
+        let seq = self.next_seq;
+        self.tx
+            .send(Sequenced { seq, item })
+            .await
+            .map_err(|_| ())?;
+        self.next_seq += 1;
+        Ok(())
+    }
+
+    /// [`StreamSender::send`] without waiting; fails if the channel is full.
+    fn try_send(&mut self, item: Result<Message>) -> std::result::Result<(), ()> {
+        let seq = self.next_seq;
+        self.tx.try_send(Sequenced { seq, item }).map_err(|_| ())?;
+        self.next_seq += 1;
+        Ok(())
+    }
+
+    /// Resolves once the stream has been dropped.
+    async fn closed(&self) {
+        self.tx.closed().await
+    }
+
+    fn is_closed(&self) -> bool {
+        self.tx.is_closed()
+    }
+
+    /// Record why the stream ended; the first call wins.
+    pub(crate) fn set_end(&self, end: StreamEnd) {
+        let _ = self.end.set(end);
+    }
+}
+
+/// A [`QueryStream`] fed by the returned sender. Used by `runner` to splice
+/// fallback attempts together and by tests to inject pre-built message
+/// sequences.
+pub(crate) fn stream_channel(capacity: usize) -> (StreamSender, QueryStream) {
+    let (tx, rx) = mpsc::channel(capacity);
+    let end = Arc::new(OnceLock::new());
+    let stream = QueryStream {
+        rx,
+        last_seq: None,
+        init: None,
+        answers: None,
+        end: Arc::clone(&end),
+        exhausted: false,
+    };
+    (
+        StreamSender {
+            tx,
+            next_seq: 0,
+            end,
+        },
+        stream,
+    )
+}
+
+/// [`stream_channel`] for a stream spliced from other streams: answers to
+/// permission prompts given on the returned stream arrive on the receiver,
+/// for the splicer to pass to whichever inner stream is current.
+pub(crate) fn spliced_channel(
+    capacity: usize,
+) -> (StreamSender, mpsc::Receiver<serde_json::Value>, QueryStream) {
+    let (tx, mut stream) = stream_channel(capacity);
+    let (answers, answer_rx) = mpsc::channel(8);
+    stream.answers = Some(answers);
+    (tx, answer_rx, stream)
+}
+
+/// Sending half of [`user_input_channel`]: hands text to a running session.
+#[derive(Debug, Clone)]
+pub struct UserInputSender(mpsc::UnboundedSender<String>);
+
+impl UserInputSender {
+    /// Queue `text` as a user message. Fails once the session has ended.
+    pub fn send(&self, text: impl Into<String>) -> Result<()> {
+        self.0
+            .send(text.into())
+            .map_err(|_| ClaudeAgentError::Process("agent session has ended".into()))
+    }
 }
 
+/// Receiving half of [`user_input_channel`], set as
+/// [`QueryOptions::user_input`]. Clones share one queue, so fallback
+/// attempts built from the same options drain the same messages.
+#[derive(Debug, Clone)]
+pub struct UserInput(std::sync::Arc<tokio::sync::Mutex<mpsc::UnboundedReceiver<String>>>);
+
+impl UserInput {
+    /// The next queued message; `None` once every sender is gone.
+    pub async fn recv(&self) -> Option<String> {
+        self.0.lock().await.recv().await
+    }
+}
+
+/// A channel for injecting user messages into a live session.
+pub fn user_input_channel() -> (UserInputSender, UserInput) {
+    let (tx, rx) = mpsc::unbounded_channel();
+    (
+        UserInputSender(tx),
+        UserInput(std::sync::Arc::new(tokio::sync::Mutex::new(rx))),
+    )
+}
+
+/// Wait for the next injected message; pends forever without a channel.
+async fn next_user_input(input: Option<&UserInput>) -> Option<String> {
+    match input {
+        Some(input) => input.recv().await,
+        None => std::future::pending().await,
+    }
+}
+
+/// How long the `claude` child of a dropped or finished stream gets to exit
+/// after SIGTERM before it is killed.
+pub const TERMINATE_GRACE: Duration = Duration::from_secs(2);
+
 impl QueryStream {
     pub(crate) fn new(prompt: String, opts: QueryOptions) -> Self {
-        let (tx, rx) = mpsc::channel(32);
+        if let Transport::Mock(mock) = &opts.transport {
+            return Self::replay(&prompt, mock);
+        }
+        let spawn_opts = opts.clone();
+        Self::pump(
+            async move { ClaudeProcess::spawn(&prompt, &spawn_opts).await },
+            opts,
+        )
+    }
+
+    /// Start the background task that owns the child process and forwards
+    /// its messages. Dropping the stream closes the channel; the task sees
+    /// that immediately (not only on its next send) and terminates the child.
+    pub(crate) fn pump<F>(spawn: F, opts: QueryOptions) -> Self
+    where
+        F: Future<Output = Result<ClaudeProcess>> + Send + 'static,
+    {
+        let (mut tx, stream) = stream_channel(32);
+        let (answers, mut answer_rx) = mpsc::channel::<serde_json::Value>(8);
 
+        // Created here rather than in the task so it nests under the caller's
+        // span (e.g. `claude.run`).
+        let spawn_span = tracing::info_span!(
+            "claude.spawn",
+            model = opts.model.as_deref().unwrap_or("default"),
+            pid = tracing::field::Empty,
+        );
         tokio::spawn(async move {
-            let mut process = match ClaudeProcess::spawn(&prompt, &opts).await {
+            let mut process = match spawn.instrument(spawn_span.clone()).await {
                 Ok(p) => p,
                 Err(e) => {
                     let _ = tx.send(Err(e)).await;
                     return;
                 }
             };
+            if let Some(pid) = process.id() {
+                spawn_span.record("pid", pid);
+            }
 
             let mut got_result = false;
+            let mut runaway = false;
             loop {
-                match process.next_message().await {
+                let next = tokio::select! {
+                    next = process.next_message() => next,
+                    Some(answer) = answer_rx.recv() => {
+                        if let Err(e) = process.send_message(&answer).await {
+                            tracing::warn!(error = %e, "failed to send permission answer");
+                        }
+                        continue;
+                    }
+                    Some(text) = next_user_input(opts.user_input.as_ref()) => {
+                        if let Err(e) = process.send_message(&user_message(&text)).await {
+                            tracing::warn!(error = %e, "failed to send user input");
+                        }
+                        continue;
+                    }
+                    () = tx.closed() => break, // Receiver dropped
+                };
+                match next {
                     Err(e) => {
+                        // A child still writing an oversized line won't exit
+                        // on its own; skip the exit wait and kill it below.
+                        runaway = matches!(e, ClaudeAgentError::LineTooLong { .. });
                         let _ = tx.send(Err(e)).await;
                         break;
                     }
                     Ok(None) => break, // EOF — process exited
                     Ok(Some(msg)) => {
+                        if let Some(answer) = policy_answer(&msg, &opts) {
+                            if let Err(e) = process.send_message(&answer).await {
+                                let _ = tx.send(Err(e)).await;
+                                break;
+                            }
+                            continue;
+                        }
+                        let Some(msg) = shape_partial(msg, &opts) else {
+                            continue;
+                        };
+                        warn_on_denied_tools(&msg, &opts);
                         let is_terminal = matches!(msg, Message::Result(_));
                         if is_terminal {
                             got_result = true;
@@ -72,24 +314,221 @@ impl QueryStream {
 
             // If the process exited without sending a Result message, check
             // for a non-zero exit code and surface stderr (matches TS SDK's
-            // `getProcessExitError` pattern).
-            if !got_result {
-                if let Some(exit_err) = process.wait_exit_error().await {
+            // `getProcessExitError` pattern). Nobody is listening once the
+            // receiver is gone, and the child may never exit on its own.
+            if got_result {
+                tx.set_end(StreamEnd::Completed);
+            } else if !runaway && !tx.is_closed() {
+                let exit_err = process.wait_exit_error().await;
+                if let Some(end) = unfinished_end(&process) {
+                    tx.set_end(end);
+                }
+                if let Some(exit_err) = exit_err {
                     let _ = tx.send(Err(exit_err)).await;
                 }
             }
 
-            process.kill().await;
+            process.terminate(TERMINATE_GRACE).await;
         });
 
-        QueryStream { rx }
+        QueryStream {
+            answers: Some(answers),
+            ..stream
+        }
     }
 
-    /// Test-only constructor: wrap a raw mpsc receiver as a `QueryStream`.
-    /// Used by `runner` tests to inject pre-built message sequences.
-    #[cfg(test)]
-    pub(crate) fn from_channel(rx: mpsc::Receiver<Result<Message>>) -> Self {
-        Self { rx }
+    /// Play `mock`'s script for `prompt`; nothing is spawned.
+    fn replay(prompt: &str, mock: &MockTransport) -> Self {
+        let script = mock.script_for(prompt);
+        let items: Vec<Result<Message>> = match script {
+            Some(messages) => messages.iter().cloned().map(Ok).collect(),
+            None => vec![Err(ClaudeAgentError::Process(format!(
+                "mock transport has no script for prompt: {prompt}"
+            )))],
+        };
+        let (mut tx, stream) = stream_channel(items.len().max(1));
+        if script.is_some() {
+            tx.set_end(StreamEnd::Completed);
+        }
+        for item in items {
+            let _ = tx.try_send(item);
+        }
+        stream
+    }
+
+    /// Yield every item together with its sequence number.
+    pub fn sequenced(self) -> SequencedStream {
+        SequencedStream(self)
+    }
+
+    /// The session's `system/init` payload — resolved model, tools, cwd,
+    /// permission mode — once the stream has yielded it. `None` before then.
+    pub fn init_info(&self) -> Option<&SystemInit> {
+        self.init.as_ref()
+    }
+
+    /// Why the process ended, once the stream has yielded `None` — the cue
+    /// for deciding whether to retry. `None` before that, and when the end
+    /// was never observed: the process failed to spawn or was killed over a
+    /// runaway output line.
+    pub fn end_reason(&self) -> Option<StreamEnd> {
+        if !self.exhausted {
+            return None;
+        }
+        self.end.get().copied()
+    }
+
+    /// Answer a permission prompt the stream yielded as a
+    /// [`Message::ControlRequest`] — one a [`PermissionPolicy`] left to the
+    /// caller with [`PermissionDecision::Ask`]. Fails on streams with no
+    /// process behind them, such as injected test streams.
+    ///
+    /// [`PermissionPolicy`]: crate::PermissionPolicy
+    pub async fn answer_permission(
+        &self,
+        request: &ControlRequestMessage,
+        allow: bool,
+    ) -> Result<()> {
+        let ControlRequest::CanUseTool { input, .. } = &request.request else {
+            return Err(ClaudeAgentError::Process(
+                "control request is not a permission prompt".into(),
+            ));
+        };
+        let answer = permission_response(&request.request_id, input, allow, "denied by the caller");
+        self.send_answer(answer).await
+    }
+
+    /// Write an already-built `control_response` to the process.
+    pub(crate) async fn send_answer(&self, answer: serde_json::Value) -> Result<()> {
+        let answers = self.answers.as_ref().ok_or_else(|| {
+            ClaudeAgentError::Process("stream cannot answer permission prompts".into())
+        })?;
+        answers
+            .send(answer)
+            .await
+            .map_err(|_| ClaudeAgentError::Process("agent process has exited".into()))
+    }
+}
+
+/// Why a process that never sent a `result` stopped; `None` until it has
+/// been reaped.
+fn unfinished_end(process: &ClaudeProcess) -> Option<StreamEnd> {
+    let status = process.exit_status()?;
+    Some(if !status.success() {
+        StreamEnd::ProcessCrashed {
+            code: status.code(),
+        }
+    } else if process.stdin_closed() {
+        StreamEnd::StdinClosed
+    } else {
+        StreamEnd::Completed
+    })
+}
+
+/// A stream-json user message carrying `text`.
+pub(crate) fn user_message(text: &str) -> serde_json::Value {
+    serde_json::json!({
+        "type": "user",
+        "message": {
+            "role": "user",
+            "content": [{"type": "text", "text": text}]
+        }
+    })
+}
+
+/// Settle a `can_use_tool` request from the policy in `opts`. Returns the
+/// `control_response` to write back, or `None` when the message is not a
+/// permission prompt or the policy says [`PermissionDecision::Ask`] — those
+/// go to the caller.
+pub(crate) fn policy_answer(msg: &Message, opts: &QueryOptions) -> Option<serde_json::Value> {
+    let PermissionMode::Policy(policy) = &opts.permission_mode else {
+        return None;
+    };
+    let Message::ControlRequest(request) = msg else {
+        return None;
+    };
+    let ControlRequest::CanUseTool {
+        tool_name, input, ..
+    } = &request.request
+    else {
+        return None;
+    };
+    match policy.decide(tool_name) {
+        PermissionDecision::Allow => {
+            Some(permission_response(&request.request_id, input, true, ""))
+        }
+        PermissionDecision::Deny => Some(permission_response(
+            &request.request_id,
+            input,
+            false,
+            &format!("{tool_name} is denied by the permission policy"),
+        )),
+        PermissionDecision::Ask => None,
+    }
+}
+
+/// The `control_response` answering a `can_use_tool` request. Allowing echoes
+/// the tool input back unchanged; denying carries `reason` to the model.
+fn permission_response(
+    request_id: &str,
+    input: &serde_json::Value,
+    allow: bool,
+    reason: &str,
+) -> serde_json::Value {
+    let decision = if allow {
+        serde_json::json!({ "behavior": "allow", "updatedInput": input })
+    } else {
+        serde_json::json!({ "behavior": "deny", "message": reason })
+    };
+    serde_json::json!({
+        "type": "control_response",
+        "response": {
+            "subtype": "success",
+            "request_id": request_id,
+            "response": decision,
+        }
+    })
+}
+
+/// Apply `stream_deltas` / `include_partial_messages` to a message read from
+/// the CLI. Text deltas become `Message::AssistantDelta` when deltas are
+/// requested; other `stream_event`s survive only if partial messages were
+/// asked for explicitly. Returns `None` when the message should be dropped.
+pub(crate) fn shape_partial(msg: Message, opts: &QueryOptions) -> Option<Message> {
+    let Message::StreamEvent(event) = msg else {
+        return Some(msg);
+    };
+    if opts.stream_deltas {
+        if let Some(text) = event.text_delta() {
+            return Some(Message::AssistantDelta(AssistantDeltaMessage {
+                text: text.to_string(),
+                parent_tool_use_id: event.parent_tool_use_id.clone(),
+                session_id: event.session_id.clone(),
+            }));
+        }
+    }
+    opts.include_partial_messages
+        .then_some(Message::StreamEvent(event))
+}
+
+/// Log a warning for every `tool_use` block naming a tool in
+/// `opts.disallowed_tools`. The CLI should never let that happen, so a hit
+/// means the deny list is not being enforced.
+pub(crate) fn warn_on_denied_tools(msg: &Message, opts: &QueryOptions) {
+    let Message::Assistant(asst) = msg else {
+        return;
+    };
+    for block in &asst.message.content {
+        if let ContentBlock::ToolUse { id, name, .. } = block {
+            if opts.disallowed_tools.iter().any(|t| t == name) {
+                tracing::warn!(
+                    tool = %name,
+                    tool_use_id = %id,
+                    session_id = %asst.session_id,
+                    "Claude called a disallowed tool"
+                );
+            }
+        }
     }
 }
 
@@ -97,7 +536,55 @@ impl Stream for QueryStream {
     type Item = Result<Message>;
 
     fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
-        self.rx.poll_recv(cx)
+        let Some(Sequenced { seq, item }) = ready!(self.rx.poll_recv(cx)) else {
+            self.exhausted = true;
+            return Poll::Ready(None);
+        };
+        debug_assert_eq!(
+            seq,
+            self.last_seq.map_or(0, |last| last + 1),
+            "stream items delivered out of order"
+        );
+        self.last_seq = Some(seq);
+        if let Ok(Message::System(SystemMessage {
+            payload: SystemPayload::Init(init),
+            ..
+        })) = &item
+        {
+            if self.init.is_none() {
+                self.init = Some(init.clone());
+            }
+        }
+        Poll::Ready(Some(item))
+    }
+}
+
+/// A [`QueryStream`] that yields each item with its sequence number; see
+/// [`QueryStream::sequenced`].
+pub struct SequencedStream(QueryStream);
+
+impl SequencedStream {
+    /// See [`QueryStream::init_info`].
+    pub fn init_info(&self) -> Option<&SystemInit> {
+        self.0.init_info()
+    }
+
+    /// See [`QueryStream::end_reason`].
+    pub fn end_reason(&self) -> Option<StreamEnd> {
+        self.0.end_reason()
+    }
+}
+
+impl Stream for SequencedStream {
+    type Item = Sequenced<Result<Message>>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let inner = Pin::new(&mut self.0);
+        let item = ready!(inner.poll_next(cx));
+        Poll::Ready(item.map(|item| Sequenced {
+            seq: self.0.last_seq.expect("set when an item is yielded"),
+            item,
+        }))
     }
 }
 
@@ -172,7 +659,7 @@ mod tests {
         // Keep the file alive for the duration of the test
         std::mem::forget(f);
 
-        let (tx, rx) = mpsc::channel(32);
+        let (mut tx, stream) = stream_channel(32);
 
         tokio::spawn(async move {
             let mut cmd = Command::new("cat");
@@ -197,10 +684,10 @@ mod tests {
                     }
                 }
             }
-            process.kill().await;
+            process.terminate(TERMINATE_GRACE).await;
         });
 
-        QueryStream { rx }
+        stream
     }
 
     const INIT_LINE: &str = r#"{"type":"system","subtype":"init","session_id":"s1","model":"m","tools":[],"mcp_servers":[],"permission_mode":"default","claude_code_version":"0.0.0","cwd":"/tmp"}"#;
@@ -214,6 +701,19 @@ mod tests {
         assert!(messages.iter().all(|m| m.is_ok()));
     }
 
+    #[tokio::test]
+    async fn init_info_is_captured_once_yielded() {
+        let mut stream = mock_stream(&[INIT_LINE, RESULT_LINE]);
+        assert!(stream.init_info().is_none());
+        stream.next().await.unwrap().unwrap();
+        let init = stream.init_info().expect("init captured");
+        assert_eq!(init.model, "m");
+        assert_eq!(init.cwd, "/tmp");
+        // Later messages leave it in place.
+        stream.next().await.unwrap().unwrap();
+        assert_eq!(stream.init_info().unwrap().model, "m");
+    }
+
     #[tokio::test]
     async fn stream_terminates_after_result() {
         // Add an extra line after result — stream must not emit it
@@ -259,4 +759,637 @@ mod tests {
         // Blank lines are skipped; we still get exactly 2 real messages
         assert_eq!(messages.len(), 2);
     }
+
+    fn tool_opts() -> QueryOptions {
+        QueryOptions {
+            allowed_tools: vec!["Read".into(), "Grep".into()],
+            disallowed_tools: vec!["Bash".into(), "Write".into()],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn tool_lists_are_comma_joined_in_argv() {
+        let cmd = crate::process::build_command(&tool_opts()).unwrap();
+        let args: Vec<String> = cmd
+            .as_std()
+            .get_args()
+            .map(|a| a.to_string_lossy().into_owned())
+            .collect();
+        let value_of = |flag: &str| {
+            let i = args.iter().position(|a| a == flag).unwrap();
+            args[i + 1].clone()
+        };
+        assert_eq!(value_of("--allowedTools"), "Read,Grep");
+        assert_eq!(value_of("--disallowedTools"), "Bash,Write");
+    }
+
+    #[test]
+    fn resume_at_a_message_forks_the_session() {
+        let args_of = |opts: &QueryOptions| -> Vec<String> {
+            crate::process::build_command(opts)
+                .unwrap()
+                .as_std()
+                .get_args()
+                .map(|a| a.to_string_lossy().into_owned())
+                .collect()
+        };
+        let opts = QueryOptions {
+            resume: Some("s1".into()),
+            resume_session_at: Some("u-7".into()),
+            fork_session: true,
+            ..Default::default()
+        };
+        let args = args_of(&opts);
+        let at = args
+            .iter()
+            .position(|a| a == "--resume-session-at")
+            .unwrap();
+        assert_eq!(args[at + 1], "u-7");
+        assert!(args.iter().any(|a| a == "--fork-session"));
+
+        // Neither applies without a session to resume.
+        let args = args_of(&QueryOptions {
+            resume: None,
+            ..opts
+        });
+        assert!(!args
+            .iter()
+            .any(|a| a == "--resume-session-at" || a == "--fork-session"));
+    }
+
+    #[test]
+    fn each_additional_dir_gets_its_own_add_dir_flag() {
+        let root = tempfile::TempDir::new().unwrap();
+        std::fs::create_dir(root.path().join("sibling")).unwrap();
+        let other = tempfile::TempDir::new().unwrap();
+        let opts = QueryOptions {
+            cwd: Some(root.path().to_path_buf()),
+            additional_dirs: vec!["sibling".into(), other.path().to_path_buf()],
+            ..Default::default()
+        };
+        let cmd = crate::process::build_command(&opts).unwrap();
+        let args: Vec<String> = cmd
+            .as_std()
+            .get_args()
+            .map(|a| a.to_string_lossy().into_owned())
+            .collect();
+        let dirs: Vec<&str> = args
+            .windows(2)
+            .filter(|w| w[0] == "--add-dir")
+            .map(|w| w[1].as_str())
+            .collect();
+        let canonical = |p: std::path::PathBuf| {
+            std::fs::canonicalize(p)
+                .unwrap()
+                .to_string_lossy()
+                .into_owned()
+        };
+        assert_eq!(
+            dirs,
+            vec![
+                canonical(root.path().join("sibling")),
+                canonical(other.path().to_path_buf()),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn missing_additional_dir_fails_before_spawn() {
+        let opts = QueryOptions {
+            additional_dirs: vec!["/definitely/not/a/dir".into()],
+            // Spawning this would fail with an I/O error instead.
+            path_to_executable: Some("/definitely/not/claude".into()),
+            ..Default::default()
+        };
+        let err = crate::process::ClaudeProcess::spawn("hi", &opts)
+            .await
+            .err()
+            .expect("spawn must fail");
+        assert!(
+            matches!(err, ClaudeAgentError::InvalidOptions(ref msg) if msg.contains("/definitely/not/a/dir")),
+            "{err}"
+        );
+        // The command builder refuses it too rather than dropping the flag.
+        assert!(matches!(
+            crate::process::build_command(&opts),
+            Err(ClaudeAgentError::InvalidOptions(_))
+        ));
+    }
+
+    #[test]
+    fn overlapping_tool_lists_are_rejected() {
+        let mut opts = tool_opts();
+        opts.validate().unwrap();
+        opts.allowed_tools.push("Bash".into());
+        let err = opts.validate().unwrap_err().to_string();
+        assert!(err.contains("Bash"), "{err}");
+    }
+
+    fn policy_opts() -> QueryOptions {
+        QueryOptions {
+            permission_mode: PermissionMode::Policy(
+                crate::PermissionPolicy::default()
+                    .allow("Read*")
+                    .deny("Bash"),
+            ),
+            ..Default::default()
+        }
+    }
+
+    fn can_use_tool_line(request_id: &str, tool: &str) -> String {
+        format!(
+            r#"{{"type":"control_request","request_id":"{request_id}","request":{{"subtype":"can_use_tool","tool_name":"{tool}","input":{{"path":"x"}}}}}}"#
+        )
+    }
+
+    #[test]
+    fn policy_allows_read_star_denies_bash_and_asks_otherwise() {
+        let opts = policy_opts();
+        let answer = |tool: &str| {
+            let msg: Message = serde_json::from_str(&can_use_tool_line("r1", tool)).unwrap();
+            policy_answer(&msg, &opts)
+        };
+
+        for tool in ["Read", "ReadMcpResource"] {
+            let a = answer(tool).expect("auto-answered");
+            assert_eq!(a["type"], "control_response");
+            assert_eq!(a["response"]["request_id"], "r1");
+            assert_eq!(a["response"]["response"]["behavior"], "allow");
+            assert_eq!(a["response"]["response"]["updatedInput"]["path"], "x");
+        }
+        let denied = answer("Bash").expect("auto-answered");
+        assert_eq!(denied["response"]["response"]["behavior"], "deny");
+        assert!(denied["response"]["response"]["message"]
+            .as_str()
+            .unwrap()
+            .contains("Bash"));
+        assert!(answer("Write").is_none());
+        assert!(answer("Edit").is_none());
+    }
+
+    #[test]
+    fn policy_ignores_messages_outside_policy_mode() {
+        let msg: Message = serde_json::from_str(&can_use_tool_line("r1", "Read")).unwrap();
+        assert!(policy_answer(&msg, &QueryOptions::default()).is_none());
+        let init: Message = serde_json::from_str(INIT_LINE).unwrap();
+        assert!(policy_answer(&init, &policy_opts()).is_none());
+    }
+
+    #[test]
+    fn policy_mode_routes_prompts_over_stdio() {
+        let cmd = crate::process::build_command(&policy_opts()).unwrap();
+        let args: Vec<String> = cmd
+            .as_std()
+            .get_args()
+            .map(|a| a.to_string_lossy().into_owned())
+            .collect();
+        let i = args
+            .iter()
+            .position(|a| a == "--permission-prompt-tool")
+            .unwrap();
+        assert_eq!(args[i + 1], "stdio");
+        assert!(!args.iter().any(|a| a == "--permission-mode"));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn policy_answers_on_stdin_and_surfaces_ask_to_caller() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let write = |name: &str, lines: &[&str]| {
+            std::fs::write(dir.path().join(name), lines.join("\n") + "\n").unwrap();
+        };
+        write(
+            "auto",
+            &[
+                &can_use_tool_line("r-read", "Read"),
+                &can_use_tool_line("r-bash", "Bash"),
+            ],
+        );
+        write("ask", &[&can_use_tool_line("r-write", "Write")]);
+        write("result", &[RESULT_LINE]);
+        // Replays the CLI side: two prompts the policy settles, then one it
+        // cannot, recording every answer that comes back on stdin.
+        let script = format!(
+            "cd {} && cat auto && read a && read b && cat ask && read c \
+             && printf '%s\\n%s\\n%s\\n' \"$a\" \"$b\" \"$c\" > answers && cat result",
+            dir.path().display()
+        );
+        let mut stream = QueryStream::pump(
+            async move {
+                let mut cmd = Command::new("sh");
+                cmd.arg("-c").arg(script);
+                ClaudeProcess::spawn_command(cmd)
+            },
+            policy_opts(),
+        );
+
+        let asked = match stream.next().await.unwrap().unwrap() {
+            Message::ControlRequest(req) => req,
+            other => panic!("expected the Write prompt, got {other:?}"),
+        };
+        assert_eq!(asked.request_id, "r-write");
+        stream.answer_permission(&asked, true).await.unwrap();
+        let last = stream.next().await.unwrap().unwrap();
+        assert!(matches!(last, Message::Result(_)));
+
+        let answers: Vec<serde_json::Value> = std::fs::read_to_string(dir.path().join("answers"))
+            .unwrap()
+            .lines()
+            .map(|l| serde_json::from_str(l).unwrap())
+            .collect();
+        let behavior = |i: usize| answers[i]["response"]["response"]["behavior"].clone();
+        assert_eq!(answers[0]["response"]["request_id"], "r-read");
+        assert_eq!(behavior(0), "allow");
+        assert_eq!(answers[1]["response"]["request_id"], "r-bash");
+        assert_eq!(behavior(1), "deny");
+        assert_eq!(answers[2]["response"]["request_id"], "r-write");
+        assert_eq!(behavior(2), "allow");
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn user_input_reaches_stdin_before_the_next_turn() {
+        let dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(dir.path().join("init"), format!("{INIT_LINE}\n")).unwrap();
+        std::fs::write(dir.path().join("result"), format!("{RESULT_LINE}\n")).unwrap();
+        // The CLI side: one turn, then block on stdin until the next user
+        // message arrives, record it, and finish.
+        let script = format!(
+            "cd {} && cat init && read line && printf '%s\\n' \"$line\" > injected && cat result",
+            dir.path().display()
+        );
+        let (input, user_input) = user_input_channel();
+        let opts = QueryOptions {
+            user_input: Some(user_input),
+            ..Default::default()
+        };
+        let mut stream = QueryStream::pump(
+            async move {
+                let mut cmd = Command::new("sh");
+                cmd.arg("-c").arg(script);
+                ClaudeProcess::spawn_command(cmd)
+            },
+            opts,
+        );
+
+        assert!(matches!(
+            stream.next().await.unwrap().unwrap(),
+            Message::System(_)
+        ));
+        input.send("use the v2 endpoint instead").unwrap();
+        assert!(matches!(
+            stream.next().await.unwrap().unwrap(),
+            Message::Result(_)
+        ));
+
+        let injected: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(dir.path().join("injected")).unwrap())
+                .unwrap();
+        assert_eq!(injected["type"], "user");
+        assert_eq!(
+            injected["message"]["content"][0]["text"],
+            "use the v2 endpoint instead"
+        );
+    }
+
+    #[tokio::test]
+    async fn jittery_consumer_sees_every_message_in_order_with_gap_free_seqs() {
+        const MESSAGES: usize = 3_000;
+        let dir = tempfile::TempDir::new().unwrap();
+        let fixture = dir.path().join("fixture.jsonl");
+        let mut lines = vec![INIT_LINE.to_string()];
+        lines.extend((0..MESSAGES).map(|n| {
+            format!(
+                r#"{{"type":"assistant","message":{{"id":"msg_{n}","role":"assistant","model":"m","content":[{{"type":"text","text":"{n}"}}],"usage":{{"input_tokens":1,"output_tokens":1}}}},"parent_tool_use_id":null,"session_id":"s1"}}"#
+            )
+        }));
+        lines.push(RESULT_LINE.to_string());
+        std::fs::write(&fixture, lines.join("\n") + "\n").unwrap();
+
+        // Injected input makes the reader task's select! drop in-flight
+        // reads while the consumer stalls.
+        let (input, user_input) = user_input_channel();
+        let opts = QueryOptions {
+            user_input: Some(user_input),
+            ..Default::default()
+        };
+        let mut stream = QueryStream::pump(
+            async move {
+                let mut cmd = Command::new("cat");
+                cmd.arg(fixture);
+                ClaudeProcess::spawn_command(cmd)
+            },
+            opts,
+        )
+        .sequenced();
+
+        let mut jitter: u64 = 0x9e37_79b9;
+        let mut seen = Vec::new();
+        while let Some(Sequenced { seq, item }) = stream.next().await {
+            assert_eq!(
+                seq,
+                seen.len() as u64,
+                "gap or reorder at item {}",
+                seen.len()
+            );
+            seen.push(item.unwrap());
+            jitter = jitter
+                .wrapping_mul(6_364_136_223_846_793_005)
+                .wrapping_add(1);
+            match jitter >> 60 {
+                0 => tokio::time::sleep(Duration::from_micros(jitter % 500)).await,
+                1 => tokio::task::yield_now().await,
+                2 => {
+                    let _ = input.send("keep going");
+                }
+                _ => {}
+            }
+        }
+
+        assert_eq!(seen.len(), MESSAGES + 2);
+        assert!(matches!(seen[0], Message::System(_)));
+        for (n, msg) in seen[1..=MESSAGES].iter().enumerate() {
+            let Message::Assistant(asst) = msg else {
+                panic!("expected assistant message {n}, got {msg:?}")
+            };
+            assert_eq!(asst.message.visible_text(), n.to_string());
+        }
+        assert!(matches!(seen.last(), Some(Message::Result(_))));
+    }
+
+    /// Run `script` under `sh -c` as the CLI, closing its stdin right away
+    /// when `close_stdin`, and drain the stream.
+    #[cfg(unix)]
+    async fn drain_script(script: &str, close_stdin: bool) -> (Vec<Result<Message>>, QueryStream) {
+        let script = script.to_string();
+        let mut stream = QueryStream::pump(
+            async move {
+                let mut cmd = Command::new("sh");
+                cmd.arg("-c").arg(script);
+                let mut process = ClaudeProcess::spawn_command(cmd)?;
+                if close_stdin {
+                    process.close_stdin();
+                }
+                Ok(process)
+            },
+            QueryOptions::default(),
+        );
+        let mut items = Vec::new();
+        while let Some(item) = stream.next().await {
+            assert_eq!(stream.end_reason(), None, "set before exhaustion");
+            items.push(item);
+        }
+        (items, stream)
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn end_reason_is_completed_after_a_result() {
+        let (items, stream) =
+            drain_script(&format!("echo '{INIT_LINE}'; echo '{RESULT_LINE}'"), false).await;
+        assert!(matches!(items.last(), Some(Ok(Message::Result(_)))));
+        assert_eq!(stream.end_reason(), Some(StreamEnd::Completed));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn end_reason_is_stdin_closed_when_input_runs_out_before_a_result() {
+        // Reads input until EOF, then exits 0 without finishing the turn.
+        let (items, stream) =
+            drain_script(&format!("echo '{INIT_LINE}'; cat >/dev/null"), true).await;
+        assert_eq!(items.len(), 1);
+        assert!(items[0].is_ok());
+        assert_eq!(stream.end_reason(), Some(StreamEnd::StdinClosed));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn end_reason_carries_the_exit_code_of_a_crash() {
+        let (items, stream) = drain_script(&format!("echo '{INIT_LINE}'; exit 3"), false).await;
+        assert!(matches!(
+            items.last(),
+            Some(Err(ClaudeAgentError::Process(_)))
+        ));
+        assert_eq!(
+            stream.end_reason(),
+            Some(StreamEnd::ProcessCrashed { code: Some(3) })
+        );
+
+        let (_, stream) = drain_script("kill -9 $$", false).await;
+        assert_eq!(
+            stream.end_reason(),
+            Some(StreamEnd::ProcessCrashed { code: None })
+        );
+    }
+
+    #[test]
+    fn user_input_send_fails_once_the_session_is_gone() {
+        let (input, user_input) = user_input_channel();
+        drop(user_input);
+        assert!(input.send("too late").is_err());
+    }
+
+    #[tokio::test]
+    async fn spliced_stream_cannot_answer_permission_prompts() {
+        let (_tx, stream) = stream_channel(1);
+        let msg: Message = serde_json::from_str(&can_use_tool_line("r1", "Write")).unwrap();
+        let Message::ControlRequest(req) = msg else {
+            unreachable!()
+        };
+        assert!(stream.answer_permission(&req, true).await.is_err());
+    }
+
+    /// Captures formatted tracing output for assertions.
+    #[derive(Clone, Default)]
+    struct LogBuffer(std::sync::Arc<std::sync::Mutex<Vec<u8>>>);
+
+    impl Write for LogBuffer {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn denied_tool_use_logs_warning() {
+        let line = r#"{"type":"assistant","message":{"id":"msg_1","role":"assistant","model":"m","content":[{"type":"tool_use","id":"tu_1","name":"Bash","input":{"command":"ls"}},{"type":"tool_use","id":"tu_2","name":"Read","input":{}}],"usage":{"input_tokens":1,"output_tokens":1}},"parent_tool_use_id":null,"session_id":"s1"}"#;
+        let msg: Message = serde_json::from_str(line).unwrap();
+        let buffer = LogBuffer::default();
+        let writer = buffer.clone();
+        let subscriber = tracing_subscriber::fmt()
+            .with_writer(move || writer.clone())
+            .with_ansi(false)
+            .finish();
+
+        tracing::subscriber::with_default(subscriber, || {
+            warn_on_denied_tools(&msg, &tool_opts());
+        });
+
+        let logs = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+        assert_eq!(logs.matches("disallowed tool").count(), 1, "{logs}");
+        assert!(logs.contains("WARN"), "{logs}");
+        assert!(logs.contains("tool=Bash"), "{logs}");
+    }
+
+    #[tokio::test]
+    async fn oversized_line_fails_stream_instead_of_buffering() {
+        // An endless line with no newline: unbounded reading would grow forever.
+        let stream = QueryStream::pump(
+            async move {
+                let mut cmd = Command::new("cat");
+                cmd.arg("/dev/zero");
+                Ok(ClaudeProcess::spawn_command(cmd)?.with_max_line_bytes(64 * 1024))
+            },
+            QueryOptions::default(),
+        );
+
+        let items: Vec<_> = tokio::time::timeout(Duration::from_secs(10), stream.collect())
+            .await
+            .expect("stream did not end");
+        assert_eq!(items.len(), 1);
+        match &items[0] {
+            Err(ClaudeAgentError::LineTooLong { bytes }) => assert!(*bytes > 64 * 1024),
+            other => panic!("expected LineTooLong, got {other:?}"),
+        }
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn dropped_stream_terminates_and_reaps_child() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let pidfile = dir.path().join("pid");
+        // Ignores SIGTERM, so only the SIGKILL escalation can stop it.
+        let script = format!(
+            "trap '' TERM; echo $$ > {}; while :; do sleep 0.1; done",
+            pidfile.display()
+        );
+        let stream = QueryStream::pump(
+            async move {
+                let mut cmd = Command::new("sh");
+                cmd.arg("-c").arg(script);
+                ClaudeProcess::spawn_command(cmd)
+            },
+            QueryOptions::default(),
+        );
+
+        let start = std::time::Instant::now();
+        let pid: libc::pid_t = loop {
+            if let Some(pid) = std::fs::read_to_string(&pidfile)
+                .ok()
+                .and_then(|s| s.trim().parse().ok())
+            {
+                break pid;
+            }
+            assert!(
+                start.elapsed() < Duration::from_secs(5),
+                "child never started"
+            );
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        };
+
+        drop(stream);
+
+        // kill(pid, 0) succeeds for zombies too, so this also proves reaping.
+        let alive = || unsafe { libc::kill(pid, 0) == 0 };
+        let deadline = std::time::Instant::now() + TERMINATE_GRACE + Duration::from_secs(2);
+        while alive() {
+            assert!(
+                std::time::Instant::now() < deadline,
+                "child {pid} still running"
+            );
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+    }
+
+    /// An executable `claude` stand-in reporting `0.2.9` for `--version` and
+    /// running `body` otherwise.
+    #[cfg(unix)]
+    fn old_cli(dir: &tempfile::TempDir, body: &str) -> String {
+        use std::os::unix::fs::PermissionsExt;
+        let path = dir.path().join("claude");
+        std::fs::write(
+            &path,
+            format!(
+                "#!/bin/sh\n\
+                 if [ \"$1\" = \"--version\" ]; then echo '0.2.9 (Claude Code)'; exit 0; fi\n\
+                 {body}\n"
+            ),
+        )
+        .unwrap();
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).unwrap();
+        path.display().to_string()
+    }
+
+    #[cfg(unix)]
+    async fn first_item(executable: String) -> Result<Message> {
+        let opts = QueryOptions {
+            path_to_executable: Some(executable),
+            ..Default::default()
+        };
+        let mut stream = QueryStream::new("hi".into(), opts);
+        tokio::time::timeout(Duration::from_secs(10), stream.next())
+            .await
+            .expect("stream did not end")
+            .expect("stream yielded nothing")
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn cli_rejecting_stream_json_flags_is_a_version_error() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let exe = old_cli(
+            &dir,
+            "echo \"error: unknown option '--output-format'\" >&2; exit 1",
+        );
+
+        match first_item(exe).await {
+            Err(ClaudeAgentError::UnsupportedCliVersion { found, required }) => {
+                assert_eq!(found, "0.2.9");
+                assert_eq!(required, crate::types::MIN_CLI_VERSION);
+            }
+            other => panic!("expected UnsupportedCliVersion, got {other:?}"),
+        }
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn old_cli_printing_plain_text_is_a_version_error() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let exe = old_cli(&dir, "cat > /dev/null; echo 'Hello! How can I help?'");
+
+        let err = first_item(exe).await.unwrap_err();
+        assert!(
+            matches!(err, ClaudeAgentError::UnsupportedCliVersion { ref found, .. } if found == "0.2.9"),
+            "{err}"
+        );
+        assert!(err.to_string().contains("upgrade to"), "{err}");
+    }
+
+    #[tokio::test]
+    async fn mock_transport_plays_the_script_matching_the_prompt() {
+        let init: Message = serde_json::from_str(INIT_LINE).unwrap();
+        let result: Message = serde_json::from_str(RESULT_LINE).unwrap();
+        let mock = MockTransport::new()
+            .on("spec", vec![result.clone()])
+            .on("design", vec![init, result]);
+        let opts = QueryOptions {
+            transport: Transport::Mock(mock),
+            ..Default::default()
+        };
+
+        let mut stream = crate::query("write the design", opts.clone());
+        assert!(matches!(stream.next().await, Some(Ok(Message::System(_)))));
+        assert_eq!(stream.init_info().unwrap().model, "m");
+        assert!(matches!(stream.next().await, Some(Ok(Message::Result(_)))));
+        assert!(stream.next().await.is_none());
+
+        let unscripted: Vec<_> = crate::query("implement it", opts).collect().await;
+        assert_eq!(unscripted.len(), 1);
+        let err = unscripted.into_iter().next().unwrap().unwrap_err();
+        assert!(err.to_string().contains("no script"), "{err}");
+    }
 }
@@ -34,6 +34,35 @@ mod unit {
         assert_eq!(init.mcp_servers[0].name, "sdlc");
     }
 
+    /// `system/init` as emitted by Claude CLI 2.x, camelCase keys included.
+    const INIT_FIXTURE: &str = r#"{"type":"system","subtype":"init","session_id":"abc-123","cwd":"/work/project","tools":["Task","Bash","Read","mcp__sdlc__sdlc_get_directive"],"mcp_servers":[{"name":"sdlc","status":"connected"}],"model":"claude-opus-4-1","permissionMode":"bypassPermissions","slash_commands":["compact","review"],"apiKeySource":"none","claude_code_version":"2.0.14","output_style":"default","agents":["general-purpose"],"skills":[],"plugins":[],"uuid":"0b3c5a1e"}"#;
+
+    #[test]
+    fn parse_system_init_all_fields() {
+        let Message::System(sys) = parse(INIT_FIXTURE) else {
+            panic!("expected System")
+        };
+        let SystemPayload::Init(init) = sys.payload else {
+            panic!("expected Init")
+        };
+        assert_eq!(init.model, "claude-opus-4-1");
+        assert_eq!(init.cwd, "/work/project");
+        assert_eq!(
+            init.tools,
+            vec!["Task", "Bash", "Read", "mcp__sdlc__sdlc_get_directive"]
+        );
+        assert_eq!(init.mcp_servers.len(), 1);
+        assert_eq!(init.mcp_servers[0].name, "sdlc");
+        assert_eq!(init.mcp_servers[0].status, "connected");
+        assert_eq!(init.permission_mode, "bypassPermissions");
+        assert_eq!(init.api_key_source.as_deref(), Some("none"));
+        assert_eq!(init.claude_code_version, "2.0.14");
+        assert_eq!(init.output_style.as_deref(), Some("default"));
+        assert_eq!(init.agents, vec!["general-purpose"]);
+        assert_eq!(init.slash_commands, vec!["compact", "review"]);
+        assert_eq!(init.uuid.as_deref(), Some("0b3c5a1e"));
+    }
+
     #[test]
     fn parse_system_unknown_subtype() {
         let json = r#"{
@@ -144,4 +173,153 @@ mod unit {
         assert_eq!(tp.tool_name, "Bash");
         assert!((tp.elapsed_time_seconds - 2.5).abs() < f64::EPSILON);
     }
+
+    const THINKING_FIXTURE: &str = r#"{"type":"assistant","message":{"id":"msg_2","role":"assistant","model":"claude-sonnet-4-6","content":[{"type":"thinking","thinking":"The spec needs an error section.","signature":"sig-abc"},{"type":"text","text":"Adding "},{"type":"text","text":"the section."}],"stop_reason":"end_turn","usage":{"input_tokens":10,"output_tokens":5}},"parent_tool_use_id":null,"session_id":"abc-123"}"#;
+
+    const IMAGE_FIXTURE: &str = r#"{"type":"assistant","message":{"id":"msg_3","role":"assistant","model":"claude-sonnet-4-6","content":[{"type":"text","text":"Here is the diagram."},{"type":"image","source":{"type":"base64","media_type":"image/png","data":"iVBORw0KGgo="}},{"type":"image","source":{"type":"url","url":"https://example.com/flow.svg"}}],"stop_reason":"end_turn","usage":{"input_tokens":10,"output_tokens":5}},"parent_tool_use_id":null,"session_id":"abc-123"}"#;
+
+    #[test]
+    fn image_blocks_round_trip_and_stay_out_of_visible_text() {
+        let Message::Assistant(asst) = parse(IMAGE_FIXTURE) else {
+            panic!("expected Assistant")
+        };
+        assert_eq!(
+            asst.message.images(),
+            vec![
+                &crate::types::ImageSource::Base64 {
+                    media_type: "image/png".into(),
+                    data: "iVBORw0KGgo=".into(),
+                },
+                &crate::types::ImageSource::Url {
+                    url: "https://example.com/flow.svg".into(),
+                },
+            ]
+        );
+        assert_eq!(asst.message.visible_text(), "Here is the diagram.");
+
+        let original: serde_json::Value = serde_json::from_str(IMAGE_FIXTURE).unwrap();
+        let reencoded = serde_json::to_value(Message::Assistant(asst)).unwrap();
+        assert_eq!(
+            reencoded["message"]["content"],
+            original["message"]["content"]
+        );
+    }
+
+    #[test]
+    fn thinking_block_round_trips() {
+        let Message::Assistant(asst) = parse(THINKING_FIXTURE) else {
+            panic!("expected Assistant")
+        };
+        assert_eq!(
+            asst.message.thinking(),
+            vec!["The spec needs an error section."]
+        );
+        assert_eq!(asst.message.visible_text(), "Adding the section.");
+        let crate::types::ContentBlock::Thinking { signature, .. } = &asst.message.content[0]
+        else {
+            panic!("expected Thinking")
+        };
+        assert_eq!(signature.as_deref(), Some("sig-abc"));
+
+        let original: serde_json::Value = serde_json::from_str(THINKING_FIXTURE).unwrap();
+        let reencoded = serde_json::to_value(Message::Assistant(asst)).unwrap();
+        assert_eq!(
+            reencoded["message"]["content"],
+            original["message"]["content"]
+        );
+    }
+
+    /// An MCP tool returning JSON, then a plain-text tool result.
+    const TOOL_RESULT_FIXTURE: &str = r#"{"type":"user","message":{"role":"user","content":[{"type":"tool_result","tool_use_id":"tu_1","content":[{"type":"text","text":"{\"phase\":\"specified\",\"tasks\":[1,2]}"}]},{"type":"tool_result","tool_use_id":"tu_2","content":[{"type":"text","text":"42 files changed"}]}]},"parent_tool_use_id":null,"session_id":"abc-123"}"#;
+
+    #[test]
+    fn json_tool_result_is_surfaced_as_json() {
+        use crate::types::{ToolResultContent, UserContentBlock};
+
+        let Message::User(user) = parse(TOOL_RESULT_FIXTURE) else {
+            panic!("expected User")
+        };
+        let blocks: Vec<&ToolResultContent> = user
+            .message
+            .content
+            .iter()
+            .filter_map(|c| match c {
+                UserContentBlock::ToolResult { content, .. } => content.as_ref()?.first(),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            blocks[0],
+            &ToolResultContent::Json(serde_json::json!({ "phase": "specified", "tasks": [1, 2] }))
+        );
+        assert_eq!(blocks[0].as_json().unwrap()["phase"], "specified");
+        assert_eq!(
+            blocks[1],
+            &ToolResultContent::Text("42 files changed".into())
+        );
+        assert!(blocks[1].as_json().is_none());
+
+        let original: serde_json::Value = serde_json::from_str(TOOL_RESULT_FIXTURE).unwrap();
+        let reencoded = serde_json::to_value(Message::User(user)).unwrap();
+        assert_eq!(
+            reencoded["message"]["content"],
+            original["message"]["content"]
+        );
+    }
+
+    /// `--include-partial-messages` output for one short assistant turn.
+    const DELTA_FIXTURE: &str = r#"{"type":"stream_event","event":{"type":"message_start","message":{"id":"msg_1","role":"assistant","content":[]}},"parent_tool_use_id":null,"session_id":"abc-123"}
+{"type":"stream_event","event":{"type":"content_block_start","index":0,"content_block":{"type":"text","text":""}},"parent_tool_use_id":null,"session_id":"abc-123"}
+{"type":"stream_event","event":{"type":"content_block_delta","index":0,"delta":{"type":"text_delta","text":"Writing "}},"parent_tool_use_id":null,"session_id":"abc-123"}
+{"type":"stream_event","event":{"type":"content_block_delta","index":0,"delta":{"type":"text_delta","text":"the spec "}},"parent_tool_use_id":null,"session_id":"abc-123"}
+{"type":"stream_event","event":{"type":"content_block_delta","index":0,"delta":{"type":"text_delta","text":"now."}},"parent_tool_use_id":null,"session_id":"abc-123"}
+{"type":"stream_event","event":{"type":"content_block_stop","index":0},"parent_tool_use_id":null,"session_id":"abc-123"}
+{"type":"assistant","message":{"id":"msg_1","role":"assistant","model":"claude-sonnet-4-6","content":[{"type":"text","text":"Writing the spec now."}],"stop_reason":"end_turn","usage":{"input_tokens":10,"output_tokens":5}},"parent_tool_use_id":null,"session_id":"abc-123"}
+{"type":"stream_event","event":{"type":"message_stop"},"parent_tool_use_id":null,"session_id":"abc-123"}"#;
+
+    fn replay(opts: &crate::QueryOptions) -> Vec<Message> {
+        DELTA_FIXTURE
+            .lines()
+            .filter_map(|line| crate::stream::shape_partial(parse(line), opts))
+            .collect()
+    }
+
+    #[test]
+    fn stream_deltas_concatenate_to_final_text() {
+        let opts = crate::QueryOptions {
+            stream_deltas: true,
+            ..Default::default()
+        };
+        let messages = replay(&opts);
+
+        let streamed: String = messages
+            .iter()
+            .filter_map(|m| match m {
+                Message::AssistantDelta(d) => Some(d.text.as_str()),
+                _ => None,
+            })
+            .collect();
+        let final_text = messages
+            .iter()
+            .find_map(|m| match m {
+                Message::Assistant(a) => a.message.content.iter().find_map(|c| match c {
+                    crate::types::ContentBlock::Text { text } => Some(text.clone()),
+                    _ => None,
+                }),
+                _ => None,
+            })
+            .expect("fixture has an assistant message");
+        assert_eq!(streamed, final_text);
+        // Non-text stream events are dropped unless explicitly requested.
+        assert!(!messages
+            .iter()
+            .any(|m| matches!(m, Message::StreamEvent(_))));
+    }
+
+    #[test]
+    fn stream_deltas_off_leaves_whole_messages() {
+        let messages = replay(&crate::QueryOptions::default());
+        assert_eq!(messages.len(), 1);
+        assert!(matches!(messages[0], Message::Assistant(_)));
+    }
 }
@@ -0,0 +1,54 @@
+//! Where a [`QueryStream`](crate::QueryStream) gets its messages: the real
+//! `claude` subprocess, or a scripted mock that spawns nothing.
+//!
+//! The mock lets callers — the server's route tests in particular — drive
+//! deterministic agent behavior through the normal [`crate::query`] path
+//! without a `claude` binary on `PATH`.
+
+use crate::types::Message;
+
+// ─── Transport ────────────────────────────────────────────────────────────
+
+/// Message source for a query, selected via `QueryOptions::transport`.
+#[derive(Debug, Clone, Default)]
+pub enum Transport {
+    /// Spawn the `claude` CLI and read stream-json from its stdout.
+    #[default]
+    Subprocess,
+    /// Replay a [`MockTransport`] script.
+    Mock(MockTransport),
+}
+
+// ─── MockTransport ────────────────────────────────────────────────────────
+
+/// Preconfigured message scripts keyed by prompt substring.
+///
+/// A query plays the first script whose key occurs in its prompt; an empty
+/// key matches every prompt. A prompt no script matches fails the stream
+/// with `ClaudeAgentError::Process`, so a test never silently runs against
+/// the wrong script.
+#[derive(Debug, Clone, Default)]
+pub struct MockTransport {
+    scripts: Vec<(String, Vec<Message>)>,
+}
+
+impl MockTransport {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Answer prompts containing `prompt_contains` with `messages`, in order.
+    /// Scripts are matched in the order they were added.
+    pub fn on(mut self, prompt_contains: impl Into<String>, messages: Vec<Message>) -> Self {
+        self.scripts.push((prompt_contains.into(), messages));
+        self
+    }
+
+    /// The script `prompt` would play, if any.
+    pub fn script_for(&self, prompt: &str) -> Option<&[Message]> {
+        self.scripts
+            .iter()
+            .find(|(key, _)| prompt.contains(key.as_str()))
+            .map(|(_, messages)| messages.as_slice())
+    }
+}
@@ -69,15 +69,30 @@ pub enum AgentEvent {
         turns: u32,
         session_id: Option<String>,
         stop_reason: Option<String>,
+        /// Token counts for the whole query, when the provider reports them.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        usage: Option<TokenUsage>,
         timestamp: String,
     },
     StreamEvent {
         timestamp: String,
     },
+    /// Incremental assistant text, only when `QueryOptions::stream_deltas` is set.
+    AssistantDelta {
+        text: String,
+        timestamp: String,
+    },
     AuthStatus {
         is_authenticating: bool,
         timestamp: String,
     },
+    /// A tool call waiting on the host's approval (`PermissionMode::Policy`
+    /// rules that resolve to `Ask`).
+    PermissionRequest {
+        request_id: String,
+        tool: String,
+        timestamp: String,
+    },
     System {
         timestamp: String,
     },
@@ -123,12 +138,19 @@ pub enum Message {
     Result(ResultMessage),
     /// `stream_event` — partial assistant message chunks (--include-partial-messages)
     StreamEvent(StreamEventMessage),
+    /// `assistant_delta` — a text chunk of the assistant message in progress.
+    /// Never sent by the CLI; `QueryStream` synthesizes it from `stream_event`
+    /// text deltas when `QueryOptions::stream_deltas` is set.
+    AssistantDelta(AssistantDeltaMessage),
     /// `tool_progress` — progress updates during tool execution
     ToolProgress(ToolProgressMessage),
     /// `tool_use_summary` — summary after tool calls complete
     ToolUseSummary(ToolUseSummaryMessage),
     /// `auth_status` — authentication status during session init
     AuthStatus(AuthStatusMessage),
+    /// `control_request` — the CLI asking the host to decide something
+    /// mid-run, e.g. whether a tool may run under `PermissionMode::Policy`.
+    ControlRequest(ControlRequestMessage),
 }
 
 impl Message {
@@ -139,9 +161,12 @@ impl Message {
             Message::User(m) => &m.session_id,
             Message::Result(m) => m.session_id(),
             Message::StreamEvent(m) => &m.session_id,
+            Message::AssistantDelta(m) => &m.session_id,
             Message::ToolProgress(m) => &m.session_id,
             Message::ToolUseSummary(m) => &m.session_id,
             Message::AuthStatus(m) => &m.session_id,
+            // Control requests are not tied to a session.
+            Message::ControlRequest(_) => "",
         }
     }
 
@@ -183,6 +208,9 @@ pub enum SystemPayload {
     TaskProgress(TaskProgressPayload),
     /// Subtask completed/failed
     TaskNotification(TaskNotificationPayload),
+    /// Emitted by the runner (not the CLI) when a query is retried on the
+    /// next entry in `QueryOptions::model_fallbacks`
+    ModelFallback(ModelFallbackPayload),
     /// Any future/unknown system subtype — safe to ignore
     #[serde(other)]
     Unknown,
@@ -274,6 +302,15 @@ pub struct TaskNotificationPayload {
     pub usage: Option<TaskUsage>,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct ModelFallbackPayload {
+    /// Model that failed; `None` when the CLI default was used
+    pub from_model: Option<String>,
+    pub to_model: String,
+    /// Error text that triggered the fallback
+    pub reason: String,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct TaskUsage {
     pub total_tokens: u64,
@@ -307,6 +344,42 @@ pub struct AssistantContent {
     pub usage: TokenUsage,
 }
 
+impl AssistantContent {
+    /// Extended-thinking text, in block order.
+    pub fn thinking(&self) -> Vec<&str> {
+        self.content
+            .iter()
+            .filter_map(|c| match c {
+                ContentBlock::Thinking { text, .. } => Some(text.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Image blocks (e.g. rendered diagrams), in block order.
+    pub fn images(&self) -> Vec<&ImageSource> {
+        self.content
+            .iter()
+            .filter_map(|c| match c {
+                ContentBlock::Image { source } => Some(source),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// The answer text shown to the user: every `Text` block concatenated,
+    /// with thinking, images and tool calls left out.
+    pub fn visible_text(&self) -> String {
+        self.content
+            .iter()
+            .filter_map(|c| match c {
+                ContentBlock::Text { text } => Some(text.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
 /// Content blocks within an assistant message.
 #[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
@@ -320,8 +393,30 @@ pub enum ContentBlock {
         /// Tool inputs are schema-polymorphic (varies per tool), so Value is correct here.
         input: serde_json::Value,
     },
+    /// Extended-thinking output. The wire field is `thinking`; `signature`
+    /// verifies the block when it is sent back to the API.
     Thinking {
-        thinking: String,
+        #[serde(rename = "thinking")]
+        text: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        signature: Option<String>,
+    },
+    Image {
+        source: ImageSource,
+    },
+}
+
+/// Where an image block's bytes come from.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ImageSource {
+    /// Inline image data, base64-encoded.
+    Base64 {
+        media_type: String,
+        data: String,
+    },
+    Url {
+        url: String,
     },
 }
 
@@ -372,12 +467,66 @@ pub enum UserContentBlock {
     },
 }
 
-#[derive(Debug, Clone, Deserialize, Serialize)]
-#[serde(tag = "type", rename_all = "snake_case")]
+/// One block of a `tool_result`'s content. On the wire every block is
+/// `{"type": "text", "text": ...}`; text holding a JSON object or array is
+/// surfaced as [`ToolResultContent::Json`] so consumers don't re-parse it.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(from = "RawToolResultContent", into = "RawToolResultContent")]
 pub enum ToolResultContent {
+    Text(String),
+    Json(serde_json::Value),
+}
+
+impl ToolResultContent {
+    /// Classify a block's text: a JSON object or array becomes `Json`,
+    /// anything else (including bare JSON scalars) stays `Text`.
+    pub fn from_text(text: String) -> Self {
+        match serde_json::from_str::<serde_json::Value>(&text) {
+            Ok(value) if value.is_object() || value.is_array() => Self::Json(value),
+            _ => Self::Text(text),
+        }
+    }
+
+    /// The structured payload, if the block held one.
+    pub fn as_json(&self) -> Option<&serde_json::Value> {
+        match self {
+            Self::Json(value) => Some(value),
+            Self::Text(_) => None,
+        }
+    }
+
+    /// The block as text; a `Json` payload is rendered compactly.
+    pub fn text(&self) -> std::borrow::Cow<'_, str> {
+        match self {
+            Self::Text(text) => text.as_str().into(),
+            Self::Json(value) => value.to_string().into(),
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum RawToolResultContent {
     Text { text: String },
 }
 
+impl From<RawToolResultContent> for ToolResultContent {
+    fn from(raw: RawToolResultContent) -> Self {
+        let RawToolResultContent::Text { text } = raw;
+        Self::from_text(text)
+    }
+}
+
+impl From<ToolResultContent> for RawToolResultContent {
+    fn from(content: ToolResultContent) -> Self {
+        let text = match content {
+            ToolResultContent::Text(text) => text,
+            ToolResultContent::Json(value) => value.to_string(),
+        };
+        Self::Text { text }
+    }
+}
+
 // ─── Result messages ──────────────────────────────────────────────────────
 
 /// `type = "result"` — the terminal message in every query stream.
@@ -446,6 +595,16 @@ impl ResultMessage {
             | ResultMessage::ErrorMaxStructuredOutputRetries(r) => r.stop_reason.as_deref(),
         }
     }
+
+    pub fn usage(&self) -> &ResultUsage {
+        match self {
+            ResultMessage::Success(r) => &r.usage,
+            ResultMessage::ErrorDuringExecution(r)
+            | ResultMessage::ErrorMaxTurns(r)
+            | ResultMessage::ErrorMaxBudgetUsd(r)
+            | ResultMessage::ErrorMaxStructuredOutputRetries(r) => &r.usage,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -489,18 +648,81 @@ pub struct ResultUsage {
     pub cache_read_input_tokens: Option<u64>,
 }
 
+impl From<&ResultUsage> for TokenUsage {
+    fn from(u: &ResultUsage) -> Self {
+        TokenUsage {
+            input_tokens: u.input_tokens,
+            output_tokens: u.output_tokens,
+            cache_creation_input_tokens: u.cache_creation_input_tokens,
+            cache_read_input_tokens: u.cache_read_input_tokens,
+        }
+    }
+}
+
+// ─── Control requests ─────────────────────────────────────────────────────
+
+/// `type = "control_request"` — sent on stdout; the host answers with a
+/// `control_response` carrying the same `request_id` on stdin.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ControlRequestMessage {
+    pub request_id: String,
+    pub request: ControlRequest,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(tag = "subtype", rename_all = "snake_case")]
+pub enum ControlRequest {
+    /// Permission prompt for a single tool call.
+    CanUseTool {
+        tool_name: String,
+        input: serde_json::Value,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        tool_use_id: Option<String>,
+    },
+    /// Any subtype we do not handle.
+    #[serde(other)]
+    Other,
+}
+
 // ─── Ancillary message types ──────────────────────────────────────────────
 
 /// `type = "stream_event"` — partial chunks (only with --include-partial-messages).
 /// We don't process partial chunks, but we must not fail to parse them.
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct StreamEventMessage {
+    /// Raw Anthropic streaming event (`message_start`, `content_block_delta`, …)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub event: Option<serde_json::Value>,
     pub parent_tool_use_id: Option<String>,
     pub session_id: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub uuid: Option<String>,
 }
 
+impl StreamEventMessage {
+    /// The text carried by a `content_block_delta` / `text_delta` event.
+    /// `None` for every other event (thinking, tool input JSON, block starts).
+    pub fn text_delta(&self) -> Option<&str> {
+        let event = self.event.as_ref()?;
+        if event.get("type")?.as_str()? != "content_block_delta" {
+            return None;
+        }
+        let delta = event.get("delta")?;
+        if delta.get("type")?.as_str()? != "text_delta" {
+            return None;
+        }
+        delta.get("text")?.as_str()
+    }
+}
+
+/// `type = "assistant_delta"` — see [`Message::AssistantDelta`].
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct AssistantDeltaMessage {
+    pub text: String,
+    pub parent_tool_use_id: Option<String>,
+    pub session_id: String,
+}
+
 /// `type = "tool_progress"` — emitted periodically while a tool is running.
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct ToolProgressMessage {
@@ -540,6 +762,18 @@ pub struct AuthStatusMessage {
 
 // ─── QueryOptions ─────────────────────────────────────────────────────────
 
+/// Thinking budget passed to the CLI when `QueryOptions::include_thinking`
+/// is set — the CLI's own maximum.
+pub const DEFAULT_MAX_THINKING_TOKENS: u32 = 31_999;
+
+/// Longest stdout line accepted from the CLI when
+/// `QueryOptions::max_line_bytes` is unset.
+pub const DEFAULT_MAX_LINE_BYTES: usize = 16 * 1024 * 1024;
+
+/// Oldest `claude` CLI version with the bidirectional stream-json protocol
+/// (`--output-format stream-json --input-format stream-json`).
+pub const MIN_CLI_VERSION: &str = "1.0.0";
+
 /// Options for driving a Claude subprocess query.
 ///
 /// Maps to the `Options` type in `@anthropic-ai/claude-agent-sdk/sdk.d.ts`.
@@ -547,6 +781,9 @@ pub struct AuthStatusMessage {
 pub struct QueryOptions {
     /// Claude model name (e.g. `"claude-sonnet-4-6"`)
     pub model: Option<String>,
+    /// Models to retry with, in order, when `model` is rate-limited or
+    /// unavailable. Only honored by [`crate::runner::query_with_fallbacks`].
+    pub model_fallbacks: Vec<String>,
     /// Maximum number of agentic turns before stopping with `error_max_turns`
     pub max_turns: Option<u32>,
     /// Maximum budget in USD before stopping with `error_max_budget_usd`
@@ -554,8 +791,15 @@ pub struct QueryOptions {
     /// Effort level for reasoning depth
     pub effort: Option<Effort>,
     /// Tool names that are auto-approved without user prompting
+    /// (`--allowedTools`, comma-joined). Empty means no allow list.
+    ///
+    /// When both lists are set the deny list wins in the CLI; [`validate`]
+    /// rejects a tool named in both so the intent is never ambiguous.
+    ///
+    /// [`validate`]: QueryOptions::validate
     pub allowed_tools: Vec<String>,
-    /// Tool names that are explicitly disallowed
+    /// Tool names Claude may not call (`--disallowedTools`, comma-joined).
+    /// Empty means nothing is denied beyond the permission mode.
     pub disallowed_tools: Vec<String>,
     /// Permission mode for tool execution
     pub permission_mode: PermissionMode,
@@ -565,6 +809,12 @@ pub struct QueryOptions {
     pub append_system_prompt: Option<String>,
     /// Session ID to resume (loads conversation history)
     pub resume: Option<String>,
+    /// With `resume`, keep the history only up to and including the
+    /// assistant message with this `uuid` (`--resume-session-at`)
+    pub resume_session_at: Option<String>,
+    /// With `resume`, continue under a new session ID, leaving the resumed
+    /// session as it was (`--fork-session`)
+    pub fork_session: bool,
     /// Continue the most recent conversation
     pub continue_conversation: bool,
     /// Session ID for a specific conversation
@@ -575,16 +825,84 @@ pub struct QueryOptions {
     pub cwd: Option<std::path::PathBuf>,
     /// Additional environment variables for the subprocess
     pub env: HashMap<String, String>,
-    /// Additional working directories (`--add-dir`)
-    pub additional_directories: Vec<String>,
+    /// Extra directory roots the agent may read and write, e.g. sibling
+    /// repos (one `--add-dir` each). Grants the agent filesystem access
+    /// beyond `cwd`. Relative paths resolve against `cwd`; each must exist,
+    /// see [`QueryOptions::validate`].
+    pub additional_dirs: Vec<std::path::PathBuf>,
     /// Custom path to the `claude` binary (default: `"claude"`)
     pub path_to_executable: Option<String>,
     /// Enable debug mode (`--debug`)
     pub debug: bool,
     /// Include partial/streaming messages (`--include-partial-messages`)
     pub include_partial_messages: bool,
+    /// Enable extended thinking (`--max-thinking-tokens`, see
+    /// [`DEFAULT_MAX_THINKING_TOKENS`]). Thinking arrives as
+    /// `ContentBlock::Thinking` blocks.
+    pub include_thinking: bool,
+    /// Yield `Message::AssistantDelta` for each text chunk as it arrives.
+    /// Implies `--include-partial-messages`; the raw `stream_event` messages
+    /// are still passed through only if `include_partial_messages` is set.
+    pub stream_deltas: bool,
     /// Disable session persistence (`--no-session-persistence`)
     pub no_session_persistence: bool,
+    /// Further user messages for the live session, e.g. a human steering a
+    /// long run. Keeps stdin open; each message is written as a user turn
+    /// as soon as it arrives. See [`crate::stream::user_input_channel`].
+    pub user_input: Option<crate::stream::UserInput>,
+    /// Longest stream-json line read from the subprocess before the stream
+    /// fails with `ClaudeAgentError::LineTooLong` and the child is killed
+    /// (default: [`DEFAULT_MAX_LINE_BYTES`]).
+    pub max_line_bytes: Option<usize>,
+    /// Where messages come from (default: the `claude` subprocess). A
+    /// [`crate::MockTransport`] replays scripted messages instead; only the
+    /// Claude provider honors it.
+    pub transport: crate::transport::Transport,
+    /// Rates used to estimate cost for providers that do not report it
+    /// (default: the built-in [`crate::PricingTable`]).
+    pub pricing: crate::pricing::PricingTable,
+}
+
+impl QueryOptions {
+    /// Reject option combinations the CLI would resolve silently.
+    /// Called before the subprocess is spawned.
+    pub fn validate(&self) -> crate::Result<()> {
+        let overlap: Vec<&str> = self
+            .allowed_tools
+            .iter()
+            .filter(|t| self.disallowed_tools.contains(t))
+            .map(String::as_str)
+            .collect();
+        if !overlap.is_empty() {
+            return Err(crate::ClaudeAgentError::InvalidOptions(format!(
+                "tools both allowed and disallowed: {}",
+                overlap.join(", ")
+            )));
+        }
+        self.additional_dir_paths()?;
+        Ok(())
+    }
+
+    /// `additional_dirs` canonicalized, relative entries resolved against
+    /// `cwd`. Fails on a directory that does not exist.
+    pub(crate) fn additional_dir_paths(&self) -> crate::Result<Vec<std::path::PathBuf>> {
+        self.additional_dirs
+            .iter()
+            .map(|dir| {
+                let path = match &self.cwd {
+                    Some(cwd) => cwd.join(dir),
+                    None => dir.clone(),
+                };
+                match std::fs::canonicalize(&path) {
+                    Ok(canonical) if canonical.is_dir() => Ok(canonical),
+                    _ => Err(crate::ClaudeAgentError::InvalidOptions(format!(
+                        "additional dir is not an existing directory: {}",
+                        path.display()
+                    ))),
+                }
+            })
+            .collect()
+    }
 }
 
 /// Effort level for Claude reasoning depth.
@@ -621,6 +939,9 @@ pub enum PermissionMode {
     Plan,
     /// Don't prompt; deny if not pre-approved
     DontAsk,
+    /// Answer each permission prompt from a [`PermissionPolicy`]. The CLI runs
+    /// in default mode with its prompts routed over stdio.
+    Policy(PermissionPolicy),
 }
 
 impl PermissionMode {
@@ -631,29 +952,336 @@ impl PermissionMode {
             PermissionMode::BypassPermissions => "bypassPermissions",
             PermissionMode::Plan => "plan",
             PermissionMode::DontAsk => "dontAsk",
+            PermissionMode::Policy(_) => "default",
+        }
+    }
+}
+
+/// What a [`PermissionPolicy`] does with a tool call.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PermissionDecision {
+    /// Approve without asking anyone.
+    Allow,
+    /// Refuse without asking anyone.
+    Deny,
+    /// Leave the decision to the stream's caller.
+    Ask,
+}
+
+/// Ordered tool-name rules for [`PermissionMode::Policy`].
+///
+/// The first rule whose pattern matches decides; tools no rule matches get
+/// `fallback`. In a pattern `*` matches any run of characters, so `Read*`
+/// covers `Read` and `ReadMcpResource`, and `mcp__sdlc__*` a whole server.
+///
+/// ```rust
+/// use claude_agent::{PermissionDecision, PermissionPolicy};
+///
+/// let policy = PermissionPolicy::default().allow("Read*").deny("Bash");
+/// assert_eq!(policy.decide("Read"), PermissionDecision::Allow);
+/// assert_eq!(policy.decide("Bash"), PermissionDecision::Deny);
+/// assert_eq!(policy.decide("Write"), PermissionDecision::Ask);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PermissionPolicy {
+    pub rules: Vec<(String, PermissionDecision)>,
+    pub fallback: PermissionDecision,
+}
+
+impl Default for PermissionPolicy {
+    /// No rules; every tool is left to the caller.
+    fn default() -> Self {
+        Self {
+            rules: Vec::new(),
+            fallback: PermissionDecision::Ask,
+        }
+    }
+}
+
+impl PermissionPolicy {
+    pub fn allow(self, pattern: impl Into<String>) -> Self {
+        self.rule(pattern, PermissionDecision::Allow)
+    }
+
+    pub fn deny(self, pattern: impl Into<String>) -> Self {
+        self.rule(pattern, PermissionDecision::Deny)
+    }
+
+    pub fn ask(self, pattern: impl Into<String>) -> Self {
+        self.rule(pattern, PermissionDecision::Ask)
+    }
+
+    /// Decision for tools no rule matches.
+    pub fn otherwise(mut self, decision: PermissionDecision) -> Self {
+        self.fallback = decision;
+        self
+    }
+
+    fn rule(mut self, pattern: impl Into<String>, decision: PermissionDecision) -> Self {
+        self.rules.push((pattern.into(), decision));
+        self
+    }
+
+    pub fn decide(&self, tool_name: &str) -> PermissionDecision {
+        self.rules
+            .iter()
+            .find(|(pattern, _)| pattern_matches(pattern, tool_name))
+            .map_or(self.fallback, |(_, decision)| *decision)
+    }
+}
+
+/// Match `name` against a pattern where `*` stands for any run of characters.
+fn pattern_matches(pattern: &str, name: &str) -> bool {
+    let parts: Vec<&str> = pattern.split('*').collect();
+    let [first, middle @ .., last] = parts.as_slice() else {
+        return pattern == name;
+    };
+    let Some(mut rest) = name.strip_prefix(first) else {
+        return false;
+    };
+    for part in middle {
+        match rest.find(part) {
+            Some(i) => rest = &rest[i + part.len()..],
+            None => return false,
         }
     }
+    rest.ends_with(last)
 }
 
-/// MCP server configuration for stdio transport (the most common case).
+/// MCP server configuration.
 ///
-/// Maps to `McpStdioServerConfig` in the SDK.
+/// Maps to `McpStdioServerConfig` in the SDK, or `McpHttpServerConfig` when
+/// `transport` is [`McpTransport::Http`].
 #[derive(Debug, Clone)]
 pub struct McpServerConfig {
     /// Logical name for this server (used in tool names as `mcp__<name>__<tool>`)
     pub name: String,
-    /// Executable to spawn
+    /// How the CLI reaches the server
+    pub transport: McpTransport,
+    /// Executable to spawn (stdio only)
     pub command: String,
-    /// Arguments for the executable
+    /// Arguments for the executable (stdio only)
     pub args: Vec<String>,
-    /// Additional environment variables for the server process
+    /// Additional environment variables for the server process (stdio only)
     pub env: HashMap<String, String>,
 }
 
+/// Transport for an [`McpServerConfig`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum McpTransport {
+    /// Spawn `command` and speak JSON-RPC over its stdin/stdout (the most
+    /// common case).
+    #[default]
+    Stdio,
+    /// Connect to a running server over streamable HTTP.
+    Http { url: String },
+}
+
+impl McpServerConfig {
+    /// An HTTP server; `command`, `args` and `env` stay empty.
+    pub fn http(name: impl Into<String>, url: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            transport: McpTransport::Http { url: url.into() },
+            command: String::new(),
+            args: Vec::new(),
+            env: HashMap::new(),
+        }
+    }
+
+    /// Catch a misconfigured server before a run starts rather than deep in
+    /// it: a stdio `command` must be an existing file or a name found on
+    /// `PATH`, with no blank arguments; an HTTP server needs an absolute
+    /// `http`/`https` URL.
+    pub fn validate(&self) -> crate::Result<()> {
+        let invalid = |detail: String| crate::ClaudeAgentError::McpConfigInvalid {
+            server: self.name.clone(),
+            detail,
+        };
+        match &self.transport {
+            McpTransport::Http { url } => {
+                let parsed = reqwest::Url::parse(url)
+                    .map_err(|e| invalid(format!("invalid url '{url}': {e}")))?;
+                if !matches!(parsed.scheme(), "http" | "https") || !parsed.has_host() {
+                    return Err(invalid(format!("url '{url}' is not an http(s) address")));
+                }
+            }
+            McpTransport::Stdio => {
+                if self.command.trim().is_empty() {
+                    return Err(invalid("no command given".into()));
+                }
+                if !command_exists(&self.command) {
+                    return Err(invalid(format!(
+                        "command '{}' not found (not a file, not on PATH)",
+                        self.command
+                    )));
+                }
+                if let Some(i) = self.args.iter().position(|a| a.trim().is_empty()) {
+                    return Err(invalid(format!("argument {} is blank", i + 1)));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// The entry for this server under `mcpServers` in `--mcp-config`.
+    pub(crate) fn config_json(&self) -> serde_json::Value {
+        if let McpTransport::Http { url } = &self.transport {
+            return serde_json::json!({ "type": "http", "url": url });
+        }
+        let mut cfg = serde_json::Map::new();
+        cfg.insert("type".into(), "stdio".into());
+        cfg.insert("command".into(), self.command.clone().into());
+        if !self.args.is_empty() {
+            cfg.insert("args".into(), self.args.clone().into());
+        }
+        if !self.env.is_empty() {
+            let env: serde_json::Map<String, serde_json::Value> = self
+                .env
+                .iter()
+                .map(|(k, v)| (k.clone(), v.clone().into()))
+                .collect();
+            cfg.insert("env".into(), env.into());
+        }
+        serde_json::Value::Object(cfg)
+    }
+}
+
+/// [`ClaudeAgentError::Process`] unless `command` can be spawned.
+pub(crate) fn require_executable(command: &str) -> crate::Result<()> {
+    if command_exists(command) {
+        Ok(())
+    } else {
+        Err(crate::ClaudeAgentError::Process(format!(
+            "'{command}' not found on PATH"
+        )))
+    }
+}
+
+/// Whether `command` can be spawned: a path (anything with a separator) must
+/// exist; a bare name is looked up on `PATH`, as a shell would.
+fn command_exists(command: &str) -> bool {
+    let path = std::path::Path::new(command);
+    if path.components().count() > 1 || path.is_absolute() {
+        return is_executable(path);
+    }
+    let Some(search) = std::env::var_os("PATH") else {
+        return false;
+    };
+    std::env::split_paths(&search).any(|dir| {
+        let exe = dir.join(command);
+        is_executable(&exe)
+            || (!std::env::consts::EXE_EXTENSION.is_empty()
+                && is_executable(&exe.with_extension(std::env::consts::EXE_EXTENSION)))
+    })
+}
+
+fn is_executable(path: &std::path::Path) -> bool {
+    let Ok(meta) = std::fs::metadata(path) else {
+        return false;
+    };
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        meta.is_file() && meta.permissions().mode() & 0o111 != 0
+    }
+    #[cfg(not(unix))]
+    {
+        meta.is_file()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn stdio_server(command: &str, args: &[&str]) -> McpServerConfig {
+        McpServerConfig {
+            name: "sdlc".into(),
+            transport: McpTransport::Stdio,
+            command: command.into(),
+            args: args.iter().map(|a| a.to_string()).collect(),
+            env: HashMap::new(),
+        }
+    }
+
+    fn invalid_detail(server: &McpServerConfig) -> String {
+        match server.validate() {
+            Err(crate::ClaudeAgentError::McpConfigInvalid { server, detail }) => {
+                assert_eq!(server, "sdlc");
+                detail
+            }
+            other => panic!("expected McpConfigInvalid, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn mcp_stdio_server_with_missing_command_is_invalid() {
+        let detail = invalid_detail(&stdio_server("sdlc-no-such-binary-xyz", &["mcp"]));
+        assert!(detail.contains("sdlc-no-such-binary-xyz"), "{detail}");
+        let detail = invalid_detail(&stdio_server("/no/such/dir/sdlc", &["mcp"]));
+        assert!(detail.contains("not found"), "{detail}");
+        invalid_detail(&stdio_server("  ", &[]));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn mcp_stdio_server_with_reachable_command_is_valid() {
+        // `sh` is on PATH everywhere the tests run.
+        stdio_server("sh", &["-c", "true"]).validate().unwrap();
+        let sh = std::env::split_paths(&std::env::var_os("PATH").unwrap())
+            .map(|d| d.join("sh"))
+            .find(|p| p.is_file())
+            .unwrap();
+        stdio_server(sh.to_str().unwrap(), &[]).validate().unwrap();
+    }
+
+    #[test]
+    fn mcp_stdio_server_with_blank_argument_is_invalid() {
+        let detail = invalid_detail(&stdio_server("sh", &["-c", ""]));
+        assert!(detail.contains("argument 2"), "{detail}");
+    }
+
+    #[test]
+    fn mcp_http_server_validates_url_not_command() {
+        let server = |url: &str| McpServerConfig::http("sdlc", url);
+        server("http://localhost:7777/mcp").validate().unwrap();
+        server("https://mcp.example.com/").validate().unwrap();
+        assert!(invalid_detail(&server("not a url")).contains("invalid url"));
+        assert!(invalid_detail(&server("file:///tmp/mcp.sock")).contains("http(s)"));
+        assert_eq!(
+            server("https://mcp.example.com/").config_json(),
+            serde_json::json!({ "type": "http", "url": "https://mcp.example.com/" })
+        );
+    }
+
+    #[test]
+    fn permission_patterns_match_with_wildcards() {
+        assert!(pattern_matches("Bash", "Bash"));
+        assert!(!pattern_matches("Bash", "BashOutput"));
+        assert!(pattern_matches("Read*", "Read"));
+        assert!(pattern_matches("Read*", "ReadMcpResource"));
+        assert!(!pattern_matches("Read*", "MultiRead"));
+        assert!(pattern_matches("mcp__*__query", "mcp__sdlc__query"));
+        assert!(!pattern_matches("mcp__*__query", "mcp__sdlc__query_all"));
+        assert!(pattern_matches("*", "Anything"));
+        assert!(!pattern_matches("a*a", "a"));
+    }
+
+    #[test]
+    fn first_matching_permission_rule_wins() {
+        let policy = PermissionPolicy::default()
+            .deny("mcp__sdlc__delete*")
+            .allow("mcp__sdlc__*")
+            .otherwise(PermissionDecision::Deny);
+        assert_eq!(
+            policy.decide("mcp__sdlc__delete_feature"),
+            PermissionDecision::Deny
+        );
+        assert_eq!(policy.decide("mcp__sdlc__query"), PermissionDecision::Allow);
+        assert_eq!(policy.decide("Bash"), PermissionDecision::Deny);
+    }
+
     fn make_result_usage() -> ResultUsage {
         ResultUsage {
             input_tokens: 10,
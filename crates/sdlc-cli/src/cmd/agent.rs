@@ -1,12 +1,14 @@
+use crate::output::{print_table, OutputFormat};
 use anyhow::{Context, Result};
 use claude_agent::{
-    runner::{self, RunConfig},
-    McpServerConfig, PermissionMode, QueryOptions,
+    runner::{RunConfig, RunResult, RunnerBuilder, DEFAULT_MAX_CONSECUTIVE_FAILURES},
+    McpServerConfig, PermissionMode, QueryOptions, SessionStore,
 };
 use sdlc_core::{
     classifier::{Classification, Classifier, EvalContext},
     config::Config,
     feature::Feature,
+    prompt_template::{self, PromptVars},
     rules::default_rules,
     state::State,
     types::ActionType,
@@ -37,6 +39,42 @@ pub enum AgentSubcommand {
         /// Model override (default: claude-sonnet-4-6)
         #[arg(long)]
         model: Option<String>,
+
+        /// Retry a failed run, resuming from its latest checkpoint
+        #[arg(long, value_name = "RUN_ID")]
+        resume: Option<String>,
+    },
+
+    /// Print the transcript of an agent run.
+    ///
+    /// Reads the JSONL transcript the server appends to while the run is
+    /// live. With `--json` each event is printed as one JSON line.
+    Logs {
+        /// Run ID (as listed by `/api/runs`)
+        run_id: String,
+
+        /// Keep reading as the run appends, until it finishes
+        #[arg(long, short)]
+        follow: bool,
+    },
+
+    /// Re-drive a recorded agent run without spawning Claude.
+    ///
+    /// Feeds the messages `sdlc agent run` recorded through the runner again
+    /// and prints the `RunResult` it assembles — for checking runner-side
+    /// logic against a real captured stream.
+    Replay {
+        /// Run ID (as printed by `sdlc agent run`)
+        run_id: String,
+    },
+
+    /// List the checkpoints of an agent run.
+    ///
+    /// A checkpoint is recorded after each artifact the agent approves;
+    /// `sdlc agent run <slug> --resume <run-id>` continues from the latest.
+    Checkpoints {
+        /// Run ID (as printed by `sdlc agent run`)
+        run_id: String,
     },
 }
 
@@ -44,12 +82,33 @@ pub enum AgentSubcommand {
 // Entry point
 // ---------------------------------------------------------------------------
 
-pub fn run(root: &Path, subcommand: AgentSubcommand, _json: bool) -> Result<()> {
-    let AgentSubcommand::Run {
-        slug,
-        max_turns,
-        model,
-    } = subcommand;
+pub fn run(root: &Path, subcommand: AgentSubcommand, out: OutputFormat) -> Result<()> {
+    match subcommand {
+        AgentSubcommand::Run {
+            slug,
+            max_turns,
+            model,
+            resume,
+        } => run_feature(root, slug, max_turns, model, resume),
+        AgentSubcommand::Logs { run_id, follow } => logs(root, &run_id, follow, out),
+        AgentSubcommand::Replay { run_id } => replay(root, &run_id, out),
+        AgentSubcommand::Checkpoints { run_id } => checkpoints(root, &run_id, out),
+    }
+}
+
+/// The sdlc MCP tool whose successful calls mark a checkpoint.
+const CHECKPOINT_TOOL: &str = "mcp__sdlc__sdlc_approve_artifact";
+
+fn run_feature(
+    root: &Path,
+    slug: String,
+    max_turns: u32,
+    model: Option<String>,
+    resume: Option<String>,
+) -> Result<()> {
+    if let Some(run_id) = &resume {
+        validate_run_id(run_id)?;
+    }
 
     // Load state machine context
     let config = Config::load(root).context("failed to load config")?;
@@ -57,12 +116,7 @@ pub fn run(root: &Path, subcommand: AgentSubcommand, _json: bool) -> Result<()>
     let feature =
         Feature::load(root, &slug).with_context(|| format!("feature '{slug}' not found"))?;
 
-    let ctx = EvalContext {
-        feature: &feature,
-        state: &state,
-        config: &config,
-        root,
-    };
+    let ctx = EvalContext::new(&feature, &state, &config, root);
     let classification = Classifier::new(default_rules()).classify(&ctx);
 
     // Short-circuit on terminal states — no need to spawn Claude
@@ -89,6 +143,7 @@ pub fn run(root: &Path, subcommand: AgentSubcommand, _json: bool) -> Result<()>
 
     let mcp_server = McpServerConfig {
         name: "sdlc".into(),
+        transport: claude_agent::McpTransport::Stdio,
         command: sdlc_bin.to_string_lossy().into_owned(),
         args: vec!["mcp".into()],
         env: HashMap::new(),
@@ -106,6 +161,7 @@ pub fn run(root: &Path, subcommand: AgentSubcommand, _json: bool) -> Result<()>
         "mcp__sdlc__sdlc_merge".into(),
         "mcp__sdlc__sdlc_project_phase".into(),
         "mcp__sdlc__sdlc_prepare".into(),
+        "mcp__sdlc__sdlc_query".into(),
     ];
 
     let opts = QueryOptions {
@@ -120,45 +176,265 @@ pub fn run(root: &Path, subcommand: AgentSubcommand, _json: bool) -> Result<()>
 
     let run_cfg = RunConfig {
         system_prompt: Some(build_system_prompt()),
-        prompt: build_prompt(&slug, &classification),
+        prompt: build_prompt(root, &classification)?,
         opts,
+        auto_compact: true,
+        trace_messages: false,
+        max_cost_usd: None,
+        max_total_tokens: None,
+        max_consecutive_failures: DEFAULT_MAX_CONSECUTIVE_FAILURES,
     };
 
+    let run_id =
+        resume.unwrap_or_else(|| format!("{slug}-{}", chrono::Utc::now().format("%Y%m%d%H%M%S")));
+    println!("Run {run_id}");
+    let run = RunnerBuilder::new(run_cfg)
+        .with_checkpoints(SessionStore::new(root), run_id.clone(), |tool| {
+            tool == CHECKPOINT_TOOL
+        })
+        .with_recording(&SessionStore::new(root), &run_id)
+        .context("failed to start the run recording")?
+        .run();
+
     // Drive the agent — Claude handles the full directive loop internally via
     // MCP tool calls. We block until it completes (up to max_turns turns).
-    tracing::info!(slug = %slug, max_turns, "spawning claude subprocess");
-    let rt = tokio::runtime::Handle::try_current()
-        .map(|_| None)
-        .unwrap_or_else(|_| Some(tokio::runtime::Runtime::new().expect("tokio runtime")));
+    tracing::info!(slug = %slug, run_id = %run_id, max_turns, "spawning claude subprocess");
+    let result =
+        block_on(run).with_context(|| format!("agent run failed; retry with --resume {run_id}"))?;
+
+    print_result(&result);
+
+    if result.is_error {
+        anyhow::bail!("agent run ended with an error result; retry with --resume {run_id}");
+    }
+
+    Ok(())
+}
 
-    let result = match rt {
-        Some(rt) => {
+/// Drive `fut` to completion from synchronous code.
+fn block_on<F: std::future::Future>(fut: F) -> F::Output {
+    match tokio::runtime::Handle::try_current() {
+        // Already inside a runtime (e.g., integration test)
+        Ok(handle) => tokio::task::block_in_place(|| handle.block_on(fut)),
+        Err(_) => {
             tracing::debug!("using new tokio runtime");
-            rt.block_on(runner::run(run_cfg))
-        }
-        None => {
-            // Already inside a runtime (e.g., integration test)
-            tokio::task::block_in_place(|| {
-                tokio::runtime::Handle::current().block_on(runner::run(run_cfg))
-            })
+            tokio::runtime::Runtime::new()
+                .expect("tokio runtime")
+                .block_on(fut)
         }
     }
-    .context("agent run failed")?;
+}
 
+fn print_result(result: &RunResult) {
     println!("{}", result.result_text);
     println!("\n---");
+    let failed_tools = result.tool_calls.iter().filter(|c| c.is_error).count();
     println!(
-        "Turns: {}  Cost: ${:.4}",
-        result.num_turns, result.total_cost_usd
+        "Turns: {}  Cost: ${:.4}  Tools: {} ({} failed)",
+        result.num_turns,
+        result.total_cost_usd,
+        result.tool_calls.len(),
+        failed_tools
     );
-
-    if result.is_error {
-        anyhow::bail!("agent run ended with an error result");
+    let slowest: Vec<String> = result
+        .tool_time_by_name()
+        .into_iter()
+        .filter(|(_, ms)| *ms > 0)
+        .take(3)
+        .map(|(name, ms)| format!("{name} {:.1}s", ms as f64 / 1000.0))
+        .collect();
+    if !slowest.is_empty() {
+        println!("Tool time: {}", slowest.join(", "));
     }
+}
+
+// ---------------------------------------------------------------------------
+// replay
+// ---------------------------------------------------------------------------
 
+fn replay(root: &Path, run_id: &str, out: OutputFormat) -> Result<()> {
+    validate_run_id(run_id)?;
+    let store = SessionStore::new(root);
+    let recording = store.recording(run_id).with_context(|| {
+        format!(
+            "no recording for run '{run_id}' (looked for {})",
+            store.recording_path(run_id).display()
+        )
+    })?;
+
+    // Compaction on, as in `agent run`, so recorded retries replay in turn.
+    let run_cfg = RunConfig {
+        system_prompt: None,
+        prompt: String::new(),
+        opts: QueryOptions::default(),
+        auto_compact: true,
+        trace_messages: false,
+        max_cost_usd: None,
+        max_total_tokens: None,
+        max_consecutive_failures: DEFAULT_MAX_CONSECUTIVE_FAILURES,
+    };
+    let result = block_on(RunnerBuilder::new(run_cfg).replay(recording))
+        .with_context(|| format!("replay of run '{run_id}' failed"))?;
+
+    out.emit(
+        &serde_json::json!({
+            "run_id": run_id,
+            "session_id": result.session_id,
+            "result_text": result.result_text,
+            "total_cost_usd": result.total_cost_usd,
+            "num_turns": result.num_turns,
+            "is_error": result.is_error,
+            "fallbacks": result.fallbacks,
+            "errors": result.errors,
+            "compactions": result.compactions,
+            "tool_calls": result.tool_calls,
+        }),
+        || {
+            print_result(&result);
+            Ok(())
+        },
+    )
+}
+
+// ---------------------------------------------------------------------------
+// logs
+// ---------------------------------------------------------------------------
+
+/// Longest tool input or tool result shown on one line of `agent logs`.
+const LOG_SNIPPET_CHARS: usize = 160;
+
+fn logs(root: &Path, run_id: &str, follow: bool, out: OutputFormat) -> Result<()> {
+    validate_run_id(run_id)?;
+    let store = SessionStore::new(root);
+    let tail = store.tail(run_id, follow).with_context(|| {
+        format!(
+            "no transcript for run '{run_id}' (looked for {})",
+            store.transcript_path(run_id).display()
+        )
+    })?;
+    for event in tail {
+        let event = event.context("failed to read transcript")?;
+        if out.is_structured() {
+            println!("{}", serde_json::to_string(&event)?);
+        } else if let Some(text) = render_event(&event) {
+            println!("{text}");
+        }
+    }
     Ok(())
 }
 
+/// Human-readable form of one transcript event; `None` for events that only
+/// matter to the web UI (status pings, deltas, subagent progress).
+fn render_event(event: &serde_json::Value) -> Option<String> {
+    let str_of = |key: &str| event[key].as_str().unwrap_or_default();
+    match str_of("type") {
+        "init" => Some(format!(
+            "▸ {} ({} tools)",
+            str_of("model"),
+            event["tools_count"].as_u64().unwrap_or(0)
+        )),
+        "assistant" => {
+            let mut lines: Vec<String> = Vec::new();
+            let text = str_of("text").trim();
+            if !text.is_empty() {
+                lines.push(text.to_string());
+            }
+            for tool in event["tools"].as_array().into_iter().flatten() {
+                lines.push(format!(
+                    "→ {} {}",
+                    tool["name"].as_str().unwrap_or("?"),
+                    snippet(&tool["input"].to_string())
+                ));
+            }
+            (!lines.is_empty()).then(|| lines.join("\n"))
+        }
+        "user" => {
+            let lines: Vec<String> = event["tool_results"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .map(|r| {
+                    let marker = if r["is_error"].as_bool() == Some(true) {
+                        "✗"
+                    } else {
+                        "←"
+                    };
+                    format!(
+                        "  {marker} {}",
+                        snippet(r["content"].as_str().unwrap_or(""))
+                    )
+                })
+                .collect();
+            (!lines.is_empty()).then(|| lines.join("\n"))
+        }
+        "tool_summary" => Some(format!("  {}", str_of("summary"))),
+        "result" => Some(if event["is_error"].as_bool() == Some(true) {
+            format!("✗ run failed: {}", snippet(str_of("text")))
+        } else {
+            format!(
+                "✓ done — {} turns, ${:.4}",
+                event["turns"].as_u64().unwrap_or(0),
+                event["cost_usd"].as_f64().unwrap_or(0.0)
+            )
+        }),
+        "error" => Some(format!("✗ {}", str_of("message"))),
+        _ => None,
+    }
+}
+
+/// First line of `text`, cut to `LOG_SNIPPET_CHARS` characters.
+fn snippet(text: &str) -> String {
+    let line = text.lines().next().unwrap_or("");
+    match line.char_indices().nth(LOG_SNIPPET_CHARS) {
+        Some((idx, _)) => format!("{}…", &line[..idx]),
+        None => line.to_string(),
+    }
+}
+
+// ---------------------------------------------------------------------------
+// checkpoints
+// ---------------------------------------------------------------------------
+
+fn checkpoints(root: &Path, run_id: &str, out: OutputFormat) -> Result<()> {
+    validate_run_id(run_id)?;
+    let checkpoints = SessionStore::new(root)
+        .checkpoints(run_id)
+        .with_context(|| format!("failed to read checkpoints for run '{run_id}'"))?;
+
+    out.emit(
+        &serde_json::json!({
+            "run_id": run_id,
+            "checkpoints": checkpoints,
+        }),
+        || {
+            if checkpoints.is_empty() {
+                println!("No checkpoints recorded for run '{run_id}'.");
+                return Ok(());
+            }
+
+            let rows: Vec<Vec<String>> = checkpoints
+                .iter()
+                .map(|c| {
+                    vec![
+                        c.turn.to_string(),
+                        c.created_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+                        c.session_id.clone(),
+                        c.message_uuid.clone().unwrap_or_else(|| "-".to_string()),
+                    ]
+                })
+                .collect();
+            print_table(&["TURN", "AT", "SESSION", "MESSAGE"], rows);
+            Ok(())
+        },
+    )
+}
+
+/// Run IDs name files under `.sdlc/sessions/`, so they must be slugs —
+/// as the IDs `sdlc agent run` generates are.
+fn validate_run_id(run_id: &str) -> Result<()> {
+    sdlc_core::paths::validate_slug(run_id).with_context(|| format!("invalid run ID '{run_id}'"))
+}
+
 // ---------------------------------------------------------------------------
 // Prompt builders
 // ---------------------------------------------------------------------------
@@ -207,14 +483,12 @@ DRAFT → SPECIFIED → PLANNED → READY → IMPLEMENTATION → REVIEW → AUDI
     .to_string()
 }
 
-fn build_prompt(slug: &str, classification: &Classification) -> String {
-    let directive_json = serde_json::to_string_pretty(classification)
-        .unwrap_or_else(|_| format!("{classification:?}"));
-
-    format!(
-        "Drive feature '{slug}' forward using the sdlc state machine tools.\n\n\
-         Current directive:\n{directive_json}\n\n\
-         Execute the action, verify state advanced with sdlc_get_directive, then loop \
-         until done. Only stop early for wait_for_approval or unblock_dependency."
-    )
+/// Render the prompt template for the directive's action; see
+/// `sdlc_core::prompt_template` for the built-ins and `.sdlc/prompts/`
+/// overrides.
+fn build_prompt(root: &Path, classification: &Classification) -> Result<String> {
+    let name = prompt_template::template_for(classification.action);
+    let vars = PromptVars::for_directive(root, classification);
+    prompt_template::render_named(root, name, &vars)
+        .with_context(|| format!("failed to render prompt template '{name}'"))
 }
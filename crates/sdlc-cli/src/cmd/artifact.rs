@@ -1,6 +1,8 @@
-use crate::output::print_json;
+use crate::output::{print_table, OutputFormat};
 use anyhow::Context;
+use chrono::{DateTime, NaiveDate, Utc};
 use clap::Subcommand;
+use sdlc_core::artifact::{check_for_secrets, SECRET_GUIDANCE};
 use sdlc_core::classifier::try_auto_transition;
 use sdlc_core::event_log::{self, EventKind};
 use sdlc_core::feature::Feature;
@@ -32,25 +34,33 @@ pub enum ArtifactSubcommand {
         artifact: String,
         #[arg(long)]
         reason: Option<String>,
+        /// Let the waiver lapse at this time (RFC 3339 or YYYY-MM-DD, UTC);
+        /// the artifact is required again afterwards
+        #[arg(long, value_name = "TIME", value_parser = parse_expiry)]
+        expires_at: Option<DateTime<Utc>>,
     },
+    /// Show every approve / reject / waive decision on an artifact
+    History { slug: String, artifact: String },
 }
 
-pub fn run(root: &Path, subcmd: ArtifactSubcommand, json: bool) -> anyhow::Result<()> {
+pub fn run(root: &Path, subcmd: ArtifactSubcommand, out: OutputFormat) -> anyhow::Result<()> {
     match subcmd {
         ArtifactSubcommand::Approve { slug, artifact, by } => {
-            approve(root, &slug, &artifact, by, json)
+            approve(root, &slug, &artifact, by, out)
         }
         ArtifactSubcommand::Reject {
             slug,
             artifact,
             reason,
-        } => reject(root, &slug, &artifact, reason, json),
-        ArtifactSubcommand::Draft { slug, artifact } => draft(root, &slug, &artifact, json),
+        } => reject(root, &slug, &artifact, reason, out),
+        ArtifactSubcommand::Draft { slug, artifact } => draft(root, &slug, &artifact, out),
         ArtifactSubcommand::Waive {
             slug,
             artifact,
             reason,
-        } => waive(root, &slug, &artifact, reason, json),
+            expires_at,
+        } => waive(root, &slug, &artifact, reason, expires_at, out),
+        ArtifactSubcommand::History { slug, artifact } => history(root, &slug, &artifact, out),
     }
 }
 
@@ -59,7 +69,7 @@ fn approve(
     slug: &str,
     artifact_str: &str,
     by: Option<String>,
-    json: bool,
+    out: OutputFormat,
 ) -> anyhow::Result<()> {
     let artifact_type = ArtifactType::from_str(artifact_str)
         .with_context(|| format!("unknown artifact type: {artifact_str}"))?;
@@ -68,7 +78,7 @@ fn approve(
         Feature::load(root, slug).with_context(|| format!("feature '{slug}' not found"))?;
 
     feature
-        .approve_artifact(artifact_type, by.clone())
+        .approve_artifact(root, artifact_type, by.clone())
         .with_context(|| format!("failed to approve {artifact_str}"))?;
     feature.save(root).context("failed to save feature")?;
 
@@ -89,24 +99,22 @@ fn approve(
 
     let transitioned_to = try_auto_transition(root, slug);
 
-    if json {
-        let mut val = serde_json::json!({
-            "slug": slug,
-            "artifact": artifact_str,
-            "status": "approved",
-            "by": by,
-        });
-        if let Some(phase) = &transitioned_to {
-            val["transitioned_to"] = serde_json::Value::String(phase.clone());
-        }
-        print_json(&val)?;
-    } else {
+    let mut val = serde_json::json!({
+        "slug": slug,
+        "artifact": artifact_str,
+        "status": "approved",
+        "by": by,
+    });
+    if let Some(phase) = &transitioned_to {
+        val["transitioned_to"] = serde_json::Value::String(phase.clone());
+    }
+    out.emit(&val, || {
         println!("Approved: {slug}/{artifact_str}");
         if let Some(phase) = &transitioned_to {
             println!("Transitioned to: {phase}");
         }
-    }
-    Ok(())
+        Ok(())
+    })
 }
 
 fn reject(
@@ -114,7 +122,7 @@ fn reject(
     slug: &str,
     artifact_str: &str,
     reason: Option<String>,
-    json: bool,
+    out: OutputFormat,
 ) -> anyhow::Result<()> {
     let artifact_type = ArtifactType::from_str(artifact_str)
         .with_context(|| format!("unknown artifact type: {artifact_str}"))?;
@@ -123,24 +131,22 @@ fn reject(
         Feature::load(root, slug).with_context(|| format!("feature '{slug}' not found"))?;
 
     feature
-        .reject_artifact(artifact_type, reason.clone())
+        .reject_artifact(root, artifact_type, reason.clone())
         .with_context(|| format!("failed to reject {artifact_str}"))?;
     feature.save(root).context("failed to save feature")?;
 
     let transitioned_to = try_auto_transition(root, slug);
 
-    if json {
-        let mut val = serde_json::json!({
-            "slug": slug,
-            "artifact": artifact_str,
-            "status": "rejected",
-            "reason": reason,
-        });
-        if let Some(phase) = &transitioned_to {
-            val["transitioned_to"] = serde_json::Value::String(phase.clone());
-        }
-        print_json(&val)?;
-    } else {
+    let mut val = serde_json::json!({
+        "slug": slug,
+        "artifact": artifact_str,
+        "status": "rejected",
+        "reason": reason,
+    });
+    if let Some(phase) = &transitioned_to {
+        val["transitioned_to"] = serde_json::Value::String(phase.clone());
+    }
+    out.emit(&val, || {
         println!("Rejected: {slug}/{artifact_str}");
         if let Some(r) = &reason {
             println!("Reason: {r}");
@@ -148,8 +154,8 @@ fn reject(
         if let Some(phase) = &transitioned_to {
             println!("Transitioned to: {phase}");
         }
-    }
-    Ok(())
+        Ok(())
+    })
 }
 
 fn waive(
@@ -157,51 +163,66 @@ fn waive(
     slug: &str,
     artifact_str: &str,
     reason: Option<String>,
-    json: bool,
+    expires_at: Option<DateTime<Utc>>,
+    out: OutputFormat,
 ) -> anyhow::Result<()> {
     let artifact_type = ArtifactType::from_str(artifact_str)
         .with_context(|| format!("unknown artifact type: {artifact_str}"))?;
+    if expires_at.is_some_and(|at| at <= Utc::now()) {
+        anyhow::bail!("--expires-at must be in the future");
+    }
 
     let mut feature =
         Feature::load(root, slug).with_context(|| format!("feature '{slug}' not found"))?;
 
     feature
-        .waive_artifact(artifact_type, reason.clone())
+        .waive_artifact(root, artifact_type, reason.clone(), expires_at)
         .with_context(|| format!("failed to waive {artifact_str}"))?;
     feature.save(root).context("failed to save feature")?;
 
     let transitioned_to = try_auto_transition(root, slug);
 
-    if json {
-        let mut val = serde_json::json!({
-            "slug": slug,
-            "artifact": artifact_str,
-            "status": "waived",
-            "reason": reason,
-        });
-        if let Some(phase) = &transitioned_to {
-            val["transitioned_to"] = serde_json::Value::String(phase.clone());
-        }
-        print_json(&val)?;
-    } else {
+    let mut val = serde_json::json!({
+        "slug": slug,
+        "artifact": artifact_str,
+        "status": "waived",
+        "reason": reason,
+        "expires_at": expires_at,
+    });
+    if let Some(phase) = &transitioned_to {
+        val["transitioned_to"] = serde_json::Value::String(phase.clone());
+    }
+    out.emit(&val, || {
         println!("Waived: {slug}/{artifact_str}");
         if let Some(r) = &reason {
             println!("Reason: {r}");
         }
+        if let Some(at) = expires_at {
+            println!("Expires: {}", at.format("%Y-%m-%d %H:%M UTC"));
+        }
         if let Some(phase) = &transitioned_to {
             println!("Transitioned to: {phase}");
         }
-    }
-    Ok(())
+        Ok(())
+    })
 }
 
-fn draft(root: &Path, slug: &str, artifact_str: &str, json: bool) -> anyhow::Result<()> {
+fn draft(root: &Path, slug: &str, artifact_str: &str, out: OutputFormat) -> anyhow::Result<()> {
     let artifact_type = ArtifactType::from_str(artifact_str)
         .with_context(|| format!("unknown artifact type: {artifact_str}"))?;
 
     let mut feature =
         Feature::load(root, slug).with_context(|| format!("feature '{slug}' not found"))?;
 
+    let secret_warnings = match feature.artifact(artifact_type) {
+        Some(artifact) if artifact.exists_on_disk(root) => {
+            let content = std::fs::read_to_string(root.join(&artifact.path))
+                .with_context(|| format!("failed to read {}", artifact.path))?;
+            check_for_secrets(root, &format!("{slug}/{artifact_str}"), &content)?
+        }
+        _ => Vec::new(),
+    };
+
     feature
         .mark_artifact_draft(artifact_type)
         .with_context(|| format!("failed to mark {artifact_str} as draft"))?;
@@ -209,21 +230,84 @@ fn draft(root: &Path, slug: &str, artifact_str: &str, json: bool) -> anyhow::Res
 
     let transitioned_to = try_auto_transition(root, slug);
 
-    if json {
-        let mut val = serde_json::json!({
-            "slug": slug,
-            "artifact": artifact_str,
-            "status": "draft",
-        });
-        if let Some(phase) = &transitioned_to {
-            val["transitioned_to"] = serde_json::Value::String(phase.clone());
-        }
-        print_json(&val)?;
-    } else {
+    let mut val = serde_json::json!({
+        "slug": slug,
+        "artifact": artifact_str,
+        "status": "draft",
+    });
+    if let Some(phase) = &transitioned_to {
+        val["transitioned_to"] = serde_json::Value::String(phase.clone());
+    }
+    if !secret_warnings.is_empty() {
+        val["secret_warnings"] = serde_json::json!(secret_warnings);
+    }
+    out.emit(&val, || {
         println!("Marked as draft: {slug}/{artifact_str}");
         if let Some(phase) = &transitioned_to {
             println!("Transitioned to: {phase}");
         }
+        Ok(())
+    })?;
+    for finding in &secret_warnings {
+        eprintln!("warning: {slug}/{artifact_str} may contain a secret at {finding}");
+    }
+    if !secret_warnings.is_empty() {
+        eprintln!("warning: {SECRET_GUIDANCE}");
     }
     Ok(())
 }
+
+/// `--expires-at`: an RFC 3339 timestamp, or a bare date meaning midnight UTC.
+fn parse_expiry(s: &str) -> Result<DateTime<Utc>, String> {
+    if let Ok(at) = DateTime::parse_from_rfc3339(s) {
+        return Ok(at.with_timezone(&Utc));
+    }
+    NaiveDate::parse_from_str(s, "%Y-%m-%d")
+        .map(|d| {
+            d.and_hms_opt(0, 0, 0)
+                .expect("midnight always valid")
+                .and_utc()
+        })
+        .map_err(|_| format!("expected an RFC 3339 time or YYYY-MM-DD, got '{s}'"))
+}
+
+fn history(root: &Path, slug: &str, artifact_str: &str, out: OutputFormat) -> anyhow::Result<()> {
+    let artifact_type = ArtifactType::from_str(artifact_str)
+        .with_context(|| format!("unknown artifact type: {artifact_str}"))?;
+
+    let feature =
+        Feature::load(root, slug).with_context(|| format!("feature '{slug}' not found"))?;
+    let artifact = feature
+        .artifact(artifact_type)
+        .with_context(|| format!("artifact '{artifact_str}' not found on '{slug}'"))?;
+
+    out.emit(
+        &serde_json::json!({
+            "slug": slug,
+            "artifact": artifact_str,
+            "history": artifact.history,
+        }),
+        || {
+            if artifact.history.is_empty() {
+                println!("No decisions recorded for {slug}/{artifact_str}.");
+                return Ok(());
+            }
+
+            let rows: Vec<Vec<String>> = artifact
+                .history
+                .iter()
+                .map(|e| {
+                    vec![
+                        e.at.format("%Y-%m-%d %H:%M").to_string(),
+                        e.decision.to_string(),
+                        e.actor.clone(),
+                        e.version.clone().unwrap_or_else(|| "-".to_string()),
+                        e.reason.clone().unwrap_or_default(),
+                    ]
+                })
+                .collect();
+            print_table(&["AT", "DECISION", "ACTOR", "VERSION", "REASON"], rows);
+            Ok(())
+        },
+    )
+}
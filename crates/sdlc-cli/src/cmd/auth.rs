@@ -1,4 +1,4 @@
-use crate::output::print_table;
+use crate::output::{print_table, OutputFormat};
 use clap::Subcommand;
 use sdlc_core::auth_config;
 use std::path::Path;
@@ -38,7 +38,7 @@ pub enum AuthTokenSubcommand {
 // Entry point
 // ---------------------------------------------------------------------------
 
-pub fn run(root: &Path, subcommand: AuthSubcommand, _json: bool) -> anyhow::Result<()> {
+pub fn run(root: &Path, subcommand: AuthSubcommand, _out: OutputFormat) -> anyhow::Result<()> {
     match subcommand {
         AuthSubcommand::Token { subcommand } => run_token(root, subcommand),
     }
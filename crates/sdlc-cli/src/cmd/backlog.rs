@@ -1,10 +1,11 @@
-use crate::output::{print_json, print_table};
+use crate::output::{print_table, OutputFormat};
 use anyhow::Context;
 use clap::Subcommand;
 use sdlc_core::{
     backlog::{BacklogKind, BacklogStatus, BacklogStore},
     feature::Feature,
     milestone::Milestone,
+    slug,
     state::State,
 };
 use std::path::Path;
@@ -89,7 +90,7 @@ pub enum BacklogSubcommand {
 // Dispatch
 // ---------------------------------------------------------------------------
 
-pub fn run(root: &Path, subcmd: BacklogSubcommand, json: bool) -> anyhow::Result<()> {
+pub fn run(root: &Path, subcmd: BacklogSubcommand, out: OutputFormat) -> anyhow::Result<()> {
     match subcmd {
         BacklogSubcommand::Add {
             title,
@@ -104,30 +105,24 @@ pub fn run(root: &Path, subcmd: BacklogSubcommand, json: bool) -> anyhow::Result
             kind,
             evidence,
             source_feature,
-            json,
+            out,
         ),
 
         BacklogSubcommand::List {
             all,
             status,
             source_feature,
-        } => list(
-            root,
-            all,
-            status.as_deref(),
-            source_feature.as_deref(),
-            json,
-        ),
+        } => list(root, all, status.as_deref(), source_feature.as_deref(), out),
 
-        BacklogSubcommand::Park { id, reason } => park(root, &id, &reason.join(" "), json),
+        BacklogSubcommand::Park { id, reason } => park(root, &id, &reason.join(" "), out),
 
         BacklogSubcommand::Promote {
             id,
             slug,
             milestone,
-        } => promote(root, &id, slug.as_deref(), milestone.as_deref(), json),
+        } => promote(root, &id, slug.as_deref(), milestone.as_deref(), out),
 
-        BacklogSubcommand::Show { id } => show(root, &id, json),
+        BacklogSubcommand::Show { id } => show(root, &id, out),
     }
 }
 
@@ -142,7 +137,7 @@ fn add(
     kind: BacklogKind,
     evidence: Option<String>,
     source_feature: Option<String>,
-    json: bool,
+    out: OutputFormat,
 ) -> anyhow::Result<()> {
     let title = title_words.join(" ");
 
@@ -163,15 +158,14 @@ fn add(
     )
     .context("failed to add backlog item")?;
 
-    if json {
-        print_json(&item)?;
-    } else {
+    out.emit(&item, || {
         let source_display = source_feature.as_deref().unwrap_or("none");
         println!(
             "Backlog item {} recorded: \"{}\" [{}]",
             item.id, item.title, source_display
         );
-    }
+        Ok(())
+    })?;
     Ok(())
 }
 
@@ -180,7 +174,7 @@ fn list(
     all: bool,
     status: Option<&str>,
     source_feature: Option<&str>,
-    json: bool,
+    out: OutputFormat,
 ) -> anyhow::Result<()> {
     let status_filter = if all {
         None
@@ -194,42 +188,38 @@ fn list(
     let items = BacklogStore::list(root, status_filter, source_feature)
         .context("failed to list backlog items")?;
 
-    if json {
-        print_json(&items)?;
-        return Ok(());
-    }
-
-    if items.is_empty() {
-        println!("No backlog items found.");
-        return Ok(());
-    }
+    out.emit(&items, || {
+        if items.is_empty() {
+            println!("No backlog items found.");
+            return Ok(());
+        }
 
-    let rows: Vec<Vec<String>> = items
-        .iter()
-        .map(|item| {
-            vec![
-                item.id.clone(),
-                item.kind.to_string(),
-                item.status.to_string(),
-                item.source_feature.as_deref().unwrap_or("-").to_string(),
-                item.title.clone(),
-            ]
-        })
-        .collect();
-
-    print_table(&["ID", "KIND", "STATUS", "SOURCE", "TITLE"], rows);
-    Ok(())
+        let rows: Vec<Vec<String>> = items
+            .iter()
+            .map(|item| {
+                vec![
+                    item.id.clone(),
+                    item.kind.to_string(),
+                    item.status.to_string(),
+                    item.source_feature.as_deref().unwrap_or("-").to_string(),
+                    item.title.clone(),
+                ]
+            })
+            .collect();
+
+        print_table(&["ID", "KIND", "STATUS", "SOURCE", "TITLE"], rows);
+        Ok(())
+    })
 }
 
-fn park(root: &Path, id: &str, reason: &str, json: bool) -> anyhow::Result<()> {
+fn park(root: &Path, id: &str, reason: &str, out: OutputFormat) -> anyhow::Result<()> {
     let item = BacklogStore::park(root, id, reason.to_string())
         .with_context(|| format!("failed to park backlog item '{id}'"))?;
 
-    if json {
-        print_json(&item)?;
-    } else {
+    out.emit(&item, || {
         println!("Parked {}: {}", item.id, reason);
-    }
+        Ok(())
+    })?;
     Ok(())
 }
 
@@ -238,7 +228,7 @@ fn promote(
     id: &str,
     slug_override: Option<&str>,
     milestone_slug: Option<&str>,
-    json: bool,
+    out: OutputFormat,
 ) -> anyhow::Result<()> {
     let item =
         BacklogStore::get(root, id).with_context(|| format!("backlog item '{id}' not found"))?;
@@ -246,7 +236,7 @@ fn promote(
     let feature_slug = if let Some(s) = slug_override {
         s.to_string()
     } else {
-        slugify(&item.title)
+        slug::derive(&item.title)
     };
 
     // Create the feature
@@ -254,9 +244,8 @@ fn promote(
         .with_context(|| format!("failed to create feature '{feature_slug}'"))?;
 
     // Add to active_features in state
-    let mut state = State::load(root).context("failed to load state")?;
-    state.add_active_feature(&feature_slug);
-    state.save(root).context("failed to save state")?;
+    State::update(root, |state| state.add_active_feature(&feature_slug))
+        .context("failed to update state")?;
 
     // Mark the backlog item as promoted
     let promoted_item = BacklogStore::mark_promoted(root, id, &feature_slug)
@@ -272,55 +261,51 @@ fn promote(
             .with_context(|| format!("failed to save milestone '{ms}'"))?;
     }
 
-    if json {
-        print_json(&promoted_item)?;
-    } else {
+    out.emit(&promoted_item, || {
         println!("Promoted {} → feature: {}", id, feature_slug);
         if let Some(ms) = milestone_slug {
             println!("Added to milestone: {}", ms);
         }
-    }
+        Ok(())
+    })?;
     Ok(())
 }
 
-fn show(root: &Path, id: &str, json: bool) -> anyhow::Result<()> {
+fn show(root: &Path, id: &str, out: OutputFormat) -> anyhow::Result<()> {
     let item =
         BacklogStore::get(root, id).with_context(|| format!("backlog item '{id}' not found"))?;
 
-    if json {
-        print_json(&item)?;
-        return Ok(());
-    }
-
-    println!("ID:      {}", item.id);
-    println!("Kind:    {}", item.kind);
-    println!("Status:  {}", item.status);
-    println!("Title:   {}", item.title);
-    if let Some(desc) = &item.description {
-        println!("Description: {}", desc);
-    }
-    if let Some(ev) = &item.evidence {
-        println!("Evidence:    {}", ev);
-    }
-    if let Some(sf) = &item.source_feature {
-        println!("Source:      {}", sf);
-    }
-    if let Some(reason) = &item.park_reason {
-        println!("Park Reason: {}", reason);
-    }
-    if let Some(promoted_to) = &item.promoted_to {
-        println!("Promoted To: {}", promoted_to);
-    }
-    println!(
-        "Created:     {}",
-        item.created_at.format("%Y-%m-%d %H:%M UTC")
-    );
-    println!(
-        "Updated:     {}",
-        item.updated_at.format("%Y-%m-%d %H:%M UTC")
-    );
+    out.emit(&item, || {
+        println!("ID:      {}", item.id);
+        println!("Kind:    {}", item.kind);
+        println!("Status:  {}", item.status);
+        println!("Title:   {}", item.title);
+        if let Some(desc) = &item.description {
+            println!("Description: {}", desc);
+        }
+        if let Some(ev) = &item.evidence {
+            println!("Evidence:    {}", ev);
+        }
+        if let Some(sf) = &item.source_feature {
+            println!("Source:      {}", sf);
+        }
+        if let Some(reason) = &item.park_reason {
+            println!("Park Reason: {}", reason);
+        }
+        if let Some(promoted_to) = &item.promoted_to {
+            println!("Promoted To: {}", promoted_to);
+        }
+        println!(
+            "Created:     {}",
+            item.created_at.format("%Y-%m-%d %H:%M UTC")
+        );
+        println!(
+            "Updated:     {}",
+            item.updated_at.format("%Y-%m-%d %H:%M UTC")
+        );
 
-    Ok(())
+        Ok(())
+    })
 }
 
 // ---------------------------------------------------------------------------
@@ -348,34 +333,6 @@ fn infer_source_feature(root: &Path) -> Option<String> {
     }
 }
 
-/// Convert a free-form title string to a kebab-case slug, max 40 chars.
-pub fn slugify(title: &str) -> String {
-    // Replace non-alphanumeric chars with dashes, lowercase
-    let raw: String = title
-        .to_lowercase()
-        .chars()
-        .map(|c| if c.is_alphanumeric() { c } else { '-' })
-        .collect();
-
-    // Collapse consecutive dashes and trim leading/trailing dashes
-    let collapsed: String = raw
-        .split('-')
-        .filter(|part| !part.is_empty())
-        .collect::<Vec<_>>()
-        .join("-");
-
-    if collapsed.len() <= 40 {
-        collapsed
-    } else {
-        // Truncate at a word boundary (last dash within 40 chars)
-        let truncated = &collapsed[..40];
-        match truncated.rfind('-') {
-            Some(pos) => collapsed[..pos].to_string(),
-            None => truncated.to_string(),
-        }
-    }
-}
-
 /// Parse a kind string for Clap value_parser.
 fn parse_kind(s: &str) -> Result<BacklogKind, String> {
     match s {
@@ -406,40 +363,6 @@ fn parse_status(s: &str) -> anyhow::Result<BacklogStatus> {
 mod tests {
     use super::*;
 
-    #[test]
-    fn slugify_basic() {
-        assert_eq!(slugify("Fix auth token race"), "fix-auth-token-race");
-    }
-
-    #[test]
-    fn slugify_special_characters() {
-        assert_eq!(
-            slugify("Fix auth.rs: token race! (critical)"),
-            "fix-auth-rs-token-race-critical"
-        );
-    }
-
-    #[test]
-    fn slugify_truncates_at_word_boundary() {
-        let long = "a very long title that exceeds forty characters limit here";
-        let result = slugify(long);
-        assert!(result.len() <= 40, "slug too long: {result}");
-        assert!(!result.ends_with('-'), "slug ends with dash: {result}");
-    }
-
-    #[test]
-    fn slugify_short_no_dash_truncation() {
-        // If there's no dash in the first 40 chars, just truncate at 40
-        let no_dashes = "abcdefghijklmnopqrstuvwxyzabcdefghijklmnopqrstuvwxyz";
-        let result = slugify(no_dashes);
-        assert!(result.len() <= 40);
-    }
-
-    #[test]
-    fn slugify_collapses_multiple_dashes() {
-        assert_eq!(slugify("foo   bar"), "foo-bar");
-    }
-
     #[test]
     fn parse_kind_valid() {
         assert!(matches!(parse_kind("concern"), Ok(BacklogKind::Concern)));
@@ -1,4 +1,4 @@
-use crate::output::print_json;
+use crate::output::OutputFormat;
 use anyhow::{bail, Context};
 use chrono::{DateTime, Duration, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
@@ -185,7 +185,7 @@ fn format_relative(delta: Duration) -> String {
 // Entry point
 // ---------------------------------------------------------------------------
 
-pub fn run(root: &Path, since_str: &str, limit: usize, json: bool) -> anyhow::Result<()> {
+pub fn run(root: &Path, since_str: &str, limit: usize, out: OutputFormat) -> anyhow::Result<()> {
     let since_spec = parse_since(since_str)?;
     let all_runs = load_runs(root);
     let now = Utc::now();
@@ -226,33 +226,31 @@ pub fn run(root: &Path, since_str: &str, limit: usize, json: bool) -> anyhow::Re
         .take(limit)
         .collect();
 
-    if json {
-        let events: Vec<ChangelogEvent> = filtered
-            .iter()
-            .map(|r| {
-                let cat = classify(r);
-                let icon = cat.icon().to_string();
-                ChangelogEvent {
-                    id: r.id.clone(),
-                    icon,
-                    category: cat,
-                    label: r.label.clone(),
-                    run_type: r.run_type.clone(),
-                    status: r.status.clone(),
-                    started_at: r.started_at.clone(),
-                    cost_usd: r.cost_usd,
-                }
-            })
-            .collect();
-        let total = events.len();
-        let output = ChangelogOutput {
-            since: cutoff.to_rfc3339(),
-            limit,
-            total,
-            events,
-        };
-        print_json(&output)?;
-    } else {
+    let events: Vec<ChangelogEvent> = filtered
+        .iter()
+        .map(|r| {
+            let cat = classify(r);
+            let icon = cat.icon().to_string();
+            ChangelogEvent {
+                id: r.id.clone(),
+                icon,
+                category: cat,
+                label: r.label.clone(),
+                run_type: r.run_type.clone(),
+                status: r.status.clone(),
+                started_at: r.started_at.clone(),
+                cost_usd: r.cost_usd,
+            }
+        })
+        .collect();
+    let total = events.len();
+    let output = ChangelogOutput {
+        since: cutoff.to_rfc3339(),
+        limit,
+        total,
+        events,
+    };
+    out.emit(&output, || {
         if filtered.is_empty() {
             println!("No activity in the selected window.");
             return Ok(());
@@ -267,7 +265,8 @@ pub fn run(root: &Path, since_str: &str, limit: usize, json: bool) -> anyhow::Re
             // Left-pad label to 45 chars
             println!("{icon}  {label:<45}  {rel}");
         }
-    }
+        Ok(())
+    })?;
 
     Ok(())
 }
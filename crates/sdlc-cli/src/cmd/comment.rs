@@ -1,8 +1,10 @@
-use crate::output::{print_json, print_table};
+use crate::output::{print_table, OutputFormat};
 use anyhow::Context;
 use clap::Subcommand;
 use sdlc_core::{
-    comment::{add_comment, resolve_comment, CommentFlag, CommentTarget},
+    comment::{
+        add_comment, add_reply, resolve_comment, threads, CommentFlag, CommentTarget, CommentThread,
+    },
     feature::Feature,
     types::ArtifactType,
 };
@@ -26,6 +28,9 @@ pub enum CommentSubcommand {
         /// Author name or agent identifier
         #[arg(long)]
         by: Option<String>,
+        /// Reply to an existing comment (e.g. C1); the reply joins its thread
+        #[arg(long, conflicts_with_all = ["task", "artifact"])]
+        reply_to: Option<String>,
     },
     /// List comments on a feature, optionally filtered to a task
     List {
@@ -34,11 +39,12 @@ pub enum CommentSubcommand {
         #[arg(long)]
         task: Option<String>,
     },
-    /// Resolve (remove) a comment, clearing any pipeline block it caused
+    /// Resolve (remove) a comment and its replies, clearing any pipeline
+    /// block it caused
     Resolve { slug: String, comment_id: String },
 }
 
-pub fn run(root: &Path, subcmd: CommentSubcommand, json: bool) -> anyhow::Result<()> {
+pub fn run(root: &Path, subcmd: CommentSubcommand, out: OutputFormat) -> anyhow::Result<()> {
     match subcmd {
         CommentSubcommand::Create {
             slug,
@@ -47,6 +53,7 @@ pub fn run(root: &Path, subcmd: CommentSubcommand, json: bool) -> anyhow::Result
             artifact,
             flag,
             by,
+            reply_to,
         } => create(
             root,
             &slug,
@@ -55,10 +62,11 @@ pub fn run(root: &Path, subcmd: CommentSubcommand, json: bool) -> anyhow::Result
             artifact.as_deref(),
             flag.as_deref(),
             by.as_deref(),
-            json,
+            reply_to.as_deref(),
+            out,
         ),
-        CommentSubcommand::List { slug, task } => list(root, &slug, task.as_deref(), json),
-        CommentSubcommand::Resolve { slug, comment_id } => resolve(root, &slug, &comment_id, json),
+        CommentSubcommand::List { slug, task } => list(root, &slug, task.as_deref(), out),
+        CommentSubcommand::Resolve { slug, comment_id } => resolve(root, &slug, &comment_id, out),
     }
 }
 
@@ -84,7 +92,8 @@ fn create(
     artifact: Option<&str>,
     flag: Option<&str>,
     by: Option<&str>,
-    json: bool,
+    reply_to: Option<&str>,
+    out: OutputFormat,
 ) -> anyhow::Result<()> {
     let mut feature =
         Feature::load(root, slug).with_context(|| format!("feature '{slug}' not found"))?;
@@ -103,35 +112,53 @@ fn create(
     };
 
     let comment_flag = flag.map(parse_flag).transpose()?;
-    let id = add_comment(
-        &mut feature.comments,
-        &mut feature.next_comment_seq,
-        body,
-        comment_flag,
-        target,
-        by.map(str::to_string),
-    );
+    let id = match reply_to {
+        Some(parent) => add_reply(
+            &mut feature.comments,
+            &mut feature.next_comment_seq,
+            parent,
+            body,
+            comment_flag,
+            by.map(str::to_string),
+        )
+        .with_context(|| format!("comment '{parent}' not found on feature '{slug}'"))?,
+        None => add_comment(
+            &mut feature.comments,
+            &mut feature.next_comment_seq,
+            body,
+            comment_flag,
+            target,
+            by.map(str::to_string),
+        ),
+    };
     feature.save(root).context("failed to save feature")?;
 
-    if json {
-        print_json(&serde_json::json!({
+    out.emit(
+        &serde_json::json!({
             "slug": slug,
             "comment_id": id,
+            "parent_id": reply_to,
             "body": body,
-        }))?;
-    } else {
-        println!("Added comment [{id}]");
-    }
+        }),
+        || {
+            match reply_to {
+                Some(parent) => println!("Added reply [{id}] to [{parent}]"),
+                None => println!("Added comment [{id}]"),
+            }
+            Ok(())
+        },
+    )?;
     Ok(())
 }
 
-fn list(root: &Path, slug: &str, task: Option<&str>, json: bool) -> anyhow::Result<()> {
+fn list(root: &Path, slug: &str, task: Option<&str>, out: OutputFormat) -> anyhow::Result<()> {
     let feature =
         Feature::load(root, slug).with_context(|| format!("feature '{slug}' not found"))?;
 
+    // Replies share their root's target, so filtering keeps threads whole.
     let comments: Vec<_> = feature
         .comments
-        .iter()
+        .into_iter()
         .filter(|c| match task {
             Some(tid) => {
                 matches!(&c.target, CommentTarget::Task { task_id } if task_id == tid)
@@ -139,35 +166,45 @@ fn list(root: &Path, slug: &str, task: Option<&str>, json: bool) -> anyhow::Resu
             None => true,
         })
         .collect();
+    let threads = threads(&comments);
 
-    if json {
-        print_json(&comments)?;
-        return Ok(());
-    }
+    out.emit(&threads, || {
+        if comments.is_empty() {
+            let scope = task.map(|t| format!(" on task {t}")).unwrap_or_default();
+            println!("No comments{scope} for '{slug}'.");
+            return Ok(());
+        }
 
-    if comments.is_empty() {
-        let scope = task.map(|t| format!(" on task {t}")).unwrap_or_default();
-        println!("No comments{scope} for '{slug}'.");
-        return Ok(());
-    }
+        let mut rows: Vec<Vec<String>> = Vec::new();
+        for thread in &threads {
+            thread_rows(thread, 0, &mut rows);
+        }
+        print_table(&["ID", "FLAG", "TARGET", "AUTHOR", "BODY"], rows);
+        Ok(())
+    })
+}
 
-    let rows: Vec<Vec<String>> = comments
-        .iter()
-        .map(|c| {
-            vec![
-                c.id.clone(),
-                c.flag.as_ref().map(|f| f.to_string()).unwrap_or_default(),
-                c.target.to_string(),
-                c.author.clone().unwrap_or_default(),
-                c.body.clone(),
-            ]
-        })
-        .collect();
-    print_table(&["ID", "FLAG", "TARGET", "AUTHOR", "BODY"], rows);
-    Ok(())
+/// One row per comment in `thread`, replies indented beneath their parent.
+fn thread_rows(thread: &CommentThread, depth: usize, rows: &mut Vec<Vec<String>>) {
+    let c = &thread.comment;
+    let id = if depth == 0 {
+        c.id.clone()
+    } else {
+        format!("{}└ {}", "  ".repeat(depth - 1), c.id)
+    };
+    rows.push(vec![
+        id,
+        c.flag.as_ref().map(|f| f.to_string()).unwrap_or_default(),
+        c.target.to_string(),
+        c.author.clone().unwrap_or_default(),
+        c.body.clone(),
+    ]);
+    for reply in &thread.replies {
+        thread_rows(reply, depth + 1, rows);
+    }
 }
 
-fn resolve(root: &Path, slug: &str, comment_id: &str, json: bool) -> anyhow::Result<()> {
+fn resolve(root: &Path, slug: &str, comment_id: &str, out: OutputFormat) -> anyhow::Result<()> {
     let mut feature =
         Feature::load(root, slug).with_context(|| format!("feature '{slug}' not found"))?;
 
@@ -178,14 +215,16 @@ fn resolve(root: &Path, slug: &str, comment_id: &str, json: bool) -> anyhow::Res
 
     feature.save(root).context("failed to save feature")?;
 
-    if json {
-        print_json(&serde_json::json!({
+    out.emit(
+        &serde_json::json!({
             "slug": slug,
             "comment_id": comment_id,
             "resolved": true,
-        }))?;
-    } else {
-        println!("Resolved comment [{comment_id}] on feature '{slug}'.");
-    }
+        }),
+        || {
+            println!("Resolved comment [{comment_id}] on feature '{slug}'.");
+            Ok(())
+        },
+    )?;
     Ok(())
 }
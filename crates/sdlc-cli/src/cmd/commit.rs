@@ -1,4 +1,4 @@
-use crate::output::print_json;
+use crate::output::OutputFormat;
 use anyhow::Context;
 use std::path::Path;
 use std::process::Command;
@@ -28,7 +28,7 @@ fn git_try(root: &Path, args: &[&str]) -> anyhow::Result<(bool, String)> {
     Ok((out.status.success(), stdout))
 }
 
-pub fn run(root: &Path, message: Option<&str>, json: bool) -> anyhow::Result<()> {
+pub fn run(root: &Path, message: Option<&str>, out: OutputFormat) -> anyhow::Result<()> {
     // 1. Verify on main
     let branch = git(root, &["branch", "--show-current"])?;
     if branch != "main" {
@@ -38,15 +38,17 @@ pub fn run(root: &Path, message: Option<&str>, json: bool) -> anyhow::Result<()>
     // 2. Check for changes
     let status = git(root, &["status", "--short"])?;
     if status.is_empty() {
-        if json {
-            print_json(&serde_json::json!({
+        out.emit(
+            &serde_json::json!({
                 "committed": false,
                 "merged": false,
                 "reason": "nothing to commit",
-            }))?;
-        } else {
-            println!("Nothing to commit — working tree clean.");
-        }
+            }),
+            || {
+                println!("Nothing to commit — working tree clean.");
+                Ok(())
+            },
+        )?;
         return Ok(());
     }
 
@@ -62,7 +64,7 @@ pub fn run(root: &Path, message: Option<&str>, json: bool) -> anyhow::Result<()>
     git(root, &["commit", "-m", msg])?;
 
     let commit_sha = git(root, &["rev-parse", "--short", "HEAD"])?;
-    if !json {
+    if !out.is_structured() {
         println!("Committed {commit_sha}: {msg}");
     }
 
@@ -77,34 +79,38 @@ pub fn run(root: &Path, message: Option<&str>, json: bool) -> anyhow::Result<()>
     // 6. Fetch origin
     let (fetch_ok, _) = git_try(root, &["fetch", "origin"])?;
     if !fetch_ok {
-        if json {
-            print_json(&serde_json::json!({
+        out.emit(
+            &serde_json::json!({
                 "committed": true,
                 "commit": commit_sha,
                 "message": msg,
                 "merged": false,
                 "reason": "fetch failed — offline or no remote",
-            }))?;
-        } else {
-            println!("Fetch failed (offline or no remote). Commit is local-only.");
-        }
+            }),
+            || {
+                println!("Fetch failed (offline or no remote). Commit is local-only.");
+                Ok(())
+            },
+        )?;
         return Ok(());
     }
 
     // 7. Check if origin/main exists
     let (has_origin, _) = git_try(root, &["rev-parse", "origin/main"])?;
     if !has_origin {
-        if json {
-            print_json(&serde_json::json!({
+        out.emit(
+            &serde_json::json!({
                 "committed": true,
                 "commit": commit_sha,
                 "message": msg,
                 "merged": false,
                 "reason": "no origin/main",
-            }))?;
-        } else {
-            println!("No origin/main found. Commit is local-only.");
-        }
+            }),
+            || {
+                println!("No origin/main found. Commit is local-only.");
+                Ok(())
+            },
+        )?;
         return Ok(());
     }
 
@@ -119,8 +125,8 @@ pub fn run(root: &Path, message: Option<&str>, json: bool) -> anyhow::Result<()>
 
     if behind == 0 {
         // No divergence — safe to push
-        if json {
-            print_json(&serde_json::json!({
+        out.emit(
+            &serde_json::json!({
                 "committed": true,
                 "commit": commit_sha,
                 "message": msg,
@@ -128,15 +134,17 @@ pub fn run(root: &Path, message: Option<&str>, json: bool) -> anyhow::Result<()>
                 "ahead": ahead,
                 "behind": 0,
                 "status": "ready to push",
-            }))?;
-        } else {
-            println!("Local main is {ahead} ahead, 0 behind origin/main. Ready to push.");
-        }
+            }),
+            || {
+                println!("Local main is {ahead} ahead, 0 behind origin/main. Ready to push.");
+                Ok(())
+            },
+        )?;
         return Ok(());
     }
 
     // 9. Diverged — reconcile
-    if !json {
+    if !out.is_structured() {
         println!("Diverged: {ahead} ahead, {behind} behind origin/main. Reconciling...");
     }
 
@@ -154,8 +162,7 @@ pub fn run(root: &Path, message: Option<&str>, json: bool) -> anyhow::Result<()>
         // Conflict — report and stop
         let conflict_files =
             git(root, &["diff", "--name-only", "--diff-filter=U"]).unwrap_or_default();
-        if json {
-            print_json(&serde_json::json!({
+        out.emit(&serde_json::json!({
                 "committed": true,
                 "commit": commit_sha,
                 "message": msg,
@@ -163,8 +170,7 @@ pub fn run(root: &Path, message: Option<&str>, json: bool) -> anyhow::Result<()>
                 "conflict": true,
                 "conflict_files": conflict_files.lines().collect::<Vec<_>>(),
                 "instructions": "Resolve conflicts, then: git merge --continue && git branch -d dev/xist",
-            }))?;
-        } else {
+            }), || {
             eprintln!("Merge conflict. Conflicting files:");
             for f in conflict_files.lines() {
                 eprintln!("  {f}");
@@ -173,7 +179,8 @@ pub fn run(root: &Path, message: Option<&str>, json: bool) -> anyhow::Result<()>
             eprintln!("Resolve conflicts, then run:");
             eprintln!("  git merge --continue");
             eprintln!("  git branch -d dev/xist");
-        }
+            Ok(())
+        })?;
         return Ok(());
     }
 
@@ -188,8 +195,8 @@ pub fn run(root: &Path, message: Option<&str>, json: bool) -> anyhow::Result<()>
     let final_parts: Vec<&str> = final_counts.split_whitespace().collect();
     let final_ahead: u64 = final_parts.first().unwrap_or(&"0").parse().unwrap_or(0);
 
-    if json {
-        print_json(&serde_json::json!({
+    out.emit(
+        &serde_json::json!({
             "committed": true,
             "commit": commit_sha,
             "message": msg,
@@ -198,15 +205,16 @@ pub fn run(root: &Path, message: Option<&str>, json: bool) -> anyhow::Result<()>
             "ahead": final_ahead,
             "behind": 0,
             "status": "ready to push",
-        }))?;
-    } else {
-        if !merge_out.is_empty() {
-            println!("{merge_out}");
-        }
-        println!("Merged origin/main into local main at {final_sha}.");
-        println!("{final_ahead} ahead, 0 behind. Ready to push.");
-    }
+        }),
+        || {
+            if !merge_out.is_empty() {
+                println!("{merge_out}");
+            }
+            println!("Merged origin/main into local main at {final_sha}.");
+            println!("{final_ahead} ahead, 0 behind. Ready to push.");
+            Ok(())
+        },
+    )?;
 
     Ok(())
 }
-
@@ -1,4 +1,4 @@
-use crate::output::print_json;
+use crate::output::OutputFormat;
 use anyhow::Context;
 use clap::Subcommand;
 use sdlc_core::config::Config;
@@ -19,16 +19,24 @@ pub enum ConfigSubcommand {
         #[arg(long)]
         json: bool,
     },
+
+    /// Show what the config customizes compared to the scaffolded defaults
+    Diff {
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
 }
 
 // ---------------------------------------------------------------------------
 // Entry point
 // ---------------------------------------------------------------------------
 
-pub fn run(root: &Path, subcmd: ConfigSubcommand, json: bool) -> anyhow::Result<()> {
+pub fn run(root: &Path, subcmd: ConfigSubcommand, out: OutputFormat) -> anyhow::Result<()> {
     match subcmd {
-        ConfigSubcommand::Validate => validate(root, json),
-        ConfigSubcommand::Show { json: show_json } => show_config(root, json || show_json),
+        ConfigSubcommand::Validate => validate(root, out),
+        ConfigSubcommand::Show { json } => show_config(root, out.or_json(json)),
+        ConfigSubcommand::Diff { json } => diff(root, out.or_json(json)),
     }
 }
 
@@ -36,42 +44,42 @@ pub fn run(root: &Path, subcmd: ConfigSubcommand, json: bool) -> anyhow::Result<
 // validate
 // ---------------------------------------------------------------------------
 
-fn show_config(root: &Path, json: bool) -> anyhow::Result<()> {
+fn show_config(root: &Path, out: OutputFormat) -> anyhow::Result<()> {
     let config = Config::load(root).context("failed to load config")?;
 
-    if json {
-        print_json(&config)?;
-    } else {
+    out.emit(&config, || {
         // Human-readable: pretty-print as YAML
         let yaml = serde_yaml::to_string(&config).context("failed to serialize config")?;
         print!("{yaml}");
-    }
+        Ok(())
+    })?;
 
     Ok(())
 }
 
-fn validate(root: &Path, json: bool) -> anyhow::Result<()> {
+fn validate(root: &Path, out: OutputFormat) -> anyhow::Result<()> {
     use sdlc_core::config::WarnLevel;
 
     let config = Config::load(root).context("failed to load config")?;
     let warnings = config.validate();
 
-    if json {
-        let value = serde_json::json!({
-            "warnings": warnings,
-        });
-        print_json(&value)?;
-    } else if warnings.is_empty() {
-        println!("Config is valid. No warnings.");
-    } else {
-        for w in &warnings {
-            let prefix = match w.level {
-                WarnLevel::Warning => "warning",
-                WarnLevel::Error => "error",
-            };
-            println!("[{prefix}] {}", w.message);
+    let value = serde_json::json!({
+        "warnings": warnings,
+    });
+    out.emit(&value, || {
+        if warnings.is_empty() {
+            println!("Config is valid. No warnings.");
+        } else {
+            for w in &warnings {
+                let prefix = match w.level {
+                    WarnLevel::Warning => "warning",
+                    WarnLevel::Error => "error",
+                };
+                println!("[{prefix}] {}", w.message);
+            }
         }
-    }
+        Ok(())
+    })?;
 
     let has_errors = warnings.iter().any(|w| w.level == WarnLevel::Error);
     if has_errors {
@@ -80,3 +88,42 @@ fn validate(root: &Path, json: bool) -> anyhow::Result<()> {
 
     Ok(())
 }
+
+// ---------------------------------------------------------------------------
+// diff
+// ---------------------------------------------------------------------------
+
+fn diff(root: &Path, out: OutputFormat) -> anyhow::Result<()> {
+    use sdlc_core::config::ChangeKind;
+
+    let config = Config::load(root).context("failed to load config")?;
+    let changes = config
+        .diff_from_defaults()
+        .context("failed to diff config")?;
+
+    let rows: Vec<serde_json::Value> = changes
+        .iter()
+        .map(|c| {
+            serde_json::json!({
+                "path": c.path,
+                "kind": c.kind(),
+                "default": c.old,
+                "current": c.new,
+            })
+        })
+        .collect();
+    out.emit(&serde_json::json!({ "changes": rows }), || {
+        if changes.is_empty() {
+            println!("Config matches the defaults.");
+        } else {
+            for c in &changes {
+                match c.kind() {
+                    ChangeKind::Added => println!("+ {}: {}", c.path, c.new),
+                    ChangeKind::Removed => println!("- {}: {}", c.path, c.old),
+                    ChangeKind::Changed => println!("~ {}: {} -> {}", c.path, c.old, c.new),
+                }
+            }
+        }
+        Ok(())
+    })
+}
@@ -0,0 +1,55 @@
+use crate::output::{print_table, OutputFormat};
+use anyhow::Context;
+use sdlc_core::doctor::{self, Problem};
+use std::path::Path;
+
+pub fn run(root: &Path, fix: bool, out: OutputFormat) -> anyhow::Result<()> {
+    let mut problems = doctor::diagnose(root).context("failed to inspect .sdlc/")?;
+    let mut fixed = 0;
+    if fix {
+        fixed = doctor::repair(root, &problems).context("failed to apply repairs")?;
+        if fixed > 0 {
+            problems = doctor::diagnose(root).context("failed to re-inspect .sdlc/")?;
+        }
+    }
+
+    let value = serde_json::json!({
+        "fixed": fixed,
+        "problems": problems,
+    });
+    out.emit(&value, || {
+        print_report(&problems, fix, fixed);
+        Ok(())
+    })?;
+
+    if !problems.is_empty() {
+        anyhow::bail!("{} problem(s) remain in .sdlc/", problems.len());
+    }
+    Ok(())
+}
+
+fn print_report(problems: &[Problem], fix: bool, fixed: usize) {
+    if fixed > 0 {
+        println!("Fixed {fixed} problem(s).");
+    }
+    if problems.is_empty() {
+        println!("No problems found in .sdlc/.");
+        return;
+    }
+
+    let rows: Vec<Vec<String>> = problems
+        .iter()
+        .map(|p| {
+            vec![
+                if p.is_fixable() { "fixable" } else { "manual" }.to_string(),
+                p.message.clone(),
+                p.remediation.clone(),
+            ]
+        })
+        .collect();
+    print_table(&["REPAIR", "PROBLEM", "REMEDIATION"], rows);
+
+    if !fix && problems.iter().any(Problem::is_fixable) {
+        println!("\nRun `sdlc doctor --fix` to repair the fixable problems.");
+    }
+}
@@ -1,4 +1,4 @@
-use crate::output::print_table;
+use crate::output::{print_table, OutputFormat};
 use clap::Subcommand;
 use sdlc_core::escalation::{self, EscalationKind};
 use std::path::Path;
@@ -55,7 +55,7 @@ pub enum EscalateSubcommand {
 // Entry point
 // ---------------------------------------------------------------------------
 
-pub fn run(root: &Path, subcommand: EscalateSubcommand, json: bool) -> anyhow::Result<()> {
+pub fn run(root: &Path, subcommand: EscalateSubcommand, out: OutputFormat) -> anyhow::Result<()> {
     match subcommand {
         EscalateSubcommand::Create {
             kind,
@@ -65,9 +65,7 @@ pub fn run(root: &Path, subcommand: EscalateSubcommand, json: bool) -> anyhow::R
         } => {
             let kind: EscalationKind = kind.parse()?;
             let item = escalation::create(root, kind, &title, &context, feature.as_deref())?;
-            if json {
-                crate::output::print_json(&item)?;
-            } else {
+            out.emit(&item, || {
                 println!("created escalation {}", item.id);
                 if let Some(slug) = &item.source_feature {
                     println!(
@@ -81,42 +79,39 @@ pub fn run(root: &Path, subcommand: EscalateSubcommand, json: bool) -> anyhow::R
                     "The escalation will appear in the Dashboard under \"Needs Your Attention\"."
                 );
                 println!("After creating an escalation, stop the current run — the human must act first.");
-            }
+                Ok(())
+            })?;
             Ok(())
         }
 
         EscalateSubcommand::List { status } => {
             let items = escalation::list(root, Some(status.as_str()))?;
-            if json {
-                crate::output::print_json(&items)?;
-                return Ok(());
-            }
-            if items.is_empty() {
-                println!("no escalations (status: {status})");
-                return Ok(());
-            }
-            print_table(
-                &["ID", "KIND", "STATUS", "TITLE"],
-                items
-                    .iter()
-                    .map(|e| {
-                        vec![
-                            e.id.clone(),
-                            e.kind.to_string(),
-                            e.status.to_string(),
-                            truncate(&e.title, 60),
-                        ]
-                    })
-                    .collect(),
-            );
-            Ok(())
+            out.emit(&items, || {
+                if items.is_empty() {
+                    println!("no escalations (status: {status})");
+                    return Ok(());
+                }
+                print_table(
+                    &["ID", "KIND", "STATUS", "TITLE"],
+                    items
+                        .iter()
+                        .map(|e| {
+                            vec![
+                                e.id.clone(),
+                                e.kind.to_string(),
+                                e.status.to_string(),
+                                truncate(&e.title, 60),
+                            ]
+                        })
+                        .collect(),
+                );
+                Ok(())
+            })
         }
 
         EscalateSubcommand::Show { id } => {
             let item = escalation::get(root, &id)?;
-            if json {
-                crate::output::print_json(&item)?;
-            } else {
+            out.emit(&item, || {
                 println!("ID:      {}", item.id);
                 println!("Kind:    {}", item.kind);
                 println!("Status:  {}", item.status);
@@ -128,21 +123,24 @@ pub fn run(root: &Path, subcommand: EscalateSubcommand, json: bool) -> anyhow::R
                 if let Some(res) = &item.resolution {
                     println!("Resolved: {res}");
                 }
+                if let Some(slug) = &item.spawned_feature {
+                    println!("Became:  feature {slug}");
+                }
                 println!("Created: {}", item.created_at.format("%Y-%m-%d %H:%M UTC"));
-            }
+                Ok(())
+            })?;
             Ok(())
         }
 
         EscalateSubcommand::Resolve { id, resolution } => {
             let item = escalation::resolve(root, &id, &resolution)?;
-            if json {
-                crate::output::print_json(&item)?;
-            } else {
+            out.emit(&item, || {
                 println!("resolved escalation {}", item.id);
                 if let Some(slug) = &item.source_feature {
                     println!("  blocker comment removed from '{slug}'");
                 }
-            }
+                Ok(())
+            })?;
             Ok(())
         }
     }
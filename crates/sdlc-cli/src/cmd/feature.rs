@@ -1,21 +1,45 @@
-use crate::output::{print_json, print_table};
+use crate::output::{print_table, OutputFormat};
 use anyhow::Context;
 use clap::Subcommand;
-use sdlc_core::{config::Config, feature::Feature, paths, state::State, types::Phase};
+use sdlc_core::{
+    config::Config,
+    escalation::{self, EscalationStatus},
+    feature::Feature,
+    feature_template, paths, slug,
+    state::State,
+    types::{FeatureTrack, Phase, Priority},
+};
 use std::collections::HashMap;
 use std::path::Path;
 use std::str::FromStr;
 
+/// Default per-artifact cap for `feature show --with-artifacts`.
+const DEFAULT_ARTIFACT_CAP: usize = 64 * 1024;
+
 #[derive(Subcommand)]
 pub enum FeatureSubcommand {
     /// Create a new feature
     Create {
-        slug: String,
+        /// Derived from --title when omitted
+        slug: Option<String>,
         #[arg(long)]
         title: Option<String>,
         /// Optional one-liner description of the feature's intent
         #[arg(long)]
         description: Option<String>,
+        /// Pipeline to run: `full` (default) or `lightweight` (spec → implementation → review → merge)
+        #[arg(long)]
+        track: Option<String>,
+        /// `normal` (default) or `hotfix` (focused ahead of milestone order)
+        #[arg(long)]
+        priority: Option<String>,
+        /// Seed description and tasks from a template (built-in or `.sdlc/templates/<name>.yaml`)
+        #[arg(long)]
+        template: Option<String>,
+        /// Build the feature from an open escalation: its title and context
+        /// seed the feature, and the escalation is resolved pointing at it
+        #[arg(long, value_name = "ID")]
+        from_escalation: Option<String>,
     },
     /// List all features
     List {
@@ -24,11 +48,42 @@ pub enum FeatureSubcommand {
         phase: Option<String>,
     },
     /// Show feature details
-    Show { slug: String },
+    Show {
+        slug: String,
+        /// Inline each existing artifact's content (capped per artifact)
+        #[arg(long)]
+        with_artifacts: bool,
+        /// Per-artifact size cap in bytes for --with-artifacts
+        #[arg(long, default_value_t = DEFAULT_ARTIFACT_CAP, requires = "with_artifacts")]
+        max_artifact_bytes: usize,
+    },
     /// Transition a feature to a new phase
     Transition { slug: String, phase: String },
     /// Archive a feature
     Archive { slug: String },
+    /// Restore an archived feature and re-link it to its milestone
+    Restore { slug: String },
+    /// Mark a feature as a hotfix so focus picks it ahead of milestone order
+    Promote {
+        slug: String,
+        /// Return the feature to normal priority instead
+        #[arg(long)]
+        normal: bool,
+    },
+    /// Move some tasks of an oversized feature into a new feature
+    Split {
+        slug: String,
+        new_slug: String,
+        /// Comma-separated ids of the tasks to move (e.g. T3,T4)
+        #[arg(long, value_delimiter = ',', required = true)]
+        tasks: Vec<String>,
+        /// Title for the new feature (default: derived from the slug)
+        #[arg(long)]
+        title: Option<String>,
+        /// Make the new feature depend on the original
+        #[arg(long)]
+        depends_on_source: bool,
+    },
     /// Update feature metadata (title, description)
     Update {
         slug: String,
@@ -45,17 +100,78 @@ pub enum FeatureSubcommand {
     },
 }
 
-pub fn run(root: &Path, subcmd: FeatureSubcommand, json: bool) -> anyhow::Result<()> {
+pub fn run(root: &Path, subcmd: FeatureSubcommand, out: OutputFormat) -> anyhow::Result<()> {
     match subcmd {
         FeatureSubcommand::Create {
             slug,
             title,
             description,
-        } => create(root, &slug, title, description, json),
-        FeatureSubcommand::List { phase } => list(root, phase.as_deref(), json),
-        FeatureSubcommand::Show { slug } => show(root, &slug, json),
-        FeatureSubcommand::Transition { slug, phase } => transition(root, &slug, &phase, json),
-        FeatureSubcommand::Archive { slug } => archive(root, &slug, json),
+            track,
+            priority,
+            template,
+            from_escalation,
+        } => {
+            let (mut title, mut description) = (title, description);
+            if let Some(id) = &from_escalation {
+                let item = escalation::get(root, id)?;
+                if item.status != EscalationStatus::Open {
+                    anyhow::bail!("escalation {id} is already resolved");
+                }
+                title.get_or_insert_with(|| item.title.clone());
+                description.get_or_insert_with(|| item.feature_description());
+            }
+            let slug = match (slug, &title) {
+                (Some(slug), _) => slug,
+                (None, Some(title)) => slug::derive(title),
+                (None, None) => anyhow::bail!("provide a slug or --title"),
+            };
+            create(
+                root,
+                &slug,
+                title,
+                description,
+                track.as_deref(),
+                priority.as_deref(),
+                template.as_deref(),
+                from_escalation.as_deref(),
+                out,
+            )
+        }
+        FeatureSubcommand::List { phase } => list(root, phase.as_deref(), out),
+        FeatureSubcommand::Show {
+            slug,
+            with_artifacts,
+            max_artifact_bytes,
+        } => {
+            let cap = with_artifacts.then_some(max_artifact_bytes);
+            show(root, &slug, cap, out)
+        }
+        FeatureSubcommand::Transition { slug, phase } => transition(root, &slug, &phase, out),
+        FeatureSubcommand::Archive { slug } => archive(root, &slug, out),
+        FeatureSubcommand::Restore { slug } => restore(root, &slug, out),
+        FeatureSubcommand::Promote { slug, normal } => {
+            let priority = if normal {
+                Priority::Normal
+            } else {
+                Priority::Hotfix
+            };
+            promote(root, &slug, priority, out)
+        }
+        FeatureSubcommand::Split {
+            slug,
+            new_slug,
+            tasks,
+            title,
+            depends_on_source,
+        } => split(
+            root,
+            &slug,
+            &new_slug,
+            title,
+            &tasks,
+            depends_on_source,
+            out,
+        ),
         FeatureSubcommand::Update {
             slug,
             title,
@@ -69,36 +185,73 @@ pub fn run(root: &Path, subcmd: FeatureSubcommand, json: bool) -> anyhow::Result
             description.as_deref(),
             &depends_on,
             clear_depends_on,
-            json,
+            out,
         ),
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 fn create(
     root: &Path,
     slug: &str,
     title: Option<String>,
     description: Option<String>,
-    json: bool,
+    track: Option<&str>,
+    priority: Option<&str>,
+    template: Option<&str>,
+    from_escalation: Option<&str>,
+    out: OutputFormat,
 ) -> anyhow::Result<()> {
+    let track = track
+        .map(FeatureTrack::from_str)
+        .transpose()?
+        .unwrap_or_default();
+    let priority = priority
+        .map(Priority::from_str)
+        .transpose()?
+        .unwrap_or_default();
+    // Resolve the template before creating anything so a bad name leaves no trace.
+    let template = template
+        .map(|name| feature_template::load(root, name))
+        .transpose()?;
     let title = title.unwrap_or_else(|| slug.replace('-', " "));
-    let feature = Feature::create_with_description(root, slug, &title, description)
+    let mut feature = Feature::create_with_track(root, slug, &title, description, track)
         .with_context(|| format!("failed to create feature '{slug}'"))?;
+    if let Some(template) = &template {
+        template.apply(&mut feature);
+    }
+    if template.is_some() || priority != Priority::Normal {
+        feature.priority = priority;
+        feature.save(root).context("failed to save feature")?;
+    }
 
-    let mut state = State::load(root).context("failed to load state")?;
-    state.add_active_feature(slug);
-    state.save(root).context("failed to save state")?;
+    State::update(root, |state| state.add_active_feature(slug))
+        .context("failed to update state")?;
+    if let Some(id) = from_escalation {
+        escalation::resolve_into_feature(root, id, slug)
+            .with_context(|| format!("failed to resolve escalation {id}"))?;
+        feature = Feature::load(root, slug)?;
+    }
 
-    if json {
-        print_json(&feature)?;
-    } else {
+    out.emit(&feature, || {
         println!("Created feature: {slug} — {title}");
+        if let Some(id) = from_escalation {
+            println!("Resolved escalation {id} into this feature");
+        }
+        if let Some(template) = &template {
+            println!(
+                "Seeded {} task(s) from template '{}'",
+                template.tasks.len(),
+                template.name
+            );
+        }
         println!("Next: sdlc next --for {slug}");
-    }
+        Ok(())
+    })?;
     Ok(())
 }
 
-fn list(root: &Path, phase_filter: Option<&str>, json: bool) -> anyhow::Result<()> {
+fn list(root: &Path, phase_filter: Option<&str>, out: OutputFormat) -> anyhow::Result<()> {
     let phase = phase_filter
         .map(Phase::from_str)
         .transpose()
@@ -109,87 +262,126 @@ fn list(root: &Path, phase_filter: Option<&str>, json: bool) -> anyhow::Result<(
         features.retain(|f| f.phase == p);
     }
 
-    if json {
-        let summaries: Vec<_> = features
-            .iter()
-            .map(|f| {
-                serde_json::json!({
-                    "slug": f.slug,
-                    "title": f.title,
-                    "description": f.description,
-                    "phase": f.phase.to_string(),
-                    "blocked": f.is_blocked(),
-                    "archived": f.archived,
-                })
-            })
-            .collect();
-        print_json(&summaries)?;
-        return Ok(());
-    }
-
-    if features.is_empty() {
-        println!("No features yet.");
-        return Ok(());
-    }
-
-    let rows: Vec<Vec<String>> = features
+    let summaries: Vec<_> = features
         .iter()
         .map(|f| {
-            vec![
-                f.slug.clone(),
-                f.phase.to_string(),
-                if f.archived {
-                    "archived".to_string()
-                } else {
-                    String::new()
-                },
-                f.title.clone(),
-            ]
+            serde_json::json!({
+                "slug": f.slug,
+                "title": f.title,
+                "description": f.description,
+                "phase": f.phase.to_string(),
+                "blocked": f.is_blocked(),
+                "archived": f.archived,
+            })
         })
         .collect();
-    print_table(&["SLUG", "PHASE", "STATUS", "TITLE"], rows);
-    Ok(())
+    out.emit(&summaries, || {
+        if features.is_empty() {
+            println!("No features yet.");
+            return Ok(());
+        }
+
+        let rows: Vec<Vec<String>> = features
+            .iter()
+            .map(|f| {
+                vec![
+                    f.slug.clone(),
+                    f.phase.to_string(),
+                    if f.archived {
+                        "archived".to_string()
+                    } else {
+                        String::new()
+                    },
+                    f.title.clone(),
+                ]
+            })
+            .collect();
+        print_table(&["SLUG", "PHASE", "STATUS", "TITLE"], rows);
+        Ok(())
+    })
 }
 
-fn show(root: &Path, slug: &str, json: bool) -> anyhow::Result<()> {
+/// `artifact_cap` is `Some` when `--with-artifacts` was passed.
+fn show(
+    root: &Path,
+    slug: &str,
+    artifact_cap: Option<usize>,
+    out: OutputFormat,
+) -> anyhow::Result<()> {
     let feature =
         Feature::load(root, slug).with_context(|| format!("feature '{slug}' not found"))?;
 
-    if json {
-        print_json(&feature)?;
-        return Ok(());
+    let mut contents = Vec::new();
+    if let Some(cap) = artifact_cap {
+        for artifact in &feature.artifacts {
+            let content = artifact
+                .read_capped(root, cap)
+                .with_context(|| format!("failed to read {}", artifact.path))?;
+            contents.push(content);
+        }
     }
 
-    println!("Feature: {} — {}", feature.slug, feature.title);
-    if let Some(ref desc) = feature.description {
-        println!("Desc:    {desc}");
+    let mut json = serde_json::to_value(&feature)?;
+    if let Some(items) = json["artifacts"].as_array_mut() {
+        for (item, content) in items.iter_mut().zip(&contents) {
+            if let Some(c) = content {
+                item["content"] = serde_json::json!(c.text);
+                item["content_bytes"] = serde_json::json!(c.bytes);
+                item["truncated"] = serde_json::json!(c.truncated);
+            }
+        }
     }
-    println!("Phase:   {}", feature.phase);
-    println!("Created: {}", feature.created_at.format("%Y-%m-%d %H:%M"));
+    out.emit(&json, || {
+        println!("Feature: {} — {}", feature.slug, feature.title);
+        if let Some(ref desc) = feature.description {
+            println!("Desc:    {desc}");
+        }
+        println!("Phase:   {}", feature.phase);
+        if feature.track != FeatureTrack::Full {
+            println!("Track:   {}", feature.track);
+        }
+        if feature.priority != Priority::Normal {
+            println!("Priority: {}", feature.priority);
+        }
+        println!("Created: {}", feature.created_at.format("%Y-%m-%d %H:%M"));
 
-    println!("\nArtifacts:");
-    for artifact in &feature.artifacts {
-        println!("  {:<15} {}", artifact.artifact_type, artifact.status);
-    }
+        println!("\nArtifacts:");
+        for artifact in &feature.artifacts {
+            println!("  {:<15} {}", artifact.artifact_type, artifact.status);
+        }
 
-    if !feature.tasks.is_empty() {
-        println!("\nTasks ({}):", feature.tasks.len());
-        for task in &feature.tasks {
-            println!("  [{}] {} — {}", task.id, task.status, task.title);
+        for (artifact, content) in feature.artifacts.iter().zip(&contents) {
+            if let Some(c) = content {
+                println!(
+                    "\n── {} ({}) ──────────────────────────────",
+                    artifact.artifact_type, artifact.status
+                );
+                print!("{}", c.text);
+                if !c.text.ends_with('\n') {
+                    println!();
+                }
+            }
         }
-    }
 
-    if feature.is_blocked() {
-        println!("\nBlockers:");
-        for b in &feature.blockers {
-            println!("  - {b}");
+        if !feature.tasks.is_empty() {
+            println!("\nTasks ({}):", feature.tasks.len());
+            for task in &feature.tasks {
+                println!("  [{}] {} — {}", task.id, task.status, task.title);
+            }
         }
-    }
 
-    Ok(())
+        if feature.is_blocked() {
+            println!("\nBlockers:");
+            for b in &feature.blockers {
+                println!("  - {b}");
+            }
+        }
+
+        Ok(())
+    })
 }
 
-fn transition(root: &Path, slug: &str, phase_str: &str, json: bool) -> anyhow::Result<()> {
+fn transition(root: &Path, slug: &str, phase_str: &str, out: OutputFormat) -> anyhow::Result<()> {
     let target =
         Phase::from_str(phase_str).with_context(|| format!("unknown phase: {phase_str}"))?;
 
@@ -202,23 +394,65 @@ fn transition(root: &Path, slug: &str, phase_str: &str, json: bool) -> anyhow::R
         .with_context(|| format!("cannot transition '{slug}' to {phase_str}"))?;
     feature.save(root).context("failed to save feature")?;
 
-    let mut state = State::load(root).context("failed to load state")?;
-    state.record_action(
-        slug,
-        sdlc_core::types::ActionType::ImplementTask,
-        target,
-        "transition",
-    );
-    state.save(root).context("failed to save state")?;
-
-    if json {
-        print_json(&serde_json::json!({
+    State::update(root, |state| {
+        state.record_action(
+            slug,
+            sdlc_core::types::ActionType::ImplementTask,
+            target,
+            "transition",
+        );
+    })
+    .context("failed to update state")?;
+
+    out.emit(
+        &serde_json::json!({
             "slug": slug,
             "phase": target.to_string(),
-        }))?;
-    } else {
-        println!("Transitioned '{slug}' to {target}");
-    }
+        }),
+        || {
+            println!("Transitioned '{slug}' to {target}");
+            Ok(())
+        },
+    )?;
+    Ok(())
+}
+
+fn split(
+    root: &Path,
+    slug: &str,
+    new_slug: &str,
+    title: Option<String>,
+    tasks: &[String],
+    depends_on_source: bool,
+    out: OutputFormat,
+) -> anyhow::Result<()> {
+    let title = title.unwrap_or_else(|| new_slug.replace('-', " "));
+    let tasks: Vec<String> = tasks.iter().map(|t| t.trim().to_string()).collect();
+    let report = Feature::split(root, slug, new_slug, &title, &tasks, depends_on_source)
+        .with_context(|| format!("failed to split feature '{slug}'"))?;
+
+    out.emit(&report, || {
+        println!(
+            "Split {} task(s) from '{slug}' into: {new_slug} — {title}",
+            report.moved_tasks.len()
+        );
+        println!("  tasks: {}", report.moved_tasks.join(", "));
+        if !report.copied_artifacts.is_empty() {
+            let copied: Vec<_> = report
+                .copied_artifacts
+                .iter()
+                .map(|a| a.to_string())
+                .collect();
+            println!("  copied as draft: {}", copied.join(", "));
+        }
+        if !report.milestones.is_empty() {
+            println!("  milestone: {}", report.milestones.join(", "));
+        }
+        if depends_on_source {
+            println!("  depends_on: {slug}");
+        }
+        Ok(())
+    })?;
     Ok(())
 }
 
@@ -229,7 +463,7 @@ fn update(
     description: Option<&str>,
     depends_on: &[String],
     clear_depends_on: bool,
-    json: bool,
+    out: OutputFormat,
 ) -> anyhow::Result<()> {
     let mut feature =
         Feature::load(root, slug).with_context(|| format!("feature '{slug}' not found"))?;
@@ -274,44 +508,85 @@ fn update(
     }
     feature.save(root).context("failed to save feature")?;
 
-    if json {
-        print_json(&serde_json::json!({
+    out.emit(
+        &serde_json::json!({
             "slug": slug,
             "title": feature.title,
             "description": feature.description,
             "dependencies": feature.dependencies,
-        }))?;
-    } else {
-        println!("Updated feature: {slug}");
-        if let Some(t) = title {
-            println!("  title: {t}");
-        }
-        if let Some(d) = description {
-            println!("  description: {d}");
-        }
-        if !depends_on.is_empty() {
-            println!("  depends_on: {}", feature.dependencies.join(", "));
-        } else if clear_depends_on {
-            println!("  depends_on: (cleared)");
-        }
-    }
+        }),
+        || {
+            println!("Updated feature: {slug}");
+            if let Some(t) = title {
+                println!("  title: {t}");
+            }
+            if let Some(d) = description {
+                println!("  description: {d}");
+            }
+            if !depends_on.is_empty() {
+                println!("  depends_on: {}", feature.dependencies.join(", "));
+            } else if clear_depends_on {
+                println!("  depends_on: (cleared)");
+            }
+            Ok(())
+        },
+    )?;
+    Ok(())
+}
+
+fn archive(root: &Path, slug: &str, out: OutputFormat) -> anyhow::Result<()> {
+    Feature::archive(root, slug).with_context(|| format!("failed to archive feature '{slug}'"))?;
+
+    out.emit(
+        &serde_json::json!({ "slug": slug, "archived": true }),
+        || {
+            println!("Archived feature: {slug}");
+            Ok(())
+        },
+    )?;
     Ok(())
 }
 
-fn archive(root: &Path, slug: &str, json: bool) -> anyhow::Result<()> {
+fn restore(root: &Path, slug: &str, out: OutputFormat) -> anyhow::Result<()> {
+    let report = Feature::restore(root, slug)
+        .with_context(|| format!("failed to restore feature '{slug}'"))?;
+
+    out.emit(
+        &serde_json::json!({
+            "slug": slug,
+            "archived": false,
+            "milestone": report.milestone,
+            "warnings": report.warnings,
+        }),
+        || {
+            for warning in &report.warnings {
+                eprintln!("warning: {warning}");
+            }
+            match report.milestone {
+                Some(ms) => println!("Restored feature: {slug} (milestone: {ms})"),
+                None => println!("Restored feature: {slug}"),
+            }
+            Ok(())
+        },
+    )?;
+    Ok(())
+}
+
+fn promote(root: &Path, slug: &str, priority: Priority, out: OutputFormat) -> anyhow::Result<()> {
     let mut feature =
         Feature::load(root, slug).with_context(|| format!("feature '{slug}' not found"))?;
-    feature.archived = true;
+    feature.priority = priority;
     feature.save(root).context("failed to save feature")?;
 
-    let mut state = State::load(root).context("failed to load state")?;
-    state.remove_active_feature(slug);
-    state.save(root).context("failed to save state")?;
-
-    if json {
-        print_json(&serde_json::json!({ "slug": slug, "archived": true }))?;
-    } else {
-        println!("Archived feature: {slug}");
-    }
+    out.emit(
+        &serde_json::json!({
+            "slug": slug,
+            "priority": priority,
+        }),
+        || {
+            println!("Set '{slug}' priority to {priority}");
+            Ok(())
+        },
+    )?;
     Ok(())
 }
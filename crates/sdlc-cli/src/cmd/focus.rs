@@ -1,23 +1,24 @@
-use crate::output::print_json;
+use crate::output::OutputFormat;
 use anyhow::Context;
 use sdlc_core::focus::focus;
+use sdlc_core::types::Priority;
 use std::path::Path;
 
-pub fn run(root: &Path, json: bool) -> anyhow::Result<()> {
+pub fn run(root: &Path, out: OutputFormat) -> anyhow::Result<()> {
     let result = focus(root).context("failed to determine focus")?;
 
     match result {
         None => {
-            if json {
-                print_json(&serde_json::Value::Null)?;
-            } else {
+            out.emit(&serde_json::Value::Null, || {
                 println!("Nothing to work on. All features are done, blocked, or waiting.");
-            }
+                Ok(())
+            })?;
         }
         Some(r) => {
-            if json {
-                print_json(&r)?;
-            } else {
+            out.emit(&r, || {
+                if r.priority == Priority::Hotfix {
+                    print!("[HOTFIX] ");
+                }
                 print!(
                     "Next: {} [{}] → {}",
                     r.classification.feature,
@@ -33,7 +34,8 @@ pub fn run(root: &Path, json: bool) -> anyhow::Result<()> {
                 if !r.classification.next_command.is_empty() {
                     println!("  Run: {}", r.classification.next_command);
                 }
-            }
+                Ok(())
+            })?;
         }
     }
 
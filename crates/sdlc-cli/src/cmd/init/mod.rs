@@ -41,16 +41,7 @@ pub fn run(root: &Path, platform: Option<&str>) -> anyhow::Result<()> {
     println!("Initializing SDLC in: {}", root.display());
 
     // 1. Create .sdlc directory structure
-    let dirs = [
-        paths::SDLC_DIR,
-        paths::FEATURES_DIR,
-        paths::PATTERNS_DIR,
-        paths::AUDITS_DIR,
-        paths::BRANCHES_DIR,
-        paths::ARCHIVES_DIR,
-        paths::ROADMAP_DIR,
-    ];
-    for dir in dirs {
+    for dir in paths::SCAFFOLD_DIRS {
         let p = root.join(dir);
         io::ensure_dir(&p).with_context(|| format!("failed to create {}", p.display()))?;
     }
@@ -291,6 +282,12 @@ fn scaffold_masquerade(root: &Path) -> anyhow::Result<()> {
         let path = platform_dir.join(filename);
         let created = io::write_if_missing(&path, content.as_bytes())?;
         if created {
+            // The run directive tells users to execute these directly.
+            #[cfg(unix)]
+            {
+                use std::os::unix::fs::PermissionsExt;
+                std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755))?;
+            }
             println!("  created: .sdlc/platform/{filename}");
         } else {
             println!("  exists:  .sdlc/platform/{filename}");
@@ -937,6 +934,9 @@ pub fn write_core_tools(root: &Path) -> anyhow::Result<()> {
     append_gitignore_entry(root, ".sdlc/telemetry.redb")?;
     append_gitignore_entry(root, ".sdlc/orchestrator.redb")?;
 
+    // .gitignore — transient advisory lock held during state read-modify-write
+    append_gitignore_entry(root, ".sdlc/state.lock")?;
+
     // .gitignore — ensure plain env files are never committed
     // (.sdlc/secrets/envs/*.age and *.meta.yaml are safe to commit)
     append_gitignore_entry(root, ".env")?;
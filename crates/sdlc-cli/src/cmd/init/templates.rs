@@ -169,26 +169,39 @@ triggering "missing field `id`" schema errors on next load).
 
 | Action | Command |
 |---|---|
-| Create feature | `sdlc feature create <slug> --title "…"` |
+| Create feature | `sdlc feature create [<slug>] --title "…" [--track lightweight] [--template <name>]` |
 | Get next action | `sdlc next --for <slug> --json` |
+| Follow next action as it changes | `sdlc next --for <slug> --watch [--json]` |
 | Write artifact | Write Markdown to `output_path` from the directive |
 | Submit draft | `sdlc artifact draft <slug> <type>` |
 | Approve artifact | `sdlc artifact approve <slug> <type>` |
 | Reject artifact | `sdlc artifact reject <slug> <type>` |
-| Merge (release feature) | `sdlc merge <slug>` |
+| Merge (release feature) | `sdlc merge <slug> [--no-verify]` |
 | Commit to main + reconcile | `sdlc commit [-m "message"]` |
 | Add task | `sdlc task add <slug> "title"` |
 | Start task | `sdlc task start <slug> <task-id>` |
 | Complete task | `sdlc task complete <slug> <task-id>` |
 | Block task | `sdlc task block <slug> <task-id> "reason"` |
 | Add comment | `sdlc comment create <slug> "body"` |
+| Reply to comment | `sdlc comment create <slug> "body" --reply-to <comment-id>` |
 | Show feature | `sdlc feature show <slug> --json` |
+| Restore archived feature | `sdlc feature restore <slug>` |
+| Split tasks into a new feature | `sdlc feature split <slug> <new-slug> --tasks <id,id>` |
+| Jump a hotfix ahead of milestone order | `sdlc feature promote <slug>` |
 | List tasks | `sdlc task list <slug>` |
+| Reorder tasks | `sdlc task reorder <slug> <task-id>...` |
+| Sync tasks from tasks.md checklist | `sdlc task sync-from-artifact <slug>` |
 | Project state | `sdlc state` |
+| Stream state for large projects (JSONL) | `sdlc state --jsonl` |
+| Pick an output format (table, json, jsonl) | `sdlc <command> --format jsonl` |
 | Parallel work queue | `sdlc parallel-work --json` |
+| Features startable now (dependencies released) | `sdlc query ready [--include-blocked-by]` |
 | Survey milestone waves | `sdlc project prepare [--milestone <slug>]` |
 | Mark milestone prepared | `sdlc milestone mark-prepared <slug>` |
+| Export / import milestone bundle | `sdlc milestone export <slug> [--out <path>]` / `sdlc milestone import <path>` |
 | Project phase | `sdlc project status` |
+| Audit history | `sdlc project history [--feature <slug>]` |
+| Follow an agent run's transcript | `sdlc agent logs <run-id> [--follow] [--json]` |
 | Escalate to human | `sdlc escalate create --kind <kind> --title "…" --context "…" [--feature <slug>]` |
 | List escalations | `sdlc escalate list` |
 | Resolve escalation | `sdlc escalate resolve <id> "resolution note"` |
@@ -228,6 +241,10 @@ Encrypted secrets live in `.sdlc/secrets/`. The encrypted files (`.age`) and key
 name sidecars (`.meta.yaml`) are **safe to commit**. Plain `.env.*` files must never
 be committed — they are gitignored automatically.
 
+With `secrets: { backend: env }` in `.sdlc/config.yaml`, values are read from
+`SDLC_SECRET_<ENV>__<KEY>` environment variables instead; `names` and `export` work
+the same either way.
+
 | Action | Command |
 |---|---|
 | List environments | `sdlc secrets env list` |
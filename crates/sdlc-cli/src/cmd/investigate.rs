@@ -1,4 +1,4 @@
-use crate::output::{print_json, print_table};
+use crate::output::{print_table, OutputFormat};
 use anyhow::Context;
 use clap::Subcommand;
 use sdlc_core::investigation::{self, InvestigationEntry, InvestigationKind, InvestigationStatus};
@@ -108,18 +108,18 @@ pub enum InvestigateSessionSubcommand {
     },
 }
 
-pub fn run(root: &Path, subcmd: InvestigateSubcommand, json: bool) -> anyhow::Result<()> {
+pub fn run(root: &Path, subcmd: InvestigateSubcommand, out: OutputFormat) -> anyhow::Result<()> {
     match subcmd {
         InvestigateSubcommand::Create {
             slug,
             title,
             kind,
             context,
-        } => create(root, &slug, &title, &kind, context, json),
+        } => create(root, &slug, &title, &kind, context, out),
         InvestigateSubcommand::List { kind, status } => {
-            list(root, kind.as_deref(), status.as_deref(), json)
+            list(root, kind.as_deref(), status.as_deref(), out)
         }
-        InvestigateSubcommand::Show { slug } => show(root, &slug, json),
+        InvestigateSubcommand::Show { slug } => show(root, &slug, out),
         InvestigateSubcommand::Capture {
             slug,
             content,
@@ -131,7 +131,7 @@ pub fn run(root: &Path, subcmd: InvestigateSubcommand, json: bool) -> anyhow::Re
             content.as_deref(),
             file.as_deref(),
             filename.as_deref(),
-            json,
+            out,
         ),
         InvestigateSubcommand::Update {
             slug,
@@ -156,18 +156,18 @@ pub fn run(root: &Path, subcmd: InvestigateSubcommand, json: bool) -> anyhow::Re
             principles_count,
             output_type.as_deref(),
             output_ref.as_deref(),
-            json,
+            out,
         ),
-        InvestigateSubcommand::Artifacts { slug } => artifacts(root, &slug, json),
+        InvestigateSubcommand::Artifacts { slug } => artifacts(root, &slug, out),
         InvestigateSubcommand::Session { subcommand } => match subcommand {
             InvestigateSessionSubcommand::Log {
                 slug,
                 content,
                 file,
-            } => session_log(root, &slug, content.as_deref(), file.as_deref(), json),
-            InvestigateSessionSubcommand::List { slug } => session_list(root, &slug, json),
+            } => session_log(root, &slug, content.as_deref(), file.as_deref(), out),
+            InvestigateSessionSubcommand::List { slug } => session_list(root, &slug, out),
             InvestigateSessionSubcommand::Read { slug, number } => {
-                session_read(root, &slug, number, json)
+                session_read(root, &slug, number, out)
             }
         },
     }
@@ -179,7 +179,7 @@ fn create(
     title: &str,
     kind_str: &str,
     context: Option<String>,
-    json: bool,
+    out: OutputFormat,
 ) -> anyhow::Result<()> {
     let kind: InvestigationKind = kind_str
         .parse()
@@ -188,21 +188,23 @@ fn create(
     let entry = investigation::create(root, slug, title, kind, context)
         .with_context(|| format!("failed to create investigation '{slug}'"))?;
 
-    if json {
-        print_json(&serde_json::json!({
+    out.emit(
+        &serde_json::json!({
             "slug": entry.slug,
             "title": entry.title,
             "kind": entry.kind.to_string(),
             "phase": entry.phase,
             "status": entry.status.to_string(),
-        }))?;
-    } else {
-        println!(
-            "Created {} investigation '{}': {}",
-            entry.kind, entry.slug, entry.title
-        );
-        println!("Phase: {} | Status: {}", entry.phase, entry.status);
-    }
+        }),
+        || {
+            println!(
+                "Created {} investigation '{}': {}",
+                entry.kind, entry.slug, entry.title
+            );
+            println!("Phase: {} | Status: {}", entry.phase, entry.status);
+            Ok(())
+        },
+    )?;
     Ok(())
 }
 
@@ -210,7 +212,7 @@ fn list(
     root: &Path,
     kind_filter: Option<&str>,
     status_filter: Option<&str>,
-    json: bool,
+    out: OutputFormat,
 ) -> anyhow::Result<()> {
     let kind_opt: Option<InvestigationKind> = kind_filter
         .map(|k| k.parse().with_context(|| format!("invalid kind: {k}")))
@@ -229,80 +231,74 @@ fn list(
         entries.retain(|e| e.status == status);
     }
 
-    if json {
-        let items: Vec<serde_json::Value> = entries.iter().map(entry_to_json_summary).collect();
-        print_json(&items)?;
-        return Ok(());
-    }
-
-    if entries.is_empty() {
-        println!("No investigations.");
-        return Ok(());
-    }
+    let items: Vec<serde_json::Value> = entries.iter().map(entry_to_json_summary).collect();
+    out.emit(&items, || {
+        if entries.is_empty() {
+            println!("No investigations.");
+            return Ok(());
+        }
 
-    let rows: Vec<Vec<String>> = entries
-        .iter()
-        .map(|e| {
-            vec![
-                e.slug.clone(),
-                e.title.clone(),
-                e.kind.to_string(),
-                e.phase.clone(),
-                e.status.to_string(),
-                e.sessions.to_string(),
-            ]
-        })
-        .collect();
-    print_table(
-        &["SLUG", "TITLE", "KIND", "PHASE", "STATUS", "SESSIONS"],
-        rows,
-    );
-    Ok(())
+        let rows: Vec<Vec<String>> = entries
+            .iter()
+            .map(|e| {
+                vec![
+                    e.slug.clone(),
+                    e.title.clone(),
+                    e.kind.to_string(),
+                    e.phase.clone(),
+                    e.status.to_string(),
+                    e.sessions.to_string(),
+                ]
+            })
+            .collect();
+        print_table(
+            &["SLUG", "TITLE", "KIND", "PHASE", "STATUS", "SESSIONS"],
+            rows,
+        );
+        Ok(())
+    })
 }
 
-fn show(root: &Path, slug: &str, json: bool) -> anyhow::Result<()> {
+fn show(root: &Path, slug: &str, out: OutputFormat) -> anyhow::Result<()> {
     let entry = investigation::load(root, slug)
         .with_context(|| format!("investigation '{slug}' not found"))?;
     let artifacts =
         investigation::list_artifacts(root, slug).context("failed to list artifacts")?;
 
-    if json {
-        let mut val = entry_to_json_full(&entry);
-        let artifact_list: Vec<serde_json::Value> = artifacts
-            .iter()
-            .map(|a| {
-                serde_json::json!({
-                    "filename": a.filename,
-                    "size_bytes": a.size_bytes,
-                    "modified_at": a.modified_at,
-                })
+    let mut val = entry_to_json_full(&entry);
+    let artifact_list: Vec<serde_json::Value> = artifacts
+        .iter()
+        .map(|a| {
+            serde_json::json!({
+                "filename": a.filename,
+                "size_bytes": a.size_bytes,
+                "modified_at": a.modified_at,
             })
-            .collect();
-        val["artifacts"] = serde_json::json!(artifact_list);
-        print_json(&val)?;
-        return Ok(());
-    }
-
-    println!(
-        "Investigation: {} — {} ({})",
-        entry.slug, entry.title, entry.kind
-    );
-    println!("Phase:   {} | Status: {}", entry.phase, entry.status);
-    println!("Sessions: {}", entry.sessions);
-    if let Some(ctx) = &entry.context {
-        println!("Context: {ctx}");
-    }
-    if let Some(o) = &entry.orientation {
-        println!("\nOrientation:");
-        println!("  WHERE: {}", o.current);
-        println!("  NEXT:  {}", o.next);
-        println!("  COMMIT: {}", o.commit);
-    }
-    println!("\nArtifacts: {}", artifacts.len());
-    for a in &artifacts {
-        println!("  {} ({} bytes)", a.filename, a.size_bytes);
-    }
-    Ok(())
+        })
+        .collect();
+    val["artifacts"] = serde_json::json!(artifact_list);
+    out.emit(&val, || {
+        println!(
+            "Investigation: {} — {} ({})",
+            entry.slug, entry.title, entry.kind
+        );
+        println!("Phase:   {} | Status: {}", entry.phase, entry.status);
+        println!("Sessions: {}", entry.sessions);
+        if let Some(ctx) = &entry.context {
+            println!("Context: {ctx}");
+        }
+        if let Some(o) = &entry.orientation {
+            println!("\nOrientation:");
+            println!("  WHERE: {}", o.current);
+            println!("  NEXT:  {}", o.next);
+            println!("  COMMIT: {}", o.commit);
+        }
+        println!("\nArtifacts: {}", artifacts.len());
+        for a in &artifacts {
+            println!("  {} ({} bytes)", a.filename, a.size_bytes);
+        }
+        Ok(())
+    })
 }
 
 fn capture(
@@ -311,7 +307,7 @@ fn capture(
     content: Option<&str>,
     file: Option<&Path>,
     filename_override: Option<&str>,
-    json: bool,
+    out: OutputFormat,
 ) -> anyhow::Result<()> {
     let target_filename;
 
@@ -337,15 +333,17 @@ fn capture(
         }
     }
 
-    if json {
-        print_json(&serde_json::json!({
+    out.emit(
+        &serde_json::json!({
             "slug": slug,
             "filename": target_filename,
             "captured": true,
-        }))?;
-    } else {
-        println!("Captured '{target_filename}' into investigation '{slug}'.");
-    }
+        }),
+        || {
+            println!("Captured '{target_filename}' into investigation '{slug}'.");
+            Ok(())
+        },
+    )?;
     Ok(())
 }
 
@@ -362,7 +360,7 @@ fn update(
     principles_count: Option<u32>,
     output_type: Option<&str>,
     output_ref: Option<&str>,
-    json: bool,
+    out: OutputFormat,
 ) -> anyhow::Result<()> {
     if phase.is_none()
         && status_str.is_none()
@@ -468,7 +466,7 @@ fn update(
     if entry.status == sdlc_core::investigation::InvestigationStatus::Complete {
         match sdlc_core::knowledge::librarian_harvest_workspace(root, "investigation", slug) {
             Ok(result) => {
-                if !json {
+                if !out.is_structured() {
                     if result.created {
                         println!("Knowledge entry created: {}", result.slug);
                     } else {
@@ -483,50 +481,46 @@ fn update(
         }
     }
 
-    if json {
-        print_json(&entry_to_json_summary(&entry))?;
-    } else {
+    out.emit(&entry_to_json_summary(&entry), || {
         println!("Updated investigation '{slug}'.");
-    }
+        Ok(())
+    })?;
     Ok(())
 }
 
-fn artifacts(root: &Path, slug: &str, json: bool) -> anyhow::Result<()> {
+fn artifacts(root: &Path, slug: &str, out: OutputFormat) -> anyhow::Result<()> {
     let artifacts = investigation::list_artifacts(root, slug)
         .with_context(|| format!("failed to list artifacts for '{slug}'"))?;
 
-    if json {
-        let items: Vec<serde_json::Value> = artifacts
-            .iter()
-            .map(|a| {
-                serde_json::json!({
-                    "filename": a.filename,
-                    "size_bytes": a.size_bytes,
-                    "modified_at": a.modified_at,
-                })
-            })
-            .collect();
-        print_json(&items)?;
-        return Ok(());
-    }
-
-    if artifacts.is_empty() {
-        println!("No artifacts for investigation '{slug}'.");
-        return Ok(());
-    }
-
-    let rows: Vec<Vec<String>> = artifacts
+    let items: Vec<serde_json::Value> = artifacts
         .iter()
         .map(|a| {
-            vec![
-                a.filename.clone(),
-                format!("{} bytes", a.size_bytes),
-                a.modified_at.format("%Y-%m-%d %H:%M").to_string(),
-            ]
+            serde_json::json!({
+                "filename": a.filename,
+                "size_bytes": a.size_bytes,
+                "modified_at": a.modified_at,
+            })
         })
         .collect();
-    print_table(&["FILENAME", "SIZE", "MODIFIED"], rows);
-    Ok(())
+    out.emit(&items, || {
+        if artifacts.is_empty() {
+            println!("No artifacts for investigation '{slug}'.");
+            return Ok(());
+        }
+
+        let rows: Vec<Vec<String>> = artifacts
+            .iter()
+            .map(|a| {
+                vec![
+                    a.filename.clone(),
+                    format!("{} bytes", a.size_bytes),
+                    a.modified_at.format("%Y-%m-%d %H:%M").to_string(),
+                ]
+            })
+            .collect();
+        print_table(&["FILENAME", "SIZE", "MODIFIED"], rows);
+        Ok(())
+    })
 }
 
 fn session_log(
@@ -534,7 +528,7 @@ fn session_log(
     slug: &str,
     content: Option<&str>,
     file: Option<&Path>,
-    json: bool,
+    out: OutputFormat,
 ) -> anyhow::Result<()> {
     let body = match (content, file) {
         (Some(c), _) => c.to_string(),
@@ -552,76 +546,77 @@ fn session_log(
     let n = investigation::log_session(root, slug, &body)
         .with_context(|| format!("failed to log session for '{slug}'"))?;
 
-    if json {
-        print_json(&serde_json::json!({
+    out.emit(
+        &serde_json::json!({
             "slug": slug,
             "session": n,
             "logged": true,
-        }))?;
-    } else {
-        println!("Logged session {n} for investigation '{slug}'.");
-    }
+        }),
+        || {
+            println!("Logged session {n} for investigation '{slug}'.");
+            Ok(())
+        },
+    )?;
     Ok(())
 }
 
-fn session_list(root: &Path, slug: &str, json: bool) -> anyhow::Result<()> {
+fn session_list(root: &Path, slug: &str, out: OutputFormat) -> anyhow::Result<()> {
     let sessions = investigation::list_sessions(root, slug)
         .with_context(|| format!("failed to list sessions for '{slug}'"))?;
 
-    if json {
-        let items: Vec<serde_json::Value> = sessions
-            .iter()
-            .map(|s| {
-                serde_json::json!({
-                    "session": s.session,
-                    "timestamp": s.timestamp,
-                    "orientation": s.orientation.as_ref().map(|o| serde_json::json!({
-                        "current": o.current,
-                        "next": o.next,
-                        "commit": o.commit,
-                    })),
-                })
-            })
-            .collect();
-        print_json(&items)?;
-        return Ok(());
-    }
-
-    if sessions.is_empty() {
-        println!("No sessions for investigation '{slug}'.");
-        return Ok(());
-    }
-
-    let rows: Vec<Vec<String>> = sessions
+    let items: Vec<serde_json::Value> = sessions
         .iter()
         .map(|s| {
-            vec![
-                s.session.to_string(),
-                s.timestamp.format("%Y-%m-%d %H:%M").to_string(),
-                s.orientation
-                    .as_ref()
-                    .map(|o| o.current.clone())
-                    .unwrap_or_default(),
-            ]
+            serde_json::json!({
+                "session": s.session,
+                "timestamp": s.timestamp,
+                "orientation": s.orientation.as_ref().map(|o| serde_json::json!({
+                    "current": o.current,
+                    "next": o.next,
+                    "commit": o.commit,
+                })),
+            })
         })
         .collect();
-    print_table(&["#", "DATE", "WHERE WE ARE"], rows);
-    Ok(())
+    out.emit(&items, || {
+        if sessions.is_empty() {
+            println!("No sessions for investigation '{slug}'.");
+            return Ok(());
+        }
+
+        let rows: Vec<Vec<String>> = sessions
+            .iter()
+            .map(|s| {
+                vec![
+                    s.session.to_string(),
+                    s.timestamp.format("%Y-%m-%d %H:%M").to_string(),
+                    s.orientation
+                        .as_ref()
+                        .map(|o| o.current.clone())
+                        .unwrap_or_default(),
+                ]
+            })
+            .collect();
+        print_table(&["#", "DATE", "WHERE WE ARE"], rows);
+        Ok(())
+    })
 }
 
-fn session_read(root: &Path, slug: &str, number: u32, json: bool) -> anyhow::Result<()> {
+fn session_read(root: &Path, slug: &str, number: u32, out: OutputFormat) -> anyhow::Result<()> {
     let content = investigation::read_session(root, slug, number)
         .with_context(|| format!("session {number} not found for investigation '{slug}'"))?;
 
-    if json {
-        print_json(&serde_json::json!({
+    out.emit(
+        &serde_json::json!({
             "slug": slug,
             "session": number,
             "content": content,
-        }))?;
-    } else {
-        print!("{content}");
-    }
+        }),
+        || {
+            print!("{content}");
+            Ok(())
+        },
+    )?;
     Ok(())
 }
 
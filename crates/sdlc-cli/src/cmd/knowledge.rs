@@ -1,7 +1,8 @@
-use crate::output::{print_json, print_table};
+use crate::output::{print_table, OutputFormat};
 use anyhow::{anyhow, Context};
 use clap::Subcommand;
 use sdlc_core::knowledge::{self, KnowledgeEntry, KnowledgeStatus, OriginKind, Source, SourceType};
+use sdlc_core::slug;
 use sdlc_core::ui_registry;
 use std::path::{Path, PathBuf};
 
@@ -168,9 +169,9 @@ pub enum KnowledgeSessionSubcommand {
     Read { slug: String, number: u32 },
 }
 
-pub fn run(root: &Path, subcmd: KnowledgeSubcommand, json: bool) -> anyhow::Result<()> {
+pub fn run(root: &Path, subcmd: KnowledgeSubcommand, out: OutputFormat) -> anyhow::Result<()> {
     match subcmd {
-        KnowledgeSubcommand::Status => status(root, json),
+        KnowledgeSubcommand::Status => status(root, out),
         KnowledgeSubcommand::Add {
             title,
             code,
@@ -184,7 +185,7 @@ pub fn run(root: &Path, subcmd: KnowledgeSubcommand, json: bool) -> anyhow::Resu
             content.as_deref(),
             from_url.as_deref(),
             from_file.as_deref(),
-            json,
+            out,
         ),
         KnowledgeSubcommand::List {
             code_prefix,
@@ -195,10 +196,10 @@ pub fn run(root: &Path, subcmd: KnowledgeSubcommand, json: bool) -> anyhow::Resu
             code_prefix.as_deref(),
             tag.as_deref(),
             status.as_deref(),
-            json,
+            out,
         ),
-        KnowledgeSubcommand::Show { slug } => show(root, &slug, json),
-        KnowledgeSubcommand::Search { query } => search(root, &query, json),
+        KnowledgeSubcommand::Show { slug } => show(root, &slug, out),
+        KnowledgeSubcommand::Search { query } => search(root, &query, out),
         KnowledgeSubcommand::Update {
             slug,
             code,
@@ -214,32 +215,32 @@ pub fn run(root: &Path, subcmd: KnowledgeSubcommand, json: bool) -> anyhow::Resu
             tag.as_deref(),
             related.as_deref(),
             summary.as_deref(),
-            json,
+            out,
         ),
         KnowledgeSubcommand::Catalog { subcommand } => match subcommand {
-            KnowledgeCatalogSubcommand::Show => catalog_show(root, json),
+            KnowledgeCatalogSubcommand::Show => catalog_show(root, out),
             KnowledgeCatalogSubcommand::Add {
                 code,
                 name,
                 description,
-            } => catalog_add(root, &code, &name, description.as_deref(), json),
+            } => catalog_add(root, &code, &name, description.as_deref(), out),
         },
         KnowledgeSubcommand::Session { subcommand } => match subcommand {
             KnowledgeSessionSubcommand::Log {
                 slug,
                 content,
                 file,
-            } => session_log(root, &slug, content.as_deref(), file.as_deref(), json),
-            KnowledgeSessionSubcommand::List { slug } => session_list(root, &slug, json),
+            } => session_log(root, &slug, content.as_deref(), file.as_deref(), out),
+            KnowledgeSessionSubcommand::List { slug } => session_list(root, &slug, out),
             KnowledgeSessionSubcommand::Read { slug, number } => {
-                session_read(root, &slug, number, json)
+                session_read(root, &slug, number, out)
             }
         },
-        KnowledgeSubcommand::Librarian { subcommand } => run_librarian(root, subcommand, json),
+        KnowledgeSubcommand::Librarian { subcommand } => run_librarian(root, subcommand, out),
         KnowledgeSubcommand::Research { topic, code } => {
-            research(root, &topic, code.as_deref(), json)
+            research(root, &topic, code.as_deref(), out)
         }
-        KnowledgeSubcommand::Ask { question } => ask(root, &question, json),
+        KnowledgeSubcommand::Ask { question } => ask(root, &question, out),
     }
 }
 
@@ -247,19 +248,21 @@ pub fn run(root: &Path, subcmd: KnowledgeSubcommand, json: bool) -> anyhow::Resu
 // Handlers
 // ---------------------------------------------------------------------------
 
-fn status(root: &Path, json: bool) -> anyhow::Result<()> {
+fn status(root: &Path, out: OutputFormat) -> anyhow::Result<()> {
     let knowledge_dir = root.join(".sdlc/knowledge");
     let entries = knowledge::list(root).context("failed to list knowledge entries")?;
 
     if !knowledge_dir.exists() || entries.is_empty() {
-        if json {
-            print_json(&serde_json::json!({
+        out.emit(
+            &serde_json::json!({
                 "initialized": false,
                 "message": EMPTY_STATE_MSG,
-            }))?;
-        } else {
-            println!("{EMPTY_STATE_MSG}");
-        }
+            }),
+            || {
+                println!("{EMPTY_STATE_MSG}");
+                Ok(())
+            },
+        )?;
         return Ok(());
     }
 
@@ -274,19 +277,21 @@ fn status(root: &Path, json: bool) -> anyhow::Result<()> {
         .map(|a| a.timestamp.format("%Y-%m-%d %H:%M").to_string())
         .unwrap_or_else(|| "never".to_string());
 
-    if json {
-        print_json(&serde_json::json!({
+    out.emit(
+        &serde_json::json!({
             "initialized": true,
             "entry_count": entry_count,
             "catalog_class_count": class_count,
             "last_maintained": last_maintained,
-        }))?;
-    } else {
-        println!("Knowledge base: initialized");
-        println!("Entries:        {entry_count}");
-        println!("Catalog:        {class_count} top-level classes");
-        println!("Last maintained: {last_maintained}");
-    }
+        }),
+        || {
+            println!("Knowledge base: initialized");
+            println!("Entries:        {entry_count}");
+            println!("Catalog:        {class_count} top-level classes");
+            println!("Last maintained: {last_maintained}");
+            Ok(())
+        },
+    )?;
     Ok(())
 }
 
@@ -298,9 +303,9 @@ fn add(
     content: Option<&str>,
     from_url: Option<&str>,
     from_file: Option<&Path>,
-    json: bool,
+    out: OutputFormat,
 ) -> anyhow::Result<()> {
-    let slug = slugify_title(title);
+    let slug = slug::derive(title);
     let entry_code = code.unwrap_or("uncategorized");
 
     let mut entry = knowledge::create(root, &slug, title, entry_code)
@@ -350,11 +355,10 @@ fn add(
         _ => {}
     }
 
-    if json {
-        print_json(&entry_to_json_summary(&entry))?;
-    } else {
+    out.emit(&entry_to_json_summary(&entry), || {
         println!("Created knowledge entry '{slug}'.");
-    }
+        Ok(())
+    })?;
     Ok(())
 }
 
@@ -363,7 +367,7 @@ fn list(
     code_prefix: Option<&str>,
     tag: Option<&str>,
     status_filter: Option<&str>,
-    json: bool,
+    out: OutputFormat,
 ) -> anyhow::Result<()> {
     let mut entries = match code_prefix {
         Some(prefix) => {
@@ -381,132 +385,121 @@ fn list(
     }
 
     if entries.is_empty() {
-        if json {
-            print_json(&serde_json::json!([]))?;
-        } else {
+        out.emit(&serde_json::json!([]), || {
             println!("{EMPTY_STATE_MSG}");
-        }
-        return Ok(());
-    }
-
-    if json {
-        let items: Vec<serde_json::Value> = entries.iter().map(entry_to_json_summary).collect();
-        print_json(&items)?;
+            Ok(())
+        })?;
         return Ok(());
     }
 
-    let rows: Vec<Vec<String>> = entries
-        .iter()
-        .map(|e| {
-            let title_trunc: String = e.title.chars().take(30).collect();
-            let summary_trunc: String = e
-                .summary
-                .as_deref()
-                .unwrap_or("")
-                .chars()
-                .take(60)
-                .collect();
-            vec![
-                e.code.clone(),
-                title_trunc,
-                summary_trunc,
-                e.status.to_string(),
-                e.updated_at.format("%Y-%m-%d").to_string(),
-            ]
-        })
-        .collect();
-    print_table(&["CODE", "TITLE", "SUMMARY", "STATUS", "UPDATED"], rows);
-    Ok(())
+    let items: Vec<serde_json::Value> = entries.iter().map(entry_to_json_summary).collect();
+    out.emit(&items, || {
+        let rows: Vec<Vec<String>> = entries
+            .iter()
+            .map(|e| {
+                let title_trunc: String = e.title.chars().take(30).collect();
+                let summary_trunc: String = e
+                    .summary
+                    .as_deref()
+                    .unwrap_or("")
+                    .chars()
+                    .take(60)
+                    .collect();
+                vec![
+                    e.code.clone(),
+                    title_trunc,
+                    summary_trunc,
+                    e.status.to_string(),
+                    e.updated_at.format("%Y-%m-%d").to_string(),
+                ]
+            })
+            .collect();
+        print_table(&["CODE", "TITLE", "SUMMARY", "STATUS", "UPDATED"], rows);
+        Ok(())
+    })
 }
 
-fn show(root: &Path, slug: &str, json: bool) -> anyhow::Result<()> {
+fn show(root: &Path, slug: &str, out: OutputFormat) -> anyhow::Result<()> {
     let entry = knowledge::load(root, slug)
         .with_context(|| format!("knowledge entry '{slug}' not found"))?;
     let content = knowledge::read_content(root, slug).unwrap_or_default();
 
-    if json {
-        let mut val = entry_to_json_full(&entry);
-        val["content"] = serde_json::json!(content);
-        print_json(&val)?;
-        return Ok(());
-    }
-
-    println!("[{}] {}", entry.code, entry.title);
-    if let Some(s) = &entry.summary {
-        println!("\n{s}");
-    }
-    if !entry.tags.is_empty() {
-        println!("\nTags: {}", entry.tags.join(", "));
-    }
-    if !entry.sources.is_empty() {
-        println!("\nSources:");
-        for source in &entry.sources {
-            match source.source_type {
-                SourceType::Web => {
-                    println!("  - web: {}", source.url.as_deref().unwrap_or("(unknown)"));
-                }
-                SourceType::LocalFile => {
-                    println!(
-                        "  - file: {}",
-                        source.path.as_deref().unwrap_or("(unknown)")
-                    );
-                }
-                ref st => {
-                    println!("  - {st}");
+    let mut val = entry_to_json_full(&entry);
+    val["content"] = serde_json::json!(content);
+    out.emit(&val, || {
+        println!("[{}] {}", entry.code, entry.title);
+        if let Some(s) = &entry.summary {
+            println!("\n{s}");
+        }
+        if !entry.tags.is_empty() {
+            println!("\nTags: {}", entry.tags.join(", "));
+        }
+        if !entry.sources.is_empty() {
+            println!("\nSources:");
+            for source in &entry.sources {
+                match source.source_type {
+                    SourceType::Web => {
+                        println!("  - web: {}", source.url.as_deref().unwrap_or("(unknown)"));
+                    }
+                    SourceType::LocalFile => {
+                        println!(
+                            "  - file: {}",
+                            source.path.as_deref().unwrap_or("(unknown)")
+                        );
+                    }
+                    ref st => {
+                        println!("  - {st}");
+                    }
                 }
             }
         }
-    }
-    if !content.is_empty() {
-        println!("\n---\n{content}");
-    }
-    Ok(())
+        if !content.is_empty() {
+            println!("\n---\n{content}");
+        }
+        Ok(())
+    })
 }
 
-fn search(root: &Path, query: &str, json: bool) -> anyhow::Result<()> {
+fn search(root: &Path, query: &str, out: OutputFormat) -> anyhow::Result<()> {
     let results =
         knowledge::full_text_search(root, query).context("failed to search knowledge base")?;
 
     if results.is_empty() {
-        if json {
-            print_json(&serde_json::json!([]))?;
-        } else {
+        out.emit(&serde_json::json!([]), || {
             let entries = knowledge::list(root).unwrap_or_default();
             if entries.is_empty() {
                 println!("{EMPTY_STATE_MSG}");
             } else {
                 println!("No results found for '{query}'.");
             }
-        }
-        return Ok(());
-    }
-
-    if json {
-        let items: Vec<serde_json::Value> = results
-            .iter()
-            .map(|r| {
-                serde_json::json!({
-                    "slug": r.entry.slug,
-                    "title": r.entry.title,
-                    "code": r.entry.code,
-                    "excerpt": r.excerpt,
-                })
-            })
-            .collect();
-        print_json(&items)?;
+            Ok(())
+        })?;
         return Ok(());
     }
 
-    let rows: Vec<Vec<String>> = results
+    let items: Vec<serde_json::Value> = results
         .iter()
         .map(|r| {
-            let title_trunc: String = r.entry.title.chars().take(30).collect();
-            let excerpt_trunc: String = r.excerpt.chars().take(60).collect();
-            vec![r.entry.slug.clone(), title_trunc, excerpt_trunc]
+            serde_json::json!({
+                "slug": r.entry.slug,
+                "title": r.entry.title,
+                "code": r.entry.code,
+                "excerpt": r.excerpt,
+            })
         })
         .collect();
-    print_table(&["SLUG", "TITLE", "EXCERPT"], rows);
-    Ok(())
+    out.emit(&items, || {
+        let rows: Vec<Vec<String>> = results
+            .iter()
+            .map(|r| {
+                let title_trunc: String = r.entry.title.chars().take(30).collect();
+                let excerpt_trunc: String = r.excerpt.chars().take(60).collect();
+                vec![r.entry.slug.clone(), title_trunc, excerpt_trunc]
+            })
+            .collect();
+        print_table(&["SLUG", "TITLE", "EXCERPT"], rows);
+        Ok(())
+    })
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -518,7 +511,7 @@ fn update(
     tag: Option<&str>,
     related: Option<&str>,
     summary: Option<&str>,
-    json: bool,
+    out: OutputFormat,
 ) -> anyhow::Result<()> {
     let status = status_str
         .map(|s| {
@@ -542,72 +535,70 @@ fn update(
     )
     .with_context(|| format!("failed to update entry '{slug}'"))?;
 
-    if json {
-        print_json(&entry_to_json_summary(&entry))?;
-    } else {
+    out.emit(&entry_to_json_summary(&entry), || {
         println!("Updated knowledge entry '{slug}'.");
-    }
+        Ok(())
+    })?;
     Ok(())
 }
 
-fn catalog_show(root: &Path, json: bool) -> anyhow::Result<()> {
+fn catalog_show(root: &Path, out: OutputFormat) -> anyhow::Result<()> {
     let catalog = knowledge::load_catalog(root).context("failed to load catalog")?;
 
     if catalog.classes.is_empty() {
-        if json {
-            print_json(&serde_json::json!({ "classes": [] }))?;
-        } else {
+        out.emit(&serde_json::json!({ "classes": [] }), || {
             println!(
                 "No catalog defined. Run sdlc knowledge librarian init or sdlc knowledge catalog add."
             );
-        }
+            Ok(())
+        })?;
         return Ok(());
     }
 
-    if json {
-        let classes: Vec<serde_json::Value> = catalog
-            .classes
-            .iter()
-            .map(|c| {
-                let divisions: Vec<serde_json::Value> = c
-                    .divisions
-                    .iter()
-                    .map(|d| {
-                        serde_json::json!({
-                            "code": d.code,
-                            "name": d.name,
-                            "description": d.description,
-                        })
+    let classes: Vec<serde_json::Value> = catalog
+        .classes
+        .iter()
+        .map(|c| {
+            let divisions: Vec<serde_json::Value> = c
+                .divisions
+                .iter()
+                .map(|d| {
+                    serde_json::json!({
+                        "code": d.code,
+                        "name": d.name,
+                        "description": d.description,
                     })
-                    .collect();
-                serde_json::json!({
-                    "code": c.code,
-                    "name": c.name,
-                    "description": c.description,
-                    "divisions": divisions,
                 })
+                .collect();
+            serde_json::json!({
+                "code": c.code,
+                "name": c.name,
+                "description": c.description,
+                "divisions": divisions,
             })
-            .collect();
-        print_json(&serde_json::json!({
+        })
+        .collect();
+    out.emit(
+        &serde_json::json!({
             "classes": classes,
             "updated_at": catalog.updated_at,
-        }))?;
-        return Ok(());
-    }
-
-    for class in &catalog.classes {
-        println!("[{}] {}", class.code, class.name);
-        if let Some(desc) = &class.description {
-            println!("    {desc}");
-        }
-        for div in &class.divisions {
-            println!("  [{}] {}", div.code, div.name);
-            if let Some(desc) = &div.description {
-                println!("      {desc}");
+        }),
+        || {
+            for class in &catalog.classes {
+                println!("[{}] {}", class.code, class.name);
+                if let Some(desc) = &class.description {
+                    println!("    {desc}");
+                }
+                for div in &class.divisions {
+                    println!("  [{}] {}", div.code, div.name);
+                    if let Some(desc) = &div.description {
+                        println!("      {desc}");
+                    }
+                }
             }
-        }
-    }
-    Ok(())
+            Ok(())
+        },
+    )
 }
 
 fn catalog_add(
@@ -615,7 +606,7 @@ fn catalog_add(
     code: &str,
     name: &str,
     description: Option<&str>,
-    json: bool,
+    out: OutputFormat,
 ) -> anyhow::Result<()> {
     let catalog = if code.contains('.') {
         // Division: parent class is everything before the first dot
@@ -626,18 +617,21 @@ fn catalog_add(
         knowledge::add_class(root, code, name, description).context("failed to add class")?
     };
 
-    if json {
-        print_json(&serde_json::json!({
+    out.emit(
+        &serde_json::json!({
             "code": code,
             "name": name,
             "class_count": catalog.classes.len(),
-        }))?;
-    } else if code.contains('.') {
-        println!("Added division [{code}] '{name}' to catalog.");
-    } else {
-        println!("Added class [{code}] '{name}' to catalog.");
-    }
-    Ok(())
+        }),
+        || {
+            if code.contains('.') {
+                println!("Added division [{code}] '{name}' to catalog.");
+            } else {
+                println!("Added class [{code}] '{name}' to catalog.");
+            }
+            Ok(())
+        },
+    )
 }
 
 fn session_log(
@@ -645,7 +639,7 @@ fn session_log(
     slug: &str,
     content: Option<&str>,
     file: Option<&Path>,
-    json: bool,
+    out: OutputFormat,
 ) -> anyhow::Result<()> {
     use std::io::Read as _;
     let body = match (content, file) {
@@ -664,63 +658,64 @@ fn session_log(
     let n = knowledge::log_session(root, slug, &body)
         .with_context(|| format!("failed to log session for knowledge entry '{slug}'"))?;
 
-    if json {
-        print_json(&serde_json::json!({ "slug": slug, "session": n, "logged": true }))?;
-    } else {
-        println!("Logged session {n} for knowledge entry '{slug}'.");
-    }
+    out.emit(
+        &serde_json::json!({ "slug": slug, "session": n, "logged": true }),
+        || {
+            println!("Logged session {n} for knowledge entry '{slug}'.");
+            Ok(())
+        },
+    )?;
     Ok(())
 }
 
-fn session_list(root: &Path, slug: &str, json: bool) -> anyhow::Result<()> {
+fn session_list(root: &Path, slug: &str, out: OutputFormat) -> anyhow::Result<()> {
     let sessions = knowledge::list_sessions(root, slug)
         .with_context(|| format!("failed to list sessions for '{slug}'"))?;
 
-    if json {
-        let items: Vec<serde_json::Value> = sessions
-            .iter()
-            .map(|s| {
-                serde_json::json!({
-                    "session": s.session,
-                    "timestamp": s.timestamp,
-                })
-            })
-            .collect();
-        print_json(&items)?;
-        return Ok(());
-    }
-
-    if sessions.is_empty() {
-        println!("No sessions for knowledge entry '{slug}'.");
-        return Ok(());
-    }
-
-    let rows: Vec<Vec<String>> = sessions
+    let items: Vec<serde_json::Value> = sessions
         .iter()
         .map(|s| {
-            vec![
-                s.session.to_string(),
-                s.timestamp.format("%Y-%m-%d %H:%M").to_string(),
-            ]
+            serde_json::json!({
+                "session": s.session,
+                "timestamp": s.timestamp,
+            })
         })
         .collect();
-    print_table(&["#", "DATE"], rows);
-    Ok(())
+    out.emit(&items, || {
+        if sessions.is_empty() {
+            println!("No sessions for knowledge entry '{slug}'.");
+            return Ok(());
+        }
+
+        let rows: Vec<Vec<String>> = sessions
+            .iter()
+            .map(|s| {
+                vec![
+                    s.session.to_string(),
+                    s.timestamp.format("%Y-%m-%d %H:%M").to_string(),
+                ]
+            })
+            .collect();
+        print_table(&["#", "DATE"], rows);
+        Ok(())
+    })
 }
 
-fn session_read(root: &Path, slug: &str, number: u32, json: bool) -> anyhow::Result<()> {
+fn session_read(root: &Path, slug: &str, number: u32, out: OutputFormat) -> anyhow::Result<()> {
     let content = knowledge::read_session(root, slug, number)
         .with_context(|| format!("session {number} not found for knowledge entry '{slug}'"))?;
 
-    if json {
-        print_json(&serde_json::json!({
+    out.emit(
+        &serde_json::json!({
             "slug": slug,
             "session": number,
             "content": content,
-        }))?;
-    } else {
-        print!("{content}");
-    }
+        }),
+        || {
+            print!("{content}");
+            Ok(())
+        },
+    )?;
     Ok(())
 }
 
@@ -728,38 +723,41 @@ fn run_harvest(
     root: &Path,
     workspace_type: &str,
     workspace_slug: &str,
-    json: bool,
+    out: OutputFormat,
 ) -> anyhow::Result<()> {
     let result = knowledge::librarian_harvest_workspace(root, workspace_type, workspace_slug)
         .with_context(|| {
             format!("failed to harvest {workspace_type}/{workspace_slug} into knowledge base")
         })?;
 
-    if json {
-        print_json(&serde_json::json!({
+    out.emit(
+        &serde_json::json!({
             "type": workspace_type,
             "slug": workspace_slug,
             "created": result.created,
             "entry_slug": result.slug,
-        }))?;
-    } else if result.created {
-        println!(
-            "Harvested {workspace_type}/{workspace_slug} → created knowledge entry '{}'.",
-            result.slug
-        );
-    } else {
-        println!(
-            "Harvested {workspace_type}/{workspace_slug} → updated knowledge entry '{}'.",
-            result.slug
-        );
-    }
-    Ok(())
+        }),
+        || {
+            if result.created {
+                println!(
+                    "Harvested {workspace_type}/{workspace_slug} → created knowledge entry '{}'.",
+                    result.slug
+                );
+            } else {
+                println!(
+                    "Harvested {workspace_type}/{workspace_slug} → updated knowledge entry '{}'.",
+                    result.slug
+                );
+            }
+            Ok(())
+        },
+    )
 }
 
 fn run_librarian(
     root: &Path,
     subcmd: KnowledgeLibrarianSubcommand,
-    json: bool,
+    out: OutputFormat,
 ) -> anyhow::Result<()> {
     match subcmd {
         KnowledgeLibrarianSubcommand::Run { mode, r#type, slug } => match mode.as_str() {
@@ -798,14 +796,14 @@ All changes are logged to .sdlc/knowledge/maintenance-log.yaml automatically.
                 let workspace_slug = slug.ok_or_else(|| {
                     anyhow::anyhow!("--slug is required when --mode harvest is used")
                 })?;
-                run_harvest(root, &workspace_type, &workspace_slug, json)
+                run_harvest(root, &workspace_type, &workspace_slug, out)
             }
             other => Err(anyhow::anyhow!(
                 "unknown mode '{other}'; valid modes are 'maintain' and 'harvest'"
             )),
         },
         KnowledgeLibrarianSubcommand::Harvest { r#type, slug } => {
-            run_harvest(root, &r#type, &slug, json)
+            run_harvest(root, &r#type, &slug, out)
         }
         KnowledgeLibrarianSubcommand::Init => {
             let report = knowledge::librarian_init(root).context("librarian init failed")?;
@@ -820,8 +818,8 @@ All changes are logged to .sdlc/knowledge/maintenance-log.yaml automatically.
             let ponder_upd: usize = report.ponder_results.len() - ponder_new;
             let guideline_count = report.guideline_results.len();
 
-            if json {
-                print_json(&serde_json::json!({
+            out.emit(
+                &serde_json::json!({
                     "investigations_new": inv_new,
                     "investigations_updated": inv_upd,
                     "ponders_new": ponder_new,
@@ -831,33 +829,35 @@ All changes are logged to .sdlc/knowledge/maintenance-log.yaml automatically.
                     "catalog_class_count": report.catalog_class_count,
                     "cross_ref_count": report.cross_ref_count,
                     "agent_file": report.agent_file_path.display().to_string(),
-                }))?;
-            } else {
-                println!("Knowledge base initialized");
-                println!(
-                    "  Investigations harvested: {} ({} new, {} updated)",
-                    report.investigation_results.len(),
-                    inv_new,
-                    inv_upd
-                );
-                println!(
-                    "  Ponders harvested:        {} ({} new, {} updated)",
-                    report.ponder_results.len(),
-                    ponder_new,
-                    ponder_upd
-                );
-                println!("  Guidelines linked:        {guideline_count}");
-                println!(
-                    "  Catalog:                  {} classes (created: {})",
-                    report.catalog_class_count,
-                    if report.catalog_created { "yes" } else { "no" }
-                );
-                println!("  Cross-references added:   {}", report.cross_ref_count);
-                println!(
-                    "  Librarian agent:          {}",
-                    report.agent_file_path.display()
-                );
-            }
+                }),
+                || {
+                    println!("Knowledge base initialized");
+                    println!(
+                        "  Investigations harvested: {} ({} new, {} updated)",
+                        report.investigation_results.len(),
+                        inv_new,
+                        inv_upd
+                    );
+                    println!(
+                        "  Ponders harvested:        {} ({} new, {} updated)",
+                        report.ponder_results.len(),
+                        ponder_new,
+                        ponder_upd
+                    );
+                    println!("  Guidelines linked:        {guideline_count}");
+                    println!(
+                        "  Catalog:                  {} classes (created: {})",
+                        report.catalog_class_count,
+                        if report.catalog_created { "yes" } else { "no" }
+                    );
+                    println!("  Cross-references added:   {}", report.cross_ref_count);
+                    println!(
+                        "  Librarian agent:          {}",
+                        report.agent_file_path.display()
+                    );
+                    Ok(())
+                },
+            )?;
             Ok(())
         }
     }
@@ -867,27 +867,6 @@ All changes are logged to .sdlc/knowledge/maintenance-log.yaml automatically.
 // Helpers
 // ---------------------------------------------------------------------------
 
-/// Derive a URL-safe slug from a title.
-/// Lowercase, replace non-alnum chars with `-`, strip leading/trailing `-`, truncate at 40.
-fn slugify_title(title: &str) -> String {
-    let lower = title.to_lowercase();
-    let mut result = String::new();
-    let mut last_was_dash = false;
-    for c in lower.chars() {
-        if c.is_ascii_alphanumeric() {
-            result.push(c);
-            last_was_dash = false;
-        } else if !last_was_dash && !result.is_empty() {
-            result.push('-');
-            last_was_dash = true;
-        }
-    }
-    while result.ends_with('-') {
-        result.pop();
-    }
-    result.chars().take(40).collect()
-}
-
 /// Fetch the `<title>` tag from a URL. Best-effort — returns `None` on any failure.
 fn fetch_page_title(url: &str) -> Option<String> {
     let response = ureq::get(url)
@@ -910,9 +889,14 @@ fn fetch_page_title(url: &str) -> Option<String> {
 // Research (agent run via server)
 // ---------------------------------------------------------------------------
 
-fn research(_root: &Path, topic: &str, _code: Option<&str>, json: bool) -> anyhow::Result<()> {
+fn research(
+    _root: &Path,
+    topic: &str,
+    _code: Option<&str>,
+    out: OutputFormat,
+) -> anyhow::Result<()> {
     // Derive slug from topic
-    let slug = slugify_topic(topic);
+    let slug = slug::derive(topic);
 
     // Find the running server URL via the ui_registry.
     let records = ui_registry::read_all().map_err(|e| anyhow!("{e}"))?;
@@ -948,14 +932,13 @@ fn research(_root: &Path, topic: &str, _code: Option<&str>, json: bool) -> anyho
     let resp_json: serde_json::Value =
         serde_json::from_str(&resp_body).map_err(|e| anyhow!("Failed to parse response: {e}"))?;
 
-    if json {
-        print_json(&resp_json)?;
-    } else {
+    out.emit(&resp_json, || {
         println!(
             "Research started for '{}' (slug: {slug}). Watch the Activity feed for progress.",
             topic
         );
-    }
+        Ok(())
+    })?;
 
     Ok(())
 }
@@ -964,7 +947,7 @@ fn research(_root: &Path, topic: &str, _code: Option<&str>, json: bool) -> anyho
 // Ask (agent query via server)
 // ---------------------------------------------------------------------------
 
-fn ask(root: &Path, question: &str, json: bool) -> anyhow::Result<()> {
+fn ask(root: &Path, question: &str, out: OutputFormat) -> anyhow::Result<()> {
     let _ = root; // root is used for context but server handles the query
 
     // Find the running server URL via the ui_registry.
@@ -1005,34 +988,14 @@ fn ask(root: &Path, question: &str, json: bool) -> anyhow::Result<()> {
     let resp_json: serde_json::Value =
         serde_json::from_str(&resp_body).map_err(|e| anyhow!("Failed to parse response: {e}"))?;
 
-    if json {
-        print_json(&resp_json)?;
-    } else {
+    out.emit(&resp_json, || {
         println!("Knowledge query started. Watch the Activity feed in the UI for the answer.");
-    }
+        Ok(())
+    })?;
 
     Ok(())
 }
 
-fn slugify_topic(topic: &str) -> String {
-    let lower = topic.to_lowercase();
-    let mut result = String::new();
-    let mut last_was_dash = false;
-    for c in lower.chars() {
-        if c.is_ascii_alphanumeric() {
-            result.push(c);
-            last_was_dash = false;
-        } else if !last_was_dash && !result.is_empty() {
-            result.push('-');
-            last_was_dash = true;
-        }
-    }
-    while result.ends_with('-') {
-        result.pop();
-    }
-    result.chars().take(40).collect()
-}
-
 // ---------------------------------------------------------------------------
 // JSON helpers
 // ---------------------------------------------------------------------------
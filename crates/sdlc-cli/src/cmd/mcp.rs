@@ -53,7 +53,7 @@ struct ToolCallResult {
 pub fn run(root: &Path) -> anyhow::Result<()> {
     let stdin = std::io::stdin();
     let stdout = std::io::stdout();
-    let tools = tools::all_tools();
+    let tools = tools::all_tools_for(root);
 
     eprintln!(
         "[sdlc-mcp] server started, {} tools registered",
@@ -359,6 +359,7 @@ mod tests {
         assert!(names.contains(&"sdlc_prepare"));
         assert!(names.contains(&"sdlc_run_wave"));
         assert!(names.contains(&"sdlc_ponder_chat"));
+        assert!(names.contains(&"sdlc_query"));
     }
 
     #[test]
@@ -1,15 +1,22 @@
-use crate::output::print_json;
+use crate::output::OutputFormat;
 use anyhow::Context;
 use sdlc_core::{
     config::Config,
     event_log::{self, EventKind},
-    feature::Feature,
+    feature::{self, Feature},
+    git,
     state::State,
     types::{ActionType, Phase},
 };
 use std::path::Path;
 
-pub fn run(root: &Path, slug: &str, json: bool) -> anyhow::Result<()> {
+pub fn run(
+    root: &Path,
+    slug: &str,
+    no_verify: bool,
+    force: bool,
+    out: OutputFormat,
+) -> anyhow::Result<()> {
     let config = Config::load(root).context("failed to load config")?;
     let mut feature =
         Feature::load(root, slug).with_context(|| format!("feature '{slug}' not found"))?;
@@ -21,15 +28,42 @@ pub fn run(root: &Path, slug: &str, json: bool) -> anyhow::Result<()> {
         );
     }
 
+    if !force {
+        feature
+            .ensure_dependencies_released(root)
+            .with_context(|| format!("refusing to merge '{slug}' (pass --force to override)"))?;
+    }
+
+    if !no_verify {
+        let check = git::verify_clean_merge(root, config.base_branch()).with_context(|| {
+            format!("refusing to merge '{slug}'; resolve the conflicts or pass --no-verify")
+        })?;
+        if let Some(warning) = check.warning() {
+            eprintln!("warn: {warning}");
+        }
+    }
+
     feature
         .transition(Phase::Released, &config)
         .with_context(|| format!("cannot transition '{slug}' to released"))?;
     feature.save(root).context("failed to save feature")?;
 
-    let mut state = State::load(root).context("failed to load state")?;
-    state.record_action(slug, ActionType::Merge, Phase::Released, "merged");
-    state.complete_directive(slug);
-    state.save(root).context("failed to save state")?;
+    let state = State::update(root, |state| {
+        state.record_action(slug, ActionType::Merge, Phase::Released, "merged");
+        state.complete_directive(slug);
+        state.clone()
+    })
+    .context("failed to update state")?;
+
+    // Record what the feature delivered in CHANGELOG.md — non-fatal, and a
+    // re-run merge leaves an existing entry alone.
+    let changelog = match feature::write_changelog(root, &feature, &state) {
+        Ok(written) => written,
+        Err(e) => {
+            eprintln!("warn: CHANGELOG.md write failed: {e}");
+            false
+        }
+    };
 
     // Emit changelog event — non-fatal.
     if let Err(e) = event_log::append_event(
@@ -41,15 +75,70 @@ pub fn run(root: &Path, slug: &str, json: bool) -> anyhow::Result<()> {
         eprintln!("warn: changelog write failed: {e}");
     }
 
-    if json {
-        print_json(&serde_json::json!({
+    out.emit(
+        &serde_json::json!({
             "slug": slug,
             "phase": "released",
             "merged": true,
-        }))?;
-    } else {
-        println!("Merged '{slug}' and marked as released");
-    }
+            "changelog": changelog,
+        }),
+        || {
+            println!("Merged '{slug}' and marked as released");
+            if changelog {
+                println!("Added a CHANGELOG.md entry");
+            }
+            Ok(())
+        },
+    )?;
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    /// A project where `api` is in the merge phase and depends on `db`.
+    fn project() -> TempDir {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path();
+        Config::new("test").save(root).unwrap();
+        State::new("test").save(root).unwrap();
+        let mut db = Feature::create(root, "db", "DB").unwrap();
+        db.phase = Phase::Implementation;
+        db.save(root).unwrap();
+        let mut api = Feature::create(root, "api", "API").unwrap();
+        api.phase = Phase::Merge;
+        api.dependencies = vec!["db".to_string()];
+        api.save(root).unwrap();
+        dir
+    }
+
+    #[test]
+    fn merge_blocked_until_dependencies_are_released() {
+        let dir = project();
+        let root = dir.path();
+
+        let err = run(root, "api", true, false, OutputFormat::Json).unwrap_err();
+        let msg = format!("{err:#}");
+        assert!(msg.contains("not yet released: db"), "{msg}");
+        assert!(msg.contains("--force"), "{msg}");
+        assert_eq!(Feature::load(root, "api").unwrap().phase, Phase::Merge);
+
+        let mut db = Feature::load(root, "db").unwrap();
+        db.phase = Phase::Released;
+        db.save(root).unwrap();
+        run(root, "api", true, false, OutputFormat::Json).unwrap();
+        assert_eq!(Feature::load(root, "api").unwrap().phase, Phase::Released);
+    }
+
+    #[test]
+    fn merge_force_skips_the_dependency_check() {
+        let dir = project();
+        let root = dir.path();
+
+        run(root, "api", true, true, OutputFormat::Json).unwrap();
+        assert_eq!(Feature::load(root, "api").unwrap().phase, Phase::Released);
+    }
+}
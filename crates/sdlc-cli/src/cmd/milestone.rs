@@ -1,23 +1,32 @@
-use crate::output::{print_json, print_table};
+use crate::output::{print_table, OutputFormat};
 use anyhow::Context;
 use clap::Subcommand;
 use sdlc_core::{
     classifier::{Classifier, EvalContext},
     config::Config,
     feature::Feature,
-    milestone::Milestone,
+    milestone::{self, Bundle, Milestone},
     rules::default_rules,
+    slug,
     state::State,
     types::ActionType,
 };
 use std::io::Read as IoRead;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+
+#[derive(Clone, Copy, clap::ValueEnum)]
+pub enum GraphSyntax {
+    Dot,
+    Mermaid,
+    Json,
+}
 
 #[derive(Subcommand)]
 pub enum MilestoneSubcommand {
     /// Create a new milestone
     Create {
-        slug: String,
+        /// Derived from --title when omitted
+        slug: Option<String>,
         /// Milestone title
         #[arg(long)]
         title: String,
@@ -31,6 +40,15 @@ pub enum MilestoneSubcommand {
     Info { slug: String },
     /// List all tasks across every feature in a milestone
     Tasks { slug: String },
+    /// Remaining tasks and features per day, from the audit log
+    Burndown { slug: String },
+    /// Print the feature dependency graph (fails on a dependency cycle)
+    Deps {
+        slug: String,
+        /// Graph syntax (the global --format/--json flags select json too)
+        #[arg(long = "as", value_enum, default_value_t = GraphSyntax::Dot)]
+        syntax: GraphSyntax,
+    },
     /// Add a feature to a milestone
     AddFeature {
         slug: String,
@@ -73,40 +91,56 @@ pub enum MilestoneSubcommand {
     Review { slug: String },
     /// Mark a milestone as prepared (pre-flight complete, wave plan ready)
     MarkPrepared { slug: String },
+    /// Export a milestone, its features, and their artifacts as a .tar.gz bundle
+    Export {
+        slug: String,
+        /// Output path (default: ./<slug>.tar.gz)
+        #[arg(long)]
+        out: Option<PathBuf>,
+    },
+    /// Import a milestone bundle created by `milestone export` (idempotent)
+    Import { path: PathBuf },
 }
 
-pub fn run(root: &Path, subcmd: MilestoneSubcommand, json: bool) -> anyhow::Result<()> {
+pub fn run(root: &Path, subcmd: MilestoneSubcommand, out: OutputFormat) -> anyhow::Result<()> {
     match subcmd {
         MilestoneSubcommand::Create {
             slug,
             title,
             features,
-        } => create(root, &slug, &title, &features, json),
-        MilestoneSubcommand::List => list(root, json),
-        MilestoneSubcommand::Info { slug } => info(root, &slug, json),
-        MilestoneSubcommand::Tasks { slug } => tasks(root, &slug, json),
+        } => {
+            let slug = slug.unwrap_or_else(|| slug::derive(&title));
+            create(root, &slug, &title, &features, out)
+        }
+        MilestoneSubcommand::List => list(root, out),
+        MilestoneSubcommand::Info { slug } => info(root, &slug, out),
+        MilestoneSubcommand::Tasks { slug } => tasks(root, &slug, out),
+        MilestoneSubcommand::Burndown { slug } => burndown(root, &slug, out),
+        MilestoneSubcommand::Deps { slug, syntax } => deps(root, &slug, syntax, out),
         MilestoneSubcommand::AddFeature {
             slug,
             feature_slug,
             position,
-        } => add_feature(root, &slug, &feature_slug, position, json),
+        } => add_feature(root, &slug, &feature_slug, position, out),
         MilestoneSubcommand::RemoveFeature { slug, feature_slug } => {
-            remove_feature(root, &slug, &feature_slug, json)
+            remove_feature(root, &slug, &feature_slug, out)
         }
-        MilestoneSubcommand::Reorder { slug, features } => reorder(root, &slug, &features, json),
-        MilestoneSubcommand::Complete { slug } => complete(root, &slug, json),
-        MilestoneSubcommand::Cancel { slug } => cancel(root, &slug, json),
-        MilestoneSubcommand::Skip { slug } => skip(root, &slug, json),
+        MilestoneSubcommand::Reorder { slug, features } => reorder(root, &slug, &features, out),
+        MilestoneSubcommand::Complete { slug } => complete(root, &slug, out),
+        MilestoneSubcommand::Cancel { slug } => cancel(root, &slug, out),
+        MilestoneSubcommand::Skip { slug } => skip(root, &slug, out),
         MilestoneSubcommand::Update {
             slug,
             title,
             vision,
-        } => update(root, &slug, title.as_deref(), vision.as_deref(), json),
+        } => update(root, &slug, title.as_deref(), vision.as_deref(), out),
         MilestoneSubcommand::SetAcceptanceTest { slug, file } => {
-            set_acceptance_test(root, &slug, file.as_deref(), json)
+            set_acceptance_test(root, &slug, file.as_deref(), out)
         }
-        MilestoneSubcommand::Review { slug } => review(root, &slug, json),
+        MilestoneSubcommand::Review { slug } => review(root, &slug, out),
         MilestoneSubcommand::MarkPrepared { slug } => mark_prepared(root, &slug),
+        MilestoneSubcommand::Export { slug, out: path } => export(root, &slug, path, out),
+        MilestoneSubcommand::Import { path } => import(root, &path, out),
     }
 }
 
@@ -115,7 +149,7 @@ fn create(
     slug: &str,
     title: &str,
     initial_features: &[String],
-    json: bool,
+    out: OutputFormat,
 ) -> anyhow::Result<()> {
     let mut milestone = Milestone::create(root, slug, title)
         .with_context(|| format!("failed to create milestone '{slug}'"))?;
@@ -125,66 +159,63 @@ fn create(
     }
     milestone.save(root).context("failed to save milestone")?;
 
-    let mut state = State::load(root).context("failed to load state")?;
-    state.add_milestone(slug);
-    state.save(root).context("failed to save state")?;
+    State::update(root, |state| state.add_milestone(slug)).context("failed to update state")?;
 
-    if json {
-        print_json(&serde_json::json!({
+    out.emit(
+        &serde_json::json!({
             "slug": slug,
             "title": title,
             "features": milestone.features,
-        }))?;
-    } else {
-        println!("Created milestone '{slug}'.");
-        if !milestone.features.is_empty() {
-            println!("  Features: {}", milestone.features.join(", "));
-        }
-    }
+        }),
+        || {
+            println!("Created milestone '{slug}'.");
+            if !milestone.features.is_empty() {
+                println!("  Features: {}", milestone.features.join(", "));
+            }
+            Ok(())
+        },
+    )?;
     Ok(())
 }
 
-fn list(root: &Path, json: bool) -> anyhow::Result<()> {
+fn list(root: &Path, out: OutputFormat) -> anyhow::Result<()> {
     let milestones = Milestone::list(root).context("failed to list milestones")?;
     let features = Feature::list(root).unwrap_or_default();
 
-    if json {
-        let items: Vec<serde_json::Value> = milestones
-            .iter()
-            .map(|m| {
-                serde_json::json!({
-                    "slug": m.slug,
-                    "title": m.title,
-                    "status": m.compute_status(&features).to_string(),
-                    "feature_count": m.features.len(),
-                })
-            })
-            .collect();
-        print_json(&items)?;
-        return Ok(());
-    }
-
-    if milestones.is_empty() {
-        println!("No milestones.");
-        return Ok(());
-    }
-
-    let rows: Vec<Vec<String>> = milestones
+    let items: Vec<serde_json::Value> = milestones
         .iter()
         .map(|m| {
-            vec![
-                m.slug.clone(),
-                m.title.clone(),
-                m.compute_status(&features).to_string(),
-                m.features.len().to_string(),
-            ]
+            serde_json::json!({
+                "slug": m.slug,
+                "title": m.title,
+                "status": m.compute_status(&features).to_string(),
+                "feature_count": m.features.len(),
+            })
         })
         .collect();
-    print_table(&["SLUG", "TITLE", "STATUS", "FEATURES"], rows);
-    Ok(())
+    out.emit(&items, || {
+        if milestones.is_empty() {
+            println!("No milestones.");
+            return Ok(());
+        }
+
+        let rows: Vec<Vec<String>> = milestones
+            .iter()
+            .map(|m| {
+                vec![
+                    m.slug.clone(),
+                    m.title.clone(),
+                    m.compute_status(&features).to_string(),
+                    m.features.len().to_string(),
+                ]
+            })
+            .collect();
+        print_table(&["SLUG", "TITLE", "STATUS", "FEATURES"], rows);
+        Ok(())
+    })
 }
 
-fn info(root: &Path, slug: &str, json: bool) -> anyhow::Result<()> {
+fn info(root: &Path, slug: &str, out: OutputFormat) -> anyhow::Result<()> {
     let milestone =
         Milestone::load(root, slug).with_context(|| format!("milestone '{slug}' not found"))?;
     let features = Feature::list(root).unwrap_or_default();
@@ -203,8 +234,7 @@ fn info(root: &Path, slug: &str, json: bool) -> anyhow::Result<()> {
             .map(|l| l.replace("**Verdict:**", "").trim().to_string())
     });
 
-    if json {
-        print_json(&serde_json::json!({
+    out.emit(&serde_json::json!({
             "slug": milestone.slug,
             "title": milestone.title,
             "vision": milestone.vision,
@@ -217,37 +247,36 @@ fn info(root: &Path, slug: &str, json: bool) -> anyhow::Result<()> {
             "updated_at": milestone.updated_at,
             "skipped_at": milestone.skipped_at,
             "prepared_at": milestone.prepared_at,
-        }))?;
-        return Ok(());
-    }
+        }), || {
 
-    println!("Milestone: {} — {}", milestone.slug, milestone.title);
-    println!("Status:    {}", status);
-    if let Some(ref v) = milestone.vision {
-        println!("Vision:    {v}");
-    }
-    if acceptance_test.is_some() {
-        let verdict_str = uat_verdict
-            .map(|v| format!(" — last run: {v}"))
-            .unwrap_or_default();
-        println!("UAT:       acceptance_test.md (set){verdict_str}");
-    } else {
-        println!(
-            "UAT:       (not set — use `sdlc milestone set-acceptance-test {slug} --file acceptance_test.md`)"
-        );
-    }
-    println!("Features:  {}", milestone.features.len());
-    if milestone.features.is_empty() {
-        println!("  (none)");
-    } else {
-        for f in &milestone.features {
-            println!("  {}", f);
+        println!("Milestone: {} — {}", milestone.slug, milestone.title);
+        println!("Status:    {}", status);
+        if let Some(ref v) = milestone.vision {
+            println!("Vision:    {v}");
         }
-    }
-    Ok(())
+        if acceptance_test.is_some() {
+            let verdict_str = uat_verdict
+                .map(|v| format!(" — last run: {v}"))
+                .unwrap_or_default();
+            println!("UAT:       acceptance_test.md (set){verdict_str}");
+        } else {
+            println!(
+                "UAT:       (not set — use `sdlc milestone set-acceptance-test {slug} --file acceptance_test.md`)"
+            );
+        }
+        println!("Features:  {}", milestone.features.len());
+        if milestone.features.is_empty() {
+            println!("  (none)");
+        } else {
+            for f in &milestone.features {
+                println!("  {}", f);
+            }
+        }
+        Ok(())
+    })
 }
 
-fn tasks(root: &Path, slug: &str, json: bool) -> anyhow::Result<()> {
+fn tasks(root: &Path, slug: &str, out: OutputFormat) -> anyhow::Result<()> {
     let milestone =
         Milestone::load(root, slug).with_context(|| format!("milestone '{slug}' not found"))?;
 
@@ -275,33 +304,30 @@ fn tasks(root: &Path, slug: &str, json: bool) -> anyhow::Result<()> {
         }
     }
 
-    if json {
-        let items: Vec<serde_json::Value> = all_tasks
-            .iter()
-            .map(|(feature, id, status, title)| {
-                serde_json::json!({
-                    "feature": feature,
-                    "task_id": id,
-                    "status": status,
-                    "title": title,
-                })
+    let items: Vec<serde_json::Value> = all_tasks
+        .iter()
+        .map(|(feature, id, status, title)| {
+            serde_json::json!({
+                "feature": feature,
+                "task_id": id,
+                "status": status,
+                "title": title,
             })
-            .collect();
-        print_json(&items)?;
-        return Ok(());
-    }
-
-    if all_tasks.is_empty() {
-        println!("No tasks in milestone '{slug}'.");
-        return Ok(());
-    }
-
-    let rows: Vec<Vec<String>> = all_tasks
-        .into_iter()
-        .map(|(feature, id, status, title)| vec![feature, id, status, title])
+        })
         .collect();
-    print_table(&["FEATURE", "TASK ID", "STATUS", "TITLE"], rows);
-    Ok(())
+    out.emit(&items, || {
+        if all_tasks.is_empty() {
+            println!("No tasks in milestone '{slug}'.");
+            return Ok(());
+        }
+
+        let rows: Vec<Vec<String>> = all_tasks
+            .into_iter()
+            .map(|(feature, id, status, title)| vec![feature, id, status, title])
+            .collect();
+        print_table(&["FEATURE", "TASK ID", "STATUS", "TITLE"], rows);
+        Ok(())
+    })
 }
 
 fn add_feature(
@@ -309,7 +335,7 @@ fn add_feature(
     slug: &str,
     feature_slug: &str,
     position: Option<usize>,
-    json: bool,
+    out: OutputFormat,
 ) -> anyhow::Result<()> {
     let mut milestone =
         Milestone::load(root, slug).with_context(|| format!("milestone '{slug}' not found"))?;
@@ -329,19 +355,21 @@ fn add_feature(
     }
     milestone.save(root).context("failed to save milestone")?;
 
-    if json {
-        print_json(&serde_json::json!({
+    out.emit(
+        &serde_json::json!({
             "slug": slug,
             "feature_slug": feature_slug,
             "added": true,
-        }))?;
-    } else {
-        println!("Added feature '{feature_slug}' to milestone '{slug}'.");
-    }
+        }),
+        || {
+            println!("Added feature '{feature_slug}' to milestone '{slug}'.");
+            Ok(())
+        },
+    )?;
     Ok(())
 }
 
-fn reorder(root: &Path, slug: &str, features: &[String], json: bool) -> anyhow::Result<()> {
+fn reorder(root: &Path, slug: &str, features: &[String], out: OutputFormat) -> anyhow::Result<()> {
     let mut milestone =
         Milestone::load(root, slug).with_context(|| format!("milestone '{slug}' not found"))?;
 
@@ -349,20 +377,27 @@ fn reorder(root: &Path, slug: &str, features: &[String], json: bool) -> anyhow::
     milestone.reorder_features(&refs)?;
     milestone.save(root).context("failed to save milestone")?;
 
-    if json {
-        print_json(&serde_json::json!({
+    out.emit(
+        &serde_json::json!({
             "slug": slug,
             "features": milestone.features,
-        }))?;
-    } else {
-        for (i, f) in milestone.features.iter().enumerate() {
-            println!("{}. {}", i + 1, f);
-        }
-    }
+        }),
+        || {
+            for (i, f) in milestone.features.iter().enumerate() {
+                println!("{}. {}", i + 1, f);
+            }
+            Ok(())
+        },
+    )?;
     Ok(())
 }
 
-fn remove_feature(root: &Path, slug: &str, feature_slug: &str, json: bool) -> anyhow::Result<()> {
+fn remove_feature(
+    root: &Path,
+    slug: &str,
+    feature_slug: &str,
+    out: OutputFormat,
+) -> anyhow::Result<()> {
     let mut milestone =
         Milestone::load(root, slug).with_context(|| format!("milestone '{slug}' not found"))?;
 
@@ -376,60 +411,68 @@ fn remove_feature(root: &Path, slug: &str, feature_slug: &str, json: bool) -> an
     }
     milestone.save(root).context("failed to save milestone")?;
 
-    if json {
-        print_json(&serde_json::json!({
+    out.emit(
+        &serde_json::json!({
             "slug": slug,
             "feature_slug": feature_slug,
             "removed": true,
-        }))?;
-    } else {
-        println!("Removed feature '{feature_slug}' from milestone '{slug}'.");
-    }
+        }),
+        || {
+            println!("Removed feature '{feature_slug}' from milestone '{slug}'.");
+            Ok(())
+        },
+    )?;
     Ok(())
 }
 
-fn complete(root: &Path, slug: &str, json: bool) -> anyhow::Result<()> {
+fn complete(root: &Path, slug: &str, out: OutputFormat) -> anyhow::Result<()> {
     let mut milestone =
         Milestone::load(root, slug).with_context(|| format!("milestone '{slug}' not found"))?;
 
     milestone.release();
     milestone.save(root).context("failed to save milestone")?;
 
-    if json {
-        print_json(&serde_json::json!({ "slug": slug, "status": "released" }))?;
-    } else {
-        println!("Milestone '{slug}' marked complete.");
-    }
+    out.emit(
+        &serde_json::json!({ "slug": slug, "status": "released" }),
+        || {
+            println!("Milestone '{slug}' marked complete.");
+            Ok(())
+        },
+    )?;
     Ok(())
 }
 
-fn cancel(root: &Path, slug: &str, json: bool) -> anyhow::Result<()> {
+fn cancel(root: &Path, slug: &str, out: OutputFormat) -> anyhow::Result<()> {
     let mut milestone =
         Milestone::load(root, slug).with_context(|| format!("milestone '{slug}' not found"))?;
 
     milestone.skip();
     milestone.save(root).context("failed to save milestone")?;
 
-    if json {
-        print_json(&serde_json::json!({ "slug": slug, "status": "skipped" }))?;
-    } else {
-        println!("Milestone '{slug}' cancelled.");
-    }
+    out.emit(
+        &serde_json::json!({ "slug": slug, "status": "skipped" }),
+        || {
+            println!("Milestone '{slug}' cancelled.");
+            Ok(())
+        },
+    )?;
     Ok(())
 }
 
-fn skip(root: &Path, slug: &str, json: bool) -> anyhow::Result<()> {
+fn skip(root: &Path, slug: &str, out: OutputFormat) -> anyhow::Result<()> {
     let mut milestone =
         Milestone::load(root, slug).with_context(|| format!("milestone '{slug}' not found"))?;
 
     milestone.skip();
     milestone.save(root).context("failed to save milestone")?;
 
-    if json {
-        print_json(&serde_json::json!({ "slug": slug, "status": "skipped" }))?;
-    } else {
-        println!("Milestone '{slug}' skipped.");
-    }
+    out.emit(
+        &serde_json::json!({ "slug": slug, "status": "skipped" }),
+        || {
+            println!("Milestone '{slug}' skipped.");
+            Ok(())
+        },
+    )?;
     Ok(())
 }
 
@@ -438,7 +481,7 @@ fn update(
     slug: &str,
     title: Option<&str>,
     vision: Option<&str>,
-    json: bool,
+    out: OutputFormat,
 ) -> anyhow::Result<()> {
     let mut milestone =
         Milestone::load(root, slug).with_context(|| format!("milestone '{slug}' not found"))?;
@@ -451,15 +494,17 @@ fn update(
     }
     milestone.save(root).context("failed to save milestone")?;
 
-    if json {
-        print_json(&serde_json::json!({
+    out.emit(
+        &serde_json::json!({
             "slug": slug,
             "title": milestone.title,
             "vision": milestone.vision,
-        }))?;
-    } else {
-        println!("Updated milestone '{slug}'.");
-    }
+        }),
+        || {
+            println!("Updated milestone '{slug}'.");
+            Ok(())
+        },
+    )?;
     Ok(())
 }
 
@@ -467,7 +512,7 @@ fn set_acceptance_test(
     root: &Path,
     slug: &str,
     file: Option<&std::path::Path>,
-    json: bool,
+    out: OutputFormat,
 ) -> anyhow::Result<()> {
     let milestone =
         Milestone::load(root, slug).with_context(|| format!("milestone '{slug}' not found"))?;
@@ -487,18 +532,20 @@ fn set_acceptance_test(
         .save_acceptance_test(root, &content)
         .context("failed to save acceptance test")?;
 
-    if json {
-        print_json(&serde_json::json!({
+    out.emit(
+        &serde_json::json!({
             "slug": slug,
             "acceptance_test_set": true,
             "bytes": content.len(),
-        }))?;
-    } else {
-        println!(
-            "Acceptance test set for milestone '{slug}' ({} bytes).",
-            content.len()
-        );
-    }
+        }),
+        || {
+            println!(
+                "Acceptance test set for milestone '{slug}' ({} bytes).",
+                content.len()
+            );
+            Ok(())
+        },
+    )?;
     Ok(())
 }
 
@@ -511,7 +558,98 @@ fn mark_prepared(root: &Path, slug: &str) -> anyhow::Result<()> {
     Ok(())
 }
 
-fn review(root: &Path, slug: &str, json: bool) -> anyhow::Result<()> {
+fn export(root: &Path, slug: &str, path: Option<PathBuf>, out: OutputFormat) -> anyhow::Result<()> {
+    let path = path.unwrap_or_else(|| PathBuf::from(format!("{slug}.tar.gz")));
+    let bundle =
+        milestone::export(root, slug).with_context(|| format!("failed to export '{slug}'"))?;
+    bundle
+        .write_tar_gz(&path)
+        .with_context(|| format!("failed to write {}", path.display()))?;
+
+    out.emit(
+        &serde_json::json!({
+            "milestone": slug,
+            "path": path,
+            "features": bundle.manifest.features,
+            "files": bundle.manifest.files.len(),
+        }),
+        || {
+            println!(
+                "Exported milestone '{slug}' ({} features, {} files) to {}",
+                bundle.manifest.features.len(),
+                bundle.manifest.files.len(),
+                path.display()
+            );
+            Ok(())
+        },
+    )?;
+    Ok(())
+}
+
+fn import(root: &Path, path: &Path, out: OutputFormat) -> anyhow::Result<()> {
+    let bundle = Bundle::read_tar_gz(path)
+        .with_context(|| format!("failed to read bundle {}", path.display()))?;
+    milestone::import(root, &bundle).context("failed to import bundle")?;
+
+    let slug = &bundle.manifest.milestone;
+    out.emit(
+        &serde_json::json!({
+            "milestone": slug,
+            "features": bundle.manifest.features,
+            "files": bundle.manifest.files.len(),
+        }),
+        || {
+            println!(
+                "Imported milestone '{slug}' ({} features, {} files)",
+                bundle.manifest.features.len(),
+                bundle.manifest.files.len()
+            );
+            Ok(())
+        },
+    )?;
+    Ok(())
+}
+
+fn burndown(root: &Path, slug: &str, out: OutputFormat) -> anyhow::Result<()> {
+    let points = milestone::burndown(root, slug)
+        .with_context(|| format!("failed to compute burndown for '{slug}'"))?;
+
+    out.emit(&points, || {
+        if points.is_empty() {
+            println!("No history for milestone '{slug}' yet.");
+            return Ok(());
+        }
+
+        let rows: Vec<Vec<String>> = points
+            .iter()
+            .map(|p| {
+                vec![
+                    p.date.to_string(),
+                    format!("{}/{}", p.remaining_tasks, p.total_tasks),
+                    format!("{}/{}", p.remaining_features, p.total_features),
+                ]
+            })
+            .collect();
+        print_table(&["DATE", "TASKS LEFT", "FEATURES LEFT"], rows);
+        Ok(())
+    })
+}
+
+fn deps(root: &Path, slug: &str, syntax: GraphSyntax, out: OutputFormat) -> anyhow::Result<()> {
+    let graph = milestone::dep_graph(root, slug)
+        .with_context(|| format!("failed to build dependency graph for '{slug}'"))?;
+
+    out.emit(&graph, || {
+        match syntax {
+            GraphSyntax::Json => OutputFormat::Json.print(&graph)?,
+            GraphSyntax::Dot => print!("{}", graph.to_dot()),
+            GraphSyntax::Mermaid => print!("{}", graph.to_mermaid()),
+        }
+        Ok(())
+    })
+}
+
+fn review(root: &Path, slug: &str, out: OutputFormat) -> anyhow::Result<()> {
     let milestone =
         Milestone::load(root, slug).with_context(|| format!("milestone '{slug}' not found"))?;
 
@@ -525,12 +663,7 @@ fn review(root: &Path, slug: &str, json: bool) -> anyhow::Result<()> {
     for feature_slug in &milestone.features {
         match Feature::load(root, feature_slug) {
             Ok(feature) => {
-                let ctx = EvalContext {
-                    feature: &feature,
-                    state: &state,
-                    config: &config,
-                    root,
-                };
+                let ctx = EvalContext::new(&feature, &state, &config, root);
                 let c = classifier.classify(&ctx);
 
                 let next_action = if let Some(tid) = &c.task_id {
@@ -571,50 +704,50 @@ fn review(root: &Path, slug: &str, json: bool) -> anyhow::Result<()> {
         }
     }
 
-    if json {
-        let items: Vec<serde_json::Value> = rows
-            .iter()
-            .map(|(feature, phase, next_action, message, blocked)| {
-                serde_json::json!({
-                    "feature": feature,
-                    "phase": phase,
-                    "next_action": next_action,
-                    "message": message,
-                    "blocked": blocked != "no",
-                    "blocked_reason": if blocked == "no" { None } else { Some(blocked) },
-                })
+    let items: Vec<serde_json::Value> = rows
+        .iter()
+        .map(|(feature, phase, next_action, message, blocked)| {
+            serde_json::json!({
+                "feature": feature,
+                "phase": phase,
+                "next_action": next_action,
+                "message": message,
+                "blocked": blocked != "no",
+                "blocked_reason": if blocked == "no" { None } else { Some(blocked) },
             })
-            .collect();
-        print_json(&serde_json::json!({
+        })
+        .collect();
+    out.emit(
+        &serde_json::json!({
             "milestone": slug,
             "feature_count": milestone.features.len(),
             "features": items,
-        }))?;
-        return Ok(());
-    }
-
-    println!(
-        "Milestone: {} ({} feature{})\n",
-        slug,
-        milestone.features.len(),
-        if milestone.features.len() == 1 {
-            ""
-        } else {
-            "s"
-        }
-    );
+        }),
+        || {
+            println!(
+                "Milestone: {} ({} feature{})\n",
+                slug,
+                milestone.features.len(),
+                if milestone.features.len() == 1 {
+                    ""
+                } else {
+                    "s"
+                }
+            );
 
-    if rows.is_empty() {
-        println!("No features in this milestone.");
-        return Ok(());
-    }
+            if rows.is_empty() {
+                println!("No features in this milestone.");
+                return Ok(());
+            }
 
-    let table_rows: Vec<Vec<String>> = rows
-        .into_iter()
-        .map(|(feature, phase, next_action, _message, blocked)| {
-            vec![feature, phase, next_action, blocked]
-        })
-        .collect();
-    print_table(&["FEATURE", "PHASE", "NEXT ACTION", "BLOCKED"], table_rows);
-    Ok(())
+            let table_rows: Vec<Vec<String>> = rows
+                .into_iter()
+                .map(|(feature, phase, next_action, _message, blocked)| {
+                    vec![feature, phase, next_action, blocked]
+                })
+                .collect();
+            print_table(&["FEATURE", "PHASE", "NEXT ACTION", "BLOCKED"], table_rows);
+            Ok(())
+        },
+    )
 }
@@ -6,6 +6,7 @@ pub mod changelog;
 pub mod comment;
 pub mod commit;
 pub mod config;
+pub mod doctor;
 pub mod escalate;
 pub mod feature;
 pub mod focus;
@@ -1,15 +1,36 @@
-use crate::output::print_json;
+use crate::output::OutputFormat;
 use anyhow::Context;
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
 use sdlc_core::{
     classifier::{Classifier, EvalContext},
     config::Config,
     feature::Feature,
+    paths,
     rules::default_rules,
     state::State,
 };
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver};
+use std::time::Duration;
 
-pub fn run(root: &Path, feature_slug: Option<&str>, json: bool) -> anyhow::Result<()> {
+/// Quiet period after the last filesystem event before re-emitting.
+/// Atomic writes produce several events (create, rename) per save.
+const WATCH_DEBOUNCE: Duration = Duration::from_millis(250);
+
+pub fn run(
+    root: &Path,
+    feature_slug: Option<&str>,
+    out: OutputFormat,
+    watch: bool,
+) -> anyhow::Result<()> {
+    if watch {
+        return run_watch(root, feature_slug, out);
+    }
+    emit(root, feature_slug, out)
+}
+
+/// Print the directive(s) once.
+fn emit(root: &Path, feature_slug: Option<&str>, out: OutputFormat) -> anyhow::Result<()> {
     let config = Config::load(root).context("failed to load config")?;
     let state = State::load(root).context("failed to load state")?;
     let classifier = Classifier::new(default_rules());
@@ -18,20 +39,14 @@ pub fn run(root: &Path, feature_slug: Option<&str>, json: bool) -> anyhow::Resul
         Some(slug) => {
             let feature =
                 Feature::load(root, slug).with_context(|| format!("feature '{slug}' not found"))?;
-            let ctx = EvalContext {
-                feature: &feature,
-                state: &state,
-                config: &config,
-                root,
-            };
+            let ctx = EvalContext::new(&feature, &state, &config, root);
             let classification = classifier.classify(&ctx);
 
-            if json {
-                print_json(&classification)?;
-            } else {
+            out.emit(&classification, || {
                 println!("Feature:  {}", classification.feature);
                 println!("Phase:    {}", classification.current_phase);
                 println!("Action:   {}", classification.action);
+                println!("Why:      {}", classification.rationale);
                 println!("Message:  {}", classification.message);
                 if !classification.next_command.is_empty() {
                     println!("Command:  {}", classification.next_command);
@@ -39,41 +54,37 @@ pub fn run(root: &Path, feature_slug: Option<&str>, json: bool) -> anyhow::Resul
                 if let Some(ref path) = classification.output_path {
                     println!("Output:   {path}");
                 }
-            }
+                if !classification.stale_artifacts.is_empty() {
+                    let stale: Vec<String> = classification
+                        .stale_artifacts
+                        .iter()
+                        .map(|a| a.to_string())
+                        .collect();
+                    println!(
+                        "Stale:    {} (upstream changed since approval)",
+                        stale.join(", ")
+                    );
+                }
+                Ok(())
+            })?;
         }
         None => {
             // Classify all active features
             let features = Feature::list(root).context("failed to list features")?;
             let active: Vec<&Feature> = features.iter().filter(|f| !f.archived).collect();
 
-            if active.is_empty() {
-                println!("No active features. Run: sdlc feature create <slug>");
-                return Ok(());
-            }
-
-            if json {
-                let classifications: Vec<_> = active
-                    .iter()
-                    .map(|f| {
-                        let ctx = EvalContext {
-                            feature: f,
-                            state: &state,
-                            config: &config,
-                            root,
-                        };
-                        classifier.classify(&ctx)
-                    })
-                    .collect();
-                print_json(&classifications)?;
-            } else {
-                for feature in active {
-                    let ctx = EvalContext {
-                        feature,
-                        state: &state,
-                        config: &config,
-                        root,
-                    };
-                    let c = classifier.classify(&ctx);
+            let classifications: Vec<_> = active
+                .iter()
+                .map(|f| {
+                    let ctx = EvalContext::new(f, &state, &config, root);
+                    classifier.classify(&ctx)
+                })
+                .collect();
+            out.emit(&classifications, || {
+                if classifications.is_empty() {
+                    println!("No active features. Run: sdlc feature create <slug>");
+                }
+                for c in &classifications {
                     println!(
                         "{:<20} [{:<15}] {} — {}",
                         c.feature,
@@ -82,9 +93,147 @@ pub fn run(root: &Path, feature_slug: Option<&str>, json: bool) -> anyhow::Resul
                         c.message
                     );
                 }
-            }
+                Ok(())
+            })?;
         }
     }
 
     Ok(())
 }
+
+// ---------------------------------------------------------------------------
+// --watch
+// ---------------------------------------------------------------------------
+
+/// Emit once, then re-emit after every debounced change to `state.yaml` or
+/// the watched feature directory. Runs until the process is interrupted.
+fn run_watch(root: &Path, feature_slug: Option<&str>, out: OutputFormat) -> anyhow::Result<()> {
+    // A watch streams one document per change, so structured output is JSONL.
+    let out = if out.is_structured() {
+        OutputFormat::Jsonl
+    } else {
+        out
+    };
+    emit(root, feature_slug, out)?;
+
+    let targets = WatchTargets::new(root, feature_slug);
+    let (_watcher, rx) = targets.start().context("failed to start file watcher")?;
+
+    watch_loop(rx, &targets, WATCH_DEBOUNCE, || {
+        if !out.is_structured() {
+            println!();
+            println!("--- {} ---", chrono::Local::now().format("%H:%M:%S"));
+        }
+        // A transient read error (e.g. a feature removed mid-watch) should not
+        // end the session — report it and wait for the next change.
+        if let Err(e) = emit(root, feature_slug, out) {
+            eprintln!("error: {e:#}");
+        }
+    });
+    Ok(())
+}
+
+/// The files whose changes can alter the directive.
+struct WatchTargets {
+    sdlc_dir: PathBuf,
+    state_file: PathBuf,
+    features: PathBuf,
+}
+
+impl WatchTargets {
+    fn new(root: &Path, feature_slug: Option<&str>) -> Self {
+        // Watch canonical paths so event paths compare equal on platforms
+        // that report resolved symlinks (e.g. /private/var on macOS).
+        let root = std::fs::canonicalize(root).unwrap_or_else(|_| root.to_path_buf());
+        let features = match feature_slug {
+            Some(slug) => paths::feature_dir(&root, slug),
+            None => root.join(paths::FEATURES_DIR),
+        };
+        Self {
+            sdlc_dir: paths::sdlc_dir(&root),
+            state_file: paths::state_path(&root),
+            features,
+        }
+    }
+
+    /// `state.yaml` is replaced via rename on every save, so its parent
+    /// directory is watched rather than the file itself.
+    fn start(&self) -> notify::Result<(RecommendedWatcher, Receiver<notify::Result<Event>>)> {
+        let (tx, rx) = mpsc::channel();
+        let mut watcher = notify::recommended_watcher(tx)?;
+        watcher.watch(&self.sdlc_dir, RecursiveMode::NonRecursive)?;
+        watcher.watch(&self.features, RecursiveMode::Recursive)?;
+        Ok((watcher, rx))
+    }
+
+    fn is_relevant(&self, event: &Event) -> bool {
+        matches!(
+            event.kind,
+            EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_)
+        ) && event
+            .paths
+            .iter()
+            .any(|p| p == &self.state_file || p.starts_with(&self.features))
+    }
+}
+
+/// Call `on_change` once per burst of relevant events. A burst ends when no
+/// event arrives for `debounce`. Returns when the watcher is dropped.
+fn watch_loop(
+    rx: Receiver<notify::Result<Event>>,
+    targets: &WatchTargets,
+    debounce: Duration,
+    mut on_change: impl FnMut(),
+) {
+    loop {
+        match rx.recv() {
+            Ok(Ok(event)) if targets.is_relevant(&event) => {}
+            Ok(_) => continue,
+            Err(_) => return,
+        }
+        // Drain until the burst goes quiet (or the watcher is dropped).
+        while rx.recv_timeout(debounce).is_ok() {}
+        on_change();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use tempfile::TempDir;
+
+    #[test]
+    fn state_change_triggers_one_emission() {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path();
+        Config::new("test").save(root).unwrap();
+        let mut state = State::new("test");
+        Feature::create(root, "auth", "Auth").unwrap();
+        state.add_active_feature("auth");
+        state.save(root).unwrap();
+
+        let targets = WatchTargets::new(root, Some("auth"));
+        let (watcher, rx) = targets.start().unwrap();
+        let emissions = Arc::new(AtomicUsize::new(0));
+        let counter = emissions.clone();
+        let debounce = Duration::from_millis(100);
+        let handle = std::thread::spawn(move || {
+            watch_loop(rx, &targets, debounce, || {
+                counter.fetch_add(1, Ordering::SeqCst);
+            });
+        });
+
+        // Two saves in quick succession are one logical change.
+        let mut state = State::load(root).unwrap();
+        state.set_blocked("auth", "waiting on credentials");
+        state.save(root).unwrap();
+        state.save(root).unwrap();
+
+        std::thread::sleep(debounce * 10);
+        drop(watcher);
+        handle.join().unwrap();
+        assert_eq!(emissions.load(Ordering::SeqCst), 1);
+    }
+}
@@ -1,76 +1,98 @@
-use crate::output::{print_json, print_table};
+use crate::output::{print_table, OutputFormat};
 use anyhow::Context;
 use clap::Subcommand;
 use sdlc_core::config::Config;
-use std::path::Path;
+use sdlc_core::platform_runner::{self, OutputStream, PlatformRunResult};
+use std::path::{Path, PathBuf};
 
 #[derive(Subcommand)]
 pub enum PlatformSubcommand {
     /// List all configured platform commands
     List,
-    /// Show how to run a platform command (sdlc does not execute scripts)
+    /// Run a platform command from the project root, streaming its output
+    Run {
+        /// The command, an optional subcommand, then the script's arguments
+        #[arg(required = true, trailing_var_arg = true, allow_hyphen_values = true)]
+        args: Vec<String>,
+    },
+    /// Show how to run a platform command (sdlc only executes scripts
+    /// through `sdlc platform run`)
     #[command(external_subcommand)]
     External(Vec<String>),
 }
 
-pub fn run(root: &Path, subcmd: PlatformSubcommand, json: bool) -> anyhow::Result<()> {
+pub fn run(root: &Path, subcmd: PlatformSubcommand, out: OutputFormat) -> anyhow::Result<()> {
     match subcmd {
-        PlatformSubcommand::List => list(root, json),
-        PlatformSubcommand::External(args) => show_command(root, &args),
+        PlatformSubcommand::List => list(root, out),
+        PlatformSubcommand::Run { args } => {
+            let resolved = resolve(root, &args)?;
+            execute(root, &resolved, out)
+        }
+        PlatformSubcommand::External(args) => {
+            let resolved = resolve(root, &args)?;
+            print_run_directive(&resolved, &args);
+            Ok(())
+        }
     }
 }
 
-fn list(root: &Path, json: bool) -> anyhow::Result<()> {
+/// A platform command resolved to its script.
+struct ResolvedCommand {
+    /// The command as invoked, e.g. `deploy` or `dev start`.
+    command: String,
+    script_path: PathBuf,
+    /// Arguments passed through to the script.
+    args: Vec<String>,
+}
+
+fn list(root: &Path, out: OutputFormat) -> anyhow::Result<()> {
     let config = Config::load(root).context("failed to load config")?;
     let platform = config
         .platform
         .as_ref()
         .ok_or_else(|| anyhow::anyhow!("no platform commands configured in .sdlc/config.yaml\nRun: sdlc init --platform <name>"))?;
 
-    if json {
-        let items: Vec<serde_json::Value> = platform
-            .commands
-            .iter()
-            .map(|(name, cmd)| {
-                let subs: Vec<&str> = cmd.subcommands.keys().map(|s| s.as_str()).collect();
-                serde_json::json!({
-                    "name": name,
-                    "description": cmd.description,
-                    "script": cmd.script,
-                    "subcommands": subs,
-                })
-            })
-            .collect();
-        print_json(&items)?;
-        return Ok(());
-    }
-
-    if platform.commands.is_empty() {
-        println!("No platform commands configured.");
-        return Ok(());
-    }
-
-    let mut rows: Vec<Vec<String>> = platform
+    let items: Vec<serde_json::Value> = platform
         .commands
         .iter()
         .map(|(name, cmd)| {
-            let detail = if cmd.subcommands.is_empty() {
-                cmd.script.clone()
-            } else {
-                let subs: Vec<&str> = cmd.subcommands.keys().map(|s| s.as_str()).collect();
-                format!("subcommands: {}", subs.join(", "))
-            };
-            vec![name.clone(), cmd.description.clone(), detail]
+            let subs: Vec<&str> = cmd.subcommands.keys().map(|s| s.as_str()).collect();
+            serde_json::json!({
+                "name": name,
+                "description": cmd.description,
+                "script": cmd.script,
+                "subcommands": subs,
+            })
         })
         .collect();
-    rows.sort_by(|a, b| a[0].cmp(&b[0]));
-    print_table(&["COMMAND", "DESCRIPTION", "SCRIPT / SUBCOMMANDS"], rows);
-    Ok(())
+    out.emit(&items, || {
+        if platform.commands.is_empty() {
+            println!("No platform commands configured.");
+            return Ok(());
+        }
+
+        let mut rows: Vec<Vec<String>> = platform
+            .commands
+            .iter()
+            .map(|(name, cmd)| {
+                let detail = if cmd.subcommands.is_empty() {
+                    cmd.script.clone()
+                } else {
+                    let subs: Vec<&str> = cmd.subcommands.keys().map(|s| s.as_str()).collect();
+                    format!("subcommands: {}", subs.join(", "))
+                };
+                vec![name.clone(), cmd.description.clone(), detail]
+            })
+            .collect();
+        rows.sort_by(|a, b| a[0].cmp(&b[0]));
+        print_table(&["COMMAND", "DESCRIPTION", "SCRIPT / SUBCOMMANDS"], rows);
+        Ok(())
+    })
 }
 
-/// Resolve the script path and display it so the user can run it directly.
-/// sdlc does not execute platform scripts.
-fn show_command(root: &Path, args: &[String]) -> anyhow::Result<()> {
+/// Resolve the script for `args`: the command, an optional subcommand, then
+/// the script's arguments.
+fn resolve(root: &Path, args: &[String]) -> anyhow::Result<ResolvedCommand> {
     if args.is_empty() {
         anyhow::bail!("no platform command specified; run 'sdlc platform list'");
     }
@@ -113,19 +135,22 @@ fn show_command(root: &Path, args: &[String]) -> anyhow::Result<()> {
                     available.join(", ")
                 )
             })?;
-        let extra_args: Vec<&str> = args[2..].iter().map(|s| s.as_str()).collect();
-        let script_path = root.join(script);
-        print_run_directive(&script_path, &extra_args);
-        return Ok(());
+        return Ok(ResolvedCommand {
+            command: format!("{cmd_name} {sub_name}"),
+            script_path: root.join(script),
+            args: args[2..].to_vec(),
+        });
     }
 
     // Positional arg validation
     let extra_args: Vec<&str> = args[1..].iter().map(|s| s.as_str()).collect();
     validate_args(&cmd_config.args, &extra_args, cmd_name)?;
 
-    let script_path = root.join(&cmd_config.script);
-    print_run_directive(&script_path, &extra_args);
-    Ok(())
+    Ok(ResolvedCommand {
+        command: cmd_name.clone(),
+        script_path: root.join(&cmd_config.script),
+        args: args[1..].to_vec(),
+    })
 }
 
 fn validate_args(
@@ -162,11 +187,44 @@ fn validate_args(
     Ok(())
 }
 
-fn print_run_directive(script_path: &Path, args: &[&str]) {
-    eprintln!("sdlc does not execute platform scripts. Run the script directly:");
-    if args.is_empty() {
-        eprintln!("  {}", script_path.display());
-    } else {
-        eprintln!("  {} {}", script_path.display(), args.join(" "));
+fn print_run_directive(resolved: &ResolvedCommand, args: &[String]) {
+    eprintln!("sdlc does not execute platform scripts unless asked. Run the script directly:");
+    let mut line = resolved.script_path.display().to_string();
+    for arg in &resolved.args {
+        line.push(' ');
+        line.push_str(arg);
+    }
+    eprintln!("  {line}");
+    eprintln!("or let sdlc run it:");
+    eprintln!("  sdlc platform run {}", args.join(" "));
+}
+
+/// Run the script, echoing its output live. Structured output keeps stdout
+/// for the final [`PlatformRunResult`], so the live echo goes to stderr.
+fn execute(root: &Path, resolved: &ResolvedCommand, out: OutputFormat) -> anyhow::Result<()> {
+    let ResolvedCommand {
+        command,
+        script_path,
+        args,
+    } = resolved;
+    let structured = out.is_structured();
+    let result: PlatformRunResult =
+        platform_runner::run_platform_script(root, command, script_path, args, |stream, line| {
+            match stream {
+                OutputStream::Stdout if !structured => println!("{line}"),
+                _ => eprintln!("{line}"),
+            }
+        })
+        .with_context(|| format!("failed to run {}", script_path.display()))?;
+
+    if structured {
+        out.print(&result)?;
+    }
+    if !result.success() {
+        match result.exit_code {
+            Some(code) => anyhow::bail!("platform command '{command}' exited with code {code}"),
+            None => anyhow::bail!("platform command '{command}' was killed by a signal"),
+        }
     }
+    Ok(())
 }
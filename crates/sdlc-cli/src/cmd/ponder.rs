@@ -1,9 +1,11 @@
-use crate::output::{print_json, print_table};
+use crate::output::{print_table, OutputFormat};
 use anyhow::Context;
 use clap::Subcommand;
 use sdlc_core::{
     ponder::{PonderEntry, PonderTeamMember},
+    slug,
     state::State,
+    SdlcError,
 };
 use std::io::Read as _;
 use std::path::{Path, PathBuf};
@@ -12,7 +14,8 @@ use std::path::{Path, PathBuf};
 pub enum PonderSubcommand {
     /// Create a new ponder entry
     Create {
-        slug: String,
+        /// Derived from --title when omitted
+        slug: Option<String>,
         /// Entry title
         #[arg(long)]
         title: String,
@@ -131,13 +134,14 @@ pub enum TeamSubcommand {
     List { slug: String },
 }
 
-pub fn run(root: &Path, subcmd: PonderSubcommand, json: bool) -> anyhow::Result<()> {
+pub fn run(root: &Path, subcmd: PonderSubcommand, out: OutputFormat) -> anyhow::Result<()> {
     match subcmd {
         PonderSubcommand::Create { slug, title, brief } => {
-            create(root, &slug, &title, brief.as_deref(), json)
+            let slug = slug.unwrap_or_else(|| slug::derive(&title));
+            create(root, &slug, &title, brief.as_deref(), out)
         }
-        PonderSubcommand::List { status, all } => list(root, status.as_deref(), all, json),
-        PonderSubcommand::Show { slug } => show(root, &slug, json),
+        PonderSubcommand::List { status, all } => list(root, status.as_deref(), all, out),
+        PonderSubcommand::Show { slug } => show(root, &slug, out),
         PonderSubcommand::Capture {
             slug,
             content,
@@ -149,7 +153,7 @@ pub fn run(root: &Path, subcmd: PonderSubcommand, json: bool) -> anyhow::Result<
             content.as_deref(),
             file.as_deref(),
             filename.as_deref(),
-            json,
+            out,
         ),
         PonderSubcommand::Team { subcommand } => match subcommand {
             TeamSubcommand::Add {
@@ -158,8 +162,8 @@ pub fn run(root: &Path, subcmd: PonderSubcommand, json: bool) -> anyhow::Result<
                 role,
                 context,
                 agent,
-            } => team_add(root, &slug, &name, &role, &context, &agent, json),
-            TeamSubcommand::List { slug } => team_list(root, &slug, json),
+            } => team_add(root, &slug, &name, &role, &context, &agent, out),
+            TeamSubcommand::List { slug } => team_list(root, &slug, out),
         },
         PonderSubcommand::Update {
             slug,
@@ -174,20 +178,20 @@ pub fn run(root: &Path, subcmd: PonderSubcommand, json: bool) -> anyhow::Result<
             title.as_deref(),
             &tags,
             &committed_to,
-            json,
+            out,
         ),
-        PonderSubcommand::Merge { source, into } => merge(root, &source, &into, json),
-        PonderSubcommand::Archive { slug } => archive(root, &slug, json),
-        PonderSubcommand::Delete { slug, force } => delete(root, &slug, force, json),
-        PonderSubcommand::Artifacts { slug } => artifacts(root, &slug, json),
+        PonderSubcommand::Merge { source, into } => merge(root, &source, &into, out),
+        PonderSubcommand::Archive { slug } => archive(root, &slug, out),
+        PonderSubcommand::Delete { slug, force } => delete(root, &slug, force, out),
+        PonderSubcommand::Artifacts { slug } => artifacts(root, &slug, out),
         PonderSubcommand::Session { subcommand } => match subcommand {
             SessionSubcommand::Log {
                 slug,
                 content,
                 file,
-            } => session_log(root, &slug, content.as_deref(), file.as_deref(), json),
-            SessionSubcommand::List { slug } => session_list(root, &slug, json),
-            SessionSubcommand::Read { slug, number } => session_read(root, &slug, number, json),
+            } => session_log(root, &slug, content.as_deref(), file.as_deref(), out),
+            SessionSubcommand::List { slug } => session_list(root, &slug, out),
+            SessionSubcommand::Read { slug, number } => session_read(root, &slug, number, out),
         },
     }
 }
@@ -197,7 +201,7 @@ fn create(
     slug: &str,
     title: &str,
     brief: Option<&str>,
-    json: bool,
+    out: OutputFormat,
 ) -> anyhow::Result<()> {
     let entry = PonderEntry::create(root, slug, title)
         .with_context(|| format!("failed to create ponder entry '{slug}'"))?;
@@ -207,24 +211,28 @@ fn create(
             .context("failed to write brief")?;
     }
 
-    if let Ok(mut state) = State::load(root) {
-        state.add_ponder(slug);
-        state.save(root).context("failed to save state")?;
-    }
+    update_state(root, |state| state.add_ponder(slug))?;
 
-    if json {
-        print_json(&serde_json::json!({
+    out.emit(
+        &serde_json::json!({
             "slug": entry.slug,
             "title": entry.title,
             "status": entry.status.to_string(),
-        }))?;
-    } else {
-        println!("Created ponder entry '{slug}'.");
-    }
+        }),
+        || {
+            println!("Created ponder entry '{slug}'.");
+            Ok(())
+        },
+    )?;
     Ok(())
 }
 
-fn list(root: &Path, status_filter: Option<&str>, all: bool, json: bool) -> anyhow::Result<()> {
+fn list(
+    root: &Path,
+    status_filter: Option<&str>,
+    all: bool,
+    out: OutputFormat,
+) -> anyhow::Result<()> {
     let mut entries = PonderEntry::list(root).context("failed to list ponder entries")?;
 
     if let Some(status_str) = status_filter {
@@ -239,82 +247,78 @@ fn list(root: &Path, status_filter: Option<&str>, all: bool, json: bool) -> anyh
         entries.retain(|e| e.merged_into.is_none());
     }
 
-    if json {
-        let items: Vec<serde_json::Value> = entries
-            .iter()
-            .map(|e| {
-                serde_json::json!({
-                    "slug": e.slug,
-                    "title": e.title,
-                    "status": e.status.to_string(),
-                    "tags": e.tags,
-                    "sessions": e.sessions,
-                    "created_at": e.created_at,
-                    "merged_into": e.merged_into,
-                })
-            })
-            .collect();
-        print_json(&items)?;
-        return Ok(());
-    }
-
-    if entries.is_empty() {
-        println!("No ponder entries.");
-        return Ok(());
-    }
-
-    let rows: Vec<Vec<String>> = entries
+    let items: Vec<serde_json::Value> = entries
         .iter()
         .map(|e| {
-            let status_display = if let Some(target) = &e.merged_into {
-                format!("parked -> {target}")
-            } else {
-                e.status.to_string()
-            };
-            vec![
-                e.slug.clone(),
-                e.title.clone(),
-                status_display,
-                e.tags.join(", "),
-                e.sessions.to_string(),
-            ]
+            serde_json::json!({
+                "slug": e.slug,
+                "title": e.title,
+                "status": e.status.to_string(),
+                "tags": e.tags,
+                "sessions": e.sessions,
+                "created_at": e.created_at,
+                "merged_into": e.merged_into,
+            })
         })
         .collect();
-    print_table(&["SLUG", "TITLE", "STATUS", "TAGS", "SESSIONS"], rows);
-    Ok(())
+    out.emit(&items, || {
+        if entries.is_empty() {
+            println!("No ponder entries.");
+            return Ok(());
+        }
+
+        let rows: Vec<Vec<String>> = entries
+            .iter()
+            .map(|e| {
+                let status_display = if let Some(target) = &e.merged_into {
+                    format!("parked -> {target}")
+                } else {
+                    e.status.to_string()
+                };
+                vec![
+                    e.slug.clone(),
+                    e.title.clone(),
+                    status_display,
+                    e.tags.join(", "),
+                    e.sessions.to_string(),
+                ]
+            })
+            .collect();
+        print_table(&["SLUG", "TITLE", "STATUS", "TAGS", "SESSIONS"], rows);
+        Ok(())
+    })
 }
 
-fn show(root: &Path, slug: &str, json: bool) -> anyhow::Result<()> {
+fn show(root: &Path, slug: &str, out: OutputFormat) -> anyhow::Result<()> {
     let entry = PonderEntry::load(root, slug)
         .with_context(|| format!("ponder entry '{slug}' not found"))?;
     let team = sdlc_core::ponder::load_team(root, slug).context("failed to load team")?;
     let artifacts =
         sdlc_core::ponder::list_artifacts(root, slug).context("failed to list artifacts")?;
 
-    if json {
-        let artifact_list: Vec<serde_json::Value> = artifacts
-            .iter()
-            .map(|a| {
-                serde_json::json!({
-                    "filename": a.filename,
-                    "size_bytes": a.size_bytes,
-                    "modified_at": a.modified_at,
-                })
+    let artifact_list: Vec<serde_json::Value> = artifacts
+        .iter()
+        .map(|a| {
+            serde_json::json!({
+                "filename": a.filename,
+                "size_bytes": a.size_bytes,
+                "modified_at": a.modified_at,
             })
-            .collect();
-        let team_list: Vec<serde_json::Value> = team
-            .partners
-            .iter()
-            .map(|m| {
-                serde_json::json!({
-                    "name": m.name,
-                    "role": m.role,
-                    "context": m.context,
-                    "agent": m.agent,
-                })
+        })
+        .collect();
+    let team_list: Vec<serde_json::Value> = team
+        .partners
+        .iter()
+        .map(|m| {
+            serde_json::json!({
+                "name": m.name,
+                "role": m.role,
+                "context": m.context,
+                "agent": m.agent,
             })
-            .collect();
-        print_json(&serde_json::json!({
+        })
+        .collect();
+    out.emit(&serde_json::json!({
             "slug": entry.slug,
             "title": entry.title,
             "status": entry.status.to_string(),
@@ -328,35 +332,34 @@ fn show(root: &Path, slug: &str, json: bool) -> anyhow::Result<()> {
             "updated_at": entry.updated_at,
             "team": team_list,
             "artifacts": artifact_list,
-        }))?;
-        return Ok(());
-    }
+        }), || {
 
-    // Redirect banner for merged entries
-    if let Some(target) = &entry.merged_into {
-        println!(
-            "Note: This entry was merged into '{target}'. Use `sdlc ponder show {target}` instead.\n"
-        );
-    }
+        // Redirect banner for merged entries
+        if let Some(target) = &entry.merged_into {
+            println!(
+                "Note: This entry was merged into '{target}'. Use `sdlc ponder show {target}` instead.\n"
+            );
+        }
 
-    println!("Ponder: {} — {}", entry.slug, entry.title);
-    println!("Status:   {}", entry.status);
-    if !entry.tags.is_empty() {
-        println!("Tags:     {}", entry.tags.join(", "));
-    }
-    println!("Sessions: {}", entry.sessions);
-    if !entry.committed_to.is_empty() {
-        println!("Committed to: {}", entry.committed_to.join(", "));
-    }
-    println!("Team:     {} partner(s)", team.partners.len());
-    for m in &team.partners {
-        println!("  {} — {} ({})", m.name, m.role, m.context);
-    }
-    println!("Artifacts: {}", artifacts.len());
-    for a in &artifacts {
-        println!("  {} ({} bytes)", a.filename, a.size_bytes);
-    }
-    Ok(())
+        println!("Ponder: {} — {}", entry.slug, entry.title);
+        println!("Status:   {}", entry.status);
+        if !entry.tags.is_empty() {
+            println!("Tags:     {}", entry.tags.join(", "));
+        }
+        println!("Sessions: {}", entry.sessions);
+        if !entry.committed_to.is_empty() {
+            println!("Committed to: {}", entry.committed_to.join(", "));
+        }
+        println!("Team:     {} partner(s)", team.partners.len());
+        for m in &team.partners {
+            println!("  {} — {} ({})", m.name, m.role, m.context);
+        }
+        println!("Artifacts: {}", artifacts.len());
+        for a in &artifacts {
+            println!("  {} ({} bytes)", a.filename, a.size_bytes);
+        }
+        Ok(())
+    })
 }
 
 fn capture(
@@ -365,7 +368,7 @@ fn capture(
     content: Option<&str>,
     file: Option<&Path>,
     filename_override: Option<&str>,
-    json: bool,
+    out: OutputFormat,
 ) -> anyhow::Result<()> {
     let target_filename;
 
@@ -389,15 +392,17 @@ fn capture(
         }
     }
 
-    if json {
-        print_json(&serde_json::json!({
+    out.emit(
+        &serde_json::json!({
             "slug": slug,
             "filename": target_filename,
             "captured": true,
-        }))?;
-    } else {
-        println!("Captured '{target_filename}' into ponder '{slug}'.");
-    }
+        }),
+        || {
+            println!("Captured '{target_filename}' into ponder '{slug}'.");
+            Ok(())
+        },
+    )?;
     Ok(())
 }
 
@@ -408,7 +413,7 @@ fn team_add(
     role: &str,
     context: &str,
     agent: &str,
-    json: bool,
+    out: OutputFormat,
 ) -> anyhow::Result<()> {
     let member = PonderTeamMember {
         name: name.to_string(),
@@ -421,60 +426,59 @@ fn team_add(
     let team = sdlc_core::ponder::add_team_member(root, slug, member)
         .with_context(|| format!("failed to add team member to '{slug}'"))?;
 
-    if json {
-        print_json(&serde_json::json!({
+    out.emit(
+        &serde_json::json!({
             "slug": slug,
             "name": name,
             "role": role,
             "team_size": team.partners.len(),
-        }))?;
-    } else {
-        println!("Added '{name}' ({role}) to ponder '{slug}' team.");
-    }
+        }),
+        || {
+            println!("Added '{name}' ({role}) to ponder '{slug}' team.");
+            Ok(())
+        },
+    )?;
     Ok(())
 }
 
-fn team_list(root: &Path, slug: &str, json: bool) -> anyhow::Result<()> {
+fn team_list(root: &Path, slug: &str, out: OutputFormat) -> anyhow::Result<()> {
     let team = sdlc_core::ponder::load_team(root, slug)
         .with_context(|| format!("failed to load team for '{slug}'"))?;
 
-    if json {
-        let items: Vec<serde_json::Value> = team
-            .partners
-            .iter()
-            .map(|m| {
-                serde_json::json!({
-                    "name": m.name,
-                    "role": m.role,
-                    "context": m.context,
-                    "agent": m.agent,
-                    "recruited_at": m.recruited_at,
-                })
-            })
-            .collect();
-        print_json(&items)?;
-        return Ok(());
-    }
-
-    if team.partners.is_empty() {
-        println!("No team members for ponder '{slug}'.");
-        return Ok(());
-    }
-
-    let rows: Vec<Vec<String>> = team
+    let items: Vec<serde_json::Value> = team
         .partners
         .iter()
         .map(|m| {
-            vec![
-                m.name.clone(),
-                m.role.clone(),
-                m.context.clone(),
-                m.agent.clone(),
-            ]
+            serde_json::json!({
+                "name": m.name,
+                "role": m.role,
+                "context": m.context,
+                "agent": m.agent,
+                "recruited_at": m.recruited_at,
+            })
         })
         .collect();
-    print_table(&["NAME", "ROLE", "CONTEXT", "AGENT"], rows);
-    Ok(())
+    out.emit(&items, || {
+        if team.partners.is_empty() {
+            println!("No team members for ponder '{slug}'.");
+            return Ok(());
+        }
+
+        let rows: Vec<Vec<String>> = team
+            .partners
+            .iter()
+            .map(|m| {
+                vec![
+                    m.name.clone(),
+                    m.role.clone(),
+                    m.context.clone(),
+                    m.agent.clone(),
+                ]
+            })
+            .collect();
+        print_table(&["NAME", "ROLE", "CONTEXT", "AGENT"], rows);
+        Ok(())
+    })
 }
 
 fn update(
@@ -484,7 +488,7 @@ fn update(
     title: Option<&str>,
     tags: &[String],
     committed_to: &[String],
-    json: bool,
+    out: OutputFormat,
 ) -> anyhow::Result<()> {
     if status.is_none() && title.is_none() && tags.is_empty() && committed_to.is_empty() {
         anyhow::bail!("nothing to update: provide --status, --title, --tag, or --committed-to");
@@ -516,17 +520,14 @@ fn update(
         entry.status,
         sdlc_core::ponder::PonderStatus::Parked | sdlc_core::ponder::PonderStatus::Committed
     ) {
-        if let Ok(mut state) = State::load(root) {
-            state.remove_ponder(slug);
-            state.save(root).context("failed to save state")?;
-        }
+        update_state(root, |state| state.remove_ponder(slug))?;
     }
 
     // Auto-harvest into the knowledge base when a ponder is committed.
     if entry.status == sdlc_core::ponder::PonderStatus::Committed {
         match sdlc_core::knowledge::librarian_harvest_workspace(root, "ponder", slug) {
             Ok(result) => {
-                if !json {
+                if !out.is_structured() {
                     if result.created {
                         println!("Knowledge entry created: {}", result.slug);
                     } else {
@@ -541,48 +542,49 @@ fn update(
         }
     }
 
-    if json {
-        print_json(&serde_json::json!({
+    out.emit(
+        &serde_json::json!({
             "slug": entry.slug,
             "title": entry.title,
             "status": entry.status.to_string(),
             "tags": entry.tags,
-        }))?;
-    } else {
-        println!("Updated ponder entry '{slug}'.");
-    }
+        }),
+        || {
+            println!("Updated ponder entry '{slug}'.");
+            Ok(())
+        },
+    )?;
     Ok(())
 }
 
-fn archive(root: &Path, slug: &str, json: bool) -> anyhow::Result<()> {
+fn archive(root: &Path, slug: &str, out: OutputFormat) -> anyhow::Result<()> {
     let mut entry = PonderEntry::load(root, slug)
         .with_context(|| format!("ponder entry '{slug}' not found"))?;
 
     entry.update_status(sdlc_core::ponder::PonderStatus::Parked);
     entry.save(root).context("failed to save ponder entry")?;
 
-    if let Ok(mut state) = State::load(root) {
-        state.remove_ponder(slug);
-        state.save(root).context("failed to save state")?;
-    }
+    update_state(root, |state| state.remove_ponder(slug))?;
 
-    if json {
-        print_json(&serde_json::json!({
+    out.emit(
+        &serde_json::json!({
             "slug": slug,
             "status": "parked",
-        }))?;
-    } else {
-        println!("Archived ponder entry '{slug}'.");
-    }
+        }),
+        || {
+            println!("Archived ponder entry '{slug}'.");
+            Ok(())
+        },
+    )?;
     Ok(())
 }
 
-fn delete(root: &Path, slug: &str, force: bool, json: bool) -> anyhow::Result<()> {
+fn delete(root: &Path, slug: &str, force: bool, out: OutputFormat) -> anyhow::Result<()> {
     // Verify entry exists
     let _entry = PonderEntry::load(root, slug)
         .with_context(|| format!("ponder entry '{slug}' not found"))?;
 
-    if !force && !json {
+    if !force && !out.is_structured() {
         eprint!("Permanently delete ponder '{slug}' and all its artifacts? [y/N] ");
         let mut answer = String::new();
         std::io::stdin()
@@ -600,50 +602,57 @@ fn delete(root: &Path, slug: &str, force: bool, json: bool) -> anyhow::Result<()
             .with_context(|| format!("failed to remove ponder directory '{}'", dir.display()))?;
     }
 
-    if let Ok(mut state) = State::load(root) {
-        state.remove_ponder(slug);
-        state.save(root).context("failed to save state")?;
-    }
+    update_state(root, |state| state.remove_ponder(slug))?;
 
-    if json {
-        print_json(&serde_json::json!({
+    out.emit(
+        &serde_json::json!({
             "slug": slug,
             "deleted": true,
-        }))?;
-    } else {
-        println!("Deleted ponder entry '{slug}'.");
-    }
+        }),
+        || {
+            println!("Deleted ponder entry '{slug}'.");
+            Ok(())
+        },
+    )?;
     Ok(())
 }
 
-fn merge(root: &Path, source: &str, target: &str, json: bool) -> anyhow::Result<()> {
+/// Update `active_ponders` under the state lock. A project without
+/// `state.yaml` has nothing to track, so that case is not an error.
+fn update_state(root: &Path, f: impl FnOnce(&mut State)) -> anyhow::Result<()> {
+    match State::update(root, f) {
+        Ok(()) | Err(SdlcError::NotInitialized) => Ok(()),
+        Err(e) => Err(e).context("failed to update state"),
+    }
+}
+
+fn merge(root: &Path, source: &str, target: &str, out: OutputFormat) -> anyhow::Result<()> {
     let result = sdlc_core::ponder::merge_entries(root, source, target)
         .with_context(|| format!("failed to merge '{source}' into '{target}'"))?;
 
     // Remove source from active_ponders in state.yaml
-    if let Ok(mut state) = State::load(root) {
-        state.remove_ponder(source);
-        state.save(root).context("failed to save state")?;
-    }
+    update_state(root, |state| state.remove_ponder(source))?;
 
-    if json {
-        print_json(&serde_json::json!({
+    out.emit(
+        &serde_json::json!({
             "source": source,
             "target": target,
             "sessions_copied": result.sessions_copied,
             "artifacts_copied": result.artifacts_copied,
             "team_members_copied": result.team_members_copied,
-        }))?;
-    } else {
-        println!(
-            "Merged '{}' into '{}': {} sessions, {} artifacts, {} team members copied",
-            source,
-            target,
-            result.sessions_copied,
-            result.artifacts_copied,
-            result.team_members_copied
-        );
-    }
+        }),
+        || {
+            println!(
+                "Merged '{}' into '{}': {} sessions, {} artifacts, {} team members copied",
+                source,
+                target,
+                result.sessions_copied,
+                result.artifacts_copied,
+                result.team_members_copied
+            );
+            Ok(())
+        },
+    )?;
     Ok(())
 }
 
@@ -652,7 +661,7 @@ fn session_log(
     slug: &str,
     content: Option<&str>,
     file: Option<&Path>,
-    json: bool,
+    out: OutputFormat,
 ) -> anyhow::Result<()> {
     let body = match (content, file) {
         (Some(c), _) => c.to_string(),
@@ -671,113 +680,111 @@ fn session_log(
     let n = sdlc_core::ponder::log_session(root, slug, &body)
         .with_context(|| format!("failed to log session for '{slug}'"))?;
 
-    if json {
-        print_json(&serde_json::json!({
+    out.emit(
+        &serde_json::json!({
             "slug": slug,
             "session": n,
             "logged": true,
-        }))?;
-    } else {
-        println!("Logged session {n} for ponder '{slug}'.");
-    }
+        }),
+        || {
+            println!("Logged session {n} for ponder '{slug}'.");
+            Ok(())
+        },
+    )?;
     Ok(())
 }
 
-fn session_list(root: &Path, slug: &str, json: bool) -> anyhow::Result<()> {
+fn session_list(root: &Path, slug: &str, out: OutputFormat) -> anyhow::Result<()> {
     let sessions = sdlc_core::ponder::list_sessions(root, slug)
         .with_context(|| format!("failed to list sessions for '{slug}'"))?;
 
-    if json {
-        let items: Vec<serde_json::Value> = sessions
-            .iter()
-            .map(|s| {
-                serde_json::json!({
-                    "session": s.session,
-                    "timestamp": s.timestamp,
-                    "orientation": s.orientation.as_ref().map(|o| serde_json::json!({
-                        "current": o.current,
-                        "next": o.next,
-                        "commit": o.commit,
-                    })),
-                })
-            })
-            .collect();
-        print_json(&items)?;
-        return Ok(());
-    }
-
-    if sessions.is_empty() {
-        println!("No sessions for ponder '{slug}'.");
-        return Ok(());
-    }
-
-    let rows: Vec<Vec<String>> = sessions
+    let items: Vec<serde_json::Value> = sessions
         .iter()
         .map(|s| {
-            vec![
-                s.session.to_string(),
-                s.timestamp.format("%Y-%m-%d %H:%M").to_string(),
-                s.orientation
-                    .as_ref()
-                    .map(|o| o.current.clone())
-                    .unwrap_or_default(),
-            ]
+            serde_json::json!({
+                "session": s.session,
+                "timestamp": s.timestamp,
+                "orientation": s.orientation.as_ref().map(|o| serde_json::json!({
+                    "current": o.current,
+                    "next": o.next,
+                    "commit": o.commit,
+                })),
+            })
         })
         .collect();
-    print_table(&["#", "DATE", "WHERE WE ARE"], rows);
-    Ok(())
+    out.emit(&items, || {
+        if sessions.is_empty() {
+            println!("No sessions for ponder '{slug}'.");
+            return Ok(());
+        }
+
+        let rows: Vec<Vec<String>> = sessions
+            .iter()
+            .map(|s| {
+                vec![
+                    s.session.to_string(),
+                    s.timestamp.format("%Y-%m-%d %H:%M").to_string(),
+                    s.orientation
+                        .as_ref()
+                        .map(|o| o.current.clone())
+                        .unwrap_or_default(),
+                ]
+            })
+            .collect();
+        print_table(&["#", "DATE", "WHERE WE ARE"], rows);
+        Ok(())
+    })
 }
 
-fn session_read(root: &Path, slug: &str, number: u32, json: bool) -> anyhow::Result<()> {
+fn session_read(root: &Path, slug: &str, number: u32, out: OutputFormat) -> anyhow::Result<()> {
     let content = sdlc_core::ponder::read_session(root, slug, number)
         .with_context(|| format!("session {number} not found for ponder '{slug}'"))?;
 
-    if json {
-        print_json(&serde_json::json!({
+    out.emit(
+        &serde_json::json!({
             "slug": slug,
             "session": number,
             "content": content,
-        }))?;
-    } else {
-        print!("{content}");
-    }
+        }),
+        || {
+            print!("{content}");
+            Ok(())
+        },
+    )?;
     Ok(())
 }
 
-fn artifacts(root: &Path, slug: &str, json: bool) -> anyhow::Result<()> {
+fn artifacts(root: &Path, slug: &str, out: OutputFormat) -> anyhow::Result<()> {
     let artifacts = sdlc_core::ponder::list_artifacts(root, slug)
         .with_context(|| format!("failed to list artifacts for '{slug}'"))?;
 
-    if json {
-        let items: Vec<serde_json::Value> = artifacts
-            .iter()
-            .map(|a| {
-                serde_json::json!({
-                    "filename": a.filename,
-                    "size_bytes": a.size_bytes,
-                    "modified_at": a.modified_at,
-                })
-            })
-            .collect();
-        print_json(&items)?;
-        return Ok(());
-    }
-
-    if artifacts.is_empty() {
-        println!("No artifacts for ponder '{slug}'.");
-        return Ok(());
-    }
-
-    let rows: Vec<Vec<String>> = artifacts
+    let items: Vec<serde_json::Value> = artifacts
         .iter()
         .map(|a| {
-            vec![
-                a.filename.clone(),
-                format!("{} bytes", a.size_bytes),
-                a.modified_at.format("%Y-%m-%d %H:%M").to_string(),
-            ]
+            serde_json::json!({
+                "filename": a.filename,
+                "size_bytes": a.size_bytes,
+                "modified_at": a.modified_at,
+            })
         })
         .collect();
-    print_table(&["FILENAME", "SIZE", "MODIFIED"], rows);
-    Ok(())
+    out.emit(&items, || {
+        if artifacts.is_empty() {
+            println!("No artifacts for ponder '{slug}'.");
+            return Ok(());
+        }
+
+        let rows: Vec<Vec<String>> = artifacts
+            .iter()
+            .map(|a| {
+                vec![
+                    a.filename.clone(),
+                    format!("{} bytes", a.size_bytes),
+                    a.modified_at.format("%Y-%m-%d %H:%M").to_string(),
+                ]
+            })
+            .collect();
+        print_table(&["FILENAME", "SIZE", "MODIFIED"], rows);
+        Ok(())
+    })
 }
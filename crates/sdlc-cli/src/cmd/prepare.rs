@@ -1,24 +1,39 @@
-use crate::output::{print_json, print_table};
+use crate::output::{print_table, OutputFormat};
 use anyhow::Context;
 use sdlc_core::prepare::{self, write_wave_plan, GapSeverity, PrepareResult, ProjectPhase};
 use std::path::Path;
 
-pub fn run(root: &Path, milestone: Option<&str>, json: bool) -> anyhow::Result<()> {
+pub fn run(
+    root: &Path,
+    milestone: Option<&str>,
+    dry_run: bool,
+    out: OutputFormat,
+) -> anyhow::Result<()> {
+    let result = plan(root, milestone, dry_run)?;
+
+    let mut json = serde_json::to_value(&result)?;
+    if dry_run {
+        json["dry_run"] = serde_json::Value::Bool(true);
+    }
+    out.emit(&json, || {
+        if dry_run {
+            println!("Dry run — wave_plan.yaml not written.\n");
+        }
+        render_human(&result);
+        Ok(())
+    })
+}
+
+/// Compute the wave plan and, unless `dry_run`, persist it.
+fn plan(root: &Path, milestone: Option<&str>, dry_run: bool) -> anyhow::Result<PrepareResult> {
     let result = prepare::prepare(root, milestone).context("failed to prepare milestone")?;
 
     if let Some(ref slug) = result.milestone {
-        if !result.waves.is_empty() {
+        if !dry_run && !result.waves.is_empty() {
             write_wave_plan(root, slug, &result.waves).context("failed to write wave_plan.yaml")?;
         }
     }
-
-    if json {
-        print_json(&result)?;
-        return Ok(());
-    }
-
-    render_human(&result);
-    Ok(())
+    Ok(result)
 }
 
 fn render_human(result: &PrepareResult) {
@@ -110,3 +125,47 @@ fn render_human(result: &PrepareResult) {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sdlc_core::{config::Config, feature::Feature, milestone::Milestone, state::State};
+    use tempfile::TempDir;
+
+    #[test]
+    fn dry_run_matches_real_waves_without_writing() {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path();
+        Config::new("test").save(root).unwrap();
+        let mut state = State::new("test");
+        let mut milestone = Milestone::create(root, "v1", "V1").unwrap();
+        for slug in ["auth", "billing"] {
+            Feature::create(root, slug, slug).unwrap();
+            state.add_active_feature(slug);
+            milestone.add_feature(slug);
+        }
+        let mut billing = Feature::load(root, "billing").unwrap();
+        billing.dependencies = vec!["auth".to_string()];
+        billing.save(root).unwrap();
+        milestone.save(root).unwrap();
+        state.add_milestone("v1");
+        state.save(root).unwrap();
+        let wave_plan = root.join(".sdlc/milestones/v1/wave_plan.yaml");
+
+        let dry = plan(root, Some("v1"), true).unwrap();
+        assert!(!dry.waves.is_empty());
+        assert!(!wave_plan.exists());
+        assert!(Milestone::load(root, "v1").unwrap().prepared_at.is_none());
+
+        let real = plan(root, Some("v1"), false).unwrap();
+        assert!(wave_plan.exists());
+        assert_eq!(
+            serde_json::to_value(&dry.waves).unwrap(),
+            serde_json::to_value(&real.waves).unwrap()
+        );
+        assert_eq!(
+            serde_json::to_value(&dry.gaps).unwrap(),
+            serde_json::to_value(&real.gaps).unwrap()
+        );
+    }
+}
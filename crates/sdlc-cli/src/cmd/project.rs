@@ -1,10 +1,10 @@
-use crate::output::{print_json, print_table};
+use crate::output::{print_table, OutputFormat};
 use anyhow::Context;
 use chrono::Utc;
 use clap::Subcommand;
-use sdlc_core::{feature::Feature, state::State, types::TaskStatus};
+use sdlc_core::{audit, feature::Feature, state::State, types::TaskStatus};
 use std::collections::HashMap;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 #[derive(Subcommand)]
 pub enum ProjectSubcommand {
@@ -19,21 +19,59 @@ pub enum ProjectSubcommand {
         /// Milestone slug (auto-detects if omitted)
         #[arg(long)]
         milestone: Option<String>,
+        /// Print the wave plan and gaps without writing wave_plan.yaml
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Show the audit log of transitions, task changes, and approvals
+    History {
+        /// Only show entries for this feature
+        #[arg(long)]
+        feature: Option<String>,
+        /// Only show entries since a duration ago (30m, 2h, 3d, 1w) or an
+        /// ISO-8601 timestamp
+        #[arg(long)]
+        since: Option<String>,
+    },
+    /// Write a tar backup of .sdlc/ (plaintext .env files excluded)
+    Backup {
+        /// Output path for the .tar archive
+        #[arg(long)]
+        out: PathBuf,
+    },
+    /// Restore a backup made by `project backup` into a project without a .sdlc/
+    Restore { path: PathBuf },
+    /// Rename a term across feature descriptions, artifact bodies, and
+    /// milestone visions (whole words only)
+    RenameTerm {
+        old: String,
+        new: String,
+        /// Show the diff without writing anything
+        #[arg(long)]
+        dry_run: bool,
     },
 }
 
-pub fn run(root: &Path, subcmd: ProjectSubcommand, json: bool) -> anyhow::Result<()> {
+pub fn run(root: &Path, subcmd: ProjectSubcommand, out: OutputFormat) -> anyhow::Result<()> {
     match subcmd {
-        ProjectSubcommand::Status => status(root, json),
-        ProjectSubcommand::Stats => stats(root, json),
-        ProjectSubcommand::Blockers => blockers(root, json),
-        ProjectSubcommand::Prepare { milestone } => {
-            super::prepare::run(root, milestone.as_deref(), json)
+        ProjectSubcommand::Status => status(root, out),
+        ProjectSubcommand::Stats => stats(root, out),
+        ProjectSubcommand::Blockers => blockers(root, out),
+        ProjectSubcommand::Prepare { milestone, dry_run } => {
+            super::prepare::run(root, milestone.as_deref(), dry_run, out)
+        }
+        ProjectSubcommand::History { feature, since } => {
+            history(root, feature.as_deref(), since.as_deref(), out)
+        }
+        ProjectSubcommand::Backup { out: path } => backup(root, &path, out),
+        ProjectSubcommand::Restore { path } => restore(root, &path, out),
+        ProjectSubcommand::RenameTerm { old, new, dry_run } => {
+            rename_term(root, &old, &new, dry_run, out)
         }
     }
 }
 
-fn status(root: &Path, json: bool) -> anyhow::Result<()> {
+fn status(root: &Path, out: OutputFormat) -> anyhow::Result<()> {
     let features = Feature::list(root).context("failed to list features")?;
     let state = State::load(root).context("failed to load state")?;
 
@@ -66,9 +104,8 @@ fn status(root: &Path, json: bool) -> anyhow::Result<()> {
 
     let blocked_count = state.blocked.len();
 
-    if json {
-        let phases_json = serde_json::to_value(&phase_counts)?;
-        print_json(&serde_json::json!({
+    let phases_json = serde_json::to_value(&phase_counts)?;
+    out.emit(&serde_json::json!({
             "project": state.project,
             "active_feature_count": active_count,
             "phases": phases_json,
@@ -80,42 +117,41 @@ fn status(root: &Path, json: bool) -> anyhow::Result<()> {
                 "pending": pending,
             },
             "blocked_count": blocked_count,
-        }))?;
-        return Ok(());
-    }
+        }), || {
 
-    println!(
-        "Project: {}   Active features: {}",
-        state.project, active_count
-    );
-    println!();
-
-    if !phase_counts.is_empty() {
-        println!("PHASE DISTRIBUTION");
-        for phase in sdlc_core::types::Phase::all() {
-            if let Some(count) = phase_counts.get(phase.as_str()) {
-                println!("  {:<18} {}", phase, count);
+        println!(
+            "Project: {}   Active features: {}",
+            state.project, active_count
+        );
+        println!();
+
+        if !phase_counts.is_empty() {
+            println!("PHASE DISTRIBUTION");
+            for phase in sdlc_core::types::Phase::all() {
+                if let Some(count) = phase_counts.get(phase.as_str()) {
+                    println!("  {:<18} {}", phase, count);
+                }
             }
+            println!();
         }
-        println!();
-    }
 
-    println!(
-        "TASK SUMMARY\n  Total: {} | Completed: {} | In Progress: {} | Blocked: {} | Pending: {}",
-        total, completed, in_progress, task_blocked, pending
-    );
-    println!();
+        println!(
+            "TASK SUMMARY\n  Total: {} | Completed: {} | In Progress: {} | Blocked: {} | Pending: {}",
+            total, completed, in_progress, task_blocked, pending
+        );
+        println!();
 
-    if blocked_count == 0 {
-        println!("BLOCKERS  No blocked features");
-    } else {
-        println!("BLOCKERS  {} feature(s) blocked", blocked_count);
-    }
+        if blocked_count == 0 {
+            println!("BLOCKERS  No blocked features");
+        } else {
+            println!("BLOCKERS  {} feature(s) blocked", blocked_count);
+        }
 
-    Ok(())
+        Ok(())
+    })
 }
 
-fn stats(root: &Path, json: bool) -> anyhow::Result<()> {
+fn stats(root: &Path, out: OutputFormat) -> anyhow::Result<()> {
     let features = Feature::list(root).context("failed to list features")?;
     let state = State::load(root).context("failed to load state")?;
 
@@ -155,34 +191,34 @@ fn stats(root: &Path, json: bool) -> anyhow::Result<()> {
         .map(|(phase, (total, count))| (phase.clone(), *total / *count as f64))
         .collect();
 
-    if json {
-        let avg_dwell_json = serde_json::to_value(&avg_dwell_days)?;
-        print_json(&serde_json::json!({
+    let avg_dwell_json = serde_json::to_value(&avg_dwell_days)?;
+    out.emit(
+        &serde_json::json!({
             "actions_7d": actions_7d,
             "transitions_7d": transitions_7d,
             "avg_dwell_days": avg_dwell_json,
-        }))?;
-        return Ok(());
-    }
-
-    println!("Actions (last 7 days): {}", actions_7d);
-    println!("Phase transitions:     {}", transitions_7d);
+        }),
+        || {
+            println!("Actions (last 7 days): {}", actions_7d);
+            println!("Phase transitions:     {}", transitions_7d);
 
-    if !avg_dwell_days.is_empty() {
-        println!();
-        println!("AVERAGE PHASE DWELL");
-        // Print in natural phase order
-        for phase in sdlc_core::types::Phase::all() {
-            if let Some(days) = avg_dwell_days.get(phase.as_str()) {
-                println!("  {:<18} {:.1} days", phase, days);
+            if !avg_dwell_days.is_empty() {
+                println!();
+                println!("AVERAGE PHASE DWELL");
+                // Print in natural phase order
+                for phase in sdlc_core::types::Phase::all() {
+                    if let Some(days) = avg_dwell_days.get(phase.as_str()) {
+                        println!("  {:<18} {:.1} days", phase, days);
+                    }
+                }
             }
-        }
-    }
 
-    Ok(())
+            Ok(())
+        },
+    )
 }
 
-fn blockers(root: &Path, json: bool) -> anyhow::Result<()> {
+fn blockers(root: &Path, out: OutputFormat) -> anyhow::Result<()> {
     let state = State::load(root).context("failed to load state")?;
     let features = Feature::list(root).context("failed to list features")?;
     let now = Utc::now();
@@ -219,38 +255,182 @@ fn blockers(root: &Path, json: bool) -> anyhow::Result<()> {
         }
     }
 
-    if json {
-        let items: Vec<serde_json::Value> = blocker_rows
+    let items: Vec<serde_json::Value> = blocker_rows
+        .iter()
+        .map(|(slug, phase, days, reason)| {
+            serde_json::json!({
+                "slug": slug,
+                "phase": phase,
+                "days_blocked": days,
+                "reason": reason,
+            })
+        })
+        .collect();
+    out.emit(&items, || {
+        if blocker_rows.is_empty() {
+            println!("No blocked features.");
+            return Ok(());
+        }
+
+        let rows: Vec<Vec<String>> = blocker_rows
             .iter()
             .map(|(slug, phase, days, reason)| {
-                serde_json::json!({
-                    "slug": slug,
-                    "phase": phase,
-                    "days_blocked": days,
-                    "reason": reason,
-                })
+                vec![
+                    slug.clone(),
+                    phase.clone(),
+                    format!("{} days", days),
+                    reason.clone(),
+                ]
             })
             .collect();
-        print_json(&items)?;
-        return Ok(());
+        print_table(&["FEATURE", "PHASE", "BLOCKED FOR", "REASON"], rows);
+        Ok(())
+    })
+}
+
+fn history(
+    root: &Path,
+    feature: Option<&str>,
+    since: Option<&str>,
+    out: OutputFormat,
+) -> anyhow::Result<()> {
+    let since = since
+        .map(|s| sdlc_core::since::parse_since(s, Utc::now()))
+        .transpose()?;
+    let entries = audit::read_since(root, feature, since).context("failed to read audit log")?;
+
+    out.emit(&entries, || {
+        if entries.is_empty() {
+            println!("No audit entries.");
+            return Ok(());
+        }
+
+        let rows: Vec<Vec<String>> = entries
+            .iter()
+            .map(|e| {
+                vec![
+                    e.timestamp.format("%Y-%m-%d %H:%M:%S").to_string(),
+                    e.actor.clone(),
+                    e.feature.clone(),
+                    e.action.to_string(),
+                    e.detail.clone().unwrap_or_default(),
+                ]
+            })
+            .collect();
+        print_table(&["TIME", "ACTOR", "FEATURE", "ACTION", "DETAIL"], rows);
+        Ok(())
+    })
+}
+
+fn backup(root: &Path, path: &Path, out: OutputFormat) -> anyhow::Result<()> {
+    let file = std::fs::File::create(path)
+        .with_context(|| format!("failed to create {}", path.display()))?;
+    let manifest = sdlc_core::backup::backup(
+        root,
+        std::io::BufWriter::new(file),
+        super::init::SDLC_BINARY_VERSION,
+    )
+    .inspect_err(|_| {
+        // Don't leave a truncated archive that looks like a backup.
+        let _ = std::fs::remove_file(path);
+    })
+    .with_context(|| format!("failed to write backup to {}", path.display()))?;
+
+    out.emit(
+        &serde_json::json!({
+            "path": path,
+            "sdlc_version": manifest.sdlc_version,
+            "files": manifest.files.len(),
+        }),
+        || {
+            println!(
+                "Backed up {} files to {}",
+                manifest.files.len(),
+                path.display()
+            );
+            Ok(())
+        },
+    )?;
+    Ok(())
+}
+
+fn restore(root: &Path, path: &Path, out: OutputFormat) -> anyhow::Result<()> {
+    let file =
+        std::fs::File::open(path).with_context(|| format!("failed to open {}", path.display()))?;
+    let manifest = sdlc_core::backup::restore(
+        root,
+        std::io::BufReader::new(file),
+        super::init::SDLC_BINARY_VERSION,
+    )
+    .with_context(|| format!("failed to restore {}", path.display()))?;
+
+    out.emit(
+        &serde_json::json!({
+            "path": path,
+            "sdlc_version": manifest.sdlc_version,
+            "created_at": manifest.created_at,
+            "files": manifest.files.len(),
+        }),
+        || {
+            println!(
+                "Restored {} files from {} (sdlc {}, {})",
+                manifest.files.len(),
+                path.display(),
+                manifest.sdlc_version,
+                manifest.created_at.format("%Y-%m-%d %H:%M:%S")
+            );
+            Ok(())
+        },
+    )?;
+    Ok(())
+}
+
+fn rename_term(
+    root: &Path,
+    old: &str,
+    new: &str,
+    dry_run: bool,
+    out: OutputFormat,
+) -> anyhow::Result<()> {
+    if old.trim().is_empty() {
+        anyhow::bail!("the term to rename must not be empty");
     }
+    let edits = sdlc_core::rename_term::rename_term(root, old, new, true)
+        .context("failed to scan project text")?;
 
-    if blocker_rows.is_empty() {
-        println!("No blocked features.");
-        return Ok(());
+    if !out.is_structured() {
+        // The diff comes first, before anything is written.
+        for edit in &edits {
+            println!("--- {}", edit.location);
+            for (before, after) in &edit.lines {
+                println!("- {before}");
+                println!("+ {after}");
+            }
+            println!();
+        }
+    }
+    if !dry_run && !edits.is_empty() {
+        sdlc_core::rename_term::rename_term(root, old, new, false)
+            .context("failed to rename term")?;
     }
 
-    let rows: Vec<Vec<String>> = blocker_rows
-        .iter()
-        .map(|(slug, phase, days, reason)| {
-            vec![
-                slug.clone(),
-                phase.clone(),
-                format!("{} days", days),
-                reason.clone(),
-            ]
-        })
-        .collect();
-    print_table(&["FEATURE", "PHASE", "BLOCKED FOR", "REASON"], rows);
+    let replacements: usize = edits.iter().map(|e| e.replacements).sum();
+    out.emit(
+        &serde_json::json!({
+            "old": old,
+            "new": new,
+            "dry_run": dry_run,
+            "replacements": replacements,
+            "edits": edits,
+        }),
+        || {
+            let verb = if dry_run { "Would replace" } else { "Replaced" };
+            println!(
+                "{verb} {replacements} occurrence(s) of '{old}' in {} place(s)",
+                edits.len()
+            );
+            Ok(())
+        },
+    )?;
     Ok(())
 }
@@ -1,15 +1,8 @@
-use crate::output::print_json;
+use crate::output::{print_table, OutputFormat};
 use anyhow::Context;
+use chrono::Utc;
 use clap::Subcommand;
-use sdlc_core::{
-    classifier::{Classifier, EvalContext},
-    config::Config,
-    feature::Feature,
-    rules::default_rules,
-    search::{EntityIndex, EntitySources, TaskIndex},
-    state::State,
-    types::ActionType,
-};
+use sdlc_core::{feature::Feature, query, search::TaskIndex};
 use std::path::Path;
 
 #[derive(Subcommand)]
@@ -21,9 +14,19 @@ pub enum QuerySubcommand {
         /// Filter by phase (e.g. draft, specified, planned)
         #[arg(long)]
         phase: Option<String>,
+        /// Also list features held back by unreleased dependencies, with the
+        /// dependencies blocking each
+        #[arg(long)]
+        include_blocked_by: bool,
     },
     /// Show features with artifacts awaiting approval
     NeedsApproval,
+    /// Show active features with no activity for longer than a threshold
+    Stale {
+        /// Idle threshold: a duration (12h, 7d, 2w) or an ISO-8601 timestamp
+        #[arg(long, default_value = "7d")]
+        older_than: String,
+    },
     /// Full-text search across feature titles, descriptions, and comments
     ///
     /// Supports AND/OR/NOT, field scoping (phase:ready, slug:auth),
@@ -50,263 +53,186 @@ pub enum QuerySubcommand {
     },
 }
 
-pub fn run(root: &Path, subcmd: QuerySubcommand, json: bool) -> anyhow::Result<()> {
+pub fn run(root: &Path, subcmd: QuerySubcommand, out: OutputFormat) -> anyhow::Result<()> {
     match subcmd {
-        QuerySubcommand::Blocked => blocked(root, json),
-        QuerySubcommand::Ready { phase } => ready(root, phase, json),
-        QuerySubcommand::NeedsApproval => needs_approval(root, json),
-        QuerySubcommand::Search { query, limit } => search(root, &query, limit, json),
-        QuerySubcommand::SearchTasks { query, limit } => search_tasks(root, &query, limit, json),
+        QuerySubcommand::Blocked => blocked(root, out),
+        QuerySubcommand::Ready {
+            phase,
+            include_blocked_by,
+        } => ready(root, phase, include_blocked_by, out),
+        QuerySubcommand::NeedsApproval => needs_approval(root, out),
+        QuerySubcommand::Stale { older_than } => stale(root, &older_than, out),
+        QuerySubcommand::Search { query, limit } => search(root, &query, limit, out),
+        QuerySubcommand::SearchTasks { query, limit } => search_tasks(root, &query, limit, out),
     }
 }
 
-fn blocked(root: &Path, json: bool) -> anyhow::Result<()> {
-    let features = Feature::list(root).context("failed to list features")?;
-    let blocked: Vec<_> = features.iter().filter(|f| f.is_blocked()).collect();
+fn blocked(root: &Path, out: OutputFormat) -> anyhow::Result<()> {
+    let blocked = query::blocked(root).context("failed to list features")?;
 
-    if json {
-        let out: Vec<_> = blocked
-            .iter()
-            .map(|f| serde_json::json!({ "slug": f.slug, "blockers": f.blockers }))
-            .collect();
-        return print_json(&out);
-    }
+    out.emit(&blocked, || {
+        if blocked.is_empty() {
+            println!("No blocked features.");
+        } else {
+            for f in &blocked {
+                println!("{}: {}", f.slug, f.blockers.join(", "));
+            }
+        }
+        Ok(())
+    })
+}
 
-    if blocked.is_empty() {
-        println!("No blocked features.");
+fn ready(
+    root: &Path,
+    phase: Option<String>,
+    include_blocked_by: bool,
+    out: OutputFormat,
+) -> anyhow::Result<()> {
+    let report =
+        query::ready_report(root, phase.as_deref()).context("failed to classify features")?;
+
+    let value = if include_blocked_by {
+        serde_json::to_value(&report)?
     } else {
-        for f in blocked {
-            println!("{}: {}", f.slug, f.blockers.join(", "));
+        serde_json::to_value(&report.ready)?
+    };
+    out.emit(&value, || {
+        if report.ready.is_empty() {
+            println!("No features ready to work on.");
+        } else {
+            println!("Ready to work on:");
+            for f in &report.ready {
+                println!("  {} [{}]", f.slug, f.phase);
+            }
         }
-    }
-    Ok(())
+        if include_blocked_by && !report.blocked_by_dependency.is_empty() {
+            println!("Waiting on dependencies:");
+            for f in &report.blocked_by_dependency {
+                let deps: Vec<String> = f
+                    .blocked_by
+                    .iter()
+                    .map(|d| match d.phase {
+                        Some(phase) => format!("{} [{phase}]", d.slug),
+                        None => format!("{} [missing]", d.slug),
+                    })
+                    .collect();
+                println!("  {} [{}] blocked by {}", f.slug, f.phase, deps.join(", "));
+            }
+        }
+        Ok(())
+    })
 }
 
-fn ready(root: &Path, phase: Option<String>, json: bool) -> anyhow::Result<()> {
-    let config = Config::load(root).context("failed to load config")?;
-    let state = State::load(root).context("failed to load state")?;
-    let features = Feature::list(root).context("failed to list features")?;
-    let classifier = Classifier::new(default_rules());
-
-    let ready: Vec<_> = features
-        .iter()
-        .filter(|f| !f.archived && !f.is_blocked())
-        .filter(|f| phase.as_deref().is_none_or(|p| f.phase.to_string() == p))
-        .filter_map(|f| {
-            let ctx = EvalContext {
-                feature: f,
-                state: &state,
-                config: &config,
-                root,
-            };
-            let c = classifier.classify(&ctx);
-            if matches!(
-                c.action,
-                ActionType::WaitForApproval | ActionType::Done | ActionType::UnblockDependency
-            ) {
-                None
-            } else {
-                Some((f, c))
+fn needs_approval(root: &Path, out: OutputFormat) -> anyhow::Result<()> {
+    let pending = query::needs_approval(root).context("failed to classify features")?;
+
+    out.emit(&pending, || {
+        if pending.is_empty() {
+            println!("No features need approval.");
+        } else {
+            println!("Awaiting approval:");
+            for f in &pending {
+                println!("  {} — {}", f.slug, f.message);
+                println!("    Run: {}", f.next_command);
             }
-        })
-        .collect();
-
-    if json {
-        let out: Vec<_> = ready
-            .iter()
-            .map(|(f, c)| {
-                serde_json::json!({
-                    "slug": f.slug,
-                    "phase": f.phase.to_string(),
-                    "action": c.action.as_str(),
-                    "message": c.message,
-                    "next_command": c.next_command,
-                })
-            })
-            .collect();
-        return print_json(&out);
-    }
-
-    if ready.is_empty() {
-        println!("No features ready to work on.");
-    } else {
-        println!("Ready to work on:");
-        for (f, _c) in ready {
-            println!("  {} [{}]", f.slug, f.phase);
         }
-    }
-    Ok(())
+        Ok(())
+    })
 }
 
-fn needs_approval(root: &Path, json: bool) -> anyhow::Result<()> {
-    let config = Config::load(root).context("failed to load config")?;
-    let state = State::load(root).context("failed to load state")?;
-    let features = Feature::list(root).context("failed to list features")?;
-    let classifier = Classifier::new(default_rules());
+fn stale(root: &Path, older_than: &str, out: OutputFormat) -> anyhow::Result<()> {
+    let now = Utc::now();
+    let cutoff = sdlc_core::since::parse_since(older_than, now)?;
+    let stale = query::stale_at(root, now - cutoff, now).context("failed to list features")?;
 
-    let pending: Vec<_> = features
-        .iter()
-        .filter(|f| !f.archived)
-        .filter_map(|f| {
-            let ctx = EvalContext {
-                feature: f,
-                state: &state,
-                config: &config,
-                root,
-            };
-            let c = classifier.classify(&ctx);
-            if is_approval_action(c.action) {
-                Some((f, c))
-            } else {
-                None
-            }
-        })
-        .collect();
-
-    if json {
-        let out: Vec<_> = pending
+    out.emit(&stale, || {
+        if stale.is_empty() {
+            println!("No stale features.");
+            return Ok(());
+        }
+        let rows: Vec<Vec<String>> = stale
             .iter()
-            .map(|(f, c)| {
-                serde_json::json!({
-                    "slug": f.slug,
-                    "action": c.action.as_str(),
-                    "message": c.message,
-                    "next_command": c.next_command,
-                })
+            .map(|f| {
+                vec![
+                    f.slug.clone(),
+                    f.phase.to_string(),
+                    format!("{}d", f.idle_seconds / 86_400),
+                    f.last_activity.format("%Y-%m-%d %H:%M").to_string(),
+                ]
             })
             .collect();
-        return print_json(&out);
-    }
-
-    if pending.is_empty() {
-        println!("No features need approval.");
-    } else {
-        println!("Awaiting approval:");
-        for (f, c) in pending {
-            println!("  {} — {}", f.slug, c.message);
-            println!("    Run: {}", c.next_command);
-        }
-    }
-    Ok(())
-}
-
-fn is_approval_action(action: ActionType) -> bool {
-    matches!(
-        action,
-        ActionType::ApproveSpec
-            | ActionType::ApproveDesign
-            | ActionType::ApproveTasks
-            | ActionType::ApproveQaPlan
-            | ActionType::ApproveReview
-            | ActionType::ApproveAudit
-            | ActionType::ApproveMerge
-            | ActionType::WaitForApproval
-    )
-}
-
-fn search(root: &Path, query_str: &str, limit: usize, json: bool) -> anyhow::Result<()> {
-    let features = Feature::list(root).context("failed to list features")?;
-
-    let ponder_entries = sdlc_core::ponder::PonderEntry::list(root).unwrap_or_default();
-    let ponder_artifacts: Vec<_> = ponder_entries
-        .iter()
-        .map(|e| {
-            let arts = sdlc_core::ponder::list_artifacts(root, &e.slug).unwrap_or_default();
-            (e.clone(), arts)
-        })
-        .collect();
-
-    let milestones = sdlc_core::milestone::Milestone::list(root).unwrap_or_default();
-    let milestone_statuses: Vec<_> = milestones
-        .into_iter()
-        .map(|m| {
-            let status = m.compute_status(&features);
-            (m, status)
-        })
-        .collect();
-
-    let investigations = sdlc_core::investigation::list(root).unwrap_or_default();
-    let inv_artifacts: Vec<_> = investigations
-        .into_iter()
-        .map(|e| {
-            let arts = sdlc_core::investigation::list_artifacts(root, &e.slug).unwrap_or_default();
-            (e, arts)
-        })
-        .collect();
-
-    let index = EntityIndex::build(EntitySources {
-        features: &features,
-        ponders: &ponder_artifacts,
-        milestones: &milestone_statuses,
-        investigations: &inv_artifacts,
-        root,
+        print_table(&["SLUG", "PHASE", "IDLE", "LAST ACTIVITY"], rows);
+        Ok(())
     })
-    .context("failed to build search index")?;
-    let results = index.search(query_str, limit).context("search failed")?;
+}
 
-    if json {
-        return print_json(&results);
-    }
+fn search(root: &Path, query_str: &str, limit: usize, out: OutputFormat) -> anyhow::Result<()> {
+    let results = query::search(root, query_str, limit).context("search failed")?;
 
-    if results.is_empty() {
-        println!("No results.");
-        return Ok(());
-    }
+    out.emit(&results, || {
+        if results.is_empty() {
+            println!("No results.");
+            return Ok(());
+        }
 
-    println!(
-        "{} result{} for {:?}:",
-        results.len(),
-        if results.len() == 1 { "" } else { "s" },
-        query_str
-    );
-    for r in &results {
         println!(
-            "  [{:.2}] {:<12} {:<30} {:<40} {}",
-            r.score, r.kind, r.slug, r.title, r.status
+            "{} result{} for {:?}:",
+            results.len(),
+            if results.len() == 1 { "" } else { "s" },
+            query_str
         );
-    }
-    Ok(())
+        for r in &results {
+            println!(
+                "  [{:.2}] {:<12} {:<30} {:<40} {}",
+                r.score, r.kind, r.slug, r.title, r.status
+            );
+        }
+        Ok(())
+    })
 }
 
-fn search_tasks(root: &Path, query_str: &str, limit: usize, json: bool) -> anyhow::Result<()> {
+fn search_tasks(
+    root: &Path,
+    query_str: &str,
+    limit: usize,
+    out: OutputFormat,
+) -> anyhow::Result<()> {
     let features = Feature::list(root).context("failed to list features")?;
     let index = TaskIndex::build(&features).context("failed to build task search index")?;
     let results = index
         .search(query_str, limit)
         .context("task search failed")?;
 
-    if json {
-        let out: Vec<_> = results
-            .iter()
-            .map(|r| {
-                serde_json::json!({
-                    "feature_slug": r.feature_slug,
-                    "task_id":      r.task_id,
-                    "title":        r.title,
-                    "status":       r.status,
-                    "score":        r.score,
-                })
+    let rows: Vec<_> = results
+        .iter()
+        .map(|r| {
+            serde_json::json!({
+                "feature_slug": r.feature_slug,
+                "task_id":      r.task_id,
+                "title":        r.title,
+                "status":       r.status,
+                "score":        r.score,
             })
-            .collect();
-        return print_json(&out);
-    }
-
-    if results.is_empty() {
-        println!("No results.");
-        return Ok(());
-    }
+        })
+        .collect();
+    out.emit(&rows, || {
+        if results.is_empty() {
+            println!("No results.");
+            return Ok(());
+        }
 
-    println!(
-        "{} result{} for {:?}:",
-        results.len(),
-        if results.len() == 1 { "" } else { "s" },
-        query_str
-    );
-    for r in &results {
         println!(
-            "  [{:.2}] {:<30} {:<20} {:<40} {}",
-            r.score, r.feature_slug, r.task_id, r.title, r.status
+            "{} result{} for {:?}:",
+            results.len(),
+            if results.len() == 1 { "" } else { "s" },
+            query_str
         );
-    }
-    Ok(())
+        for r in &results {
+            println!(
+                "  [{:.2}] {:<30} {:<20} {:<40} {}",
+                r.score, r.feature_slug, r.task_id, r.title, r.status
+            );
+        }
+        Ok(())
+    })
 }
@@ -1,4 +1,4 @@
-use crate::output::print_json;
+use crate::output::OutputFormat;
 use anyhow::Context;
 use clap::Subcommand;
 use sdlc_core::feature::Feature;
@@ -43,16 +43,16 @@ pub enum ScoreSubcommand {
 // Entry point
 // ---------------------------------------------------------------------------
 
-pub fn run(root: &Path, subcmd: ScoreSubcommand, json: bool) -> anyhow::Result<()> {
+pub fn run(root: &Path, subcmd: ScoreSubcommand, out: OutputFormat) -> anyhow::Result<()> {
     match subcmd {
         ScoreSubcommand::Set {
             slug,
             lens,
             value,
             evaluator,
-        } => set_score(root, &slug, &lens, value, &evaluator, json),
-        ScoreSubcommand::Show { slug } => show_scores(root, &slug, json),
-        ScoreSubcommand::History { slug } => show_history(root, &slug, json),
+        } => set_score(root, &slug, &lens, value, &evaluator, out),
+        ScoreSubcommand::Show { slug } => show_scores(root, &slug, out),
+        ScoreSubcommand::History { slug } => show_history(root, &slug, out),
     }
 }
 
@@ -66,7 +66,7 @@ fn set_score(
     lens: &str,
     value: u32,
     evaluator: &str,
-    _json: bool,
+    _out: OutputFormat,
 ) -> anyhow::Result<()> {
     if value > 100 {
         anyhow::bail!("score value must be 0-100, got {value}");
@@ -83,6 +83,10 @@ fn set_score(
     feature.add_score(score);
     feature.save(root).context("failed to save feature")?;
     println!("Score set: {slug} [{lens}] = {value} (by {evaluator})");
+    // A new score can make an `if_score_above` auto-approve policy apply.
+    if let Some(phase) = sdlc_core::classifier::try_auto_transition(root, slug) {
+        println!("Transitioned to: {phase}");
+    }
     Ok(())
 }
 
@@ -90,61 +94,55 @@ fn set_score(
 // show
 // ---------------------------------------------------------------------------
 
-fn show_scores(root: &Path, slug: &str, json: bool) -> anyhow::Result<()> {
+fn show_scores(root: &Path, slug: &str, out: OutputFormat) -> anyhow::Result<()> {
     let feature = Feature::load(root, slug).context("failed to load feature")?;
 
-    if json {
-        let value = serde_json::json!({
-            "slug": slug,
-            "scores": feature.scores,
-        });
-        print_json(&value)?;
-        return Ok(());
-    }
-
-    if feature.scores.is_empty() {
-        println!("No scores for feature '{slug}'.");
-        return Ok(());
-    }
-
-    println!("Scores for '{slug}':");
-    for s in &feature.scores {
-        let deduction_count = s.deductions.len();
-        println!(
-            "  {:<24} {:>3}/100  (evaluator: {}, deductions: {})",
-            s.lens, s.score, s.evaluator, deduction_count,
-        );
-    }
-    Ok(())
+    let value = serde_json::json!({
+        "slug": slug,
+        "scores": feature.scores,
+    });
+    out.emit(&value, || {
+        if feature.scores.is_empty() {
+            println!("No scores for feature '{slug}'.");
+            return Ok(());
+        }
+
+        println!("Scores for '{slug}':");
+        for s in &feature.scores {
+            let deduction_count = s.deductions.len();
+            println!(
+                "  {:<24} {:>3}/100  (evaluator: {}, deductions: {})",
+                s.lens, s.score, s.evaluator, deduction_count,
+            );
+        }
+        Ok(())
+    })
 }
 
 // ---------------------------------------------------------------------------
 // history
 // ---------------------------------------------------------------------------
 
-fn show_history(root: &Path, slug: &str, json: bool) -> anyhow::Result<()> {
+fn show_history(root: &Path, slug: &str, out: OutputFormat) -> anyhow::Result<()> {
     let feature = Feature::load(root, slug).context("failed to load feature")?;
 
-    if json {
-        let value = serde_json::json!({
-            "slug": slug,
-            "scores": feature.scores,
-        });
-        print_json(&value)?;
-        return Ok(());
-    }
-
-    if feature.scores.is_empty() {
-        println!("No score history for feature '{slug}'.");
-        return Ok(());
-    }
-
-    println!("Score history for '{slug}':");
-    for s in &feature.scores {
-        println!(
-            "  {} | {:<24} {:>3}/100 | {}",
-            s.timestamp, s.lens, s.score, s.evaluator,
-        );
-    }
-    Ok(())
+    let value = serde_json::json!({
+        "slug": slug,
+        "scores": feature.scores,
+    });
+    out.emit(&value, || {
+        if feature.scores.is_empty() {
+            println!("No score history for feature '{slug}'.");
+            return Ok(());
+        }
+
+        println!("Score history for '{slug}':");
+        for s in &feature.scores {
+            println!(
+                "  {} | {:<24} {:>3}/100 | {}",
+                s.timestamp, s.lens, s.score, s.evaluator,
+            );
+        }
+        Ok(())
+    })
 }
@@ -1,6 +1,6 @@
-use crate::output::print_table;
+use crate::output::{print_table, OutputFormat};
 use clap::Subcommand;
-use sdlc_core::secrets;
+use sdlc_core::{secrets, secrets_backend};
 use std::path::{Path, PathBuf};
 
 // ---------------------------------------------------------------------------
@@ -120,7 +120,7 @@ fn resolve_identity(explicit: Option<PathBuf>) -> anyhow::Result<PathBuf> {
 // Entry point
 // ---------------------------------------------------------------------------
 
-pub fn run(root: &Path, subcommand: SecretsSubcommand, _json: bool) -> anyhow::Result<()> {
+pub fn run(root: &Path, subcommand: SecretsSubcommand, _out: OutputFormat) -> anyhow::Result<()> {
     match subcommand {
         SecretsSubcommand::List => run_list(root),
         SecretsSubcommand::Keys { subcommand } => run_keys(root, subcommand),
@@ -276,11 +276,11 @@ fn run_env(root: &Path, subcommand: SecretsEnvSubcommand) -> anyhow::Result<()>
         }
 
         SecretsEnvSubcommand::Names { env } => {
-            let meta = secrets::load_env_meta(root, &env)?;
-            if meta.key_names.is_empty() {
+            let names = secrets_backend::open(root, None)?.list_names(&env)?;
+            if names.is_empty() {
                 println!("(no key names recorded for '{env}')");
             } else {
-                for name in &meta.key_names {
+                for name in &names {
                     println!("{name}");
                 }
             }
@@ -288,8 +288,8 @@ fn run_env(root: &Path, subcommand: SecretsEnvSubcommand) -> anyhow::Result<()>
         }
 
         SecretsEnvSubcommand::Export { env, identity } => {
-            let id = resolve_identity(identity)?;
-            let content = secrets::export_env(root, &env, &id)?;
+            let backend = secrets_backend::open(root, identity)?;
+            let content = backend.get(&env)?;
             // Print without trailing newline modification — let the shell eval handle it.
             print!("{content}");
             Ok(())
@@ -303,14 +303,10 @@ fn run_env(root: &Path, subcommand: SecretsEnvSubcommand) -> anyhow::Result<()>
             if pairs.is_empty() {
                 anyhow::bail!("no KEY=VALUE pairs provided");
             }
-            // Only need identity if the env file already exists (decrypt + merge).
-            let env_exists = sdlc_core::paths::secrets_env_path(root, &env).exists();
-            let id_path = if env_exists {
-                Some(resolve_identity(identity)?)
-            } else {
-                None
-            };
-            secrets::set_env_pairs(root, &env, &pairs, id_path.as_deref())?;
+            // The age backend only needs an identity if the env file already
+            // exists (decrypt + merge).
+            let backend = secrets_backend::open(root, identity)?;
+            backend.set(&env, &pairs)?;
             println!("updated {} key(s) in '{env}'", pairs.len());
             Ok(())
         }
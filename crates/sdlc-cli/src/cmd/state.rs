@@ -1,4 +1,4 @@
-use crate::output::print_json;
+use crate::output::OutputFormat;
 use anyhow::Context;
 use sdlc_core::{
     feature::Feature,
@@ -21,8 +21,119 @@ pub fn rebuild(root: &Path) -> anyhow::Result<()> {
     Ok(())
 }
 
-pub fn run(root: &Path, json: bool) -> anyhow::Result<()> {
+#[derive(serde::Serialize)]
+struct MilestoneFeature<'a> {
+    slug: &'a str,
+    phase: String,
+    status: &'static str,
+    title: &'a str,
+}
+
+impl<'a> MilestoneFeature<'a> {
+    fn new(f: &'a Feature) -> Self {
+        MilestoneFeature {
+            slug: &f.slug,
+            phase: f.phase.to_string(),
+            status: feature_status(f),
+            title: &f.title,
+        }
+    }
+}
+
+#[derive(serde::Serialize)]
+struct MilestoneSummary<'a> {
+    slug: &'a str,
+    title: &'a str,
+    status: String,
+    features: Vec<MilestoneFeature<'a>>,
+    done: usize,
+    total: usize,
+}
+
+impl<'a> MilestoneSummary<'a> {
+    /// `features` may hold any superset of the milestone's own features.
+    fn new(m: &'a Milestone, features: &'a [Feature]) -> Self {
+        let mf: Vec<MilestoneFeature> = m
+            .features
+            .iter()
+            .filter_map(|slug| features.iter().find(|f| f.slug == *slug))
+            .map(MilestoneFeature::new)
+            .collect();
+        let done = mf.iter().filter(|f| f.phase == "released").count();
+        MilestoneSummary {
+            slug: &m.slug,
+            title: &m.title,
+            status: m.compute_status(features).to_string(),
+            done,
+            total: mf.len(),
+            features: mf,
+        }
+    }
+}
+
+/// One line of `sdlc state --jsonl`.
+#[derive(serde::Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum StateLine<'a> {
+    Header {
+        project: &'a str,
+        feature_count: usize,
+        milestone_count: usize,
+        last_action: Option<&'a sdlc_core::state::HistoryEntry>,
+        active_directives: &'a [sdlc_core::state::ActiveDirective],
+        blocked: &'a [sdlc_core::state::BlockedItem],
+    },
+    Milestone(MilestoneSummary<'a>),
+    Feature(MilestoneFeature<'a>),
+}
+
+fn print_line(line: &StateLine) -> anyhow::Result<()> {
+    println!("{}", serde_json::to_string(line)?);
+    Ok(())
+}
+
+/// Stream state as JSON lines: a header with counts, each milestone, then
+/// each unassigned feature. Only one milestone's features are held in
+/// memory at a time.
+fn run_jsonl(root: &Path, state: &State) -> anyhow::Result<()> {
+    let slugs = Feature::slugs(root).context("failed to list features")?;
+    let milestones = Milestone::list(root).context("failed to list milestones")?;
+
+    print_line(&StateLine::Header {
+        project: &state.project,
+        feature_count: slugs.len(),
+        milestone_count: milestones.len(),
+        last_action: state.last_action(),
+        active_directives: &state.active_directives,
+        blocked: &state.blocked,
+    })?;
+
+    let mut assigned: HashSet<&str> = HashSet::new();
+    for m in &milestones {
+        let features: Vec<Feature> = m
+            .features
+            .iter()
+            .filter(|slug| slugs.contains(slug))
+            .map(|slug| Feature::load(root, slug))
+            .collect::<Result<_, _>>()
+            .with_context(|| format!("failed to load features of milestone '{}'", m.slug))?;
+        print_line(&StateLine::Milestone(MilestoneSummary::new(m, &features)))?;
+        assigned.extend(m.features.iter().map(String::as_str));
+    }
+
+    for slug in slugs.iter().filter(|s| !assigned.contains(s.as_str())) {
+        let feature = Feature::load(root, slug)
+            .with_context(|| format!("failed to load feature '{slug}'"))?;
+        print_line(&StateLine::Feature(MilestoneFeature::new(&feature)))?;
+    }
+    Ok(())
+}
+
+pub fn run(root: &Path, out: OutputFormat, jsonl: bool) -> anyhow::Result<()> {
     let state = State::load(root).context("failed to load state")?;
+    if jsonl {
+        return run_jsonl(root, &state);
+    }
     let features = Feature::list(root).unwrap_or_default();
     let milestones = Milestone::list(root).unwrap_or_default();
     let active_milestones: Vec<&Milestone> = milestones
@@ -35,172 +146,126 @@ pub fn run(root: &Path, json: bool) -> anyhow::Result<()> {
         })
         .collect();
 
-    if json {
-        #[derive(serde::Serialize)]
-        struct MilestoneFeature<'a> {
-            slug: &'a str,
-            phase: String,
-            status: &'static str,
-            title: &'a str,
-        }
+    #[derive(serde::Serialize)]
+    struct StateOutput<'a> {
+        project: &'a str,
+        milestones: Vec<MilestoneSummary<'a>>,
+        unassigned: Vec<MilestoneFeature<'a>>,
+        feature_count: usize,
+        last_action: Option<&'a sdlc_core::state::HistoryEntry>,
+        active_directives: &'a [sdlc_core::state::ActiveDirective],
+        blocked: &'a [sdlc_core::state::BlockedItem],
+    }
 
-        #[derive(serde::Serialize)]
-        struct MilestoneSummary<'a> {
-            slug: &'a str,
-            title: &'a str,
-            status: String,
-            features: Vec<MilestoneFeature<'a>>,
-            done: usize,
-            total: usize,
-        }
+    let assigned: HashSet<&str> = milestones
+        .iter()
+        .flat_map(|m| m.features.iter().map(|s| s.as_str()))
+        .collect();
 
-        #[derive(serde::Serialize)]
-        struct StateOutput<'a> {
-            project: &'a str,
-            milestones: Vec<MilestoneSummary<'a>>,
-            unassigned: Vec<MilestoneFeature<'a>>,
-            feature_count: usize,
-            last_action: Option<&'a sdlc_core::state::HistoryEntry>,
-            active_directives: &'a [sdlc_core::state::ActiveDirective],
-            blocked: &'a [sdlc_core::state::BlockedItem],
-        }
+    let milestone_summaries: Vec<MilestoneSummary> = milestones
+        .iter()
+        .map(|m| MilestoneSummary::new(m, &features))
+        .collect();
 
-        let assigned: HashSet<&str> = milestones
-            .iter()
-            .flat_map(|m| m.features.iter().map(|s| s.as_str()))
-            .collect();
+    let unassigned: Vec<MilestoneFeature> = features
+        .iter()
+        .filter(|f| !assigned.contains(f.slug.as_str()))
+        .map(MilestoneFeature::new)
+        .collect();
 
-        let milestone_summaries: Vec<MilestoneSummary> = milestones
-            .iter()
-            .map(|m| {
-                let mf: Vec<MilestoneFeature> = m
-                    .features
-                    .iter()
-                    .filter_map(|slug| features.iter().find(|f| f.slug == *slug))
-                    .map(|f| MilestoneFeature {
-                        slug: &f.slug,
-                        phase: f.phase.to_string(),
-                        status: feature_status(f),
-                        title: &f.title,
-                    })
-                    .collect();
-                let done = mf.iter().filter(|f| f.phase == "released").count();
-                MilestoneSummary {
-                    slug: &m.slug,
-                    title: &m.title,
-                    status: m.compute_status(&features).to_string(),
-                    done,
-                    total: mf.len(),
-                    features: mf,
-                }
-            })
-            .collect();
+    let output = StateOutput {
+        project: &state.project,
+        milestones: milestone_summaries,
+        unassigned,
+        feature_count: features.len(),
+        last_action: state.last_action(),
+        active_directives: &state.active_directives,
+        blocked: &state.blocked,
+    };
+    out.emit(&output, || {
+        // -- Human-readable output ------------------------------------------------
 
-        let unassigned: Vec<MilestoneFeature> = features
+        println!("Project: {}", state.project);
+
+        if features.is_empty() && active_milestones.is_empty() {
+            println!("Features: 0");
+            println!("\nNo features yet. Run: sdlc feature create <slug>");
+            return Ok(());
+        }
+
+        // Collect all feature slugs assigned to any active milestone
+        let assigned: HashSet<&str> = active_milestones
             .iter()
-            .filter(|f| !assigned.contains(f.slug.as_str()))
-            .map(|f| MilestoneFeature {
-                slug: &f.slug,
-                phase: f.phase.to_string(),
-                status: feature_status(f),
-                title: &f.title,
-            })
+            .flat_map(|m| m.features.iter().map(|s| s.as_str()))
             .collect();
 
-        let output = StateOutput {
-            project: &state.project,
-            milestones: milestone_summaries,
-            unassigned,
-            feature_count: features.len(),
-            last_action: state.last_action(),
-            active_directives: &state.active_directives,
-            blocked: &state.blocked,
-        };
-        return print_json(&output);
-    }
-
-    // -- Human-readable output ------------------------------------------------
+        // Print each active milestone with its features
+        for ms in &active_milestones {
+            let ms_features: Vec<&Feature> = ms
+                .features
+                .iter()
+                .filter_map(|slug| features.iter().find(|f| f.slug == *slug))
+                .collect();
 
-    println!("Project: {}", state.project);
+            let done = ms_features
+                .iter()
+                .filter(|f| f.phase.to_string() == "released")
+                .count();
 
-    if features.is_empty() && active_milestones.is_empty() {
-        println!("Features: 0");
-        println!("\nNo features yet. Run: sdlc feature create <slug>");
-        return Ok(());
-    }
+            println!(
+                "\nMilestone: {} ({}/{} done)",
+                ms.title,
+                done,
+                ms_features.len()
+            );
 
-    // Collect all feature slugs assigned to any active milestone
-    let assigned: HashSet<&str> = active_milestones
-        .iter()
-        .flat_map(|m| m.features.iter().map(|s| s.as_str()))
-        .collect();
+            if ms_features.is_empty() {
+                println!("  (no features)");
+            } else {
+                let rows: Vec<Vec<String>> = ms_features.iter().map(|f| feature_row(f)).collect();
+                print_table_indented(&["SLUG", "PHASE", "STATUS", "TITLE"], rows);
+            }
+        }
 
-    // Print each active milestone with its features
-    for ms in &active_milestones {
-        let ms_features: Vec<&Feature> = ms
-            .features
+        // Unassigned features
+        let unassigned: Vec<&Feature> = features
             .iter()
-            .filter_map(|slug| features.iter().find(|f| f.slug == *slug))
+            .filter(|f| !assigned.contains(f.slug.as_str()))
             .collect();
 
-        let done = ms_features
-            .iter()
-            .filter(|f| f.phase.to_string() == "released")
-            .count();
-
-        println!(
-            "\nMilestone: {} ({}/{} done)",
-            ms.title,
-            done,
-            ms_features.len()
-        );
-
-        if ms_features.is_empty() {
-            println!("  (no features)");
-        } else {
-            let rows: Vec<Vec<String>> = ms_features.iter().map(|f| feature_row(f)).collect();
+        if !unassigned.is_empty() {
+            if active_milestones.is_empty() {
+                println!("\nFeatures:");
+            } else {
+                println!("\nUnassigned:");
+            }
+            let rows: Vec<Vec<String>> = unassigned.iter().map(|f| feature_row(f)).collect();
             print_table_indented(&["SLUG", "PHASE", "STATUS", "TITLE"], rows);
         }
-    }
-
-    // Unassigned features
-    let unassigned: Vec<&Feature> = features
-        .iter()
-        .filter(|f| !assigned.contains(f.slug.as_str()))
-        .collect();
-
-    if !unassigned.is_empty() {
-        if active_milestones.is_empty() {
-            println!("\nFeatures:");
-        } else {
-            println!("\nUnassigned:");
-        }
-        let rows: Vec<Vec<String>> = unassigned.iter().map(|f| feature_row(f)).collect();
-        print_table_indented(&["SLUG", "PHASE", "STATUS", "TITLE"], rows);
-    }
 
-    // Active directives
-    if !state.active_directives.is_empty() {
-        println!("\nActive directives:");
-        for w in &state.active_directives {
-            println!("  {} — {}", w.feature, w.action);
+        // Active directives
+        if !state.active_directives.is_empty() {
+            println!("\nActive directives:");
+            for w in &state.active_directives {
+                println!("  {} — {}", w.feature, w.action);
+            }
         }
-    }
 
-    // Blocked items
-    if !state.blocked.is_empty() {
-        println!("\nBlocked:");
-        for b in &state.blocked {
-            let reason = if b.reason.len() > 50 {
-                format!("{}...", &b.reason[..47])
-            } else {
-                b.reason.clone()
-            };
-            println!("  {} — {}", b.feature, reason);
+        // Blocked items
+        if !state.blocked.is_empty() {
+            println!("\nBlocked:");
+            for b in &state.blocked {
+                let reason = if b.reason.len() > 50 {
+                    format!("{}...", &b.reason[..47])
+                } else {
+                    b.reason.clone()
+                };
+                println!("  {} — {}", b.feature, reason);
+            }
         }
-    }
 
-    Ok(())
+        Ok(())
+    })
 }
 
 fn feature_status(f: &Feature) -> &'static str {
@@ -1,7 +1,10 @@
-use crate::output::{print_json, print_table};
+use crate::output::{print_table, OutputFormat};
 use anyhow::Context;
 use clap::Subcommand;
-use sdlc_core::{classifier::try_auto_transition, feature::Feature, task as task_ops};
+use sdlc_core::{
+    artifact, classifier::try_auto_transition, error::SdlcError, feature::Feature, paths,
+    task as task_ops, types::ArtifactType,
+};
 use std::path::Path;
 
 #[derive(Subcommand)]
@@ -25,6 +28,13 @@ pub enum TaskSubcommand {
     },
     /// List tasks for a feature, or all tasks across every feature when no slug is given
     List { slug: Option<String> },
+    /// Set the execution order of a feature's tasks
+    Reorder {
+        slug: String,
+        /// Every task ID of the feature, in desired order
+        #[arg(required = true)]
+        task_ids: Vec<String>,
+    },
     /// Edit task fields (title, description, dependencies)
     Edit {
         slug: String,
@@ -39,6 +49,8 @@ pub enum TaskSubcommand {
         #[arg(long)]
         depends: Option<String>,
     },
+    /// Create tasks for checklist items in tasks.md that have no matching task
+    SyncFromArtifact { slug: String },
     /// Show full details for a single task
     Get { slug: String, task_id: String },
     /// Search tasks by title or description
@@ -53,17 +65,18 @@ pub enum TaskSubcommand {
     },
 }
 
-pub fn run(root: &Path, subcmd: TaskSubcommand, json: bool) -> anyhow::Result<()> {
+pub fn run(root: &Path, subcmd: TaskSubcommand, out: OutputFormat) -> anyhow::Result<()> {
     match subcmd {
-        TaskSubcommand::Add { slug, title } => add(root, &slug, &title.join(" "), json),
-        TaskSubcommand::Start { slug, task_id } => start(root, &slug, &task_id, json),
-        TaskSubcommand::Complete { slug, task_id } => complete(root, &slug, &task_id, json),
+        TaskSubcommand::Add { slug, title } => add(root, &slug, &title.join(" "), out),
+        TaskSubcommand::Start { slug, task_id } => start(root, &slug, &task_id, out),
+        TaskSubcommand::Complete { slug, task_id } => complete(root, &slug, &task_id, out),
         TaskSubcommand::Block {
             slug,
             task_id,
             reason,
-        } => block(root, &slug, &task_id, &reason.join(" "), json),
-        TaskSubcommand::List { slug } => list(root, slug.as_deref(), json),
+        } => block(root, &slug, &task_id, &reason.join(" "), out),
+        TaskSubcommand::List { slug } => list(root, slug.as_deref(), out),
+        TaskSubcommand::Reorder { slug, task_ids } => reorder(root, &slug, &task_ids, out),
         TaskSubcommand::Edit {
             slug,
             task_id,
@@ -77,47 +90,110 @@ pub fn run(root: &Path, subcmd: TaskSubcommand, json: bool) -> anyhow::Result<()
             title.as_deref(),
             description.as_deref(),
             depends.as_deref(),
-            json,
+            out,
         ),
-        TaskSubcommand::Get { slug, task_id } => get(root, &slug, &task_id, json),
+        TaskSubcommand::SyncFromArtifact { slug } => sync_from_artifact(root, &slug, out),
+        TaskSubcommand::Get { slug, task_id } => get(root, &slug, &task_id, out),
         TaskSubcommand::Search { query, slug, limit } => {
-            search(root, &query, slug.as_deref(), limit, json)
+            search(root, &query, slug.as_deref(), limit, out)
         }
     }
 }
 
-fn add(root: &Path, slug: &str, title: &str, json: bool) -> anyhow::Result<()> {
+fn add(root: &Path, slug: &str, title: &str, out: OutputFormat) -> anyhow::Result<()> {
     let mut feature =
         Feature::load(root, slug).with_context(|| format!("feature '{slug}' not found"))?;
     let id = task_ops::add_task(&mut feature.tasks, title);
     feature.save(root).context("failed to save feature")?;
 
-    if json {
-        print_json(&serde_json::json!({ "slug": slug, "task_id": id, "title": title }))?;
-    } else {
-        println!("Added task [{id}]: {title}");
+    out.emit(
+        &serde_json::json!({ "slug": slug, "task_id": id, "title": title }),
+        || {
+            println!("Added task [{id}]: {title}");
+            Ok(())
+        },
+    )?;
+    Ok(())
+}
+
+fn sync_from_artifact(root: &Path, slug: &str, out: OutputFormat) -> anyhow::Result<()> {
+    let mut feature =
+        Feature::load(root, slug).with_context(|| format!("feature '{slug}' not found"))?;
+    let path = paths::artifact_path(root, slug, ArtifactType::Tasks.filename());
+    if !path.exists() {
+        return Err(SdlcError::ArtifactNotFound(path.display().to_string()).into());
+    }
+    let content = std::fs::read_to_string(&path)
+        .with_context(|| format!("failed to read {}", path.display()))?;
+    let items = artifact::parse_task_checklist(&content);
+    let created = task_ops::sync_from_checklist(&mut feature.tasks, &items);
+    if !created.is_empty() {
+        feature.save(root).context("failed to save feature")?;
     }
+
+    let tasks: Vec<_> = feature
+        .tasks
+        .iter()
+        .filter(|t| created.contains(&t.id))
+        .map(|t| serde_json::json!({ "task_id": t.id, "title": t.title, "status": t.status }))
+        .collect();
+    out.emit(
+        &serde_json::json!({
+            "slug": slug,
+            "checklist_items": items.len(),
+            "created": tasks,
+        }),
+        || {
+            if created.is_empty() {
+                println!(
+                    "Tasks already in sync with tasks.md ({} items)",
+                    items.len()
+                );
+            } else {
+                for t in feature.tasks.iter().filter(|t| created.contains(&t.id)) {
+                    println!("Added task [{}]: {}", t.id, t.title);
+                }
+            }
+            Ok(())
+        },
+    )
+}
+
+fn reorder(root: &Path, slug: &str, task_ids: &[String], out: OutputFormat) -> anyhow::Result<()> {
+    let refs: Vec<&str> = task_ids.iter().map(|s| s.as_str()).collect();
+    task_ops::reorder(root, slug, &refs)
+        .with_context(|| format!("failed to reorder tasks for '{slug}'"))?;
+
+    out.emit(
+        &serde_json::json!({ "slug": slug, "tasks": task_ids }),
+        || {
+            for (i, id) in task_ids.iter().enumerate() {
+                println!("{}. {}", i + 1, id);
+            }
+            Ok(())
+        },
+    )?;
     Ok(())
 }
 
-fn start(root: &Path, slug: &str, task_id: &str, json: bool) -> anyhow::Result<()> {
+fn start(root: &Path, slug: &str, task_id: &str, out: OutputFormat) -> anyhow::Result<()> {
     let mut feature =
         Feature::load(root, slug).with_context(|| format!("feature '{slug}' not found"))?;
     task_ops::start_task(&mut feature.tasks, task_id)
         .with_context(|| format!("task '{task_id}' not found"))?;
     feature.save(root).context("failed to save feature")?;
 
-    if json {
-        print_json(
-            &serde_json::json!({ "slug": slug, "task_id": task_id, "status": "in_progress" }),
-        )?;
-    } else {
-        println!("Started task [{task_id}]");
-    }
+    out.emit(
+        &serde_json::json!({ "slug": slug, "task_id": task_id, "status": "in_progress" }),
+        || {
+            println!("Started task [{task_id}]");
+            Ok(())
+        },
+    )?;
     Ok(())
 }
 
-fn complete(root: &Path, slug: &str, task_id: &str, json: bool) -> anyhow::Result<()> {
+fn complete(root: &Path, slug: &str, task_id: &str, out: OutputFormat) -> anyhow::Result<()> {
     let mut feature =
         Feature::load(root, slug).with_context(|| format!("feature '{slug}' not found"))?;
     task_ops::complete_task(&mut feature.tasks, task_id)
@@ -126,39 +202,44 @@ fn complete(root: &Path, slug: &str, task_id: &str, json: bool) -> anyhow::Resul
 
     let transitioned_to = try_auto_transition(root, slug);
 
-    if json {
-        let mut val =
-            serde_json::json!({ "slug": slug, "task_id": task_id, "status": "completed" });
-        if let Some(phase) = &transitioned_to {
-            val["transitioned_to"] = serde_json::Value::String(phase.clone());
-        }
-        print_json(&val)?;
-    } else {
+    let mut val = serde_json::json!({ "slug": slug, "task_id": task_id, "status": "completed" });
+    if let Some(phase) = &transitioned_to {
+        val["transitioned_to"] = serde_json::Value::String(phase.clone());
+    }
+    out.emit(&val, || {
         println!("Completed task [{task_id}]");
         if let Some(phase) = &transitioned_to {
             println!("Transitioned to: {phase}");
         }
-    }
-    Ok(())
+        Ok(())
+    })
 }
 
-fn block(root: &Path, slug: &str, task_id: &str, reason: &str, json: bool) -> anyhow::Result<()> {
+fn block(
+    root: &Path,
+    slug: &str,
+    task_id: &str,
+    reason: &str,
+    out: OutputFormat,
+) -> anyhow::Result<()> {
     let mut feature =
         Feature::load(root, slug).with_context(|| format!("feature '{slug}' not found"))?;
     task_ops::block_task(&mut feature.tasks, task_id, reason)
         .with_context(|| format!("task '{task_id}' not found"))?;
     feature.save(root).context("failed to save feature")?;
 
-    if json {
-        print_json(&serde_json::json!({
+    out.emit(
+        &serde_json::json!({
             "slug": slug,
             "task_id": task_id,
             "status": "blocked",
             "reason": reason,
-        }))?;
-    } else {
-        println!("Blocked task [{task_id}]: {reason}");
-    }
+        }),
+        || {
+            println!("Blocked task [{task_id}]: {reason}");
+            Ok(())
+        },
+    )?;
     Ok(())
 }
 
@@ -169,7 +250,7 @@ fn edit(
     title: Option<&str>,
     description: Option<&str>,
     depends: Option<&str>,
-    json: bool,
+    out: OutputFormat,
 ) -> anyhow::Result<()> {
     let mut feature =
         Feature::load(root, slug).with_context(|| format!("feature '{slug}' not found"))?;
@@ -195,19 +276,21 @@ fn edit(
 
     feature.save(root).context("failed to save feature")?;
 
-    if json {
-        print_json(&serde_json::json!({
+    out.emit(
+        &serde_json::json!({
             "slug": slug,
             "task_id": task_id,
             "updated": true,
-        }))?;
-    } else {
-        println!("Updated task [{task_id}]");
-    }
+        }),
+        || {
+            println!("Updated task [{task_id}]");
+            Ok(())
+        },
+    )?;
     Ok(())
 }
 
-fn get(root: &Path, slug: &str, task_id: &str, json: bool) -> anyhow::Result<()> {
+fn get(root: &Path, slug: &str, task_id: &str, out: OutputFormat) -> anyhow::Result<()> {
     let feature =
         Feature::load(root, slug).with_context(|| format!("feature '{slug}' not found"))?;
     let task = feature
@@ -216,29 +299,26 @@ fn get(root: &Path, slug: &str, task_id: &str, json: bool) -> anyhow::Result<()>
         .find(|t| t.id == task_id)
         .with_context(|| format!("task '{task_id}' not found in feature '{slug}'"))?;
 
-    if json {
-        print_json(task)?;
-        return Ok(());
-    }
-
-    println!("Task: {}", task.id);
-    println!("Status:      {}", task.status);
-    println!("Title:       {}", task.title);
-    if let Some(desc) = &task.description {
-        println!("Description: {}", desc);
-    }
-    if let Some(started) = task.started_at {
-        println!("Started:     {}", started.format("%Y-%m-%d %H:%M"));
-    }
-    if !task.depends_on.is_empty() {
-        println!("Depends:     {}", task.depends_on.join(", "));
-    }
-    println!(
-        "Blocker:     {}",
-        task.blocker.as_deref().unwrap_or("(none)")
-    );
+    out.emit(task, || {
+        println!("Task: {}", task.id);
+        println!("Status:      {}", task.status);
+        println!("Title:       {}", task.title);
+        if let Some(desc) = &task.description {
+            println!("Description: {}", desc);
+        }
+        if let Some(started) = task.started_at {
+            println!("Started:     {}", started.format("%Y-%m-%d %H:%M"));
+        }
+        if !task.depends_on.is_empty() {
+            println!("Depends:     {}", task.depends_on.join(", "));
+        }
+        println!(
+            "Blocker:     {}",
+            task.blocker.as_deref().unwrap_or("(none)")
+        );
 
-    Ok(())
+        Ok(())
+    })
 }
 
 fn search(
@@ -246,7 +326,7 @@ fn search(
     query: &str,
     slug: Option<&str>,
     limit: usize,
-    json: bool,
+    out: OutputFormat,
 ) -> anyhow::Result<()> {
     use sdlc_core::search::TaskIndex;
 
@@ -259,86 +339,85 @@ fn search(
     let index = TaskIndex::build(&features).context("failed to build task index")?;
     let results = index.search(query, limit).context("search failed")?;
 
-    if json {
-        let items: Vec<serde_json::Value> = results
-            .iter()
-            .map(|r| {
-                serde_json::json!({
-                    "feature": r.feature_slug,
-                    "task_id": r.task_id,
-                    "status": r.status,
-                    "title": r.title,
-                    "score": r.score,
-                })
-            })
-            .collect();
-        print_json(&items)?;
-        return Ok(());
-    }
-
-    if results.is_empty() {
-        println!("No tasks matching '{}'.", query);
-        return Ok(());
-    }
-
-    let count = results.len();
-    println!(
-        "{count} result{} for \"{}\":",
-        if count == 1 { "" } else { "s" },
-        query
-    );
-    println!();
-    let rows: Vec<Vec<String>> = results
-        .into_iter()
+    let items: Vec<serde_json::Value> = results
+        .iter()
         .map(|r| {
-            vec![
-                format!("{:.2}", r.score),
-                r.feature_slug,
-                r.task_id,
-                r.status,
-                r.title,
-            ]
+            serde_json::json!({
+                "feature": r.feature_slug,
+                "task_id": r.task_id,
+                "status": r.status,
+                "title": r.title,
+                "score": r.score,
+            })
         })
         .collect();
-    print_table(&["SCORE", "FEATURE", "TASK ID", "STATUS", "TITLE"], rows);
-    Ok(())
-}
-
-fn list(root: &Path, slug: Option<&str>, json: bool) -> anyhow::Result<()> {
-    if let Some(slug) = slug {
-        // Single-feature mode — preserve existing compact output
-        let feature =
-            Feature::load(root, slug).with_context(|| format!("feature '{slug}' not found"))?;
-
-        if json {
-            print_json(&feature.tasks)?;
-            return Ok(());
-        }
-
-        if feature.tasks.is_empty() {
-            println!("No tasks for '{slug}'.");
+    out.emit(&items, || {
+        if results.is_empty() {
+            println!("No tasks matching '{}'.", query);
             return Ok(());
         }
 
-        println!("{}", task_ops::summarize(&feature.tasks));
+        let count = results.len();
+        println!(
+            "{count} result{} for \"{}\":",
+            if count == 1 { "" } else { "s" },
+            query
+        );
         println!();
-
-        let rows: Vec<Vec<String>> = feature
-            .tasks
-            .iter()
-            .map(|t| {
+        let rows: Vec<Vec<String>> = results
+            .into_iter()
+            .map(|r| {
                 vec![
-                    t.id.clone(),
-                    t.status.to_string(),
-                    t.title.clone(),
-                    t.blocker.clone().unwrap_or_default(),
+                    format!("{:.2}", r.score),
+                    r.feature_slug,
+                    r.task_id,
+                    r.status,
+                    r.title,
                 ]
             })
             .collect();
-        print_table(&["ID", "STATUS", "TITLE", "BLOCKER"], rows);
+        print_table(&["SCORE", "FEATURE", "TASK ID", "STATUS", "TITLE"], rows);
+        Ok(())
+    })
+}
+
+fn list(root: &Path, slug: Option<&str>, out: OutputFormat) -> anyhow::Result<()> {
+    if let Some(slug) = slug {
+        // Single-feature mode — preserve existing compact output
+        let mut feature =
+            Feature::load(root, slug).with_context(|| format!("feature '{slug}' not found"))?;
+        task_ops::sort_by_order(&mut feature.tasks);
+
+        out.emit(&feature.tasks, || {
+            if feature.tasks.is_empty() {
+                println!("No tasks for '{slug}'.");
+                return Ok(());
+            }
+
+            println!("{}", task_ops::summarize(&feature.tasks));
+            println!();
+
+            let rows: Vec<Vec<String>> = feature
+                .tasks
+                .iter()
+                .map(|t| {
+                    vec![
+                        t.id.clone(),
+                        t.status.to_string(),
+                        t.title.clone(),
+                        t.blocker.clone().unwrap_or_default(),
+                    ]
+                })
+                .collect();
+            print_table(&["ID", "STATUS", "TITLE", "BLOCKER"], rows);
+            Ok(())
+        })?;
     } else {
         // All-features mode — scan every feature and aggregate
-        let features = Feature::list(root).context("failed to list features")?;
+        let mut features = Feature::list(root).context("failed to list features")?;
+        for f in &mut features {
+            task_ops::sort_by_order(&mut f.tasks);
+        }
 
         // Collect (feature_slug, task) pairs, skip features with no tasks
         let rows: Vec<Vec<String>> = features
@@ -356,38 +435,36 @@ fn list(root: &Path, slug: Option<&str>, json: bool) -> anyhow::Result<()> {
             })
             .collect();
 
-        if json {
-            let items: Vec<serde_json::Value> = features
-                .iter()
-                .flat_map(|f| {
-                    f.tasks.iter().map(move |t| {
-                        serde_json::json!({
-                            "feature": f.slug,
-                            "task_id": t.id,
-                            "status": t.status,
-                            "title": t.title,
-                            "blocker": t.blocker,
-                        })
+        let items: Vec<serde_json::Value> = features
+            .iter()
+            .flat_map(|f| {
+                f.tasks.iter().map(move |t| {
+                    serde_json::json!({
+                        "feature": f.slug,
+                        "task_id": t.id,
+                        "status": t.status,
+                        "title": t.title,
+                        "blocker": t.blocker,
                     })
                 })
-                .collect();
-            print_json(&items)?;
-            return Ok(());
-        }
-
-        if rows.is_empty() {
-            println!("No tasks found.");
-            return Ok(());
-        }
-
-        let total: usize = rows.len();
-        let done = rows.iter().filter(|r| r[2] == "completed").count();
-        println!(
-            "{done}/{total} tasks completed across {} feature(s)",
-            features.iter().filter(|f| !f.tasks.is_empty()).count()
-        );
-        println!();
-        print_table(&["FEATURE", "ID", "STATUS", "TITLE", "BLOCKER"], rows);
+            })
+            .collect();
+        out.emit(&items, || {
+            if rows.is_empty() {
+                println!("No tasks found.");
+                return Ok(());
+            }
+
+            let total: usize = rows.len();
+            let done = rows.iter().filter(|r| r[2] == "completed").count();
+            println!(
+                "{done}/{total} tasks completed across {} feature(s)",
+                features.iter().filter(|f| !f.tasks.is_empty()).count()
+            );
+            println!();
+            print_table(&["FEATURE", "ID", "STATUS", "TITLE", "BLOCKER"], rows);
+            Ok(())
+        })?;
     }
     Ok(())
 }
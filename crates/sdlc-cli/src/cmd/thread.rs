@@ -1,6 +1,6 @@
 //! `sdlc thread` — manage feedback threads (create, post, list, show).
 
-use crate::output::{print_json, print_table};
+use crate::output::{print_table, OutputFormat};
 use anyhow::Context;
 use clap::Subcommand;
 use std::path::Path;
@@ -60,7 +60,7 @@ pub enum ThreadSubcommand {
 // Entry point
 // ---------------------------------------------------------------------------
 
-pub fn run(root: &Path, subcommand: ThreadSubcommand, _json: bool) -> anyhow::Result<()> {
+pub fn run(root: &Path, subcommand: ThreadSubcommand, out: OutputFormat) -> anyhow::Result<()> {
     match subcommand {
         ThreadSubcommand::Create { context, title } => {
             let title_str = title.as_deref().unwrap_or("");
@@ -91,28 +91,27 @@ pub fn run(root: &Path, subcommand: ThreadSubcommand, _json: bool) -> anyhow::Re
             let threads = sdlc_core::feedback_thread::list_threads(root, context.as_deref())
                 .context("failed to list threads")?;
 
-            if json {
-                return print_json(&threads);
-            }
-
-            if threads.is_empty() {
-                println!("No threads found.");
-                return Ok(());
-            }
+            let out = out.or_json(json);
+            out.emit(&threads, || {
+                if threads.is_empty() {
+                    println!("No threads found.");
+                    return Ok(());
+                }
 
-            let rows: Vec<Vec<String>> = threads
-                .iter()
-                .map(|t| {
-                    vec![
-                        t.id.clone(),
-                        t.context.clone(),
-                        t.post_count.to_string(),
-                        t.updated_at.format("%Y-%m-%d %H:%M").to_string(),
-                    ]
-                })
-                .collect();
-            print_table(&["ID", "CONTEXT", "POSTS", "UPDATED"], rows);
-            Ok(())
+                let rows: Vec<Vec<String>> = threads
+                    .iter()
+                    .map(|t| {
+                        vec![
+                            t.id.clone(),
+                            t.context.clone(),
+                            t.post_count.to_string(),
+                            t.updated_at.format("%Y-%m-%d %H:%M").to_string(),
+                        ]
+                    })
+                    .collect();
+                print_table(&["ID", "CONTEXT", "POSTS", "UPDATED"], rows);
+                Ok(())
+            })
         }
 
         ThreadSubcommand::Show { id, json } => {
@@ -121,41 +120,40 @@ pub fn run(root: &Path, subcommand: ThreadSubcommand, _json: bool) -> anyhow::Re
             let posts = sdlc_core::feedback_thread::list_posts(root, &id)
                 .with_context(|| format!("failed to load posts for thread '{id}'"))?;
 
-            if json {
-                let mut value = serde_json::to_value(&thread)?;
-                value["posts"] = serde_json::to_value(&posts)?;
-                return print_json(&value);
-            }
-
-            println!("ID:      {}", thread.id);
-            println!("Title:   {}", thread.title);
-            println!("Context: {}", thread.context);
-            println!("Posts:   {}", thread.post_count);
-            println!(
-                "Created: {}",
-                thread.created_at.format("%Y-%m-%d %H:%M UTC")
-            );
-            println!(
-                "Updated: {}",
-                thread.updated_at.format("%Y-%m-%d %H:%M UTC")
-            );
-
-            if posts.is_empty() {
-                println!("\n(no posts yet)");
-            } else {
-                println!();
-                for post in &posts {
-                    println!(
-                        "--- #{} [{}] {} ---",
-                        post.seq,
-                        post.author,
-                        post.created_at.format("%Y-%m-%d %H:%M UTC")
-                    );
-                    println!("{}", post.content);
+            let out = out.or_json(json);
+            let mut value = serde_json::to_value(&thread)?;
+            value["posts"] = serde_json::to_value(&posts)?;
+            out.emit(&value, || {
+                println!("ID:      {}", thread.id);
+                println!("Title:   {}", thread.title);
+                println!("Context: {}", thread.context);
+                println!("Posts:   {}", thread.post_count);
+                println!(
+                    "Created: {}",
+                    thread.created_at.format("%Y-%m-%d %H:%M UTC")
+                );
+                println!(
+                    "Updated: {}",
+                    thread.updated_at.format("%Y-%m-%d %H:%M UTC")
+                );
+
+                if posts.is_empty() {
+                    println!("\n(no posts yet)");
+                } else {
                     println!();
+                    for post in &posts {
+                        println!(
+                            "--- #{} [{}] {} ---",
+                            post.seq,
+                            post.author,
+                            post.created_at.format("%Y-%m-%d %H:%M UTC")
+                        );
+                        println!("{}", post.content);
+                        println!();
+                    }
                 }
-            }
-            Ok(())
+                Ok(())
+            })
         }
     }
 }
@@ -3,7 +3,10 @@ use crate::cmd::{orchestrate, update};
 use crate::output::print_table;
 use anyhow::{anyhow, Result};
 use clap::Subcommand;
-use sdlc_core::{config::Config, ui_registry};
+use sdlc_core::{
+    config::{Config, HttpConfig},
+    ui_registry,
+};
 use sdlc_server::tunnel::{derive_tunnel_name, generate_token, Tunnel};
 use std::path::Path;
 
@@ -11,6 +14,34 @@ use std::path::Path;
 // Subcommand definition
 // ---------------------------------------------------------------------------
 
+/// HTTP tuning flags; each overrides the matching `http` field in config.yaml.
+#[derive(clap::Args, Debug, Clone, Copy, Default)]
+pub struct HttpArgs {
+    /// Seconds between SSE keep-alive comments (default 15)
+    #[arg(long, value_name = "SECS")]
+    pub keep_alive_secs: Option<u64>,
+    /// Largest accepted request body in bytes (default 32 MiB)
+    #[arg(long, value_name = "BYTES")]
+    pub body_limit_bytes: Option<usize>,
+    /// Seconds a request may take to produce a response; 0 disables (default 0)
+    #[arg(long, value_name = "SECS")]
+    pub request_timeout_secs: Option<u64>,
+    /// Seconds allowed to receive a request body; 0 disables (default 300)
+    #[arg(long, value_name = "SECS")]
+    pub read_timeout_secs: Option<u64>,
+}
+
+impl From<HttpArgs> for HttpConfig {
+    fn from(args: HttpArgs) -> Self {
+        HttpConfig {
+            keep_alive_secs: args.keep_alive_secs,
+            body_limit_bytes: args.body_limit_bytes,
+            request_timeout_secs: args.request_timeout_secs,
+            read_timeout_secs: args.read_timeout_secs,
+        }
+    }
+}
+
 #[derive(Subcommand, Debug)]
 pub enum UiSubcommand {
     /// Start the web UI server
@@ -33,6 +64,8 @@ pub enum UiSubcommand {
         /// Start in hub mode — project navigator, no project required
         #[arg(long)]
         hub: bool,
+        #[command(flatten)]
+        http: HttpArgs,
     },
     /// List all running UI instances
     List,
@@ -52,6 +85,7 @@ pub enum UiSubcommand {
 // Dispatch
 // ---------------------------------------------------------------------------
 
+#[allow(clippy::too_many_arguments)]
 pub fn run(
     root: &Path,
     subcommand: Option<UiSubcommand>,
@@ -60,6 +94,7 @@ pub fn run(
     no_tunnel: bool,
     tick_rate: u64,
     run_actions: bool,
+    http: HttpArgs,
 ) -> Result<()> {
     match subcommand {
         None => run_start(
@@ -70,6 +105,7 @@ pub fn run(
             tick_rate,
             run_actions,
             false,
+            http.into(),
         ),
         Some(UiSubcommand::Start {
             port: p,
@@ -78,7 +114,8 @@ pub fn run(
             tick_rate: tr,
             run_actions: ra,
             hub,
-        }) => run_start(root, p, n, nt, tr, ra, hub),
+            http: h,
+        }) => run_start(root, p, n, nt, tr, ra, hub, h.into()),
         Some(UiSubcommand::List) => run_list(),
         Some(UiSubcommand::Kill { name }) => run_kill(name.as_deref(), root),
         Some(UiSubcommand::Open { name }) => run_open(name.as_deref(), root),
@@ -89,6 +126,7 @@ pub fn run(
 // start
 // ---------------------------------------------------------------------------
 
+#[allow(clippy::too_many_arguments)]
 fn run_start(
     root: &Path,
     port: u16,
@@ -97,10 +135,11 @@ fn run_start(
     tick_rate: u64,
     run_actions: bool,
     hub_mode: bool,
+    http: HttpConfig,
 ) -> Result<()> {
     // Hub mode: skip update scaffolding and project config load.
     if hub_mode {
-        return run_start_hub(root, port, no_open);
+        return run_start_hub(root, port, no_open, http);
     }
 
     let use_tunnel = !no_tunnel;
@@ -145,7 +184,7 @@ fn run_start(
     let rt = tokio::runtime::Runtime::new()?;
     let root_buf = root.to_path_buf();
 
-    rt.block_on(async move {
+    let result = rt.block_on(async move {
         let listener = tokio::net::TcpListener::bind(format!("0.0.0.0:{port}")).await?;
         let actual_port = listener.local_addr()?.port();
         let pid = std::process::id();
@@ -174,7 +213,7 @@ fn run_start(
                     let token = generate_token();
                     print_tunnel_info(&name, actual_port, &tun.url, &token);
                     tokio::select! {
-                        res = sdlc_server::serve_on(root_buf, listener, false, Some((tun, token))) => res,
+                        res = sdlc_server::serve_on(root_buf, listener, false, Some((tun, token)), http) => res,
                         _ = tokio::signal::ctrl_c() => Ok(()),
                     }
                 }
@@ -183,7 +222,7 @@ fn run_start(
                     eprintln!("Warning: orch-tunnel failed to start ({e}). Running in local-only mode.");
                     println!("SDLC UI for '{name}' → {local_url}  (PID {pid})");
                     tokio::select! {
-                        res = sdlc_server::serve_on(root_buf, listener, !no_open, None) => res,
+                        res = sdlc_server::serve_on(root_buf, listener, !no_open, None, http) => res,
                         _ = tokio::signal::ctrl_c() => Ok(()),
                     }
                 }
@@ -192,18 +231,25 @@ fn run_start(
             println!("SDLC UI for '{name}' → {local_url}  (PID {pid})");
 
             tokio::select! {
-                res = sdlc_server::serve_on(root_buf, listener, !no_open, None) => res,
+                res = sdlc_server::serve_on(root_buf, listener, !no_open, None, http) => res,
                 _ = tokio::signal::ctrl_c() => Ok(()),
             }
         };
 
         let _ = record_clone.remove();
         result
-    })
+    });
+
+    // Stop any ponder chat still running, then log the dialogue it had so far.
+    rt.shutdown_timeout(std::time::Duration::from_secs(5));
+    if let Err(e) = sdlc_core::ponder::recover_partial_sessions(root) {
+        eprintln!("sdlc ui: could not save interrupted ponder sessions: {e}");
+    }
+    result
 }
 
 /// Start the server in hub mode — project navigator, no project config required.
-fn run_start_hub(root: &Path, port: u16, no_open: bool) -> Result<()> {
+fn run_start_hub(root: &Path, port: u16, no_open: bool, http: HttpConfig) -> Result<()> {
     let name = "hub";
     let root_buf = root.to_path_buf();
     let rt = tokio::runtime::Runtime::new()?;
@@ -242,7 +288,7 @@ fn run_start_hub(root: &Path, port: u16, no_open: bool) -> Result<()> {
         println!("SDLC Hub → {local_url}  (PID {pid})");
 
         let result = tokio::select! {
-            res = sdlc_server::serve_on_hub(root_buf, listener, !no_open) => res,
+            res = sdlc_server::serve_on_hub(root_buf, listener, !no_open, http) => res,
             _ = tokio::signal::ctrl_c() => Ok(()),
         };
 
@@ -14,6 +14,7 @@ use cmd::{
     secrets::SecretsSubcommand, spike::SpikeSubcommand, task::TaskSubcommand,
     thread::ThreadSubcommand, tool::ToolCommand, ui::UiSubcommand,
 };
+use output::OutputFormat;
 use std::path::PathBuf;
 
 #[derive(Parser)]
@@ -28,10 +29,18 @@ struct Cli {
     #[arg(long, global = true, env = "SDLC_ROOT")]
     root: Option<PathBuf>,
 
-    /// Output as JSON
+    /// Output as JSON (same as `--format json`)
     #[arg(long, global = true, short = 'j')]
     json: bool,
 
+    /// Output format: table, json or jsonl (overrides --json)
+    #[arg(long, global = true, value_enum)]
+    format: Option<OutputFormat>,
+
+    /// Actor recorded in the audit log (default: $USER)
+    #[arg(long, global = true, env = "SDLC_ACTOR")]
+    actor: Option<String>,
+
     #[command(subcommand)]
     command: Commands,
 }
@@ -62,7 +71,19 @@ enum Commands {
     },
 
     /// Show project state
-    State,
+    State {
+        /// Stream one JSON object per line: a header with counts, then each
+        /// milestone and unassigned feature as it is loaded
+        #[arg(long)]
+        jsonl: bool,
+    },
+
+    /// Diagnose a corrupted .sdlc/ directory (unparseable YAML, dangling links, missing dirs)
+    Doctor {
+        /// Repair the problems that are safe to fix automatically
+        #[arg(long)]
+        fix: bool,
+    },
 
     /// Rebuild state.yaml from .sdlc/ directory contents (features, milestones, ponders)
     #[command(name = "state-rebuild")]
@@ -73,6 +94,9 @@ enum Commands {
         /// Feature slug (omit to show all active features)
         #[arg(long = "for")]
         feature: Option<String>,
+        /// Re-print the directive whenever state or the feature changes (JSONL with --json)
+        #[arg(long)]
+        watch: bool,
     },
 
     /// Show the single highest-priority actionable item (milestone order → feature order)
@@ -235,8 +259,16 @@ enum Commands {
         message: Option<String>,
     },
 
-    /// Merge a feature (stub)
-    Merge { slug: String },
+    /// Finalize a feature merge and mark it released
+    Merge {
+        slug: String,
+        /// Skip the check that the current branch merges cleanly into the base branch
+        #[arg(long)]
+        no_verify: bool,
+        /// Merge even if some dependencies are not yet released
+        #[arg(long)]
+        force: bool,
+    },
 
     /// Archive a feature
     Archive { slug: String },
@@ -276,6 +308,9 @@ enum Commands {
         #[arg(long)]
         debug: bool,
 
+        #[command(flatten)]
+        http: cmd::ui::HttpArgs,
+
         #[command(subcommand)]
         subcommand: Option<UiSubcommand>,
     },
@@ -315,6 +350,10 @@ fn main() {
             .init();
     }
 
+    if let Some(actor) = &cli.actor {
+        sdlc_core::audit::set_actor(actor.clone());
+    }
+
     let root_path = cli.root.as_deref();
     // `sdlc init` always targets CWD — don't walk up to an ancestor .sdlc/
     let root = if matches!(cli.command, Commands::Init { .. }) {
@@ -325,32 +364,36 @@ fn main() {
         root::resolve_root(root_path)
     };
 
+    let out = OutputFormat::resolve(cli.json, cli.format);
     let result = match cli.command {
         Commands::Init { platform } => cmd::init::run(&root, platform.as_deref()),
-        Commands::State => cmd::state::run(&root, cli.json),
+        Commands::State { jsonl } => {
+            cmd::state::run(&root, out, jsonl || out == OutputFormat::Jsonl)
+        }
         Commands::StateRebuild => cmd::state::rebuild(&root),
-        Commands::Next { feature } => cmd::next::run(&root, feature.as_deref(), cli.json),
-        Commands::Focus => cmd::focus::run(&root, cli.json),
-        Commands::ParallelWork => cmd::parallel_work::run(&root, cli.json),
-        Commands::Feature { subcommand } => cmd::feature::run(&root, subcommand, cli.json),
-        Commands::Backlog { subcommand } => cmd::backlog::run(&root, subcommand, cli.json),
-        Commands::Artifact { subcommand } => cmd::artifact::run(&root, subcommand, cli.json),
-        Commands::Task { subcommand } => cmd::task::run(&root, subcommand, cli.json),
-        Commands::Comment { subcommand } => cmd::comment::run(&root, subcommand, cli.json),
-        Commands::Milestone { subcommand } => cmd::milestone::run(&root, subcommand, cli.json),
-        Commands::Ponder { subcommand } => cmd::ponder::run(&root, subcommand, cli.json),
-        Commands::Investigate { subcommand } => cmd::investigate::run(&root, subcommand, cli.json),
-        Commands::Spike { subcommand } => cmd::spike::run(&root, subcommand, cli.json),
-        Commands::Knowledge { subcommand } => cmd::knowledge::run(&root, subcommand, cli.json),
-        Commands::Platform { subcommand } => cmd::platform::run(&root, subcommand, cli.json),
-        Commands::Project { subcommand } => cmd::project::run(&root, subcommand, cli.json),
-        Commands::Query { subcommand } => cmd::query::run(&root, subcommand, cli.json),
-        Commands::Config { subcommand } => cmd::config::run(&root, subcommand, cli.json),
-        Commands::Score { subcommand } => cmd::score::run(&root, subcommand, cli.json),
-        Commands::Secrets { subcommand } => cmd::secrets::run(&root, subcommand, cli.json),
-        Commands::Auth { subcommand } => cmd::auth::run(&root, subcommand, cli.json),
-        Commands::Escalate { subcommand } => cmd::escalate::run(&root, subcommand, cli.json),
-        Commands::Thread { subcommand } => cmd::thread::run(&root, subcommand, cli.json),
+        Commands::Doctor { fix } => cmd::doctor::run(&root, fix, out),
+        Commands::Next { feature, watch } => cmd::next::run(&root, feature.as_deref(), out, watch),
+        Commands::Focus => cmd::focus::run(&root, out),
+        Commands::ParallelWork => cmd::parallel_work::run(&root, out),
+        Commands::Feature { subcommand } => cmd::feature::run(&root, subcommand, out),
+        Commands::Backlog { subcommand } => cmd::backlog::run(&root, subcommand, out),
+        Commands::Artifact { subcommand } => cmd::artifact::run(&root, subcommand, out),
+        Commands::Task { subcommand } => cmd::task::run(&root, subcommand, out),
+        Commands::Comment { subcommand } => cmd::comment::run(&root, subcommand, out),
+        Commands::Milestone { subcommand } => cmd::milestone::run(&root, subcommand, out),
+        Commands::Ponder { subcommand } => cmd::ponder::run(&root, subcommand, out),
+        Commands::Investigate { subcommand } => cmd::investigate::run(&root, subcommand, out),
+        Commands::Spike { subcommand } => cmd::spike::run(&root, subcommand, out),
+        Commands::Knowledge { subcommand } => cmd::knowledge::run(&root, subcommand, out),
+        Commands::Platform { subcommand } => cmd::platform::run(&root, subcommand, out),
+        Commands::Project { subcommand } => cmd::project::run(&root, subcommand, out),
+        Commands::Query { subcommand } => cmd::query::run(&root, subcommand, out),
+        Commands::Config { subcommand } => cmd::config::run(&root, subcommand, out),
+        Commands::Score { subcommand } => cmd::score::run(&root, subcommand, out),
+        Commands::Secrets { subcommand } => cmd::secrets::run(&root, subcommand, out),
+        Commands::Auth { subcommand } => cmd::auth::run(&root, subcommand, out),
+        Commands::Escalate { subcommand } => cmd::escalate::run(&root, subcommand, out),
+        Commands::Thread { subcommand } => cmd::thread::run(&root, subcommand, out),
         Commands::Tool { cmd } => cmd::tool::run(cmd, &root),
         Commands::Orchestrate {
             tick_rate,
@@ -368,15 +411,19 @@ fn main() {
                 suffix,
                 count,
             }) => cmd::changelog::reassign(&root, &from, &suffix, count),
-            None => cmd::changelog::run(&root, &since, limit, cli.json),
+            None => cmd::changelog::run(&root, &since, limit, out),
         },
-        Commands::Commit { message } => cmd::commit::run(&root, message.as_deref(), cli.json),
-        Commands::Merge { slug } => cmd::merge::run(&root, &slug, cli.json),
+        Commands::Commit { message } => cmd::commit::run(&root, message.as_deref(), out),
+        Commands::Merge {
+            slug,
+            no_verify,
+            force,
+        } => cmd::merge::run(&root, &slug, no_verify, force, out),
         Commands::Archive { slug } => {
-            cmd::feature::run(&root, FeatureSubcommand::Archive { slug }, cli.json)
+            cmd::feature::run(&root, FeatureSubcommand::Archive { slug }, out)
         }
         Commands::Mcp => cmd::mcp::run(&root),
-        Commands::Agent { subcommand } => cmd::agent::run(&root, subcommand, cli.json),
+        Commands::Agent { subcommand } => cmd::agent::run(&root, subcommand, out),
         Commands::Ui {
             port,
             no_open,
@@ -384,6 +431,7 @@ fn main() {
             tick_rate,
             run_actions,
             debug: _,
+            http,
             subcommand,
         } => cmd::ui::run(
             &root,
@@ -393,6 +441,7 @@ fn main() {
             no_tunnel,
             tick_rate,
             run_actions,
+            http,
         ),
     };
 
@@ -1,5 +1,114 @@
 use serde::Serialize;
 
+/// Renders a command result in one output format.
+///
+/// Structured formats return the text to print; [`Table`] returns `None`,
+/// leaving the command to print its own human-readable view.
+pub trait Output {
+    fn render(&self, value: &serde_json::Value) -> anyhow::Result<Option<String>>;
+}
+
+/// Human-readable output, printed by each command.
+pub struct Table;
+
+/// One pretty-printed JSON document.
+pub struct Json;
+
+/// Compact JSON, one line per element when the result is a list.
+pub struct Jsonl;
+
+impl Output for Table {
+    fn render(&self, _value: &serde_json::Value) -> anyhow::Result<Option<String>> {
+        Ok(None)
+    }
+}
+
+impl Output for Json {
+    fn render(&self, value: &serde_json::Value) -> anyhow::Result<Option<String>> {
+        Ok(Some(serde_json::to_string_pretty(value)?))
+    }
+}
+
+impl Output for Jsonl {
+    fn render(&self, value: &serde_json::Value) -> anyhow::Result<Option<String>> {
+        let lines = match value {
+            serde_json::Value::Array(items) => items
+                .iter()
+                .map(serde_json::to_string)
+                .collect::<Result<Vec<_>, _>>()?,
+            other => vec![serde_json::to_string(other)?],
+        };
+        Ok(Some(lines.join("\n")))
+    }
+}
+
+/// The output format for a whole invocation, chosen once from the global
+/// `--format` and `--json` flags and passed to every command.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum OutputFormat {
+    #[default]
+    Table,
+    Json,
+    Jsonl,
+}
+
+impl OutputFormat {
+    /// `--format` wins; otherwise `--json` selects [`OutputFormat::Json`].
+    pub fn resolve(json: bool, format: Option<OutputFormat>) -> Self {
+        match format {
+            Some(format) => format,
+            None if json => Self::Json,
+            None => Self::Table,
+        }
+    }
+
+    /// True for machine-readable formats. Commands print results through
+    /// [`OutputFormat::emit`]; this only gates progress notes and prompts.
+    pub fn is_structured(self) -> bool {
+        self != Self::Table
+    }
+
+    /// Apply a subcommand's own `--json` flag on top of the global choice.
+    pub fn or_json(self, json: bool) -> Self {
+        if json && !self.is_structured() {
+            Self::Json
+        } else {
+            self
+        }
+    }
+
+    fn output(self) -> &'static dyn Output {
+        match self {
+            Self::Table => &Table,
+            Self::Json => &Json,
+            Self::Jsonl => &Jsonl,
+        }
+    }
+
+    /// Print `value` in this format, or call `table` for the human view.
+    pub fn emit<T: Serialize>(
+        self,
+        value: &T,
+        table: impl FnOnce() -> anyhow::Result<()>,
+    ) -> anyhow::Result<()> {
+        match self.output().render(&serde_json::to_value(value)?)? {
+            // An empty JSONL list renders as no lines at all.
+            Some(text) if text.is_empty() => Ok(()),
+            Some(text) => {
+                println!("{text}");
+                Ok(())
+            }
+            None => table(),
+        }
+    }
+
+    /// Print `value` in this format. A table has no generic layout, so it
+    /// falls back to pretty JSON; callers print their own table instead.
+    pub fn print<T: Serialize>(self, value: &T) -> anyhow::Result<()> {
+        self.emit(value, || print_json(value))
+    }
+}
+
 pub fn print_json<T: Serialize>(value: &T) -> anyhow::Result<()> {
     let json = serde_json::to_string_pretty(value)?;
     println!("{}", json);
@@ -42,3 +151,41 @@ pub fn print_table(headers: &[&str], rows: Vec<Vec<String>>) {
         println!("{}", cells.join("  "));
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_flag_wins_over_json() {
+        assert_eq!(OutputFormat::resolve(false, None), OutputFormat::Table);
+        assert_eq!(OutputFormat::resolve(true, None), OutputFormat::Json);
+        assert_eq!(
+            OutputFormat::resolve(true, Some(OutputFormat::Jsonl)),
+            OutputFormat::Jsonl
+        );
+    }
+
+    #[test]
+    fn jsonl_puts_each_list_item_on_its_own_line() {
+        let list = serde_json::json!([{ "slug": "a" }, { "slug": "b" }]);
+        let text = Jsonl.render(&list).unwrap().unwrap();
+        assert_eq!(text, "{\"slug\":\"a\"}\n{\"slug\":\"b\"}");
+
+        let one = serde_json::json!({ "slug": "a", "phase": "draft" });
+        let text = Jsonl.render(&one).unwrap().unwrap();
+        assert_eq!(text.lines().count(), 1);
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(&text).unwrap(),
+            one
+        );
+    }
+
+    #[test]
+    fn table_leaves_rendering_to_the_command() {
+        let value = serde_json::json!({ "slug": "a" });
+        assert!(Table.render(&value).unwrap().is_none());
+        let pretty = Json.render(&value).unwrap().unwrap();
+        assert_eq!(pretty, serde_json::to_string_pretty(&value).unwrap());
+    }
+}
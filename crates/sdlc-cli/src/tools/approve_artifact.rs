@@ -43,7 +43,7 @@ impl SdlcTool for ApproveArtifactTool {
 
         let mut feature = Feature::load(root, slug).map_err(|e| e.to_string())?;
         feature
-            .approve_artifact(artifact_type, None)
+            .approve_artifact(root, artifact_type, None)
             .map_err(|e| e.to_string())?;
         feature.save(root).map_err(|e| e.to_string())?;
 
@@ -42,12 +42,7 @@ impl SdlcTool for GetDirectiveTool {
         let feature = Feature::load(root, slug).map_err(|e| e.to_string())?;
 
         let classifier = Classifier::new(default_rules());
-        let ctx = EvalContext {
-            feature: &feature,
-            state: &state,
-            config: &config,
-            root,
-        };
+        let ctx = EvalContext::new(&feature, &state, &config, root);
         let classification = classifier.classify(&ctx);
 
         serde_json::to_value(&classification).map_err(|e| e.to_string())
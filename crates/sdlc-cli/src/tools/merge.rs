@@ -30,7 +30,8 @@ impl SdlcTool for MergeTool {
             .as_str()
             .ok_or_else(|| "missing required argument: slug".to_string())?;
 
-        crate::cmd::merge::run(root, slug, true).map_err(|e| e.to_string())?;
+        crate::cmd::merge::run(root, slug, false, false, crate::output::OutputFormat::Json)
+            .map_err(|e| format!("{e:#}"))?;
 
         Ok(serde_json::json!({
             "slug": slug,
@@ -9,6 +9,8 @@ pub mod merge;
 pub mod ponder_chat;
 pub mod prepare;
 pub mod project_phase;
+pub mod project_tool;
+pub mod query;
 pub mod reject_artifact;
 pub mod repair_artifact;
 pub mod run_wave;
@@ -36,5 +38,16 @@ pub fn all_tools() -> Vec<Box<dyn SdlcTool>> {
         Box::new(prepare::PrepareTool),
         Box::new(run_wave::RunWaveTool),
         Box::new(ponder_chat::PonderChatTool),
+        Box::new(query::QueryTool),
     ]
 }
+
+/// The built-in tools plus the project's custom tools from `.sdlc/tools/`
+/// (see [`project_tool`]).
+pub fn all_tools_for(root: &Path) -> Vec<Box<dyn SdlcTool>> {
+    let mut tools = all_tools();
+    for tool in project_tool::discover(root) {
+        tools.push(Box::new(tool));
+    }
+    tools
+}
@@ -0,0 +1,241 @@
+use super::SdlcTool;
+use sdlc_core::tool_runner::{self, ToolMeta, ToolRunResult};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Starts a project tool script in one of its modes.
+pub trait ToolLauncher: Send + Sync {
+    /// The tool's `--meta` output.
+    fn meta(&self, script: &Path, root: &Path) -> sdlc_core::Result<ToolMeta>;
+    /// The tool's `--run` result for `input` (JSON).
+    fn run(&self, script: &Path, input: &str, root: &Path) -> sdlc_core::Result<ToolRunResult>;
+}
+
+/// Spawns the script with the detected JavaScript runtime.
+pub struct Subprocess;
+
+impl ToolLauncher for Subprocess {
+    fn meta(&self, script: &Path, root: &Path) -> sdlc_core::Result<ToolMeta> {
+        let stdout = tool_runner::run_tool(script, "--meta", None, root, None)?;
+        tool_runner::parse_tool_meta(&stdout)
+    }
+
+    fn run(&self, script: &Path, input: &str, root: &Path) -> sdlc_core::Result<ToolRunResult> {
+        tool_runner::run_tool_result(script, "--run", Some(input), root, None)
+    }
+}
+
+/// A custom tool from `.sdlc/tools/<name>/tool.ts`, exposed over MCP as
+/// `sdlc_tool_<name>`. Its `--meta` is read once, at discovery; each call
+/// spawns only `--run`.
+pub struct ProjectTool {
+    mcp_name: String,
+    description: String,
+    schema: serde_json::Value,
+    script: PathBuf,
+    launcher: Arc<dyn ToolLauncher>,
+}
+
+/// Every project tool whose `--meta` succeeds, by name. Tools that fail it
+/// are skipped with a warning on stderr (stdout carries the MCP protocol).
+pub fn discover(root: &Path) -> Vec<ProjectTool> {
+    discover_with(root, Arc::new(Subprocess))
+}
+
+pub fn discover_with(root: &Path, launcher: Arc<dyn ToolLauncher>) -> Vec<ProjectTool> {
+    let Ok(entries) = std::fs::read_dir(sdlc_core::paths::tools_dir(root)) else {
+        return Vec::new();
+    };
+    let mut names: Vec<String> = entries
+        .filter_map(|e| e.ok())
+        .filter_map(|e| e.file_name().into_string().ok())
+        // `_shared/` holds helpers, not a tool.
+        .filter(|name| !name.starts_with('_'))
+        .filter(|name| sdlc_core::paths::tool_script(root, name).is_file())
+        .collect();
+    names.sort();
+
+    names
+        .into_iter()
+        .filter_map(|name| {
+            let script = sdlc_core::paths::tool_script(root, &name);
+            let meta = match launcher.meta(&script, root) {
+                Ok(meta) => meta,
+                Err(e) => {
+                    eprintln!("[sdlc-mcp] skipping project tool '{name}': --meta failed: {e}");
+                    return None;
+                }
+            };
+            let description = if meta.description.is_empty() {
+                meta.display_name
+            } else {
+                meta.description
+            };
+            let schema = if meta.input_schema.is_object() {
+                meta.input_schema
+            } else {
+                serde_json::json!({ "type": "object" })
+            };
+            Some(ProjectTool {
+                mcp_name: format!("sdlc_tool_{}", name.replace('-', "_")),
+                description,
+                schema,
+                script,
+                launcher: launcher.clone(),
+            })
+        })
+        .collect()
+}
+
+impl SdlcTool for ProjectTool {
+    fn name(&self) -> &str {
+        &self.mcp_name
+    }
+
+    fn description(&self) -> &str {
+        &self.description
+    }
+
+    fn schema(&self) -> serde_json::Value {
+        self.schema.clone()
+    }
+
+    fn call(&self, args: serde_json::Value, root: &Path) -> Result<serde_json::Value, String> {
+        let input = if args.is_null() {
+            serde_json::json!({})
+        } else {
+            args
+        };
+        let result = self
+            .launcher
+            .run(&self.script, &input.to_string(), root)
+            .map_err(|e| e.to_string())?;
+        if result.ok {
+            Ok(result.data.unwrap_or(serde_json::Value::Null))
+        } else {
+            Err(result
+                .error
+                .unwrap_or_else(|| "tool reported ok: false".to_string()))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cmd::mcp::{handle_request, JsonRpcRequest};
+    use tempfile::TempDir;
+
+    /// Answers `--meta` from a fixed description and echoes `--run` input
+    /// back as data, counting how often each mode is started.
+    #[derive(Default)]
+    struct EchoLauncher {
+        metas: std::sync::atomic::AtomicUsize,
+    }
+
+    impl ToolLauncher for EchoLauncher {
+        fn meta(&self, script: &Path, _root: &Path) -> sdlc_core::Result<ToolMeta> {
+            self.metas.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            let name = script.parent().unwrap().file_name().unwrap();
+            Ok(ToolMeta {
+                name: name.to_string_lossy().into_owned(),
+                description: "Echo the input back".into(),
+                input_schema: serde_json::json!({
+                    "type": "object",
+                    "properties": { "message": { "type": "string" } }
+                }),
+                ..Default::default()
+            })
+        }
+
+        fn run(
+            &self,
+            _script: &Path,
+            input: &str,
+            _root: &Path,
+        ) -> sdlc_core::Result<ToolRunResult> {
+            let input: serde_json::Value = serde_json::from_str(input).unwrap();
+            Ok(ToolRunResult {
+                ok: input["message"] != "fail",
+                data: Some(serde_json::json!({ "echo": input["message"] })),
+                error: Some("asked to fail".into()).filter(|_| input["message"] == "fail"),
+                duration_ms: Some(1),
+            })
+        }
+    }
+
+    fn request(method: &str, params: serde_json::Value) -> JsonRpcRequest {
+        JsonRpcRequest {
+            jsonrpc: "2.0".into(),
+            id: Some(1.into()),
+            method: method.into(),
+            params: Some(params),
+        }
+    }
+
+    #[test]
+    fn custom_tool_is_listed_and_round_trips_a_run() {
+        let dir = TempDir::new().unwrap();
+        let script = sdlc_core::paths::tool_script(dir.path(), "echo-back");
+        std::fs::create_dir_all(script.parent().unwrap()).unwrap();
+        std::fs::write(&script, "// fixture").unwrap();
+        std::fs::create_dir_all(sdlc_core::paths::tools_dir(dir.path()).join("_shared")).unwrap();
+
+        let launcher = Arc::new(EchoLauncher::default());
+        let mut tools = crate::tools::all_tools();
+        for tool in discover_with(dir.path(), launcher.clone()) {
+            tools.push(Box::new(tool));
+        }
+
+        let resp = handle_request(
+            &request("tools/list", serde_json::json!({})),
+            &tools,
+            dir.path(),
+        );
+        let result = resp.result.unwrap();
+        let listed = result["tools"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|t| t["name"] == "sdlc_tool_echo_back")
+            .expect("custom tool listed");
+        assert_eq!(listed["description"], "Echo the input back");
+        assert_eq!(
+            listed["inputSchema"]["properties"]["message"]["type"],
+            "string"
+        );
+
+        let call = |message: &str| {
+            let resp = handle_request(
+                &request(
+                    "tools/call",
+                    serde_json::json!({
+                        "name": "sdlc_tool_echo_back",
+                        "arguments": { "message": message }
+                    }),
+                ),
+                &tools,
+                dir.path(),
+            );
+            resp.result.unwrap()
+        };
+        let ok = call("hello");
+        assert_eq!(ok["isError"], false);
+        let data: serde_json::Value =
+            serde_json::from_str(ok["content"][0]["text"].as_str().unwrap()).unwrap();
+        assert_eq!(data, serde_json::json!({ "echo": "hello" }));
+
+        let failed = call("fail");
+        assert_eq!(failed["isError"], true);
+        assert_eq!(failed["content"][0]["text"], "asked to fail");
+
+        // Meta was read once, at discovery, not per call.
+        assert_eq!(launcher.metas.load(std::sync::atomic::Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn missing_tools_dir_discovers_nothing() {
+        let dir = TempDir::new().unwrap();
+        assert!(discover_with(dir.path(), Arc::new(EchoLauncher::default())).is_empty());
+    }
+}
@@ -0,0 +1,174 @@
+use super::SdlcTool;
+use sdlc_core::query;
+use std::path::Path;
+
+const DEFAULT_SEARCH_LIMIT: usize = 10;
+
+pub struct QueryTool;
+
+impl SdlcTool for QueryTool {
+    fn name(&self) -> &str {
+        "sdlc_query"
+    }
+
+    fn description(&self) -> &str {
+        "Query project features: blocked, ready to work on, awaiting approval, or full-text search"
+    }
+
+    fn schema(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "operation": {
+                    "type": "string",
+                    "enum": ["blocked", "ready", "needs_approval", "search"],
+                    "description": "Which query to run"
+                },
+                "phase": {
+                    "type": "string",
+                    "description": "ready only: restrict to features in this phase (e.g. draft, planned)"
+                },
+                "query": {
+                    "type": "string",
+                    "description": "search only: query string (AND/OR/NOT, field:value, \"phrases\", prefix*)"
+                },
+                "limit": {
+                    "type": "integer",
+                    "description": "search only: maximum number of results (default 10)"
+                }
+            },
+            "required": ["operation"]
+        })
+    }
+
+    fn call(&self, args: serde_json::Value, root: &Path) -> Result<serde_json::Value, String> {
+        let operation = args["operation"]
+            .as_str()
+            .ok_or_else(|| "missing required argument: operation".to_string())?;
+
+        let result = match operation {
+            "blocked" => serde_json::to_value(query::blocked(root).map_err(|e| e.to_string())?),
+            "ready" => serde_json::to_value(
+                query::ready(root, args["phase"].as_str()).map_err(|e| e.to_string())?,
+            ),
+            "needs_approval" => {
+                serde_json::to_value(query::needs_approval(root).map_err(|e| e.to_string())?)
+            }
+            "search" => {
+                let query_str = args["query"]
+                    .as_str()
+                    .ok_or_else(|| "missing required argument: query".to_string())?;
+                let limit = args["limit"]
+                    .as_u64()
+                    .map_or(DEFAULT_SEARCH_LIMIT, |n| n as usize);
+                serde_json::to_value(
+                    query::search(root, query_str, limit).map_err(|e| e.to_string())?,
+                )
+            }
+            other => return Err(format!(
+                "unknown operation '{other}'; expected blocked, ready, needs_approval, or search"
+            )),
+        };
+        result.map_err(|e| e.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sdlc_core::{config::Config, feature::Feature, state::State, types::ArtifactType};
+    use tempfile::TempDir;
+
+    /// `auth` is blocked, `billing` has a drafted spec awaiting approval, and
+    /// `search-ui` is a fresh draft ready for its spec.
+    fn fixture() -> TempDir {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path();
+        Config::new("test").save(root).unwrap();
+        State::new("test").save(root).unwrap();
+
+        let mut auth = Feature::create(root, "auth", "Auth").unwrap();
+        auth.blockers.push("waiting on credentials".to_string());
+        auth.save(root).unwrap();
+
+        let mut billing = Feature::create(root, "billing", "Billing").unwrap();
+        std::fs::write(root.join(".sdlc/features/billing/spec.md"), "# Spec\n").unwrap();
+        billing.mark_artifact_draft(ArtifactType::Spec).unwrap();
+        billing.save(root).unwrap();
+
+        Feature::create(root, "search-ui", "Search UI").unwrap();
+        dir
+    }
+
+    fn slugs(value: &serde_json::Value) -> Vec<&str> {
+        value
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|v| v["slug"].as_str().unwrap())
+            .collect()
+    }
+
+    fn call(root: &Path, args: serde_json::Value) -> serde_json::Value {
+        QueryTool.call(args, root).unwrap()
+    }
+
+    #[test]
+    fn blocked_lists_blockers() {
+        let dir = fixture();
+        let result = call(dir.path(), serde_json::json!({"operation": "blocked"}));
+        assert_eq!(slugs(&result), vec!["auth"]);
+        assert_eq!(result[0]["blockers"][0], "waiting on credentials");
+    }
+
+    #[test]
+    fn ready_excludes_blocked() {
+        let dir = fixture();
+        let result = call(dir.path(), serde_json::json!({"operation": "ready"}));
+        assert_eq!(slugs(&result), vec!["billing", "search-ui"]);
+        assert_eq!(result[1]["phase"], "draft");
+        assert_eq!(result[1]["action"], "create_spec");
+
+        let result = call(
+            dir.path(),
+            serde_json::json!({"operation": "ready", "phase": "planned"}),
+        );
+        assert!(result.as_array().unwrap().is_empty());
+    }
+
+    #[test]
+    fn needs_approval_lists_drafted_spec() {
+        let dir = fixture();
+        let result = call(
+            dir.path(),
+            serde_json::json!({"operation": "needs_approval"}),
+        );
+        assert_eq!(slugs(&result), vec!["billing"]);
+        assert_eq!(result[0]["action"], "approve_spec");
+    }
+
+    #[test]
+    fn search_finds_by_title() {
+        let dir = fixture();
+        let result = call(
+            dir.path(),
+            serde_json::json!({"operation": "search", "query": "billing", "limit": 5}),
+        );
+        assert_eq!(slugs(&result), vec!["billing"]);
+        assert_eq!(result[0]["kind"], "feature");
+
+        let err = QueryTool
+            .call(serde_json::json!({"operation": "search"}), dir.path())
+            .unwrap_err();
+        assert!(err.contains("missing required argument: query"));
+    }
+
+    #[test]
+    fn unknown_operation_errors() {
+        let dir = fixture();
+        let err = QueryTool
+            .call(serde_json::json!({"operation": "frobnicate"}), dir.path())
+            .unwrap_err();
+        assert!(err.contains("unknown operation 'frobnicate'"));
+    }
+}
@@ -50,7 +50,7 @@ impl SdlcTool for RejectArtifactTool {
 
         let mut feature = Feature::load(root, slug).map_err(|e| e.to_string())?;
         feature
-            .reject_artifact(artifact_type, Some(reason.to_string()))
+            .reject_artifact(root, artifact_type, Some(reason.to_string()))
             .map_err(|e| e.to_string())?;
         feature.save(root).map_err(|e| e.to_string())?;
 
@@ -85,12 +85,7 @@ impl SdlcTool for RunWaveTool {
             .filter_map(|item| {
                 let feature = sdlc_core::feature::Feature::load(root, &item.slug).ok()?;
 
-                let ctx = sdlc_core::classifier::EvalContext {
-                    feature: &feature,
-                    state: &state,
-                    config: &config,
-                    root,
-                };
+                let ctx = sdlc_core::classifier::EvalContext::new(&feature, &state, &config, root);
                 let classification = classifier.classify(&ctx);
 
                 // Skip terminal states
@@ -105,6 +100,7 @@ impl SdlcTool for RunWaveTool {
 
                 let mcp_server = claude_agent::McpServerConfig {
                     name: "sdlc".into(),
+                    transport: claude_agent::McpTransport::Stdio,
                     command: sdlc_bin.to_string_lossy().into_owned(),
                     args: vec!["mcp".into()],
                     env: std::collections::HashMap::new(),
@@ -120,6 +116,7 @@ impl SdlcTool for RunWaveTool {
                     "mcp__sdlc__sdlc_add_comment".into(),
                     "mcp__sdlc__sdlc_project_phase".into(),
                     "mcp__sdlc__sdlc_prepare".into(),
+                    "mcp__sdlc__sdlc_query".into(),
                 ];
 
                 let opts = claude_agent::QueryOptions {
@@ -132,24 +129,34 @@ impl SdlcTool for RunWaveTool {
                     ..Default::default()
                 };
 
-                let directive_json = serde_json::to_string_pretty(&classification)
-                    .unwrap_or_else(|_| format!("{classification:?}"));
+                let template = sdlc_core::prompt_template::template_for(classification.action);
+                let vars =
+                    sdlc_core::prompt_template::PromptVars::for_directive(root, &classification);
+                let prompt = match sdlc_core::prompt_template::render_named(root, template, &vars) {
+                    Ok(prompt) => prompt,
+                    Err(e) => {
+                        return Some(Err(format!(
+                            "failed to render prompt template '{template}' for '{}': {e}",
+                            item.slug
+                        )))
+                    }
+                };
 
                 let run_cfg = claude_agent::runner::RunConfig {
                     system_prompt: Some(build_system_prompt()),
-                    prompt: format!(
-                        "Drive feature '{}' forward using the sdlc state machine tools.\n\n\
-                         Current directive:\n{directive_json}\n\n\
-                         Execute the action, verify state advanced with sdlc_get_directive, then loop \
-                         until done or a human gate (wait_for_approval / unblock_dependency).",
-                        item.slug
-                    ),
+                    prompt,
                     opts,
+                    auto_compact: true,
+                    trace_messages: false,
+                    max_cost_usd: None,
+                    max_total_tokens: None,
+                    max_consecutive_failures:
+                        claude_agent::runner::DEFAULT_MAX_CONSECUTIVE_FAILURES,
                 };
 
-                Some((item.slug.clone(), run_cfg))
+                Some(Ok((item.slug.clone(), run_cfg)))
             })
-            .collect();
+            .collect::<Result<_, String>>()?;
 
         if configs.is_empty() {
             return Ok(serde_json::json!({
@@ -190,6 +197,7 @@ impl SdlcTool for RunWaveTool {
                     "result_text": run_result.result_text,
                     "turns": run_result.num_turns,
                     "cost_usd": run_result.total_cost_usd,
+                    "tool_calls": run_result.tool_calls,
                 }),
                 Err(err) => serde_json::json!({
                     "slug": slug,
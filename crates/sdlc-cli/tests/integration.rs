@@ -412,6 +412,116 @@ fn feature_create_and_list() {
         .stdout(predicate::str::contains("auth-login"));
 }
 
+#[test]
+fn feature_create_from_template_seeds_tasks() {
+    let dir = TempDir::new().unwrap();
+    init_project(&dir);
+
+    let output = sdlc(&dir)
+        .args([
+            "--json",
+            "feature",
+            "create",
+            "orders-api",
+            "--template",
+            "api-endpoint",
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let feature: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(feature["description"], "Add an HTTP API endpoint");
+    let tasks = feature["tasks"].as_array().unwrap();
+    assert_eq!(tasks.len(), 5);
+    assert_eq!(tasks[0]["id"], "T1");
+    assert_eq!(tasks[0]["title"], "Define request and response types");
+
+    // Project-local templates are picked up by name.
+    let templates = dir.path().join(".sdlc/templates");
+    std::fs::create_dir_all(&templates).unwrap();
+    std::fs::write(
+        templates.join("ui-component.yaml"),
+        "description: New UI component\ntasks:\n  - Build the component\n  - Add a story\n",
+    )
+    .unwrap();
+    sdlc(&dir)
+        .args(["feature", "create", "avatar", "--template", "ui-component"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Seeded 2 task(s)"));
+}
+
+#[test]
+fn feature_create_unknown_template_fails() {
+    let dir = TempDir::new().unwrap();
+    init_project(&dir);
+
+    sdlc(&dir)
+        .args(["feature", "create", "auth", "--template", "nope"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "unknown feature template 'nope' (available: api-endpoint, migration)",
+        ));
+    assert!(!dir.path().join(".sdlc/features/auth").exists());
+}
+
+#[test]
+fn project_history_filters_by_feature() {
+    let dir = TempDir::new().unwrap();
+    init_project(&dir);
+
+    for slug in ["auth", "billing"] {
+        sdlc(&dir)
+            .args(["feature", "create", slug])
+            .assert()
+            .success();
+        sdlc(&dir)
+            .args(["--actor", "ci-bot", "task", "add", slug, "First task"])
+            .assert()
+            .success();
+    }
+
+    let output = sdlc(&dir)
+        .args(["--json", "project", "history", "--feature", "billing"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let entries: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let entries = entries.as_array().unwrap();
+    assert_eq!(entries.len(), 2);
+    assert!(entries.iter().all(|e| e["feature"] == "billing"));
+    assert_eq!(entries[0]["action"], "created");
+    assert_eq!(entries[1]["action"], "task_added");
+    assert_eq!(entries[1]["actor"], "ci-bot");
+
+    sdlc(&dir)
+        .args(["project", "history"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("auth"))
+        .stdout(predicate::str::contains("billing"));
+}
+
+#[test]
+fn create_without_slug_derives_it_from_title() {
+    let dir = TempDir::new().unwrap();
+    init_project(&dir);
+
+    for kind in ["feature", "milestone", "ponder"] {
+        sdlc(&dir)
+            .args([kind, "create", "--title", "Café: Auth & Login!"])
+            .assert()
+            .success();
+    }
+    let sdlc_dir = dir.path().join(".sdlc");
+    assert!(sdlc_dir.join("features/cafe-auth-login").is_dir());
+    assert!(sdlc_dir.join("milestones/cafe-auth-login").is_dir());
+    assert!(sdlc_dir.join("roadmap/cafe-auth-login").is_dir());
+
+    sdlc(&dir).args(["feature", "create"]).assert().failure();
+}
+
 #[test]
 fn feature_create_invalid_slug_fails() {
     let dir = TempDir::new().unwrap();
@@ -511,6 +621,68 @@ fn feature_update_dependencies_and_clear() {
     );
 }
 
+#[test]
+fn feature_show_with_artifacts_inlines_content_and_truncates() {
+    let dir = TempDir::new().unwrap();
+    init_project(&dir);
+    sdlc(&dir)
+        .args(["feature", "create", "auth", "--title", "Auth"])
+        .assert()
+        .success();
+
+    let feature_dir = dir.path().join(".sdlc/features/auth");
+    std::fs::write(feature_dir.join("spec.md"), "# Spec\nLogin flow.\n").unwrap();
+    std::fs::write(feature_dir.join("design.md"), "d".repeat(100)).unwrap();
+    sdlc(&dir)
+        .args(["artifact", "draft", "auth", "spec"])
+        .assert()
+        .success();
+    sdlc(&dir)
+        .args(["artifact", "approve", "auth", "spec"])
+        .assert()
+        .success();
+
+    let output = sdlc(&dir)
+        .args([
+            "feature",
+            "show",
+            "auth",
+            "--with-artifacts",
+            "--max-artifact-bytes",
+            "40",
+            "--json",
+        ])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let json: serde_json::Value = serde_json::from_slice(&output).unwrap();
+    let artifacts = json["artifacts"].as_array().unwrap();
+    let find = |ty: &str| {
+        artifacts
+            .iter()
+            .find(|a| a["artifact_type"] == ty)
+            .unwrap()
+            .clone()
+    };
+
+    let spec = find("spec");
+    assert_eq!(spec["status"], "approved");
+    assert_eq!(spec["content"], "# Spec\nLogin flow.\n");
+    assert_eq!(spec["truncated"], false);
+
+    let design = find("design");
+    assert_eq!(design["truncated"], true);
+    assert_eq!(design["content_bytes"], 100);
+    let content = design["content"].as_str().unwrap();
+    assert!(content.starts_with(&"d".repeat(40)));
+    assert!(content.contains("truncated: 40 of 100 bytes shown"));
+
+    // Artifacts without a file carry no content.
+    assert!(find("tasks").get("content").is_none());
+}
+
 // ---------------------------------------------------------------------------
 // sdlc next
 // ---------------------------------------------------------------------------
@@ -605,6 +777,54 @@ fn approve_spec_enables_transition_to_specified() {
         .stdout(predicate::str::contains("specified"));
 }
 
+#[test]
+fn lightweight_feature_skips_to_implementation_after_spec() {
+    let dir = TempDir::new().unwrap();
+    init_project(&dir);
+
+    sdlc(&dir)
+        .args([
+            "feature",
+            "create",
+            "docs-refresh",
+            "--track",
+            "lightweight",
+        ])
+        .assert()
+        .success();
+    sdlc(&dir)
+        .args(["artifact", "draft", "docs-refresh", "spec"])
+        .assert()
+        .success();
+    sdlc(&dir)
+        .args(["artifact", "approve", "docs-refresh", "spec"])
+        .assert()
+        .success();
+
+    let output = sdlc(&dir)
+        .args(["feature", "show", "docs-refresh", "--json"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let json: serde_json::Value = serde_json::from_slice(&output).unwrap();
+    assert_eq!(json["track"], "lightweight");
+    assert_eq!(json["phase"], "implementation");
+}
+
+#[test]
+fn feature_create_invalid_track_fails() {
+    let dir = TempDir::new().unwrap();
+    init_project(&dir);
+
+    sdlc(&dir)
+        .args(["feature", "create", "auth", "--track", "express"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("invalid feature track"));
+}
+
 // ---------------------------------------------------------------------------
 // sdlc task
 // ---------------------------------------------------------------------------
@@ -663,6 +883,67 @@ fn state_shows_features() {
         .stdout(predicate::str::contains("my-feat"));
 }
 
+#[test]
+fn state_jsonl_matches_json() {
+    let dir = TempDir::new().unwrap();
+    init_project(&dir);
+    for slug in ["auth", "billing", "search"] {
+        sdlc(&dir)
+            .args(["feature", "create", slug])
+            .assert()
+            .success();
+    }
+    sdlc(&dir)
+        .args(["milestone", "create", "v2", "--title", "v2"])
+        .assert()
+        .success();
+    sdlc(&dir)
+        .args(["milestone", "add-feature", "v2", "auth"])
+        .assert()
+        .success();
+
+    let out = sdlc(&dir).args(["--json", "state"]).output().unwrap();
+    assert!(out.status.success());
+    let whole: serde_json::Value = serde_json::from_slice(&out.stdout).unwrap();
+
+    let out = sdlc(&dir).args(["state", "--jsonl"]).output().unwrap();
+    assert!(out.status.success());
+    let mut lines: Vec<serde_json::Value> = String::from_utf8(out.stdout)
+        .unwrap()
+        .lines()
+        .map(|l| serde_json::from_str(l).unwrap())
+        .collect();
+
+    let header = lines.remove(0);
+    assert_eq!(header["type"], "header");
+    assert_eq!(header["project"], whole["project"]);
+    assert_eq!(header["feature_count"], whole["feature_count"]);
+    assert_eq!(header["milestone_count"], 1);
+    assert_eq!(header["last_action"], whole["last_action"]);
+
+    let take = |kind: &str, lines: &[serde_json::Value]| -> Vec<serde_json::Value> {
+        let mut objs: Vec<serde_json::Value> = lines
+            .iter()
+            .filter(|l| l["type"] == kind)
+            .cloned()
+            .map(|mut l| {
+                l.as_object_mut().unwrap().remove("type");
+                l
+            })
+            .collect();
+        objs.sort_by_key(|o| o["slug"].to_string());
+        objs
+    };
+    let sorted = |v: &serde_json::Value| {
+        let mut objs = v.as_array().unwrap().clone();
+        objs.sort_by_key(|o| o["slug"].to_string());
+        objs
+    };
+    assert_eq!(take("milestone", &lines), sorted(&whole["milestones"]));
+    assert_eq!(take("feature", &lines), sorted(&whole["unassigned"]));
+    assert_eq!(lines.len(), 1 + 2);
+}
+
 // ---------------------------------------------------------------------------
 // sdlc merge
 // ---------------------------------------------------------------------------
@@ -719,6 +1000,151 @@ fn merge_fails_when_feature_not_in_merge_phase() {
         .stderr(predicate::str::contains("move it to 'merge' first"));
 }
 
+fn enter_merge_phase(dir: &TempDir, slug: &str) {
+    sdlc(dir)
+        .args(["artifact", "approve", slug, "qa_results"])
+        .assert()
+        .success();
+    sdlc(dir)
+        .args(["feature", "transition", slug, "merge"])
+        .assert()
+        .success();
+}
+
+#[test]
+fn merge_blocked_until_dependencies_are_released() {
+    let dir = TempDir::new().unwrap();
+    init_project(&dir);
+    for slug in ["db", "api"] {
+        sdlc(&dir)
+            .args(["feature", "create", slug])
+            .assert()
+            .success();
+    }
+    sdlc(&dir)
+        .args(["feature", "update", "api", "--depends-on", "db"])
+        .assert()
+        .success();
+    sdlc(&dir)
+        .args(["feature", "transition", "db", "implementation"])
+        .assert()
+        .success();
+    enter_merge_phase(&dir, "api");
+
+    sdlc(&dir)
+        .args(["merge", "api"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("not yet released: db"))
+        .stderr(predicate::str::contains("--force"));
+
+    enter_merge_phase(&dir, "db");
+    sdlc(&dir).args(["merge", "db"]).assert().success();
+    sdlc(&dir).args(["merge", "api"]).assert().success();
+}
+
+#[test]
+fn merge_force_skips_the_dependency_check() {
+    let dir = TempDir::new().unwrap();
+    init_project(&dir);
+    for slug in ["db", "api"] {
+        sdlc(&dir)
+            .args(["feature", "create", slug])
+            .assert()
+            .success();
+    }
+    sdlc(&dir)
+        .args(["feature", "update", "api", "--depends-on", "db"])
+        .assert()
+        .success();
+    enter_merge_phase(&dir, "api");
+
+    sdlc(&dir)
+        .args(["merge", "api", "--force"])
+        .assert()
+        .success();
+}
+
+fn git(dir: &TempDir, args: &[&str]) {
+    let out = std::process::Command::new("git")
+        .args(args)
+        .current_dir(dir.path())
+        .output()
+        .unwrap();
+    assert!(out.status.success(), "git {args:?}: {out:?}");
+}
+
+/// An sdlc project in a git repo with feature `f` in the merge phase, checked
+/// out on branch `feat` which edits `app.txt`. When `conflict` is set, `main`
+/// edits the same line after the branch point.
+fn merge_ready_repo(conflict: bool) -> TempDir {
+    let dir = TempDir::new().unwrap();
+    git(&dir, &["init", "-q", "-b", "main"]);
+    git(&dir, &["config", "user.email", "test@example.com"]);
+    git(&dir, &["config", "user.name", "Test"]);
+    init_project(&dir);
+    sdlc(&dir)
+        .args(["feature", "create", "f"])
+        .assert()
+        .success();
+    sdlc(&dir)
+        .args(["artifact", "approve", "f", "qa_results"])
+        .assert()
+        .success();
+    sdlc(&dir)
+        .args(["feature", "transition", "f", "merge"])
+        .assert()
+        .success();
+    std::fs::write(dir.path().join("app.txt"), "one\n").unwrap();
+    git(&dir, &["add", "-A"]);
+    git(&dir, &["commit", "-q", "-m", "initial"]);
+
+    git(&dir, &["checkout", "-q", "-b", "feat"]);
+    std::fs::write(dir.path().join("app.txt"), "feat\n").unwrap();
+    git(&dir, &["commit", "-q", "-am", "feat edit"]);
+    if conflict {
+        git(&dir, &["checkout", "-q", "main"]);
+        std::fs::write(dir.path().join("app.txt"), "main\n").unwrap();
+        git(&dir, &["commit", "-q", "-am", "main edit"]);
+        git(&dir, &["checkout", "-q", "feat"]);
+    }
+    dir
+}
+
+#[test]
+fn merge_blocked_by_conflict_with_main() {
+    let dir = merge_ready_repo(true);
+
+    sdlc(&dir)
+        .args(["merge", "f"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--no-verify"))
+        .stderr(predicate::str::contains("app.txt"));
+    sdlc(&dir)
+        .args(["feature", "show", "f"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("released").not());
+
+    sdlc(&dir)
+        .args(["merge", "f", "--no-verify"])
+        .assert()
+        .success();
+}
+
+#[test]
+fn merge_proceeds_when_branch_merges_cleanly() {
+    let dir = merge_ready_repo(false);
+
+    sdlc(&dir).args(["merge", "f"]).assert().success();
+    sdlc(&dir)
+        .args(["feature", "show", "f"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("released"));
+}
+
 // ---------------------------------------------------------------------------
 // sdlc query
 // ---------------------------------------------------------------------------
@@ -775,6 +1201,135 @@ fn query_needs_approval_includes_wait_for_approval_actions() {
         ));
 }
 
+// ---------------------------------------------------------------------------
+// Output formats
+// ---------------------------------------------------------------------------
+
+#[test]
+fn feature_show_table_and_json_agree_on_key_fields() {
+    let dir = TempDir::new().unwrap();
+    init_project(&dir);
+    sdlc(&dir)
+        .args(["feature", "create", "auth", "--title", "Login flow"])
+        .assert()
+        .success();
+    sdlc(&dir)
+        .args(["task", "add", "auth", "Hash passwords"])
+        .assert()
+        .success();
+
+    let json_out = sdlc(&dir)
+        .args(["--format", "json", "feature", "show", "auth"])
+        .output()
+        .unwrap();
+    let feature: serde_json::Value = serde_json::from_slice(&json_out.stdout).unwrap();
+    let table = String::from_utf8(
+        sdlc(&dir)
+            .args(["feature", "show", "auth"])
+            .output()
+            .unwrap()
+            .stdout,
+    )
+    .unwrap();
+
+    for field in [
+        &feature["slug"],
+        &feature["title"],
+        &feature["phase"],
+        &feature["tasks"][0]["id"],
+        &feature["tasks"][0]["title"],
+    ] {
+        let value = field.as_str().unwrap();
+        assert!(table.contains(value), "table is missing {value}:\n{table}");
+    }
+
+    // `--json` is the same as `--format json`.
+    let legacy = sdlc(&dir)
+        .args(["--json", "feature", "show", "auth"])
+        .output()
+        .unwrap();
+    assert_eq!(legacy.stdout, json_out.stdout);
+}
+
+#[test]
+fn feature_list_jsonl_prints_one_feature_per_line() {
+    let dir = TempDir::new().unwrap();
+    init_project(&dir);
+    for slug in ["auth", "billing"] {
+        sdlc(&dir)
+            .args(["feature", "create", slug])
+            .assert()
+            .success();
+    }
+
+    let out = sdlc(&dir)
+        .args(["--format", "jsonl", "feature", "list"])
+        .output()
+        .unwrap();
+    assert!(out.status.success());
+    let slugs: Vec<String> = String::from_utf8(out.stdout)
+        .unwrap()
+        .lines()
+        .map(|l| {
+            let v: serde_json::Value = serde_json::from_str(l).unwrap();
+            v["slug"].as_str().unwrap().to_string()
+        })
+        .collect();
+    assert_eq!(slugs, ["auth", "billing"]);
+}
+
+// ---------------------------------------------------------------------------
+// sdlc task sync-from-artifact
+// ---------------------------------------------------------------------------
+
+#[test]
+fn task_sync_from_artifact_is_idempotent() {
+    let dir = TempDir::new().unwrap();
+    init_project(&dir);
+    sdlc(&dir)
+        .args(["feature", "create", "auth"])
+        .assert()
+        .success();
+    sdlc(&dir)
+        .args(["task", "sync-from-artifact", "auth"])
+        .assert()
+        .failure();
+
+    sdlc(&dir)
+        .args(["task", "add", "auth", "Add login route"])
+        .assert()
+        .success();
+    std::fs::write(
+        dir.path().join(".sdlc/features/auth/tasks.md"),
+        "# Tasks\n- [ ] Add login route\n- [x] Hash passwords\n- [ ] Write tests\n",
+    )
+    .unwrap();
+
+    let out = sdlc(&dir)
+        .args(["--json", "task", "sync-from-artifact", "auth"])
+        .output()
+        .unwrap();
+    assert!(out.status.success());
+    let v: serde_json::Value = serde_json::from_slice(&out.stdout).unwrap();
+    assert_eq!(v["checklist_items"], 3);
+    let created = v["created"].as_array().unwrap();
+    assert_eq!(created.len(), 2);
+    assert_eq!(created[0]["task_id"], "T2");
+    assert_eq!(created[0]["status"], "completed");
+
+    sdlc(&dir)
+        .args(["task", "sync-from-artifact", "auth"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("already in sync"));
+    let out = sdlc(&dir)
+        .args(["--json", "task", "list", "auth"])
+        .output()
+        .unwrap();
+    let listed: serde_json::Value = serde_json::from_slice(&out.stdout).unwrap();
+    assert_eq!(listed.as_array().map(Vec::len), Some(3), "{listed}");
+}
+
 // ---------------------------------------------------------------------------
 // sdlc task edit
 // ---------------------------------------------------------------------------
@@ -1459,6 +2014,62 @@ fn milestone_add_and_remove_feature() {
         .failure();
 }
 
+#[test]
+fn milestone_export_import_round_trip() {
+    let src = TempDir::new().unwrap();
+    init_project(&src);
+    sdlc(&src)
+        .args(["feature", "create", "auth", "--title", "Auth"])
+        .assert()
+        .success();
+    sdlc(&src)
+        .args(["task", "add", "auth", "Write the handler"])
+        .assert()
+        .success();
+    sdlc(&src)
+        .args([
+            "milestone",
+            "create",
+            "v2",
+            "--title",
+            "v2",
+            "--feature",
+            "auth",
+        ])
+        .assert()
+        .success();
+
+    let bundle = src.path().join("v2.tar.gz");
+    sdlc(&src)
+        .args(["milestone", "export", "v2", "--out"])
+        .arg(&bundle)
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("Exported milestone 'v2'"));
+
+    let dst = TempDir::new().unwrap();
+    init_project(&dst);
+    for _ in 0..2 {
+        sdlc(&dst)
+            .args(["milestone", "import"])
+            .arg(&bundle)
+            .assert()
+            .success()
+            .stdout(predicates::str::contains("Imported milestone 'v2'"));
+    }
+
+    sdlc(&dst)
+        .args(["milestone", "info", "v2"])
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("auth"));
+    sdlc(&dst)
+        .args(["task", "list", "auth"])
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("Write the handler"));
+}
+
 #[test]
 fn milestone_complete_and_cancel() {
     let dir = TempDir::new().unwrap();
@@ -1696,6 +2307,54 @@ fn milestone_reorder_rejects_extra_slug() {
         .stderr(predicates::str::contains("not in this milestone"));
 }
 
+#[test]
+fn task_reorder_changes_list_order() {
+    let dir = TempDir::new().unwrap();
+    init_project(&dir);
+    sdlc(&dir)
+        .args(["feature", "create", "auth", "--title", "Auth"])
+        .assert()
+        .success();
+    for title in ["Schema", "Handler", "Tests"] {
+        sdlc(&dir)
+            .args(["task", "add", "auth", title])
+            .assert()
+            .success();
+    }
+
+    sdlc(&dir)
+        .args(["task", "reorder", "auth", "T3", "T1", "T2"])
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("1. T3"));
+    sdlc(&dir)
+        .args(["task", "add", "auth", "Docs"])
+        .assert()
+        .success();
+
+    let out = sdlc(&dir)
+        .args(["--json", "task", "list", "auth"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let tasks: serde_json::Value = serde_json::from_slice(&out).unwrap();
+    let ids: Vec<&str> = tasks
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|t| t["id"].as_str().unwrap())
+        .collect();
+    assert_eq!(ids, vec!["T3", "T1", "T2", "T4"]);
+
+    sdlc(&dir)
+        .args(["task", "reorder", "auth", "T1", "T2"])
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("missing task id"));
+}
+
 #[test]
 fn milestone_add_feature_with_position() {
     let dir = TempDir::new().unwrap();
@@ -1856,9 +2515,12 @@ fn platform_deploy_shows_run_directive() {
         .assert()
         .success()
         .stderr(predicates::str::contains(
-            "sdlc does not execute platform scripts",
+            "sdlc does not execute platform scripts unless asked",
         ))
-        .stderr(predicates::str::contains("deploy.sh"));
+        .stderr(predicates::str::contains("deploy.sh auth-service staging"))
+        .stderr(predicates::str::contains(
+            "sdlc platform run deploy auth-service staging",
+        ));
 }
 
 #[test]
@@ -1871,11 +2533,64 @@ fn platform_dev_subcommand_shows_run_directive() {
         .assert()
         .success()
         .stderr(predicates::str::contains(
-            "sdlc does not execute platform scripts",
+            "sdlc does not execute platform scripts unless asked",
         ))
         .stderr(predicates::str::contains("dev-start.sh"));
 }
 
+/// Replace a scaffolded platform script with a harmless fixture. It is left
+/// without the exec bit: `sdlc platform run` goes through the interpreter.
+#[cfg(unix)]
+fn fixture_platform_script(dir: &TempDir, name: &str, body: &str) {
+    use std::os::unix::fs::PermissionsExt;
+    let path = dir.path().join(".sdlc/platform").join(name);
+    std::fs::write(&path, format!("#!/bin/sh\n{body}\n")).unwrap();
+    std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o644)).unwrap();
+}
+
+#[cfg(unix)]
+#[test]
+fn platform_run_deploy_runs_script_and_streams_output() {
+    let dir = TempDir::new().unwrap();
+    init_with_platform(&dir);
+    fixture_platform_script(
+        &dir,
+        "deploy.sh",
+        "echo \"deploying $1 to $2\"\necho warn >&2",
+    );
+
+    sdlc(&dir)
+        .args(["platform", "run", "deploy", "auth-service", "staging"])
+        .assert()
+        .success()
+        .stdout(predicates::str::contains(
+            "deploying auth-service to staging",
+        ))
+        .stderr(predicates::str::contains("warn"));
+}
+
+#[cfg(unix)]
+#[test]
+fn platform_run_dev_subcommand_json_reports_run_result() {
+    let dir = TempDir::new().unwrap();
+    init_with_platform(&dir);
+    fixture_platform_script(&dir, "dev-start.sh", "echo started\nexit 4");
+
+    let output = sdlc(&dir)
+        .args(["--json", "platform", "run", "dev", "start"])
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("exited with code 4"))
+        .get_output()
+        .stdout
+        .clone();
+    let result: serde_json::Value = serde_json::from_slice(&output).unwrap();
+    assert_eq!(result["command"], "dev start");
+    assert_eq!(result["exit_code"], 4);
+    assert_eq!(result["stdout"], "started\n");
+    assert_eq!(result["output_truncated"], false);
+}
+
 #[test]
 fn platform_dev_unknown_subcommand_errors() {
     let dir = TempDir::new().unwrap();
@@ -3728,3 +4443,202 @@ fn run_one_tick_sentinel_updates_on_each_tick() {
         "sentinel mtime must advance (or stay equal) on second tick"
     );
 }
+
+// ---------------------------------------------------------------------------
+// sdlc agent logs
+// ---------------------------------------------------------------------------
+
+fn write_transcript(dir: &TempDir, run_id: &str) {
+    let store = claude_agent::SessionStore::new(dir.path());
+    let mut writer = store.transcript_writer(run_id).unwrap();
+    for event in [
+        serde_json::json!({"type": "init", "model": "claude-test", "tools_count": 3}),
+        serde_json::json!({"type": "assistant", "text": "Reading the spec.",
+            "tools": [{"name": "Read", "input": {"file_path": "spec.md"}}]}),
+        serde_json::json!({"type": "user", "tool_results": [
+            {"type": "tool_result", "tool_use_id": "t1", "is_error": false, "content": "# Spec"}]}),
+        serde_json::json!({"type": "status", "status": "thinking"}),
+        serde_json::json!({"type": "result", "is_error": false, "text": "ok",
+            "turns": 2, "cost_usd": 0.0125}),
+    ] {
+        writer.append(&event).unwrap();
+    }
+}
+
+#[test]
+fn agent_logs_renders_transcript() {
+    let dir = TempDir::new().unwrap();
+    write_transcript(&dir, "20260101-000000-abc");
+
+    let out = sdlc(&dir)
+        .args(["agent", "logs", "20260101-000000-abc"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let out = String::from_utf8(out).unwrap();
+    let lines: Vec<&str> = out.lines().collect();
+    assert_eq!(lines[0], "▸ claude-test (3 tools)");
+    assert_eq!(lines[1], "Reading the spec.");
+    assert_eq!(lines[2], r#"→ Read {"file_path":"spec.md"}"#);
+    assert_eq!(lines[3], "  ← # Spec");
+    assert_eq!(lines[4], "✓ done — 2 turns, $0.0125");
+    assert_eq!(lines.len(), 5, "status pings are not rendered: {out}");
+}
+
+#[test]
+fn agent_logs_json_prints_one_event_per_line() {
+    let dir = TempDir::new().unwrap();
+    write_transcript(&dir, "run-1");
+
+    let out = sdlc(&dir)
+        .args(["agent", "logs", "run-1", "--json"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let types: Vec<String> = String::from_utf8(out)
+        .unwrap()
+        .lines()
+        .map(|l| serde_json::from_str::<serde_json::Value>(l).unwrap()["type"].to_string())
+        .collect();
+    assert_eq!(
+        types,
+        [
+            r#""init""#,
+            r#""assistant""#,
+            r#""user""#,
+            r#""status""#,
+            r#""result""#
+        ]
+    );
+}
+
+#[test]
+fn agent_logs_fails_for_unknown_run() {
+    let dir = TempDir::new().unwrap();
+    sdlc(&dir)
+        .args(["agent", "logs", "no-such-run"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "no transcript for run 'no-such-run'",
+        ));
+
+    sdlc(&dir)
+        .args(["agent", "logs", "../outside"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("invalid run ID '../outside'"));
+}
+
+// ---------------------------------------------------------------------------
+// sdlc agent replay
+// ---------------------------------------------------------------------------
+
+#[test]
+fn agent_replay_reassembles_the_recorded_result() {
+    let dir = TempDir::new().unwrap();
+    let mut writer = claude_agent::SessionStore::new(dir.path())
+        .recording_writer("run-1")
+        .unwrap();
+    for message in [
+        serde_json::json!({"type": "assistant", "session_id": "s1", "parent_tool_use_id": null,
+            "message": {"id": "msg_1", "role": "assistant", "model": "m",
+                "content": [{"type": "tool_use", "id": "tu_1", "name": "Bash",
+                    "input": {"command": "cargo test"}}],
+                "usage": {"input_tokens": 1, "output_tokens": 1}}}),
+        serde_json::json!({"type": "user", "session_id": "s1", "parent_tool_use_id": null,
+            "message": {"role": "user", "content": [
+                {"type": "tool_result", "tool_use_id": "tu_1", "is_error": false}]}}),
+        serde_json::json!({"type": "result", "subtype": "success", "session_id": "s1",
+            "result": "all done", "duration_ms": 1, "duration_api_ms": 1, "is_error": false,
+            "num_turns": 2, "total_cost_usd": 0.01,
+            "usage": {"input_tokens": 1, "output_tokens": 1}}),
+    ] {
+        writer.append(&message).unwrap();
+    }
+
+    let out = sdlc(&dir)
+        .args(["agent", "replay", "run-1", "--json"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let result: serde_json::Value = serde_json::from_slice(&out).unwrap();
+    assert_eq!(result["session_id"], "s1");
+    assert_eq!(result["result_text"], "all done");
+    assert_eq!(result["num_turns"], 2);
+    assert_eq!(result["tool_calls"][0]["name"], "Bash");
+    assert_eq!(result["tool_calls"][0]["is_error"], false);
+
+    sdlc(&dir)
+        .args(["agent", "replay", "no-such-run"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "no recording for run 'no-such-run'",
+        ));
+
+    sdlc(&dir)
+        .args(["agent", "replay", "../run-1"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("invalid run ID '../run-1'"));
+}
+
+#[test]
+fn query_ready_waits_for_dependencies_to_be_released() {
+    let dir = TempDir::new().unwrap();
+    init_project(&dir);
+    for slug in ["db", "api"] {
+        sdlc(&dir)
+            .args(["feature", "create", slug, "--title", slug])
+            .assert()
+            .success();
+    }
+    sdlc(&dir)
+        .args(["feature", "update", "api", "--depends-on", "db"])
+        .assert()
+        .success();
+
+    let ready = |extra: &[&str]| -> serde_json::Value {
+        let output = sdlc(&dir)
+            .args(["query", "ready", "--json"])
+            .args(extra)
+            .assert()
+            .success()
+            .get_output()
+            .stdout
+            .clone();
+        serde_json::from_slice(&output).unwrap()
+    };
+
+    let before = ready(&[]);
+    assert_eq!(before.as_array().unwrap().len(), 1);
+    assert_eq!(before[0]["slug"], "db");
+    let report = ready(&["--include-blocked-by"]);
+    assert_eq!(report["blocked_by_dependency"][0]["slug"], "api");
+    assert_eq!(
+        report["blocked_by_dependency"][0]["blocked_by"][0],
+        serde_json::json!({"slug": "db", "phase": "draft"})
+    );
+    sdlc(&dir)
+        .args(["query", "ready", "--include-blocked-by"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "api [draft] blocked by db [draft]",
+        ));
+
+    let mut db = sdlc_core::feature::Feature::load(dir.path(), "db").unwrap();
+    db.phase = sdlc_core::types::Phase::Released;
+    db.save(dir.path()).unwrap();
+
+    let now = ready(&[]);
+    assert_eq!(now.as_array().unwrap().len(), 1);
+    assert_eq!(now[0]["slug"], "api");
+}
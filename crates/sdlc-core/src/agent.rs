@@ -0,0 +1,287 @@
+//! Claude agent definitions (`.claude/agents/<name>.md`).
+//!
+//! An agent file is YAML frontmatter (`name`, `description`, `model`,
+//! `color`, optional `tools`) followed by a Markdown system prompt. Agents
+//! written by `/sdlc-recruit` and `/sdlc-specialize` use a fixed set of
+//! body sections — Principles, This Codebase, ALWAYS and NEVER — which are
+//! lifted into typed fields here. The full body is always kept in `content`.
+
+use crate::error::{Result, SdlcError};
+use serde::{Deserialize, Deserializer, Serialize};
+use std::path::Path;
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AgentDefinition {
+    pub name: String,
+    pub description: String,
+    pub model: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+    pub tools: Vec<String>,
+    /// Bullets under the `## ... Principles` heading.
+    pub principles: Vec<String>,
+    /// Raw Markdown under `## This Codebase` (grouped file references).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub codebase: Option<String>,
+    /// Bullets under `## ALWAYS`.
+    pub always: Vec<String>,
+    /// Bullets under `## NEVER`.
+    pub never: Vec<String>,
+    pub content: String,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct Frontmatter {
+    #[serde(default)]
+    name: String,
+    #[serde(default)]
+    description: String,
+    #[serde(default)]
+    model: String,
+    #[serde(default)]
+    color: Option<String>,
+    #[serde(default, deserialize_with = "tools_list")]
+    tools: Vec<String>,
+}
+
+/// Accept `tools` both as a YAML list and as Claude's `Read, Write` string form.
+fn tools_list<'de, D: Deserializer<'de>>(de: D) -> std::result::Result<Vec<String>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Tools {
+        List(Vec<String>),
+        Csv(String),
+    }
+    Ok(match Option::<Tools>::deserialize(de)? {
+        Some(Tools::List(list)) => list,
+        Some(Tools::Csv(s)) => s
+            .split(',')
+            .map(str::trim)
+            .filter(|t| !t.is_empty())
+            .map(str::to_string)
+            .collect(),
+        None => Vec::new(),
+    })
+}
+
+/// Split `---`-delimited frontmatter from the body. Files without
+/// frontmatter (or without a closing delimiter) are all body.
+fn split_frontmatter(raw: &str) -> (&str, &str) {
+    let Some(rest) = raw.strip_prefix("---\n") else {
+        return ("", raw);
+    };
+    if let Some(pos) = rest.find("\n---\n") {
+        (&rest[..pos], &rest[pos + "\n---\n".len()..])
+    } else if let Some(pos) = rest.find("\n---") {
+        (&rest[..pos], &rest[pos + "\n---".len()..])
+    } else {
+        ("", raw)
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Section {
+    Other,
+    Principles,
+    Codebase,
+    Always,
+    Never,
+}
+
+fn section_for(heading: &str) -> Section {
+    let h = heading.trim().to_ascii_lowercase();
+    if h.ends_with("principles") {
+        Section::Principles
+    } else if h == "this codebase" {
+        Section::Codebase
+    } else if h == "always" {
+        Section::Always
+    } else if h == "never" {
+        Section::Never
+    } else {
+        Section::Other
+    }
+}
+
+/// Top-level `- ` / `* ` bullets; indented continuation lines are folded
+/// into the preceding bullet.
+fn push_bullet_line(bullets: &mut Vec<String>, line: &str) {
+    if let Some(item) = line.strip_prefix("- ").or_else(|| line.strip_prefix("* ")) {
+        bullets.push(item.trim().to_string());
+    } else if line.starts_with(char::is_whitespace) && !line.trim().is_empty() {
+        if let Some(last) = bullets.last_mut() {
+            last.push(' ');
+            last.push_str(line.trim());
+        }
+    }
+}
+
+/// Parse an agent file. `stem` is the filename without `.md` and stands in
+/// for a missing `name`. Frontmatter that is not valid YAML is an error.
+pub fn parse(stem: &str, raw: &str) -> Result<AgentDefinition> {
+    let (frontmatter, body) = split_frontmatter(raw);
+    let fm: Frontmatter = if frontmatter.trim().is_empty() {
+        Frontmatter::default()
+    } else {
+        serde_yaml::from_str(frontmatter).map_err(|e| SdlcError::ManifestParseFailed {
+            path: format!("{stem}.md"),
+            message: e.to_string(),
+        })?
+    };
+
+    let mut agent = AgentDefinition {
+        name: if fm.name.is_empty() {
+            stem.to_string()
+        } else {
+            fm.name
+        },
+        description: fm.description,
+        model: fm.model,
+        color: fm.color,
+        tools: fm.tools,
+        principles: Vec::new(),
+        codebase: None,
+        always: Vec::new(),
+        never: Vec::new(),
+        content: body.trim_start().to_string(),
+    };
+
+    let mut section = Section::Other;
+    let mut codebase = String::new();
+    let mut in_fence = false;
+    for line in body.lines() {
+        if line.trim_start().starts_with("```") {
+            in_fence = !in_fence;
+        }
+        if !in_fence {
+            if let Some(heading) = line.strip_prefix("## ") {
+                section = section_for(heading);
+                continue;
+            }
+        }
+        match section {
+            Section::Principles => push_bullet_line(&mut agent.principles, line),
+            Section::Always => push_bullet_line(&mut agent.always, line),
+            Section::Never => push_bullet_line(&mut agent.never, line),
+            Section::Codebase => {
+                codebase.push_str(line);
+                codebase.push('\n');
+            }
+            Section::Other => {}
+        }
+    }
+    let codebase = codebase.trim();
+    if !codebase.is_empty() {
+        agent.codebase = Some(codebase.to_string());
+    }
+    Ok(agent)
+}
+
+/// Read and parse one agent file.
+pub fn load(path: &Path) -> Result<AgentDefinition> {
+    let raw = std::fs::read_to_string(path)?;
+    let stem = path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or_default();
+    parse(stem, &raw).map_err(|e| match e {
+        SdlcError::ManifestParseFailed { message, .. } => SdlcError::ManifestParseFailed {
+            path: path.display().to_string(),
+            message,
+        },
+        other => other,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FULL: &str = "---
+name: kai-hoffmann
+description: Use when touching the run loop.
+model: opus
+color: teal
+---
+
+You are Kai Hoffmann, a systems engineer.
+
+## Your Principles
+
+- **Small diffs.** Reviewable changes land faster.
+- **Measure first.** Profiles beat intuition
+  every time.
+
+## This Codebase
+
+**Server:**
+- `crates/sdlc-server/src/routes/runs.rs` — agent run lifecycle
+
+## ALWAYS
+
+- Write state through `io::atomic_write`
+
+## NEVER
+
+- Call `unwrap()` in library code
+- Block the async runtime
+
+## When You're Stuck
+
+1. **Flaky test:** rerun with `--nocapture`.
+";
+
+    #[test]
+    fn parses_frontmatter_and_sections() {
+        let agent = parse("kai-hoffmann", FULL).unwrap();
+        assert_eq!(agent.name, "kai-hoffmann");
+        assert_eq!(agent.model, "opus");
+        assert_eq!(agent.color.as_deref(), Some("teal"));
+        assert_eq!(
+            agent.principles,
+            [
+                "**Small diffs.** Reviewable changes land faster.",
+                "**Measure first.** Profiles beat intuition every time.",
+            ]
+        );
+        assert!(agent
+            .codebase
+            .as_deref()
+            .unwrap()
+            .contains("routes/runs.rs"));
+        assert_eq!(agent.always, ["Write state through `io::atomic_write`"]);
+        assert_eq!(agent.never.len(), 2);
+        assert!(agent.content.starts_with("You are Kai Hoffmann"));
+    }
+
+    #[test]
+    fn frontmatter_only_file_has_empty_sections() {
+        let raw = "---\ndescription: No name field\ntools: Read, Grep\n---\n";
+        let agent = parse("bare-agent", raw).unwrap();
+        assert_eq!(agent.name, "bare-agent");
+        assert_eq!(agent.description, "No name field");
+        assert_eq!(agent.tools, ["Read", "Grep"]);
+        assert!(agent.principles.is_empty() && agent.always.is_empty());
+        assert_eq!(agent.codebase, None);
+        assert_eq!(agent.content, "");
+    }
+
+    #[test]
+    fn tools_accept_a_yaml_list_and_missing_frontmatter_is_all_body() {
+        let raw = "---\nname: t\ntools:\n  - Read\n  - Write\n---\nBody";
+        assert_eq!(parse("t", raw).unwrap().tools, ["Read", "Write"]);
+
+        let agent = parse("bare", "# Just content\n\nNo frontmatter here.").unwrap();
+        assert_eq!(agent.name, "bare");
+        assert!(agent.content.starts_with("# Just content"));
+    }
+
+    #[test]
+    fn malformed_frontmatter_is_an_error() {
+        let raw = "---\nname: [unclosed\n---\nBody";
+        assert!(matches!(
+            parse("broken", raw),
+            Err(SdlcError::ManifestParseFailed { .. })
+        ));
+    }
+}
@@ -0,0 +1,444 @@
+//! Append-only audit log of feature state changes.
+//!
+//! Layout:
+//!   .sdlc/audit.log   — JSONL, one `AuditEntry` per line, never rewritten
+//!
+//! Entries are recorded by `Feature::save`, which diffs the manifest being
+//! written against the one this process last loaded or saved for that path
+//! (see [`remember`]), or records `created` for a new feature. Every phase
+//! transition, task change, and artifact status change is therefore captured
+//! no matter which surface (CLI, server, agent tool) made it.
+//!
+//! The actor comes from `set_actor` (the CLI's `--actor` flag), then the
+//! `SDLC_ACTOR` env var, then `USER`.
+
+use crate::error::{Result, SdlcError};
+use crate::feature::Feature;
+use crate::io;
+use crate::paths;
+use crate::types::{ArtifactStatus, Phase};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock};
+
+pub const ACTOR_ENV: &str = "SDLC_ACTOR";
+
+static ACTOR: OnceLock<String> = OnceLock::new();
+
+/// Feature manifests as this process last loaded or saved them, keyed by
+/// manifest path.
+static LOADED: OnceLock<Mutex<HashMap<PathBuf, Arc<Feature>>>> = OnceLock::new();
+
+// ---------------------------------------------------------------------------
+// Types
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditAction {
+    Created,
+    Transition,
+    TaskAdded,
+    TaskStatusChanged,
+    TaskRemoved,
+    ArtifactApproved,
+    ArtifactStatusChanged,
+}
+
+impl std::fmt::Display for AuditAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AuditAction::Created => write!(f, "created"),
+            AuditAction::Transition => write!(f, "transition"),
+            AuditAction::TaskAdded => write!(f, "task_added"),
+            AuditAction::TaskStatusChanged => write!(f, "task_status_changed"),
+            AuditAction::TaskRemoved => write!(f, "task_removed"),
+            AuditAction::ArtifactApproved => write!(f, "artifact_approved"),
+            AuditAction::ArtifactStatusChanged => write!(f, "artifact_status_changed"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditEntry {
+    pub timestamp: DateTime<Utc>,
+    pub actor: String,
+    pub feature: String,
+    pub action: AuditAction,
+    pub phase_before: Phase,
+    pub phase_after: Phase,
+    /// What changed, e.g. `T2: pending → completed` or `spec: draft → approved`.
+    /// For people; read `task`, `from` and `to` instead of parsing it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+    /// Id of the task a task entry is about.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub task: Option<String>,
+    /// Status of the task or artifact before the change; unset when it was
+    /// just added.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub from: Option<String>,
+    /// Status of the task or artifact after the change; unset when it was
+    /// removed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub to: Option<String>,
+}
+
+// ---------------------------------------------------------------------------
+// Actor
+// ---------------------------------------------------------------------------
+
+/// Override the actor for every entry written by this process.
+/// Only the first call takes effect.
+pub fn set_actor(actor: impl Into<String>) {
+    let _ = ACTOR.set(actor.into());
+}
+
+/// The actor recorded on new entries.
+pub fn actor() -> String {
+    if let Some(actor) = ACTOR.get() {
+        return actor.clone();
+    }
+    std::env::var(ACTOR_ENV)
+        .or_else(|_| std::env::var("USER"))
+        .ok()
+        .filter(|s| !s.trim().is_empty())
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+// ---------------------------------------------------------------------------
+// Recording
+// ---------------------------------------------------------------------------
+
+/// Record `feature` as the contents of `manifest`, for the next save to diff
+/// against.
+pub(crate) fn remember(manifest: &Path, feature: &Feature) {
+    let loaded = LOADED.get_or_init(Default::default);
+    loaded
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .insert(manifest.to_path_buf(), Arc::new(feature.clone()));
+}
+
+/// The manifest at `manifest` as this process last loaded or saved it.
+pub(crate) fn loaded(manifest: &Path) -> Option<Arc<Feature>> {
+    LOADED
+        .get()?
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .get(manifest)
+        .cloned()
+}
+
+/// The entry for the first save of a new feature.
+pub fn created(feature: &Feature) -> AuditEntry {
+    AuditEntry {
+        timestamp: Utc::now(),
+        actor: actor(),
+        feature: feature.slug.clone(),
+        action: AuditAction::Created,
+        phase_before: feature.phase,
+        phase_after: feature.phase,
+        detail: Some(feature.title.clone()),
+        task: None,
+        from: None,
+        to: None,
+    }
+}
+
+/// Compute the audit entries for a save that replaces `before` with `after`.
+pub fn diff(before: &Feature, after: &Feature) -> Vec<AuditEntry> {
+    let now = Utc::now();
+    let actor = actor();
+    let entry = |action: AuditAction, detail: Option<String>| AuditEntry {
+        timestamp: now,
+        actor: actor.clone(),
+        feature: after.slug.clone(),
+        action,
+        phase_before: before.phase,
+        phase_after: after.phase,
+        detail,
+        task: None,
+        from: None,
+        to: None,
+    };
+
+    let mut entries = Vec::new();
+    if before.phase != after.phase {
+        entries.push(entry(
+            AuditAction::Transition,
+            Some(format!("{} → {}", before.phase, after.phase)),
+        ));
+    }
+
+    for task in &after.tasks {
+        match before.tasks.iter().find(|t| t.id == task.id) {
+            None => entries.push(AuditEntry {
+                task: Some(task.id.clone()),
+                to: Some(task.status.to_string()),
+                ..entry(
+                    AuditAction::TaskAdded,
+                    Some(format!("{}: {}", task.id, task.title)),
+                )
+            }),
+            Some(old) if old.status != task.status => entries.push(AuditEntry {
+                task: Some(task.id.clone()),
+                from: Some(old.status.to_string()),
+                to: Some(task.status.to_string()),
+                ..entry(
+                    AuditAction::TaskStatusChanged,
+                    Some(format!("{}: {} → {}", task.id, old.status, task.status)),
+                )
+            }),
+            Some(_) => {}
+        }
+    }
+    for task in &before.tasks {
+        if !after.tasks.iter().any(|t| t.id == task.id) {
+            entries.push(AuditEntry {
+                task: Some(task.id.clone()),
+                from: Some(task.status.to_string()),
+                ..entry(
+                    AuditAction::TaskRemoved,
+                    Some(format!("{}: {}", task.id, task.title)),
+                )
+            });
+        }
+    }
+
+    for artifact in &after.artifacts {
+        let old_status = before
+            .artifacts
+            .iter()
+            .find(|a| a.artifact_type == artifact.artifact_type)
+            .map(|a| a.status)
+            .unwrap_or(ArtifactStatus::Missing);
+        if old_status == artifact.status {
+            continue;
+        }
+        let action = if artifact.status == ArtifactStatus::Approved {
+            AuditAction::ArtifactApproved
+        } else {
+            AuditAction::ArtifactStatusChanged
+        };
+        entries.push(AuditEntry {
+            from: Some(old_status.to_string()),
+            to: Some(artifact.status.to_string()),
+            ..entry(
+                action,
+                Some(format!(
+                    "{}: {} → {}",
+                    artifact.artifact_type, old_status, artifact.status
+                )),
+            )
+        });
+    }
+
+    entries
+}
+
+/// Append entries to `.sdlc/audit.log`. Existing lines are never touched.
+pub fn append(root: &Path, entries: &[AuditEntry]) -> Result<()> {
+    if entries.is_empty() {
+        return Ok(());
+    }
+    let mut text = String::new();
+    for entry in entries {
+        text.push_str(&serde_json::to_string(entry)?);
+        text.push('\n');
+    }
+    io::append_text(&paths::audit_log_path(root), &text)
+}
+
+// ---------------------------------------------------------------------------
+// Reading
+// ---------------------------------------------------------------------------
+
+/// Read the audit log oldest-first, optionally restricted to one feature.
+/// Returns an empty list if the log does not exist yet.
+pub fn read(root: &Path, feature: Option<&str>) -> Result<Vec<AuditEntry>> {
+    read_since(root, feature, None)
+}
+
+/// [`read`], keeping only entries at or after `since` when it is set.
+pub fn read_since(
+    root: &Path,
+    feature: Option<&str>,
+    since: Option<DateTime<Utc>>,
+) -> Result<Vec<AuditEntry>> {
+    let path = paths::audit_log_path(root);
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let data = std::fs::read_to_string(&path)?;
+    let mut entries = Vec::new();
+    for (i, line) in data.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let entry: AuditEntry =
+            serde_json::from_str(line).map_err(|e| SdlcError::ManifestParseFailed {
+                path: path.display().to_string(),
+                message: format!("line {}: {e}", i + 1),
+            })?;
+        if feature.is_none_or(|slug| entry.feature == slug)
+            && since.is_none_or(|cutoff| entry.timestamp >= cutoff)
+        {
+            entries.push(entry);
+        }
+    }
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use crate::types::ArtifactType;
+    use tempfile::TempDir;
+
+    #[test]
+    fn transition_appends_one_entry() {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path();
+        let cfg = Config::new("test");
+        let mut feature = Feature::create(root, "auth", "Auth").unwrap();
+        feature
+            .approve_artifact(root, ArtifactType::Spec, None)
+            .unwrap();
+        feature.save(root).unwrap();
+        let before = read(root, None).unwrap().len();
+
+        feature.transition(Phase::Specified, &cfg).unwrap();
+        feature.save(root).unwrap();
+
+        let raw = std::fs::read_to_string(paths::audit_log_path(root)).unwrap();
+        let last: serde_json::Value = serde_json::from_str(raw.lines().last().unwrap()).unwrap();
+        assert_eq!(raw.lines().count(), before + 1);
+        assert_eq!(last["feature"], "auth");
+        assert_eq!(last["action"], "transition");
+        assert_eq!(last["phase_before"], "draft");
+        assert_eq!(last["phase_after"], "specified");
+        assert!(last["actor"].as_str().is_some_and(|a| !a.is_empty()));
+        assert!(last["timestamp"].as_str().is_some());
+    }
+
+    #[test]
+    fn task_and_approval_changes_are_recorded() {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path();
+        let mut feature = Feature::create(root, "auth", "Auth").unwrap();
+        crate::task::add_task(&mut feature.tasks, "Write handler");
+        feature.save(root).unwrap();
+        crate::task::complete_task(&mut feature.tasks, "T1").unwrap();
+        feature
+            .approve_artifact(root, ArtifactType::Spec, None)
+            .unwrap();
+        feature.save(root).unwrap();
+
+        let entries = read(root, None).unwrap();
+        let actions: Vec<_> = entries.iter().map(|e| e.action).collect();
+        assert_eq!(
+            actions,
+            vec![
+                AuditAction::Created,
+                AuditAction::TaskAdded,
+                AuditAction::TaskStatusChanged,
+                AuditAction::ArtifactApproved,
+            ]
+        );
+        let completed = &entries[2];
+        assert_eq!(completed.task.as_deref(), Some("T1"));
+        assert_eq!(completed.from.as_deref(), Some("pending"));
+        assert_eq!(completed.to.as_deref(), Some("completed"));
+        assert_eq!(entries[3].to.as_deref(), Some("approved"));
+    }
+
+    #[test]
+    fn read_filters_by_feature() {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path();
+        for slug in ["auth", "billing"] {
+            let mut f = Feature::create(root, slug, slug).unwrap();
+            crate::task::add_task(&mut f.tasks, "First task");
+            f.save(root).unwrap();
+        }
+
+        assert_eq!(read(root, None).unwrap().len(), 4);
+        let billing = read(root, Some("billing")).unwrap();
+        assert_eq!(billing.len(), 2);
+        assert!(billing.iter().all(|e| e.feature == "billing"));
+        assert!(read(root, Some("nope")).unwrap().is_empty());
+    }
+
+    #[test]
+    fn read_since_excludes_entries_before_cutoff() {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path();
+        let entry = |timestamp: &str, feature: &str| AuditEntry {
+            timestamp: timestamp.parse().unwrap(),
+            actor: "alice".into(),
+            feature: feature.into(),
+            action: AuditAction::TaskAdded,
+            phase_before: Phase::Draft,
+            phase_after: Phase::Draft,
+            detail: None,
+            task: None,
+            from: None,
+            to: None,
+        };
+        std::fs::create_dir_all(paths::audit_log_path(root).parent().unwrap()).unwrap();
+        append(
+            root,
+            &[
+                entry("2026-03-01T09:00:00Z", "auth"),
+                entry("2026-03-01T10:00:00Z", "auth"),
+                entry("2026-03-01T11:00:00Z", "billing"),
+            ],
+        )
+        .unwrap();
+
+        let cutoff = "2026-03-01T10:00:00Z".parse().unwrap();
+        let kept: Vec<_> = read_since(root, None, Some(cutoff))
+            .unwrap()
+            .into_iter()
+            .map(|e| e.timestamp.to_rfc3339())
+            .collect();
+        assert_eq!(
+            kept,
+            ["2026-03-01T10:00:00+00:00", "2026-03-01T11:00:00+00:00"]
+        );
+        assert_eq!(
+            read_since(root, Some("auth"), Some(cutoff)).unwrap().len(),
+            1
+        );
+        assert_eq!(read_since(root, None, None).unwrap().len(), 3);
+    }
+
+    #[test]
+    fn save_without_changes_records_only_the_creation() {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path();
+        let feature = Feature::create(root, "auth", "Auth").unwrap();
+        feature.save(root).unwrap();
+        Feature::load(root, "auth").unwrap().save(root).unwrap();
+        let entries = read(root, None).unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].action, AuditAction::Created);
+        assert_eq!(entries[0].detail.as_deref(), Some("Auth"));
+    }
+
+    #[test]
+    fn save_succeeds_when_the_audit_log_cannot_be_written() {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path();
+        // A directory where the log file should be makes every append fail.
+        std::fs::create_dir_all(paths::audit_log_path(root)).unwrap();
+        let mut feature = Feature::create(root, "auth", "Auth").unwrap();
+        crate::task::add_task(&mut feature.tasks, "Write handler");
+        feature.save(root).unwrap();
+        assert_eq!(Feature::load(root, "auth").unwrap().tasks.len(), 1);
+    }
+}
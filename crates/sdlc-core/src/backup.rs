@@ -0,0 +1,324 @@
+//! Whole-project backup — `.sdlc/` as a plain tar stream.
+//!
+//! The archive starts with a [`BackupManifest`] entry (`backup.yaml`)
+//! followed by every directory and file under `.sdlc/`, stored at its
+//! root-relative path. Plaintext env files (`.env`, `.env.*`), the state
+//! lock, and temp files from unfinished writes are left out. Both directions
+//! stream file contents, so memory use does not grow with the size of the
+//! project.
+
+use crate::error::{Result, SdlcError};
+use crate::paths;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::io::{Read, Write};
+use std::path::{Component, Path, PathBuf};
+
+/// Name of the manifest entry at the start of a backup archive.
+pub const BACKUP_MANIFEST: &str = "backup.yaml";
+pub const BACKUP_FORMAT_VERSION: u32 = 1;
+
+/// Staging directory a restore unpacks into before it is renamed to `.sdlc`.
+const RESTORE_STAGING_DIR: &str = ".sdlc.restore";
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BackupManifest {
+    pub format_version: u32,
+    /// Version of the `sdlc` binary that wrote the backup.
+    pub sdlc_version: String,
+    pub created_at: DateTime<Utc>,
+    /// Every file in the archive, relative to the project root.
+    pub files: Vec<String>,
+}
+
+// ---------------------------------------------------------------------------
+// Backup
+// ---------------------------------------------------------------------------
+
+/// Stream a backup of `root/.sdlc` to `out`, stamped with `sdlc_version`.
+pub fn backup<W: Write>(root: &Path, out: W, sdlc_version: &str) -> Result<BackupManifest> {
+    let sdlc_dir = root.join(paths::SDLC_DIR);
+    if !sdlc_dir.is_dir() {
+        return Err(SdlcError::NotInitialized);
+    }
+    let mut entries = Vec::new();
+    collect_paths(root, &sdlc_dir, &mut entries)?;
+
+    let manifest = BackupManifest {
+        format_version: BACKUP_FORMAT_VERSION,
+        sdlc_version: sdlc_version.to_string(),
+        created_at: Utc::now(),
+        files: entries
+            .iter()
+            .filter(|(_, is_dir)| !is_dir)
+            .map(|(rel, _)| archive_name(rel))
+            .collect(),
+    };
+
+    let mut tar = tar::Builder::new(out);
+    let yaml = serde_yaml::to_string(&manifest)?;
+    let mut header = tar::Header::new_gnu();
+    header.set_size(yaml.len() as u64);
+    header.set_mode(0o644);
+    header.set_mtime(manifest.created_at.timestamp().max(0) as u64);
+    header.set_cksum();
+    tar.append_data(&mut header, BACKUP_MANIFEST, yaml.as_bytes())?;
+    // Directories are archived too, so empty ones survive the round trip.
+    for (rel, is_dir) in &entries {
+        if *is_dir {
+            tar.append_dir(archive_name(rel), root.join(rel))?;
+        } else {
+            tar.append_path_with_name(root.join(rel), archive_name(rel))?;
+        }
+    }
+    tar.into_inner()?.flush()?;
+    Ok(manifest)
+}
+
+fn is_excluded(rel: &Path) -> bool {
+    let name = rel.file_name().and_then(|n| n.to_str()).unwrap_or_default();
+    name == ".env"
+        || name.starts_with(".env.")
+        || crate::io::is_temp_file(name)
+        || rel == Path::new(paths::STATE_LOCK_FILE)
+}
+
+fn archive_name(rel: &Path) -> String {
+    rel.to_string_lossy().replace('\\', "/")
+}
+
+/// Recursively collect every directory and regular file under `dir` as
+/// `(root-relative path, is_dir)`, parents before children, in sorted order.
+fn collect_paths(root: &Path, dir: &Path, out: &mut Vec<(PathBuf, bool)>) -> Result<()> {
+    let mut entries: Vec<_> = std::fs::read_dir(dir)?.collect::<std::io::Result<_>>()?;
+    entries.sort_by_key(|e| e.file_name());
+    for entry in entries {
+        let path = entry.path();
+        let rel = path.strip_prefix(root).unwrap_or(&path).to_path_buf();
+        let file_type = entry.file_type()?;
+        if file_type.is_dir() {
+            out.push((rel, true));
+            collect_paths(root, &path, out)?;
+        } else if file_type.is_file() && !is_excluded(&rel) {
+            out.push((rel, false));
+        }
+    }
+    Ok(())
+}
+
+// ---------------------------------------------------------------------------
+// Restore
+// ---------------------------------------------------------------------------
+
+/// Restore a backup read from `input` into `root`, which must not already
+/// hold a project (`.sdlc/` absent or empty).
+///
+/// Backups written by a newer `sdlc` than `sdlc_version` are rejected. Files
+/// are unpacked into a staging directory that only becomes `.sdlc/` once
+/// every entry has been written, so a failed restore leaves `root` as it was.
+pub fn restore<R: Read>(root: &Path, input: R, sdlc_version: &str) -> Result<BackupManifest> {
+    let sdlc_dir = root.join(paths::SDLC_DIR);
+    if sdlc_dir.exists() && std::fs::read_dir(&sdlc_dir)?.next().is_some() {
+        return Err(SdlcError::RestoreTargetNotEmpty(
+            sdlc_dir.display().to_string(),
+        ));
+    }
+
+    let staging = root.join(RESTORE_STAGING_DIR);
+    if staging.exists() {
+        std::fs::remove_dir_all(&staging)?;
+    }
+    std::fs::create_dir_all(&staging)?;
+    match unpack(&staging, input, sdlc_version) {
+        Ok(manifest) => {
+            if sdlc_dir.exists() {
+                std::fs::remove_dir(&sdlc_dir)?;
+            }
+            std::fs::rename(staging.join(paths::SDLC_DIR), &sdlc_dir)?;
+            std::fs::remove_dir_all(&staging)?;
+            Ok(manifest)
+        }
+        Err(e) => {
+            let _ = std::fs::remove_dir_all(&staging);
+            Err(e)
+        }
+    }
+}
+
+fn unpack<R: Read>(staging: &Path, input: R, sdlc_version: &str) -> Result<BackupManifest> {
+    let mut archive = tar::Archive::new(input);
+    let mut entries = archive.entries()?;
+
+    let manifest: BackupManifest = match entries.next() {
+        Some(entry) => {
+            let mut entry = entry?;
+            if entry.path()?.as_ref() != Path::new(BACKUP_MANIFEST) {
+                return Err(invalid(format!("first entry is not {BACKUP_MANIFEST}")));
+            }
+            let mut yaml = Vec::new();
+            entry.read_to_end(&mut yaml)?;
+            serde_yaml::from_slice(&yaml)?
+        }
+        None => return Err(invalid("archive is empty")),
+    };
+    check_compatible(&manifest, sdlc_version)?;
+
+    let mut restored = 0usize;
+    std::fs::create_dir_all(staging.join(paths::SDLC_DIR))?;
+    for entry in entries {
+        let mut entry = entry?;
+        let kind = entry.header().entry_type();
+        if !kind.is_file() && !kind.is_dir() {
+            continue;
+        }
+        let rel = entry.path()?.into_owned();
+        let inside_sdlc = rel.starts_with(paths::SDLC_DIR)
+            && rel.components().all(|c| matches!(c, Component::Normal(_)));
+        if !inside_sdlc {
+            return Err(invalid(format!("entry outside .sdlc/: {}", rel.display())));
+        }
+        let dest = staging.join(&rel);
+        if kind.is_dir() {
+            std::fs::create_dir_all(&dest)?;
+            continue;
+        }
+        if let Some(parent) = dest.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        entry.unpack(&dest)?;
+        restored += 1;
+    }
+    if restored != manifest.files.len() {
+        return Err(invalid(format!(
+            "archive holds {restored} files but its manifest lists {}",
+            manifest.files.len()
+        )));
+    }
+    Ok(manifest)
+}
+
+/// A backup is restorable when its format is known and it was written by
+/// this `sdlc` version or an older one.
+fn check_compatible(manifest: &BackupManifest, sdlc_version: &str) -> Result<()> {
+    if manifest.format_version > BACKUP_FORMAT_VERSION {
+        return Err(invalid(format!(
+            "format version {} is newer than supported version {BACKUP_FORMAT_VERSION}",
+            manifest.format_version
+        )));
+    }
+    let written = parse_version(&manifest.sdlc_version).ok_or_else(|| {
+        invalid(format!(
+            "unrecognized sdlc_version '{}'",
+            manifest.sdlc_version
+        ))
+    })?;
+    // An unparseable running version (a dev build) accepts any backup.
+    if parse_version(sdlc_version).is_some_and(|running| written > running) {
+        return Err(invalid(format!(
+            "written by sdlc {}, newer than this sdlc {sdlc_version}; upgrade before restoring",
+            manifest.sdlc_version
+        )));
+    }
+    Ok(())
+}
+
+/// `MAJOR.MINOR.PATCH`, ignoring any `-pre`/`+build` suffix.
+fn parse_version(version: &str) -> Option<(u64, u64, u64)> {
+    let core = version.split(['-', '+']).next()?;
+    let mut parts = core.split('.').map(|p| p.parse::<u64>().ok());
+    let version = (parts.next()??, parts.next()??, parts.next()??);
+    parts.next().is_none().then_some(version)
+}
+
+fn invalid(reason: impl Into<String>) -> SdlcError {
+    SdlcError::InvalidBackup(reason.into())
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::feature::Feature;
+    use tempfile::TempDir;
+
+    fn init_project(root: &Path) {
+        crate::config::Config::new("test").save(root).unwrap();
+        let mut state = crate::state::State::new("test");
+        let mut f = Feature::create(root, "auth", "Auth").unwrap();
+        crate::task::add_task(&mut f.tasks, "Write the handler");
+        f.save(root).unwrap();
+        state.add_active_feature("auth");
+        state.save(root).unwrap();
+        std::fs::write(paths::feature_dir(root, "auth").join("spec.md"), "# Auth\n").unwrap();
+    }
+
+    fn tree(root: &Path) -> Vec<(PathBuf, Option<Vec<u8>>)> {
+        let mut entries = Vec::new();
+        collect_paths(root, &root.join(paths::SDLC_DIR), &mut entries).unwrap();
+        entries
+            .into_iter()
+            .map(|(rel, is_dir)| {
+                let contents = (!is_dir).then(|| std::fs::read(root.join(&rel)).unwrap());
+                (rel, contents)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn backup_round_trips_into_a_fresh_project() {
+        let src = TempDir::new().unwrap();
+        init_project(src.path());
+        let secrets = src.path().join(paths::SECRETS_DIR);
+        std::fs::create_dir_all(&secrets).unwrap();
+        std::fs::write(secrets.join(".env.production"), "TOKEN=hunter2\n").unwrap();
+        std::fs::create_dir_all(src.path().join(paths::ARCHIVES_DIR)).unwrap();
+
+        let mut archive = Vec::new();
+        let manifest = backup(src.path(), &mut archive, "1.2.3").unwrap();
+        assert_eq!(manifest.sdlc_version, "1.2.3");
+        assert!(manifest
+            .files
+            .contains(&".sdlc/features/auth/spec.md".into()));
+        assert!(!manifest.files.iter().any(|f| f.contains(".env")));
+
+        let dst = TempDir::new().unwrap();
+        let restored = restore(dst.path(), archive.as_slice(), "1.3.0").unwrap();
+        assert_eq!(restored, manifest);
+        assert!(!dst.path().join(RESTORE_STAGING_DIR).exists());
+        assert_eq!(tree(dst.path()), tree(src.path()));
+        assert!(!dst
+            .path()
+            .join(paths::SECRETS_DIR)
+            .join(".env.production")
+            .exists());
+        assert_eq!(Feature::load(dst.path(), "auth").unwrap().tasks.len(), 1);
+    }
+
+    #[test]
+    fn restore_refuses_a_non_empty_project() {
+        let src = TempDir::new().unwrap();
+        init_project(src.path());
+        let mut archive = Vec::new();
+        backup(src.path(), &mut archive, "1.2.3").unwrap();
+
+        let err = restore(src.path(), archive.as_slice(), "1.2.3").unwrap_err();
+        assert!(matches!(err, SdlcError::RestoreTargetNotEmpty(_)), "{err}");
+    }
+
+    #[test]
+    fn restore_rejects_backups_from_a_newer_sdlc() {
+        let src = TempDir::new().unwrap();
+        init_project(src.path());
+        let mut archive = Vec::new();
+        backup(src.path(), &mut archive, "2.0.0").unwrap();
+
+        let dst = TempDir::new().unwrap();
+        let err = restore(dst.path(), archive.as_slice(), "1.9.9").unwrap_err();
+        assert!(matches!(err, SdlcError::InvalidBackup(_)), "{err}");
+        assert!(!dst.path().join(paths::SDLC_DIR).exists());
+        assert!(!dst.path().join(RESTORE_STAGING_DIR).exists());
+    }
+}
@@ -1,9 +1,12 @@
+use crate::artifact::stale_artifacts;
 use crate::config::Config;
+use crate::error::Result;
+use crate::escalation::{self, EscalationItem, EscalationKind};
 use crate::event_log::{self, EventKind};
 use crate::feature::Feature;
 use crate::rules::default_rules;
 use crate::state::State;
-use crate::types::{ActionType, Phase};
+use crate::types::{ActionType, ArtifactType, Phase};
 use serde::{Deserialize, Serialize};
 use std::path::Path;
 
@@ -16,6 +19,33 @@ pub struct EvalContext<'a> {
     pub state: &'a State,
     pub config: &'a Config,
     pub root: &'a std::path::Path,
+    blocking_escalation: std::sync::OnceLock<Option<EscalationItem>>,
+}
+
+impl<'a> EvalContext<'a> {
+    pub fn new(
+        feature: &'a Feature,
+        state: &'a State,
+        config: &'a Config,
+        root: &'a std::path::Path,
+    ) -> Self {
+        Self {
+            feature,
+            state,
+            config,
+            root,
+            blocking_escalation: std::sync::OnceLock::new(),
+        }
+    }
+
+    /// The open escalation holding the feature back (see
+    /// [`escalation::blocking`]), read on first use and reused for the rest
+    /// of the evaluation.
+    pub fn blocking_escalation(&self) -> Option<&EscalationItem> {
+        self.blocking_escalation
+            .get_or_init(|| escalation::blocking(self.root, self.feature).ok().flatten())
+            .as_ref()
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -30,6 +60,9 @@ pub struct Classification {
     pub description: Option<String>,
     pub current_phase: Phase,
     pub action: ActionType,
+    /// One sentence naming the condition that selected `action` — the
+    /// matched rule's rationale, or the fallback when no rule matched.
+    pub rationale: String,
     pub message: String,
     pub next_command: String,
     pub output_path: Option<String>,
@@ -41,6 +74,20 @@ pub struct Classification {
     /// Advisory hint for directive consumers: suggested timeout budget in
     /// minutes. Included in directive output as consumer metadata.
     pub timeout_minutes: u32,
+    /// Approved artifacts an upstream artifact changed under after their
+    /// approval — candidates for re-review. See
+    /// [`crate::artifact::stale_artifacts`].
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub stale_artifacts: Vec<ArtifactType>,
+    /// ID of the open escalation a `wait_for_approval` directive is waiting
+    /// on, so consumers can link straight to it. See
+    /// [`crate::escalation::blocking`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub blocked_by_escalation: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub escalation_kind: Option<EscalationKind>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub escalation_title: Option<String>,
 }
 
 // ---------------------------------------------------------------------------
@@ -52,6 +99,8 @@ pub struct Rule {
     pub id: &'static str,
     pub condition: fn(&EvalContext) -> bool,
     pub action: ActionType,
+    /// Why this rule fires, phrased as the state its condition matches.
+    pub rationale: &'static str,
     pub message: fn(&EvalContext) -> String,
     pub next_command: fn(&EvalContext) -> String,
     pub output_path: Option<fn(&EvalContext) -> String>,
@@ -73,14 +122,21 @@ impl Classifier {
     }
 
     pub fn classify(&self, ctx: &EvalContext) -> Classification {
+        let stale_artifacts = stale_artifacts(ctx.root, &ctx.feature.artifacts);
         for rule in &self.rules {
             if (rule.condition)(ctx) {
+                let escalation = if rule.action == ActionType::WaitForApproval {
+                    ctx.blocking_escalation()
+                } else {
+                    None
+                };
                 return Classification {
                     feature: ctx.feature.slug.clone(),
                     title: ctx.feature.title.clone(),
                     description: ctx.feature.description.clone(),
                     current_phase: ctx.feature.phase,
                     action: rule.action,
+                    rationale: rule.rationale.to_string(),
                     message: (rule.message)(ctx),
                     next_command: (rule.next_command)(ctx),
                     output_path: rule.output_path.map(|f| f(ctx)),
@@ -88,6 +144,10 @@ impl Classifier {
                     task_id: rule.task_id.map(|f| f(ctx)),
                     is_heavy: rule.action.is_heavy(),
                     timeout_minutes: rule.action.timeout_minutes(),
+                    stale_artifacts,
+                    blocked_by_escalation: escalation.as_ref().map(|e| e.id.clone()),
+                    escalation_kind: escalation.as_ref().map(|e| e.kind.clone()),
+                    escalation_title: escalation.map(|e| e.title.clone()),
                 };
             }
         }
@@ -99,6 +159,7 @@ impl Classifier {
             description: ctx.feature.description.clone(),
             current_phase: ctx.feature.phase,
             action: ActionType::Done,
+            rationale: "no rule matched the feature's current state".to_string(),
             message: format!("Feature '{}' has no pending actions", ctx.feature.slug),
             next_command: String::new(),
             output_path: None,
@@ -106,10 +167,34 @@ impl Classifier {
             task_id: None,
             is_heavy: false,
             timeout_minutes: 0,
+            stale_artifacts,
+            blocked_by_escalation: None,
+            escalation_kind: None,
+            escalation_title: None,
         }
     }
 }
 
+// ---------------------------------------------------------------------------
+// Batch classification
+// ---------------------------------------------------------------------------
+
+/// Classify every active (non-archived) feature against a single load of the
+/// project config and state, so all directives see the same snapshot.
+pub fn next_all(root: &Path) -> Result<Vec<(String, Classification)>> {
+    let config = Config::load(root)?;
+    let state = State::load(root)?;
+    let classifier = Classifier::new(default_rules());
+    Ok(Feature::list(root)?
+        .iter()
+        .filter(|f| !f.archived)
+        .map(|feature| {
+            let ctx = EvalContext::new(feature, &state, &config, root);
+            (feature.slug.clone(), classifier.classify(&ctx))
+        })
+        .collect())
+}
+
 // ---------------------------------------------------------------------------
 // Auto-transition helper
 // ---------------------------------------------------------------------------
@@ -118,6 +203,9 @@ impl Classifier {
 /// task completed, etc.). If the classifier determines a phase transition is
 /// ready, execute it automatically.
 ///
+/// Drafts whose `auto_approve` policy permits it are approved first (see
+/// [`crate::artifact::apply_auto_approve`]), so they can carry the transition.
+///
 /// Returns `Some(phase_name)` if a transition occurred, `None` otherwise.
 ///
 /// This implements the CLAUDE.md contract: "Phases advance from artifact state,
@@ -125,18 +213,16 @@ impl Classifier {
 pub fn try_auto_transition(root: &Path, slug: &str) -> Option<String> {
     let config = Config::load(root).ok()?;
     let state = State::load(root).ok()?;
-    let feature = Feature::load(root, slug).ok()?;
-
-    let ctx = EvalContext {
-        feature: &feature,
-        state: &state,
-        config: &config,
-        root,
-    };
+    let mut feature = Feature::load(root, slug).ok()?;
+
+    if !crate::artifact::apply_auto_approve(root, &mut feature, &config).is_empty() {
+        feature.save(root).ok()?;
+    }
+
+    let ctx = EvalContext::new(&feature, &state, &config, root);
     let classification = Classifier::new(default_rules()).classify(&ctx);
 
     if let Some(target_phase) = classification.transition_to {
-        let mut feature = feature;
         if feature.transition(target_phase, &config).is_ok() && feature.save(root).is_ok() {
             // Emit feature_phase_advanced for implementation phase or later — non-fatal.
             if matches!(
@@ -160,3 +246,82 @@ pub fn try_auto_transition(root: &Path, slug: &str) -> Option<String> {
     }
     None
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn auto_approve_policy_advances_draft_spec_unless_blocked() {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path();
+        let mut config = Config::new("test");
+        config
+            .auto_approve
+            .insert(ArtifactType::Spec, crate::config::AutoApprovePolicy::Always);
+        config.save(root).unwrap();
+        State::new("test").save(root).unwrap();
+
+        for slug in ["eligible", "blocked"] {
+            let mut feature = Feature::create(root, slug, slug).unwrap();
+            feature.mark_artifact_draft(ArtifactType::Spec).unwrap();
+            if slug == "blocked" {
+                crate::comment::add_comment(
+                    &mut feature.comments,
+                    &mut feature.next_comment_seq,
+                    "spec misses the error cases",
+                    Some(crate::comment::CommentFlag::Blocker),
+                    crate::comment::CommentTarget::Artifact {
+                        artifact_type: ArtifactType::Spec,
+                    },
+                    None,
+                );
+            }
+            feature.save(root).unwrap();
+        }
+
+        assert_eq!(
+            try_auto_transition(root, "eligible").as_deref(),
+            Some("specified")
+        );
+        let eligible = Feature::load(root, "eligible").unwrap();
+        assert!(eligible.artifact(ArtifactType::Spec).unwrap().is_approved());
+
+        assert_eq!(try_auto_transition(root, "blocked"), None);
+        let blocked = Feature::load(root, "blocked").unwrap();
+        assert_eq!(blocked.phase, Phase::Draft);
+        assert!(!blocked.artifact(ArtifactType::Spec).unwrap().is_approved());
+    }
+
+    #[test]
+    fn next_all_matches_per_feature_classification() {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path();
+        Config::new("test").save(root).unwrap();
+        State::new("test").save(root).unwrap();
+        Feature::create(root, "auth", "Auth").unwrap();
+        let mut api = Feature::create(root, "api", "API").unwrap();
+        api.phase = Phase::Implementation;
+        api.save(root).unwrap();
+        Feature::create(root, "old", "Old").unwrap();
+        Feature::archive(root, "old").unwrap();
+
+        let batch = next_all(root).unwrap();
+        let mut slugs: Vec<&str> = batch.iter().map(|(s, _)| s.as_str()).collect();
+        slugs.sort();
+        assert_eq!(slugs, ["api", "auth"]);
+
+        let config = Config::load(root).unwrap();
+        let state = State::load(root).unwrap();
+        let classifier = Classifier::new(default_rules());
+        for (slug, batched) in &batch {
+            let feature = Feature::load(root, slug).unwrap();
+            let single = classifier.classify(&EvalContext::new(&feature, &state, &config, root));
+            assert_eq!(
+                serde_json::to_value(batched).unwrap(),
+                serde_json::to_value(&single).unwrap()
+            );
+        }
+    }
+}
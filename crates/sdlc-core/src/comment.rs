@@ -65,6 +65,34 @@ pub struct Comment {
     pub flag: Option<CommentFlag>,
     pub target: CommentTarget,
     pub created_at: DateTime<Utc>,
+    /// The comment this one replies to. `None` for the root of a thread.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent_id: Option<String>,
+}
+
+impl Comment {
+    pub fn is_root(&self) -> bool {
+        self.parent_id.is_none()
+    }
+
+    /// Whether this comment holds up the pipeline: a root flagged blocker or
+    /// question. A reply's flag is informational — its thread stays open
+    /// until the root itself is resolved.
+    pub fn is_blocking(&self) -> bool {
+        self.is_root()
+            && matches!(
+                self.flag,
+                Some(CommentFlag::Blocker) | Some(CommentFlag::Question)
+            )
+    }
+}
+
+/// A comment with its replies, nested in the order they were added.
+#[derive(Debug, Clone, Serialize)]
+pub struct CommentThread {
+    #[serde(flatten)]
+    pub comment: Comment,
+    pub replies: Vec<CommentThread>,
 }
 
 // ---------------------------------------------------------------------------
@@ -93,18 +121,74 @@ pub fn add_comment(
         flag,
         target,
         created_at: Utc::now(),
+        parent_id: None,
     });
     id
 }
 
-/// Remove a comment by ID. Returns `true` if found and removed, `false` if not found.
+/// Append a reply to `parent_id` and return its ID, or `None` if the parent
+/// does not exist. The reply inherits the parent's target so task filters
+/// keep threads whole.
+pub fn add_reply(
+    comments: &mut Vec<Comment>,
+    seq: &mut u32,
+    parent_id: &str,
+    body: impl Into<String>,
+    flag: Option<CommentFlag>,
+    author: Option<String>,
+) -> Option<String> {
+    let target = comments.iter().find(|c| c.id == parent_id)?.target.clone();
+    let id = add_comment(comments, seq, body, flag, target, author);
+    if let Some(reply) = comments.last_mut() {
+        reply.parent_id = Some(parent_id.to_string());
+    }
+    Some(id)
+}
+
+/// Remove a comment and every reply beneath it. Returns `true` if found and
+/// removed, `false` if not found.
 pub fn resolve_comment(comments: &mut Vec<Comment>, id: &str) -> bool {
-    if let Some(pos) = comments.iter().position(|c| c.id == id) {
-        comments.remove(pos);
-        true
-    } else {
-        false
+    if !comments.iter().any(|c| c.id == id) {
+        return false;
+    }
+    let mut removed = vec![id.to_string()];
+    // Replies always follow their parent, so one pass collects the subtree.
+    for c in comments.iter() {
+        if let Some(parent) = &c.parent_id {
+            if removed.contains(parent) {
+                removed.push(c.id.clone());
+            }
+        }
     }
+    comments.retain(|c| !removed.contains(&c.id));
+    true
+}
+
+/// Nest `comments` into threads. Roots and replies keep the order they were
+/// added; a reply whose parent is absent (e.g. filtered out) becomes a root.
+pub fn threads(comments: &[Comment]) -> Vec<CommentThread> {
+    fn children(comments: &[Comment], parent: &str) -> Vec<CommentThread> {
+        comments
+            .iter()
+            .filter(|c| c.parent_id.as_deref() == Some(parent))
+            .map(|c| CommentThread {
+                comment: c.clone(),
+                replies: children(comments, &c.id),
+            })
+            .collect()
+    }
+
+    comments
+        .iter()
+        .filter(|c| match &c.parent_id {
+            Some(parent) => !comments.iter().any(|p| &p.id == parent),
+            None => true,
+        })
+        .map(|c| CommentThread {
+            comment: c.clone(),
+            replies: children(comments, &c.id),
+        })
+        .collect()
 }
 
 // ---------------------------------------------------------------------------
@@ -229,4 +313,118 @@ mod tests {
         assert_eq!(comments[1].body, "third");
         assert_eq!(comments[1].id, "C3");
     }
+
+    #[test]
+    fn reply_nests_under_its_parent() {
+        let mut comments: Vec<Comment> = Vec::new();
+        let mut seq: u32 = 0;
+        let task = CommentTarget::Task {
+            task_id: "T1".to_string(),
+        };
+        add_comment(&mut comments, &mut seq, "root", None, task, None); // C1
+        add_comment(
+            &mut comments,
+            &mut seq,
+            "other",
+            None,
+            CommentTarget::Feature,
+            None,
+        ); // C2
+        let reply = add_reply(&mut comments, &mut seq, "C1", "reply", None, None).unwrap();
+        let nested = add_reply(&mut comments, &mut seq, &reply, "nested", None, None).unwrap();
+        assert_eq!((reply.as_str(), nested.as_str()), ("C3", "C4"));
+        assert_eq!(comments[2].parent_id.as_deref(), Some("C1"));
+        assert_eq!(comments[2].target.to_string(), "task:T1");
+        assert!(add_reply(&mut comments, &mut seq, "C99", "orphan", None, None).is_none());
+
+        let tree = threads(&comments);
+        assert_eq!(tree.len(), 2);
+        assert_eq!(tree[0].comment.id, "C1");
+        assert_eq!(tree[0].replies[0].comment.id, "C3");
+        assert_eq!(tree[0].replies[0].replies[0].comment.id, "C4");
+        assert!(tree[1].replies.is_empty());
+
+        let json = serde_json::to_value(&tree[0]).unwrap();
+        assert_eq!(json["id"], "C1");
+        assert_eq!(json["replies"][0]["parent_id"], "C1");
+    }
+
+    #[test]
+    fn threads_preserve_order() {
+        let mut comments: Vec<Comment> = Vec::new();
+        let mut seq: u32 = 0;
+        for body in ["a", "b"] {
+            add_comment(
+                &mut comments,
+                &mut seq,
+                body,
+                None,
+                CommentTarget::Feature,
+                None,
+            );
+        }
+        // Replies interleaved across threads: C3 → C2, C4 → C1, C5 → C2.
+        add_reply(&mut comments, &mut seq, "C2", "b1", None, None);
+        add_reply(&mut comments, &mut seq, "C1", "a1", None, None);
+        add_reply(&mut comments, &mut seq, "C2", "b2", None, None);
+
+        let order: Vec<(String, Vec<String>)> = threads(&comments)
+            .into_iter()
+            .map(|t| {
+                let replies = t.replies.into_iter().map(|r| r.comment.body).collect();
+                (t.comment.body, replies)
+            })
+            .collect();
+        assert_eq!(
+            order,
+            vec![
+                ("a".to_string(), vec!["a1".to_string()]),
+                ("b".to_string(), vec!["b1".to_string(), "b2".to_string()]),
+            ]
+        );
+    }
+
+    #[test]
+    fn only_root_blockers_block_and_resolving_root_closes_thread() {
+        let mut comments: Vec<Comment> = Vec::new();
+        let mut seq: u32 = 0;
+        add_comment(
+            &mut comments,
+            &mut seq,
+            "fyi root",
+            Some(CommentFlag::Fyi),
+            CommentTarget::Feature,
+            None,
+        );
+        add_reply(
+            &mut comments,
+            &mut seq,
+            "C1",
+            "blocker reply",
+            Some(CommentFlag::Blocker),
+            None,
+        );
+        assert!(!comments.iter().any(Comment::is_blocking));
+
+        add_comment(
+            &mut comments,
+            &mut seq,
+            "real blocker",
+            Some(CommentFlag::Blocker),
+            CommentTarget::Feature,
+            None,
+        ); // C3
+        add_reply(&mut comments, &mut seq, "C3", "working on it", None, None); // C4
+        assert!(comments.iter().any(Comment::is_blocking));
+
+        // Resolving the reply leaves the thread open.
+        assert!(resolve_comment(&mut comments, "C4"));
+        assert!(comments.iter().any(Comment::is_blocking));
+
+        add_reply(&mut comments, &mut seq, "C3", "done", None, None); // C5
+        assert!(resolve_comment(&mut comments, "C3"));
+        assert!(!comments.iter().any(Comment::is_blocking));
+        let ids: Vec<_> = comments.iter().map(|c| c.id.as_str()).collect();
+        assert_eq!(ids, ["C1", "C2"]);
+    }
 }
@@ -2,8 +2,9 @@ use crate::error::{Result, SdlcError};
 use crate::paths;
 use crate::types::{ArtifactType, Phase};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::path::Path;
+use std::time::Duration;
 
 // ---------------------------------------------------------------------------
 // ConfigWarning / WarnLevel
@@ -58,6 +59,128 @@ impl Default for QualityConfig {
     }
 }
 
+// ---------------------------------------------------------------------------
+// SecretsBackendConfig
+// ---------------------------------------------------------------------------
+
+/// Which [`crate::secrets_backend::SecretsBackend`] serves secret envs.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SecretsBackendKind {
+    /// Age-encrypted files in `.sdlc/secrets/envs/`.
+    #[default]
+    Age,
+    /// Read-only `SDLC_SECRET_<ENV>__<KEY>` process variables.
+    Env,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SecretsBackendConfig {
+    #[serde(default)]
+    pub backend: SecretsBackendKind,
+    /// Refuse to draft an artifact whose content looks like it contains a
+    /// secret, instead of only warning.
+    #[serde(default)]
+    pub block_in_artifacts: bool,
+}
+
+// ---------------------------------------------------------------------------
+// AutoApprovePolicy
+// ---------------------------------------------------------------------------
+
+/// Whether a drafted artifact may advance without a human approval. Written
+/// in YAML as `never`, `always`, or `if_score_above(<n>)`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum AutoApprovePolicy {
+    #[default]
+    Never,
+    /// Approve once the feature has quality scores and every one is at
+    /// least `n` (the same bar as [`crate::feature::Feature::all_scores_above`]).
+    IfScoreAbove(u32),
+    Always,
+}
+
+impl std::str::FromStr for AutoApprovePolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let s = s.trim();
+        match s {
+            "never" => return Ok(Self::Never),
+            "always" => return Ok(Self::Always),
+            _ => {}
+        }
+        s.strip_prefix("if_score_above(")
+            .and_then(|rest| rest.strip_suffix(')'))
+            .and_then(|n| n.trim().parse().ok())
+            .map(Self::IfScoreAbove)
+            .ok_or_else(|| {
+                format!("invalid auto_approve policy '{s}' (expected never, always, or if_score_above(<n>))")
+            })
+    }
+}
+
+impl std::fmt::Display for AutoApprovePolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Never => f.write_str("never"),
+            Self::IfScoreAbove(n) => write!(f, "if_score_above({n})"),
+            Self::Always => f.write_str("always"),
+        }
+    }
+}
+
+impl TryFrom<String> for AutoApprovePolicy {
+    type Error = String;
+
+    fn try_from(s: String) -> std::result::Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl From<AutoApprovePolicy> for String {
+    fn from(p: AutoApprovePolicy) -> Self {
+        p.to_string()
+    }
+}
+
+// ---------------------------------------------------------------------------
+// ModelRates
+// ---------------------------------------------------------------------------
+
+/// Token prices for one model under `pricing:`, in USD per million tokens.
+/// Entries override the agent's built-in rates for the same model name.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct ModelRates {
+    pub input: f64,
+    pub output: f64,
+    #[serde(default)]
+    pub cache_write: f64,
+    #[serde(default)]
+    pub cache_read: f64,
+}
+
+impl ModelRates {
+    /// Every rate must be a finite, non-negative number.
+    pub fn check(&self, model: &str) -> Result<()> {
+        for (field, rate) in [
+            ("input", self.input),
+            ("output", self.output),
+            ("cache_write", self.cache_write),
+            ("cache_read", self.cache_read),
+        ] {
+            if !rate.is_finite() || rate < 0.0 {
+                return Err(SdlcError::InvalidPricing {
+                    model: model.to_string(),
+                    field,
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
 // ---------------------------------------------------------------------------
 // PhaseConfig
 // ---------------------------------------------------------------------------
@@ -160,6 +283,76 @@ pub struct ProjectConfig {
     pub description: Option<String>,
 }
 
+// ---------------------------------------------------------------------------
+// HttpConfig
+// ---------------------------------------------------------------------------
+
+/// HTTP tuning for the `sdlc ui` server. Unset fields use the defaults below,
+/// which suit running behind a tunnel: Cloudflare drops connections that sit
+/// idle for 100 seconds and already cuts off responses slower than that.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HttpConfig {
+    /// Seconds between SSE keep-alive comments.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub keep_alive_secs: Option<u64>,
+    /// Largest request body accepted, in bytes; bigger ones get a 413.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body_limit_bytes: Option<usize>,
+    /// Seconds a request may take to produce response headers; `0` disables.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_timeout_secs: Option<u64>,
+    /// Seconds allowed to receive a request body; `0` disables.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub read_timeout_secs: Option<u64>,
+}
+
+pub const DEFAULT_SSE_KEEP_ALIVE_SECS: u64 = 15;
+/// Large enough for big artifact uploads.
+pub const DEFAULT_BODY_LIMIT_BYTES: usize = 32 * 1024 * 1024;
+/// Off: the tunnel already bounds slow responses, and local sync tool runs
+/// may legitimately take longer.
+pub const DEFAULT_REQUEST_TIMEOUT_SECS: u64 = 0;
+pub const DEFAULT_READ_TIMEOUT_SECS: u64 = 300;
+
+impl HttpConfig {
+    /// Fields set here win; the rest come from `fallback`.
+    pub fn or(self, fallback: HttpConfig) -> HttpConfig {
+        HttpConfig {
+            keep_alive_secs: self.keep_alive_secs.or(fallback.keep_alive_secs),
+            body_limit_bytes: self.body_limit_bytes.or(fallback.body_limit_bytes),
+            request_timeout_secs: self.request_timeout_secs.or(fallback.request_timeout_secs),
+            read_timeout_secs: self.read_timeout_secs.or(fallback.read_timeout_secs),
+        }
+    }
+
+    /// SSE keep-alive interval; never below one second.
+    pub fn keep_alive(&self) -> Duration {
+        Duration::from_secs(
+            self.keep_alive_secs
+                .unwrap_or(DEFAULT_SSE_KEEP_ALIVE_SECS)
+                .max(1),
+        )
+    }
+
+    pub fn body_limit(&self) -> usize {
+        self.body_limit_bytes.unwrap_or(DEFAULT_BODY_LIMIT_BYTES)
+    }
+
+    /// `None` when disabled.
+    pub fn request_timeout(&self) -> Option<Duration> {
+        let secs = self
+            .request_timeout_secs
+            .unwrap_or(DEFAULT_REQUEST_TIMEOUT_SECS);
+        (secs > 0).then(|| Duration::from_secs(secs))
+    }
+
+    /// `None` when disabled.
+    pub fn read_timeout(&self) -> Option<Duration> {
+        let secs = self.read_timeout_secs.unwrap_or(DEFAULT_READ_TIMEOUT_SECS);
+        (secs > 0).then(|| Duration::from_secs(secs))
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Config (top-level)
 // ---------------------------------------------------------------------------
@@ -182,8 +375,42 @@ pub struct Config {
     /// UI can pre-populate the port input across restarts.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub app_port: Option<u16>,
+    /// Upper bound on agent runs the server executes at once; further runs
+    /// wait in a `queued` state. Defaults to [`DEFAULT_MAX_CONCURRENT_RUNS`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_concurrent_runs: Option<usize>,
+    /// How many times the app-tunnel proxy retries a GET/HEAD whose
+    /// connection to the dev server was refused (e.g. mid-restart). Defaults
+    /// to [`DEFAULT_APP_PROXY_RETRIES`]; `0` disables retrying.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub app_proxy_retries: Option<u32>,
+    /// Where secret env values come from. Defaults to the age-encrypted
+    /// files in `.sdlc/secrets/`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub secrets: Option<SecretsBackendConfig>,
+    /// Per-model token prices used for cost estimates, keyed by model name
+    /// (or name prefix). Models not listed keep their built-in rates.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub pricing: BTreeMap<String, ModelRates>,
+    /// Per-artifact-type policies that let drafts skip human approval.
+    /// Types not listed are never auto-approved.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub auto_approve: BTreeMap<ArtifactType, AutoApprovePolicy>,
+    /// Keep-alive, body size, and timeout settings for the web server.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub http: Option<HttpConfig>,
+    /// Branch that features merge into. Defaults to
+    /// [`crate::git::DEFAULT_BASE_BRANCH`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base_branch: Option<String>,
 }
 
+/// Concurrent agent runs allowed when `max_concurrent_runs` is unset.
+pub const DEFAULT_MAX_CONCURRENT_RUNS: usize = 4;
+
+/// App-tunnel proxy retries when `app_proxy_retries` is unset.
+pub const DEFAULT_APP_PROXY_RETRIES: u32 = 3;
+
 fn default_version() -> u32 {
     1
 }
@@ -201,6 +428,13 @@ impl Config {
             quality: None,
             sdlc_version: None,
             app_port: None,
+            max_concurrent_runs: None,
+            app_proxy_retries: None,
+            secrets: None,
+            pricing: BTreeMap::new(),
+            auto_approve: BTreeMap::new(),
+            http: None,
+            base_branch: None,
         }
     }
 
@@ -211,6 +445,9 @@ impl Config {
         }
         let data = std::fs::read_to_string(&path)?;
         let cfg: Config = serde_yaml::from_str(&data)?;
+        for (model, rates) in &cfg.pricing {
+            rates.check(model)?;
+        }
         Ok(cfg)
     }
 
@@ -220,6 +457,38 @@ impl Config {
         crate::io::atomic_write(&path, data.as_bytes())
     }
 
+    /// Effective concurrent-run limit; never less than one.
+    pub fn max_concurrent_runs(&self) -> usize {
+        self.max_concurrent_runs
+            .unwrap_or(DEFAULT_MAX_CONCURRENT_RUNS)
+            .max(1)
+    }
+
+    /// Effective base branch for merge checks.
+    pub fn base_branch(&self) -> &str {
+        self.base_branch
+            .as_deref()
+            .unwrap_or(crate::git::DEFAULT_BASE_BRANCH)
+    }
+
+    /// The `auto_approve` policy for an artifact type (`never` when unset).
+    pub fn auto_approve_policy(&self, artifact_type: ArtifactType) -> AutoApprovePolicy {
+        self.auto_approve
+            .get(&artifact_type)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Whether `secrets.block_in_artifacts` turns secret warnings into errors.
+    pub fn block_secrets_in_artifacts(&self) -> bool {
+        self.secrets.as_ref().is_some_and(|s| s.block_in_artifacts)
+    }
+
+    /// Effective app-tunnel proxy retry count.
+    pub fn app_proxy_retries(&self) -> u32 {
+        self.app_proxy_retries.unwrap_or(DEFAULT_APP_PROXY_RETRIES)
+    }
+
     // -----------------------------------------------------------------------
     // Validation
     // -----------------------------------------------------------------------
@@ -227,6 +496,100 @@ impl Config {
     pub fn validate(&self) -> Vec<ConfigWarning> {
         Vec::new()
     }
+
+    // -----------------------------------------------------------------------
+    // Diffing
+    // -----------------------------------------------------------------------
+
+    /// Every leaf field that differs between `self` and `after`, in path
+    /// order. Objects are compared key by key; arrays and scalars as wholes.
+    pub fn diff(&self, after: &Config) -> Result<Vec<FieldChange>> {
+        let mut changes = Vec::new();
+        diff_values(
+            "",
+            &serde_json::to_value(self)?,
+            &serde_json::to_value(after)?,
+            &mut changes,
+        );
+        Ok(changes)
+    }
+
+    /// What this config customizes: its [`diff`](Config::diff) against the
+    /// scaffolded `Config::new` for the same project name.
+    /// `sdlc_version` is bookkeeping written by `sdlc init`, not a
+    /// customization, so it is left out.
+    pub fn diff_from_defaults(&self) -> Result<Vec<FieldChange>> {
+        let mut defaults = Config::new(self.project.name.clone());
+        defaults.sdlc_version = self.sdlc_version.clone();
+        defaults.diff(self)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// FieldChange
+// ---------------------------------------------------------------------------
+
+/// One changed config field, addressed by dotted path (e.g.
+/// `project.description`). A field that was or became unset is `null`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FieldChange {
+    pub path: String,
+    pub old: serde_json::Value,
+    pub new: serde_json::Value,
+}
+
+/// How a [`FieldChange`] alters its field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChangeKind {
+    /// Unset before, set after.
+    Added,
+    /// Set before, unset after.
+    Removed,
+    Changed,
+}
+
+impl FieldChange {
+    pub fn kind(&self) -> ChangeKind {
+        match (&self.old, &self.new) {
+            (serde_json::Value::Null, _) => ChangeKind::Added,
+            (_, serde_json::Value::Null) => ChangeKind::Removed,
+            _ => ChangeKind::Changed,
+        }
+    }
+}
+
+fn diff_values(
+    path: &str,
+    old: &serde_json::Value,
+    new: &serde_json::Value,
+    changes: &mut Vec<FieldChange>,
+) {
+    use serde_json::Value;
+    match (old, new) {
+        (Value::Object(a), Value::Object(b)) => {
+            let keys: std::collections::BTreeSet<&String> = a.keys().chain(b.keys()).collect();
+            for key in keys {
+                let child = if path.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{path}.{key}")
+                };
+                diff_values(
+                    &child,
+                    a.get(key).unwrap_or(&Value::Null),
+                    b.get(key).unwrap_or(&Value::Null),
+                    changes,
+                );
+            }
+        }
+        _ if old != new => changes.push(FieldChange {
+            path: path.to_string(),
+            old: old.clone(),
+            new: new.clone(),
+        }),
+        _ => {}
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -237,6 +600,59 @@ impl Config {
 mod tests {
     use super::*;
 
+    #[test]
+    fn diff_reports_changed_leaves_by_path() {
+        let before = Config::new("test-project");
+        assert!(before.diff(&before.clone()).unwrap().is_empty());
+
+        let mut after = before.clone();
+        after.project.description = Some("A tool".into());
+        after.project.name = "renamed".into();
+        assert_eq!(
+            before.diff(&after).unwrap(),
+            [
+                FieldChange {
+                    path: "project.description".into(),
+                    old: serde_json::Value::Null,
+                    new: "A tool".into(),
+                },
+                FieldChange {
+                    path: "project.name".into(),
+                    old: "test-project".into(),
+                    new: "renamed".into(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn diff_from_defaults_reports_only_the_customized_platform() {
+        let yaml = r#"
+version: 1
+project:
+  name: my-project
+platform:
+  commands:
+    deploy:
+      description: Deploy the app
+      script: .sdlc/platform/deploy.sh
+"#;
+        let cfg: Config = serde_yaml::from_str(yaml).unwrap();
+        let changes = cfg.diff_from_defaults().unwrap();
+        assert_eq!(changes.len(), 1, "{changes:?}");
+        assert_eq!(changes[0].path, "platform");
+        assert_eq!(changes[0].kind(), ChangeKind::Added);
+        assert_eq!(
+            changes[0].new["commands"]["deploy"]["script"],
+            ".sdlc/platform/deploy.sh"
+        );
+
+        assert!(Config::new("my-project")
+            .diff_from_defaults()
+            .unwrap()
+            .is_empty());
+    }
+
     #[test]
     fn default_config_roundtrip() {
         let cfg = Config::new("test-project");
@@ -246,6 +662,21 @@ mod tests {
         assert_eq!(parsed.version, 1);
     }
 
+    #[test]
+    fn max_concurrent_runs_defaults_and_clamps() {
+        let mut cfg = Config::new("test-project");
+        assert_eq!(cfg.max_concurrent_runs(), DEFAULT_MAX_CONCURRENT_RUNS);
+        let yaml = "version: 1\nproject:\n  name: p\nmax_concurrent_runs: 2\n";
+        assert_eq!(
+            serde_yaml::from_str::<Config>(yaml)
+                .unwrap()
+                .max_concurrent_runs(),
+            2
+        );
+        cfg.max_concurrent_runs = Some(0);
+        assert_eq!(cfg.max_concurrent_runs(), 1);
+    }
+
     #[test]
     fn platform_config_roundtrip() {
         let mut cmds = HashMap::new();
@@ -348,4 +779,99 @@ quality:
         let out = serde_yaml::to_string(&cfg).unwrap();
         assert!(!out.contains("quality"));
     }
+
+    fn write_config(dir: &Path, yaml: &str) {
+        let path = paths::config_path(dir);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, yaml).unwrap();
+    }
+
+    #[test]
+    fn pricing_section_loads_with_cache_rates_optional() {
+        let dir = tempfile::TempDir::new().unwrap();
+        write_config(
+            dir.path(),
+            "version: 1\nproject:\n  name: p\npricing:\n  claude-sonnet-4:\n    input: 2.5\n    output: 12\n    cache_read: 0.25\n",
+        );
+        let cfg = Config::load(dir.path()).unwrap();
+        assert_eq!(
+            cfg.pricing["claude-sonnet-4"],
+            ModelRates {
+                input: 2.5,
+                output: 12.0,
+                cache_write: 0.0,
+                cache_read: 0.25,
+            }
+        );
+        assert!(!serde_yaml::to_string(&Config::new("p"))
+            .unwrap()
+            .contains("pricing"));
+    }
+
+    #[test]
+    fn negative_pricing_rate_fails_load() {
+        let dir = tempfile::TempDir::new().unwrap();
+        write_config(
+            dir.path(),
+            "version: 1\nproject:\n  name: p\npricing:\n  gpt-4.1:\n    input: 2\n    output: -8\n",
+        );
+        let err = Config::load(dir.path()).unwrap_err();
+        assert!(
+            matches!(err, SdlcError::InvalidPricing { ref model, field: "output" } if model == "gpt-4.1"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn auto_approve_section_parses_policies_and_round_trips() {
+        let dir = tempfile::TempDir::new().unwrap();
+        write_config(
+            dir.path(),
+            "version: 1\nproject:\n  name: p\nauto_approve:\n  spec: if_score_above(80)\n  design: always\n  tasks: never\n",
+        );
+        let cfg = Config::load(dir.path()).unwrap();
+        assert_eq!(
+            cfg.auto_approve_policy(ArtifactType::Spec),
+            AutoApprovePolicy::IfScoreAbove(80)
+        );
+        assert_eq!(
+            cfg.auto_approve_policy(ArtifactType::Design),
+            AutoApprovePolicy::Always
+        );
+        assert_eq!(
+            cfg.auto_approve_policy(ArtifactType::Review),
+            AutoApprovePolicy::Never
+        );
+        let yaml = serde_yaml::to_string(&cfg).unwrap();
+        assert!(yaml.contains("spec: if_score_above(80)"), "{yaml}");
+
+        write_config(
+            dir.path(),
+            "version: 1\nproject:\n  name: p\nauto_approve:\n  spec: sometimes\n",
+        );
+        assert!(Config::load(dir.path()).is_err());
+    }
+
+    #[test]
+    fn http_flags_override_config_and_zero_disables_timeouts() {
+        let dir = tempfile::TempDir::new().unwrap();
+        write_config(
+            dir.path(),
+            "version: 1\nproject:\n  name: p\nhttp:\n  body_limit_bytes: 1024\n  request_timeout_secs: 30\n  read_timeout_secs: 0\n",
+        );
+        let from_file = Config::load(dir.path()).unwrap().http.unwrap();
+        let flags = HttpConfig {
+            request_timeout_secs: Some(60),
+            ..Default::default()
+        };
+        let http = flags.or(from_file);
+
+        assert_eq!(http.body_limit(), 1024);
+        assert_eq!(http.request_timeout(), Some(Duration::from_secs(60)));
+        assert_eq!(http.read_timeout(), None);
+        assert_eq!(
+            http.keep_alive(),
+            Duration::from_secs(DEFAULT_SSE_KEEP_ALIVE_SECS)
+        );
+    }
 }
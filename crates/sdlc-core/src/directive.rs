@@ -57,6 +57,7 @@ pub fn build_directive(c: &Classification, slug: &str, root: &Path) -> String {
 
     doc.push_str(&format!("# Directive: {slug}\n\n"));
     doc.push_str(&format!("**Action:** {}\n", c.action));
+    doc.push_str(&format!("**Why:** {}\n", c.rationale));
     doc.push_str(&format!("**Phase:** {}\n", c.current_phase));
     let output_display = c.output_path.as_deref().unwrap_or("—");
     doc.push_str(&format!("**Output:** {output_display}\n"));
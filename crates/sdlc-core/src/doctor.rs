@@ -0,0 +1,571 @@
+//! Consistency checks for the `.sdlc/` tree, backing `sdlc doctor`.
+//!
+//! [`diagnose`] never mutates anything: it parses every YAML file, loads each
+//! feature and milestone manifest, and cross-checks the links between them.
+//! Each [`Problem`] carries a human remediation and, when the repair cannot
+//! lose information, a [`Fix`] that [`repair`] applies. Ambiguous problems
+//! (unparseable YAML, a feature claimed by two milestones, artifact files
+//! that vanished) have no `Fix` and are left for a human.
+
+use crate::config::Config;
+use crate::error::{Result, SdlcError};
+use crate::feature::Feature;
+use crate::milestone::Milestone;
+use crate::state::State;
+use crate::types::ArtifactStatus;
+use crate::{io, paths};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashSet};
+use std::path::Path;
+
+// ---------------------------------------------------------------------------
+// Problem / Fix
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ProblemKind {
+    /// A `.yaml` file is not valid YAML.
+    InvalidYaml,
+    /// Valid YAML that does not deserialize into the expected manifest.
+    InvalidManifest,
+    MissingDir,
+    MissingManifest,
+    MissingState,
+    /// The manifest's `slug` differs from its directory name.
+    SlugMismatch,
+    /// A milestone lists a feature that does not exist.
+    DanglingMilestoneFeature,
+    /// A feature is listed by more than one milestone.
+    DuplicateMilestoneFeature,
+    /// A feature depends on a feature that does not exist.
+    DanglingDependency,
+    /// `state.yaml` references a feature or milestone that does not exist.
+    DanglingStateEntry,
+    /// An artifact is recorded past `missing` but its file is gone.
+    MissingArtifactFile,
+}
+
+/// A repair that cannot lose information.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum Fix {
+    CreateDir { path: String },
+    RebuildState,
+    RemoveMilestoneFeature { milestone: String, feature: String },
+    RemoveDependency { feature: String, dependency: String },
+    RemoveStateFeature { feature: String },
+    RemoveStateMilestone { milestone: String },
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Problem {
+    pub kind: ProblemKind,
+    /// Path relative to the project root.
+    pub path: String,
+    pub message: String,
+    pub remediation: String,
+    /// `None` when the problem needs a human decision.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fix: Option<Fix>,
+}
+
+impl Problem {
+    fn new(
+        kind: ProblemKind,
+        path: impl Into<String>,
+        message: impl Into<String>,
+        remediation: impl Into<String>,
+    ) -> Self {
+        Self {
+            kind,
+            path: path.into(),
+            message: message.into(),
+            remediation: remediation.into(),
+            fix: None,
+        }
+    }
+
+    fn with_fix(mut self, fix: Fix) -> Self {
+        self.fix = Some(fix);
+        self
+    }
+
+    pub fn is_fixable(&self) -> bool {
+        self.fix.is_some()
+    }
+}
+
+// ---------------------------------------------------------------------------
+// diagnose
+// ---------------------------------------------------------------------------
+
+/// Inspect `.sdlc/` and return every problem found, in a stable order.
+pub fn diagnose(root: &Path) -> Result<Vec<Problem>> {
+    if !root.join(paths::SDLC_DIR).is_dir() {
+        return Err(SdlcError::NotInitialized);
+    }
+    let mut problems = Vec::new();
+
+    for dir in paths::SCAFFOLD_DIRS {
+        if !root.join(dir).is_dir() {
+            problems.push(
+                Problem::new(
+                    ProblemKind::MissingDir,
+                    *dir,
+                    format!("directory {dir} is missing"),
+                    "recreate it (`sdlc doctor --fix`)",
+                )
+                .with_fix(Fix::CreateDir {
+                    path: dir.to_string(),
+                }),
+            );
+        }
+    }
+
+    // Unparseable files are reported once here; the typed loads below skip them.
+    let mut broken: HashSet<String> = HashSet::new();
+    let mut yaml_files = Vec::new();
+    collect_yaml_files(&root.join(paths::SDLC_DIR), &mut yaml_files)?;
+    yaml_files.sort();
+    for path in yaml_files {
+        let rel = relative(root, &path);
+        let data = std::fs::read_to_string(&path)?;
+        if let Err(e) = serde_yaml::from_str::<serde_yaml::Value>(&data) {
+            problems.push(Problem::new(
+                ProblemKind::InvalidYaml,
+                &rel,
+                format!("{rel} is not valid YAML: {e}"),
+                format!("fix the syntax by hand or restore it with `git checkout -- {rel}`"),
+            ));
+            broken.insert(rel);
+        }
+    }
+
+    if !broken.contains(paths::CONFIG_FILE) && root.join(paths::CONFIG_FILE).exists() {
+        if let Err(e) = Config::load(root) {
+            problems.push(invalid_manifest(paths::CONFIG_FILE, &e));
+        }
+    }
+
+    let state = if !root.join(paths::STATE_FILE).exists() {
+        problems.push(
+            Problem::new(
+                ProblemKind::MissingState,
+                paths::STATE_FILE,
+                "state.yaml is missing",
+                "rebuild it from the .sdlc/ tree (`sdlc state-rebuild`)",
+            )
+            .with_fix(Fix::RebuildState),
+        );
+        None
+    } else if broken.contains(paths::STATE_FILE) {
+        None
+    } else {
+        match State::load(root) {
+            Ok(s) => Some(s),
+            Err(e) => {
+                problems.push(invalid_manifest(paths::STATE_FILE, &e));
+                None
+            }
+        }
+    };
+
+    let (feature_slugs, features) =
+        load_entities(root, paths::FEATURES_DIR, &broken, &mut problems, |slug| {
+            Feature::load(root, slug).map(|f| (f.slug.clone(), f))
+        })?;
+    let (milestone_slugs, milestones) = load_entities(
+        root,
+        paths::MILESTONES_DIR,
+        &broken,
+        &mut problems,
+        |slug| Milestone::load(root, slug).map(|m| (m.slug.clone(), m)),
+    )?;
+
+    // Milestone → feature links.
+    let mut owners: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+    for m in &milestones {
+        let manifest = relative(root, &paths::milestone_manifest(root, &m.slug));
+        for f in &m.features {
+            if feature_slugs.contains(f) {
+                owners.entry(f.as_str()).or_default().push(&m.slug);
+            } else {
+                problems.push(
+                    Problem::new(
+                        ProblemKind::DanglingMilestoneFeature,
+                        &manifest,
+                        format!("milestone '{}' lists unknown feature '{f}'", m.slug),
+                        format!("remove '{f}' from the milestone's features"),
+                    )
+                    .with_fix(Fix::RemoveMilestoneFeature {
+                        milestone: m.slug.clone(),
+                        feature: f.clone(),
+                    }),
+                );
+            }
+        }
+    }
+    for (feature, ms) in owners.into_iter().filter(|(_, ms)| ms.len() > 1) {
+        problems.push(Problem::new(
+            ProblemKind::DuplicateMilestoneFeature,
+            relative(root, &paths::feature_manifest(root, feature)),
+            format!(
+                "feature '{feature}' belongs to several milestones: {}",
+                ms.join(", ")
+            ),
+            format!(
+                "keep it in one milestone (`sdlc milestone remove-feature <milestone> {feature}`)"
+            ),
+        ));
+    }
+
+    // Feature dependencies and artifact files.
+    for f in &features {
+        let manifest = relative(root, &paths::feature_manifest(root, &f.slug));
+        for dep in f
+            .dependencies
+            .iter()
+            .filter(|d| !feature_slugs.contains(*d))
+        {
+            problems.push(
+                Problem::new(
+                    ProblemKind::DanglingDependency,
+                    &manifest,
+                    format!("feature '{}' depends on unknown feature '{dep}'", f.slug),
+                    format!("remove '{dep}' from the feature's dependencies"),
+                )
+                .with_fix(Fix::RemoveDependency {
+                    feature: f.slug.clone(),
+                    dependency: dep.clone(),
+                }),
+            );
+        }
+        for a in &f.artifacts {
+            let recorded = !matches!(a.status, ArtifactStatus::Missing | ArtifactStatus::Waived);
+            if recorded && !root.join(&a.path).exists() {
+                problems.push(Problem::new(
+                    ProblemKind::MissingArtifactFile,
+                    &a.path,
+                    format!(
+                        "{} artifact of '{}' is {} but {} does not exist",
+                        a.artifact_type, f.slug, a.status, a.path
+                    ),
+                    format!(
+                        "restore the file from git, or rewrite it and run `sdlc artifact draft {} {}`",
+                        f.slug, a.artifact_type
+                    ),
+                ));
+            }
+        }
+    }
+
+    // state.yaml → entities.
+    if let Some(state) = state {
+        for slug in state
+            .active_features
+            .iter()
+            .filter(|s| !paths::feature_dir(root, s).is_dir())
+        {
+            problems.push(
+                Problem::new(
+                    ProblemKind::DanglingStateEntry,
+                    paths::STATE_FILE,
+                    format!("state.yaml lists missing feature '{slug}'"),
+                    format!("remove '{slug}' from active_features"),
+                )
+                .with_fix(Fix::RemoveStateFeature {
+                    feature: slug.clone(),
+                }),
+            );
+        }
+        for slug in state
+            .milestones
+            .iter()
+            .filter(|s| !milestone_slugs.contains(*s))
+        {
+            problems.push(
+                Problem::new(
+                    ProblemKind::DanglingStateEntry,
+                    paths::STATE_FILE,
+                    format!("state.yaml lists missing milestone '{slug}'"),
+                    format!("remove '{slug}' from milestones"),
+                )
+                .with_fix(Fix::RemoveStateMilestone {
+                    milestone: slug.clone(),
+                }),
+            );
+        }
+    }
+
+    Ok(problems)
+}
+
+/// Load every `<dir>/<slug>/manifest.yaml`, reporting missing, unloadable,
+/// and misnamed manifests. Returns the slugs whose directory holds a manifest
+/// (loadable or not, so links to a broken entity are not reported as
+/// dangling) and the entities that loaded.
+fn load_entities<T>(
+    root: &Path,
+    dir: &str,
+    broken: &HashSet<String>,
+    problems: &mut Vec<Problem>,
+    load: impl Fn(&str) -> Result<(String, T)>,
+) -> Result<(HashSet<String>, Vec<T>)> {
+    let mut slugs = HashSet::new();
+    let mut loaded = Vec::new();
+    let base = root.join(dir);
+    if !base.is_dir() {
+        return Ok((slugs, loaded));
+    }
+    let mut entries: Vec<String> = std::fs::read_dir(&base)?
+        .filter_map(|e| e.ok())
+        .filter(|e| e.path().is_dir())
+        .filter_map(|e| e.file_name().into_string().ok())
+        .collect();
+    entries.sort();
+
+    for name in entries {
+        let manifest = format!("{dir}/{name}/{}", paths::MANIFEST_FILE);
+        if !root.join(&manifest).exists() {
+            problems.push(Problem::new(
+                ProblemKind::MissingManifest,
+                format!("{dir}/{name}"),
+                format!("{dir}/{name} has no {}", paths::MANIFEST_FILE),
+                "restore the manifest from git, or delete the directory if it is stray",
+            ));
+            continue;
+        }
+        slugs.insert(name.clone());
+        if broken.contains(&manifest) {
+            continue;
+        }
+        match load(&name) {
+            Ok((slug, entity)) if slug == name => loaded.push(entity),
+            Ok((slug, _)) => problems.push(Problem::new(
+                ProblemKind::SlugMismatch,
+                &manifest,
+                format!("{manifest} declares slug '{slug}'"),
+                format!("set `slug: {name}` or rename the directory to {dir}/{slug}"),
+            )),
+            Err(e) => problems.push(invalid_manifest(&manifest, &e)),
+        }
+    }
+    Ok((slugs, loaded))
+}
+
+fn invalid_manifest(path: &str, err: &SdlcError) -> Problem {
+    Problem::new(
+        ProblemKind::InvalidManifest,
+        path,
+        format!("{path} could not be loaded: {err}"),
+        format!("correct the fields by hand or restore it with `git checkout -- {path}`"),
+    )
+}
+
+fn collect_yaml_files(dir: &Path, out: &mut Vec<std::path::PathBuf>) -> Result<()> {
+    if !dir.is_dir() {
+        return Ok(());
+    }
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            collect_yaml_files(&path, out)?;
+        } else if path.extension().is_some_and(|e| e == "yaml" || e == "yml") {
+            out.push(path);
+        }
+    }
+    Ok(())
+}
+
+fn relative(root: &Path, path: &Path) -> String {
+    path.strip_prefix(root)
+        .unwrap_or(path)
+        .to_string_lossy()
+        .into_owned()
+}
+
+// ---------------------------------------------------------------------------
+// repair
+// ---------------------------------------------------------------------------
+
+/// Apply the [`Fix`] of every fixable problem. Returns how many were applied;
+/// problems without a fix are ignored. Call [`diagnose`] again to see what
+/// remains.
+pub fn repair(root: &Path, problems: &[Problem]) -> Result<usize> {
+    let mut applied = 0;
+    for fix in problems.iter().filter_map(|p| p.fix.as_ref()) {
+        match fix {
+            Fix::CreateDir { path } => io::ensure_dir(&root.join(path))?,
+            Fix::RebuildState => State::rebuild(root)?.save(root)?,
+            Fix::RemoveMilestoneFeature { milestone, feature } => {
+                let mut m = Milestone::load(root, milestone)?;
+                m.features.retain(|f| f != feature);
+                m.save(root)?;
+            }
+            Fix::RemoveDependency {
+                feature,
+                dependency,
+            } => {
+                let mut f = Feature::load(root, feature)?;
+                f.dependencies.retain(|d| d != dependency);
+                f.save(root)?;
+            }
+            Fix::RemoveStateFeature { feature } => {
+                State::update(root, |s| s.remove_active_feature(feature))?;
+            }
+            Fix::RemoveStateMilestone { milestone } => {
+                State::update(root, |s| s.remove_milestone(milestone))?;
+            }
+        }
+        applied += 1;
+    }
+    Ok(applied)
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn init() -> TempDir {
+        let dir = TempDir::new().unwrap();
+        for d in paths::SCAFFOLD_DIRS {
+            std::fs::create_dir_all(dir.path().join(d)).unwrap();
+        }
+        Config::new("test").save(dir.path()).unwrap();
+        State::new("test").save(dir.path()).unwrap();
+        dir
+    }
+
+    fn kinds(problems: &[Problem]) -> Vec<ProblemKind> {
+        problems.iter().map(|p| p.kind).collect()
+    }
+
+    #[test]
+    fn clean_project_has_no_problems() {
+        let dir = init();
+        let root = dir.path();
+        Feature::create(root, "auth", "Auth").unwrap();
+        let mut m = Milestone::create(root, "v1", "V1").unwrap();
+        m.add_feature("auth");
+        m.save(root).unwrap();
+        State::update(root, |s| {
+            s.add_active_feature("auth");
+            s.add_milestone("v1");
+        })
+        .unwrap();
+
+        assert!(diagnose(root).unwrap().is_empty());
+    }
+
+    #[test]
+    fn uninitialized_root_is_an_error() {
+        let dir = TempDir::new().unwrap();
+        assert!(matches!(
+            diagnose(dir.path()),
+            Err(SdlcError::NotInitialized)
+        ));
+    }
+
+    #[test]
+    fn detects_and_repairs_safe_corruptions() {
+        let dir = init();
+        let root = dir.path();
+        let mut auth = Feature::create(root, "auth", "Auth").unwrap();
+        auth.dependencies = vec!["ghost".into()];
+        auth.save(root).unwrap();
+        let mut m = Milestone::create(root, "v1", "V1").unwrap();
+        m.add_feature("auth");
+        m.add_feature("removed-feature");
+        m.save(root).unwrap();
+        State::update(root, |s| {
+            s.add_active_feature("auth");
+            s.add_active_feature("gone");
+            s.add_milestone("v1");
+            s.add_milestone("v0");
+        })
+        .unwrap();
+        std::fs::remove_dir_all(root.join(paths::ARCHIVES_DIR)).unwrap();
+
+        let problems = diagnose(root).unwrap();
+        assert_eq!(
+            kinds(&problems),
+            [
+                ProblemKind::MissingDir,
+                ProblemKind::DanglingMilestoneFeature,
+                ProblemKind::DanglingDependency,
+                ProblemKind::DanglingStateEntry,
+                ProblemKind::DanglingStateEntry,
+            ]
+        );
+        assert!(problems.iter().all(Problem::is_fixable));
+
+        assert_eq!(repair(root, &problems).unwrap(), 5);
+        assert!(diagnose(root).unwrap().is_empty());
+        assert!(root.join(paths::ARCHIVES_DIR).is_dir());
+        assert_eq!(Milestone::load(root, "v1").unwrap().features, ["auth"]);
+        assert!(Feature::load(root, "auth").unwrap().dependencies.is_empty());
+        let state = State::load(root).unwrap();
+        assert_eq!(state.active_features, ["auth"]);
+        assert_eq!(state.milestones, ["v1"]);
+    }
+
+    #[test]
+    fn missing_state_is_rebuilt() {
+        let dir = init();
+        let root = dir.path();
+        Feature::create(root, "auth", "Auth").unwrap();
+        std::fs::remove_file(root.join(paths::STATE_FILE)).unwrap();
+
+        let problems = diagnose(root).unwrap();
+        assert_eq!(kinds(&problems), [ProblemKind::MissingState]);
+        repair(root, &problems).unwrap();
+        assert_eq!(State::load(root).unwrap().active_features, ["auth"]);
+    }
+
+    #[test]
+    fn ambiguous_problems_are_reported_but_left_alone() {
+        let dir = init();
+        let root = dir.path();
+        let mut auth = Feature::create(root, "auth", "Auth").unwrap();
+        auth.artifacts[0].mark_draft();
+        auth.save(root).unwrap();
+        Feature::create(root, "billing", "Billing").unwrap();
+        for slug in ["v1", "v2"] {
+            let mut m = Milestone::create(root, slug, slug).unwrap();
+            m.add_feature("billing");
+            m.save(root).unwrap();
+        }
+        let broken = paths::feature_manifest(root, "billing");
+        std::fs::write(&broken, "slug: billing\ntitle: [unclosed\n").unwrap();
+        std::fs::create_dir_all(paths::feature_dir(root, "stray")).unwrap();
+        State::update(root, |s| {
+            s.add_milestone("v1");
+            s.add_milestone("v2");
+        })
+        .unwrap();
+
+        let problems = diagnose(root).unwrap();
+        assert_eq!(
+            kinds(&problems),
+            [
+                ProblemKind::InvalidYaml,
+                ProblemKind::MissingManifest,
+                ProblemKind::DuplicateMilestoneFeature,
+                ProblemKind::MissingArtifactFile,
+            ]
+        );
+        assert_eq!(problems[0].path, ".sdlc/features/billing/manifest.yaml");
+        assert!(problems.iter().all(|p| !p.is_fixable()));
+
+        let before = std::fs::read_to_string(&broken).unwrap();
+        assert_eq!(repair(root, &problems).unwrap(), 0);
+        assert_eq!(std::fs::read_to_string(&broken).unwrap(), before);
+        assert_eq!(diagnose(root).unwrap().len(), 4);
+    }
+}
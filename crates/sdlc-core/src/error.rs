@@ -11,15 +11,39 @@ pub enum SdlcError {
     #[error("feature already exists: {0}")]
     FeatureExists(String),
 
+    #[error("unknown feature template '{0}' (available: {1})")]
+    FeatureTemplateNotFound(String, String),
+
+    #[error("unknown prompt template '{0}' (available: {1})")]
+    PromptTemplateNotFound(String, String),
+
+    #[error("prompt template placeholder '{{{{{0}}}}}' has no value")]
+    PromptPlaceholderMissing(String),
+
     #[error("milestone not found: {0}")]
     MilestoneNotFound(String),
 
     #[error("milestone already exists: {0}")]
     MilestoneExists(String),
 
+    #[error("invalid milestone bundle: {0}")]
+    InvalidBundle(String),
+
+    #[error("invalid backup: {0}")]
+    InvalidBackup(String),
+
+    #[error("cannot restore into {0}: it already holds a project")]
+    RestoreTargetNotEmpty(String),
+
     #[error("invalid feature order: {0}")]
     InvalidFeatureOrder(String),
 
+    #[error("invalid task order: {0}")]
+    InvalidTaskOrder(String),
+
+    #[error("invalid task spec: {0}")]
+    InvalidTaskSpec(String),
+
     #[error("invalid slug '{0}': must be lowercase alphanumeric with hyphens")]
     InvalidSlug(String),
 
@@ -33,6 +57,20 @@ pub enum SdlcError {
     #[error("invalid phase: {0}")]
     InvalidPhase(String),
 
+    #[error("invalid feature track '{0}': must be 'full' or 'lightweight'")]
+    InvalidFeatureTrack(String),
+
+    #[error("invalid priority '{0}': must be 'normal' or 'hotfix'")]
+    InvalidPriority(String),
+
+    #[error(
+        "invalid since value '{0}': expected a duration (30s, 15m, 2h, 3d, 1w) or an ISO-8601 timestamp"
+    )]
+    InvalidSince(String),
+
+    #[error("invalid pricing for model '{model}': {field} rate must be a non-negative number")]
+    InvalidPricing { model: String, field: &'static str },
+
     #[error("task not found: {0}")]
     TaskNotFound(String),
 
@@ -102,11 +140,6 @@ pub enum SdlcError {
     #[error("home directory not found: set HOME environment variable")]
     HomeNotFound,
 
-    #[error(
-        "age encryption tool not found — install with: brew install age  (or: apt install age)"
-    )]
-    AgeNotInstalled,
-
     #[error("age decryption failed: {0}")]
     AgeDecryptFailed(String),
 
@@ -128,6 +161,9 @@ pub enum SdlcError {
     #[error("env already exists: {0}")]
     SecretEnvExists(String),
 
+    #[error("secrets backend '{0}' is read-only")]
+    SecretsBackendReadOnly(String),
+
     #[error("escalation not found: {0}")]
     EscalationNotFound(String),
 
@@ -161,6 +197,27 @@ pub enum SdlcError {
     #[error("orchestrator DB error: {0}")]
     OrchestratorDb(String),
 
+    #[error("timed out waiting for lock {0}")]
+    LockTimeout(String),
+
+    #[error("write to {0} was not applied: the written contents did not read back intact")]
+    WriteNotPersisted(String),
+
+    #[error("branch does not merge cleanly; conflicting paths: {}", files.join(", "))]
+    MergeConflict { files: Vec<String> },
+
+    #[error("dependencies of '{feature}' not yet released: {}", dependencies.join(", "))]
+    UnreleasedDependencies {
+        feature: String,
+        dependencies: Vec<String>,
+    },
+
+    #[error("{artifact} looks like it contains a secret ({}); {}", findings.join("; "), crate::artifact::SECRET_GUIDANCE)]
+    SecretInArtifact {
+        artifact: String,
+        findings: Vec<String>,
+    },
+
     /// A manifest file exists but contains invalid YAML syntax.
     #[error("{path}: cannot parse YAML: {message}")]
     ManifestParseFailed { path: String, message: String },
@@ -89,6 +89,22 @@ pub struct EscalationItem {
     pub resolved_at: Option<DateTime<Utc>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub resolution: Option<String>,
+    /// Feature created from this escalation (`sdlc feature create --from-escalation`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub spawned_feature: Option<String>,
+}
+
+impl EscalationItem {
+    /// Description for a feature built from this escalation: the title,
+    /// then the context when there is any.
+    pub fn feature_description(&self) -> String {
+        let context = self.context.trim();
+        if context.is_empty() {
+            self.title.clone()
+        } else {
+            format!("{}\n\n{context}", self.title)
+        }
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -168,6 +184,7 @@ pub fn create(
         created_at: Utc::now(),
         resolved_at: None,
         resolution: None,
+        spawned_feature: None,
     };
 
     items.push(item.clone());
@@ -208,7 +225,15 @@ pub fn get(root: &Path, id: &str) -> Result<EscalationItem> {
 /// If it has a linked feature comment, that comment is removed so the
 /// `wait_for_approval` gate disengages.
 pub fn resolve(root: &Path, id: &str, resolution: impl Into<String>) -> Result<EscalationItem> {
-    let resolution = resolution.into();
+    resolve_with(root, id, resolution.into(), None)
+}
+
+fn resolve_with(
+    root: &Path,
+    id: &str,
+    resolution: String,
+    spawned_feature: Option<&str>,
+) -> Result<EscalationItem> {
     let mut items = load_all(root)?;
 
     let pos = items
@@ -248,6 +273,7 @@ pub fn resolve(root: &Path, id: &str, resolution: impl Into<String>) -> Result<E
     items[pos].resolved_at = Some(Utc::now());
     items[pos].resolution = Some(resolution);
     items[pos].linked_comment_id = None;
+    items[pos].spawned_feature = spawned_feature.map(str::to_string);
 
     let resolved = items[pos].clone();
     save_all(root, &items)?;
@@ -255,6 +281,46 @@ pub fn resolve(root: &Path, id: &str, resolution: impl Into<String>) -> Result<E
     Ok(resolved)
 }
 
+/// Resolve open escalation `id` in favour of the already-created feature
+/// `slug`: the feature gets a comment pointing back at the escalation, and
+/// the escalation records `slug` as its spawned feature.
+pub fn resolve_into_feature(root: &Path, id: &str, slug: &str) -> Result<EscalationItem> {
+    let item = get(root, id)?;
+    if item.status != EscalationStatus::Open {
+        return Err(SdlcError::Other(format!(
+            "escalation {id} is already resolved"
+        )));
+    }
+
+    let mut feature = Feature::load(root, slug)?;
+    add_comment(
+        &mut feature.comments,
+        &mut feature.next_comment_seq,
+        format!("[From escalation {id}] {}", item.title),
+        Some(CommentFlag::Fyi),
+        CommentTarget::Feature,
+        Some("sdlc".to_string()),
+    );
+    feature.save(root)?;
+
+    resolve_with(root, id, format!("Became feature '{slug}'"), Some(slug))
+}
+
+/// The open escalation holding `feature` back: one raised from it whose
+/// blocker comment is still on the feature. `None` when nothing blocks it.
+pub fn blocking(root: &Path, feature: &Feature) -> Result<Option<EscalationItem>> {
+    Ok(load_all(root)?.into_iter().find(|e| {
+        e.status == EscalationStatus::Open
+            && e.source_feature.as_deref() == Some(feature.slug.as_str())
+            && e.linked_comment_id.as_ref().is_some_and(|id| {
+                feature
+                    .comments
+                    .iter()
+                    .any(|c| &c.id == id && c.is_blocking())
+            })
+    }))
+}
+
 // ---------------------------------------------------------------------------
 // Tests
 // ---------------------------------------------------------------------------
@@ -347,4 +413,51 @@ mod tests {
         let all = list(dir.path(), Some("all")).unwrap();
         assert_eq!(all.len(), 2);
     }
+
+    #[test]
+    fn resolve_into_feature_links_both_ways() {
+        let dir = init_dir();
+        std::fs::create_dir_all(dir.path().join(".sdlc/features")).unwrap();
+        let item = create(
+            dir.path(),
+            EscalationKind::Question,
+            "Support SSO",
+            "Three enterprise customers asked for SAML login.",
+            None,
+        )
+        .unwrap();
+        assert_eq!(
+            item.feature_description(),
+            "Support SSO\n\nThree enterprise customers asked for SAML login."
+        );
+        Feature::create_with_description(
+            dir.path(),
+            "support-sso",
+            "Support SSO",
+            Some(item.feature_description()),
+        )
+        .unwrap();
+
+        let resolved = resolve_into_feature(dir.path(), "E1", "support-sso").unwrap();
+        assert_eq!(resolved.status, EscalationStatus::Resolved);
+        assert_eq!(resolved.spawned_feature.as_deref(), Some("support-sso"));
+        assert_eq!(
+            get(dir.path(), "E1").unwrap().spawned_feature.as_deref(),
+            Some("support-sso")
+        );
+
+        let feature = Feature::load(dir.path(), "support-sso").unwrap();
+        assert!(feature
+            .description
+            .as_deref()
+            .unwrap()
+            .contains("SAML login"));
+        assert!(feature
+            .comments
+            .iter()
+            .any(|c| c.body == "[From escalation E1] Support SSO" && !c.is_blocking()));
+
+        let err = resolve_into_feature(dir.path(), "E1", "support-sso").unwrap_err();
+        assert!(err.to_string().contains("already resolved"), "{err}");
+    }
 }
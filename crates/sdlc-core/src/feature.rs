@@ -4,12 +4,16 @@ use crate::config::Config;
 use crate::error::{Result, SdlcError};
 use crate::paths;
 use crate::score::QualityScore;
+use crate::state::State;
 use crate::task::Task;
-use crate::types::{ArtifactStatus, ArtifactType, Phase};
+use crate::types::{
+    ActionType, ArtifactStatus, ArtifactType, FeatureTrack, Phase, Priority, TaskStatus,
+};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 use std::path::Path;
+use std::sync::Arc;
 
 /// Deserialize `artifacts` accepting both a YAML sequence (`[]`) and an empty
 /// YAML map (`{}`). Older manifest files were written with `artifacts: {}` by
@@ -76,6 +80,28 @@ pub struct PhaseTransition {
 // Feature
 // ---------------------------------------------------------------------------
 
+/// Outcome of `Feature::restore`.
+#[derive(Debug, Clone, Serialize)]
+pub struct RestoreReport {
+    /// Milestone the feature was re-linked to, if it still exists.
+    pub milestone: Option<String>,
+    /// Problems found that did not prevent the restore.
+    pub warnings: Vec<String>,
+}
+
+/// Outcome of `Feature::split`.
+#[derive(Debug, Clone, Serialize)]
+pub struct SplitReport {
+    /// The newly created feature.
+    pub feature: Feature,
+    /// Ids of the tasks moved into the new feature.
+    pub moved_tasks: Vec<String>,
+    /// Artifacts whose files were copied over as drafts.
+    pub copied_artifacts: Vec<ArtifactType>,
+    /// Milestones the new feature was linked to.
+    pub milestones: Vec<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Feature {
     pub slug: String,
@@ -83,6 +109,13 @@ pub struct Feature {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
     pub phase: Phase,
+    /// Pipeline this feature runs through. Older manifests without the field
+    /// default to the full lifecycle.
+    #[serde(default)]
+    pub track: FeatureTrack,
+    /// `hotfix` features are focused ahead of milestone order.
+    #[serde(default, skip_serializing_if = "Priority::is_normal")]
+    pub priority: Priority,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
     #[serde(default, deserialize_with = "deserialize_artifacts")]
@@ -101,6 +134,10 @@ pub struct Feature {
     pub dependencies: Vec<String>,
     #[serde(default)]
     pub archived: bool,
+    /// Milestone the feature belonged to when it was archived, so
+    /// `Feature::restore` can put it back.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub archived_milestone: Option<String>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub scores: Vec<QualityScore>,
     /// Schema version for this manifest. Migration on load brings older files
@@ -110,6 +147,15 @@ pub struct Feature {
     pub schema_version: u32,
 }
 
+/// A dependency that has not been released yet.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct UnmetDependency {
+    pub slug: String,
+    /// `None` when the dependency names a feature that does not exist.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub phase: Option<Phase>,
+}
+
 impl Feature {
     pub fn new(slug: impl Into<String>, title: impl Into<String>) -> Self {
         Self::with_description(slug, title, None)
@@ -131,6 +177,8 @@ impl Feature {
             title,
             description,
             phase: Phase::Draft,
+            track: FeatureTrack::Full,
+            priority: Priority::Normal,
             created_at: now,
             updated_at: now,
             artifacts,
@@ -145,6 +193,7 @@ impl Feature {
             }],
             dependencies: Vec::new(),
             archived: false,
+            archived_milestone: None,
             scores: Vec::new(),
             schema_version: crate::migrations::FEATURE_SCHEMA_VERSION,
         }
@@ -179,6 +228,16 @@ impl Feature {
         slug: impl Into<String>,
         title: impl Into<String>,
         description: Option<String>,
+    ) -> Result<Self> {
+        Self::create_with_track(root, slug, title, description, FeatureTrack::Full)
+    }
+
+    pub fn create_with_track(
+        root: &Path,
+        slug: impl Into<String>,
+        title: impl Into<String>,
+        description: Option<String>,
+        track: FeatureTrack,
     ) -> Result<Self> {
         let slug = slug.into();
         paths::validate_slug(&slug)?;
@@ -188,7 +247,8 @@ impl Feature {
             return Err(SdlcError::FeatureExists(slug));
         }
 
-        let feature = Self::with_description(slug, title, description);
+        let mut feature = Self::with_description(slug, title, description);
+        feature.track = track;
         feature.save(root)?;
         Ok(feature)
     }
@@ -230,6 +290,8 @@ impl Feature {
                 fix_hint: crate::migrations::feature_fix_hint(&e),
             })?;
 
+        crate::audit::remember(&manifest, &feature);
+
         // Phase 4: self-heal — rewrite the file if migration upgraded it.
         if migrated {
             let _ = feature.save(root); // best-effort; load still succeeds on save failure
@@ -238,10 +300,56 @@ impl Feature {
         Ok(feature)
     }
 
+    /// Persist the manifest and append an audit entry for every phase, task,
+    /// and artifact change relative to the manifest this feature was loaded
+    /// from or last saved as, or a `created` entry for a new feature.
+    ///
+    /// The audit log is secondary: once the manifest is written, failing to
+    /// append to it only warns.
     pub fn save(&self, root: &Path) -> Result<()> {
         let manifest = paths::feature_manifest(root, &self.slug);
+        let previous = match crate::audit::loaded(&manifest) {
+            Some(previous) => Some(previous),
+            // Not loaded by this process: diff against the file.
+            None if manifest.exists() => Self::load(root, &self.slug).ok().map(Arc::new),
+            None => None,
+        };
         let data = serde_yaml::to_string(self)?;
-        crate::io::atomic_write(&manifest, data.as_bytes())
+        crate::io::atomic_write(&manifest, data.as_bytes())?;
+        let entries = match &previous {
+            Some(previous) => crate::audit::diff(previous, self),
+            None => vec![crate::audit::created(self)],
+        };
+        if let Err(e) = crate::audit::append(root, &entries) {
+            eprintln!(
+                "warning: could not record audit log entries for '{}': {e}",
+                self.slug
+            );
+        }
+        crate::audit::remember(&manifest, self);
+        Ok(())
+    }
+
+    /// Slugs of every feature with a manifest on disk, sorted by slug.
+    /// Unlike `list`, no manifest is parsed.
+    pub fn slugs(root: &Path) -> Result<Vec<String>> {
+        let features_dir = root.join(paths::FEATURES_DIR);
+        if !features_dir.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut slugs = Vec::new();
+        for entry in std::fs::read_dir(&features_dir)? {
+            let entry = entry?;
+            if entry.file_type()?.is_dir() {
+                let slug = entry.file_name().to_string_lossy().into_owned();
+                if paths::feature_manifest(root, &slug).exists() {
+                    slugs.push(slug);
+                }
+            }
+        }
+        slugs.sort();
+        Ok(slugs)
     }
 
     pub fn list(root: &Path) -> Result<Vec<Self>> {
@@ -266,6 +374,175 @@ impl Feature {
         Ok(features)
     }
 
+    // ---------------------------------------------------------------------------
+    // Archival
+    // ---------------------------------------------------------------------------
+
+    /// Mark the feature archived, unlink it from its milestone (remembering
+    /// which one), and drop it from the active set in state.yaml.
+    pub fn archive(root: &Path, slug: &str) -> Result<Self> {
+        let mut feature = Self::load(root, slug)?;
+        feature.archived = true;
+        for mut ms in crate::milestone::Milestone::list(root)? {
+            if ms.remove_feature(slug) {
+                ms.save(root)?;
+                feature.archived_milestone.get_or_insert(ms.slug);
+            }
+        }
+        feature.updated_at = Utc::now();
+        feature.save(root)?;
+        crate::state::State::update(root, |state| state.remove_active_feature(slug))?;
+        Ok(feature)
+    }
+
+    /// Undo `archive`: clear the flag, re-activate the feature in state.yaml,
+    /// and re-link it to the milestone it was archived from. A missing
+    /// milestone or dependency does not block the restore; it is reported in
+    /// `RestoreReport::warnings` instead.
+    pub fn restore(root: &Path, slug: &str) -> Result<RestoreReport> {
+        let mut feature = Self::load(root, slug)?;
+        if !feature.archived {
+            return Err(SdlcError::Other(format!(
+                "feature '{slug}' is not archived"
+            )));
+        }
+
+        let mut warnings = Vec::new();
+        for dep in &feature.dependencies {
+            match Self::load(root, dep) {
+                Ok(d) if d.archived => warnings.push(format!("dependency '{dep}' is archived")),
+                Ok(_) => {}
+                Err(SdlcError::FeatureNotFound(_)) => {
+                    warnings.push(format!("dependency '{dep}' no longer exists"))
+                }
+                Err(e) => return Err(e),
+            }
+        }
+
+        let mut milestone = None;
+        if let Some(ms_slug) = feature.archived_milestone.take() {
+            match crate::milestone::Milestone::load(root, &ms_slug) {
+                Ok(mut ms) => {
+                    if ms.add_feature(slug) {
+                        ms.save(root)?;
+                    }
+                    milestone = Some(ms_slug);
+                }
+                Err(SdlcError::MilestoneNotFound(_)) => warnings.push(format!(
+                    "milestone '{ms_slug}' no longer exists; feature left unassigned"
+                )),
+                Err(e) => return Err(e),
+            }
+        }
+
+        feature.archived = false;
+        feature.updated_at = Utc::now();
+        feature.save(root)?;
+        crate::state::State::update(root, |state| state.add_active_feature(slug))?;
+        Ok(RestoreReport {
+            milestone,
+            warnings,
+        })
+    }
+
+    // ---------------------------------------------------------------------------
+    // Splitting
+    // ---------------------------------------------------------------------------
+
+    /// Break `slug` in two: create `new_slug`, move `task_ids` into it, and
+    /// link it to every milestone `slug` belongs to, right after `slug`.
+    ///
+    /// The new feature inherits the track, description, and dependencies, and
+    /// gets the source's spec and design as drafts so it starts with the same
+    /// context. Task dependencies that would cross the split are dropped. With
+    /// `depends_on_source`, the new feature also depends on `slug`.
+    pub fn split(
+        root: &Path,
+        slug: &str,
+        new_slug: &str,
+        title: &str,
+        task_ids: &[String],
+        depends_on_source: bool,
+    ) -> Result<SplitReport> {
+        let mut source = Self::load(root, slug)?;
+        if source.archived {
+            return Err(SdlcError::Other(format!("feature '{slug}' is archived")));
+        }
+        if task_ids.is_empty() {
+            return Err(SdlcError::Other("no tasks given to split off".to_string()));
+        }
+        for id in task_ids {
+            if !source.tasks.iter().any(|t| &t.id == id) {
+                return Err(SdlcError::TaskNotFound(format!(
+                    "{id} (in feature '{slug}')"
+                )));
+            }
+        }
+
+        let mut feature = Self::create_with_track(
+            root,
+            new_slug,
+            title,
+            source.description.clone(),
+            source.track,
+        )?;
+        feature.dependencies = source.dependencies.clone();
+        if depends_on_source {
+            feature.dependencies.push(slug.to_string());
+        }
+
+        let (mut moved, kept): (Vec<Task>, Vec<Task>) = std::mem::take(&mut source.tasks)
+            .into_iter()
+            .partition(|t| task_ids.contains(&t.id));
+        source.tasks = kept;
+        let moved_ids: HashSet<String> = moved.iter().map(|t| t.id.clone()).collect();
+        for task in &mut moved {
+            task.depends_on.retain(|d| moved_ids.contains(d));
+        }
+        for task in &mut source.tasks {
+            task.depends_on.retain(|d| !moved_ids.contains(d));
+        }
+        feature.tasks = moved;
+
+        let mut copied_artifacts = Vec::new();
+        for artifact_type in [ArtifactType::Spec, ArtifactType::Design] {
+            let from = paths::artifact_path(root, slug, artifact_type.filename());
+            if !from.exists() {
+                continue;
+            }
+            let to = paths::artifact_path(root, new_slug, artifact_type.filename());
+            std::fs::copy(&from, &to)?;
+            if let Some(artifact) = feature.artifact_mut(artifact_type) {
+                artifact.mark_draft();
+            }
+            copied_artifacts.push(artifact_type);
+        }
+
+        let now = Utc::now();
+        source.updated_at = now;
+        feature.updated_at = now;
+        source.save(root)?;
+        feature.save(root)?;
+
+        let mut milestones = Vec::new();
+        for mut ms in crate::milestone::Milestone::list(root)? {
+            if let Some(pos) = ms.features.iter().position(|f| f == slug) {
+                if ms.add_feature_at(new_slug, pos + 1) {
+                    ms.save(root)?;
+                }
+                milestones.push(ms.slug);
+            }
+        }
+        crate::state::State::update(root, |state| state.add_active_feature(new_slug))?;
+
+        Ok(SplitReport {
+            moved_tasks: feature.tasks.iter().map(|t| t.id.clone()).collect(),
+            feature,
+            copied_artifacts,
+            milestones,
+        })
+    }
+
     // ---------------------------------------------------------------------------
     // Dependency cycle detection
     // ---------------------------------------------------------------------------
@@ -348,6 +625,50 @@ impl Feature {
             .collect())
     }
 
+    /// Dependencies of this feature that are not released according to
+    /// `phases` (each feature's phase by slug), in declaration order. A
+    /// dependency missing from `phases` counts as unreleased.
+    pub fn unmet_dependencies(&self, phases: &HashMap<String, Phase>) -> Vec<UnmetDependency> {
+        self.dependencies
+            .iter()
+            .filter_map(|dep| match phases.get(dep) {
+                Some(Phase::Released) => None,
+                phase => Some(UnmetDependency {
+                    slug: dep.clone(),
+                    phase: phase.copied(),
+                }),
+            })
+            .collect()
+    }
+
+    /// [`Self::unmet_dependencies`] against the dependency manifests on disk.
+    pub fn unreleased_dependencies(&self, root: &Path) -> Result<Vec<UnmetDependency>> {
+        let mut phases = HashMap::new();
+        for dep in &self.dependencies {
+            match Self::load(root, dep) {
+                Ok(d) => {
+                    phases.insert(d.slug, d.phase);
+                }
+                Err(SdlcError::FeatureNotFound(_)) => {}
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(self.unmet_dependencies(&phases))
+    }
+
+    /// The merge gate: fail with [`SdlcError::UnreleasedDependencies`] unless
+    /// every dependency is released.
+    pub fn ensure_dependencies_released(&self, root: &Path) -> Result<()> {
+        let unmet = self.unreleased_dependencies(root)?;
+        if unmet.is_empty() {
+            return Ok(());
+        }
+        Err(SdlcError::UnreleasedDependencies {
+            feature: self.slug.clone(),
+            dependencies: unmet.into_iter().map(|d| d.slug).collect(),
+        })
+    }
+
     // ---------------------------------------------------------------------------
     // Phase transitions
     // ---------------------------------------------------------------------------
@@ -369,9 +690,13 @@ impl Feature {
             });
         }
 
-        // Check required artifacts for the target phase
+        // Check required artifacts for the target phase, ignoring any the
+        // feature's track never produces.
         let required = cfg.phases.required_for(target);
         for &artifact_type in required {
+            if self.track.skips(artifact_type) {
+                continue;
+            }
             let artifact = self.artifact(artifact_type);
             if !artifact.map(|a| a.is_satisfied()).unwrap_or(false) {
                 return Err(SdlcError::MissingArtifact {
@@ -419,8 +744,11 @@ impl Feature {
             .find(|a| a.artifact_type == artifact_type)
     }
 
+    /// Approve an artifact, recording the content hash of its file under
+    /// `root` with the decision.
     pub fn approve_artifact(
         &mut self,
+        root: &Path,
         artifact_type: ArtifactType,
         by: Option<String>,
     ) -> Result<()> {
@@ -429,13 +757,16 @@ impl Feature {
             .iter_mut()
             .find(|a| a.artifact_type == artifact_type)
             .ok_or_else(|| SdlcError::ArtifactNotFound(artifact_type.to_string()))?;
-        artifact.approve(by);
+        artifact.approve(root, by);
         self.updated_at = Utc::now();
         Ok(())
     }
 
+    /// Reject an artifact, recording the content hash of its file under
+    /// `root` with the decision.
     pub fn reject_artifact(
         &mut self,
+        root: &Path,
         artifact_type: ArtifactType,
         reason: Option<String>,
     ) -> Result<()> {
@@ -444,7 +775,7 @@ impl Feature {
             .iter_mut()
             .find(|a| a.artifact_type == artifact_type)
             .ok_or_else(|| SdlcError::ArtifactNotFound(artifact_type.to_string()))?;
-        artifact.reject(reason);
+        artifact.reject(root, reason);
         self.updated_at = Utc::now();
         Ok(())
     }
@@ -460,17 +791,22 @@ impl Feature {
         Ok(())
     }
 
+    /// Waive an artifact; with `expires_at`, the waiver lapses at that time
+    /// and the artifact is required again. `root` locates the file whose
+    /// content hash is recorded with the decision.
     pub fn waive_artifact(
         &mut self,
+        root: &Path,
         artifact_type: ArtifactType,
         reason: Option<String>,
+        expires_at: Option<DateTime<Utc>>,
     ) -> Result<()> {
         let artifact = self
             .artifacts
             .iter_mut()
             .find(|a| a.artifact_type == artifact_type)
             .ok_or_else(|| SdlcError::ArtifactNotFound(artifact_type.to_string()))?;
-        artifact.waive(reason);
+        artifact.waive(root, reason, expires_at);
         self.updated_at = Utc::now();
         Ok(())
     }
@@ -545,6 +881,7 @@ impl Feature {
         cfg.phases
             .required_for(phase)
             .iter()
+            .filter(|&&t| !self.track.skips(t))
             .all(|&t| self.artifact(t).map(|a| a.is_satisfied()).unwrap_or(false))
     }
 
@@ -557,6 +894,117 @@ impl Feature {
     }
 }
 
+// ---------------------------------------------------------------------------
+// Changelog
+// ---------------------------------------------------------------------------
+
+/// Marks the changelog entry for a feature so a re-run merge can find it.
+fn changelog_marker(slug: &str) -> String {
+    format!("<!-- sdlc:feature={slug} -->")
+}
+
+/// A markdown changelog entry for `feature`: its title, the tasks it
+/// completed, and the summaries of its review and audit. Dated by the
+/// feature's merge in `state` history, or its last update if it has none.
+pub fn changelog(root: &Path, feature: &Feature, state: &State) -> String {
+    let date = state
+        .history
+        .iter()
+        .rev()
+        .find(|h| h.feature == feature.slug && h.action == ActionType::Merge)
+        .map(|h| h.timestamp)
+        .unwrap_or(feature.updated_at);
+
+    let mut entry = format!(
+        "## {} (`{}`) — {}\n{}\n",
+        feature.title,
+        feature.slug,
+        date.format("%Y-%m-%d"),
+        changelog_marker(&feature.slug)
+    );
+    if let Some(description) = feature.description.as_deref().filter(|d| !d.is_empty()) {
+        entry.push_str(&format!("\n{description}\n"));
+    }
+
+    let completed: Vec<&Task> = feature
+        .tasks
+        .iter()
+        .filter(|t| t.status == TaskStatus::Completed)
+        .collect();
+    if !completed.is_empty() {
+        entry.push_str("\n### Delivered\n\n");
+        for task in completed {
+            entry.push_str(&format!("- {}\n", task.title));
+        }
+    }
+
+    for (heading, artifact_type) in [
+        ("Review", ArtifactType::Review),
+        ("Audit", ArtifactType::Audit),
+    ] {
+        if let Some(summary) = feature
+            .artifact(artifact_type)
+            .and_then(|a| artifact_summary(root, a))
+        {
+            entry.push_str(&format!("\n### {heading}\n\n{summary}\n"));
+        }
+    }
+    entry
+}
+
+/// The artifact's `## Summary` section, else its first non-heading
+/// paragraph. A waived artifact is summarised by its waive reason.
+fn artifact_summary(root: &Path, artifact: &Artifact) -> Option<String> {
+    if artifact.status == ArtifactStatus::Waived {
+        let reason = artifact
+            .waive_reason
+            .as_deref()
+            .unwrap_or("no reason given");
+        return Some(format!("Waived: {reason}"));
+    }
+    let text = std::fs::read_to_string(root.join(&artifact.path)).ok()?;
+    let paragraphs: Vec<&str> = text
+        .split("\n\n")
+        .map(str::trim)
+        .filter(|p| !p.is_empty())
+        .collect();
+    let summary = paragraphs
+        .iter()
+        .position(|p| p.eq_ignore_ascii_case("## summary"))
+        .and_then(|i| paragraphs.get(i + 1))
+        .or_else(|| paragraphs.iter().find(|p| !p.starts_with('#')))?;
+    Some(summary.to_string())
+}
+
+/// Add the feature's entry to the project `CHANGELOG.md`, newest first,
+/// creating the file if needed. Returns `false` without touching the file
+/// when the feature already has an entry.
+pub fn write_changelog(root: &Path, feature: &Feature, state: &State) -> Result<bool> {
+    let path = root.join(paths::CHANGELOG_MD);
+    let existing = if path.exists() {
+        std::fs::read_to_string(&path)?
+    } else {
+        String::new()
+    };
+    if existing.contains(&changelog_marker(&feature.slug)) {
+        return Ok(false);
+    }
+
+    let entry = changelog(root, feature, state);
+    // Insert above the newest existing entry, keeping any header and intro.
+    let updated = if existing.trim().is_empty() {
+        format!("# Changelog\n\n{entry}")
+    } else if existing.starts_with("## ") {
+        format!("{entry}\n{existing}")
+    } else if let Some(at) = existing.find("\n## ") {
+        format!("{}{entry}\n{}", &existing[..=at], &existing[at + 1..])
+    } else {
+        format!("{}\n\n{entry}", existing.trim_end())
+    };
+    crate::io::atomic_write(&path, updated.as_bytes())?;
+    Ok(true)
+}
+
 // ---------------------------------------------------------------------------
 // Tests
 // ---------------------------------------------------------------------------
@@ -605,11 +1053,59 @@ mod tests {
         assert!(feature.transition(Phase::Specified, &cfg).is_err());
 
         // Approve spec → can now transition
-        feature.approve_artifact(ArtifactType::Spec, None).unwrap();
+        feature
+            .approve_artifact(dir.path(), ArtifactType::Spec, None)
+            .unwrap();
         feature.transition(Phase::Specified, &cfg).unwrap();
         assert_eq!(feature.phase, Phase::Specified);
     }
 
+    #[test]
+    fn lightweight_track_skips_required_artifacts() {
+        let dir = TempDir::new().unwrap();
+        std::fs::create_dir_all(dir.path().join(".sdlc/features")).unwrap();
+        let cfg = make_config();
+
+        let mut feature = Feature::create_with_track(
+            dir.path(),
+            "docs-fix",
+            "Docs Fix",
+            None,
+            FeatureTrack::Lightweight,
+        )
+        .unwrap();
+        assert_eq!(
+            Feature::load(dir.path(), "docs-fix").unwrap().track,
+            FeatureTrack::Lightweight
+        );
+
+        feature.phase = Phase::Review;
+        feature
+            .approve_artifact(dir.path(), ArtifactType::Review, None)
+            .unwrap();
+        // Full track would require approved qa_results to enter merge.
+        feature.transition(Phase::Merge, &cfg).unwrap();
+        assert_eq!(feature.phase, Phase::Merge);
+    }
+
+    #[test]
+    fn manifest_without_track_defaults_to_full() {
+        let dir = TempDir::new().unwrap();
+        std::fs::create_dir_all(dir.path().join(".sdlc/features")).unwrap();
+        Feature::create(dir.path(), "legacy", "Legacy").unwrap();
+        let manifest = paths::feature_manifest(dir.path(), "legacy");
+        let data = std::fs::read_to_string(&manifest).unwrap();
+        let stripped: String = data
+            .lines()
+            .filter(|l| !l.starts_with("track:"))
+            .map(|l| format!("{l}\n"))
+            .collect();
+        std::fs::write(&manifest, stripped).unwrap();
+
+        let loaded = Feature::load(dir.path(), "legacy").unwrap();
+        assert_eq!(loaded.track, FeatureTrack::Full);
+    }
+
     #[test]
     fn feature_description_round_trip() {
         let dir = TempDir::new().unwrap();
@@ -793,13 +1289,273 @@ mod tests {
 
         let mut feature = Feature::create(dir.path(), "f1", "F1").unwrap();
         let cfg = make_config();
-        feature.approve_artifact(ArtifactType::Spec, None).unwrap();
+        feature
+            .approve_artifact(dir.path(), ArtifactType::Spec, None)
+            .unwrap();
         feature.transition(Phase::Specified, &cfg).unwrap();
 
         // Can't go back
         assert!(feature.transition(Phase::Draft, &cfg).is_err());
     }
 
+    #[test]
+    fn artifact_decisions_recorded_in_order_with_versions() {
+        use crate::artifact::Decision;
+
+        let dir = TempDir::new().unwrap();
+        let root = dir.path();
+        let mut feature = Feature::create(root, "auth", "Auth").unwrap();
+        let spec = paths::artifact_path(root, "auth", ArtifactType::Spec.filename());
+
+        std::fs::write(&spec, "v1").unwrap();
+        feature
+            .approve_artifact(root, ArtifactType::Spec, Some("alice".into()))
+            .unwrap();
+        feature.save(root).unwrap();
+        feature
+            .reject_artifact(root, ArtifactType::Spec, Some("missing edge cases".into()))
+            .unwrap();
+        feature.save(root).unwrap();
+        std::fs::write(&spec, "v2").unwrap();
+        feature
+            .approve_artifact(root, ArtifactType::Spec, Some("bob".into()))
+            .unwrap();
+        feature.save(root).unwrap();
+
+        let loaded = Feature::load(root, "auth").unwrap();
+        let history = &loaded.artifact(ArtifactType::Spec).unwrap().history;
+        let decisions: Vec<_> = history.iter().map(|e| e.decision).collect();
+        assert_eq!(
+            decisions,
+            vec![Decision::Approved, Decision::Rejected, Decision::Approved]
+        );
+        assert_eq!(history[0].actor, "alice");
+        assert_eq!(history[2].actor, "bob");
+        assert_eq!(history[1].reason.as_deref(), Some("missing edge cases"));
+        assert!(history.windows(2).all(|w| w[0].at <= w[1].at));
+        // The first two decisions saw "v1"; the last one saw "v2".
+        assert!(history.iter().all(|e| e.version.is_some()));
+        assert_eq!(history[0].version, history[1].version);
+        assert_ne!(history[1].version, history[2].version);
+    }
+
+    // ---------------------------------------------------------------------------
+    // Archival tests
+    // ---------------------------------------------------------------------------
+
+    fn archival_project(dir: &TempDir) {
+        let root = dir.path();
+        crate::state::State::new("test").save(root).unwrap();
+        let mut ms = crate::milestone::Milestone::create(root, "v1", "V1").unwrap();
+        Feature::create(root, "auth", "Auth").unwrap();
+        ms.add_feature("auth");
+        ms.save(root).unwrap();
+        crate::state::State::update(root, |s| s.add_active_feature("auth")).unwrap();
+    }
+
+    #[test]
+    fn archive_restore_round_trip() {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path();
+        archival_project(&dir);
+        let mut f = Feature::load(root, "auth").unwrap();
+        crate::task::add_task(&mut f.tasks, "Write handler");
+        f.save(root).unwrap();
+
+        let archived = Feature::archive(root, "auth").unwrap();
+        assert!(archived.archived);
+        assert_eq!(archived.archived_milestone.as_deref(), Some("v1"));
+        let ms = crate::milestone::Milestone::load(root, "v1").unwrap();
+        assert!(ms.features.is_empty());
+        let state = crate::state::State::load(root).unwrap();
+        assert!(!state.active_features.contains(&"auth".to_string()));
+
+        let report = Feature::restore(root, "auth").unwrap();
+        assert_eq!(report.milestone.as_deref(), Some("v1"));
+        assert!(report.warnings.is_empty());
+
+        let restored = Feature::load(root, "auth").unwrap();
+        assert!(!restored.archived);
+        assert!(restored.archived_milestone.is_none());
+        assert_eq!(restored.title, "Auth");
+        assert_eq!(restored.tasks.len(), 1);
+        let ms = crate::milestone::Milestone::load(root, "v1").unwrap();
+        assert_eq!(ms.features, vec!["auth"]);
+        let state = crate::state::State::load(root).unwrap();
+        assert!(state.active_features.contains(&"auth".to_string()));
+
+        assert!(Feature::restore(root, "auth").is_err());
+    }
+
+    #[test]
+    fn restore_warns_on_deleted_milestone_and_dangling_dependency() {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path();
+        archival_project(&dir);
+        Feature::create(root, "db", "DB").unwrap();
+        let mut f = Feature::load(root, "auth").unwrap();
+        f.dependencies = vec!["db".to_string()];
+        f.save(root).unwrap();
+
+        Feature::archive(root, "auth").unwrap();
+        std::fs::remove_dir_all(paths::milestone_dir(root, "v1")).unwrap();
+        std::fs::remove_dir_all(paths::feature_dir(root, "db")).unwrap();
+
+        let report = Feature::restore(root, "auth").unwrap();
+        assert!(report.milestone.is_none());
+        assert_eq!(report.warnings.len(), 2);
+        assert!(report.warnings.iter().any(|w| w.contains("'db'")));
+        assert!(report.warnings.iter().any(|w| w.contains("'v1'")));
+        assert!(!Feature::load(root, "auth").unwrap().archived);
+    }
+
+    #[test]
+    fn unreleased_dependencies_lists_deps_until_released() {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path();
+        let mut db = Feature::create(root, "db", "DB").unwrap();
+        db.phase = Phase::Implementation;
+        db.save(root).unwrap();
+        let mut api = Feature::create(root, "api", "API").unwrap();
+        api.dependencies = vec!["db".to_string(), "gone".to_string()];
+
+        let unmet = api.unreleased_dependencies(root).unwrap();
+        assert_eq!(
+            unmet,
+            [
+                UnmetDependency {
+                    slug: "db".to_string(),
+                    phase: Some(Phase::Implementation),
+                },
+                UnmetDependency {
+                    slug: "gone".to_string(),
+                    phase: None,
+                },
+            ]
+        );
+        let err = api.ensure_dependencies_released(root).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "dependencies of 'api' not yet released: db, gone"
+        );
+
+        db.phase = Phase::Released;
+        db.save(root).unwrap();
+        api.dependencies.pop();
+        assert!(api.unreleased_dependencies(root).unwrap().is_empty());
+        api.ensure_dependencies_released(root).unwrap();
+    }
+
+    #[test]
+    fn split_moves_tasks_and_links_milestone() {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path();
+        archival_project(&dir);
+        let mut f = Feature::load(root, "auth").unwrap();
+        let login = crate::task::add_task(&mut f.tasks, "Login form");
+        let reset = crate::task::add_task(&mut f.tasks, "Password reset");
+        let email = crate::task::add_task(&mut f.tasks, "Reset email");
+        f.tasks[2].depends_on = vec![reset.clone()];
+        f.tasks[1].depends_on = vec![login.clone()];
+        f.approve_artifact(root, ArtifactType::Spec, None).unwrap();
+        f.save(root).unwrap();
+        std::fs::write(
+            paths::artifact_path(root, "auth", ArtifactType::Spec.filename()),
+            "# Auth spec",
+        )
+        .unwrap();
+
+        let report = Feature::split(
+            root,
+            "auth",
+            "auth-reset",
+            "Auth Reset",
+            &[reset.clone(), email.clone()],
+            true,
+        )
+        .unwrap();
+        assert_eq!(report.moved_tasks, vec![reset.clone(), email.clone()]);
+        assert_eq!(report.copied_artifacts, vec![ArtifactType::Spec]);
+        assert_eq!(report.milestones, vec!["v1"]);
+
+        let source = Feature::load(root, "auth").unwrap();
+        let ids: Vec<_> = source.tasks.iter().map(|t| t.id.as_str()).collect();
+        assert_eq!(ids, vec![login.as_str()]);
+
+        let split = Feature::load(root, "auth-reset").unwrap();
+        assert_eq!(split.tasks.len(), 2);
+        assert!(split.tasks[0].depends_on.is_empty());
+        assert_eq!(split.tasks[1].depends_on, vec![reset]);
+        assert_eq!(split.dependencies, vec!["auth"]);
+        let spec = split.artifact(ArtifactType::Spec).unwrap();
+        assert_eq!(spec.status, ArtifactStatus::Draft);
+        let copied = std::fs::read_to_string(paths::artifact_path(
+            root,
+            "auth-reset",
+            ArtifactType::Spec.filename(),
+        ))
+        .unwrap();
+        assert_eq!(copied, "# Auth spec");
+
+        let ms = crate::milestone::Milestone::load(root, "v1").unwrap();
+        assert_eq!(ms.features, vec!["auth", "auth-reset"]);
+        let state = crate::state::State::load(root).unwrap();
+        assert!(state.active_features.contains(&"auth-reset".to_string()));
+    }
+
+    #[test]
+    fn tasks_added_after_a_split_get_fresh_ids() {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path();
+        archival_project(&dir);
+        let mut f = Feature::load(root, "auth").unwrap();
+        for title in ["Login form", "Password reset", "Reset email", "Logout"] {
+            crate::task::add_task(&mut f.tasks, title);
+        }
+        f.save(root).unwrap();
+
+        Feature::split(
+            root,
+            "auth",
+            "auth-reset",
+            "Auth Reset",
+            &["T2".to_string(), "T3".to_string()],
+            false,
+        )
+        .unwrap();
+
+        let mut source = Feature::load(root, "auth").unwrap();
+        assert_eq!(
+            crate::task::add_task(&mut source.tasks, "Session expiry"),
+            "T5"
+        );
+        let mut split = Feature::load(root, "auth-reset").unwrap();
+        assert_eq!(crate::task::add_task(&mut split.tasks, "Reset audit"), "T4");
+    }
+
+    #[test]
+    fn split_rejects_foreign_task_ids() {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path();
+        archival_project(&dir);
+        let mut f = Feature::load(root, "auth").unwrap();
+        let login = crate::task::add_task(&mut f.tasks, "Login form");
+        f.save(root).unwrap();
+
+        let err = Feature::split(
+            root,
+            "auth",
+            "auth-reset",
+            "Auth Reset",
+            &[login, "T99".to_string()],
+            false,
+        )
+        .unwrap_err();
+        assert!(matches!(err, SdlcError::TaskNotFound(_)));
+        assert!(!paths::feature_dir(root, "auth-reset").exists());
+        assert_eq!(Feature::load(root, "auth").unwrap().tasks.len(), 1);
+    }
+
     // ---------------------------------------------------------------------------
     // Dependency cycle detection tests
     // ---------------------------------------------------------------------------
@@ -890,4 +1646,58 @@ mod tests {
         let result = Feature::validate_no_dep_cycle("a", &["a".to_string()], &graph);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn changelog_lists_completed_tasks_and_review_summary() {
+        let dir = TempDir::new().unwrap();
+        std::fs::create_dir_all(dir.path().join(".sdlc/features")).unwrap();
+
+        let mut f = Feature::create(dir.path(), "auth", "Auth Login").unwrap();
+        let done = crate::task::add_task(&mut f.tasks, "Login form");
+        crate::task::add_task(&mut f.tasks, "Password reset");
+        crate::task::complete_task(&mut f.tasks, &done).unwrap();
+        let review = f.artifact(ArtifactType::Review).unwrap().path.clone();
+        std::fs::write(
+            dir.path().join(review),
+            "# Review\n\n## Summary\n\nLogin is solid.\n\n## Findings\n\nNone.\n",
+        )
+        .unwrap();
+        f.waive_artifact(
+            dir.path(),
+            ArtifactType::Audit,
+            Some("internal only".into()),
+            None,
+        )
+        .unwrap();
+
+        let mut state = State::new("test");
+        state.record_action("auth", ActionType::Merge, Phase::Released, "merged");
+        let entry = changelog(dir.path(), &f, &state);
+
+        assert!(entry.starts_with("## Auth Login (`auth`)"), "{entry}");
+        assert!(entry.contains("- Login form"), "{entry}");
+        assert!(!entry.contains("Password reset"), "{entry}");
+        assert!(entry.contains("### Review\n\nLogin is solid."), "{entry}");
+        assert!(
+            entry.contains("### Audit\n\nWaived: internal only"),
+            "{entry}"
+        );
+    }
+
+    #[test]
+    fn write_changelog_is_idempotent_and_newest_first() {
+        let dir = TempDir::new().unwrap();
+        std::fs::create_dir_all(dir.path().join(".sdlc/features")).unwrap();
+        let state = State::new("test");
+        let first = Feature::create(dir.path(), "first", "First").unwrap();
+        let second = Feature::create(dir.path(), "second", "Second").unwrap();
+
+        assert!(write_changelog(dir.path(), &first, &state).unwrap());
+        assert!(write_changelog(dir.path(), &second, &state).unwrap());
+        assert!(!write_changelog(dir.path(), &first, &state).unwrap());
+
+        let text = std::fs::read_to_string(dir.path().join("CHANGELOG.md")).unwrap();
+        assert!(text.starts_with("# Changelog\n\n## Second"), "{text}");
+        assert_eq!(text.matches("## First").count(), 1, "{text}");
+    }
 }
@@ -0,0 +1,177 @@
+//! Feature templates — recurring feature shapes with a canned description and
+//! starting task list, applied by `sdlc feature create --template <name>`.
+//!
+//! Layout:
+//!   .sdlc/templates/<name>.yaml   — project-local template (overrides a
+//!                                   built-in of the same name)
+//!
+//! Built-ins: `api-endpoint`, `migration`.
+
+use crate::error::{Result, SdlcError};
+use crate::feature::Feature;
+use crate::paths;
+use crate::task::add_task;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+// ---------------------------------------------------------------------------
+// Types
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FeatureTemplate {
+    /// Template name; taken from the file stem for project-local templates.
+    #[serde(default)]
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Task titles, added in order as T1, T2, …
+    #[serde(default)]
+    pub tasks: Vec<String>,
+}
+
+impl FeatureTemplate {
+    /// Seed `feature` from this template. An explicit description on the
+    /// feature wins; template tasks are appended after any existing tasks.
+    pub fn apply(&self, feature: &mut Feature) {
+        if feature.description.is_none() {
+            feature.description = self.description.clone();
+        }
+        for title in &self.tasks {
+            add_task(&mut feature.tasks, title.clone());
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Built-ins
+// ---------------------------------------------------------------------------
+
+pub const BUILTIN_TEMPLATES: &[&str] = &["api-endpoint", "migration"];
+
+fn builtin(name: &str) -> Option<FeatureTemplate> {
+    let (description, tasks): (&str, &[&str]) = match name {
+        "api-endpoint" => (
+            "Add an HTTP API endpoint",
+            &[
+                "Define request and response types",
+                "Implement the handler",
+                "Register the route",
+                "Add integration tests for success and error responses",
+                "Document the endpoint",
+            ],
+        ),
+        "migration" => (
+            "Schema or data migration",
+            &[
+                "Write the forward migration",
+                "Write the rollback migration",
+                "Update models and queries for the new schema",
+                "Test the migration against representative data",
+                "Document deploy ordering and rollback steps",
+            ],
+        ),
+        _ => return None,
+    };
+    Some(FeatureTemplate {
+        name: name.to_string(),
+        description: Some(description.to_string()),
+        tasks: tasks.iter().map(|t| t.to_string()).collect(),
+    })
+}
+
+// ---------------------------------------------------------------------------
+// Public API
+// ---------------------------------------------------------------------------
+
+/// Load a template by name. A project-local `.sdlc/templates/<name>.yaml`
+/// takes precedence over a built-in of the same name.
+pub fn load(root: &Path, name: &str) -> Result<FeatureTemplate> {
+    paths::validate_slug(name)?;
+    let path = paths::feature_template_path(root, name);
+    if path.exists() {
+        let data = std::fs::read_to_string(&path)?;
+        let mut template: FeatureTemplate = serde_yaml::from_str(&data)?;
+        template.name = name.to_string();
+        return Ok(template);
+    }
+    builtin(name).ok_or_else(|| {
+        SdlcError::FeatureTemplateNotFound(name.to_string(), available(root).join(", "))
+    })
+}
+
+/// Names of every template available to this project, sorted: built-ins plus
+/// any `.sdlc/templates/*.yaml`.
+pub fn available(root: &Path) -> Vec<String> {
+    let mut names: Vec<String> = BUILTIN_TEMPLATES.iter().map(|n| n.to_string()).collect();
+    if let Ok(entries) = std::fs::read_dir(root.join(paths::TEMPLATES_DIR)) {
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.extension().is_some_and(|e| e == "yaml") {
+                if let Some(stem) = path.file_stem().and_then(|s| s.to_str()) {
+                    names.push(stem.to_string());
+                }
+            }
+        }
+    }
+    names.sort();
+    names.dedup();
+    names
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn builtin_templates_load() {
+        let dir = TempDir::new().unwrap();
+        for name in BUILTIN_TEMPLATES {
+            let t = load(dir.path(), name).unwrap();
+            assert_eq!(t.name, *name);
+            assert!(t.description.is_some());
+            assert!(!t.tasks.is_empty());
+        }
+    }
+
+    #[test]
+    fn project_template_overrides_builtin() {
+        let dir = TempDir::new().unwrap();
+        let path = paths::feature_template_path(dir.path(), "migration");
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(
+            &path,
+            "description: Our migrations\ntasks:\n  - Run sqlx prepare\n",
+        )
+        .unwrap();
+
+        let t = load(dir.path(), "migration").unwrap();
+        assert_eq!(t.description.as_deref(), Some("Our migrations"));
+        assert_eq!(t.tasks, vec!["Run sqlx prepare"]);
+    }
+
+    #[test]
+    fn unknown_template_lists_available() {
+        let dir = TempDir::new().unwrap();
+        let err = load(dir.path(), "nope").unwrap_err();
+        let msg = err.to_string();
+        assert!(msg.contains("'nope'"), "{msg}");
+        assert!(msg.contains("api-endpoint, migration"), "{msg}");
+    }
+
+    #[test]
+    fn apply_keeps_explicit_description() {
+        let t = builtin("api-endpoint").unwrap();
+        let mut f = Feature::with_description("auth", "Auth", Some("Login API".to_string()));
+        t.apply(&mut f);
+        assert_eq!(f.description.as_deref(), Some("Login API"));
+        assert_eq!(f.tasks.len(), t.tasks.len());
+        assert_eq!(f.tasks[0].id, "T1");
+        assert_eq!(f.tasks[0].title, "Define request and response types");
+    }
+}
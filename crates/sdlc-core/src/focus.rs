@@ -5,7 +5,7 @@ use crate::feature::Feature;
 use crate::milestone::Milestone;
 use crate::rules::default_rules;
 use crate::state::State;
-use crate::types::ActionType;
+use crate::types::{ActionType, Priority};
 use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
 use std::path::Path;
@@ -33,6 +33,9 @@ pub struct FocusResult {
     pub classification: Classification,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub milestone: Option<MilestoneSummary>,
+    /// `hotfix` when the feature jumped milestone order to get here.
+    #[serde(default)]
+    pub priority: Priority,
 }
 
 // ---------------------------------------------------------------------------
@@ -54,13 +57,38 @@ fn is_actionable(action: ActionType) -> bool {
 /// 2. Fall back to features in `state.active_features` that are not
 ///    assigned to any milestone, in insertion order.
 ///
+/// `hotfix` features override that order: every actionable hotfix feature
+/// (taken in the order above) is surfaced before any normal feature.
+///
 /// Skips: archived features, `done`, `wait_for_approval`, `unblock_dependency`.
 pub fn focus(root: &Path) -> Result<Option<FocusResult>> {
     let state = State::load(root)?;
     let config = Config::load(root)?;
     let classifier = Classifier::new(default_rules());
 
+    let candidates = candidates(root, &state);
+
+    // Hotfixes first, then everything in queue order.
+    for hotfix_only in [true, false] {
+        for (feature, milestone) in &candidates {
+            if hotfix_only && feature.priority != Priority::Hotfix {
+                continue;
+            }
+            if let Some(result) =
+                try_classify(root, feature, &state, &config, &classifier, milestone)
+            {
+                return Ok(Some(result));
+            }
+        }
+    }
+
+    Ok(None)
+}
+
+/// Non-archived features in queue order, each with its milestone context.
+fn candidates(root: &Path, state: &State) -> Vec<(Feature, Option<MilestoneSummary>)> {
     let mut visited: HashSet<String> = HashSet::new();
+    let mut out = Vec::new();
 
     // Pass 1 — milestones in state order, features in milestone order
     for milestone_slug in &state.milestones {
@@ -72,62 +100,57 @@ pub fn focus(root: &Path) -> Result<Option<FocusResult>> {
         let total = milestone.features.len();
 
         for (idx, feature_slug) in milestone.features.iter().enumerate() {
-            visited.insert(feature_slug.clone());
-
-            if let Some(result) = try_classify(
-                root,
-                feature_slug,
-                &state,
-                &config,
-                &classifier,
-                Some(MilestoneSummary {
-                    slug: milestone.slug.clone(),
-                    title: milestone.title.clone(),
-                    position: idx + 1,
-                    total,
-                }),
-            ) {
-                return Ok(Some(result));
+            if !visited.insert(feature_slug.clone()) {
+                continue;
+            }
+            if let Some(feature) = load_live(root, feature_slug) {
+                out.push((
+                    feature,
+                    Some(MilestoneSummary {
+                        slug: milestone.slug.clone(),
+                        title: milestone.title.clone(),
+                        position: idx + 1,
+                        total,
+                    }),
+                ));
             }
         }
     }
 
     // Pass 2 — features not in any milestone, in state.active_features order
     for feature_slug in &state.active_features {
-        if visited.contains(feature_slug) {
+        if !visited.insert(feature_slug.clone()) {
             continue;
         }
-        if let Some(result) = try_classify(root, feature_slug, &state, &config, &classifier, None) {
-            return Ok(Some(result));
+        if let Some(feature) = load_live(root, feature_slug) {
+            out.push((feature, None));
         }
     }
 
-    Ok(None)
+    out
+}
+
+fn load_live(root: &Path, feature_slug: &str) -> Option<Feature> {
+    Feature::load(root, feature_slug)
+        .ok()
+        .filter(|f| !f.archived)
 }
 
 fn try_classify(
     root: &Path,
-    feature_slug: &str,
+    feature: &Feature,
     state: &State,
     config: &Config,
     classifier: &Classifier,
-    milestone: Option<MilestoneSummary>,
+    milestone: &Option<MilestoneSummary>,
 ) -> Option<FocusResult> {
-    let feature = Feature::load(root, feature_slug).ok()?;
-    if feature.archived {
-        return None;
-    }
-    let ctx = EvalContext {
-        feature: &feature,
-        state,
-        config,
-        root,
-    };
+    let ctx = EvalContext::new(feature, state, config, root);
     let classification = classifier.classify(&ctx);
     if is_actionable(classification.action) {
         Some(FocusResult {
             classification,
-            milestone,
+            milestone: milestone.clone(),
+            priority: feature.priority,
         })
     } else {
         None
@@ -274,4 +297,53 @@ mod tests {
         assert_eq!(result.classification.feature, "orphan");
         assert!(result.milestone.is_none());
     }
+
+    #[test]
+    fn focus_hotfix_wins_over_earlier_milestone_feature() {
+        let dir = TempDir::new().unwrap();
+        let mut state = setup(&dir);
+
+        create_feature(&dir, "planned");
+        create_feature(&dir, "urgent");
+
+        let mut m1 = Milestone::create(dir.path(), "v1", "V1").unwrap();
+        m1.add_feature("planned");
+        m1.save(dir.path()).unwrap();
+        let mut m2 = Milestone::create(dir.path(), "v2", "V2").unwrap();
+        m2.add_feature("urgent");
+        m2.save(dir.path()).unwrap();
+        state.add_milestone("v1");
+        state.add_milestone("v2");
+        state.save(dir.path()).unwrap();
+
+        let result = focus(dir.path()).unwrap().unwrap();
+        assert_eq!(result.classification.feature, "planned");
+        assert_eq!(result.priority, Priority::Normal);
+
+        let mut f = Feature::load(dir.path(), "urgent").unwrap();
+        f.priority = Priority::Hotfix;
+        f.save(dir.path()).unwrap();
+
+        let result = focus(dir.path()).unwrap().unwrap();
+        assert_eq!(result.classification.feature, "urgent");
+        assert_eq!(result.priority, Priority::Hotfix);
+        assert_eq!(result.milestone.unwrap().slug, "v2");
+    }
+
+    #[test]
+    fn focus_falls_through_when_hotfix_is_not_actionable() {
+        let dir = TempDir::new().unwrap();
+        setup(&dir);
+
+        create_feature(&dir, "normal");
+        create_feature(&dir, "shipped-hotfix");
+        let mut f = Feature::load(dir.path(), "shipped-hotfix").unwrap();
+        f.priority = Priority::Hotfix;
+        f.phase = Phase::Released;
+        f.save(dir.path()).unwrap();
+
+        let result = focus(dir.path()).unwrap().unwrap();
+        assert_eq!(result.classification.feature, "normal");
+        assert_eq!(result.priority, Priority::Normal);
+    }
 }
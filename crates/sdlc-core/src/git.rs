@@ -0,0 +1,284 @@
+//! Read-only git queries used to gate state transitions.
+//!
+//! Nothing here touches the working tree or index: merge checks use
+//! `git merge-tree --write-tree` (git ≥ 2.38), which computes the merge
+//! entirely in the object database. With an older git the check is skipped.
+
+use crate::error::{Result, SdlcError};
+use std::path::Path;
+use std::process::{Command, Output};
+
+/// Branch that features merge into when `base_branch` is not configured.
+pub const DEFAULT_BASE_BRANCH: &str = "main";
+
+/// Oldest git whose `merge-tree` supports `--write-tree`.
+pub const MERGE_TREE_MIN_VERSION: (u32, u32) = (2, 38);
+
+fn git(root: &Path, args: &[&str]) -> Result<Output> {
+    Ok(Command::new("git").args(args).current_dir(root).output()?)
+}
+
+fn stdout(out: &Output) -> String {
+    String::from_utf8_lossy(&out.stdout).trim().to_string()
+}
+
+/// The installed git's `(major, minor)` version, or `None` if git is missing
+/// or its version cannot be read.
+pub fn version() -> Option<(u32, u32)> {
+    let out = Command::new("git").arg("--version").output().ok()?;
+    parse_version(&stdout(&out))
+}
+
+/// Parse `git version 2.39.2 (Apple Git-143)` or `git version 2.45.1.windows.1`.
+fn parse_version(text: &str) -> Option<(u32, u32)> {
+    let mut parts = text.strip_prefix("git version ")?.split(['.', ' ']);
+    let major = parts.next()?.parse().ok()?;
+    let minor = parts.next()?.parse().ok()?;
+    Some((major, minor))
+}
+
+/// True if `root` is inside a git work tree.
+pub fn is_repo(root: &Path) -> bool {
+    git(root, &["rev-parse", "--is-inside-work-tree"])
+        .map(|out| out.status.success() && stdout(&out) == "true")
+        .unwrap_or(false)
+}
+
+/// The checked-out branch, or `None` on a detached HEAD.
+pub fn current_branch(root: &Path) -> Result<Option<String>> {
+    let out = git(root, &["branch", "--show-current"])?;
+    if !out.status.success() {
+        return Err(SdlcError::Other(format!(
+            "git branch --show-current failed: {}",
+            String::from_utf8_lossy(&out.stderr).trim()
+        )));
+    }
+    let branch = stdout(&out);
+    Ok((!branch.is_empty()).then_some(branch))
+}
+
+/// True if `branch` exists as a local branch.
+pub fn branch_exists(root: &Path, branch: &str) -> bool {
+    git(
+        root,
+        &[
+            "rev-parse",
+            "--verify",
+            "--quiet",
+            &format!("refs/heads/{branch}"),
+        ],
+    )
+    .map(|out| out.status.success())
+    .unwrap_or(false)
+}
+
+/// Paths that would conflict if `head` were merged into `base`.
+/// Empty when the merge is clean.
+pub fn merge_conflicts(root: &Path, base: &str, head: &str) -> Result<Vec<String>> {
+    let out = git(
+        root,
+        &[
+            "merge-tree",
+            "--write-tree",
+            "--name-only",
+            "--no-messages",
+            base,
+            head,
+        ],
+    )?;
+    match out.status.code() {
+        Some(0) => Ok(Vec::new()),
+        // Exit 1 means conflicts: the first line is the tree OID, followed by
+        // one conflicted path per line.
+        Some(1) => {
+            let mut files: Vec<String> = stdout(&out)
+                .lines()
+                .skip(1)
+                .filter(|l| !l.is_empty())
+                .map(str::to_string)
+                .collect();
+            files.dedup();
+            Ok(files)
+        }
+        _ => Err(SdlcError::Other(format!(
+            "git merge-tree {base} {head} failed: {}",
+            String::from_utf8_lossy(&out.stderr).trim()
+        ))),
+    }
+}
+
+/// Fail with `SdlcError::MergeConflict` if `head` does not merge cleanly
+/// into `base`.
+pub fn check_merge(root: &Path, base: &str, head: &str) -> Result<()> {
+    let files = merge_conflicts(root, base, head)?;
+    if files.is_empty() {
+        Ok(())
+    } else {
+        Err(SdlcError::MergeConflict { files })
+    }
+}
+
+/// How [`verify_clean_merge`] went, when nothing conflicts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MergeCheck {
+    /// The current branch merges cleanly into the base branch.
+    Clean,
+    /// Nothing to check: not a git repo, no local base branch, or the base
+    /// branch is checked out.
+    NotApplicable,
+    /// The installed git is too old for `merge-tree --write-tree`; carries
+    /// its version, if known.
+    UnsupportedGit(Option<(u32, u32)>),
+}
+
+impl MergeCheck {
+    /// The warning to show when the check could not run.
+    pub fn warning(&self) -> Option<String> {
+        let MergeCheck::UnsupportedGit(found) = self else {
+            return None;
+        };
+        let (major, minor) = MERGE_TREE_MIN_VERSION;
+        let found = match found {
+            Some((a, b)) => format!("git {a}.{b} is"),
+            None => "the installed git version is unknown, so it may be".to_string(),
+        };
+        Some(format!(
+            "{found} older than {major}.{minor}; skipping the merge conflict check"
+        ))
+    }
+}
+
+/// Check that the checked-out branch (or detached HEAD) merges cleanly into
+/// `base`, failing with `SdlcError::MergeConflict` if it does not.
+pub fn verify_clean_merge(root: &Path, base: &str) -> Result<MergeCheck> {
+    if !is_repo(root) || !branch_exists(root, base) {
+        return Ok(MergeCheck::NotApplicable);
+    }
+    let head = current_branch(root)?.unwrap_or_else(|| "HEAD".to_string());
+    if head == base {
+        return Ok(MergeCheck::NotApplicable);
+    }
+    let version = version();
+    if version.is_none_or(|v| v < MERGE_TREE_MIN_VERSION) {
+        return Ok(MergeCheck::UnsupportedGit(version));
+    }
+    check_merge(root, base, &head)?;
+    Ok(MergeCheck::Clean)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn run(root: &Path, args: &[&str]) {
+        let out = git(root, args).unwrap();
+        assert!(out.status.success(), "git {args:?}: {out:?}");
+    }
+
+    fn commit_file(root: &Path, name: &str, contents: &str, message: &str) {
+        std::fs::write(root.join(name), contents).unwrap();
+        run(root, &["add", name]);
+        run(root, &["commit", "-q", "-m", message]);
+    }
+
+    fn repo() -> TempDir {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path();
+        run(root, &["init", "-q", "-b", "main"]);
+        run(root, &["config", "user.email", "test@example.com"]);
+        run(root, &["config", "user.name", "Test"]);
+        commit_file(root, "app.txt", "one\n", "initial");
+        dir
+    }
+
+    #[test]
+    fn conflicting_branch_lists_paths() {
+        let dir = repo();
+        let root = dir.path();
+        run(root, &["checkout", "-q", "-b", "feature"]);
+        commit_file(root, "app.txt", "feature\n", "feature edit");
+        run(root, &["checkout", "-q", "main"]);
+        commit_file(root, "app.txt", "main\n", "main edit");
+
+        // `merge-tree --write-tree` needs a newer git.
+        if version().is_none_or(|v| v < MERGE_TREE_MIN_VERSION) {
+            return;
+        }
+        let err = check_merge(root, "main", "feature").unwrap_err();
+        match err {
+            SdlcError::MergeConflict { files } => assert_eq!(files, vec!["app.txt"]),
+            other => panic!("unexpected error: {other}"),
+        }
+        // The working tree is untouched.
+        assert_eq!(
+            std::fs::read_to_string(root.join("app.txt")).unwrap(),
+            "main\n"
+        );
+    }
+
+    #[test]
+    fn clean_branch_passes() {
+        let dir = repo();
+        let root = dir.path();
+        run(root, &["checkout", "-q", "-b", "feature"]);
+        commit_file(root, "new.txt", "added\n", "feature add");
+
+        assert!(is_repo(root));
+        assert_eq!(current_branch(root).unwrap().as_deref(), Some("feature"));
+        assert!(branch_exists(root, "main"));
+        if version().is_none_or(|v| v < MERGE_TREE_MIN_VERSION) {
+            return;
+        }
+        check_merge(root, "main", "feature").unwrap();
+    }
+
+    #[test]
+    fn parses_git_versions() {
+        assert_eq!(parse_version("git version 2.39.2"), Some((2, 39)));
+        assert_eq!(
+            parse_version("git version 2.39.2 (Apple Git-143)"),
+            Some((2, 39))
+        );
+        assert_eq!(parse_version("git version 2.45.1.windows.1"), Some((2, 45)));
+        assert_eq!(parse_version("git version 3"), None);
+        assert_eq!(parse_version("not git"), None);
+    }
+
+    #[test]
+    fn verify_clean_merge_uses_the_given_base_branch() {
+        let dir = repo();
+        let root = dir.path();
+        run(root, &["checkout", "-q", "-b", "trunk"]);
+        commit_file(root, "app.txt", "trunk\n", "trunk edit");
+        run(root, &["checkout", "-q", "main"]);
+        run(root, &["checkout", "-q", "-b", "feature"]);
+        commit_file(root, "app.txt", "feature\n", "feature edit");
+
+        if version().is_none_or(|v| v < MERGE_TREE_MIN_VERSION) {
+            assert!(matches!(
+                verify_clean_merge(root, "trunk").unwrap(),
+                MergeCheck::UnsupportedGit(_)
+            ));
+            return;
+        }
+        assert_eq!(verify_clean_merge(root, "main").unwrap(), MergeCheck::Clean);
+        assert!(matches!(
+            verify_clean_merge(root, "trunk"),
+            Err(SdlcError::MergeConflict { .. })
+        ));
+        assert_eq!(
+            verify_clean_merge(root, "missing").unwrap(),
+            MergeCheck::NotApplicable
+        );
+    }
+
+    #[test]
+    fn unsupported_git_warns() {
+        assert_eq!(MergeCheck::Clean.warning(), None);
+        assert_eq!(
+            MergeCheck::UnsupportedGit(Some((2, 34))).warning().unwrap(),
+            "git 2.34 is older than 2.38; skipping the merge conflict check"
+        );
+    }
+}
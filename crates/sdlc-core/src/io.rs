@@ -1,21 +1,101 @@
-use crate::error::Result;
+use crate::error::{Result, SdlcError};
+use fs4::FileExt;
 use std::io::Write;
-use std::path::Path;
-use tempfile::NamedTempFile;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime};
+
+/// Name prefix of the sibling temp files [`atomic_write`] renames into place.
+pub const TEMP_FILE_PREFIX: &str = ".tmp-";
+
+/// Length of the random suffix `tempfile` appends to the prefix.
+const TEMP_SUFFIX_LEN: usize = 6;
+
+/// A temp file older than this was left behind by a write that never
+/// finished (the process was killed before the rename) and is swept.
+pub const TEMP_STALE_AFTER: Duration = Duration::from_secs(60);
 
 /// Atomically write `data` to `path` using a tempfile in the same directory.
 /// Prevents partial writes from corrupting state files.
+///
+/// Inside `.sdlc/`, stale temp files from interrupted writes in the same
+/// directory are removed first. Before the rename, a YAML or JSON temp file
+/// is re-read and parsed; if it does not parse, the target is left untouched.
 pub fn atomic_write(path: &Path, data: &[u8]) -> Result<()> {
     if let Some(parent) = path.parent() {
         std::fs::create_dir_all(parent)?;
     }
     let dir = path.parent().unwrap_or(Path::new("."));
-    let mut tmp = NamedTempFile::new_in(dir)?;
+    if is_sdlc_dir(dir) {
+        sweep_stale_temp_files(dir);
+    }
+    let mut tmp = tempfile::Builder::new()
+        .prefix(TEMP_FILE_PREFIX)
+        .rand_bytes(TEMP_SUFFIX_LEN)
+        .tempfile_in(dir)?;
     tmp.write_all(data)?;
+    if !parses_as_target(path, tmp.path()) {
+        return Err(SdlcError::WriteNotPersisted(path.display().to_string()));
+    }
     tmp.persist(path).map_err(|e| e.error)?;
     Ok(())
 }
 
+/// Whether the written temp file `written` reads back as a complete file
+/// of `target`'s kind: for YAML and JSON it must parse, anything else only
+/// has to be there.
+fn parses_as_target(target: &Path, written: &Path) -> bool {
+    match target.extension().and_then(|e| e.to_str()) {
+        Some("yaml" | "yml") => std::fs::read_to_string(written)
+            .ok()
+            .is_some_and(|text| serde_yaml::from_str::<serde_yaml::Value>(&text).is_ok()),
+        Some("json") => std::fs::read(written)
+            .ok()
+            .is_some_and(|bytes| serde_json::from_slice::<serde_json::Value>(&bytes).is_ok()),
+        _ => written.is_file(),
+    }
+}
+
+/// Whether `name` is an [`atomic_write`] temp file: [`TEMP_FILE_PREFIX`],
+/// or the `.tmp` older versions used, followed by exactly the random
+/// suffix. Directory loaders skip these.
+pub fn is_temp_file(name: &str) -> bool {
+    let suffix = name
+        .strip_prefix(TEMP_FILE_PREFIX)
+        .or_else(|| name.strip_prefix(".tmp"));
+    suffix
+        .is_some_and(|s| s.len() == TEMP_SUFFIX_LEN && s.bytes().all(|b| b.is_ascii_alphanumeric()))
+}
+
+/// Only directories inside `.sdlc/` are swept; anywhere else (the project
+/// root, say) a matching name may be the user's own file.
+fn is_sdlc_dir(dir: &Path) -> bool {
+    dir.components()
+        .any(|c| c.as_os_str() == crate::paths::SDLC_DIR)
+}
+
+/// Best-effort removal of temp files in `dir` older than [`TEMP_STALE_AFTER`].
+/// Younger ones may belong to a write still in flight and are left alone.
+fn sweep_stale_temp_files(dir: &Path) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        if !is_temp_file(&entry.file_name().to_string_lossy()) {
+            continue;
+        }
+        let stale = entry
+            .metadata()
+            .ok()
+            .filter(|m| m.is_file())
+            .and_then(|m| m.modified().ok())
+            .and_then(|m| SystemTime::now().duration_since(m).ok())
+            .is_some_and(|age| age > TEMP_STALE_AFTER);
+        if stale {
+            let _ = std::fs::remove_file(entry.path());
+        }
+    }
+}
+
 /// Create a directory and all parents, idempotent.
 pub fn ensure_dir(path: &Path) -> Result<()> {
     std::fs::create_dir_all(path)?;
@@ -104,6 +184,72 @@ pub fn ensure_gitignore_entry(root: &Path, entry: &str) -> Result<()> {
     Ok(())
 }
 
+// ---------------------------------------------------------------------------
+// Advisory lock files
+// ---------------------------------------------------------------------------
+
+const LOCK_POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Held advisory lock. The OS releases it when the guard is dropped or the
+/// holding process dies; the lock file itself is left in place.
+#[derive(Debug)]
+pub struct FileLock {
+    path: PathBuf,
+    file: std::fs::File,
+}
+
+impl FileLock {
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Drop for FileLock {
+    fn drop(&mut self) {
+        let _ = FileExt::unlock(&self.file);
+    }
+}
+
+/// Acquire an exclusive OS advisory lock (`flock` / `LockFileEx`) on `path`,
+/// creating it if needed and recording the holder's pid for diagnostics.
+/// Waits up to `timeout` for the current holder to release it.
+///
+/// A crashed holder's lock is released by the OS, so there is nothing stale
+/// to break. The lock belongs to the open file, not the process: it also
+/// serializes threads.
+pub fn lock_file(path: &Path, timeout: Duration) -> Result<FileLock> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let mut file = std::fs::OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(path)?;
+    let deadline = Instant::now() + timeout;
+    loop {
+        match file.try_lock_exclusive() {
+            Ok(()) => break,
+            Err(e) if e.kind() == fs4::lock_contended_error().kind() => {
+                if Instant::now() >= deadline {
+                    return Err(SdlcError::LockTimeout(path.display().to_string()));
+                }
+                std::thread::sleep(LOCK_POLL_INTERVAL);
+            }
+            Err(e) => return Err(e.into()),
+        }
+    }
+    // Best effort: the pid is informational only.
+    let _ = file
+        .set_len(0)
+        .and_then(|()| file.write_all(std::process::id().to_string().as_bytes()));
+    Ok(FileLock {
+        path: path.to_path_buf(),
+        file,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -117,6 +263,79 @@ mod tests {
         assert_eq!(std::fs::read_to_string(&path).unwrap(), "hello: world");
     }
 
+    #[test]
+    fn atomic_write_sweeps_stale_temp_files_left_by_an_interrupted_write() {
+        let root = TempDir::new().unwrap();
+        let dir = root.path().join(".sdlc/features/auth");
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("manifest.yaml");
+        std::fs::write(&path, "slug: real\n").unwrap();
+        // A write killed before its rename leaves a partial temp file behind.
+        let stale = dir.join(".tmp-Ab12Cd");
+        std::fs::write(&stale, "slug: par").unwrap();
+        std::fs::File::options()
+            .write(true)
+            .open(&stale)
+            .unwrap()
+            .set_modified(SystemTime::now() - TEMP_STALE_AFTER - Duration::from_secs(1))
+            .unwrap();
+        // One from a write that may still be in flight.
+        let fresh = dir.join(".tmp-Ef34Gh");
+        std::fs::write(&fresh, "slug: par").unwrap();
+
+        let listed = crate::workspace::list_artifacts(&dir, &[]).unwrap();
+        let names: Vec<_> = listed.iter().map(|a| a.filename.as_str()).collect();
+        assert_eq!(names, ["manifest.yaml"]);
+
+        atomic_write(&path, b"slug: updated\n").unwrap();
+        assert!(!stale.exists());
+        assert!(fresh.exists());
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "slug: updated\n");
+        let leftovers: Vec<_> = std::fs::read_dir(&dir)
+            .unwrap()
+            .map(|e| e.unwrap().file_name().to_string_lossy().into_owned())
+            .filter(|n| is_temp_file(n))
+            .collect();
+        assert_eq!(leftovers, [".tmp-Ef34Gh"]);
+    }
+
+    #[test]
+    fn user_files_that_look_like_temp_files_are_left_alone() {
+        let dir = TempDir::new().unwrap();
+        let old = SystemTime::now() - TEMP_STALE_AFTER - Duration::from_secs(1);
+        let mut kept = Vec::new();
+        // `.tmp-Ab12Cd` has the temp-file shape but sits outside `.sdlc/`.
+        for name in [".tmp-notes", ".tmpfile", ".tmp-Ab12Cd"] {
+            let file = dir.path().join(name);
+            std::fs::write(&file, "mine").unwrap();
+            std::fs::File::options()
+                .write(true)
+                .open(&file)
+                .unwrap()
+                .set_modified(old)
+                .unwrap();
+            kept.push(file);
+        }
+        assert!(!is_temp_file(".tmp-notes"));
+        assert!(!is_temp_file(".tmpfile"));
+        assert!(is_temp_file(".tmpAb12Cd"));
+
+        atomic_write(&dir.path().join("AGENTS.md"), b"# Agents\n").unwrap();
+        assert!(kept.iter().all(|f| f.exists()));
+    }
+
+    #[test]
+    fn atomic_write_rejects_a_target_that_does_not_parse() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("state.yaml");
+        atomic_write(&path, b"project: x\n").unwrap();
+        let err = atomic_write(&path, b"project: [unclosed\n").unwrap_err();
+        assert!(matches!(err, SdlcError::WriteNotPersisted(_)), "{err}");
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "project: x\n");
+        let leftovers = std::fs::read_dir(dir.path()).unwrap().count();
+        assert_eq!(leftovers, 1, "the rejected temp file is cleaned up");
+    }
+
     #[test]
     fn atomic_write_creates_parents() {
         let dir = TempDir::new().unwrap();
@@ -167,4 +386,59 @@ mod tests {
         assert!(!written);
         assert_eq!(std::fs::read_to_string(&path).unwrap(), "original");
     }
+
+    #[test]
+    fn lock_file_times_out_while_held() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("state.lock");
+        let guard = lock_file(&path, Duration::from_secs(1)).unwrap();
+        assert!(matches!(
+            lock_file(&path, Duration::from_millis(50)),
+            Err(SdlcError::LockTimeout(_))
+        ));
+        drop(guard);
+        lock_file(&path, Duration::from_millis(50)).unwrap();
+    }
+
+    #[test]
+    fn lock_file_ignores_a_leftover_lock_file() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("state.lock");
+        // Left behind by a crashed holder — no OS lock is held on it.
+        std::fs::write(&path, "1").unwrap();
+        let guard = lock_file(&path, Duration::from_millis(50)).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(guard.path()).unwrap(),
+            std::process::id().to_string()
+        );
+    }
+
+    #[test]
+    fn lock_file_serializes_threads() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("state.lock");
+        let counter = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let handles: Vec<_> = (0..4)
+            .map(|i| {
+                let path = path.clone();
+                let counter = counter.clone();
+                std::thread::spawn(move || {
+                    let _guard = lock_file(&path, Duration::from_secs(5)).unwrap();
+                    counter.lock().unwrap().push((i, "in"));
+                    std::thread::sleep(Duration::from_millis(20));
+                    counter.lock().unwrap().push((i, "out"));
+                })
+            })
+            .collect();
+        for h in handles {
+            h.join().unwrap();
+        }
+        let log = counter.lock().unwrap();
+        for pair in log.chunks(2) {
+            assert_eq!(
+                pair[0].0, pair[1].0,
+                "critical sections interleaved: {log:?}"
+            );
+        }
+    }
 }
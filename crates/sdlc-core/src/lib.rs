@@ -1,19 +1,25 @@
 pub mod advisory;
+pub mod agent;
 pub mod ama_thread;
 pub mod artifact;
+pub mod audit;
 pub mod auth_config;
 pub mod backlog;
+pub mod backup;
 pub mod classifier;
 pub mod comment;
 pub mod config;
 pub mod directive;
+pub mod doctor;
 pub mod error;
 pub mod escalation;
 pub mod event_log;
 pub mod feature;
+pub mod feature_template;
 pub mod feedback;
 pub mod feedback_thread;
 pub mod focus;
+pub mod git;
 pub mod investigation;
 pub mod io;
 pub mod knowledge;
@@ -22,20 +28,31 @@ pub mod milestone;
 pub mod orchestrator;
 pub mod parallel_work;
 pub mod paths;
+pub mod plan;
+pub mod platform_runner;
 pub mod ponder;
 pub mod prepare;
+pub mod prompt_template;
+pub mod query;
+pub mod rename_term;
+pub mod retry;
 pub mod rules;
 pub mod score;
 pub mod search;
 pub mod secrets;
+pub mod secrets_backend;
+pub mod since;
+pub mod slug;
 pub mod spikes;
 pub mod state;
+pub mod state_store;
 pub mod task;
 pub mod telemetry_backend;
 pub mod tool_interaction;
 pub mod tool_runner;
 pub mod types;
 pub mod ui_registry;
+pub mod wave_run;
 pub mod workspace;
 
 pub use error::{Result, SdlcError};
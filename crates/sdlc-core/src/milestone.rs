@@ -1,9 +1,11 @@
+use crate::audit::{AuditAction, AuditEntry};
 use crate::error::{Result, SdlcError};
 use crate::feature::Feature;
 use crate::paths;
-use crate::types::Phase;
-use chrono::{DateTime, Utc};
+use crate::types::{Phase, TaskStatus};
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fmt;
 use std::path::Path;
 
@@ -301,12 +303,18 @@ impl Milestone {
         crate::io::atomic_write(&path, content.as_bytes())
     }
 
-    /// Replace the feature order with `ordered`. Every slug currently in
-    /// `self.features` must appear exactly once in `ordered`.
+    /// Replace the feature order with `ordered`, which must be a permutation
+    /// of exactly the milestone's current features. Slugs are trimmed, and a
+    /// feature listed twice in the stored order is counted once, so the saved
+    /// order never holds duplicates.
+    ///
+    /// On a mismatch the error names every missing and every unknown slug.
     pub fn reorder_features(&mut self, ordered: &[&str]) -> Result<()> {
+        let ordered: Vec<&str> = ordered.iter().map(|s| s.trim()).collect();
+
         // Check for duplicates in the input list
         let mut seen = std::collections::HashSet::new();
-        for &s in ordered {
+        for &s in &ordered {
             if !seen.insert(s) {
                 return Err(SdlcError::InvalidFeatureOrder(format!(
                     "duplicate slug in order list: '{s}'"
@@ -314,26 +322,40 @@ impl Milestone {
             }
         }
 
-        // Build a set of existing features for O(n) lookups
         let existing: std::collections::HashSet<&str> =
             self.features.iter().map(|s| s.as_str()).collect();
-
-        // Check for slugs in ordered that are not in self.features
-        for &s in ordered {
-            if !existing.contains(s) {
-                return Err(SdlcError::InvalidFeatureOrder(format!(
-                    "'{s}' is not in this milestone"
-                )));
+        let unknown: Vec<&str> = ordered
+            .iter()
+            .copied()
+            .filter(|s| !existing.contains(s))
+            .collect();
+        let mut missing: Vec<&str> = Vec::new();
+        for f in &self.features {
+            if !seen.contains(f.as_str()) && !missing.contains(&f.as_str()) {
+                missing.push(f);
             }
         }
 
-        // Check for slugs in self.features that are missing from ordered
-        for f in &self.features {
-            if !seen.contains(f.as_str()) {
-                return Err(SdlcError::InvalidFeatureOrder(format!(
-                    "missing slug in order list: '{f}'"
-                )));
-            }
+        let quoted = |slugs: &[&str]| {
+            slugs
+                .iter()
+                .map(|s| format!("'{s}'"))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        let mut problems = Vec::new();
+        match missing.as_slice() {
+            [] => {}
+            [one] => problems.push(format!("missing slug in order list: '{one}'")),
+            many => problems.push(format!("missing slugs in order list: {}", quoted(many))),
+        }
+        match unknown.as_slice() {
+            [] => {}
+            [one] => problems.push(format!("'{one}' is not in this milestone")),
+            many => problems.push(format!("{} are not in this milestone", quoted(many))),
+        }
+        if !problems.is_empty() {
+            return Err(SdlcError::InvalidFeatureOrder(problems.join("; ")));
         }
 
         self.features = ordered.iter().map(|s| s.to_string()).collect();
@@ -359,6 +381,84 @@ impl Milestone {
     }
 }
 
+// ---------------------------------------------------------------------------
+// Acceptance test checklist
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum AcceptanceStepStatus {
+    /// Not exercised yet.
+    Pending,
+    Passed,
+    /// Unchecked and struck through, or annotated with `✗`.
+    Failed,
+}
+
+/// One `- [ ]` / `- [x]` item of an acceptance test, in the form the UAT
+/// playbook signs off in `uat_results.md`:
+///
+/// ```text
+/// - [x] <step text> _(<timestamp>)_
+/// - [ ] ~~<step text>~~ _(✗ task <feature>#<id> — <reason>)_
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct AcceptanceStep {
+    pub text: String,
+    pub checked: bool,
+    /// Trailing `_(…)_` note, without the delimiters.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub annotation: Option<String>,
+    pub status: AcceptanceStepStatus,
+}
+
+/// Extract the checklist steps of an acceptance test in document order.
+/// Lines that are not checklist items, or sit inside a fenced code block,
+/// are ignored.
+pub fn parse_acceptance_test(content: &str) -> Vec<AcceptanceStep> {
+    crate::artifact::parse_task_checklist(content)
+        .into_iter()
+        .filter_map(acceptance_step)
+        .collect()
+}
+
+/// Split a checklist item into its step text, annotation, and status.
+fn acceptance_step(item: crate::artifact::ChecklistItem) -> Option<AcceptanceStep> {
+    let checked = item.checked;
+    let mut text = item.title.as_str();
+    let mut annotation = None;
+    if let Some((body, note)) = text
+        .strip_suffix(")_")
+        .and_then(|inner| inner.rsplit_once("_("))
+    {
+        annotation = Some(note.trim().to_string());
+        text = body.trim_end();
+    }
+    let struck = text.len() > 4 && text.starts_with("~~") && text.ends_with("~~");
+    if struck {
+        text = &text[2..text.len() - 2];
+    }
+    let text = text.trim();
+    if text.is_empty() {
+        return None;
+    }
+
+    let failed = struck || annotation.as_deref().is_some_and(|a| a.starts_with('✗'));
+    let status = if checked {
+        AcceptanceStepStatus::Passed
+    } else if failed {
+        AcceptanceStepStatus::Failed
+    } else {
+        AcceptanceStepStatus::Pending
+    };
+    Some(AcceptanceStep {
+        text: text.to_string(),
+        checked,
+        annotation,
+        status,
+    })
+}
+
 // ---------------------------------------------------------------------------
 // UatVerdict / UatRun
 // ---------------------------------------------------------------------------
@@ -500,6 +600,524 @@ pub fn latest_uat_run(root: &Path, milestone_slug: &str) -> Result<Option<UatRun
     Ok(list_uat_runs(root, milestone_slug)?.into_iter().next())
 }
 
+// ---------------------------------------------------------------------------
+// Bundle export / import
+// ---------------------------------------------------------------------------
+
+/// Name of the manifest entry at the root of a bundle archive.
+pub const BUNDLE_MANIFEST: &str = "bundle.yaml";
+pub const BUNDLE_FORMAT_VERSION: u32 = 1;
+
+/// Describes the contents of a milestone bundle.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BundleManifest {
+    pub format_version: u32,
+    pub milestone: String,
+    pub title: String,
+    pub features: Vec<String>,
+    pub exported_at: DateTime<Utc>,
+    /// Every file in the bundle, relative to the project root.
+    pub files: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BundleFile {
+    /// Path relative to the project root, e.g. `.sdlc/features/auth/spec.md`.
+    pub path: String,
+    pub contents: Vec<u8>,
+}
+
+/// A milestone with its features, artifacts, tasks, and acceptance test,
+/// detached from any project. Serialized as a gzipped tarball.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bundle {
+    pub manifest: BundleManifest,
+    pub files: Vec<BundleFile>,
+}
+
+impl Bundle {
+    /// Write the bundle as a `.tar.gz` at `out` (atomically replaced).
+    pub fn write_tar_gz(&self, out: &Path) -> Result<()> {
+        let dir = match out.parent() {
+            Some(p) if !p.as_os_str().is_empty() => p,
+            _ => Path::new("."),
+        };
+        std::fs::create_dir_all(dir)?;
+        let tmp = tempfile::NamedTempFile::new_in(dir)?;
+        let gz = flate2::write::GzEncoder::new(tmp, flate2::Compression::default());
+        let mut tar = tar::Builder::new(gz);
+        let mtime = self.manifest.exported_at.timestamp().max(0) as u64;
+
+        let manifest = serde_yaml::to_string(&self.manifest)?;
+        append_tar_entry(&mut tar, BUNDLE_MANIFEST, manifest.as_bytes(), mtime)?;
+        for file in &self.files {
+            append_tar_entry(&mut tar, &file.path, &file.contents, mtime)?;
+        }
+
+        let tmp = tar.into_inner()?.finish()?;
+        tmp.persist(out).map_err(|e| e.error)?;
+        Ok(())
+    }
+
+    /// Read a bundle written by [`Bundle::write_tar_gz`].
+    pub fn read_tar_gz(path: &Path) -> Result<Self> {
+        let file = std::fs::File::open(path)?;
+        let mut archive = tar::Archive::new(flate2::read::GzDecoder::new(file));
+        let mut manifest: Option<BundleManifest> = None;
+        let mut files = Vec::new();
+        for entry in archive.entries()? {
+            let mut entry = entry?;
+            if !entry.header().entry_type().is_file() {
+                continue;
+            }
+            let entry_path = entry.path()?.to_string_lossy().into_owned();
+            let mut contents = Vec::new();
+            std::io::Read::read_to_end(&mut entry, &mut contents)?;
+            if entry_path == BUNDLE_MANIFEST {
+                manifest = Some(serde_yaml::from_slice(&contents)?);
+            } else {
+                files.push(BundleFile {
+                    path: entry_path,
+                    contents,
+                });
+            }
+        }
+        let manifest = manifest.ok_or_else(|| {
+            SdlcError::InvalidBundle(format!("{}: missing {BUNDLE_MANIFEST}", path.display()))
+        })?;
+        Ok(Self { manifest, files })
+    }
+}
+
+fn append_tar_entry<W: std::io::Write>(
+    tar: &mut tar::Builder<W>,
+    path: &str,
+    contents: &[u8],
+    mtime: u64,
+) -> Result<()> {
+    let mut header = tar::Header::new_gnu();
+    header.set_size(contents.len() as u64);
+    header.set_mode(0o644);
+    header.set_mtime(mtime);
+    header.set_cksum();
+    tar.append_data(&mut header, path, contents)?;
+    Ok(())
+}
+
+/// Recursively collect every file under `dir` as a root-relative bundle path.
+fn collect_files(root: &Path, dir: &Path, out: &mut Vec<BundleFile>) -> Result<()> {
+    if !dir.exists() {
+        return Ok(());
+    }
+    let mut entries: Vec<_> = std::fs::read_dir(dir)?.collect::<std::io::Result<_>>()?;
+    entries.sort_by_key(|e| e.file_name());
+    for entry in entries {
+        let path = entry.path();
+        if entry.file_type()?.is_dir() {
+            collect_files(root, &path, out)?;
+        } else {
+            let rel = path.strip_prefix(root).unwrap_or(&path);
+            out.push(BundleFile {
+                path: rel.to_string_lossy().replace('\\', "/"),
+                contents: std::fs::read(&path)?,
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Package a milestone and every feature it references into a [`Bundle`].
+pub fn export(root: &Path, slug: &str) -> Result<Bundle> {
+    let milestone = Milestone::load(root, slug)?;
+    let mut files = Vec::new();
+    collect_files(root, &paths::milestone_dir(root, slug), &mut files)?;
+    for feature in &milestone.features {
+        let dir = paths::feature_dir(root, feature);
+        if !dir.exists() {
+            return Err(SdlcError::FeatureNotFound(feature.clone()));
+        }
+        collect_files(root, &dir, &mut files)?;
+    }
+
+    Ok(Bundle {
+        manifest: BundleManifest {
+            format_version: BUNDLE_FORMAT_VERSION,
+            milestone: milestone.slug,
+            title: milestone.title,
+            features: milestone.features,
+            exported_at: Utc::now(),
+            files: files.iter().map(|f| f.path.clone()).collect(),
+        },
+        files,
+    })
+}
+
+/// Re-create a bundled milestone and its features under `root`.
+///
+/// Idempotent: files are overwritten with the bundled contents and the
+/// milestone and features are registered in state only if missing.
+pub fn import(root: &Path, bundle: &Bundle) -> Result<()> {
+    let manifest = &bundle.manifest;
+    if manifest.format_version > BUNDLE_FORMAT_VERSION {
+        return Err(SdlcError::InvalidBundle(format!(
+            "format version {} is newer than supported version {BUNDLE_FORMAT_VERSION}",
+            manifest.format_version
+        )));
+    }
+    paths::validate_slug(&manifest.milestone)?;
+    for feature in &manifest.features {
+        paths::validate_slug(feature)?;
+    }
+    for listed in &manifest.files {
+        if !bundle.files.iter().any(|f| &f.path == listed) {
+            return Err(SdlcError::InvalidBundle(format!("missing file {listed}")));
+        }
+    }
+
+    // Only allow writes inside the bundled milestone and feature directories.
+    let allowed: Vec<std::path::PathBuf> =
+        std::iter::once(Path::new(paths::MILESTONES_DIR).join(&manifest.milestone))
+            .chain(
+                manifest
+                    .features
+                    .iter()
+                    .map(|f| Path::new(paths::FEATURES_DIR).join(f)),
+            )
+            .collect();
+    for file in &bundle.files {
+        let rel = Path::new(&file.path);
+        let normal = rel
+            .components()
+            .all(|c| matches!(c, std::path::Component::Normal(_)));
+        if !normal || !allowed.iter().any(|dir| rel.starts_with(dir)) {
+            return Err(SdlcError::InvalidBundle(format!(
+                "file outside the bundled milestone: {}",
+                file.path
+            )));
+        }
+    }
+
+    if !paths::state_path(root).exists() {
+        return Err(SdlcError::NotInitialized);
+    }
+    for file in &bundle.files {
+        crate::io::atomic_write(&root.join(&file.path), &file.contents)?;
+    }
+    let mut active = Vec::new();
+    for slug in &manifest.features {
+        if !Feature::load(root, slug)?.archived {
+            active.push(slug.as_str());
+        }
+    }
+    crate::state::State::update(root, |state| {
+        state.add_milestone(&manifest.milestone);
+        for slug in active {
+            state.add_active_feature(slug);
+        }
+    })
+}
+
+// ---------------------------------------------------------------------------
+// Burndown
+// ---------------------------------------------------------------------------
+
+/// Work remaining in a milestone at the end of one day.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BurndownPoint {
+    pub date: NaiveDate,
+    /// Tasks not yet completed.
+    pub remaining_tasks: usize,
+    pub total_tasks: usize,
+    /// Features not yet released.
+    pub remaining_features: usize,
+    pub total_features: usize,
+}
+
+/// Daily remaining tasks and features for the milestone's current features,
+/// replayed from `.sdlc/audit.log`, from the first day any of them existed
+/// through today.
+///
+/// Tasks the log never mentions predate it and count at their current
+/// status from the feature's creation. A milestone with no features yields
+/// no points.
+pub fn burndown(root: &Path, slug: &str) -> Result<Vec<BurndownPoint>> {
+    let milestone = Milestone::load(root, slug)?;
+    let mut features = Vec::new();
+    for feature_slug in &milestone.features {
+        match Feature::load(root, feature_slug) {
+            Ok(feature) => features.push(feature),
+            Err(SdlcError::FeatureNotFound(_)) => continue,
+            Err(e) => return Err(e),
+        }
+    }
+    let entries = crate::audit::read(root, None)?;
+    Ok(burndown_from(&features, &entries, Utc::now().date_naive()))
+}
+
+fn burndown_from(
+    features: &[Feature],
+    entries: &[AuditEntry],
+    until: NaiveDate,
+) -> Vec<BurndownPoint> {
+    let histories: Vec<FeatureHistory> = features
+        .iter()
+        .map(|f| FeatureHistory::new(f, entries))
+        .collect();
+    let Some(start) = histories.iter().map(|h| h.start).min() else {
+        return Vec::new();
+    };
+    start
+        .iter_days()
+        .take_while(|date| *date <= until)
+        .map(|date| {
+            let mut point = BurndownPoint {
+                date,
+                remaining_tasks: 0,
+                total_tasks: 0,
+                remaining_features: 0,
+                total_features: 0,
+            };
+            for history in &histories {
+                history.add_to(&mut point);
+            }
+            point
+        })
+        .collect()
+}
+
+/// One feature's audit entries, replayed up to a given day.
+struct FeatureHistory<'a> {
+    start: NaiveDate,
+    initial_phase: Phase,
+    /// Tasks that predate the log, and whether each was completed.
+    initial_tasks: BTreeMap<String, bool>,
+    entries: Vec<&'a AuditEntry>,
+}
+
+impl<'a> FeatureHistory<'a> {
+    fn new(feature: &Feature, entries: &'a [AuditEntry]) -> Self {
+        let entries: Vec<&AuditEntry> = entries
+            .iter()
+            .filter(|e| e.feature == feature.slug)
+            .collect();
+        let start = entries
+            .first()
+            .map_or(feature.created_at, |e| e.timestamp.min(feature.created_at))
+            .date_naive();
+        let initial_phase = entries.first().map_or(feature.phase, |e| e.phase_before);
+
+        // A task whose first entry is not its addition already existed.
+        let mut seen = BTreeMap::new();
+        for entry in &entries {
+            let Some(id) = &entry.task else { continue };
+            seen.entry(id.clone()).or_insert(match entry.action {
+                AuditAction::TaskAdded => None,
+                _ => Some(entry.from.as_deref().is_some_and(is_done)),
+            });
+        }
+        let mut initial_tasks: BTreeMap<String, bool> = seen
+            .into_iter()
+            .filter_map(|(id, done)| Some((id, done?)))
+            .collect();
+        for task in &feature.tasks {
+            if !entries.iter().any(|e| e.task.as_ref() == Some(&task.id)) {
+                initial_tasks.insert(task.id.clone(), task.status == TaskStatus::Completed);
+            }
+        }
+
+        Self {
+            start,
+            initial_phase,
+            initial_tasks,
+            entries,
+        }
+    }
+
+    /// Add this feature's state at the end of `point.date` to `point`.
+    fn add_to(&self, point: &mut BurndownPoint) {
+        if point.date < self.start {
+            return;
+        }
+        let mut phase = self.initial_phase;
+        let mut tasks = self.initial_tasks.clone();
+        for entry in &self.entries {
+            if entry.timestamp.date_naive() > point.date {
+                break;
+            }
+            phase = entry.phase_after;
+            let Some(id) = &entry.task else { continue };
+            match entry.action {
+                AuditAction::TaskAdded | AuditAction::TaskStatusChanged => {
+                    let done = entry.to.as_deref().is_some_and(is_done);
+                    tasks.insert(id.clone(), done);
+                }
+                AuditAction::TaskRemoved => {
+                    tasks.remove(id);
+                }
+                _ => {}
+            }
+        }
+
+        point.total_features += 1;
+        if phase != Phase::Released {
+            point.remaining_features += 1;
+        }
+        point.total_tasks += tasks.len();
+        point.remaining_tasks += tasks.values().filter(|done| !**done).count();
+    }
+}
+
+fn is_done(status: &str) -> bool {
+    status == TaskStatus::Completed.to_string()
+}
+
+// ---------------------------------------------------------------------------
+// Dependency graph
+// ---------------------------------------------------------------------------
+
+/// A feature in a milestone's dependency graph.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DepNode {
+    pub slug: String,
+    pub title: String,
+    /// `None` for a dependency that no longer exists.
+    pub phase: Option<Phase>,
+    /// False for a dependency that lives outside the milestone.
+    pub in_milestone: bool,
+}
+
+/// `from` depends on `to`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DepEdge {
+    pub from: String,
+    pub to: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DepGraph {
+    pub milestone: String,
+    pub nodes: Vec<DepNode>,
+    pub edges: Vec<DepEdge>,
+}
+
+/// The dependency graph of the milestone's features, in milestone order,
+/// followed by any dependencies from outside it. A cycle through any of its
+/// features is an [`SdlcError::DependencyCycle`] rather than a graph.
+pub fn dep_graph(root: &Path, slug: &str) -> Result<DepGraph> {
+    let milestone = Milestone::load(root, slug)?;
+    let all = Feature::dep_graph(root)?;
+
+    let mut nodes = Vec::new();
+    let mut edges = Vec::new();
+    for feature_slug in &milestone.features {
+        let feature = match Feature::load(root, feature_slug) {
+            Ok(feature) => feature,
+            Err(SdlcError::FeatureNotFound(_)) => continue,
+            Err(e) => return Err(e),
+        };
+        Feature::validate_no_dep_cycle(&feature.slug, &feature.dependencies, &all)?;
+        for dep in &feature.dependencies {
+            edges.push(DepEdge {
+                from: feature.slug.clone(),
+                to: dep.clone(),
+            });
+        }
+        nodes.push(DepNode {
+            slug: feature.slug,
+            title: feature.title,
+            phase: Some(feature.phase),
+            in_milestone: true,
+        });
+    }
+
+    for edge in &edges {
+        if nodes.iter().any(|n| n.slug == edge.to) {
+            continue;
+        }
+        let node = match Feature::load(root, &edge.to) {
+            Ok(feature) => DepNode {
+                slug: feature.slug,
+                title: feature.title,
+                phase: Some(feature.phase),
+                in_milestone: false,
+            },
+            Err(SdlcError::FeatureNotFound(_)) => DepNode {
+                slug: edge.to.clone(),
+                title: edge.to.clone(),
+                phase: None,
+                in_milestone: false,
+            },
+            Err(e) => return Err(e),
+        };
+        nodes.push(node);
+    }
+
+    Ok(DepGraph {
+        milestone: milestone.slug,
+        nodes,
+        edges,
+    })
+}
+
+impl DepNode {
+    fn label(&self) -> String {
+        let phase = self
+            .phase
+            .map(|p| p.to_string())
+            .unwrap_or_else(|| "missing".to_string());
+        format!("{} ({phase})", self.slug)
+    }
+}
+
+impl DepGraph {
+    /// Graphviz DOT; dependencies outside the milestone are dashed.
+    pub fn to_dot(&self) -> String {
+        let mut dot = format!("digraph \"{}\" {{\n  rankdir=LR;\n", self.milestone);
+        for node in &self.nodes {
+            let style = if node.in_milestone {
+                ""
+            } else {
+                ", style=dashed"
+            };
+            dot.push_str(&format!(
+                "  \"{}\" [label=\"{}\"{style}];\n",
+                node.slug,
+                node.label()
+            ));
+        }
+        for edge in &self.edges {
+            dot.push_str(&format!("  \"{}\" -> \"{}\";\n", edge.from, edge.to));
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// A Mermaid flowchart, ready to paste into a markdown code fence.
+    pub fn to_mermaid(&self) -> String {
+        // Mermaid node ids can't contain `-` and must not collide with
+        // keywords like `end`, so every id is prefixed; the label keeps the
+        // real slug.
+        let id = |slug: &str| format!("f_{}", slug.replace('-', "_"));
+        let mut mermaid = String::from("graph LR\n");
+        for node in &self.nodes {
+            let (open, close) = if node.in_milestone {
+                ("[", "]")
+            } else {
+                ("([", "])")
+            };
+            mermaid.push_str(&format!(
+                "  {}{open}\"{}\"{close}\n",
+                id(&node.slug),
+                node.label()
+            ));
+        }
+        for edge in &self.edges {
+            mermaid.push_str(&format!("  {} --> {}\n", id(&edge.from), id(&edge.to)));
+        }
+        mermaid
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Tests
 // ---------------------------------------------------------------------------
@@ -595,6 +1213,40 @@ mod tests {
         assert!(err.to_string().contains("'ghost' is not in this milestone"));
     }
 
+    #[test]
+    fn reorder_names_every_missing_and_unknown_slug() {
+        let dir = TempDir::new().unwrap();
+        setup(&dir);
+
+        let mut m = Milestone::create(dir.path(), "v2", "v2").unwrap();
+        for f in ["a", "b", "c"] {
+            m.add_feature(f);
+        }
+
+        let err = m
+            .reorder_features(&["a", "x", "y"])
+            .unwrap_err()
+            .to_string();
+        assert!(
+            err.contains("missing slugs in order list: 'b', 'c'"),
+            "{err}"
+        );
+        assert!(err.contains("'x', 'y' are not in this milestone"), "{err}");
+        assert_eq!(m.features, vec!["a", "b", "c"]);
+    }
+
+    #[test]
+    fn reorder_normalizes_whitespace_and_stored_duplicates() {
+        let dir = TempDir::new().unwrap();
+        setup(&dir);
+
+        let mut m = Milestone::create(dir.path(), "v2", "v2").unwrap();
+        m.features = vec!["a".into(), "b".into(), "a".into()];
+
+        m.reorder_features(&[" b", "a "]).unwrap();
+        assert_eq!(m.features, vec!["b", "a"]);
+    }
+
     #[test]
     fn reorder_rejects_duplicate() {
         let dir = TempDir::new().unwrap();
@@ -923,4 +1575,323 @@ mod tests {
         assert_eq!(runs.len(), 1);
         assert_eq!(runs[0].screenshot_paths, run.screenshot_paths);
     }
+
+    fn init_project(root: &Path) {
+        crate::config::Config::new("test").save(root).unwrap();
+        crate::state::State::new("test").save(root).unwrap();
+    }
+
+    #[test]
+    fn bundle_round_trip_into_fresh_project() {
+        let src = TempDir::new().unwrap();
+        init_project(src.path());
+        let mut state = crate::state::State::load(src.path()).unwrap();
+        for slug in ["auth", "billing"] {
+            let mut f = Feature::create(src.path(), slug, slug).unwrap();
+            crate::task::add_task(&mut f.tasks, "Write the handler");
+            std::fs::write(
+                paths::feature_dir(src.path(), slug).join("spec.md"),
+                format!("# {slug} spec\n"),
+            )
+            .unwrap();
+            f.approve_artifact(src.path(), crate::types::ArtifactType::Spec, None)
+                .unwrap();
+            f.save(src.path()).unwrap();
+            state.add_active_feature(slug);
+        }
+        let mut m = Milestone::create(src.path(), "v1", "Version 1").unwrap();
+        m.add_feature("auth");
+        m.add_feature("billing");
+        m.save(src.path()).unwrap();
+        m.save_acceptance_test(src.path(), "- [ ] users can log in\n")
+            .unwrap();
+        state.add_milestone("v1");
+        state.save(src.path()).unwrap();
+
+        let out = src.path().join("v1.tar.gz");
+        export(src.path(), "v1")
+            .unwrap()
+            .write_tar_gz(&out)
+            .unwrap();
+        let bundle = Bundle::read_tar_gz(&out).unwrap();
+        assert_eq!(bundle.manifest.milestone, "v1");
+        assert_eq!(bundle.manifest.features, vec!["auth", "billing"]);
+
+        let dst = TempDir::new().unwrap();
+        init_project(dst.path());
+        import(dst.path(), &bundle).unwrap();
+        // Importing again is a no-op.
+        import(dst.path(), &bundle).unwrap();
+
+        for file in &bundle.manifest.files {
+            assert_eq!(
+                std::fs::read(src.path().join(file)).unwrap(),
+                std::fs::read(dst.path().join(file)).unwrap(),
+                "{file} differs"
+            );
+        }
+        let imported = Milestone::load(dst.path(), "v1").unwrap();
+        assert_eq!(
+            imported
+                .load_acceptance_test(dst.path())
+                .unwrap()
+                .as_deref(),
+            Some("- [ ] users can log in\n")
+        );
+        assert_eq!(Feature::load(dst.path(), "auth").unwrap().tasks.len(), 1);
+        let src_state = crate::state::State::load(src.path()).unwrap();
+        let dst_state = crate::state::State::load(dst.path()).unwrap();
+        assert_eq!(dst_state.milestones, src_state.milestones);
+        assert_eq!(dst_state.active_features, src_state.active_features);
+    }
+
+    #[test]
+    fn import_rejects_paths_outside_bundle() {
+        let dir = TempDir::new().unwrap();
+        init_project(dir.path());
+        let bundle = Bundle {
+            manifest: BundleManifest {
+                format_version: BUNDLE_FORMAT_VERSION,
+                milestone: "v1".to_string(),
+                title: "v1".to_string(),
+                features: vec![],
+                exported_at: Utc::now(),
+                files: vec![".sdlc/state.yaml".to_string()],
+            },
+            files: vec![BundleFile {
+                path: ".sdlc/state.yaml".to_string(),
+                contents: b"oops".to_vec(),
+            }],
+        };
+        assert!(matches!(
+            import(dir.path(), &bundle),
+            Err(SdlcError::InvalidBundle(_))
+        ));
+    }
+
+    #[test]
+    fn parse_acceptance_test_reads_checked_unchecked_and_annotated_steps() {
+        let content = "# Acceptance Test: v1
+
+Intro paragraph with a [ ] that is not a step.
+
+- [x] Users can log in _(2026-03-03T14:25:00Z)_
+- [X] Users can reset a password _(fixed: token TTL · 2026-03-03T14:31:00Z)_
+- [ ] ~~Invoices render as PDF~~ _(✗ task billing#T4 — renderer 500s)_
+  * [ ] Empty cart shows a hint
+- [ ] Admin can export users (CSV)
+- [ ]
+
+```markdown
+- [ ] An example inside a code block, not a step
+```
+";
+        let steps = parse_acceptance_test(content);
+        let summary: Vec<_> = steps
+            .iter()
+            .map(|s| (s.text.as_str(), s.checked, s.status))
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                ("Users can log in", true, AcceptanceStepStatus::Passed),
+                (
+                    "Users can reset a password",
+                    true,
+                    AcceptanceStepStatus::Passed
+                ),
+                (
+                    "Invoices render as PDF",
+                    false,
+                    AcceptanceStepStatus::Failed
+                ),
+                (
+                    "Empty cart shows a hint",
+                    false,
+                    AcceptanceStepStatus::Pending
+                ),
+                (
+                    "Admin can export users (CSV)",
+                    false,
+                    AcceptanceStepStatus::Pending
+                ),
+            ]
+        );
+        assert_eq!(
+            steps[1].annotation.as_deref(),
+            Some("fixed: token TTL · 2026-03-03T14:31:00Z")
+        );
+        assert_eq!(
+            steps[2].annotation.as_deref(),
+            Some("✗ task billing#T4 — renderer 500s")
+        );
+        assert_eq!(steps[4].annotation, None);
+    }
+
+    fn entry(
+        day: u32,
+        feature: &str,
+        action: AuditAction,
+        task: Option<&str>,
+        to: Option<&str>,
+    ) -> AuditEntry {
+        let phase_after = if action == AuditAction::Transition {
+            Phase::Released
+        } else {
+            Phase::Implementation
+        };
+        AuditEntry {
+            timestamp: format!("2026-03-{day:02}T12:00:00Z").parse().unwrap(),
+            actor: "test".to_string(),
+            feature: feature.to_string(),
+            action,
+            phase_before: Phase::Implementation,
+            phase_after,
+            detail: None,
+            task: task.map(str::to_string),
+            from: None,
+            to: to.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn burndown_replays_task_completions_per_day() {
+        let created: DateTime<Utc> = "2026-03-01T09:00:00Z".parse().unwrap();
+        let mut auth = Feature::new("auth", "Auth");
+        auth.phase = Phase::Implementation;
+        auth.created_at = created;
+        crate::task::add_task(&mut auth.tasks, "Predates the log");
+        let mut billing = Feature::new("billing", "Billing");
+        billing.created_at = "2026-03-03T09:00:00Z".parse().unwrap();
+
+        use AuditAction::*;
+        let entries = vec![
+            entry(1, "auth", TaskAdded, Some("T2"), Some("pending")),
+            entry(1, "auth", TaskAdded, Some("T3"), Some("pending")),
+            entry(2, "auth", TaskStatusChanged, Some("T2"), Some("completed")),
+            entry(3, "billing", TaskAdded, Some("T1"), Some("pending")),
+            entry(3, "other", TaskAdded, Some("T1"), Some("pending")),
+            entry(4, "auth", TaskStatusChanged, Some("T1"), Some("completed")),
+            entry(4, "auth", TaskStatusChanged, Some("T3"), Some("completed")),
+            entry(5, "auth", Transition, None, None),
+            entry(5, "billing", TaskRemoved, Some("T1"), None),
+        ];
+        let until = NaiveDate::from_ymd_opt(2026, 3, 6).unwrap();
+        let points = burndown_from(&[auth, billing], &entries, until);
+
+        let remaining: Vec<_> = points
+            .iter()
+            .map(|p| (p.date.to_string(), p.remaining_tasks, p.remaining_features))
+            .collect();
+        assert_eq!(
+            remaining,
+            vec![
+                ("2026-03-01".to_string(), 3, 1),
+                ("2026-03-02".to_string(), 2, 1),
+                ("2026-03-03".to_string(), 3, 2),
+                ("2026-03-04".to_string(), 1, 2),
+                ("2026-03-05".to_string(), 0, 1),
+                ("2026-03-06".to_string(), 0, 1),
+            ]
+        );
+        assert_eq!(points[2].total_tasks, 4);
+        assert_eq!(points[4].total_tasks, 3);
+        assert_eq!(points[4].total_features, 2);
+    }
+
+    #[test]
+    fn burndown_of_empty_milestone_has_no_points() {
+        let dir = TempDir::new().unwrap();
+        setup(&dir);
+        Milestone::create(dir.path(), "v2", "v2").unwrap();
+
+        assert!(burndown(dir.path(), "v2").unwrap().is_empty());
+        assert!(matches!(
+            burndown(dir.path(), "nope"),
+            Err(SdlcError::MilestoneNotFound(_))
+        ));
+    }
+
+    fn deps_fixture(dir: &TempDir) {
+        setup(dir);
+        std::fs::create_dir_all(dir.path().join(".sdlc/features")).unwrap();
+        Feature::create(dir.path(), "db", "Database").unwrap();
+        let mut api = Feature::create(dir.path(), "api", "API").unwrap();
+        api.dependencies = vec!["db".into(), "infra".into()];
+        api.save(dir.path()).unwrap();
+        let mut ui = Feature::create(dir.path(), "web-ui", "Web UI").unwrap();
+        ui.dependencies = vec!["api".into()];
+        ui.save(dir.path()).unwrap();
+        Feature::create(dir.path(), "infra", "Infra").unwrap();
+
+        let mut m = Milestone::create(dir.path(), "v1", "V1").unwrap();
+        for f in ["db", "api", "web-ui"] {
+            m.add_feature(f);
+        }
+        m.save(dir.path()).unwrap();
+    }
+
+    #[test]
+    fn dep_graph_dot_has_nodes_and_edges() {
+        let dir = TempDir::new().unwrap();
+        deps_fixture(&dir);
+
+        let graph = dep_graph(dir.path(), "v1").unwrap();
+        let dot = graph.to_dot();
+        assert!(dot.starts_with("digraph \"v1\" {"), "{dot}");
+        assert!(dot.contains("\"db\" [label=\"db (draft)\"];"), "{dot}");
+        assert!(
+            dot.contains("\"infra\" [label=\"infra (draft)\", style=dashed];"),
+            "{dot}"
+        );
+        assert!(dot.contains("\"api\" -> \"db\";"), "{dot}");
+        assert!(dot.contains("\"api\" -> \"infra\";"), "{dot}");
+        assert!(dot.contains("\"web-ui\" -> \"api\";"), "{dot}");
+        assert_eq!(graph.edges.len(), 3);
+
+        let mermaid = graph.to_mermaid();
+        assert!(mermaid.contains("f_web_ui --> f_api"), "{mermaid}");
+        assert!(
+            mermaid.contains("f_web_ui[\"web-ui (draft)\"]"),
+            "{mermaid}"
+        );
+    }
+
+    #[test]
+    fn dep_graph_mermaid_ids_never_clash_with_keywords() {
+        let node = |slug: &str| DepNode {
+            slug: slug.into(),
+            title: slug.into(),
+            phase: Some(Phase::Draft),
+            in_milestone: true,
+        };
+        let graph = DepGraph {
+            milestone: "v1".into(),
+            nodes: vec![node("end"), node("graph")],
+            edges: vec![DepEdge {
+                from: "end".into(),
+                to: "graph".into(),
+            }],
+        };
+        let mermaid = graph.to_mermaid();
+        assert!(mermaid.contains("  f_end[\"end (draft)\"]\n"), "{mermaid}");
+        assert!(mermaid.contains("  f_end --> f_graph\n"), "{mermaid}");
+    }
+
+    #[test]
+    fn dep_graph_reports_a_cycle_instead_of_emitting_it() {
+        let dir = TempDir::new().unwrap();
+        deps_fixture(&dir);
+        // Written straight to disk, bypassing the CLI's cycle check.
+        let mut db = Feature::load(dir.path(), "db").unwrap();
+        db.dependencies = vec!["web-ui".into()];
+        db.save(dir.path()).unwrap();
+
+        match dep_graph(dir.path(), "v1") {
+            Err(SdlcError::DependencyCycle(path)) => {
+                assert!(path.contains("'db'") && path.contains("'web-ui'"), "{path}")
+            }
+            other => panic!("expected a cycle error, got {other:?}"),
+        }
+    }
 }
@@ -130,12 +130,7 @@ pub fn select_parallel_work_from_root(root: &Path) -> crate::Result<Vec<Parallel
 
     let mut next_actions: HashMap<String, ActionType> = HashMap::new();
     for f in &features {
-        let ctx = EvalContext {
-            feature: f,
-            state: &state,
-            config: &config,
-            root,
-        };
+        let ctx = EvalContext::new(f, &state, &config, root);
         let classification = classifier.classify(&ctx);
         next_actions.insert(f.slug.clone(), classification.action);
     }
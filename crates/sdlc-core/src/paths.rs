@@ -24,12 +24,15 @@ pub const TOOLS_MANIFEST: &str = ".sdlc/tools/tools.md";
 pub const TOOLS_SHARED_DIR: &str = ".sdlc/tools/_shared";
 pub const TOOL_INTERACTIONS_DIR: &str = ".sdlc/tool-interactions";
 pub const AMA_THREADS_DIR: &str = ".sdlc/tool-interactions/ama/threads";
+pub const TEMPLATES_DIR: &str = ".sdlc/templates";
+pub const PROMPTS_DIR: &str = ".sdlc/prompts";
 
 pub const SECRETS_DIR: &str = ".sdlc/secrets";
 pub const SECRETS_KEYS_FILE: &str = ".sdlc/secrets/keys.yaml";
 pub const SECRETS_ENVS_DIR: &str = ".sdlc/secrets/envs";
 
 pub const ADVISORY_FILE: &str = ".sdlc/advisory.yaml";
+pub const AUDIT_LOG_FILE: &str = ".sdlc/audit.log";
 pub const BACKLOG_FILE: &str = ".sdlc/backlog.yaml";
 pub const ESCALATIONS_FILE: &str = ".sdlc/escalations.yaml";
 pub const FEEDBACK_FILE: &str = ".sdlc/feedback.yaml";
@@ -37,6 +40,7 @@ pub const FEEDBACK_THREADS_DIR: &str = ".sdlc/feedback-threads";
 
 pub const CONFIG_FILE: &str = ".sdlc/config.yaml";
 pub const STATE_FILE: &str = ".sdlc/state.yaml";
+pub const STATE_LOCK_FILE: &str = ".sdlc/state.lock";
 pub const GUIDANCE_MD: &str = ".sdlc/guidance.md";
 
 pub const AI_LOOKUP_DIR: &str = ".ai";
@@ -52,10 +56,22 @@ pub const AGENTS_DIR: &str = ".agents";
 pub const AGENTS_SKILLS_DIR: &str = ".agents/skills";
 
 pub const VISION_MD: &str = "VISION.md";
+pub const CHANGELOG_MD: &str = "CHANGELOG.md";
 pub const ARCHITECTURE_MD: &str = "ARCHITECTURE.md";
 pub const AGENTS_MD: &str = "AGENTS.md";
 pub const MANIFEST_FILE: &str = "manifest.yaml";
 
+/// Directories `sdlc init` creates; `sdlc doctor --fix` recreates any that go missing.
+pub const SCAFFOLD_DIRS: &[&str] = &[
+    SDLC_DIR,
+    FEATURES_DIR,
+    PATTERNS_DIR,
+    AUDITS_DIR,
+    BRANCHES_DIR,
+    ARCHIVES_DIR,
+    ROADMAP_DIR,
+];
+
 // ---------------------------------------------------------------------------
 // Path helpers
 // ---------------------------------------------------------------------------
@@ -84,6 +100,10 @@ pub fn milestone_uat_results_path(root: &Path, slug: &str) -> PathBuf {
     milestone_dir(root, slug).join("uat_results.md")
 }
 
+pub fn milestone_wave_run_path(root: &Path, slug: &str) -> PathBuf {
+    milestone_dir(root, slug).join("wave_run.yaml")
+}
+
 pub fn milestone_uat_runs_dir(root: &Path, slug: &str) -> PathBuf {
     milestone_dir(root, slug).join("uat-runs")
 }
@@ -150,6 +170,13 @@ pub fn ponder_session_path(root: &Path, slug: &str, n: u32) -> PathBuf {
     ponder_sessions_dir(root, slug).join(format!("session-{n:03}.md"))
 }
 
+/// Dialogue of a ponder chat still in progress, flushed as the agent runs so
+/// an interrupted chat can still be logged. Not named `session-NNN.md`, so it
+/// is never listed or counted as a session.
+pub fn ponder_partial_session_path(root: &Path, slug: &str) -> PathBuf {
+    ponder_sessions_dir(root, slug).join(".partial.md")
+}
+
 pub fn artifact_path(root: &Path, slug: &str, filename: &str) -> PathBuf {
     feature_dir(root, slug).join(filename)
 }
@@ -162,6 +189,10 @@ pub fn state_path(root: &Path) -> PathBuf {
     root.join(STATE_FILE)
 }
 
+pub fn state_lock_path(root: &Path) -> PathBuf {
+    root.join(STATE_LOCK_FILE)
+}
+
 pub fn guidance_md_path(root: &Path) -> PathBuf {
     root.join(GUIDANCE_MD)
 }
@@ -246,6 +277,18 @@ pub fn escalations_path(root: &Path) -> PathBuf {
     root.join(ESCALATIONS_FILE)
 }
 
+pub fn audit_log_path(root: &Path) -> PathBuf {
+    root.join(AUDIT_LOG_FILE)
+}
+
+pub fn feature_template_path(root: &Path, name: &str) -> PathBuf {
+    root.join(TEMPLATES_DIR).join(format!("{name}.yaml"))
+}
+
+pub fn prompt_template_path(root: &Path, name: &str) -> PathBuf {
+    root.join(PROMPTS_DIR).join(format!("{name}.md"))
+}
+
 pub fn feedback_path(root: &Path) -> PathBuf {
     root.join(FEEDBACK_FILE)
 }
@@ -0,0 +1,325 @@
+//! Deterministic plan distribution.
+//!
+//! A [`Plan`] is a structured milestones → features → tasks tree, usually the
+//! output of planning done elsewhere. [`apply`] writes it into `.sdlc/`
+//! without an agent: missing entities are created, changed titles and
+//! descriptions are updated, and everything already in place is skipped, so
+//! applying the same plan twice changes nothing the second time.
+//!
+//! Slugs omitted from the plan are derived from titles with
+//! [`crate::slug::derive`]; tasks are matched by normalized title
+//! ([`crate::task::normalize_title`]).
+
+use crate::artifact::ChecklistItem;
+use crate::error::Result;
+use crate::feature::Feature;
+use crate::milestone::Milestone;
+use crate::state::State;
+use crate::{paths, slug, task, SdlcError};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Plan {
+    #[serde(default)]
+    pub milestones: Vec<PlanMilestone>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlanMilestone {
+    /// Derived from `title` when omitted.
+    #[serde(default)]
+    pub slug: Option<String>,
+    pub title: String,
+    #[serde(default)]
+    pub vision: Option<String>,
+    #[serde(default)]
+    pub features: Vec<PlanFeature>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlanFeature {
+    /// Derived from `title` when omitted.
+    #[serde(default)]
+    pub slug: Option<String>,
+    pub title: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Task titles, in execution order.
+    #[serde(default)]
+    pub tasks: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PlanEntityKind {
+    Milestone,
+    Feature,
+    Task,
+}
+
+/// One entity touched by [`apply`]. Tasks are identified as
+/// `<feature-slug>/<task-id>`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PlanEntity {
+    pub kind: PlanEntityKind,
+    pub id: String,
+}
+
+/// What [`apply`] did with every entity in the plan.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct PlanDiff {
+    pub created: Vec<PlanEntity>,
+    pub updated: Vec<PlanEntity>,
+    pub skipped: Vec<PlanEntity>,
+}
+
+impl PlanDiff {
+    /// True when applying the plan changed nothing on disk.
+    pub fn is_noop(&self) -> bool {
+        self.created.is_empty() && self.updated.is_empty()
+    }
+
+    fn record(&mut self, outcome: Outcome, kind: PlanEntityKind, id: impl Into<String>) {
+        let entity = PlanEntity {
+            kind,
+            id: id.into(),
+        };
+        match outcome {
+            Outcome::Created => self.created.push(entity),
+            Outcome::Updated => self.updated.push(entity),
+            Outcome::Skipped => self.skipped.push(entity),
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Outcome {
+    Created,
+    Updated,
+    Skipped,
+}
+
+fn resolve_slug(given: Option<&str>, title: &str) -> Result<String> {
+    let slug = match given {
+        Some(s) => s.to_string(),
+        None => slug::derive(title),
+    };
+    paths::validate_slug(&slug)?;
+    Ok(slug)
+}
+
+/// Overwrite `field` with `value` when the plan sets it to something new.
+fn set_if_changed(field: &mut Option<String>, value: Option<&String>) -> bool {
+    match value {
+        Some(v) if field.as_ref() != Some(v) => {
+            *field = Some(v.clone());
+            true
+        }
+        _ => false,
+    }
+}
+
+/// Apply `plan` to the project at `root`. Every slug is validated before
+/// anything is written, so a malformed plan leaves the project untouched.
+pub fn apply(root: &Path, plan: &Plan) -> Result<PlanDiff> {
+    for m in &plan.milestones {
+        resolve_slug(m.slug.as_deref(), &m.title)?;
+        for f in &m.features {
+            resolve_slug(f.slug.as_deref(), &f.title)?;
+        }
+    }
+
+    let mut diff = PlanDiff::default();
+    for planned in &plan.milestones {
+        let ms_slug = resolve_slug(planned.slug.as_deref(), &planned.title)?;
+        let (mut milestone, mut outcome) = match Milestone::load(root, &ms_slug) {
+            Ok(m) => (m, Outcome::Skipped),
+            Err(SdlcError::MilestoneNotFound(_)) => {
+                let m = Milestone::create(root, ms_slug.clone(), planned.title.clone())?;
+                State::update(root, |state| state.add_milestone(&m.slug))?;
+                (m, Outcome::Created)
+            }
+            Err(e) => return Err(e),
+        };
+        let mut changed = false;
+        if milestone.title != planned.title {
+            milestone.title = planned.title.clone();
+            changed = true;
+        }
+        changed |= set_if_changed(&mut milestone.vision, planned.vision.as_ref());
+
+        for planned_feature in &planned.features {
+            let slug = apply_feature(root, planned_feature, &mut diff)?;
+            changed |= milestone.add_feature(&slug);
+        }
+
+        if changed {
+            milestone.save(root)?;
+            if matches!(outcome, Outcome::Skipped) {
+                outcome = Outcome::Updated;
+            }
+        }
+        diff.record(outcome, PlanEntityKind::Milestone, ms_slug);
+    }
+    Ok(diff)
+}
+
+/// Create or update one feature and its tasks; returns the feature slug.
+fn apply_feature(root: &Path, planned: &PlanFeature, diff: &mut PlanDiff) -> Result<String> {
+    let slug = resolve_slug(planned.slug.as_deref(), &planned.title)?;
+    let (mut feature, mut outcome) = match Feature::load(root, &slug) {
+        Ok(f) => (f, Outcome::Skipped),
+        Err(SdlcError::FeatureNotFound(_)) => {
+            let f = Feature::create_with_description(
+                root,
+                slug.clone(),
+                planned.title.clone(),
+                planned.description.clone(),
+            )?;
+            State::update(root, |state| state.add_active_feature(&f.slug))?;
+            (f, Outcome::Created)
+        }
+        Err(e) => return Err(e),
+    };
+
+    let mut changed = false;
+    if feature.title != planned.title {
+        feature.title = planned.title.clone();
+        changed = true;
+    }
+    changed |= set_if_changed(&mut feature.description, planned.description.as_ref());
+    if changed && matches!(outcome, Outcome::Skipped) {
+        outcome = Outcome::Updated;
+    }
+
+    let items: Vec<ChecklistItem> = planned
+        .tasks
+        .iter()
+        .map(|title| ChecklistItem {
+            title: title.clone(),
+            checked: false,
+        })
+        .collect();
+    let created = task::sync_from_checklist(&mut feature.tasks, &items);
+    if changed || !created.is_empty() {
+        feature.save(root)?;
+    }
+
+    diff.record(outcome, PlanEntityKind::Feature, slug.clone());
+    for title in &planned.tasks {
+        let key = task::normalize_title(title);
+        let Some(t) = feature
+            .tasks
+            .iter()
+            .find(|t| task::normalize_title(&t.title) == key)
+        else {
+            continue;
+        };
+        let task_outcome = if created.contains(&t.id) {
+            Outcome::Created
+        } else {
+            Outcome::Skipped
+        };
+        diff.record(
+            task_outcome,
+            PlanEntityKind::Task,
+            format!("{slug}/{}", t.id),
+        );
+    }
+    Ok(slug)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn plan() -> Plan {
+        serde_json::from_value(serde_json::json!({
+            "milestones": [{
+                "title": "Billing v1",
+                "vision": "Customers can pay",
+                "features": [
+                    { "title": "Invoices", "tasks": ["Model invoices", "Render PDF"] },
+                    { "slug": "stripe", "title": "Stripe checkout" }
+                ]
+            }]
+        }))
+        .unwrap()
+    }
+
+    fn init() -> TempDir {
+        let dir = TempDir::new().unwrap();
+        crate::io::ensure_dir(&dir.path().join(".sdlc")).unwrap();
+        State::new("test").save(dir.path()).unwrap();
+        dir
+    }
+
+    fn ids(entities: &[PlanEntity]) -> Vec<&str> {
+        entities.iter().map(|e| e.id.as_str()).collect()
+    }
+
+    #[test]
+    fn apply_twice_creates_once_then_skips() {
+        let dir = init();
+
+        let first = apply(dir.path(), &plan()).unwrap();
+        assert_eq!(
+            ids(&first.created),
+            [
+                "invoices",
+                "invoices/T1",
+                "invoices/T2",
+                "stripe",
+                "billing-v1"
+            ]
+        );
+        assert!(first.updated.is_empty() && first.skipped.is_empty());
+        let m = Milestone::load(dir.path(), "billing-v1").unwrap();
+        assert_eq!(m.features, ["invoices", "stripe"]);
+        assert_eq!(m.vision.as_deref(), Some("Customers can pay"));
+
+        let second = apply(dir.path(), &plan()).unwrap();
+        assert!(second.is_noop(), "{second:?}");
+        assert_eq!(second.skipped.len(), 5);
+        assert_eq!(
+            Feature::load(dir.path(), "invoices").unwrap().tasks.len(),
+            2
+        );
+    }
+
+    #[test]
+    fn apply_updates_changed_fields_and_adds_new_tasks() {
+        let dir = init();
+        apply(dir.path(), &plan()).unwrap();
+
+        let mut revised = plan();
+        revised.milestones[0].features[1].title = "Stripe Checkout".into();
+        revised.milestones[0].features[0]
+            .tasks
+            .push("Email invoices".into());
+        let diff = apply(dir.path(), &revised).unwrap();
+
+        assert_eq!(ids(&diff.created), ["invoices/T3"]);
+        assert_eq!(ids(&diff.updated), ["stripe"]);
+        assert_eq!(
+            Feature::load(dir.path(), "stripe").unwrap().title,
+            "Stripe Checkout"
+        );
+    }
+
+    #[test]
+    fn invalid_slug_rejects_the_whole_plan() {
+        let dir = init();
+        let mut bad = plan();
+        bad.milestones[0].features.push(PlanFeature {
+            slug: None,
+            title: "!!!".into(),
+            description: None,
+            tasks: vec![],
+        });
+        assert!(apply(dir.path(), &bad).is_err());
+        assert!(Milestone::load(dir.path(), "billing-v1").is_err());
+    }
+}
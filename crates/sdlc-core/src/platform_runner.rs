@@ -0,0 +1,287 @@
+//! Execution of the platform commands declared in `config.yaml`
+//! (`platform.commands`).
+//!
+//! A platform script runs with the project root as its working directory.
+//! Its stdout and stderr are handed to the caller line by line as they are
+//! produced, so long-running commands (`deploy`, `logs`) show progress, and
+//! the whole run is summarized as a [`PlatformRunResult`], which keeps only
+//! the last [`MAX_CAPTURED_BYTES`] of each stream.
+//!
+//! Scripts are started through the interpreter named on their `#!` line, or
+//! `sh` without one, so they need not be executable.
+
+use std::collections::VecDeque;
+use std::io::{BufRead, BufReader, Read};
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::sync::mpsc;
+use std::time::Instant;
+
+use serde::Serialize;
+
+use crate::error::Result;
+
+/// How much of each output stream a [`PlatformRunResult`] keeps. Older lines
+/// are dropped first; every line still reaches the `on_line` callback.
+pub const MAX_CAPTURED_BYTES: usize = 1024 * 1024;
+
+// ---------------------------------------------------------------------------
+// PlatformRunResult
+// ---------------------------------------------------------------------------
+
+/// The outcome of one platform command run.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PlatformRunResult {
+    /// The command as invoked, e.g. `deploy` or `dev start`.
+    pub command: String,
+    pub args: Vec<String>,
+    /// `None` when the script was killed by a signal.
+    pub exit_code: Option<i32>,
+    pub stdout: String,
+    pub stderr: String,
+    /// Whether earlier output was dropped from `stdout` or `stderr` to stay
+    /// within [`MAX_CAPTURED_BYTES`].
+    pub output_truncated: bool,
+    pub duration_ms: u64,
+}
+
+impl PlatformRunResult {
+    pub fn success(&self) -> bool {
+        self.exit_code == Some(0)
+    }
+}
+
+/// Which output stream a line came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputStream {
+    Stdout,
+    Stderr,
+}
+
+// ---------------------------------------------------------------------------
+// Runner
+// ---------------------------------------------------------------------------
+
+/// Run `script` with `args` from `root`, calling `on_line` for every line of
+/// output as it arrives (without its trailing newline).
+///
+/// A script that cannot be started is an error; one that starts and fails is
+/// not — check [`PlatformRunResult::success`].
+pub fn run_platform_script(
+    root: &Path,
+    command: &str,
+    script: &Path,
+    args: &[String],
+    mut on_line: impl FnMut(OutputStream, &str),
+) -> Result<PlatformRunResult> {
+    let started = Instant::now();
+    let (program, interpreter_args) = interpreter(script)?;
+    let mut child = Command::new(program)
+        .args(interpreter_args)
+        .arg(script)
+        .args(args)
+        .current_dir(root)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+
+    // One reader thread per pipe, so neither can fill up and stall the
+    // script while the other is being read.
+    let (tx, rx) = mpsc::channel();
+    let readers = [
+        child
+            .stdout
+            .take()
+            .map(|pipe| forward_lines(pipe, OutputStream::Stdout, tx.clone())),
+        child
+            .stderr
+            .take()
+            .map(|pipe| forward_lines(pipe, OutputStream::Stderr, tx.clone())),
+    ];
+    drop(tx);
+
+    let (mut stdout, mut stderr) = (TailBuffer::default(), TailBuffer::default());
+    for (stream, line) in rx {
+        on_line(stream, &line);
+        match stream {
+            OutputStream::Stdout => stdout.push(line),
+            OutputStream::Stderr => stderr.push(line),
+        }
+    }
+    for reader in readers.into_iter().flatten() {
+        let _ = reader.join();
+    }
+    let status = child.wait()?;
+
+    Ok(PlatformRunResult {
+        command: command.to_string(),
+        args: args.to_vec(),
+        exit_code: status.code(),
+        output_truncated: stdout.truncated || stderr.truncated,
+        stdout: stdout.into_string(),
+        stderr: stderr.into_string(),
+        duration_ms: u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX),
+    })
+}
+
+/// The program and leading arguments that run `script`: its `#!` line split
+/// on whitespace, or `sh`.
+fn interpreter(script: &Path) -> Result<(String, Vec<String>)> {
+    let mut first_line = String::new();
+    BufReader::new(std::fs::File::open(script)?).read_line(&mut first_line)?;
+    let mut words = first_line
+        .strip_prefix("#!")
+        .unwrap_or_default()
+        .split_whitespace()
+        .map(str::to_string);
+    Ok(match words.next() {
+        Some(program) => (program, words.collect()),
+        None => ("sh".to_string(), Vec::new()),
+    })
+}
+
+/// The most recent lines of one stream, at most [`MAX_CAPTURED_BYTES`].
+#[derive(Default)]
+struct TailBuffer {
+    lines: VecDeque<String>,
+    bytes: usize,
+    truncated: bool,
+}
+
+impl TailBuffer {
+    fn push(&mut self, line: String) {
+        self.bytes += line.len() + 1;
+        self.lines.push_back(line);
+        while self.bytes > MAX_CAPTURED_BYTES {
+            let Some(dropped) = self.lines.pop_front() else {
+                break;
+            };
+            self.bytes -= dropped.len() + 1;
+            self.truncated = true;
+        }
+    }
+
+    fn into_string(self) -> String {
+        let mut out = String::with_capacity(self.bytes);
+        for line in self.lines {
+            out.push_str(&line);
+            out.push('\n');
+        }
+        out
+    }
+}
+
+fn forward_lines(
+    pipe: impl Read + Send + 'static,
+    stream: OutputStream,
+    tx: mpsc::Sender<(OutputStream, String)>,
+) -> std::thread::JoinHandle<()> {
+    std::thread::spawn(move || {
+        for line in BufReader::new(pipe).lines() {
+            let Ok(line) = line else { break };
+            if tx.send((stream, line)).is_err() {
+                break;
+            }
+        }
+    })
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::os::unix::fs::PermissionsExt;
+
+    #[test]
+    fn fixture_script_output_is_streamed_and_captured() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let script = dir.path().join("deploy.sh");
+        std::fs::write(
+            &script,
+            "#!/bin/sh\necho \"deploying $1\"\necho 'warming cache' >&2\necho done\nexit 3\n",
+        )
+        .unwrap();
+        std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        let mut lines = Vec::new();
+        let result = run_platform_script(
+            dir.path(),
+            "deploy",
+            &script,
+            &["staging".to_string()],
+            |stream, line| lines.push((stream, line.to_string())),
+        )
+        .unwrap();
+
+        assert_eq!(result.command, "deploy");
+        assert_eq!(result.args, ["staging"]);
+        assert_eq!(result.exit_code, Some(3));
+        assert!(!result.success());
+        assert_eq!(result.stdout, "deploying staging\ndone\n");
+        assert_eq!(result.stderr, "warming cache\n");
+
+        let stdout_lines: Vec<&str> = lines
+            .iter()
+            .filter(|(s, _)| *s == OutputStream::Stdout)
+            .map(|(_, l)| l.as_str())
+            .collect();
+        assert_eq!(stdout_lines, ["deploying staging", "done"]);
+        assert!(lines.contains(&(OutputStream::Stderr, "warming cache".to_string())));
+    }
+
+    #[test]
+    fn scripts_without_the_exec_bit_run_through_their_interpreter() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let plain = dir.path().join("plain.sh");
+        std::fs::write(&plain, "echo \"plain $1\"\n").unwrap();
+        let strict = dir.path().join("strict.sh");
+        std::fs::write(
+            &strict,
+            "#!/bin/sh -e\necho strict\nfalse\necho unreachable\n",
+        )
+        .unwrap();
+        for script in [&plain, &strict] {
+            std::fs::set_permissions(script, std::fs::Permissions::from_mode(0o644)).unwrap();
+        }
+
+        let plain = run_platform_script(dir.path(), "p", &plain, &["x".into()], |_, _| {}).unwrap();
+        assert!(plain.success());
+        assert_eq!(plain.stdout, "plain x\n");
+
+        let strict = run_platform_script(dir.path(), "s", &strict, &[], |_, _| {}).unwrap();
+        assert_eq!(strict.exit_code, Some(1));
+        assert_eq!(strict.stdout, "strict\n");
+    }
+
+    #[test]
+    fn captured_output_keeps_the_most_recent_lines() {
+        let mut buf = TailBuffer::default();
+        let line = "x".repeat(1023);
+        for _ in 0..MAX_CAPTURED_BYTES / 1024 {
+            buf.push(line.clone());
+        }
+        assert!(!buf.truncated);
+        buf.push("last".to_string());
+        assert!(buf.truncated);
+        let captured = buf.into_string();
+        assert!(captured.len() <= MAX_CAPTURED_BYTES);
+        assert!(captured.ends_with("x\nlast\n"));
+    }
+
+    #[test]
+    fn missing_script_is_an_error() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let err = run_platform_script(
+            dir.path(),
+            "deploy",
+            &dir.path().join("nope.sh"),
+            &[],
+            |_, _| {},
+        );
+        assert!(err.is_err());
+    }
+}
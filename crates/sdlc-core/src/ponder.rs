@@ -361,6 +361,68 @@ pub fn next_session_number(root: &Path, slug: &str) -> Result<u32> {
     workspace::next_session_number(&ponder_dir(root, slug))
 }
 
+// ---------------------------------------------------------------------------
+// Partial sessions (chat dialogue not yet logged)
+// ---------------------------------------------------------------------------
+
+/// Replace the in-progress dialogue of `slug`'s running chat with `dialogue`.
+pub fn write_partial_session(root: &Path, slug: &str, dialogue: &str) -> Result<()> {
+    ensure_ponder_exists(root, slug)?;
+    let path = paths::ponder_partial_session_path(root, slug);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    crate::io::atomic_write(&path, dialogue.as_bytes())
+}
+
+/// Drop the in-progress dialogue, e.g. once the agent logged the session
+/// itself. A missing file is not an error.
+pub fn discard_partial_session(root: &Path, slug: &str) -> Result<()> {
+    match std::fs::remove_file(paths::ponder_partial_session_path(root, slug)) {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+        _ => Ok(()),
+    }
+}
+
+/// Log the in-progress dialogue of an interrupted chat as the next session
+/// and remove it. Returns the session number, or `None` when there was no
+/// dialogue to log.
+pub fn finalize_partial_session(root: &Path, slug: &str) -> Result<Option<u32>> {
+    let path = paths::ponder_partial_session_path(root, slug);
+    let dialogue = match std::fs::read_to_string(&path) {
+        Ok(d) => d,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e.into()),
+    };
+    if dialogue.trim().is_empty() {
+        discard_partial_session(root, slug)?;
+        return Ok(None);
+    }
+    let content = format!(
+        "---\nsession: {n}\ntimestamp: {ts}\n---\n\n\
+         _This session was interrupted before the agent logged it; \
+         recovered from the live dialogue._\n\n{dialogue}",
+        n = next_session_number(root, slug)?,
+        ts = Utc::now().to_rfc3339(),
+    );
+    let n = log_session(root, slug, &content)?;
+    discard_partial_session(root, slug)?;
+    Ok(Some(n))
+}
+
+/// Finalize the partial session of every ponder entry — run once no chat
+/// can still be writing one (on shutdown, or on the next start after a
+/// crash). Returns `(slug, session number)` for each session logged.
+pub fn recover_partial_sessions(root: &Path) -> Result<Vec<(String, u32)>> {
+    let mut recovered = Vec::new();
+    for entry in PonderEntry::list(root)? {
+        if let Some(n) = finalize_partial_session(root, &entry.slug)? {
+            recovered.push((entry.slug, n));
+        }
+    }
+    Ok(recovered)
+}
+
 // ---------------------------------------------------------------------------
 // Merge
 // ---------------------------------------------------------------------------
@@ -747,6 +809,31 @@ mod tests {
         assert_eq!(loaded.sessions, 5);
     }
 
+    #[test]
+    fn interrupted_chat_dialogue_is_recovered_as_a_session() {
+        let dir = TempDir::new().unwrap();
+        setup(&dir);
+        PonderEntry::create(dir.path(), "idea", "Idea").unwrap();
+        PonderEntry::create(dir.path(), "quiet", "Quiet").unwrap();
+
+        write_partial_session(dir.path(), "idea", "**Ada · Owner**\nFirst thought").unwrap();
+        write_partial_session(dir.path(), "idea", "**Ada · Owner**\nFirst thought\n\nMore")
+            .unwrap();
+        // A partial is never listed as a session.
+        assert!(list_sessions(dir.path(), "idea").unwrap().is_empty());
+
+        let recovered = recover_partial_sessions(dir.path()).unwrap();
+        assert_eq!(recovered, vec![("idea".to_string(), 1)]);
+        let content = read_session(dir.path(), "idea", 1).unwrap();
+        assert!(content.ends_with("First thought\n\nMore"), "{content}");
+        assert_eq!(list_sessions(dir.path(), "idea").unwrap()[0].session, 1);
+        assert_eq!(PonderEntry::load(dir.path(), "idea").unwrap().sessions, 1);
+        assert!(!paths::ponder_partial_session_path(dir.path(), "idea").exists());
+
+        // Nothing left to recover the second time.
+        assert!(recover_partial_sessions(dir.path()).unwrap().is_empty());
+    }
+
     #[test]
     fn invalid_ponder_status_error() {
         let result: std::result::Result<PonderStatus, _> = "invalid".parse();
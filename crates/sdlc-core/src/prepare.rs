@@ -1,6 +1,7 @@
 use crate::classifier::{Classifier, EvalContext};
 use crate::config::Config;
 use crate::error::Result;
+use crate::escalation::{self, EscalationItem};
 use crate::feature::Feature;
 use crate::milestone::Milestone;
 use crate::ponder::{PonderEntry, PonderStatus};
@@ -136,6 +137,34 @@ pub struct PrepareResult {
     pub current_wave: Option<usize>,
 }
 
+// ---------------------------------------------------------------------------
+// ProjectSummary
+// ---------------------------------------------------------------------------
+
+/// A feature the classifier says can be worked on right now.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReadyFeature {
+    pub slug: String,
+    pub title: String,
+    pub phase: Phase,
+    pub action: ActionType,
+    pub message: String,
+    pub next_command: String,
+}
+
+/// Machine-readable snapshot of where the project stands and what is stuck.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectSummary {
+    pub project_phase: ProjectPhase,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub active_milestone: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub active_milestone_title: Option<String>,
+    pub ready: Vec<ReadyFeature>,
+    pub blocked: Vec<BlockedFeature>,
+    pub pending_escalations: Vec<EscalationItem>,
+}
+
 // ---------------------------------------------------------------------------
 // project_phase()
 // ---------------------------------------------------------------------------
@@ -290,12 +319,7 @@ pub fn prepare(root: &Path, milestone_slug: Option<&str>) -> Result<PrepareResul
         }
 
         // Classify
-        let ctx = EvalContext {
-            feature: &feature,
-            state: &state,
-            config: &config,
-            root,
-        };
+        let ctx = EvalContext::new(&feature, &state, &config, root);
         let classification = classifier.classify(&ctx);
 
         features.insert(
@@ -603,6 +627,95 @@ pub fn prepare(root: &Path, milestone_slug: Option<&str>) -> Result<PrepareResul
     })
 }
 
+// ---------------------------------------------------------------------------
+// project_summary()
+// ---------------------------------------------------------------------------
+
+/// Assemble the project phase, active milestone, ready and blocked features,
+/// and open escalations in one pass. Read-only — no side effects.
+///
+/// Ready features are those [`crate::query::ready_report`] reports as ready.
+/// A feature is blocked if it is tracked in `state.blocked`, carries its own
+/// blockers, or is waiting on unreleased dependencies; blocked features are
+/// never reported as ready.
+pub fn project_summary(root: &Path) -> Result<ProjectSummary> {
+    let project_phase = project_phase(root)?;
+    let state = State::load(root)?;
+    let features = Feature::list(root)?;
+    let titles: HashMap<&str, &str> = features
+        .iter()
+        .map(|f| (f.slug.as_str(), f.title.as_str()))
+        .collect();
+    let title = |slug: &str| titles.get(slug).copied().unwrap_or_default().to_string();
+
+    let active_milestone = project_phase.milestone_slug().map(str::to_string);
+    let active_milestone_title = active_milestone
+        .as_deref()
+        .and_then(|slug| Milestone::load(root, slug).ok())
+        .map(|m| m.title);
+
+    let mut blocked = Vec::new();
+    for feature in features.iter().filter(|f| !f.archived) {
+        let tracked = state.blocked.iter().find(|b| b.feature == feature.slug);
+        let reason = match tracked {
+            Some(item) => item.reason.clone(),
+            None if feature.is_blocked() => feature.blockers.join("; "),
+            None => continue,
+        };
+        blocked.push(BlockedFeature {
+            slug: feature.slug.clone(),
+            title: feature.title.clone(),
+            reason,
+        });
+    }
+
+    let report = crate::query::ready_report(root, None)?;
+    for waiting in report.blocked_by_dependency {
+        if blocked.iter().any(|b| b.slug == waiting.slug) {
+            continue;
+        }
+        let deps: Vec<String> = waiting
+            .blocked_by
+            .iter()
+            .map(|d| match d.phase {
+                Some(phase) => format!("{} [{phase}]", d.slug),
+                None => format!("{} [missing]", d.slug),
+            })
+            .collect();
+        blocked.push(BlockedFeature {
+            title: title(&waiting.slug),
+            slug: waiting.slug,
+            reason: format!("waiting on unreleased dependencies: {}", deps.join(", ")),
+        });
+    }
+    let blocked_set: HashSet<&str> = blocked.iter().map(|b| b.slug.as_str()).collect();
+
+    let ready = report
+        .ready
+        .into_iter()
+        .filter(|f| !blocked_set.contains(f.slug.as_str()))
+        .map(|f| ReadyFeature {
+            title: title(&f.slug),
+            slug: f.slug,
+            phase: f.phase,
+            action: f.action,
+            message: f.message,
+            next_command: f.next_command,
+        })
+        .collect();
+
+    let pending_escalations = escalation::list(root, Some("open"))?;
+
+    Ok(ProjectSummary {
+        project_phase,
+        active_milestone,
+        active_milestone_title,
+        ready,
+        blocked,
+        pending_escalations,
+    })
+}
+
 // ---------------------------------------------------------------------------
 // write_wave_plan
 // ---------------------------------------------------------------------------
@@ -1237,4 +1350,65 @@ mod tests {
         assert_eq!(progress.blocked, 1);
         assert_eq!(progress.pending, 1);
     }
+
+    #[test]
+    fn project_summary_reports_ready_blocked_and_escalations() {
+        let dir = TempDir::new().unwrap();
+        setup(&dir);
+        add_feature(&dir, "auth");
+        add_feature(&dir, "billing");
+        add_milestone(&dir, "v1", &["auth", "billing"]);
+        crate::escalation::create(
+            dir.path(),
+            crate::escalation::EscalationKind::Question,
+            "Which provider?",
+            "Needed before billing can proceed",
+            Some("billing"),
+        )
+        .unwrap();
+
+        let mut state = State::load(dir.path()).unwrap();
+        state.set_blocked("billing", "waiting on vendor contract");
+        state.save(dir.path()).unwrap();
+
+        let summary = project_summary(dir.path()).unwrap();
+        assert_eq!(
+            summary.project_phase,
+            ProjectPhase::Planning {
+                milestone: "v1".to_string()
+            }
+        );
+        assert_eq!(summary.active_milestone.as_deref(), Some("v1"));
+        assert_eq!(summary.ready.len(), 1);
+        assert_eq!(summary.ready[0].slug, "auth");
+        assert_eq!(summary.ready[0].action, ActionType::CreateSpec);
+        assert_eq!(summary.blocked.len(), 1);
+        assert_eq!(summary.blocked[0].slug, "billing");
+        assert_eq!(summary.blocked[0].reason, "waiting on vendor contract");
+        assert_eq!(summary.pending_escalations.len(), 1);
+    }
+
+    #[test]
+    fn project_summary_holds_back_features_with_unreleased_dependencies() {
+        let dir = TempDir::new().unwrap();
+        setup(&dir);
+        add_feature(&dir, "api");
+        add_feature(&dir, "ui");
+        let mut ui = Feature::load(dir.path(), "ui").unwrap();
+        ui.dependencies = vec!["api".to_string()];
+        ui.save(dir.path()).unwrap();
+
+        let summary = project_summary(dir.path()).unwrap();
+        let ready: Vec<&str> = summary.ready.iter().map(|f| f.slug.as_str()).collect();
+        assert_eq!(ready, vec!["api"]);
+        assert_eq!(summary.blocked.len(), 1);
+        assert_eq!(summary.blocked[0].slug, "ui");
+        assert_eq!(
+            summary.blocked[0].reason,
+            "waiting on unreleased dependencies: api [draft]"
+        );
+        let report = crate::query::ready_report(dir.path(), None).unwrap();
+        let from_query: Vec<&str> = report.ready.iter().map(|f| f.slug.as_str()).collect();
+        assert_eq!(from_query, ready);
+    }
 }
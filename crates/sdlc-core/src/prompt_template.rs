@@ -0,0 +1,308 @@
+//! Prompt templates — the prompt scaffolding an agent run starts from, with
+//! `{{placeholder}}` slots filled from the feature being driven.
+//!
+//! Layout:
+//!   .sdlc/prompts/<name>.md   — project-local template (overrides a
+//!                               built-in of the same name)
+//!
+//! Built-ins: `drive` (any action), `spec`, `design`, `review`.
+//! [`template_for`] picks the template for a directive's action. The server's
+//! feature runs, which start before any directive is read, use `run`, or
+//! `run-with-context` when the user supplied a problem statement.
+
+use crate::classifier::Classification;
+use crate::error::{Result, SdlcError};
+use crate::paths;
+use crate::types::ActionType;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+// ---------------------------------------------------------------------------
+// Built-ins
+// ---------------------------------------------------------------------------
+
+pub const BUILTIN_PROMPTS: &[&str] = &[
+    "design",
+    "drive",
+    "review",
+    "run",
+    "run-with-context",
+    "spec",
+];
+
+const DRIVE: &str = "\
+Drive feature '{{feature_slug}}' forward using the sdlc state machine tools.
+
+Current directive:
+{{directive}}
+
+Execute the action, verify state advanced with sdlc_get_directive, then loop \
+until done. Only stop early for wait_for_approval or unblock_dependency.
+";
+
+const SPEC: &str = "\
+Write the spec for feature '{{feature_slug}}' ({{feature_title}}), currently in \
+the {{phase}} phase.
+
+Current directive:
+{{directive}}
+
+State the problem, the users affected, the scope and explicit non-goals, and \
+testable acceptance criteria. Write it with sdlc_write_artifact, then continue \
+the directive loop until done.
+
+Project guidance:
+{{guidance}}
+";
+
+const DESIGN: &str = "\
+Write the design for feature '{{feature_slug}}' ({{feature_title}}), currently \
+in the {{phase}} phase. Read the approved spec first.
+
+Current directive:
+{{directive}}
+
+Cover the components touched, data and API changes, error handling, and the \
+alternatives you rejected. Write it with sdlc_write_artifact, then continue the \
+directive loop until done.
+
+Project guidance:
+{{guidance}}
+";
+
+const REVIEW: &str = "\
+Review the implementation of feature '{{feature_slug}}' ({{feature_title}}), \
+currently in the {{phase}} phase, against its spec, design and tasks.
+
+Current directive:
+{{directive}}
+
+List every finding with its location and severity. Write the review with \
+sdlc_write_artifact, then continue the directive loop until done.
+
+Project guidance:
+{{guidance}}
+";
+
+const RUN: &str = "\
+Drive feature '{{feature_slug}}' through the sdlc state machine. Run \
+`sdlc next --for {{feature_slug}} --json` to get the next action, execute it, \
+then loop until done or a HITL gate is reached.
+";
+
+const RUN_WITH_CONTEXT: &str = "\
+Drive feature '{{feature_slug}}' through the sdlc state machine. User context: \
+\"{{context}}\". Use this as the core problem statement when writing \
+artifacts. Run `sdlc next --for {{feature_slug}} --json` to get the next \
+action, execute it, then loop until done or a HITL gate is reached.
+";
+
+fn builtin(name: &str) -> Option<&'static str> {
+    match name {
+        "drive" => Some(DRIVE),
+        "run" => Some(RUN),
+        "run-with-context" => Some(RUN_WITH_CONTEXT),
+        "spec" => Some(SPEC),
+        "design" => Some(DESIGN),
+        "review" => Some(REVIEW),
+        _ => None,
+    }
+}
+
+/// The template a run starts from for `action`.
+pub fn template_for(action: ActionType) -> &'static str {
+    match action {
+        ActionType::CreateSpec => "spec",
+        ActionType::CreateDesign => "design",
+        ActionType::CreateReview => "review",
+        _ => "drive",
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Variables
+// ---------------------------------------------------------------------------
+
+/// Values for a template's placeholders.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PromptVars(BTreeMap<String, String>);
+
+impl PromptVars {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn set(&mut self, name: impl Into<String>, value: impl Into<String>) -> &mut Self {
+        self.0.insert(name.into(), value.into());
+        self
+    }
+
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.0.get(name).map(String::as_str)
+    }
+
+    /// The variables for driving `classification`'s feature:
+    /// `feature_slug`, `feature_title`, `phase`, `action`, `message`,
+    /// `directive` (the classification as pretty JSON), and `guidance` (the
+    /// contents of `.sdlc/guidance.md`, empty when the file is absent).
+    pub fn for_directive(root: &Path, classification: &Classification) -> Self {
+        let directive = serde_json::to_string_pretty(classification)
+            .unwrap_or_else(|_| format!("{classification:?}"));
+        let guidance = std::fs::read_to_string(paths::guidance_md_path(root)).unwrap_or_default();
+
+        let mut vars = Self::new();
+        vars.set("feature_slug", &classification.feature)
+            .set("feature_title", &classification.title)
+            .set("phase", classification.current_phase.to_string())
+            .set("action", classification.action.to_string())
+            .set("message", &classification.message)
+            .set("directive", directive)
+            .set("guidance", guidance.trim_end());
+        vars
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Public API
+// ---------------------------------------------------------------------------
+
+/// Load a template by name. A project-local `.sdlc/prompts/<name>.md` takes
+/// precedence over a built-in of the same name.
+pub fn load(root: &Path, name: &str) -> Result<String> {
+    paths::validate_slug(name)?;
+    let path = paths::prompt_template_path(root, name);
+    if path.exists() {
+        return Ok(std::fs::read_to_string(&path)?);
+    }
+    builtin(name).map(str::to_string).ok_or_else(|| {
+        SdlcError::PromptTemplateNotFound(name.to_string(), available(root).join(", "))
+    })
+}
+
+/// Names of every template available to this project, sorted: built-ins plus
+/// any `.sdlc/prompts/*.md`.
+pub fn available(root: &Path) -> Vec<String> {
+    let mut names: Vec<String> = BUILTIN_PROMPTS.iter().map(|n| n.to_string()).collect();
+    if let Ok(entries) = std::fs::read_dir(root.join(paths::PROMPTS_DIR)) {
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.extension().is_some_and(|e| e == "md") {
+                if let Some(stem) = path.file_stem().and_then(|s| s.to_str()) {
+                    names.push(stem.to_string());
+                }
+            }
+        }
+    }
+    names.sort();
+    names.dedup();
+    names
+}
+
+/// Replace every `{{name}}` in `template` with its value from `vars`.
+///
+/// Whitespace inside the braces is ignored. A placeholder without a value is
+/// an error rather than an empty string, so a typo in a project template
+/// cannot silently drop context from the prompt.
+pub fn render(template: &str, vars: &PromptVars) -> Result<String> {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        let Some(end) = after.find("}}") else {
+            // Unterminated: report the name the author started to write.
+            let name = after.split_whitespace().next().unwrap_or_default();
+            return Err(SdlcError::PromptPlaceholderMissing(name.to_string()));
+        };
+        let name = after[..end].trim();
+        let value = vars
+            .get(name)
+            .ok_or_else(|| SdlcError::PromptPlaceholderMissing(name.to_string()))?;
+        out.push_str(value);
+        rest = &after[end + 2..];
+    }
+    out.push_str(rest);
+    Ok(out)
+}
+
+/// [`load`] the template `name` and [`render`] it with `vars`.
+pub fn render_named(root: &Path, name: &str, vars: &PromptVars) -> Result<String> {
+    render(&load(root, name)?, vars)
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn vars() -> PromptVars {
+        let mut vars = PromptVars::new();
+        vars.set("feature_slug", "auth")
+            .set("feature_title", "Auth")
+            .set("phase", "draft")
+            .set("action", "create_spec")
+            .set("message", "Write the spec")
+            .set("directive", "{}")
+            .set("guidance", "Keep it small.")
+            .set("context", "Logins time out.");
+        vars
+    }
+
+    #[test]
+    fn render_fills_placeholders() {
+        let out = render(
+            "Feature {{feature_slug}} in {{ phase }}: {{guidance}}",
+            &vars(),
+        )
+        .unwrap();
+        assert_eq!(out, "Feature auth in draft: Keep it small.");
+    }
+
+    #[test]
+    fn render_missing_placeholder_is_an_error() {
+        let err = render("Feature {{feature_slg}}", &vars()).unwrap_err();
+        assert!(
+            matches!(err, SdlcError::PromptPlaceholderMissing(ref n) if n == "feature_slg"),
+            "{err}"
+        );
+        assert!(render("Feature {{feature_slug", &vars()).is_err());
+    }
+
+    #[test]
+    fn builtin_prompts_render_with_directive_vars() {
+        let dir = TempDir::new().unwrap();
+        for name in BUILTIN_PROMPTS {
+            let out = render_named(dir.path(), name, &vars()).unwrap();
+            assert!(out.contains("'auth'"), "{name}: {out}");
+            assert!(!out.contains("{{"), "{name}: {out}");
+        }
+    }
+
+    #[test]
+    fn project_prompt_overrides_builtin() {
+        let dir = TempDir::new().unwrap();
+        let path = paths::prompt_template_path(dir.path(), "spec");
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, "Spec {{feature_slug}} our way.").unwrap();
+
+        let out = render_named(dir.path(), "spec", &vars()).unwrap();
+        assert_eq!(out, "Spec auth our way.");
+        assert_eq!(template_for(ActionType::CreateSpec), "spec");
+        assert_eq!(template_for(ActionType::ImplementTask), "drive");
+    }
+
+    #[test]
+    fn unknown_prompt_lists_available() {
+        let dir = TempDir::new().unwrap();
+        let msg = load(dir.path(), "nope").unwrap_err().to_string();
+        assert!(msg.contains("'nope'"), "{msg}");
+        assert!(
+            msg.contains("design, drive, review, run, run-with-context, spec"),
+            "{msg}"
+        );
+    }
+}
@@ -0,0 +1,363 @@
+//! Project-wide feature queries shared by `sdlc query` and the MCP
+//! `sdlc_query` tool.
+
+use crate::classifier::{Classification, Classifier, EvalContext};
+use crate::config::Config;
+use crate::error::Result;
+use crate::feature::Feature;
+pub use crate::feature::UnmetDependency;
+use crate::rules::default_rules;
+use crate::search::{EntityIndex, EntitySearchResult};
+use crate::state::State;
+use crate::types::{ActionType, Phase};
+use chrono::{DateTime, Duration, Utc};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::Path;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BlockedFeature {
+    pub slug: String,
+    pub blockers: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ReadyFeature {
+    pub slug: String,
+    pub phase: Phase,
+    pub action: ActionType,
+    pub message: String,
+    pub next_command: String,
+}
+
+/// A feature that would be ready but waits on dependencies that have not
+/// been released yet.
+#[derive(Debug, Clone, Serialize)]
+pub struct DependencyBlockedFeature {
+    pub slug: String,
+    pub phase: Phase,
+    pub blocked_by: Vec<UnmetDependency>,
+}
+
+/// [`ready`] plus the features it held back for unreleased dependencies.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ReadyReport {
+    pub ready: Vec<ReadyFeature>,
+    pub blocked_by_dependency: Vec<DependencyBlockedFeature>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PendingApproval {
+    pub slug: String,
+    pub action: ActionType,
+    pub message: String,
+    pub next_command: String,
+}
+
+/// A feature with no recorded activity for longer than a threshold.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct StaleFeature {
+    pub slug: String,
+    pub title: String,
+    pub phase: Phase,
+    pub last_activity: DateTime<Utc>,
+    /// Whole seconds between `last_activity` and the query time.
+    pub idle_seconds: i64,
+}
+
+/// Features with at least one blocker.
+pub fn blocked(root: &Path) -> Result<Vec<BlockedFeature>> {
+    Ok(Feature::list(root)?
+        .into_iter()
+        .filter(|f| f.is_blocked())
+        .map(|f| BlockedFeature {
+            slug: f.slug,
+            blockers: f.blockers,
+        })
+        .collect())
+}
+
+/// Active, unblocked features whose directive is actionable now and whose
+/// dependencies are all released, optionally restricted to one phase.
+pub fn ready(root: &Path, phase: Option<&str>) -> Result<Vec<ReadyFeature>> {
+    Ok(ready_report(root, phase)?.ready)
+}
+
+/// Like [`ready`], but also reports which otherwise-ready features are
+/// waiting on unreleased dependencies, and on which ones.
+pub fn ready_report(root: &Path, phase: Option<&str>) -> Result<ReadyReport> {
+    let features = Feature::list(root)?;
+    let phases: HashMap<String, Phase> =
+        features.iter().map(|f| (f.slug.clone(), f.phase)).collect();
+    let classified = classify(root, features)?;
+
+    let mut report = ReadyReport::default();
+    for (f, c) in classified {
+        if f.is_blocked()
+            || phase.is_some_and(|p| f.phase.as_str() != p)
+            || matches!(
+                c.action,
+                ActionType::WaitForApproval | ActionType::Done | ActionType::UnblockDependency
+            )
+        {
+            continue;
+        }
+        let unmet = f.unmet_dependencies(&phases);
+        if unmet.is_empty() {
+            report.ready.push(ReadyFeature {
+                slug: f.slug,
+                phase: f.phase,
+                action: c.action,
+                message: c.message,
+                next_command: c.next_command,
+            });
+        } else {
+            report.blocked_by_dependency.push(DependencyBlockedFeature {
+                slug: f.slug,
+                phase: f.phase,
+                blocked_by: unmet,
+            });
+        }
+    }
+    Ok(report)
+}
+
+/// Active features whose directive is an approval step.
+pub fn needs_approval(root: &Path) -> Result<Vec<PendingApproval>> {
+    Ok(classify_active(root)?
+        .into_iter()
+        .filter(|(_, c)| is_approval_action(c.action))
+        .map(|(f, c)| PendingApproval {
+            slug: f.slug,
+            action: c.action,
+            message: c.message,
+            next_command: c.next_command,
+        })
+        .collect())
+}
+
+/// Active, unreleased features idle for longer than `threshold`, longest
+/// idle first.
+pub fn stale(root: &Path, threshold: Duration) -> Result<Vec<StaleFeature>> {
+    stale_at(root, threshold, Utc::now())
+}
+
+/// [`stale`], measured from `now`.
+///
+/// A feature's last activity is the latest of its `updated_at`, its task and
+/// comment timestamps, and its entries in the audit log.
+pub fn stale_at(root: &Path, threshold: Duration, now: DateTime<Utc>) -> Result<Vec<StaleFeature>> {
+    let mut audited: HashMap<String, DateTime<Utc>> = HashMap::new();
+    for entry in crate::audit::read(root, None)? {
+        let last = audited.entry(entry.feature).or_insert(entry.timestamp);
+        *last = (*last).max(entry.timestamp);
+    }
+
+    let mut stale: Vec<StaleFeature> = Feature::list(root)?
+        .into_iter()
+        .filter(|f| !f.archived && f.phase != Phase::Released)
+        .filter_map(|f| {
+            let task_times = f
+                .tasks
+                .iter()
+                .flat_map(|t| [Some(t.created_at), t.started_at, t.completed_at])
+                .flatten();
+            let comment_times = f.comments.iter().map(|c| c.created_at);
+            let last_activity = task_times
+                .chain(comment_times)
+                .chain(audited.get(&f.slug).copied())
+                .fold(f.updated_at, DateTime::max);
+            let idle = now - last_activity;
+            (idle > threshold).then(|| StaleFeature {
+                slug: f.slug,
+                title: f.title,
+                phase: f.phase,
+                last_activity,
+                idle_seconds: idle.num_seconds(),
+            })
+        })
+        .collect();
+    stale.sort_by(|a, b| {
+        b.idle_seconds
+            .cmp(&a.idle_seconds)
+            .then_with(|| a.slug.cmp(&b.slug))
+    });
+    Ok(stale)
+}
+
+/// Full-text search across features, ponders, milestones, and
+/// investigations. See [`EntityIndex::search`] for the query syntax.
+pub fn search(root: &Path, query: &str, limit: usize) -> Result<Vec<EntitySearchResult>> {
+    EntityIndex::build_for_project(root)?.search(query, limit)
+}
+
+/// Actions that need verification or human sign-off before the phase can
+/// advance: the agent-executable `approve_*` steps and the `WaitForApproval`
+/// HITL gate, which surfaces features explicitly blocked on a human.
+pub fn is_approval_action(action: ActionType) -> bool {
+    matches!(
+        action,
+        ActionType::ApproveSpec
+            | ActionType::ApproveDesign
+            | ActionType::ApproveTasks
+            | ActionType::ApproveQaPlan
+            | ActionType::ApproveReview
+            | ActionType::ApproveAudit
+            | ActionType::ApproveMerge
+            | ActionType::WaitForApproval
+    )
+}
+
+fn classify_active(root: &Path) -> Result<Vec<(Feature, Classification)>> {
+    classify(root, Feature::list(root)?)
+}
+
+/// Classify the non-archived features in `features`.
+fn classify(root: &Path, features: Vec<Feature>) -> Result<Vec<(Feature, Classification)>> {
+    let config = Config::load(root)?;
+    let state = State::load(root)?;
+    let classifier = Classifier::new(default_rules());
+    Ok(features
+        .into_iter()
+        .filter(|f| !f.archived)
+        .map(|f| {
+            let c = classifier.classify(&EvalContext::new(&f, &state, &config, root));
+            (f, c)
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn init() -> TempDir {
+        let dir = TempDir::new().unwrap();
+        crate::io::ensure_dir(&dir.path().join(".sdlc")).unwrap();
+        Config::new("test").save(dir.path()).unwrap();
+        State::new("test").save(dir.path()).unwrap();
+        dir
+    }
+
+    fn slugs(features: &[ReadyFeature]) -> Vec<&str> {
+        features.iter().map(|f| f.slug.as_str()).collect()
+    }
+
+    #[test]
+    fn feature_is_ready_only_once_its_dependency_is_released() {
+        let dir = init();
+        let root = dir.path();
+        Feature::create(root, "db", "Database").unwrap();
+        let mut api = Feature::create(root, "api", "API").unwrap();
+        api.dependencies = vec!["db".into()];
+        api.save(root).unwrap();
+        let mut ui = Feature::create(root, "ui", "UI").unwrap();
+        ui.dependencies = vec!["api".into()];
+        ui.save(root).unwrap();
+
+        let report = ready_report(root, None).unwrap();
+        assert_eq!(slugs(&report.ready), ["db"]);
+        let api_blocked = &report.blocked_by_dependency[0];
+        assert_eq!(api_blocked.slug, "api");
+        assert_eq!(
+            api_blocked.blocked_by,
+            [UnmetDependency {
+                slug: "db".into(),
+                phase: Some(Phase::Draft),
+            }]
+        );
+        assert_eq!(report.blocked_by_dependency[1].blocked_by[0].slug, "api");
+
+        let mut db = Feature::load(root, "db").unwrap();
+        db.phase = Phase::Released;
+        db.save(root).unwrap();
+        assert_eq!(slugs(&ready(root, None).unwrap()), ["api"]);
+    }
+
+    #[test]
+    fn missing_dependency_blocks_with_no_phase() {
+        let dir = init();
+        let mut f = Feature::create(dir.path(), "auth", "Auth").unwrap();
+        f.dependencies = vec!["ghost".into()];
+        f.save(dir.path()).unwrap();
+
+        let report = ready_report(dir.path(), None).unwrap();
+        assert!(report.ready.is_empty());
+        assert_eq!(report.blocked_by_dependency[0].blocked_by[0].phase, None);
+    }
+
+    #[test]
+    fn stale_lists_only_features_idle_past_the_threshold() {
+        let dir = init();
+        let root = dir.path();
+        let now = Utc::now();
+        let days_ago = |d: i64| now - Duration::days(d);
+
+        let mut old = Feature::create(root, "old", "Old").unwrap();
+        old.created_at = days_ago(40);
+        old.updated_at = days_ago(30);
+        crate::task::add_task(&mut old.tasks, "Write it");
+        old.tasks[0].created_at = days_ago(10);
+        old.save(root).unwrap();
+
+        // Untouched for a month except for a comment yesterday.
+        let mut commented = Feature::create(root, "commented", "Commented").unwrap();
+        commented.created_at = days_ago(40);
+        commented.updated_at = days_ago(30);
+        crate::comment::add_comment(
+            &mut commented.comments,
+            &mut commented.next_comment_seq,
+            "Still on it",
+            Some(crate::comment::CommentFlag::Fyi),
+            crate::comment::CommentTarget::Feature,
+            None,
+        );
+        commented.comments[0].created_at = days_ago(1);
+        commented.save(root).unwrap();
+
+        let mut released = Feature::create(root, "shipped", "Shipped").unwrap();
+        released.phase = Phase::Released;
+        released.updated_at = days_ago(90);
+        released.save(root).unwrap();
+
+        // Untouched fields, but a task moved three days ago per the audit log.
+        let mut audited = Feature::create(root, "audited", "Audited").unwrap();
+        audited.updated_at = days_ago(30);
+        audited.save(root).unwrap();
+
+        Feature::create(root, "fresh", "Fresh").unwrap();
+
+        // Replace the entries the saves above logged just now.
+        std::fs::remove_file(crate::paths::audit_log_path(root)).unwrap();
+        crate::audit::append(
+            root,
+            &[crate::audit::AuditEntry {
+                timestamp: days_ago(3),
+                actor: "test".into(),
+                feature: "audited".into(),
+                action: crate::audit::AuditAction::TaskStatusChanged,
+                phase_before: Phase::Draft,
+                phase_after: Phase::Draft,
+                detail: None,
+                task: None,
+                from: None,
+                to: None,
+            }],
+        )
+        .unwrap();
+
+        let stale = stale_at(root, Duration::days(7), now).unwrap();
+        assert_eq!(
+            stale,
+            [StaleFeature {
+                slug: "old".into(),
+                title: "Old".into(),
+                phase: Phase::Draft,
+                last_activity: days_ago(10),
+                idle_seconds: Duration::days(10).num_seconds(),
+            }]
+        );
+        assert!(stale_at(root, Duration::days(11), now).unwrap().is_empty());
+    }
+}
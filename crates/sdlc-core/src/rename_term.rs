@@ -0,0 +1,208 @@
+//! Project-wide rename of a term in free-text content.
+//!
+//! Covers feature descriptions, artifact bodies, and milestone visions. Only
+//! whole-word matches are replaced, so renaming `auth` leaves `author` alone.
+//! Structural manifest fields (slugs, titles, phases, paths) are never
+//! touched: descriptions and visions are rewritten through their typed
+//! manifests, and artifact bodies are plain markdown files.
+
+use crate::error::Result;
+use crate::feature::Feature;
+use crate::milestone::Milestone;
+use regex::Regex;
+use serde::Serialize;
+use std::path::Path;
+
+/// One piece of free text the rename changes.
+#[derive(Debug, Clone, Serialize)]
+pub struct TermEdit {
+    /// What was edited, e.g. `feature auth description` or
+    /// `.sdlc/features/auth/spec.md`.
+    pub location: String,
+    pub replacements: usize,
+    /// Changed lines as `(before, after)` pairs, for showing a diff.
+    pub lines: Vec<(String, String)>,
+}
+
+/// Replace whole-word `old` with `new` across the project, returning every
+/// edit. With `dry_run` nothing is written.
+pub fn rename_term(root: &Path, old: &str, new: &str, dry_run: bool) -> Result<Vec<TermEdit>> {
+    let Some(re) = word_regex(old) else {
+        return Ok(Vec::new());
+    };
+    let mut edits = Vec::new();
+
+    for mut feature in Feature::list(root)? {
+        if let Some((text, edit)) = feature
+            .description
+            .as_deref()
+            .and_then(|d| replace(&re, d, new, format!("feature {} description", feature.slug)))
+        {
+            if !dry_run {
+                feature.description = Some(text);
+                feature.save(root)?;
+            }
+            edits.push(edit);
+        }
+        for artifact in &feature.artifacts {
+            let path = root.join(&artifact.path);
+            let Ok(body) = std::fs::read_to_string(&path) else {
+                continue;
+            };
+            if let Some((text, edit)) = replace(&re, &body, new, artifact.path.clone()) {
+                if !dry_run {
+                    crate::io::atomic_write(&path, text.as_bytes())?;
+                }
+                edits.push(edit);
+            }
+        }
+    }
+
+    for mut milestone in Milestone::list(root)? {
+        if let Some((text, edit)) = milestone
+            .vision
+            .as_deref()
+            .and_then(|v| replace(&re, v, new, format!("milestone {} vision", milestone.slug)))
+        {
+            if !dry_run {
+                milestone.vision = Some(text);
+                milestone.save(root)?;
+            }
+            edits.push(edit);
+        }
+    }
+
+    Ok(edits)
+}
+
+/// Matches `term` as a whole word. Word boundaries are only required at ends
+/// that are word characters, so terms like `C++` still match.
+fn word_regex(term: &str) -> Option<Regex> {
+    let first = term.chars().next()?;
+    let last = term.chars().last()?;
+    let is_word = |c: char| c.is_alphanumeric() || c == '_';
+    let pattern = format!(
+        "{}{}{}",
+        if is_word(first) { r"\b" } else { "" },
+        regex::escape(term),
+        if is_word(last) { r"\b" } else { "" },
+    );
+    Regex::new(&pattern).ok()
+}
+
+fn replace(re: &Regex, text: &str, new: &str, location: String) -> Option<(String, TermEdit)> {
+    let replacements = re.find_iter(text).count();
+    if replacements == 0 {
+        return None;
+    }
+    let updated = re.replace_all(text, regex::NoExpand(new)).into_owned();
+    let lines = text
+        .lines()
+        .zip(updated.lines())
+        .filter(|(before, after)| before != after)
+        .map(|(before, after)| (before.to_string(), after.to_string()))
+        .collect();
+    Some((
+        updated,
+        TermEdit {
+            location,
+            replacements,
+            lines,
+        },
+    ))
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::ArtifactType;
+    use tempfile::TempDir;
+
+    fn project() -> TempDir {
+        let dir = TempDir::new().unwrap();
+        std::fs::create_dir_all(dir.path().join(".sdlc/features")).unwrap();
+        std::fs::create_dir_all(dir.path().join(".sdlc/milestones")).unwrap();
+        dir
+    }
+
+    #[test]
+    fn replaces_whole_words_in_descriptions_artifacts_and_visions() {
+        let dir = project();
+        Feature::create_with_description(
+            dir.path(),
+            "login",
+            "Login",
+            Some("Workspace owners sign in. Workspaces list.".into()),
+        )
+        .unwrap();
+        let feature = Feature::load(dir.path(), "login").unwrap();
+        let spec = dir
+            .path()
+            .join(&feature.artifact(ArtifactType::Spec).unwrap().path);
+        std::fs::write(&spec, "# Spec\n\nEach Workspace has members.\n").unwrap();
+        let mut m = Milestone::create(dir.path(), "v1", "V1").unwrap();
+        m.vision = Some("A Workspace for every team.".into());
+        m.save(dir.path()).unwrap();
+
+        let edits = rename_term(dir.path(), "Workspace", "Organization", false).unwrap();
+        assert_eq!(edits.len(), 3);
+
+        let feature = Feature::load(dir.path(), "login").unwrap();
+        assert_eq!(
+            feature.description.as_deref(),
+            Some("Organization owners sign in. Workspaces list.")
+        );
+        assert_eq!(feature.slug, "login");
+        assert_eq!(
+            std::fs::read_to_string(&spec).unwrap(),
+            "# Spec\n\nEach Organization has members.\n"
+        );
+        let m = Milestone::load(dir.path(), "v1").unwrap();
+        assert_eq!(m.vision.as_deref(), Some("A Organization for every team."));
+    }
+
+    #[test]
+    fn partial_word_matches_are_skipped() {
+        let dir = project();
+        Feature::create_with_description(
+            dir.path(),
+            "authoring",
+            "Authoring",
+            Some("Authors write; auth is separate.".into()),
+        )
+        .unwrap();
+
+        let edits = rename_term(dir.path(), "auth", "identity", false).unwrap();
+        assert_eq!(edits.len(), 1);
+        assert_eq!(edits[0].replacements, 1);
+        let feature = Feature::load(dir.path(), "authoring").unwrap();
+        assert_eq!(
+            feature.description.as_deref(),
+            Some("Authors write; identity is separate.")
+        );
+    }
+
+    #[test]
+    fn dry_run_writes_nothing() {
+        let dir = project();
+        Feature::create_with_description(dir.path(), "f", "F", Some("Uses the widget.".into()))
+            .unwrap();
+        let manifest = dir.path().join(".sdlc/features/f/manifest.yaml");
+        let before = std::fs::read_to_string(&manifest).unwrap();
+
+        let edits = rename_term(dir.path(), "widget", "gadget", true).unwrap();
+        assert_eq!(edits.len(), 1);
+        assert_eq!(
+            edits[0].lines,
+            vec![(
+                "Uses the widget.".to_string(),
+                "Uses the gadget.".to_string()
+            )]
+        );
+        assert_eq!(std::fs::read_to_string(&manifest).unwrap(), before);
+    }
+}
@@ -0,0 +1,206 @@
+//! Retry with exponential backoff.
+//!
+//! One implementation of the attempt/sleep/give-up loop for every caller
+//! that retries transient failures: tool spawns, the app-tunnel proxy, and
+//! anything else that talks to something which may briefly be unavailable.
+//! [`retry_with_backoff`] blocks the thread between attempts;
+//! [`retry_with_backoff_async`] takes the caller's async sleep, so this crate
+//! stays free of an async runtime.
+//!
+//! The `claude-agent` runner does not use this. That crate sits below
+//! sdlc-core and has no dependency on it, and an agent run is not safe to
+//! repeat from the top: its tool calls have side effects. It recovers in
+//! place instead, through model fallbacks, compaction and checkpoint resume.
+
+use rand::Rng;
+use std::future::Future;
+use std::time::Duration;
+
+/// How often and how patiently to retry.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RetryPolicy {
+    /// Total attempts, the first included. `1` means no retries.
+    pub max_attempts: u32,
+    /// Delay before the first retry; doubled for each further retry.
+    pub base_delay: Duration,
+    /// Ceiling on any single delay, before jitter.
+    pub max_delay: Duration,
+    /// Fraction of each delay randomized away, in `0.0..=1.0`: a delay `d`
+    /// becomes a random value in `d * (1 - jitter) ..= d`. Spreads out
+    /// callers that failed together.
+    pub jitter: f64,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(100),
+            max_delay: Duration::from_secs(5),
+            jitter: 0.2,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// The delay before retry number `retry` (1-based), without jitter.
+    pub fn delay(&self, retry: u32) -> Duration {
+        let factor = 2u32.saturating_pow(retry.saturating_sub(1));
+        self.base_delay
+            .checked_mul(factor)
+            .unwrap_or(self.max_delay)
+            .min(self.max_delay)
+    }
+
+    /// [`RetryPolicy::delay`] with jitter applied.
+    fn jittered_delay(&self, retry: u32) -> Duration {
+        let delay = self.delay(retry);
+        let jitter = self.jitter.clamp(0.0, 1.0);
+        if jitter == 0.0 || delay.is_zero() {
+            return delay;
+        }
+        delay.mul_f64(1.0 - rand::thread_rng().gen_range(0.0..=jitter))
+    }
+}
+
+/// Run `op` until it succeeds, fails with an error `is_retryable` rejects,
+/// or `policy.max_attempts` are used up; sleeps the thread between attempts.
+/// `op` gets the 1-based attempt number. The last error is returned.
+pub fn retry_with_backoff<T, E>(
+    policy: &RetryPolicy,
+    is_retryable: impl Fn(&E) -> bool,
+    mut op: impl FnMut(u32) -> Result<T, E>,
+) -> Result<T, E> {
+    let mut attempt = 1;
+    loop {
+        match op(attempt) {
+            Err(e) if attempt < policy.max_attempts && is_retryable(&e) => {
+                std::thread::sleep(policy.jittered_delay(attempt));
+                attempt += 1;
+            }
+            other => return other,
+        }
+    }
+}
+
+/// [`retry_with_backoff`] for async operations. `sleep` is the runtime's
+/// sleep, e.g. `tokio::time::sleep`.
+pub async fn retry_with_backoff_async<T, E, Op, Fut, Sleep, SleepFut>(
+    policy: &RetryPolicy,
+    is_retryable: impl Fn(&E) -> bool,
+    mut op: Op,
+    sleep: Sleep,
+) -> Result<T, E>
+where
+    Op: FnMut(u32) -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+    Sleep: Fn(Duration) -> SleepFut,
+    SleepFut: Future<Output = ()>,
+{
+    let mut attempt = 1;
+    loop {
+        match op(attempt).await {
+            Err(e) if attempt < policy.max_attempts && is_retryable(&e) => {
+                sleep(policy.jittered_delay(attempt)).await;
+                attempt += 1;
+            }
+            other => return other,
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn instant(max_attempts: u32) -> RetryPolicy {
+        RetryPolicy {
+            max_attempts,
+            base_delay: Duration::ZERO,
+            max_delay: Duration::ZERO,
+            jitter: 0.0,
+        }
+    }
+
+    #[test]
+    fn delays_double_up_to_the_ceiling() {
+        let policy = RetryPolicy {
+            max_attempts: 8,
+            base_delay: Duration::from_millis(100),
+            max_delay: Duration::from_secs(1),
+            jitter: 0.0,
+        };
+        let schedule: Vec<u64> = (1..=6)
+            .map(|retry| policy.delay(retry).as_millis() as u64)
+            .collect();
+        assert_eq!(schedule, vec![100, 200, 400, 800, 1_000, 1_000]);
+        // No overflow on absurd retry counts.
+        assert_eq!(policy.delay(200), Duration::from_secs(1));
+    }
+
+    #[test]
+    fn jitter_only_shortens_delays_within_bounds() {
+        let policy = RetryPolicy {
+            jitter: 0.5,
+            ..RetryPolicy::default()
+        };
+        for _ in 0..100 {
+            let d = policy.jittered_delay(2);
+            assert!(d <= Duration::from_millis(200), "{d:?}");
+            assert!(d >= Duration::from_millis(100), "{d:?}");
+        }
+    }
+
+    #[test]
+    fn succeeds_after_transient_failures() {
+        let mut attempts = Vec::new();
+        let result = retry_with_backoff(
+            &instant(5),
+            |_: &&str| true,
+            |attempt| {
+                attempts.push(attempt);
+                if attempt < 3 {
+                    Err("busy")
+                } else {
+                    Ok(attempt)
+                }
+            },
+        );
+        assert_eq!(result, Ok(3));
+        assert_eq!(attempts, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn non_retryable_error_bails_immediately() {
+        let mut calls = 0;
+        let result: Result<(), &str> = retry_with_backoff(
+            &instant(5),
+            |e| *e == "busy",
+            |_| {
+                calls += 1;
+                Err("denied")
+            },
+        );
+        assert_eq!(result, Err("denied"));
+        assert_eq!(calls, 1);
+    }
+
+    #[test]
+    fn stops_at_max_attempts_with_the_last_error() {
+        let mut calls = 0;
+        let result: Result<(), String> = retry_with_backoff(
+            &instant(4),
+            |_| true,
+            |attempt| {
+                calls += 1;
+                Err(format!("attempt {attempt} failed"))
+            },
+        );
+        assert_eq!(result, Err("attempt 4 failed".to_string()));
+        assert_eq!(calls, 4);
+    }
+}
@@ -4,6 +4,7 @@ use crate::{
     investigation::InvestigationEntry,
     milestone::{Milestone, MilestoneStatus},
     ponder::{PonderArtifactMeta, PonderEntry},
+    types::ArtifactType,
     workspace,
 };
 use std::path::Path;
@@ -11,9 +12,43 @@ use tantivy::{
     collector::TopDocs,
     query::QueryParser,
     schema::{Field, Schema, Value, STORED, STRING, TEXT},
+    snippet::SnippetGenerator,
     Index, IndexWriter, ReloadPolicy, TantivyDocument,
 };
 
+/// Score multipliers applied at query time. A term in a title is a much
+/// stronger signal than the same term buried in a comment or artifact.
+const TITLE_BOOST: f32 = 3.0;
+const DESCRIPTION_BOOST: f32 = 1.5;
+
+/// Maximum length of a result snippet, in characters.
+const SNIPPET_MAX_CHARS: usize = 160;
+
+/// Field aliases accepted in entity queries, rewritten to the indexed name.
+const FIELD_ALIASES: &[(&str, &str)] = &[("type:", "kind:"), ("phase:", "status:")];
+
+/// Filter keys an [`EntityIndex`] query may use, aliases included.
+pub const ENTITY_FILTER_FIELDS: &[&str] = &[
+    "kind",
+    "type",
+    "slug",
+    "title",
+    "status",
+    "phase",
+    "description",
+    "body",
+];
+
+/// Filter keys a [`TaskIndex`] query may use.
+pub const TASK_FILTER_FIELDS: &[&str] = &[
+    "feature_slug",
+    "task_id",
+    "title",
+    "status",
+    "description",
+    "body",
+];
+
 // ---------------------------------------------------------------------------
 // Public types
 // ---------------------------------------------------------------------------
@@ -25,6 +60,11 @@ pub struct EntitySearchResult {
     pub title: String,
     pub status: String,
     pub score: f32,
+    /// Best-matching fragment of the description or body, with matched
+    /// terms wrapped in `<b>…</b>` (HTML-escaped otherwise). `None` when the
+    /// match was in the title only.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub snippet: Option<String>,
 }
 
 // ---------------------------------------------------------------------------
@@ -55,6 +95,44 @@ pub struct EntityIndex {
 }
 
 impl EntityIndex {
+    /// Load every entity under `root` and build an index over them.
+    pub fn build_for_project(root: &Path) -> Result<Self> {
+        let features = Feature::list(root)?;
+
+        let ponder_entries = PonderEntry::list(root)?;
+        let ponders: Vec<_> = ponder_entries
+            .iter()
+            .map(|e| {
+                let arts = crate::ponder::list_artifacts(root, &e.slug).unwrap_or_default();
+                (e.clone(), arts)
+            })
+            .collect();
+
+        let milestones: Vec<_> = Milestone::list(root)?
+            .into_iter()
+            .map(|m| {
+                let status = m.compute_status(&features);
+                (m, status)
+            })
+            .collect();
+
+        let investigations: Vec<_> = crate::investigation::list(root)?
+            .into_iter()
+            .map(|e| {
+                let arts = crate::investigation::list_artifacts(root, &e.slug).unwrap_or_default();
+                (e, arts)
+            })
+            .collect();
+
+        Self::build(EntitySources {
+            features: &features,
+            ponders: &ponders,
+            milestones: &milestones,
+            investigations: &investigations,
+            root,
+        })
+    }
+
     /// Build an ephemeral in-RAM index from all entity types.
     ///
     /// Indexed fields:
@@ -62,8 +140,8 @@ impl EntityIndex {
     /// - `slug`        — STRING (exact-match, stored)
     /// - `title`       — TEXT (tokenized, stored)
     /// - `status`      — STRING (exact-match, stored)
-    /// - `description` — TEXT (tokenized, not stored)
-    /// - `body`        — TEXT (tokenized, not stored)
+    /// - `description` — TEXT (tokenized, stored for snippets)
+    /// - `body`        — TEXT (tokenized, stored for snippets)
     pub fn build(sources: EntitySources<'_>) -> Result<Self> {
         let (schema, fields) = build_entity_schema();
         let index = Index::create_in_ram(schema);
@@ -95,18 +173,19 @@ impl EntityIndex {
                 .filter(|s| !s.is_empty())
                 .collect();
 
-            const ARTIFACT_FILES: &[&str] = &[
-                "spec.md",
-                "design.md",
-                "tasks.md",
-                "qa_plan.md",
-                "review.md",
-                "audit.md",
-                "qa_results.md",
+            const ARTIFACT_TYPES: &[ArtifactType] = &[
+                ArtifactType::Spec,
+                ArtifactType::Design,
+                ArtifactType::Tasks,
+                ArtifactType::QaPlan,
+                ArtifactType::Review,
+                ArtifactType::Audit,
+                ArtifactType::QaResults,
             ];
+            let feature_dir = crate::paths::feature_dir(sources.root, &f.slug);
             let mut artifact_contents: Vec<String> = Vec::new();
-            for filename in ARTIFACT_FILES {
-                let path = sources.root.join("features").join(&f.slug).join(filename);
+            for artifact in ARTIFACT_TYPES {
+                let path = feature_dir.join(artifact.filename());
                 if let Ok(content) = std::fs::read_to_string(&path) {
                     let end = content.floor_char_boundary(8000);
                     artifact_contents.push(content[..end].to_string());
@@ -233,15 +312,21 @@ impl EntityIndex {
     /// - Phrase:     `"exact phrase"`
     /// - Boolean:    `auth OR oauth`, `auth NOT legacy`
     /// - Field scope: `status:ready`, `slug:auth`, `title:oauth`, `kind:milestone`
+    ///   (`type:` and `phase:` are accepted as aliases for `kind:` and `status:`)
     /// - Prefix:     `auth*`
+    ///
+    /// Title matches are boosted over description matches, which are boosted
+    /// over body (comments, tasks, artifacts) matches.
     pub fn search(&self, query_str: &str, limit: usize) -> Result<Vec<EntitySearchResult>> {
         let searcher = self.reader.searcher();
 
         let default_fields = vec![self.fields.title, self.fields.description, self.fields.body];
         let mut parser = QueryParser::for_index(&self.index, default_fields);
         parser.set_conjunction_by_default();
+        parser.set_field_boost(self.fields.title, TITLE_BOOST);
+        parser.set_field_boost(self.fields.description, DESCRIPTION_BOOST);
 
-        let query = match parser.parse_query(query_str) {
+        let query = match parser.parse_query(&expand_field_aliases(query_str)) {
             Ok(q) => q,
             Err(_) => return Ok(vec![]),
         };
@@ -250,6 +335,14 @@ impl EntityIndex {
             .search(&query, &TopDocs::with_limit(limit))
             .map_err(|e| SdlcError::Search(e.to_string()))?;
 
+        let mut snippet_generators = Vec::new();
+        for field in [self.fields.description, self.fields.body] {
+            let mut generator = SnippetGenerator::create(&searcher, &*query, field)
+                .map_err(|e| SdlcError::Search(e.to_string()))?;
+            generator.set_max_num_chars(SNIPPET_MAX_CHARS);
+            snippet_generators.push(generator);
+        }
+
         let mut results = Vec::with_capacity(top_docs.len());
         for (score, doc_addr) in top_docs {
             let doc: TantivyDocument = searcher
@@ -276,6 +369,11 @@ impl EntityIndex {
                 .and_then(|v| v.as_str())
                 .unwrap_or("")
                 .to_string();
+            let snippet = snippet_generators
+                .iter()
+                .map(|g| g.snippet_from_doc(&doc))
+                .find(|s| !s.is_empty())
+                .map(|s| s.to_html());
 
             results.push(EntitySearchResult {
                 kind,
@@ -283,6 +381,7 @@ impl EntityIndex {
                 title,
                 status,
                 score,
+                snippet,
             });
         }
 
@@ -431,10 +530,59 @@ impl TaskIndex {
     }
 }
 
+/// The filter keys (`key:` prefixes at term boundaries) used in `query`, in
+/// order of appearance. Text inside double quotes is a phrase, not a filter.
+pub fn filter_fields(query: &str) -> Vec<&str> {
+    let mut fields = Vec::new();
+    let mut in_quotes = false;
+    let mut prev: Option<char> = None;
+    for (i, c) in query.char_indices() {
+        if c == '"' {
+            in_quotes = !in_quotes;
+        }
+        let at_boundary = prev.is_none_or(|p| p.is_whitespace() || matches!(p, '(' | '-' | '+'));
+        prev = Some(c);
+        if in_quotes || !at_boundary || !(c.is_alphanumeric() || c == '_') {
+            continue;
+        }
+        let rest = &query[i..];
+        let len = rest
+            .find(|ch: char| !(ch.is_alphanumeric() || ch == '_'))
+            .unwrap_or(rest.len());
+        if rest[len..].starts_with(':') {
+            fields.push(&rest[..len]);
+        }
+    }
+    fields
+}
+
 // ---------------------------------------------------------------------------
 // Schema construction
 // ---------------------------------------------------------------------------
 
+/// Rewrite user-facing field aliases (`type:`, `phase:`) to indexed field
+/// names. Only rewrites at term boundaries, so `subtype:x` is left alone.
+fn expand_field_aliases(query: &str) -> String {
+    let mut out = String::with_capacity(query.len());
+    let mut rest = query;
+    while let Some(c) = rest.chars().next() {
+        let at_boundary = out
+            .chars()
+            .last()
+            .is_none_or(|p| p.is_whitespace() || matches!(p, '(' | '-' | '+'));
+        if at_boundary {
+            if let Some((alias, field)) = FIELD_ALIASES.iter().find(|(a, _)| rest.starts_with(a)) {
+                out.push_str(field);
+                rest = &rest[alias.len()..];
+                continue;
+            }
+        }
+        out.push(c);
+        rest = &rest[c.len_utf8()..];
+    }
+    out
+}
+
 fn build_entity_schema() -> (Schema, EntityFields) {
     let mut builder = Schema::builder();
 
@@ -442,8 +590,8 @@ fn build_entity_schema() -> (Schema, EntityFields) {
     let slug = builder.add_text_field("slug", STRING | STORED);
     let title = builder.add_text_field("title", TEXT | STORED);
     let status = builder.add_text_field("status", STRING | STORED);
-    let description = builder.add_text_field("description", TEXT);
-    let body = builder.add_text_field("body", TEXT);
+    let description = builder.add_text_field("description", TEXT | STORED);
+    let body = builder.add_text_field("body", TEXT | STORED);
 
     let schema = builder.build();
     let fields = EntityFields {
@@ -825,7 +973,7 @@ mod tests {
         let dir = tempfile::TempDir::new().unwrap();
         let features = make_features();
 
-        let feature_dir = dir.path().join("features").join("auth-login");
+        let feature_dir = crate::paths::feature_dir(dir.path(), "auth-login");
         fs::create_dir_all(&feature_dir).unwrap();
         fs::write(
             feature_dir.join("spec.md"),
@@ -839,4 +987,92 @@ mod tests {
         assert_eq!(results.len(), 1);
         assert_eq!(results[0].slug, "auth-login");
     }
+
+    #[test]
+    fn title_match_ranks_above_comment_match() {
+        use crate::comment::{add_comment, CommentTarget};
+        let dir = tempfile::TempDir::new().unwrap();
+        let mut commented = Feature::new("billing", "Billing Dashboard");
+        add_comment(
+            &mut commented.comments,
+            &mut commented.next_comment_seq,
+            "Should this reuse the webhook retry queue?",
+            None,
+            CommentTarget::Feature,
+            None,
+        );
+        let titled = Feature::new("webhooks", "Webhook Delivery");
+        let features = vec![commented, titled];
+
+        let index = EntityIndex::build(empty_sources(&features, dir.path())).unwrap();
+        let results = index.search("webhook", 10).unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].slug, "webhooks");
+        assert_eq!(results[1].slug, "billing");
+        assert!(results[0].score > results[1].score);
+    }
+
+    #[test]
+    fn search_returns_highlighted_snippet() {
+        let features = make_features();
+        let dir = tempfile::TempDir::new().unwrap();
+        let index = EntityIndex::build(empty_sources(&features, dir.path())).unwrap();
+        let results = index.search("stripe", 10).unwrap();
+        assert_eq!(
+            results[0].snippet.as_deref(),
+            Some("<b>Stripe</b> checkout integration")
+        );
+
+        // A title-only match has nothing further to show.
+        let results = index.search("interface", 10).unwrap();
+        assert_eq!(results[0].slug, "search-ui");
+        assert!(results[0].snippet.is_none());
+    }
+
+    #[test]
+    fn search_accepts_type_and_phase_aliases() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let mut reviewed = Feature::new("auth-review", "Auth Review");
+        reviewed.phase = crate::types::Phase::Review;
+        let features = vec![reviewed, Feature::new("auth-draft", "Auth Draft")];
+        let m = Milestone::new("auth-milestone", "Auth Milestone");
+        let sources = EntitySources {
+            features: &features,
+            ponders: &[],
+            milestones: &[(m, MilestoneStatus::Active)],
+            investigations: &[],
+            root: dir.path(),
+        };
+        let index = EntityIndex::build(sources).unwrap();
+
+        let results = index.search("auth type:feature", 10).unwrap();
+        assert_eq!(results.len(), 2);
+        assert!(results.iter().all(|r| r.kind == "feature"));
+
+        let results = index.search("auth phase:review", 10).unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].slug, "auth-review");
+    }
+
+    #[test]
+    fn filter_fields_skips_phrases_and_mid_word_colons() {
+        assert_eq!(
+            filter_fields(r#"auth type:feature -(phase:draft) "a:b" x:y:z"#),
+            vec!["type", "phase", "x"]
+        );
+        assert!(filter_fields("plain words").is_empty());
+    }
+
+    #[test]
+    fn expand_field_aliases_only_rewrites_whole_fields() {
+        assert_eq!(
+            expand_field_aliases("type:feature phase:review"),
+            "kind:feature status:review"
+        );
+        assert_eq!(
+            expand_field_aliases("auth -(phase:draft)"),
+            "auth -(status:draft)"
+        );
+        assert_eq!(expand_field_aliases("subtype:x"), "subtype:x");
+    }
 }
@@ -1,4 +1,4 @@
-//! Encrypted secrets management using the `age` binary.
+//! Encrypted secrets management using the `age` crate (no external binary).
 //!
 //! Layout:
 //!   .sdlc/secrets/
@@ -215,11 +215,53 @@ fn save_env_meta(root: &Path, meta: &SecretsEnvMeta) -> Result<()> {
 }
 
 // ---------------------------------------------------------------------------
-// AGE binary and identity resolution
+// AGE recipients and identity resolution
 // ---------------------------------------------------------------------------
 
-fn age_bin() -> Result<PathBuf> {
-    which::which("age").map_err(|_| SdlcError::AgeNotInstalled)
+/// Parse an authorized key into an `age` recipient.
+fn parse_recipient(key: &SecretsKey) -> Result<Box<dyn age::Recipient + Send>> {
+    let public_key = key.public_key.trim();
+    match key.key_type {
+        KeyType::Age => public_key
+            .parse::<age::x25519::Recipient>()
+            .map(|r| Box::new(r) as Box<dyn age::Recipient + Send>)
+            .map_err(|e| SdlcError::AgeEncryptFailed(format!("key '{}': {e}", key.name))),
+        KeyType::Ssh => public_key
+            .parse::<age::ssh::Recipient>()
+            .map(|r| Box::new(r) as Box<dyn age::Recipient + Send>)
+            .map_err(|e| SdlcError::AgeEncryptFailed(format!("key '{}': {e:?}", key.name))),
+    }
+}
+
+/// Load the private identities from an identity file.
+///
+/// Accepts native age identity files (`AGE-SECRET-KEY-1...`) and unencrypted
+/// OpenSSH private keys (ed25519 / rsa). Passphrase-protected SSH keys are
+/// rejected — there is no prompt in the server or agent context.
+fn load_identities(identity: &Path) -> Result<Vec<Box<dyn age::Identity>>> {
+    let content = std::fs::read_to_string(identity).map_err(|e| {
+        SdlcError::AgeDecryptFailed(format!("cannot read identity {}: {e}", identity.display()))
+    })?;
+    if content.contains("AGE-SECRET-KEY-") {
+        return age::IdentityFile::from_buffer(content.as_bytes())
+            .map_err(|e| SdlcError::AgeDecryptFailed(e.to_string()))?
+            .into_identities()
+            .map_err(|e| SdlcError::AgeDecryptFailed(e.to_string()));
+    }
+    let filename = Some(identity.display().to_string());
+    match age::ssh::Identity::from_buffer(content.as_bytes(), filename)
+        .map_err(|e| SdlcError::AgeDecryptFailed(e.to_string()))?
+    {
+        key @ age::ssh::Identity::Unencrypted(_) => Ok(vec![Box::new(key)]),
+        age::ssh::Identity::Encrypted(_) => Err(SdlcError::AgeDecryptFailed(format!(
+            "identity {} is passphrase-protected — use an unencrypted key or a native age key",
+            identity.display()
+        ))),
+        age::ssh::Identity::Unsupported(k) => Err(SdlcError::AgeDecryptFailed(format!(
+            "identity {} is an unsupported key: {k:?}",
+            identity.display()
+        ))),
+    }
 }
 
 /// Resolve the default identity path (private key for decryption).
@@ -237,27 +279,57 @@ pub fn default_identity() -> Option<PathBuf> {
 // Encryption / decryption
 // ---------------------------------------------------------------------------
 
+/// Encrypt `plaintext` to every recipient in `keys`.
+fn encrypt(plaintext: &[u8], keys: &[SecretsKey]) -> Result<Vec<u8>> {
+    let recipients = keys
+        .iter()
+        .map(parse_recipient)
+        .collect::<Result<Vec<_>>>()?;
+    let encryptor = age::Encryptor::with_recipients(recipients.iter().map(|r| r.as_ref() as _))
+        .map_err(|e| SdlcError::AgeEncryptFailed(e.to_string()))?;
+    let mut ciphertext = Vec::new();
+    let mut writer = encryptor
+        .wrap_output(&mut ciphertext)
+        .map_err(|e| SdlcError::AgeEncryptFailed(e.to_string()))?;
+    {
+        use std::io::Write as _;
+        writer
+            .write_all(plaintext)
+            .map_err(|e| SdlcError::AgeEncryptFailed(e.to_string()))?;
+    }
+    writer
+        .finish()
+        .map_err(|e| SdlcError::AgeEncryptFailed(e.to_string()))?;
+    Ok(ciphertext)
+}
+
+/// Decrypt `ciphertext` with the identities in the file at `identity`.
+fn decrypt(ciphertext: &[u8], identity: &Path) -> Result<Vec<u8>> {
+    let identities = load_identities(identity)?;
+    let decryptor = age::Decryptor::new_buffered(ciphertext)
+        .map_err(|e| SdlcError::AgeDecryptFailed(e.to_string()))?;
+    let mut reader = decryptor
+        .decrypt(identities.iter().map(|i| i.as_ref()))
+        .map_err(|e| SdlcError::AgeDecryptFailed(e.to_string()))?;
+    let mut plaintext = Vec::new();
+    {
+        use std::io::Read as _;
+        reader
+            .read_to_end(&mut plaintext)
+            .map_err(|e| SdlcError::AgeDecryptFailed(e.to_string()))?;
+    }
+    Ok(plaintext)
+}
+
 /// Decrypt an env file and return the KEY=VALUE content.
 pub fn export_env(root: &Path, env_name: &str, identity: &Path) -> Result<String> {
     let env_path = paths::secrets_env_path(root, env_name);
     if !env_path.exists() {
         return Err(SdlcError::SecretEnvNotFound(env_name.to_string()));
     }
-    let age_bin = age_bin()?;
-    let output = std::process::Command::new(&age_bin)
-        .args([
-            "--decrypt",
-            "--identity",
-            identity.to_str().unwrap_or(""),
-            env_path.to_str().unwrap_or(""),
-        ])
-        .output()
-        .map_err(|e| SdlcError::AgeDecryptFailed(e.to_string()))?;
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        return Err(SdlcError::AgeDecryptFailed(stderr.trim().to_string()));
-    }
-    String::from_utf8(output.stdout).map_err(|e| SdlcError::AgeDecryptFailed(e.to_string()))
+    let ciphertext = std::fs::read(&env_path)?;
+    let plaintext = decrypt(&ciphertext, identity)?;
+    String::from_utf8(plaintext).map_err(|e| SdlcError::AgeDecryptFailed(e.to_string()))
 }
 
 /// Encrypt `content` (KEY=VALUE text) to all current recipients and write the env file.
@@ -268,52 +340,11 @@ pub fn write_env(root: &Path, env_name: &str, content: &str, keys: &[SecretsKey]
             "no recipients configured — add a key with `sdlc secrets keys add`".to_string(),
         ));
     }
-    let age_bin = age_bin()?;
-
-    // Write recipient public keys to a temporary file accepted by `age -R`.
-    let recipients_content = keys
-        .iter()
-        .map(|k| k.public_key.as_str())
-        .collect::<Vec<_>>()
-        .join("\n");
-    let tmp =
-        tempfile::NamedTempFile::new().map_err(|e| SdlcError::AgeEncryptFailed(e.to_string()))?;
-    std::fs::write(tmp.path(), recipients_content.as_bytes())
-        .map_err(|e| SdlcError::AgeEncryptFailed(e.to_string()))?;
+    let ciphertext = encrypt(content.as_bytes(), keys)?;
 
     let env_path = paths::secrets_env_path(root, env_name);
     io::ensure_dir(&paths::secrets_envs_dir(root))?;
-
-    let mut cmd = std::process::Command::new(&age_bin);
-    cmd.args([
-        "--encrypt",
-        "--recipients-file",
-        tmp.path().to_str().unwrap_or(""),
-        "--output",
-        env_path.to_str().unwrap_or(""),
-    ]);
-    cmd.stdin(std::process::Stdio::piped());
-    cmd.stdout(std::process::Stdio::null());
-    cmd.stderr(std::process::Stdio::piped());
-
-    let mut child = cmd
-        .spawn()
-        .map_err(|e| SdlcError::AgeEncryptFailed(e.to_string()))?;
-
-    if let Some(mut stdin) = child.stdin.take() {
-        use std::io::Write as _;
-        stdin
-            .write_all(content.as_bytes())
-            .map_err(|e| SdlcError::AgeEncryptFailed(e.to_string()))?;
-    }
-
-    let output = child
-        .wait_with_output()
-        .map_err(|e| SdlcError::AgeEncryptFailed(e.to_string()))?;
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        return Err(SdlcError::AgeEncryptFailed(stderr.trim().to_string()));
-    }
+    io::atomic_write(&env_path, &ciphertext)?;
 
     // Update the sidecar with key names (not values).
     save_env_meta(
@@ -598,6 +629,88 @@ mod tests {
         assert!(matches!(result, Err(SdlcError::SecretEnvNotFound(_))));
     }
 
+    /// Generate a native age keypair, register the public key under `name`,
+    /// and write the private key to an identity file. Returns the identity path.
+    fn add_age_identity(dir: &TempDir, name: &str) -> PathBuf {
+        use age::secrecy::ExposeSecret;
+        let identity = age::x25519::Identity::generate();
+        let public_key = identity.to_public().to_string();
+        add_key(dir.path(), name, KeyType::infer(&public_key), &public_key).unwrap();
+        let path = dir.path().join(format!("{name}.key"));
+        std::fs::write(&path, identity.to_string().expose_secret()).unwrap();
+        path
+    }
+
+    #[test]
+    fn encrypt_to_two_recipients_decrypts_with_each() {
+        let dir = TempDir::new().unwrap();
+        let alice = add_age_identity(&dir, "alice");
+        let bob = add_age_identity(&dir, "bob");
+        let keys = list_keys(dir.path()).unwrap();
+        assert!(keys.iter().all(|k| k.key_type == KeyType::Age));
+
+        write_env(dir.path(), "production", "FOO=bar\nBAZ=qux\n", &keys).unwrap();
+
+        let ciphertext = std::fs::read(paths::secrets_env_path(dir.path(), "production")).unwrap();
+        assert!(!String::from_utf8_lossy(&ciphertext).contains("FOO=bar"));
+        for identity in [&alice, &bob] {
+            let content = export_env(dir.path(), "production", identity).unwrap();
+            assert_eq!(content, "FOO=bar\nBAZ=qux\n");
+        }
+        let meta = load_env_meta(dir.path(), "production").unwrap();
+        assert_eq!(meta.key_names, vec!["FOO", "BAZ"]);
+    }
+
+    #[test]
+    fn decrypt_with_unauthorized_identity_fails() {
+        let dir = TempDir::new().unwrap();
+        add_age_identity(&dir, "alice");
+        let keys = list_keys(dir.path()).unwrap();
+        write_env(dir.path(), "staging", "A=1\n", &keys).unwrap();
+
+        let outsider = dir.path().join("outsider.key");
+        {
+            use age::secrecy::ExposeSecret;
+            let id = age::x25519::Identity::generate();
+            std::fs::write(&outsider, id.to_string().expose_secret()).unwrap();
+        }
+        let result = export_env(dir.path(), "staging", &outsider);
+        assert!(matches!(result, Err(SdlcError::AgeDecryptFailed(_))));
+    }
+
+    #[test]
+    fn rekey_after_adding_key_grants_access() {
+        let dir = TempDir::new().unwrap();
+        let alice = add_age_identity(&dir, "alice");
+        set_env_pairs(
+            dir.path(),
+            "production",
+            &[("TOKEN".to_string(), "s3cret".to_string())],
+            None,
+        )
+        .unwrap();
+
+        let carol = add_age_identity(&dir, "carol");
+        // Carol was added after encryption — she cannot read the env yet.
+        assert!(export_env(dir.path(), "production", &carol).is_err());
+
+        let rekeyed = rekey(dir.path(), &alice).unwrap();
+        assert_eq!(rekeyed, vec!["production"]);
+        for identity in [&alice, &carol] {
+            let content = export_env(dir.path(), "production", identity).unwrap();
+            assert_eq!(content, "TOKEN=s3cret\n");
+        }
+    }
+
+    #[test]
+    fn write_env_rejects_invalid_recipient() {
+        let dir = TempDir::new().unwrap();
+        add_key(dir.path(), "bogus", KeyType::Age, "age1notakey").unwrap();
+        let keys = list_keys(dir.path()).unwrap();
+        let result = write_env(dir.path(), "production", "A=1\n", &keys);
+        assert!(matches!(result, Err(SdlcError::AgeEncryptFailed(_))));
+    }
+
     #[test]
     fn export_env_missing_fails() {
         let dir = TempDir::new().unwrap();
@@ -0,0 +1,303 @@
+//! Pluggable sources for secret env values.
+//!
+//! Defines the `SecretsBackend` trait so commands like `sdlc secrets env
+//! export` read values the same way whether they live in age-encrypted files
+//! under `.sdlc/secrets/` (the default) or in an external store. The backend
+//! is chosen by `secrets.backend` in `.sdlc/config.yaml`; see [`open`].
+//!
+//! Shipped backends:
+//! - [`AgeFileBackend`] — the encrypted env files managed by [`crate::secrets`].
+//! - [`EnvVarBackend`] — read-only; values come from `SDLC_SECRET_<ENV>__<KEY>`
+//!   process variables, e.g. `SDLC_SECRET_PRODUCTION__DATABASE_URL`.
+
+use crate::config::{Config, SecretsBackendKind};
+use crate::error::{Result, SdlcError};
+use crate::secrets;
+use std::path::{Path, PathBuf};
+
+/// Pluggable store for named environments of KEY=VALUE secrets.
+///
+/// All methods are synchronous — callers in async contexts must wrap calls
+/// with `tokio::task::spawn_blocking`.
+pub trait SecretsBackend: Send + Sync {
+    /// Backend name as written in config (`age`, `env`).
+    fn name(&self) -> &'static str;
+
+    /// The full KEY=VALUE content of `env`, one pair per line.
+    fn get(&self, env: &str) -> Result<String>;
+
+    /// Key names defined in `env`, without their values.
+    fn list_names(&self, env: &str) -> Result<Vec<String>>;
+
+    /// Merge `pairs` into `env`, creating it if needed. New values win.
+    fn set(&self, env: &str, pairs: &[(String, String)]) -> Result<()>;
+}
+
+/// Open the backend configured for the project at `root`. `identity` is the
+/// private key the age backend decrypts with, falling back to
+/// [`secrets::default_identity`]; other backends ignore it.
+pub fn open(root: &Path, identity: Option<PathBuf>) -> Result<Box<dyn SecretsBackend>> {
+    let kind = Config::load(root)?
+        .secrets
+        .map(|s| s.backend)
+        .unwrap_or_default();
+    Ok(match kind {
+        SecretsBackendKind::Age => Box::new(AgeFileBackend::new(
+            root,
+            identity.or_else(secrets::default_identity),
+        )),
+        SecretsBackendKind::Env => Box::new(EnvVarBackend::from_process()),
+    })
+}
+
+// ---------------------------------------------------------------------------
+// Age files
+// ---------------------------------------------------------------------------
+
+/// Age-encrypted env files in `.sdlc/secrets/envs/`.
+pub struct AgeFileBackend {
+    root: PathBuf,
+    identity: Option<PathBuf>,
+}
+
+impl AgeFileBackend {
+    pub fn new(root: &Path, identity: Option<PathBuf>) -> Self {
+        Self {
+            root: root.to_path_buf(),
+            identity,
+        }
+    }
+
+    fn identity(&self) -> Result<&Path> {
+        self.identity.as_deref().ok_or_else(|| {
+            SdlcError::AgeDecryptFailed(
+                "no identity key found — use --identity <path> or set \
+                 SDLC_AGE_IDENTITY (tried ~/.ssh/id_ed25519 and ~/.ssh/id_rsa)"
+                    .to_string(),
+            )
+        })
+    }
+}
+
+impl SecretsBackend for AgeFileBackend {
+    fn name(&self) -> &'static str {
+        "age"
+    }
+
+    fn get(&self, env: &str) -> Result<String> {
+        secrets::export_env(&self.root, env, self.identity()?)
+    }
+
+    /// Read from the `.meta.yaml` sidecar, so no identity is needed.
+    fn list_names(&self, env: &str) -> Result<Vec<String>> {
+        Ok(secrets::load_env_meta(&self.root, env)?.key_names)
+    }
+
+    fn set(&self, env: &str, pairs: &[(String, String)]) -> Result<()> {
+        secrets::set_env_pairs(&self.root, env, pairs, self.identity.as_deref())
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Process environment
+// ---------------------------------------------------------------------------
+
+const ENV_VAR_PREFIX: &str = "SDLC_SECRET_";
+
+/// Read-only secrets from `SDLC_SECRET_<ENV>__<KEY>` variables. The env name
+/// is upper-cased with `-` mapped to `_`, so `staging-eu` reads
+/// `SDLC_SECRET_STAGING_EU__*`. The double underscore keeps `staging` from
+/// matching `staging-eu`'s variables; for the same reason a key may not
+/// itself contain `__`.
+pub struct EnvVarBackend {
+    vars: Vec<(String, String)>,
+}
+
+impl EnvVarBackend {
+    /// Snapshot of the current process environment.
+    pub fn from_process() -> Self {
+        Self::from_vars(std::env::vars())
+    }
+
+    pub fn from_vars(vars: impl IntoIterator<Item = (String, String)>) -> Self {
+        let mut vars: Vec<_> = vars
+            .into_iter()
+            .filter(|(k, _)| k.starts_with(ENV_VAR_PREFIX))
+            .collect();
+        vars.sort();
+        Self { vars }
+    }
+
+    /// `(KEY, value)` pairs for `env`, sorted by key.
+    fn pairs(&self, env: &str) -> Result<Vec<(&str, &str)>> {
+        let prefix = format!(
+            "{ENV_VAR_PREFIX}{}__",
+            env.to_ascii_uppercase().replace('-', "_")
+        );
+        let pairs: Vec<_> = self
+            .vars
+            .iter()
+            .filter_map(|(k, v)| {
+                let key = k.strip_prefix(&prefix)?;
+                (!key.is_empty() && !key.contains("__")).then_some((key, v.as_str()))
+            })
+            .collect();
+        if pairs.is_empty() {
+            return Err(SdlcError::SecretEnvNotFound(env.to_string()));
+        }
+        Ok(pairs)
+    }
+}
+
+impl SecretsBackend for EnvVarBackend {
+    fn name(&self) -> &'static str {
+        "env"
+    }
+
+    fn get(&self, env: &str) -> Result<String> {
+        Ok(self
+            .pairs(env)?
+            .into_iter()
+            .map(|(k, v)| format!("{k}={v}\n"))
+            .collect())
+    }
+
+    fn list_names(&self, env: &str) -> Result<Vec<String>> {
+        Ok(self
+            .pairs(env)?
+            .into_iter()
+            .map(|(k, _)| k.to_string())
+            .collect())
+    }
+
+    fn set(&self, _env: &str, _pairs: &[(String, String)]) -> Result<()> {
+        Err(SdlcError::SecretsBackendReadOnly(self.name().to_string()))
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::secrets::KeyType;
+    use tempfile::TempDir;
+
+    /// A project with one age recipient; returns the matching identity file.
+    fn age_project(dir: &TempDir) -> PathBuf {
+        use age::secrecy::ExposeSecret;
+        let root = dir.path();
+        Config::new("test").save(root).unwrap();
+        let identity = age::x25519::Identity::generate();
+        let public_key = identity.to_public().to_string();
+        secrets::add_key(root, "me", KeyType::infer(&public_key), &public_key).unwrap();
+        let path = root.join("me.key");
+        std::fs::write(&path, identity.to_string().expose_secret()).unwrap();
+        path
+    }
+
+    fn pairs(kv: &[(&str, &str)]) -> Vec<(String, String)> {
+        kv.iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn age_backend_round_trips_through_the_trait() {
+        let dir = TempDir::new().unwrap();
+        let id = age_project(&dir);
+        let backend = open(dir.path(), Some(id)).unwrap();
+        assert_eq!(backend.name(), "age");
+
+        backend
+            .set(
+                "production",
+                &pairs(&[("DB_URL", "postgres://x"), ("TOKEN", "t1")]),
+            )
+            .unwrap();
+        backend
+            .set("production", &pairs(&[("TOKEN", "t2")]))
+            .unwrap();
+
+        assert_eq!(
+            backend.get("production").unwrap(),
+            "DB_URL=postgres://x\nTOKEN=t2\n"
+        );
+        assert_eq!(
+            backend.list_names("production").unwrap(),
+            ["DB_URL", "TOKEN"]
+        );
+        assert!(matches!(
+            backend.get("staging"),
+            Err(SdlcError::SecretEnvNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn age_backend_get_without_identity_fails() {
+        let dir = TempDir::new().unwrap();
+        age_project(&dir);
+        let backend = AgeFileBackend::new(dir.path(), None);
+        backend.set("production", &pairs(&[("A", "1")])).unwrap();
+        assert_eq!(backend.list_names("production").unwrap(), ["A"]);
+        let err = backend.get("production").unwrap_err();
+        assert!(matches!(err, SdlcError::AgeDecryptFailed(_)));
+        assert!(err.to_string().contains("tried ~/.ssh/id_ed25519"), "{err}");
+    }
+
+    #[test]
+    fn env_backend_reads_prefixed_variables_and_is_read_only() {
+        let backend = EnvVarBackend::from_vars(pairs(&[
+            ("SDLC_SECRET_PRODUCTION__TOKEN", "t"),
+            ("SDLC_SECRET_PRODUCTION__DB_URL", "postgres://x"),
+            ("SDLC_SECRET_STAGING_EU__TOKEN", "eu"),
+            ("SDLC_SECRET_PRODUCTION_TOKEN", "single underscore"),
+            ("HOME", "/root"),
+        ]));
+        let backend: &dyn SecretsBackend = &backend;
+
+        assert_eq!(
+            backend.get("production").unwrap(),
+            "DB_URL=postgres://x\nTOKEN=t\n"
+        );
+        assert_eq!(backend.list_names("staging-eu").unwrap(), ["TOKEN"]);
+        assert!(matches!(
+            backend.get("dev"),
+            Err(SdlcError::SecretEnvNotFound(_))
+        ));
+        assert!(matches!(
+            backend.set("production", &pairs(&[("A", "1")])),
+            Err(SdlcError::SecretsBackendReadOnly(_))
+        ));
+    }
+
+    #[test]
+    fn env_backend_keeps_prefix_sharing_envs_apart() {
+        let backend = EnvVarBackend::from_vars(pairs(&[
+            ("SDLC_SECRET_STAGING__TOKEN", "staging"),
+            ("SDLC_SECRET_STAGING_EU__TOKEN", "eu"),
+            ("SDLC_SECRET_STAGING_EU__REGION", "eu-west-1"),
+        ]));
+
+        assert_eq!(backend.get("staging").unwrap(), "TOKEN=staging\n");
+        assert_eq!(
+            backend.get("staging-eu").unwrap(),
+            "REGION=eu-west-1\nTOKEN=eu\n"
+        );
+        assert_eq!(backend.list_names("staging").unwrap(), ["TOKEN"]);
+    }
+
+    #[test]
+    fn config_selects_the_backend() {
+        let dir = TempDir::new().unwrap();
+        let mut config = Config::new("test");
+        config.secrets = Some(crate::config::SecretsBackendConfig {
+            backend: SecretsBackendKind::Env,
+            ..Default::default()
+        });
+        config.save(dir.path()).unwrap();
+        assert_eq!(open(dir.path(), None).unwrap().name(), "env");
+    }
+}
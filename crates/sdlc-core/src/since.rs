@@ -0,0 +1,105 @@
+//! `--since` cutoffs for history queries.
+//!
+//! Accepts a relative duration counted back from now — `30s`, `15m`, `2h`,
+//! `3d`, `1w` — or an absolute ISO-8601 time: a full RFC 3339 timestamp,
+//! `YYYY-MM-DDTHH:MM:SS` (taken as UTC), or a bare `YYYY-MM-DD` (midnight
+//! UTC).
+
+use crate::error::{Result, SdlcError};
+use chrono::{DateTime, Duration, NaiveDate, NaiveDateTime, Utc};
+
+/// Resolve `input` to a cutoff, with relative durations counted back from
+/// `now`.
+pub fn parse_since(input: &str, now: DateTime<Utc>) -> Result<DateTime<Utc>> {
+    let s = input.trim();
+    let invalid = || SdlcError::InvalidSince(input.to_string());
+
+    if let Some(duration) = parse_relative(s) {
+        return now.checked_sub_signed(duration?).ok_or_else(invalid);
+    }
+    if let Ok(dt) = DateTime::parse_from_rfc3339(s) {
+        return Ok(dt.with_timezone(&Utc));
+    }
+    if let Ok(dt) = NaiveDateTime::parse_from_str(s, "%Y-%m-%dT%H:%M:%S") {
+        return Ok(dt.and_utc());
+    }
+    if let Ok(date) = NaiveDate::parse_from_str(s, "%Y-%m-%d") {
+        return Ok(date
+            .and_hms_opt(0, 0, 0)
+            .expect("midnight always valid")
+            .and_utc());
+    }
+    Err(invalid())
+}
+
+/// `Some` when `s` looks like `<digits><unit>`; the inner result is an error
+/// for an out-of-range count.
+fn parse_relative(s: &str) -> Option<Result<Duration>> {
+    let unit = s.chars().last()?;
+    let count = &s[..s.len() - unit.len_utf8()];
+    if count.is_empty() || !count.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let make: fn(i64) -> Option<Duration> = match unit {
+        's' => Duration::try_seconds,
+        'm' => Duration::try_minutes,
+        'h' => Duration::try_hours,
+        'd' => Duration::try_days,
+        'w' => Duration::try_weeks,
+        _ => return None,
+    };
+    Some(
+        count
+            .parse()
+            .ok()
+            .and_then(make)
+            .ok_or_else(|| SdlcError::InvalidSince(s.to_string())),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn now() -> DateTime<Utc> {
+        "2026-03-10T12:00:00Z".parse().unwrap()
+    }
+
+    #[test]
+    fn relative_durations_count_back_from_now() {
+        for (input, expected) in [
+            ("30s", "2026-03-10T11:59:30Z"),
+            ("15m", "2026-03-10T11:45:00Z"),
+            ("2h", "2026-03-10T10:00:00Z"),
+            ("3d", "2026-03-07T12:00:00Z"),
+            ("1w", "2026-03-03T12:00:00Z"),
+            (" 0h ", "2026-03-10T12:00:00Z"),
+        ] {
+            let expected: DateTime<Utc> = expected.parse().unwrap();
+            assert_eq!(parse_since(input, now()).unwrap(), expected, "{input}");
+        }
+    }
+
+    #[test]
+    fn absolute_timestamps_parse_as_utc() {
+        let cases = [
+            ("2026-03-01T08:30:00Z", "2026-03-01T08:30:00Z"),
+            ("2026-03-01T10:30:00+02:00", "2026-03-01T08:30:00Z"),
+            ("2026-03-01T08:30:00", "2026-03-01T08:30:00Z"),
+            ("2026-03-01", "2026-03-01T00:00:00Z"),
+        ];
+        for (input, expected) in cases {
+            let expected: DateTime<Utc> = expected.parse().unwrap();
+            assert_eq!(parse_since(input, now()).unwrap(), expected, "{input}");
+        }
+    }
+
+    #[test]
+    fn malformed_values_are_rejected() {
+        for input in ["", "h", "2x", "-2h", "2.5h", "yesterday", "2026-13-01"] {
+            let err = parse_since(input, now()).unwrap_err();
+            assert!(matches!(err, SdlcError::InvalidSince(_)), "{input}: {err}");
+        }
+        assert!(parse_since("99999999999999d", now()).is_err());
+    }
+}
@@ -0,0 +1,151 @@
+//! Canonical slug derivation.
+//!
+//! Every surface that turns a human title into a slug — feature, milestone,
+//! and ponder creation, backlog promotion, knowledge entries — goes through
+//! [`derive`] so the same title always produces the same slug.
+//!
+//! Rules, applied in order:
+//!   1. Unicode compatibility decomposition (NFKD), dropping combining marks,
+//!      so `Café` becomes `cafe` and `ﬁle` becomes `file`.
+//!   2. Lowercase.
+//!   3. Every character other than `a-z` / `0-9` is a separator.
+//!   4. Runs of separators collapse to a single `-`; leading and trailing
+//!      separators are dropped.
+//!   5. Slugs longer than [`MAX_LEN`] are cut at the last `-` within the
+//!      limit, or hard-cut at the limit when there is none.
+//!
+//! The output is either empty (no alphanumeric input) or passes
+//! [`crate::paths::validate_slug`]. `derive` is idempotent.
+
+use unicode_normalization::char::is_combining_mark;
+use unicode_normalization::UnicodeNormalization;
+
+/// Longest slug `derive` will produce.
+pub const MAX_LEN: usize = 40;
+
+/// Derive a slug from free-form text. See the module docs for the rules.
+pub fn derive(text: &str) -> String {
+    let mut slug = String::new();
+    let mut pending_dash = false;
+    for c in text.nfkd().filter(|c| !is_combining_mark(*c)) {
+        for c in c.to_lowercase() {
+            if c.is_ascii_alphanumeric() {
+                if pending_dash && !slug.is_empty() {
+                    slug.push('-');
+                }
+                pending_dash = false;
+                slug.push(c);
+            } else {
+                pending_dash = true;
+            }
+        }
+    }
+
+    if slug.len() <= MAX_LEN {
+        return slug;
+    }
+    // Output is pure ASCII, so byte indices are char boundaries.
+    let cut = &slug[..MAX_LEN];
+    match cut.rfind('-') {
+        Some(pos) => cut[..pos].to_string(),
+        None => cut.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::paths::validate_slug;
+
+    /// Inputs covering each documented rule plus the edge cases between them.
+    const CORPUS: &[&str] = &[
+        "",
+        "   ",
+        "---",
+        "Fix auth token race",
+        "Fix auth.rs: token race! (critical)",
+        "foo   bar",
+        "  leading and trailing  ",
+        "Café Crème Brûlée",
+        "ﬁle Ⅻ ＦＵＬＬＷＩＤＴＨ",
+        "Straße",
+        "日本語 title",
+        "emoji 🚀 launch",
+        "v2.0 -- release",
+        "a very long title that exceeds forty characters limit here",
+        "abcdefghijklmnopqrstuvwxyzabcdefghijklmnopqrstuvwxyz",
+        "exactly-forty-characters-long-slug-here1",
+        "forty-one-characters-long-slug-right-here",
+        "UPPER_snake_CASE",
+        "tabs\tand\nnewlines",
+        "x",
+    ];
+
+    #[test]
+    fn documented_examples() {
+        assert_eq!(derive("Fix auth token race"), "fix-auth-token-race");
+        assert_eq!(
+            derive("Fix auth.rs: token race! (critical)"),
+            "fix-auth-rs-token-race-critical"
+        );
+        assert_eq!(derive("foo   bar"), "foo-bar");
+        assert_eq!(derive("  --Leading & trailing--  "), "leading-trailing");
+        assert_eq!(derive("Café Crème Brûlée"), "cafe-creme-brulee");
+        assert_eq!(derive("ﬁle ＦＵＬＬ"), "file-full");
+        assert_eq!(derive("日本語"), "");
+        assert_eq!(
+            derive("a very long title that exceeds forty characters limit here"),
+            "a-very-long-title-that-exceeds-forty"
+        );
+        assert_eq!(derive(&"z".repeat(50)), "z".repeat(MAX_LEN));
+    }
+
+    #[test]
+    fn derive_is_idempotent() {
+        for input in CORPUS {
+            let once = derive(input);
+            assert_eq!(derive(&once), once, "not idempotent for {input:?}");
+        }
+    }
+
+    #[test]
+    fn output_is_a_valid_slug_or_empty() {
+        for input in CORPUS {
+            let slug = derive(input);
+            assert!(slug.len() <= MAX_LEN, "{slug:?} too long");
+            if !slug.is_empty() {
+                validate_slug(&slug).unwrap_or_else(|e| panic!("{input:?} -> {e}"));
+            }
+        }
+    }
+
+    #[test]
+    fn stable_across_documented_transformations() {
+        for input in CORPUS {
+            let expected = derive(input);
+            // Case, separator choice, repeated separators, and surrounding
+            // whitespace are all normalized away.
+            assert_eq!(derive(&input.to_lowercase()), expected, "case: {input:?}");
+            assert_eq!(
+                derive(&input.to_ascii_uppercase()),
+                expected,
+                "case: {input:?}"
+            );
+            assert_eq!(derive(&input.replace(' ', "_")), expected, "sep: {input:?}");
+            assert_eq!(
+                derive(&input.replace(' ', " - ")),
+                expected,
+                "run: {input:?}"
+            );
+            assert_eq!(
+                derive(&format!("  {input}!! ")),
+                expected,
+                "trim: {input:?}"
+            );
+            // Precomposed and decomposed forms of the same text agree.
+            let nfd: String = input.nfd().collect();
+            let nfc: String = input.nfc().collect();
+            assert_eq!(derive(&nfd), derive(&nfc), "normalization: {input:?}");
+        }
+    }
+}
@@ -1,9 +1,12 @@
 use crate::error::{Result, SdlcError};
+use crate::io::{self, FileLock};
 use crate::paths;
+use crate::state_store::{FileStateStore, StateStore};
 use crate::types::{ActionType, Phase};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::path::Path;
+use std::time::Duration;
 
 // ---------------------------------------------------------------------------
 // Supporting types
@@ -61,6 +64,9 @@ fn default_version() -> u32 {
     1
 }
 
+/// How long `State::lock` waits for another writer before giving up.
+pub const STATE_LOCK_TIMEOUT: Duration = Duration::from_secs(10);
+
 impl State {
     pub fn new(project: impl Into<String>) -> Self {
         Self {
@@ -80,38 +86,39 @@ impl State {
     // Persistence
     // ---------------------------------------------------------------------------
 
+    /// Load `.sdlc/state.yaml`; see [`FileStateStore`].
     pub fn load(root: &Path) -> Result<Self> {
-        let path = paths::state_path(root);
-        if !path.exists() {
+        FileStateStore::new(root).load()
+    }
+
+    pub fn save(&self, root: &Path) -> Result<()> {
+        FileStateStore::new(root).save(self)
+    }
+
+    /// Acquire `.sdlc/state.lock`. Hold the guard across a load → mutate →
+    /// save cycle so a concurrent writer (CLI vs server) cannot interleave.
+    pub fn lock(root: &Path) -> Result<FileLock> {
+        if !paths::state_path(root).exists() {
             return Err(SdlcError::NotInitialized);
         }
-        let path_display = path.display().to_string();
-        let data = std::fs::read_to_string(&path)?;
-
-        // Phase 1: parse raw YAML (catches syntax errors with path context).
-        let value: serde_yaml::Value =
-            serde_yaml::from_str(&data).map_err(|e| SdlcError::ManifestParseFailed {
-                path: path_display.clone(),
-                message: e.to_string(),
-            })?;
-
-        // Phase 2: typed deserialization with actionable error message.
-        // State has no structural migrations — #[serde(default)] covers all Vec fields.
-        let state: State =
-            serde_yaml::from_value(value).map_err(|e| SdlcError::ManifestIncompatible {
-                path: path_display.clone(),
-                entity: "State".to_string(),
-                message: e.to_string(),
-                fix_hint: crate::migrations::state_fix_hint(&e),
-            })?;
+        io::lock_file(&paths::state_lock_path(root), STATE_LOCK_TIMEOUT)
+    }
 
-        Ok(state)
+    /// Load, mutate, and save state under [`State::lock`].
+    pub fn update<T>(root: &Path, f: impl FnOnce(&mut State) -> T) -> Result<T> {
+        Self::update_in(&FileStateStore::new(root), f)
     }
 
-    pub fn save(&self, root: &Path) -> Result<()> {
-        let path = paths::state_path(root);
-        let data = serde_yaml::to_string(self)?;
-        crate::io::atomic_write(&path, data.as_bytes())
+    /// Load, mutate, and save state in one [`StateStore::transaction`].
+    pub fn update_in<T>(store: &dyn StateStore, f: impl FnOnce(&mut State) -> T) -> Result<T> {
+        let mut f = Some(f);
+        let mut out = None;
+        store.transaction(&mut |state| {
+            if let Some(f) = f.take() {
+                out = Some(f(state));
+            }
+        })?;
+        out.ok_or_else(|| SdlcError::Other("state transaction did not run".to_string()))
     }
 
     // ---------------------------------------------------------------------------
@@ -344,4 +351,30 @@ mod tests {
         state.complete_directive("auth");
         assert!(state.active_directives.is_empty());
     }
+
+    #[test]
+    fn concurrent_updates_are_not_lost() {
+        let dir = TempDir::new().unwrap();
+        State::new("proj").save(dir.path()).unwrap();
+
+        let handles: Vec<_> = (0..2)
+            .map(|t| {
+                let root = dir.path().to_path_buf();
+                std::thread::spawn(move || {
+                    for i in 0..20 {
+                        State::update(&root, |s| s.add_active_feature(&format!("f{t}-{i}")))
+                            .unwrap();
+                    }
+                })
+            })
+            .collect();
+        for h in handles {
+            h.join().unwrap();
+        }
+
+        let state = State::load(dir.path()).unwrap();
+        assert_eq!(state.active_features.len(), 40);
+        // Every guard released its lock.
+        io::lock_file(&paths::state_lock_path(dir.path()), Duration::ZERO).unwrap();
+    }
 }
@@ -0,0 +1,237 @@
+//! Pluggable persistence for [`State`].
+//!
+//! Defines the `StateStore` trait so the domain logic in [`crate::state`]
+//! reads and writes project state the same way whether it lives in
+//! `.sdlc/state.yaml` (the default) or in a shared database. `State::load`,
+//! `State::save`, and `State::update` go through [`FileStateStore`];
+//! [`State::update_in`] takes any store.
+//!
+//! Shipped stores:
+//! - [`FileStateStore`] — `.sdlc/state.yaml`, written atomically and
+//!   serialized across processes by `.sdlc/state.lock`.
+//! - [`MemoryStateStore`] — process-local, for tests and embedding.
+
+use crate::error::{Result, SdlcError};
+use crate::io;
+use crate::paths;
+use crate::state::{State, STATE_LOCK_TIMEOUT};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// Where project state is persisted.
+///
+/// All methods are synchronous — callers in async contexts must wrap calls
+/// with `tokio::task::spawn_blocking`.
+pub trait StateStore: Send + Sync {
+    /// The stored state; [`SdlcError::NotInitialized`] when there is none.
+    fn load(&self) -> Result<State>;
+
+    /// Replace the stored state.
+    fn save(&self, state: &State) -> Result<()>;
+
+    /// Load, apply `f`, and save as one step that no other writer to the
+    /// same store can interleave with. Nothing is saved if loading fails.
+    fn transaction(&self, f: &mut dyn FnMut(&mut State)) -> Result<()>;
+}
+
+// ---------------------------------------------------------------------------
+// FileStateStore
+// ---------------------------------------------------------------------------
+
+/// `.sdlc/state.yaml` under a project root.
+#[derive(Debug, Clone)]
+pub struct FileStateStore {
+    root: PathBuf,
+}
+
+impl FileStateStore {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+}
+
+impl StateStore for FileStateStore {
+    fn load(&self) -> Result<State> {
+        let path = paths::state_path(&self.root);
+        if !path.exists() {
+            return Err(SdlcError::NotInitialized);
+        }
+        let path_display = path.display().to_string();
+        let data = std::fs::read_to_string(&path)?;
+
+        // Phase 1: parse raw YAML (catches syntax errors with path context).
+        let value: serde_yaml::Value =
+            serde_yaml::from_str(&data).map_err(|e| SdlcError::ManifestParseFailed {
+                path: path_display.clone(),
+                message: e.to_string(),
+            })?;
+
+        // Phase 2: typed deserialization with actionable error message.
+        // State has no structural migrations — #[serde(default)] covers all Vec fields.
+        let state: State =
+            serde_yaml::from_value(value).map_err(|e| SdlcError::ManifestIncompatible {
+                path: path_display.clone(),
+                entity: "State".to_string(),
+                message: e.to_string(),
+                fix_hint: crate::migrations::state_fix_hint(&e),
+            })?;
+
+        Ok(state)
+    }
+
+    fn save(&self, state: &State) -> Result<()> {
+        let path = paths::state_path(&self.root);
+        let data = serde_yaml::to_string(state)?;
+        io::atomic_write(&path, data.as_bytes())
+    }
+
+    fn transaction(&self, f: &mut dyn FnMut(&mut State)) -> Result<()> {
+        if !paths::state_path(&self.root).exists() {
+            return Err(SdlcError::NotInitialized);
+        }
+        let _lock = io::lock_file(&paths::state_lock_path(&self.root), STATE_LOCK_TIMEOUT)?;
+        let mut state = self.load()?;
+        f(&mut state);
+        self.save(&state)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// MemoryStateStore
+// ---------------------------------------------------------------------------
+
+/// State held in memory; starts empty (uninitialized) unless seeded.
+#[derive(Debug, Default)]
+pub struct MemoryStateStore {
+    state: Mutex<Option<State>>,
+}
+
+impl MemoryStateStore {
+    pub fn new(state: State) -> Self {
+        Self {
+            state: Mutex::new(Some(state)),
+        }
+    }
+
+    fn slot(&self) -> std::sync::MutexGuard<'_, Option<State>> {
+        // A panicking transaction leaves the previous state in place.
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl StateStore for MemoryStateStore {
+    fn load(&self) -> Result<State> {
+        self.slot().clone().ok_or(SdlcError::NotInitialized)
+    }
+
+    fn save(&self, state: &State) -> Result<()> {
+        *self.slot() = Some(state.clone());
+        Ok(())
+    }
+
+    fn transaction(&self, f: &mut dyn FnMut(&mut State)) -> Result<()> {
+        let mut slot = self.slot();
+        let mut state = slot.clone().ok_or(SdlcError::NotInitialized)?;
+        f(&mut state);
+        *slot = Some(state);
+        Ok(())
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{ActionType, Phase};
+    use std::sync::Arc;
+    use tempfile::TempDir;
+
+    /// The state operations the CLI and server perform, run against `store`.
+    fn exercise(store: &dyn StateStore) {
+        assert!(matches!(store.load(), Err(SdlcError::NotInitialized)));
+        assert!(matches!(
+            State::update_in(store, |s| s.add_milestone("v1")),
+            Err(SdlcError::NotInitialized)
+        ));
+
+        let mut state = State::new("proj");
+        state.add_active_feature("auth");
+        store.save(&state).unwrap();
+
+        let history_len = State::update_in(store, |s| {
+            s.record_action("auth", ActionType::CreateSpec, Phase::Draft, "ok");
+            s.issue_directive("auth", ActionType::ImplementTask);
+            s.set_blocked("billing", "waiting on auth");
+            s.add_milestone("v1");
+            s.add_ponder("idea");
+            s.history.len()
+        })
+        .unwrap();
+        assert_eq!(history_len, 1);
+
+        let loaded = store.load().unwrap();
+        assert_eq!(loaded.project, "proj");
+        assert_eq!(loaded.active_features, vec!["auth".to_string()]);
+        assert_eq!(loaded.last_action().unwrap().feature, "auth");
+        assert_eq!(loaded.active_directives.len(), 1);
+        assert_eq!(loaded.blocked.len(), 1);
+        assert_eq!(loaded.milestones, vec!["v1".to_string()]);
+        assert_eq!(loaded.active_ponders, vec!["idea".to_string()]);
+
+        State::update_in(store, |s| {
+            s.complete_directive("auth");
+            s.clear_blocked("billing");
+            s.remove_milestone("v1");
+            s.remove_ponder("idea");
+            s.remove_active_feature("auth");
+        })
+        .unwrap();
+        let loaded = store.load().unwrap();
+        assert!(loaded.active_directives.is_empty());
+        assert!(loaded.blocked.is_empty());
+        assert!(loaded.milestones.is_empty());
+        assert!(loaded.active_ponders.is_empty());
+        assert!(loaded.active_features.is_empty());
+    }
+
+    #[test]
+    fn state_operations_hold_on_the_memory_store() {
+        exercise(&MemoryStateStore::default());
+    }
+
+    #[test]
+    fn state_operations_hold_on_the_file_store() {
+        let dir = TempDir::new().unwrap();
+        std::fs::create_dir_all(dir.path().join(".sdlc")).unwrap();
+        exercise(&FileStateStore::new(dir.path()));
+    }
+
+    #[test]
+    fn memory_transactions_are_not_lost_under_contention() {
+        let store = Arc::new(MemoryStateStore::new(State::new("proj")));
+        let handles: Vec<_> = (0..4)
+            .map(|t| {
+                let store = Arc::clone(&store);
+                std::thread::spawn(move || {
+                    for i in 0..25 {
+                        State::update_in(store.as_ref(), |s| {
+                            s.add_active_feature(&format!("f{t}-{i}"))
+                        })
+                        .unwrap();
+                    }
+                })
+            })
+            .collect();
+        for h in handles {
+            h.join().unwrap();
+        }
+        assert_eq!(store.load().unwrap().active_features.len(), 100);
+    }
+}
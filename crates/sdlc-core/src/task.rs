@@ -1,7 +1,11 @@
+use crate::artifact::ChecklistItem;
 use crate::error::{Result, SdlcError};
+use crate::feature::Feature;
 use crate::types::TaskStatus;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::Path;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Task {
@@ -11,6 +15,10 @@ pub struct Task {
     pub description: Option<String>,
     #[serde(default)]
     pub status: TaskStatus,
+    /// Execution position; lower runs first. Ties (e.g. tasks written before
+    /// ordering existed, all `0`) fall back to creation time.
+    #[serde(default)]
+    pub order: u32,
     #[serde(default = "Utc::now")]
     pub created_at: DateTime<Utc>,
     #[serde(default)]
@@ -30,6 +38,7 @@ impl Task {
             title: title.into(),
             description: None,
             status: TaskStatus::Pending,
+            order: 0,
             created_at: Utc::now(),
             started_at: None,
             completed_at: None,
@@ -43,12 +52,64 @@ impl Task {
 // Task list operations (operate on a mutable Vec<Task>)
 // ---------------------------------------------------------------------------
 
+/// Append a task. It is ordered after every existing task and numbered past
+/// the highest existing `T<n>` ID, so gaps left by removed tasks are never
+/// reused.
 pub fn add_task(tasks: &mut Vec<Task>, title: impl Into<String>) -> String {
-    let id = format!("T{}", tasks.len() + 1);
-    tasks.push(Task::new(id.clone(), title));
+    let next = tasks
+        .iter()
+        .filter_map(|t| t.id.strip_prefix('T')?.parse::<u32>().ok())
+        .max()
+        .map_or(1, |n| n + 1);
+    let id = format!("T{next}");
+    let mut task = Task::new(id.clone(), title);
+    task.order = tasks.iter().map(|t| t.order).max().map_or(0, |o| o + 1);
+    tasks.push(task);
     id
 }
 
+/// Sort tasks into execution order: by `order`, then creation time.
+pub fn sort_by_order(tasks: &mut [Task]) {
+    tasks.sort_by(|a, b| a.order.cmp(&b.order).then(a.created_at.cmp(&b.created_at)));
+}
+
+/// Replace the task order with `ordered`. Every task ID must appear exactly
+/// once. Tasks are renumbered `0..n` and the list is sorted to match.
+pub fn reorder_tasks(tasks: &mut [Task], ordered: &[&str]) -> Result<()> {
+    let mut seen = HashSet::new();
+    for &id in ordered {
+        if !seen.insert(id) {
+            return Err(SdlcError::InvalidTaskOrder(format!(
+                "duplicate task id in order list: '{id}'"
+            )));
+        }
+        if !tasks.iter().any(|t| t.id == id) {
+            return Err(SdlcError::InvalidTaskOrder(format!(
+                "'{id}' is not a task of this feature"
+            )));
+        }
+    }
+    if let Some(missing) = tasks.iter().find(|t| !seen.contains(t.id.as_str())) {
+        return Err(SdlcError::InvalidTaskOrder(format!(
+            "missing task id in order list: '{}'",
+            missing.id
+        )));
+    }
+
+    for task in tasks.iter_mut() {
+        task.order = ordered.iter().position(|&id| id == task.id).unwrap_or(0) as u32;
+    }
+    sort_by_order(tasks);
+    Ok(())
+}
+
+/// Set the execution order of a feature's tasks and persist it.
+pub fn reorder(root: &Path, slug: &str, ordered: &[&str]) -> Result<()> {
+    let mut feature = Feature::load(root, slug)?;
+    reorder_tasks(&mut feature.tasks, ordered)?;
+    feature.save(root)
+}
+
 pub fn start_task(tasks: &mut [Task], id: &str) -> Result<()> {
     let task = find_mut(tasks, id)?;
     task.status = TaskStatus::InProgress;
@@ -104,6 +165,118 @@ pub fn summarize(tasks: &[Task]) -> String {
     format!("{done}/{total} completed, {in_progress} in progress, {blocked} blocked")
 }
 
+/// Normalize a task title for matching: lowercase alphanumeric words joined
+/// by single spaces, so punctuation and spacing differences don't matter.
+pub fn normalize_title(title: &str) -> String {
+    title
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .map(|w| w.to_lowercase())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Create a task for every checklist item that has no task with the same
+/// normalized title. Items already checked off are created as completed.
+/// Returns the IDs of the new tasks; re-running with the same items is a no-op.
+pub fn sync_from_checklist(tasks: &mut Vec<Task>, items: &[ChecklistItem]) -> Vec<String> {
+    let mut known: HashSet<String> = tasks.iter().map(|t| normalize_title(&t.title)).collect();
+    let mut created = Vec::new();
+    for item in items {
+        if !known.insert(normalize_title(&item.title)) {
+            continue;
+        }
+        let id = add_task(tasks, item.title.clone());
+        if item.checked {
+            if let Some(task) = tasks.last_mut() {
+                task.status = TaskStatus::Completed;
+                task.completed_at = Some(Utc::now());
+            }
+        }
+        created.push(id);
+    }
+    created
+}
+
+// ---------------------------------------------------------------------------
+// Bulk import
+// ---------------------------------------------------------------------------
+
+/// One task to create with [`import_tasks`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskSpec {
+    pub title: String,
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportStatus {
+    Created,
+    /// A task with the same normalized title already existed (or appeared
+    /// earlier in the batch); `task_id` is that task.
+    Skipped,
+}
+
+/// What [`import_tasks`] did with one [`TaskSpec`].
+#[derive(Debug, Clone, Serialize)]
+pub struct TaskImport {
+    pub title: String,
+    pub task_id: String,
+    pub status: ImportStatus,
+}
+
+/// Create a task for every spec whose normalized title is not already taken,
+/// matching the plan idempotency contract: importing the same batch twice
+/// creates nothing the second time. Returns one result per spec, in order.
+///
+/// A blank title rejects the whole batch before any task is added.
+pub fn import_tasks(tasks: &mut Vec<Task>, specs: &[TaskSpec]) -> Result<Vec<TaskImport>> {
+    if let Some(i) = specs
+        .iter()
+        .position(|s| normalize_title(&s.title).is_empty())
+    {
+        return Err(SdlcError::InvalidTaskSpec(format!(
+            "task {} has an empty title",
+            i + 1
+        )));
+    }
+
+    let mut results = Vec::with_capacity(specs.len());
+    for spec in specs {
+        let key = normalize_title(&spec.title);
+        let existing = tasks.iter().find(|t| normalize_title(&t.title) == key);
+        let (task_id, status) = match existing {
+            Some(t) => (t.id.clone(), ImportStatus::Skipped),
+            None => {
+                let id = add_task(tasks, spec.title.trim());
+                if let Some(task) = tasks.last_mut() {
+                    task.description = spec.description.clone();
+                }
+                (id, ImportStatus::Created)
+            }
+        };
+        results.push(TaskImport {
+            title: spec.title.clone(),
+            task_id,
+            status,
+        });
+    }
+    Ok(results)
+}
+
+/// [`import_tasks`] into a feature, saved in one write when anything was
+/// created.
+pub fn import(root: &Path, slug: &str, specs: &[TaskSpec]) -> Result<Vec<TaskImport>> {
+    let mut feature = Feature::load(root, slug)?;
+    let results = import_tasks(&mut feature.tasks, specs)?;
+    if results.iter().any(|r| r.status == ImportStatus::Created) {
+        feature.save(root)?;
+    }
+    Ok(results)
+}
+
 fn find_mut<'a>(tasks: &'a mut [Task], id: &str) -> Result<&'a mut Task> {
     tasks
         .iter_mut()
@@ -162,4 +335,126 @@ mod tests {
         let next = next_task(&tasks).unwrap();
         assert_eq!(next.id, t2);
     }
+
+    #[test]
+    fn add_task_appends_after_reorder() {
+        let mut tasks: Vec<Task> = Vec::new();
+        add_task(&mut tasks, "First");
+        add_task(&mut tasks, "Second");
+        reorder_tasks(&mut tasks, &["T2", "T1"]).unwrap();
+
+        let id = add_task(&mut tasks, "Third");
+        sort_by_order(&mut tasks);
+        let ids: Vec<&str> = tasks.iter().map(|t| t.id.as_str()).collect();
+        assert_eq!(ids, vec!["T2", "T1", id.as_str()]);
+    }
+
+    #[test]
+    fn add_task_numbers_past_gaps() {
+        let mut tasks = vec![Task::new("T2", "Second"), Task::new("T3", "Third")];
+        assert_eq!(add_task(&mut tasks, "Fourth"), "T4");
+    }
+
+    #[test]
+    fn reorder_rejects_incomplete_or_unknown_ids() {
+        let mut tasks: Vec<Task> = Vec::new();
+        add_task(&mut tasks, "First");
+        add_task(&mut tasks, "Second");
+        for bad in [&["T1"][..], &["T1", "T2", "T9"], &["T1", "T1", "T2"]] {
+            assert!(matches!(
+                reorder_tasks(&mut tasks, bad),
+                Err(SdlcError::InvalidTaskOrder(_))
+            ));
+        }
+        assert_eq!(tasks[0].id, "T1");
+    }
+
+    #[test]
+    fn reorder_persists() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let root = dir.path();
+        let mut feature = Feature::create(root, "auth", "Auth").unwrap();
+        for title in ["Schema", "Handler", "Tests"] {
+            add_task(&mut feature.tasks, title);
+        }
+        feature.save(root).unwrap();
+
+        reorder(root, "auth", &["T3", "T1", "T2"]).unwrap();
+
+        let feature = Feature::load(root, "auth").unwrap();
+        let ids: Vec<&str> = feature.tasks.iter().map(|t| t.id.as_str()).collect();
+        assert_eq!(ids, vec!["T3", "T1", "T2"]);
+        assert_eq!(next_task(&feature.tasks).unwrap().id, "T3");
+    }
+
+    #[test]
+    fn sync_from_checklist_is_idempotent() {
+        let mut tasks = Vec::new();
+        add_task(&mut tasks, "Add the parser");
+        let items = crate::artifact::parse_task_checklist(
+            "- [ ] add the parser.\n- [x] Wire CLI\n- [ ] Write tests\n- [ ] write  TESTS\n",
+        );
+
+        let created = sync_from_checklist(&mut tasks, &items);
+        assert_eq!(created, vec!["T2", "T3"]);
+        assert_eq!(tasks.len(), 3);
+        assert_eq!(tasks[1].status, TaskStatus::Completed);
+        assert_eq!(tasks[2].status, TaskStatus::Pending);
+
+        assert!(sync_from_checklist(&mut tasks, &items).is_empty());
+        assert_eq!(tasks.len(), 3);
+    }
+
+    #[test]
+    fn import_tasks_skips_existing_and_repeated_titles() {
+        let mut tasks: Vec<Task> = Vec::new();
+        add_task(&mut tasks, "Write the parser");
+        let spec = |title: &str| TaskSpec {
+            title: title.to_string(),
+            description: Some(format!("about {title}")),
+        };
+        let batch = [
+            spec("write the parser!"),
+            spec("Add tests"),
+            spec("add  TESTS"),
+        ];
+
+        let results = import_tasks(&mut tasks, &batch).unwrap();
+        let summary: Vec<_> = results
+            .iter()
+            .map(|r| (r.task_id.as_str(), r.status))
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                ("T1", ImportStatus::Skipped),
+                ("T2", ImportStatus::Created),
+                ("T2", ImportStatus::Skipped),
+            ]
+        );
+        assert_eq!(tasks.len(), 2);
+        assert_eq!(tasks[1].description.as_deref(), Some("about Add tests"));
+
+        let again = import_tasks(&mut tasks, &batch).unwrap();
+        assert!(again.iter().all(|r| r.status == ImportStatus::Skipped));
+        assert_eq!(tasks.len(), 2);
+    }
+
+    #[test]
+    fn import_tasks_rejects_blank_title_without_adding_any() {
+        let mut tasks: Vec<Task> = Vec::new();
+        let batch = [
+            TaskSpec {
+                title: "Real task".into(),
+                description: None,
+            },
+            TaskSpec {
+                title: " - ".into(),
+                description: None,
+            },
+        ];
+        let err = import_tasks(&mut tasks, &batch).unwrap_err();
+        assert!(matches!(err, SdlcError::InvalidTaskSpec(_)), "{err}");
+        assert!(tasks.is_empty());
+    }
 }
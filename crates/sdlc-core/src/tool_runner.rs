@@ -2,7 +2,9 @@
 //!
 //! SDLC tools are TypeScript scripts that speak a JSON stdin/stdout protocol.
 //! This module detects the best available runtime (bun > deno > node/npx) and
-//! provides a single `run_tool()` function that all callers use.
+//! provides `run_tool()`, which all callers use, plus `run_tool_result()` for
+//! callers that want the `--run` result envelope parsed into a
+//! [`ToolRunResult`].
 //!
 //! # Protocol
 //! - `--meta`:  No stdin. Writes ToolMeta JSON to stdout.
@@ -20,6 +22,7 @@ use std::path::Path;
 use std::process::{Command, Stdio};
 
 use crate::error::{Result, SdlcError};
+use crate::retry::{retry_with_backoff, RetryPolicy};
 
 // ---------------------------------------------------------------------------
 // ToolMeta and related types
@@ -101,6 +104,81 @@ pub fn parse_tool_meta(stdout: &str) -> Result<ToolMeta> {
     serde_json::from_str(stdout).map_err(SdlcError::Json)
 }
 
+// ---------------------------------------------------------------------------
+// ToolRunResult
+// ---------------------------------------------------------------------------
+
+/// Most stderr kept in a failed run's `error`; the tail is the useful part.
+const STDERR_ERROR_MAX_CHARS: usize = 4000;
+
+/// The result envelope a tool writes to stdout in `--run` and `--setup` mode.
+/// Mirrors `ToolResult` in `.sdlc/tools/_shared/types.ts`.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ToolRunResult {
+    pub ok: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<serde_json::Value>,
+    /// Present only when `ok` is false.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Wall-clock milliseconds for the operation.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "deserialize_millis"
+    )]
+    pub duration_ms: Option<u64>,
+}
+
+impl ToolRunResult {
+    /// Build the result of a finished tool process from its captured output.
+    ///
+    /// Stdout that is not a result envelope, or a non-zero exit, yields
+    /// `ok: false`. A failed run without its own `error` gets the tool's
+    /// stderr (or a description of what went wrong) in its place.
+    pub fn from_output(stdout: &str, stderr: &str, exit_ok: bool) -> Self {
+        let (mut result, fallback) = match serde_json::from_str::<ToolRunResult>(stdout.trim()) {
+            Ok(result) => (result, "tool exited non-zero".to_string()),
+            Err(e) => (
+                ToolRunResult {
+                    ok: false,
+                    data: None,
+                    error: None,
+                    duration_ms: None,
+                },
+                format!("tool did not write a result envelope to stdout: {e}"),
+            ),
+        };
+        if !exit_ok {
+            result.ok = false;
+        }
+        if !result.ok && result.error.is_none() {
+            let stderr = stderr.trim();
+            result.error = Some(if stderr.is_empty() {
+                fallback
+            } else {
+                let skip = stderr
+                    .chars()
+                    .count()
+                    .saturating_sub(STDERR_ERROR_MAX_CHARS);
+                stderr.chars().skip(skip).collect()
+            });
+        }
+        result
+    }
+}
+
+/// `duration_ms` is a JS number; accept fractional milliseconds.
+fn deserialize_millis<'de, D>(deserializer: D) -> std::result::Result<Option<u64>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let ms: Option<f64> = serde::Deserialize::deserialize(deserializer)?;
+    Ok(ms
+        .filter(|ms| ms.is_finite() && *ms >= 0.0)
+        .map(|ms| ms.round() as u64))
+}
+
 /// Call `sdlc secrets env export <group>` and parse the KEY=VALUE output.
 ///
 /// Returns a map of env var names → decrypted values.
@@ -190,6 +268,56 @@ pub fn run_tool(
     root: &Path,
     extra_env: Option<&HashMap<String, String>>,
 ) -> Result<String> {
+    // stderr flows through so tool log lines appear in the terminal/run panel
+    let output = spawn_tool(script, mode, stdin_json, root, extra_env, Stdio::inherit())?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout).into_owned();
+
+    // For --run mode, a non-zero exit code means ok:false (checks failed), not a crash.
+    // The JSON result is always in stdout — return it regardless of exit code.
+    // For --meta and --setup, a non-zero exit is a genuine error.
+    if !output.status.success() && mode != "--run" {
+        let hint = stdout.chars().take(500).collect::<String>();
+        return Err(SdlcError::ToolFailed(hint));
+    }
+
+    Ok(stdout)
+}
+
+/// Run a tool in `mode` (`--run` or `--setup`) and parse its result envelope.
+///
+/// Unlike [`run_tool`], stderr is captured so a tool that crashes or writes
+/// no envelope still yields a [`ToolRunResult`] with `ok: false` and its
+/// stderr as the error. `duration_ms` is filled in when the tool omits it.
+/// Errors only when the tool cannot be started at all.
+pub fn run_tool_result(
+    script: &Path,
+    mode: &str,
+    stdin_json: Option<&str>,
+    root: &Path,
+    extra_env: Option<&HashMap<String, String>>,
+) -> Result<ToolRunResult> {
+    let started = std::time::Instant::now();
+    let output = spawn_tool(script, mode, stdin_json, root, extra_env, Stdio::piped())?;
+    let mut result = ToolRunResult::from_output(
+        &String::from_utf8_lossy(&output.stdout),
+        &String::from_utf8_lossy(&output.stderr),
+        output.status.success(),
+    );
+    result
+        .duration_ms
+        .get_or_insert(started.elapsed().as_millis() as u64);
+    Ok(result)
+}
+
+fn spawn_tool(
+    script: &Path,
+    mode: &str,
+    stdin_json: Option<&str>,
+    root: &Path,
+    extra_env: Option<&HashMap<String, String>>,
+    stderr: Stdio,
+) -> Result<std::process::Output> {
     let runtime = detect_runtime().ok_or(SdlcError::NoToolRuntime)?;
 
     let script_str = script.to_str().ok_or_else(|| {
@@ -217,12 +345,12 @@ pub fn run_tool(
     }
 
     cmd.stdout(Stdio::piped());
-    // stderr flows through so tool log lines appear in the terminal/run panel
-    cmd.stderr(Stdio::inherit());
+    cmd.stderr(stderr);
 
-    let mut child = cmd
-        .spawn()
-        .map_err(|e| SdlcError::ToolSpawnFailed(e.to_string()))?;
+    let mut child = retry_with_backoff(&RetryPolicy::default(), is_transient_spawn_error, |_| {
+        cmd.spawn()
+    })
+    .map_err(|e| SdlcError::ToolSpawnFailed(e.to_string()))?;
 
     // Feed stdin if provided
     if let Some(json) = stdin_json {
@@ -233,21 +361,20 @@ pub fn run_tool(
         }
     }
 
-    let output = child
+    child
         .wait_with_output()
-        .map_err(|e| SdlcError::ToolSpawnFailed(e.to_string()))?;
-
-    let stdout = String::from_utf8_lossy(&output.stdout).into_owned();
-
-    // For --run mode, a non-zero exit code means ok:false (checks failed), not a crash.
-    // The JSON result is always in stdout — return it regardless of exit code.
-    // For --meta and --setup, a non-zero exit is a genuine error.
-    if !output.status.success() && mode != "--run" {
-        let hint = stdout.chars().take(500).collect::<String>();
-        return Err(SdlcError::ToolFailed(hint));
-    }
+        .map_err(|e| SdlcError::ToolSpawnFailed(e.to_string()))
+}
 
-    Ok(stdout)
+/// Spawn failures worth retrying: the runtime binary being replaced
+/// (`ETXTBSY`, common right after an install) or the process table briefly
+/// full.
+fn is_transient_spawn_error(e: &std::io::Error) -> bool {
+    const ETXTBSY: i32 = 26;
+    matches!(
+        e.kind(),
+        std::io::ErrorKind::Interrupted | std::io::ErrorKind::WouldBlock
+    ) || (cfg!(unix) && e.raw_os_error() == Some(ETXTBSY))
 }
 
 /// Return the program name and argument list for spawning a tool in a given mode.
@@ -433,6 +560,52 @@ mod tests {
         assert_eq!(Runtime::Node.name(), "node (via npx tsx)");
     }
 
+    #[test]
+    fn well_formed_tool_result_parses() {
+        let stdout = r#"{"ok":true,"data":{"answer":42},"duration_ms":12.6}"#;
+        let result = ToolRunResult::from_output(stdout, "log line\n", true);
+        assert_eq!(
+            result,
+            ToolRunResult {
+                ok: true,
+                data: Some(serde_json::json!({"answer": 42})),
+                error: None,
+                duration_ms: Some(13),
+            }
+        );
+    }
+
+    #[test]
+    fn failed_tool_result_keeps_its_own_error() {
+        let stdout = r#"{"ok":false,"error":"3 checks failed"}"#;
+        let result = ToolRunResult::from_output(stdout, "noise", false);
+        assert!(!result.ok);
+        assert_eq!(result.error.as_deref(), Some("3 checks failed"));
+    }
+
+    #[test]
+    fn crashing_tool_yields_structured_error_from_stderr() {
+        let stderr = "TypeError: x is undefined\n    at tool.ts:10\n";
+        let result = ToolRunResult::from_output("", stderr, false);
+        assert!(!result.ok);
+        assert_eq!(
+            result.error.as_deref(),
+            Some("TypeError: x is undefined\n    at tool.ts:10")
+        );
+
+        // A non-zero exit overrides a tool that still claimed success.
+        let result = ToolRunResult::from_output(r#"{"ok":true}"#, "boom", false);
+        assert!(!result.ok);
+        assert_eq!(result.error.as_deref(), Some("boom"));
+    }
+
+    #[test]
+    fn non_json_stdout_without_stderr_names_the_problem() {
+        let result = ToolRunResult::from_output("hello", "", true);
+        assert!(!result.ok);
+        assert!(result.error.unwrap().contains("result envelope"));
+    }
+
     #[test]
     fn to_display_name_capitalizes_each_word() {
         assert_eq!(to_display_name("my-tool"), "My Tool");
@@ -88,11 +88,116 @@ impl std::str::FromStr for Phase {
     }
 }
 
+// ---------------------------------------------------------------------------
+// FeatureTrack
+// ---------------------------------------------------------------------------
+
+/// Which pipeline a feature runs through.
+///
+/// `Full` is the default spec → design → tasks → qa_plan → implementation →
+/// review → audit → qa → merge lifecycle. `Lightweight` is for documentation-only
+/// or similarly low-risk changes: spec → implementation → review → merge.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FeatureTrack {
+    #[default]
+    Full,
+    Lightweight,
+}
+
+impl FeatureTrack {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            FeatureTrack::Full => "full",
+            FeatureTrack::Lightweight => "lightweight",
+        }
+    }
+
+    /// Returns true if this track never produces the given artifact, so it is
+    /// not required for any phase transition.
+    pub fn skips(self, artifact_type: ArtifactType) -> bool {
+        match self {
+            FeatureTrack::Full => false,
+            FeatureTrack::Lightweight => {
+                !matches!(artifact_type, ArtifactType::Spec | ArtifactType::Review)
+            }
+        }
+    }
+}
+
+impl fmt::Display for FeatureTrack {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for FeatureTrack {
+    type Err = crate::error::SdlcError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "full" => Ok(FeatureTrack::Full),
+            "lightweight" => Ok(FeatureTrack::Lightweight),
+            _ => Err(crate::error::SdlcError::InvalidFeatureTrack(s.to_string())),
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Priority
+// ---------------------------------------------------------------------------
+
+/// How urgently a feature should be worked.
+///
+/// `Normal` features are focused in milestone order. `Hotfix` features jump
+/// the queue: `focus()` surfaces them ahead of every normal feature,
+/// regardless of milestone position.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Priority {
+    #[default]
+    Normal,
+    Hotfix,
+}
+
+impl Priority {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Priority::Normal => "normal",
+            Priority::Hotfix => "hotfix",
+        }
+    }
+
+    pub fn is_normal(&self) -> bool {
+        *self == Priority::Normal
+    }
+}
+
+impl fmt::Display for Priority {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for Priority {
+    type Err = crate::error::SdlcError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "normal" => Ok(Priority::Normal),
+            "hotfix" => Ok(Priority::Hotfix),
+            _ => Err(crate::error::SdlcError::InvalidPriority(s.to_string())),
+        }
+    }
+}
+
 // ---------------------------------------------------------------------------
 // ArtifactType
 // ---------------------------------------------------------------------------
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+/// Variants are declared in phase-chain order, which `Ord` follows: an
+/// artifact is upstream of every artifact that sorts after it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum ArtifactType {
     Spec,
@@ -417,6 +522,22 @@ mod tests {
         }
     }
 
+    #[test]
+    fn feature_track_roundtrip_and_skips() {
+        use std::str::FromStr;
+        for track in [FeatureTrack::Full, FeatureTrack::Lightweight] {
+            assert_eq!(FeatureTrack::from_str(track.as_str()).unwrap(), track);
+        }
+        assert!(FeatureTrack::from_str("express").is_err());
+        assert_eq!(FeatureTrack::default(), FeatureTrack::Full);
+        assert!(!FeatureTrack::Full.skips(ArtifactType::Audit));
+        assert!(FeatureTrack::Lightweight.skips(ArtifactType::Design));
+        assert!(FeatureTrack::Lightweight.skips(ArtifactType::Audit));
+        assert!(FeatureTrack::Lightweight.skips(ArtifactType::QaResults));
+        assert!(!FeatureTrack::Lightweight.skips(ArtifactType::Spec));
+        assert!(!FeatureTrack::Lightweight.skips(ArtifactType::Review));
+    }
+
     #[test]
     fn action_type_all_complete() {
         // Ensure all() returns 21 variants
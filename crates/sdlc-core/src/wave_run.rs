@@ -0,0 +1,257 @@
+//! Progress of a milestone run-wave, persisted to
+//! `.sdlc/milestones/<slug>/wave_run.yaml`.
+//!
+//! A [`WaveRun`] records which wave is executing and where each of its
+//! features stands. Status is derived from the features themselves (see
+//! [`WaveRun::refresh`]), so the record stays truthful however the run
+//! ended. Starting a run-wave while an unfinished record exists resumes it
+//! with only the features that are not yet done.
+
+use crate::classifier::{Classifier, EvalContext};
+use crate::config::Config;
+use crate::error::Result;
+use crate::feature::Feature;
+use crate::prepare::{self, Wave};
+use crate::rules::default_rules;
+use crate::state::State;
+use crate::types::{ActionType, Phase};
+use crate::{io, paths};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WaveFeatureStatus {
+    Pending,
+    /// The feature has moved on from the phase it had when the wave began.
+    InProgress,
+    Completed,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WaveRunFeature {
+    pub slug: String,
+    /// Phase when the wave began; progress is measured against it.
+    pub start_phase: Phase,
+    pub status: WaveFeatureStatus,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WaveRun {
+    pub milestone: String,
+    pub wave: usize,
+    pub started_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub features: Vec<WaveRunFeature>,
+}
+
+impl WaveRun {
+    /// Begin tracking `wave` with every feature pending.
+    pub fn new(milestone: impl Into<String>, wave: &Wave) -> Self {
+        let now = Utc::now();
+        Self {
+            milestone: milestone.into(),
+            wave: wave.number,
+            started_at: now,
+            updated_at: now,
+            features: wave
+                .items
+                .iter()
+                .map(|item| WaveRunFeature {
+                    slug: item.slug.clone(),
+                    start_phase: item.phase,
+                    status: WaveFeatureStatus::Pending,
+                })
+                .collect(),
+        }
+    }
+
+    /// The persisted run for `milestone`, if any.
+    pub fn load(root: &Path, milestone: &str) -> Result<Option<Self>> {
+        paths::validate_slug(milestone)?;
+        let path = paths::milestone_wave_run_path(root, milestone);
+        if !path.exists() {
+            return Ok(None);
+        }
+        let data = std::fs::read_to_string(&path)?;
+        Ok(Some(serde_yaml::from_str(&data)?))
+    }
+
+    pub fn save(&self, root: &Path) -> Result<()> {
+        let path = paths::milestone_wave_run_path(root, &self.milestone);
+        let data = serde_yaml::to_string(self)?;
+        io::atomic_write(&path, data.as_bytes())
+    }
+
+    /// Re-derive each feature's status from its manifest. Released or
+    /// classifier-`Done` features are completed; a feature whose phase has
+    /// changed since the wave began is in progress. Missing features count
+    /// as completed so they cannot hold a resume open forever.
+    pub fn refresh(&mut self, root: &Path) -> Result<()> {
+        let config = Config::load(root)?;
+        let state = State::load(root)?;
+        let classifier = Classifier::new(default_rules());
+        for entry in &mut self.features {
+            let Ok(feature) = Feature::load(root, &entry.slug) else {
+                entry.status = WaveFeatureStatus::Completed;
+                continue;
+            };
+            let action = classifier
+                .classify(&EvalContext::new(&feature, &state, &config, root))
+                .action;
+            entry.status = if feature.phase == Phase::Released || action == ActionType::Done {
+                WaveFeatureStatus::Completed
+            } else if feature.phase != entry.start_phase {
+                WaveFeatureStatus::InProgress
+            } else {
+                WaveFeatureStatus::Pending
+            };
+        }
+        self.updated_at = Utc::now();
+        Ok(())
+    }
+
+    /// Slugs of features that still need work, in wave order.
+    pub fn remaining(&self) -> Vec<&str> {
+        self.features
+            .iter()
+            .filter(|f| f.status != WaveFeatureStatus::Completed)
+            .map(|f| f.slug.as_str())
+            .collect()
+    }
+
+    pub fn completed(&self) -> Vec<&str> {
+        self.features
+            .iter()
+            .filter(|f| f.status == WaveFeatureStatus::Completed)
+            .map(|f| f.slug.as_str())
+            .collect()
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.remaining().is_empty()
+    }
+}
+
+/// Whether [`begin_or_resume`] picked up an earlier run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WaveRunStart {
+    Fresh,
+    Resumed,
+}
+
+/// Resume the unfinished wave run for `milestone`, or start tracking the
+/// milestone's current wave. Returns `None` when there is no wave to run.
+/// The refreshed (or new) record is saved before returning.
+pub fn begin_or_resume(root: &Path, milestone: &str) -> Result<Option<(WaveRun, WaveRunStart)>> {
+    if let Some(mut run) = WaveRun::load(root, milestone)? {
+        run.refresh(root)?;
+        if !run.is_finished() {
+            run.save(root)?;
+            return Ok(Some((run, WaveRunStart::Resumed)));
+        }
+    }
+    let plan = prepare::prepare(root, Some(milestone))?;
+    let Some(wave) = plan.waves.first() else {
+        return Ok(None);
+    };
+    let run = WaveRun::new(milestone, wave);
+    run.save(root)?;
+    Ok(Some((run, WaveRunStart::Fresh)))
+}
+
+/// Refresh and save the persisted run for `milestone`, if there is one.
+pub fn checkpoint(root: &Path, milestone: &str) -> Result<Option<WaveRun>> {
+    let Some(mut run) = WaveRun::load(root, milestone)? else {
+        return Ok(None);
+    };
+    run.refresh(root)?;
+    run.save(root)?;
+    Ok(Some(run))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::milestone::Milestone;
+    use tempfile::TempDir;
+
+    fn setup() -> TempDir {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path();
+        std::fs::create_dir_all(root.join(".sdlc/features")).unwrap();
+        std::fs::create_dir_all(root.join(".sdlc/milestones")).unwrap();
+        Config::new("test").save(root).unwrap();
+        let mut state = State::new("test");
+        let mut milestone = Milestone::create(root, "v1", "V1").unwrap();
+        for slug in ["auth", "billing", "search"] {
+            Feature::create(root, slug, slug).unwrap();
+            state.add_active_feature(slug);
+            milestone.add_feature(slug);
+        }
+        milestone.save(root).unwrap();
+        state.add_milestone("v1");
+        state.save(root).unwrap();
+        dir
+    }
+
+    fn set_phase(root: &Path, slug: &str, phase: Phase) {
+        let mut f = Feature::load(root, slug).unwrap();
+        f.phase = phase;
+        f.save(root).unwrap();
+    }
+
+    #[test]
+    fn fresh_start_tracks_the_current_wave_as_pending() {
+        let dir = setup();
+        let (run, start) = begin_or_resume(dir.path(), "v1").unwrap().unwrap();
+        assert_eq!(start, WaveRunStart::Fresh);
+        assert_eq!(run.wave, 1);
+        assert_eq!(run.remaining(), ["auth", "billing", "search"]);
+        assert_eq!(WaveRun::load(dir.path(), "v1").unwrap(), Some(run));
+    }
+
+    #[test]
+    fn resume_after_stop_continues_with_unfinished_features_only() {
+        let dir = setup();
+        let root = dir.path();
+        begin_or_resume(root, "v1").unwrap();
+
+        // Mid-wave: auth shipped, billing got as far as specifying; then stop.
+        set_phase(root, "auth", Phase::Released);
+        set_phase(root, "billing", Phase::Specified);
+        let stopped = checkpoint(root, "v1").unwrap().unwrap();
+        let statuses: Vec<_> = stopped.features.iter().map(|f| f.status).collect();
+        assert_eq!(
+            statuses,
+            [
+                WaveFeatureStatus::Completed,
+                WaveFeatureStatus::InProgress,
+                WaveFeatureStatus::Pending,
+            ]
+        );
+
+        let (run, start) = begin_or_resume(root, "v1").unwrap().unwrap();
+        assert_eq!(start, WaveRunStart::Resumed);
+        assert_eq!(run.completed(), ["auth"]);
+        assert_eq!(run.remaining(), ["billing", "search"]);
+    }
+
+    #[test]
+    fn finished_wave_run_is_replaced_by_the_next_wave() {
+        let dir = setup();
+        let root = dir.path();
+        let mut search = Feature::load(root, "search").unwrap();
+        search.dependencies = vec!["auth".into()];
+        search.save(root).unwrap();
+        let (first, _) = begin_or_resume(root, "v1").unwrap().unwrap();
+        assert_eq!(first.remaining(), ["auth", "billing"]);
+
+        set_phase(root, "auth", Phase::Released);
+        set_phase(root, "billing", Phase::Released);
+        let (next, start) = begin_or_resume(root, "v1").unwrap().unwrap();
+        assert_eq!(start, WaveRunStart::Fresh);
+        assert_eq!(next.remaining(), ["search"]);
+    }
+}
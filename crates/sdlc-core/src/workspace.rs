@@ -292,13 +292,17 @@ pub fn write_artifact_from_file(dir: &Path, src: &Path, filename: &str) -> Resul
     crate::io::atomic_write(&dest, &content)
 }
 
-/// List files in `dir`, skipping entries in `skip` and any subdirectories.
+/// List files in `dir`, skipping entries in `skip`, any subdirectories, and
+/// temp files from in-flight or interrupted writes.
 pub fn list_artifacts(dir: &Path, skip: &[&str]) -> Result<Vec<ArtifactMeta>> {
     let mut artifacts = Vec::new();
     for entry in std::fs::read_dir(dir)? {
         let entry = entry?;
         let name = entry.file_name().to_string_lossy().into_owned();
-        if skip.contains(&name.as_str()) || entry.file_type()?.is_dir() {
+        if skip.contains(&name.as_str())
+            || crate::io::is_temp_file(&name)
+            || entry.file_type()?.is_dir()
+        {
             continue;
         }
         let meta = entry.metadata()?;
@@ -1,10 +1,27 @@
-use std::path::PathBuf;
+use std::io::Write;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 
 fn main() {
     let manifest_dir = PathBuf::from(std::env::var("CARGO_MANIFEST_DIR").unwrap());
     let out_dir = PathBuf::from(std::env::var("OUT_DIR").unwrap());
     let frontend_dir = manifest_dir.join("../../frontend");
+
+    let dist = resolve_dist(&frontend_dir, &out_dir);
+
+    // Embed a copy of the dist with `.br` / `.gz` siblings next to each
+    // compressible asset, so `embed::static_handler` can serve them without
+    // compressing per request.
+    let embed_dir = out_dir.join("frontend-embed");
+    if embed_dir.exists() {
+        std::fs::remove_dir_all(&embed_dir).expect("clear frontend-embed dir");
+    }
+    precompress(&dist, &embed_dir).expect("pre-compress frontend assets");
+    println!("cargo:rustc-env=SDLC_FRONTEND_DIST={}", embed_dir.display());
+}
+
+/// Locate (or build, or stub) the frontend dist directory.
+fn resolve_dist(frontend_dir: &Path, out_dir: &Path) -> PathBuf {
     let real_dist = frontend_dir.join("dist");
 
     // Rerun when frontend sources change, or when dist/index.html appears or
@@ -23,8 +40,7 @@ fn main() {
     // If frontend/dist/index.html already exists (pre-built or prior npm run
     // build), use it directly and skip the npm step.
     if real_dist.join("index.html").exists() {
-        println!("cargo:rustc-env=SDLC_FRONTEND_DIST={}", real_dist.display());
-        return;
+        return real_dist;
     }
 
     // No pre-built dist — attempt to build the frontend now.
@@ -46,9 +62,8 @@ fn main() {
              </body></html>",
         )
         .expect("write stub index.html");
-        println!("cargo:rustc-env=SDLC_FRONTEND_DIST={}", stub_dir.display());
         println!("cargo:warning=SDLC_NO_NPM set — sdlc-server will serve a stub UI");
-        return;
+        return stub_dir;
     }
 
     let npm = if cfg!(target_os = "windows") {
@@ -59,19 +74,19 @@ fn main() {
 
     let built = Command::new(npm)
         .args(["ci"])
-        .current_dir(&frontend_dir)
+        .current_dir(frontend_dir)
         .status()
         .map(|s| s.success())
         .unwrap_or(false)
         && Command::new(npm)
             .args(["run", "build"])
-            .current_dir(&frontend_dir)
+            .current_dir(frontend_dir)
             .status()
             .map(|s| s.success())
             .unwrap_or(false);
 
     if built {
-        println!("cargo:rustc-env=SDLC_FRONTEND_DIST={}", real_dist.display());
+        real_dist
     } else {
         // npm is unavailable or failed (e.g. `cargo install` without Node.js).
         // Write a stub into OUT_DIR — always writable, never touches the source
@@ -86,10 +101,67 @@ fn main() {
              </body></html>",
         )
         .expect("write stub index.html");
-        println!("cargo:rustc-env=SDLC_FRONTEND_DIST={}", stub_dir.display());
         println!(
             "cargo:warning=frontend/dist not found and npm build skipped — \
              sdlc-server will serve a stub UI"
         );
+        stub_dir
+    }
+}
+
+/// Extensions worth compressing; images and fonts are already compressed.
+const COMPRESSIBLE: &[&str] = &[
+    "html", "js", "mjs", "css", "svg", "json", "map", "txt", "xml", "wasm",
+];
+
+/// Copy `src` into `dst`, writing `<file>.br` and `<file>.gz` beside every
+/// compressible file whose compressed form is smaller than the original.
+fn precompress(src: &Path, dst: &Path) -> std::io::Result<()> {
+    std::fs::create_dir_all(dst)?;
+    for entry in std::fs::read_dir(src)? {
+        let entry = entry?;
+        let from = entry.path();
+        let to = dst.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            precompress(&from, &to)?;
+            continue;
+        }
+        let data = std::fs::read(&from)?;
+        std::fs::write(&to, &data)?;
+
+        let compressible = from
+            .extension()
+            .and_then(|e| e.to_str())
+            .is_some_and(|e| COMPRESSIBLE.contains(&e));
+        if !compressible {
+            continue;
+        }
+
+        let mut br = Vec::new();
+        {
+            let params = brotli::enc::BrotliEncoderParams {
+                quality: 11,
+                ..Default::default()
+            };
+            brotli::BrotliCompress(&mut data.as_slice(), &mut br, &params)?;
+        }
+        if br.len() < data.len() {
+            std::fs::write(with_suffix(&to, "br"), &br)?;
+        }
+
+        let mut gz = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::best());
+        gz.write_all(&data)?;
+        let gz = gz.finish()?;
+        if gz.len() < data.len() {
+            std::fs::write(with_suffix(&to, "gz"), &gz)?;
+        }
     }
+    Ok(())
+}
+
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(".");
+    name.push(suffix);
+    PathBuf::from(name)
 }
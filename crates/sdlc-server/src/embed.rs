@@ -1,5 +1,5 @@
 use axum::extract::State;
-use axum::http::{header, StatusCode};
+use axum::http::{header, HeaderMap, StatusCode};
 use axum::response::{IntoResponse, Response};
 use rust_embed::Embed;
 use std::path::Path;
@@ -47,18 +47,82 @@ fn inject_title(html: &str, title: &str) -> String {
     html.to_string()
 }
 
+/// Content codings the build pre-computes for each compressible asset, in
+/// server preference order, with the suffix of the embedded variant.
+const ENCODINGS: &[(&str, &str)] = &[("br", "br"), ("gzip", "gz")];
+
+/// Pick the most preferred coding from [`ENCODINGS`] that `accept_encoding`
+/// allows. Codings listed with `q=0` are refused; `*` admits any coding not
+/// otherwise mentioned.
+fn negotiate_encoding(accept_encoding: &str) -> Vec<(&'static str, &'static str)> {
+    let mut accepted: Vec<(&str, f32)> = Vec::new();
+    for item in accept_encoding.split(',') {
+        let mut parts = item.split(';');
+        let coding = parts.next().unwrap_or("").trim();
+        if coding.is_empty() {
+            continue;
+        }
+        let q = parts
+            .filter_map(|p| p.trim().strip_prefix("q="))
+            .find_map(|q| q.trim().parse::<f32>().ok())
+            .unwrap_or(1.0);
+        accepted.push((coding, q));
+    }
+    let quality = |name: &str| {
+        accepted
+            .iter()
+            .find(|(c, _)| c.eq_ignore_ascii_case(name))
+            .or_else(|| accepted.iter().find(|(c, _)| *c == "*"))
+            .map(|(_, q)| *q)
+            .unwrap_or(0.0)
+    };
+    ENCODINGS
+        .iter()
+        .copied()
+        .filter(|(name, _)| quality(name) > 0.0)
+        .collect()
+}
+
 /// Serve embedded frontend assets. Falls back to index.html for SPA routing.
 /// Injects a dynamic page title ("sdlc — {project-name}") into index.html at
 /// serve time so browser tabs reflect the current project.
-pub async fn static_handler(State(app): State<AppState>, uri: axum::http::Uri) -> Response {
+///
+/// Static assets are served pre-compressed (brotli, then gzip) when the
+/// client's `Accept-Encoding` allows it, and as identity otherwise.
+pub async fn static_handler(
+    State(app): State<AppState>,
+    headers: HeaderMap,
+    uri: axum::http::Uri,
+) -> Response {
     let path = uri.path().trim_start_matches('/');
 
     // Try the exact path first (static assets: JS, CSS, images, etc.)
     if let Some(content) = <FrontendAssets as Embed>::get(path) {
         let mime = mime_guess::from_path(path).first_or_octet_stream();
+        let accept = headers
+            .get(header::ACCEPT_ENCODING)
+            .and_then(|v| v.to_str().ok())
+            .unwrap_or("");
+        for (coding, suffix) in negotiate_encoding(accept) {
+            if let Some(encoded) = <FrontendAssets as Embed>::get(&format!("{path}.{suffix}")) {
+                return (
+                    StatusCode::OK,
+                    [
+                        (header::CONTENT_TYPE, mime.as_ref()),
+                        (header::CONTENT_ENCODING, coding),
+                        (header::VARY, "Accept-Encoding"),
+                    ],
+                    encoded.data.to_vec(),
+                )
+                    .into_response();
+            }
+        }
         return (
             StatusCode::OK,
-            [(header::CONTENT_TYPE, mime.as_ref())],
+            [
+                (header::CONTENT_TYPE, mime.as_ref()),
+                (header::VARY, "Accept-Encoding"),
+            ],
             content.data.to_vec(),
         )
             .into_response();
@@ -147,4 +211,63 @@ mod tests {
         let title = compute_title(tmp.path());
         assert_eq!(title, "sdlc");
     }
+
+    #[test]
+    fn negotiate_encoding_prefers_brotli_and_honours_q_zero() {
+        assert_eq!(
+            negotiate_encoding("gzip, deflate, br"),
+            [("br", "br"), ("gzip", "gz")]
+        );
+        assert_eq!(negotiate_encoding("gzip;q=0.5, br;q=0"), [("gzip", "gz")]);
+        assert_eq!(negotiate_encoding("*"), [("br", "br"), ("gzip", "gz")]);
+        assert!(negotiate_encoding("").is_empty());
+        assert!(negotiate_encoding("identity").is_empty());
+    }
+
+    async fn get_index(accept_encoding: Option<&str>) -> Response {
+        let tmp = tempfile::TempDir::new().expect("tempdir");
+        let app = AppState::new_for_test(tmp.path().to_path_buf());
+        let mut headers = HeaderMap::new();
+        if let Some(ae) = accept_encoding {
+            headers.insert(header::ACCEPT_ENCODING, ae.parse().unwrap());
+        }
+        static_handler(State(app), headers, "/index.html".parse().unwrap()).await
+    }
+
+    async fn body_bytes(resp: Response) -> Vec<u8> {
+        use http_body_util::BodyExt;
+        resp.into_body()
+            .collect()
+            .await
+            .unwrap()
+            .to_bytes()
+            .to_vec()
+    }
+
+    #[tokio::test]
+    async fn static_handler_serves_brotli_when_accepted() {
+        let resp = get_index(Some("br")).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(resp.headers()[header::CONTENT_ENCODING], "br");
+        assert_eq!(resp.headers()[header::VARY], "Accept-Encoding");
+        assert_eq!(resp.headers()[header::CONTENT_TYPE], "text/html");
+
+        let compressed = body_bytes(resp).await;
+        let mut decoded = Vec::new();
+        brotli::BrotliDecompress(&mut compressed.as_slice(), &mut decoded).unwrap();
+        let plain = <FrontendAssets as Embed>::get("index.html").unwrap();
+        assert_eq!(decoded, plain.data.as_ref());
+    }
+
+    #[tokio::test]
+    async fn static_handler_serves_identity_without_accept_encoding() {
+        let resp = get_index(None).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert!(resp.headers().get(header::CONTENT_ENCODING).is_none());
+        assert_eq!(resp.headers()[header::VARY], "Accept-Encoding");
+
+        let body = body_bytes(resp).await;
+        let plain = <FrontendAssets as Embed>::get("index.html").unwrap();
+        assert_eq!(body, plain.data.as_ref());
+    }
 }
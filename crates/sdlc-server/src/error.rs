@@ -71,6 +71,22 @@ impl std::fmt::Display for TooManyRequestsError {
 
 impl std::error::Error for TooManyRequestsError {}
 
+/// Private sentinel error type used to carry a 400 Bad Request for one
+/// invalid request parameter, named in the response body.
+#[derive(Debug)]
+struct InvalidParamError {
+    field: String,
+    message: String,
+}
+
+impl std::fmt::Display for InvalidParamError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "invalid '{}': {}", self.field, self.message)
+    }
+}
+
+impl std::error::Error for InvalidParamError {}
+
 /// Private sentinel for 422 Unprocessable Entity with a custom JSON body.
 #[derive(Debug)]
 struct UnprocessableJsonError(serde_json::Value);
@@ -97,6 +113,17 @@ impl AppError {
         Self(SdlcError::InvalidSlug(msg.into()).into())
     }
 
+    /// Construct a 400 Bad Request error naming the offending parameter.
+    pub fn invalid_param(field: impl Into<String>, msg: impl Into<String>) -> Self {
+        Self(
+            InvalidParamError {
+                field: field.into(),
+                message: msg.into(),
+            }
+            .into(),
+        )
+    }
+
     /// Construct a 409 Conflict error.
     pub fn conflict(msg: impl Into<String>) -> Self {
         Self(ConflictError(msg.into()).into())
@@ -135,6 +162,10 @@ impl IntoResponse for AppError {
             let body = serde_json::json!({ "error": u.0.clone() });
             return (StatusCode::UNAUTHORIZED, axum::Json(body)).into_response();
         }
+        if let Some(p) = self.0.downcast_ref::<InvalidParamError>() {
+            let body = serde_json::json!({ "error": p.to_string(), "field": p.field.clone() });
+            return (StatusCode::BAD_REQUEST, axum::Json(body)).into_response();
+        }
         if let Some(c) = self.0.downcast_ref::<ConflictError>() {
             let body = serde_json::json!({ "error": c.0.clone() });
             return (StatusCode::CONFLICT, axum::Json(body)).into_response();
@@ -194,6 +225,8 @@ impl IntoResponse for AppError {
             match e {
                 SdlcError::NotInitialized => StatusCode::BAD_REQUEST,
                 SdlcError::FeatureNotFound(_)
+                | SdlcError::FeatureTemplateNotFound(_, _)
+                | SdlcError::PromptTemplateNotFound(_, _)
                 | SdlcError::MilestoneNotFound(_)
                 | SdlcError::PonderNotFound(_)
                 | SdlcError::InvestigationNotFound(_)
@@ -215,34 +248,52 @@ impl IntoResponse for AppError {
                 | SdlcError::SecretKeyExists(_)
                 | SdlcError::SecretEnvExists(_)
                 | SdlcError::ToolExists(_)
-                | SdlcError::AuthTokenExists(_) => StatusCode::CONFLICT,
+                | SdlcError::AuthTokenExists(_)
+                | SdlcError::RestoreTargetNotEmpty(_) => StatusCode::CONFLICT,
                 SdlcError::InvalidSlug(_)
                 | SdlcError::InvalidPhase(_)
+                | SdlcError::InvalidFeatureTrack(_)
+                | SdlcError::InvalidPriority(_)
+                | SdlcError::InvalidSince(_)
+                | SdlcError::InvalidBundle(_)
+                | SdlcError::InvalidBackup(_)
                 | SdlcError::InvalidPonderStatus(_)
                 | SdlcError::InvalidInvestigationKind(_)
                 | SdlcError::InvalidInvestigationStatus(_)
                 | SdlcError::InvalidArtifactFilename(_)
                 | SdlcError::InvalidFeatureOrder(_)
+                | SdlcError::InvalidTaskOrder(_)
+                | SdlcError::InvalidTaskSpec(_)
                 | SdlcError::InvalidSecretKeyType(_) => StatusCode::BAD_REQUEST,
-                SdlcError::DependencyCycle(_) => StatusCode::UNPROCESSABLE_ENTITY,
+                SdlcError::DependencyCycle(_)
+                | SdlcError::SecretsBackendReadOnly(_)
+                | SdlcError::InvalidPricing { .. } => StatusCode::UNPROCESSABLE_ENTITY,
                 SdlcError::DuplicateTeamMember(_) => StatusCode::CONFLICT,
+                SdlcError::MergeConflict { .. } | SdlcError::UnreleasedDependencies { .. } => {
+                    StatusCode::CONFLICT
+                }
                 SdlcError::InvalidTransition { .. } => StatusCode::UNPROCESSABLE_ENTITY,
                 SdlcError::MissingArtifact { .. } => StatusCode::UNPROCESSABLE_ENTITY,
+                SdlcError::SecretInArtifact { .. } => StatusCode::UNPROCESSABLE_ENTITY,
                 SdlcError::Blocked(_) => StatusCode::CONFLICT,
-                SdlcError::NoToolRuntime => StatusCode::SERVICE_UNAVAILABLE,
-                SdlcError::ToolFailed(_) => StatusCode::UNPROCESSABLE_ENTITY,
+                SdlcError::NoToolRuntime | SdlcError::LockTimeout(_) => {
+                    StatusCode::SERVICE_UNAVAILABLE
+                }
+                SdlcError::ToolFailed(_) | SdlcError::PromptPlaceholderMissing(_) => {
+                    StatusCode::UNPROCESSABLE_ENTITY
+                }
                 // Manifest errors are handled above with early returns; these
                 // arms are unreachable but required for exhaustiveness.
                 SdlcError::ManifestParseFailed { .. } | SdlcError::ManifestIncompatible { .. } => {
                     StatusCode::UNPROCESSABLE_ENTITY
                 }
                 SdlcError::Search(_)
+                | SdlcError::WriteNotPersisted(_)
                 | SdlcError::Io(_)
                 | SdlcError::Yaml(_)
                 | SdlcError::Json(_)
                 | SdlcError::HomeNotFound
                 | SdlcError::ToolSpawnFailed(_)
-                | SdlcError::AgeNotInstalled
                 | SdlcError::AgeDecryptFailed(_)
                 | SdlcError::AgeEncryptFailed(_)
                 | SdlcError::OrchestratorDb(_) => StatusCode::INTERNAL_SERVER_ERROR,
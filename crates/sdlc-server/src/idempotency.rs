@@ -0,0 +1,175 @@
+//! `Idempotency-Key` support for run-starting routes.
+//!
+//! A client that retries `POST /api/run/{slug}` (or any other route that
+//! starts an agent run) after a network blip sends the same
+//! `Idempotency-Key` header both times. The first request is handled
+//! normally; a repeat with the same key and path — while the first is still
+//! in flight, or within the TTL after it succeeded — gets the first response
+//! replayed, carrying the same `run_id`, instead of starting another run.
+//!
+//! Only successful JSON responses with a `run_id` are remembered, so a
+//! failed start can be retried with the same key and other POST routes are
+//! unaffected.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use axum::body::{Body, Bytes, HttpBody};
+use axum::extract::{Request, State};
+use axum::http::{header, HeaderValue, Method, StatusCode};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use tokio::sync::OnceCell;
+
+pub const IDEMPOTENCY_KEY_HEADER: &str = "idempotency-key";
+/// Set on responses served from the cache rather than by the handler.
+pub const REPLAYED_HEADER: &str = "idempotent-replayed";
+
+/// Used when `SDLC_IDEMPOTENCY_TTL_SECS` is unset or invalid.
+const DEFAULT_TTL: Duration = Duration::from_secs(300);
+/// Run-start responses are a few hundred bytes; anything larger is not one.
+const MAX_CACHED_BODY: usize = 64 * 1024;
+
+#[derive(Debug, Clone)]
+struct CachedResponse {
+    status: StatusCode,
+    body: Bytes,
+}
+
+type Slot = Arc<OnceCell<CachedResponse>>;
+
+/// Recently seen idempotency keys, scoped by request path.
+pub struct IdempotencyKeys {
+    ttl: Duration,
+    entries: Mutex<HashMap<String, (Instant, Slot)>>,
+}
+
+impl IdempotencyKeys {
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// TTL from `SDLC_IDEMPOTENCY_TTL_SECS` (default 300).
+    pub fn from_env() -> Self {
+        let ttl = std::env::var("SDLC_IDEMPOTENCY_TTL_SECS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .map(Duration::from_secs)
+            .unwrap_or(DEFAULT_TTL);
+        Self::new(ttl)
+    }
+
+    /// The slot for `scope`, created on first use. Expired entries are
+    /// pruned on every call so the map stays bounded by request rate × TTL.
+    fn slot(&self, scope: String) -> Slot {
+        let now = Instant::now();
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        entries.retain(|_, (created, _)| now.duration_since(*created) < self.ttl);
+        entries
+            .entry(scope)
+            .or_insert_with(|| (now, Arc::new(OnceCell::new())))
+            .1
+            .clone()
+    }
+}
+
+/// Keep `response` if it is a successful run start; otherwise hand it back
+/// untouched (rebuilt around the already-read body). A body that may exceed
+/// [`MAX_CACHED_BODY`] is not a run start and is passed through unread.
+async fn cacheable(response: Response) -> Result<CachedResponse, Response> {
+    let small = response
+        .body()
+        .size_hint()
+        .upper()
+        .is_some_and(|len| len <= MAX_CACHED_BODY as u64);
+    if !response.status().is_success() || !small {
+        return Err(response);
+    }
+    let (parts, body) = response.into_parts();
+    let Ok(bytes) = axum::body::to_bytes(body, MAX_CACHED_BODY).await else {
+        return Err(StatusCode::INTERNAL_SERVER_ERROR.into_response());
+    };
+    let starts_run = serde_json::from_slice::<serde_json::Value>(&bytes)
+        .is_ok_and(|v| v.get("run_id").is_some_and(|id| id.is_string()));
+    if starts_run {
+        Ok(CachedResponse {
+            status: parts.status,
+            body: bytes,
+        })
+    } else {
+        Err(Response::from_parts(parts, Body::from(bytes)))
+    }
+}
+
+/// Axum middleware: deduplicate POSTs that carry an `Idempotency-Key`.
+pub async fn idempotency_middleware(
+    State(keys): State<Arc<IdempotencyKeys>>,
+    req: Request,
+    next: Next,
+) -> Response {
+    let key = req
+        .headers()
+        .get(IDEMPOTENCY_KEY_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .filter(|k| !k.trim().is_empty() && req.method() == Method::POST)
+        .map(str::to_owned);
+    let Some(key) = key else {
+        return next.run(req).await;
+    };
+
+    let slot = keys.slot(format!("{} {key}", req.uri().path()));
+    let mut handled = false;
+    let cached = slot
+        .get_or_try_init(|| async {
+            handled = true;
+            cacheable(next.run(req).await).await
+        })
+        .await;
+
+    match cached {
+        Ok(cached) => {
+            let mut response = (
+                cached.status,
+                [(header::CONTENT_TYPE, "application/json")],
+                cached.body.clone(),
+            )
+                .into_response();
+            if !handled {
+                tracing::info!(key = %key, "replaying response for repeated idempotency key");
+                response
+                    .headers_mut()
+                    .insert(REPLAYED_HEADER, HeaderValue::from_static("true"));
+            }
+            response
+        }
+        Err(response) => response,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn oversized_success_passes_through_uncached() {
+        let body = vec![b'x'; MAX_CACHED_BODY + 1];
+        let response = (StatusCode::OK, body.clone()).into_response();
+        let passed = cacheable(response).await.unwrap_err();
+        assert_eq!(passed.status(), StatusCode::OK);
+        let bytes = axum::body::to_bytes(passed.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(bytes.len(), body.len());
+    }
+
+    #[tokio::test]
+    async fn run_start_is_cached() {
+        let response = axum::Json(serde_json::json!({ "run_id": "r1" })).into_response();
+        let cached = cacheable(response).await.unwrap();
+        assert_eq!(cached.status, StatusCode::OK);
+    }
+}
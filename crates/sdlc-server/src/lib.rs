@@ -7,6 +7,7 @@ pub mod error;
 pub mod fleet;
 pub mod heartbeat;
 pub mod hub;
+pub mod idempotency;
 pub mod invite;
 pub mod notify;
 pub mod oauth;
@@ -15,14 +16,17 @@ pub mod pg_orchestrator;
 pub mod pg_telemetry;
 pub mod proxy;
 pub mod routes;
+pub mod run_queue;
 pub mod state;
 pub mod telemetry;
 pub mod tunnel;
 
 use axum::routing::{delete, get, patch, post, put};
 use axum::Router;
+use sdlc_core::config::HttpConfig;
 use std::path::PathBuf;
 use tower_http::cors::{Any, CorsLayer};
+use tower_http::timeout::{RequestBodyTimeoutLayer, TimeoutLayer};
 use tower_http::trace::TraceLayer;
 
 async fn log_request(
@@ -114,13 +118,11 @@ fn build_router_from_state(app_state: state::AppState) -> Router {
         .allow_methods(Any)
         .allow_headers(Any);
 
-    Router::new()
-        // Health — used by Playwright webServer health check and load balancers.
-        // Must respond 200 before any auth middleware so `reuseExistingServer` works.
-        .route(
-            "/api/health",
-            get(|| async { axum::Json(serde_json::json!({"status": "ok"})) }),
-        )
+    let router = Router::new()
+        // Health — used by Playwright webServer health check, k8s probes and
+        // load balancers. Exempt from auth (see auth.rs); 200 unless a
+        // critical subsystem is down so `reuseExistingServer` keeps working.
+        .route("/api/health", get(routes::health::health))
         // Events (SSE) — GET for local, POST for orch-tunnel Quick Tunnels
         // Quick Tunnels intentionally buffer GET streaming responses; POST streaming works.
         .route("/api/events", get(routes::events::sse_events))
@@ -160,6 +162,7 @@ fn build_router_from_state(app_state: state::AppState) -> Router {
             "/api/features/{slug}/next",
             get(routes::features::get_feature_next),
         )
+        .route("/api/next", get(routes::features::get_next_all))
         .route(
             "/api/features/{slug}/transition",
             post(routes::features::transition_feature),
@@ -176,6 +179,8 @@ fn build_router_from_state(app_state: state::AppState) -> Router {
             "/api/features/{slug}/human-qa",
             post(routes::features::submit_human_qa),
         )
+        // Plan distribution
+        .route("/api/plan/distribute", post(routes::plan::distribute_plan))
         // Milestones
         .route("/api/milestones", get(routes::milestones::list_milestones))
         .route(
@@ -190,6 +195,10 @@ fn build_router_from_state(app_state: state::AppState) -> Router {
             "/api/milestones/{slug}/review",
             get(routes::milestones::review_milestone),
         )
+        .route(
+            "/api/milestones/{slug}/burndown",
+            get(routes::milestones::get_milestone_burndown),
+        )
         .route(
             "/api/milestones/{slug}/features",
             post(routes::milestones::add_feature_to_milestone),
@@ -363,6 +372,10 @@ fn build_router_from_state(app_state: state::AppState) -> Router {
             "/api/artifacts/{slug}/{artifact_type}",
             get(routes::artifacts::get_artifact),
         )
+        .route(
+            "/api/artifacts/{slug}/{artifact_type}/history",
+            get(routes::artifacts::get_artifact_history),
+        )
         .route(
             "/api/artifacts/{slug}/{artifact_type}/draft",
             post(routes::artifacts::draft_artifact),
@@ -381,6 +394,14 @@ fn build_router_from_state(app_state: state::AppState) -> Router {
         )
         // Tasks
         .route("/api/features/{slug}/tasks", post(routes::tasks::add_task))
+        .route(
+            "/api/features/{slug}/tasks/bulk",
+            post(routes::tasks::add_tasks_bulk),
+        )
+        .route(
+            "/api/features/{slug}/tasks/order",
+            put(routes::tasks::reorder_tasks),
+        )
         .route(
             "/api/features/{slug}/tasks/{id}/start",
             post(routes::tasks::start_task),
@@ -414,6 +435,7 @@ fn build_router_from_state(app_state: state::AppState) -> Router {
         .route("/api/team/recruit", post(routes::runs::start_team_recruit))
         // Run history
         .route("/api/runs", get(routes::runs::list_runs))
+        .route("/api/runs/stats", get(routes::runs::get_run_stats))
         .route("/api/runs/{id}", get(routes::runs::get_run))
         // Run telemetry
         .route(
@@ -428,6 +450,7 @@ fn build_router_from_state(app_state: state::AppState) -> Router {
         .route("/api/run/{slug}", post(routes::runs::start_run))
         .route("/api/run/{slug}/events", get(routes::runs::run_events))
         .route("/api/run/{slug}/stop", post(routes::runs::stop_run))
+        .route("/api/run/{slug}/inject", post(routes::runs::inject_run))
         // Milestone UAT (agent execution)
         .route(
             "/api/milestone/{slug}/uat",
@@ -585,6 +608,10 @@ fn build_router_from_state(app_state: state::AppState) -> Router {
             get(routes::prepare::get_project_phase),
         )
         .route("/api/project/prepare", get(routes::prepare::get_prepare))
+        .route(
+            "/api/project/summary",
+            get(routes::prepare::get_project_summary),
+        )
         // Query
         .route("/api/query/search", get(routes::query::search))
         .route("/api/query/search-tasks", get(routes::query::search_tasks))
@@ -594,6 +621,7 @@ fn build_router_from_state(app_state: state::AppState) -> Router {
             "/api/query/needs-approval",
             get(routes::query::needs_approval),
         )
+        .route("/api/query/stale", get(routes::query::stale))
         // Feedback
         .route("/api/feedback", get(routes::feedback::list_notes))
         .route("/api/feedback", post(routes::feedback::add_note))
@@ -748,14 +776,33 @@ fn build_router_from_state(app_state: state::AppState) -> Router {
         // OTP verify — public (alongside existing /auth/* routes, bypassed by auth middleware)
         .route("/auth/otp", post(routes::invites::verify_otp))
         .fallback(proxy::proxy_handler)
+        .layer(axum::middleware::from_fn_with_state(
+            app_state.run_idempotency.clone(),
+            idempotency::idempotency_middleware,
+        ))
         .layer(TraceLayer::new_for_http())
         .layer(cors)
         .layer(axum::middleware::from_fn_with_state(
             app_state.tunnel_snapshot.clone(),
             auth::auth_middleware,
         ))
-        .layer(axum::middleware::from_fn(log_request))
-        .with_state(app_state)
+        .layer(axum::middleware::from_fn(log_request));
+    with_http_limits(router, &app_state.http).with_state(app_state)
+}
+
+/// Body size limit and request/read timeouts from the server's HTTP config.
+fn with_http_limits(router: Router<state::AppState>, http: &HttpConfig) -> Router<state::AppState> {
+    let mut router = router.layer(axum::extract::DefaultBodyLimit::max(http.body_limit()));
+    if let Some(timeout) = http.read_timeout() {
+        router = router.layer(RequestBodyTimeoutLayer::new(timeout));
+    }
+    if let Some(timeout) = http.request_timeout() {
+        router = router.layer(TimeoutLayer::with_status_code(
+            axum::http::StatusCode::REQUEST_TIMEOUT,
+            timeout,
+        ));
+    }
+    router
 }
 
 /// Start the SDLC web UI server.
@@ -773,7 +820,14 @@ pub async fn serve(
 ) -> anyhow::Result<()> {
     let addr = format!("0.0.0.0:{port}");
     let listener = tokio::net::TcpListener::bind(&addr).await?;
-    serve_on(root, listener, open_browser, initial_tunnel).await
+    serve_on(
+        root,
+        listener,
+        open_browser,
+        initial_tunnel,
+        HttpConfig::default(),
+    )
+    .await
 }
 
 /// Start the SDLC web UI server on a pre-bound listener.
@@ -785,13 +839,16 @@ pub async fn serve(
 /// Pass `Some((tunnel, token))` when an orch-tunnel was started before
 /// the server (e.g. `sdlc ui --tunnel`). The AppState will be pre-seeded so
 /// the tunnel is immediately reflected in the `/api/tunnel` response.
+///
+/// Fields set in `http` override the `http` section of config.yaml.
 pub async fn serve_on(
     root: PathBuf,
     listener: tokio::net::TcpListener,
     open_browser: bool,
     initial_tunnel: Option<(tunnel::Tunnel, String)>,
+    http: HttpConfig,
 ) -> anyhow::Result<()> {
-    serve_on_with_mode(root, listener, open_browser, initial_tunnel, false).await
+    serve_on_with_mode(root, listener, open_browser, initial_tunnel, false, http).await
 }
 
 /// Start the SDLC server in hub mode on a pre-bound listener.
@@ -800,8 +857,9 @@ pub async fn serve_on_hub(
     root: PathBuf,
     listener: tokio::net::TcpListener,
     open_browser: bool,
+    http: HttpConfig,
 ) -> anyhow::Result<()> {
-    serve_on_with_mode(root, listener, open_browser, None, true).await
+    serve_on_with_mode(root, listener, open_browser, None, true, http).await
 }
 
 async fn serve_on_with_mode(
@@ -810,6 +868,7 @@ async fn serve_on_with_mode(
     open_browser: bool,
     initial_tunnel: Option<(tunnel::Tunnel, String)>,
     hub_mode: bool,
+    http: HttpConfig,
 ) -> anyhow::Result<()> {
     let actual_port = listener.local_addr()?.port();
 
@@ -831,11 +890,12 @@ async fn serve_on_with_mode(
     }
 
     tracing::debug!("initializing app state");
-    let app_state = if hub_mode {
+    let mut app_state = if hub_mode {
         state::AppState::new_with_port_hub(root, actual_port)
     } else {
         state::AppState::new_with_port(root, actual_port)
     };
+    app_state.http = http.or(app_state.http);
     tracing::debug!("app state ready");
 
     if let Some((tun, token)) = initial_tunnel {
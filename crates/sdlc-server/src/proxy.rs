@@ -3,6 +3,10 @@
 //! When orch-tunnel proxies to sdlc-server and the `Host` header matches the
 //! active app tunnel hostname, this handler proxies the request to the user's
 //! local dev server and injects a feedback FAB widget into HTML responses.
+//!
+//! GET and HEAD requests whose connection is refused — typically the dev
+//! server restarting — are retried with exponential backoff; once retries run
+//! out the visitor gets an HTML 502 page naming the upstream address.
 
 use axum::{
     body::Body,
@@ -12,6 +16,8 @@ use axum::{
 };
 use bytes::Bytes;
 use futures::StreamExt;
+use sdlc_core::retry::{retry_with_backoff_async, RetryPolicy};
+use std::time::Duration;
 
 use crate::{embed, state::AppState};
 
@@ -31,6 +37,13 @@ const HOP_BY_HOP: &[&str] = &[
     "upgrade",
 ];
 
+/// Delay before the first retry of a refused upstream connection; doubled for
+/// each further attempt.
+const RETRY_BACKOFF: Duration = Duration::from_millis(100);
+
+/// Longest wait between two retries of a refused upstream connection.
+const RETRY_BACKOFF_MAX: Duration = Duration::from_secs(2);
+
 // ---------------------------------------------------------------------------
 // Feedback FAB widget — injected as an inline <script> before </body>.
 // ---------------------------------------------------------------------------
@@ -138,7 +151,7 @@ pub async fn proxy_handler(State(app): State<AppState>, req: Request) -> Respons
 
     if !is_app_tunnel {
         // Not an app tunnel request — serve the embedded SPA.
-        return embed::static_handler(State(app), req.uri().clone()).await;
+        return embed::static_handler(State(app), req.headers().clone(), req.uri().clone()).await;
     }
 
     // Resolve the upstream port from the app tunnel snapshot.
@@ -188,22 +201,18 @@ pub async fn proxy_handler(State(app): State<AppState>, req: Request) -> Respons
     };
 
     // Send to upstream.
-    let upstream_resp = match app
-        .http_client
-        .request(method, &upstream_url)
-        .headers(req_headers)
-        .body(body_bytes.to_vec())
-        .send()
-        .await
+    let upstream_resp = match send_upstream(
+        &app.http_client,
+        method,
+        &upstream_url,
+        req_headers,
+        body_bytes,
+        app.app_proxy_retries,
+    )
+    .await
     {
         Ok(r) => r,
-        Err(_) => {
-            return Response::builder()
-                .status(502)
-                .header("Content-Type", "text/plain")
-                .body(Body::from("Could not reach app dev server"))
-                .expect("infallible");
-        }
+        Err(_) => return upstream_unavailable(&format!("127.0.0.1:{user_port}")),
     };
 
     // Build response: copy status + headers.
@@ -261,6 +270,62 @@ pub async fn proxy_handler(State(app): State<AppState>, req: Request) -> Respons
 // Helpers
 // ---------------------------------------------------------------------------
 
+/// Send a request to the dev server. GET and HEAD are retried up to `retries`
+/// times while the connection is refused; any other failure is returned as is.
+async fn send_upstream(
+    client: &reqwest::Client,
+    method: reqwest::Method,
+    url: &str,
+    headers: reqwest::header::HeaderMap,
+    body: Bytes,
+    retries: u32,
+) -> Result<reqwest::Response, reqwest::Error> {
+    let idempotent = method == reqwest::Method::GET || method == reqwest::Method::HEAD;
+    let policy = RetryPolicy {
+        max_attempts: if idempotent {
+            retries.saturating_add(1)
+        } else {
+            1
+        },
+        base_delay: RETRY_BACKOFF,
+        max_delay: RETRY_BACKOFF_MAX,
+        ..RetryPolicy::default()
+    };
+    retry_with_backoff_async(
+        &policy,
+        reqwest::Error::is_connect,
+        |_| {
+            client
+                .request(method.clone(), url)
+                .headers(headers.clone())
+                .body(body.clone())
+                .send()
+        },
+        tokio::time::sleep,
+    )
+    .await
+}
+
+/// The 502 page shown when the dev server cannot be reached at `addr`.
+/// Reloads itself so the app reappears once the server is back.
+fn upstream_unavailable(addr: &str) -> Response {
+    let html = format!(
+        "<!doctype html>\n<html><head><meta charset=\"utf-8\">\
+         <meta http-equiv=\"refresh\" content=\"5\">\
+         <title>App unavailable</title></head>\
+         <body style=\"font-family:system-ui,sans-serif;max-width:36rem;margin:4rem auto;color:#333\">\
+         <h1>The app isn't responding</h1>\
+         <p>Could not connect to the dev server at <code>{addr}</code>. \
+         It may be restarting &mdash; this page will retry in a few seconds.</p>\
+         </body></html>\n"
+    );
+    Response::builder()
+        .status(502)
+        .header("Content-Type", "text/html; charset=utf-8")
+        .body(Body::from(html))
+        .expect("infallible")
+}
+
 /// Inject the feedback widget `<script>` immediately before `</body>`.
 /// If the response is not valid UTF-8 or has no `</body>`, append at end.
 pub fn inject_widget(body: Bytes) -> Vec<u8> {
@@ -367,4 +432,72 @@ mod tests {
         let uri: Uri = "/".parse().unwrap();
         assert_eq!(build_upstream_uri(3000, &uri), "http://127.0.0.1:3000/");
     }
+
+    /// A local port with nothing listening on it.
+    fn refused_port() -> u16 {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        listener.local_addr().unwrap().port()
+    }
+
+    #[tokio::test]
+    async fn refused_get_is_retried_until_upstream_accepts() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let port = refused_port();
+        let server = tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(150)).await;
+            let listener = tokio::net::TcpListener::bind(("127.0.0.1", port))
+                .await
+                .unwrap();
+            let (mut sock, _) = listener.accept().await.unwrap();
+            let mut buf = [0u8; 1024];
+            let _ = sock.read(&mut buf).await.unwrap();
+            sock.write_all(b"HTTP/1.1 200 OK\r\ncontent-length: 2\r\nconnection: close\r\n\r\nok")
+                .await
+                .unwrap();
+        });
+
+        let resp = send_upstream(
+            &reqwest::Client::new(),
+            reqwest::Method::GET,
+            &format!("http://127.0.0.1:{port}/"),
+            reqwest::header::HeaderMap::new(),
+            Bytes::new(),
+            5,
+        )
+        .await
+        .unwrap();
+        assert_eq!(resp.status(), 200);
+        assert_eq!(resp.text().await.unwrap(), "ok");
+        server.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn exhausted_retries_yield_friendly_502() {
+        let port = refused_port();
+        let err = send_upstream(
+            &reqwest::Client::new(),
+            reqwest::Method::GET,
+            &format!("http://127.0.0.1:{port}/"),
+            reqwest::header::HeaderMap::new(),
+            Bytes::new(),
+            2,
+        )
+        .await
+        .unwrap_err();
+        assert!(err.is_connect());
+
+        let addr = format!("127.0.0.1:{port}");
+        let resp = upstream_unavailable(&addr);
+        assert_eq!(resp.status(), 502);
+        assert!(resp.headers()["content-type"]
+            .to_str()
+            .unwrap()
+            .starts_with("text/html"));
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body = String::from_utf8(body.to_vec()).unwrap();
+        assert!(body.contains(&addr));
+    }
 }
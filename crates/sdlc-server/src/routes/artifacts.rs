@@ -1,14 +1,38 @@
-use axum::extract::{Path, State};
+use axum::extract::{Path, Query, State};
+use axum::http::header;
+use axum::response::{IntoResponse, Response};
 use axum::Json;
 
 use crate::error::AppError;
 use crate::state::AppState;
 
-/// GET /api/artifacts/:slug/:type — artifact markdown content + status.
+/// Response shape for [`get_artifact`].
+#[derive(Debug, Clone, Copy, Default, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ArtifactFormat {
+    /// Status fields plus the markdown as written (the default).
+    #[default]
+    Raw,
+    /// The markdown rendered to sanitized HTML, served as `text/html`.
+    Html,
+    /// The content plus metadata: feature phase, status, word count, and
+    /// file modification time.
+    Json,
+}
+
+#[derive(serde::Deserialize)]
+pub struct ArtifactQuery {
+    #[serde(default)]
+    pub format: ArtifactFormat,
+}
+
+/// GET /api/artifacts/:slug/:type?format=raw|html|json — artifact markdown
+/// content + status.
 pub async fn get_artifact(
     State(app): State<AppState>,
     Path((slug, artifact_type)): Path<(String, String)>,
-) -> Result<Json<serde_json::Value>, AppError> {
+    Query(query): Query<ArtifactQuery>,
+) -> Result<Response, AppError> {
     let root = app.root.clone();
     let result = tokio::task::spawn_blocking(move || {
         let feature = sdlc_core::feature::Feature::load(&root, &slug)?;
@@ -20,23 +44,97 @@ pub async fn get_artifact(
             .ok_or_else(|| sdlc_core::SdlcError::ArtifactNotFound(artifact_type.clone()))?;
 
         // Read content from disk if it exists
+        let path = root.join(&artifact.path);
         let content = if artifact.exists_on_disk(&root) {
-            std::fs::read_to_string(root.join(&artifact.path)).ok()
+            std::fs::read_to_string(&path).ok()
         } else {
             None
         };
 
+        let response = match query.format {
+            ArtifactFormat::Raw => Json(serde_json::json!({
+                "artifact_type": at,
+                "status": artifact.status,
+                "path": artifact.path,
+                "content": content,
+                "approved_at": artifact.approved_at,
+                "approved_by": artifact.approved_by,
+                "rejected_at": artifact.rejected_at,
+                "rejection_reason": artifact.rejection_reason,
+                "waived_at": artifact.waived_at,
+                "waive_reason": artifact.waive_reason,
+            }))
+            .into_response(),
+            ArtifactFormat::Html => {
+                let content = content.ok_or(sdlc_core::SdlcError::ArtifactNotFound(
+                    artifact_type.clone(),
+                ))?;
+                (
+                    [(header::CONTENT_TYPE, "text/html; charset=utf-8")],
+                    render_markdown(&content),
+                )
+                    .into_response()
+            }
+            ArtifactFormat::Json => {
+                let last_modified = content
+                    .as_ref()
+                    .and_then(|_| std::fs::metadata(&path).ok()?.modified().ok())
+                    .map(chrono::DateTime::<chrono::Utc>::from);
+                Json(serde_json::json!({
+                    "slug": feature.slug,
+                    "artifact_type": at,
+                    "phase": feature.phase,
+                    "status": artifact.status,
+                    "approved_at": artifact.approved_at,
+                    "approved_by": artifact.approved_by,
+                    "path": artifact.path,
+                    "word_count": content.as_deref().map_or(0, |c| c.split_whitespace().count()),
+                    "last_modified": last_modified,
+                    "content": content,
+                }))
+                .into_response()
+            }
+        };
+        Ok::<_, sdlc_core::SdlcError>(response)
+    })
+    .await
+    .map_err(|e| AppError(anyhow::anyhow!("task join error: {e}")))??;
+
+    Ok(result)
+}
+
+/// Render artifact markdown to HTML with scripts, event handlers, and other
+/// active content stripped.
+fn render_markdown(markdown: &str) -> String {
+    use pulldown_cmark::{html, Options, Parser};
+
+    let options =
+        Options::ENABLE_TABLES | Options::ENABLE_STRIKETHROUGH | Options::ENABLE_TASKLISTS;
+    let mut rendered = String::with_capacity(markdown.len() * 3 / 2);
+    html::push_html(&mut rendered, Parser::new_ext(markdown, options));
+    ammonia::clean(&rendered)
+}
+
+/// GET /api/artifacts/:slug/:type/history — approve / reject / waive
+/// decisions on an artifact, oldest first.
+pub async fn get_artifact_history(
+    State(app): State<AppState>,
+    Path((slug, artifact_type)): Path<(String, String)>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let root = app.root.clone();
+    let result = tokio::task::spawn_blocking(move || {
+        let feature = sdlc_core::feature::Feature::load(&root, &slug)?;
+        let at: sdlc_core::types::ArtifactType =
+            artifact_type.parse().map_err(|e: sdlc_core::SdlcError| e)?;
+
+        let artifact = feature
+            .artifact(at)
+            .ok_or_else(|| sdlc_core::SdlcError::ArtifactNotFound(artifact_type.clone()))?;
+
         Ok::<_, sdlc_core::SdlcError>(serde_json::json!({
+            "slug": slug,
             "artifact_type": at,
-            "status": artifact.status,
-            "path": artifact.path,
-            "content": content,
-            "approved_at": artifact.approved_at,
-            "approved_by": artifact.approved_by,
-            "rejected_at": artifact.rejected_at,
-            "rejection_reason": artifact.rejection_reason,
-            "waived_at": artifact.waived_at,
-            "waive_reason": artifact.waive_reason,
+            "history": artifact.history,
         }))
     })
     .await
@@ -62,7 +160,7 @@ pub async fn approve_artifact(
         let at: sdlc_core::types::ArtifactType =
             artifact_type.parse().map_err(|e: sdlc_core::SdlcError| e)?;
 
-        feature.approve_artifact(at, body.by)?;
+        feature.approve_artifact(&root, at, body.by)?;
         feature.save(&root)?;
 
         let transitioned_to = sdlc_core::classifier::try_auto_transition(&root, &slug);
@@ -100,7 +198,7 @@ pub async fn reject_artifact(
         let at: sdlc_core::types::ArtifactType =
             artifact_type.parse().map_err(|e: sdlc_core::SdlcError| e)?;
 
-        feature.reject_artifact(at, body.reason)?;
+        feature.reject_artifact(&root, at, body.reason)?;
         feature.save(&root)?;
 
         let transitioned_to = sdlc_core::classifier::try_auto_transition(&root, &slug);
@@ -132,6 +230,14 @@ pub async fn draft_artifact(
         let at: sdlc_core::types::ArtifactType =
             artifact_type.parse().map_err(|e: sdlc_core::SdlcError| e)?;
 
+        let secret_warnings = match feature.artifact(at) {
+            Some(artifact) if artifact.exists_on_disk(&root) => {
+                let content = std::fs::read_to_string(root.join(&artifact.path))?;
+                sdlc_core::artifact::check_for_secrets(&root, &format!("{slug}/{at}"), &content)?
+            }
+            _ => Vec::new(),
+        };
+
         feature.mark_artifact_draft(at)?;
         feature.save(&root)?;
 
@@ -145,6 +251,9 @@ pub async fn draft_artifact(
         if let Some(phase) = transitioned_to {
             val["transitioned_to"] = serde_json::Value::String(phase);
         }
+        if !secret_warnings.is_empty() {
+            val["secret_warnings"] = serde_json::json!(secret_warnings);
+        }
         Ok::<_, sdlc_core::SdlcError>(val)
     })
     .await
@@ -156,6 +265,10 @@ pub async fn draft_artifact(
 #[derive(serde::Deserialize)]
 pub struct WaiveBody {
     pub reason: Option<String>,
+    /// Lets the waiver lapse at this time; the artifact is required again
+    /// afterwards. Must be in the future.
+    #[serde(default)]
+    pub expires_at: Option<chrono::DateTime<chrono::Utc>>,
 }
 
 /// POST /api/artifacts/:slug/:type/waive — waive an artifact.
@@ -164,13 +277,16 @@ pub async fn waive_artifact(
     Path((slug, artifact_type)): Path<(String, String)>,
     Json(body): Json<WaiveBody>,
 ) -> Result<Json<serde_json::Value>, AppError> {
+    if body.expires_at.is_some_and(|at| at <= chrono::Utc::now()) {
+        return Err(AppError::bad_request("expires_at must be in the future"));
+    }
     let root = app.root.clone();
     let result = tokio::task::spawn_blocking(move || {
         let mut feature = sdlc_core::feature::Feature::load(&root, &slug)?;
         let at: sdlc_core::types::ArtifactType =
             artifact_type.parse().map_err(|e: sdlc_core::SdlcError| e)?;
 
-        feature.waive_artifact(at, body.reason)?;
+        feature.waive_artifact(&root, at, body.reason, body.expires_at)?;
         feature.save(&root)?;
 
         let transitioned_to = sdlc_core::classifier::try_auto_transition(&root, &slug);
@@ -179,6 +295,7 @@ pub async fn waive_artifact(
             "slug": slug,
             "artifact_type": at,
             "status": "waived",
+            "expires_at": body.expires_at,
         });
         if let Some(phase) = transitioned_to {
             val["transitioned_to"] = serde_json::Value::String(phase);
@@ -190,3 +307,53 @@ pub async fn waive_artifact(
 
     Ok(Json(result))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spec(slug: &str) -> Path<(String, String)> {
+        Path((slug.to_string(), "spec".to_string()))
+    }
+
+    #[tokio::test]
+    async fn history_lists_decisions_in_order() {
+        let dir = tempfile::TempDir::new().unwrap();
+        sdlc_core::feature::Feature::create(dir.path(), "auth", "Auth").unwrap();
+        let app = AppState::new_for_test(dir.path().to_path_buf());
+
+        let by = |name: &str| {
+            Json(ApproveBody {
+                by: Some(name.to_string()),
+            })
+        };
+        let Json(approved) = approve_artifact(State(app.clone()), spec("auth"), by("alice"))
+            .await
+            .unwrap();
+        let reason = Json(RejectBody {
+            reason: Some("too vague".to_string()),
+        });
+        let Json(rejected) = reject_artifact(State(app.clone()), spec("auth"), reason)
+            .await
+            .unwrap();
+        let Json(reapproved) = approve_artifact(State(app.clone()), spec("auth"), by("bob"))
+            .await
+            .unwrap();
+        assert_eq!(approved["status"], "approved");
+        assert_eq!(rejected["status"], "rejected");
+        assert_eq!(reapproved["status"], "approved");
+
+        let Json(body) = get_artifact_history(State(app), spec("auth"))
+            .await
+            .unwrap();
+        let history = body["history"].as_array().unwrap();
+        let decisions: Vec<_> = history
+            .iter()
+            .map(|e| e["decision"].as_str().unwrap())
+            .collect();
+        assert_eq!(decisions, vec!["approved", "rejected", "approved"]);
+        assert_eq!(history[0]["actor"], "alice");
+        assert_eq!(history[1]["reason"], "too vague");
+        assert_eq!(history[2]["actor"], "bob");
+    }
+}
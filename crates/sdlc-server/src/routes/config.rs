@@ -30,6 +30,9 @@ pub struct UpdateConfigBody {
 }
 
 /// PATCH /api/config — update `project.name` and/or `project.description` in `.sdlc/config.yaml`.
+///
+/// Returns `{ config, changes }`: the merged config plus one
+/// [`sdlc_core::config::FieldChange`] per field the patch actually changed.
 pub async fn update_config(
     State(app): State<AppState>,
     Json(body): Json<UpdateConfigBody>,
@@ -52,6 +55,7 @@ pub async fn update_config(
             }
             Err(e) => return Err(e),
         };
+        let before = config.clone();
         if let Some(name) = body.name {
             let name = name.trim().to_string();
             if !name.is_empty() {
@@ -61,9 +65,12 @@ pub async fn update_config(
         if let Some(description) = body.description {
             config.project.description = Some(description);
         }
+        let changes = before.diff(&config)?;
         config.save(&root)?;
-        let json = serde_json::to_value(&config)?;
-        Ok::<_, sdlc_core::SdlcError>(json)
+        Ok::<_, sdlc_core::SdlcError>(serde_json::json!({
+            "config": config,
+            "changes": changes,
+        }))
     })
     .await
     .map_err(|e| AppError(anyhow::anyhow!("task join error: {e}")))??;
@@ -97,4 +104,33 @@ mod tests {
         assert_eq!(json["project"]["name"], "test-project");
         assert_eq!(json["version"], 1);
     }
+
+    #[tokio::test]
+    async fn update_config_reports_exactly_the_changed_fields() {
+        let dir = tempfile::TempDir::new().unwrap();
+        sdlc_core::io::ensure_dir(&dir.path().join(".sdlc")).unwrap();
+        sdlc_core::config::Config::new("test-project")
+            .save(dir.path())
+            .unwrap();
+        let patch = |name: Option<&str>, description: Option<&str>| UpdateConfigBody {
+            name: name.map(str::to_string),
+            description: description.map(str::to_string),
+        };
+
+        let app = AppState::new(dir.path().to_path_buf());
+        let Json(json) = update_config(State(app.clone()), Json(patch(None, Some("Ships"))))
+            .await
+            .unwrap();
+        assert_eq!(
+            json["changes"],
+            serde_json::json!([{"path": "project.description", "old": null, "new": "Ships"}])
+        );
+        assert_eq!(json["config"]["project"]["description"], "Ships");
+
+        let Json(json) =
+            update_config(State(app), Json(patch(Some("test-project"), Some("Ships"))))
+                .await
+                .unwrap();
+        assert_eq!(json["changes"], serde_json::json!([]));
+    }
 }
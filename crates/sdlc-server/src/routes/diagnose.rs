@@ -1,6 +1,15 @@
 use axum::{extract::State, Json};
 use claude_agent::{query_with, types::AgentEvent, PermissionMode, QueryOptions};
+use sdlc_core::{
+    classifier::{Classifier, EvalContext},
+    config::Config,
+    feature::Feature,
+    rules::default_rules,
+    types::Phase,
+};
 use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::Path;
 use tokio_stream::StreamExt as _;
 
 use crate::{error::AppError, state::AppState};
@@ -25,6 +34,38 @@ pub struct DiagnoseResult {
     pub confidence: String,
 }
 
+/// `DiagnoseResult` plus findings from comparing it against existing
+/// features, so the UI can offer them as a checklist before creating a fix.
+#[derive(Debug, Serialize)]
+pub struct DiagnoseReport {
+    #[serde(flatten)]
+    pub result: DiagnoseResult,
+    /// Highest confidence first.
+    pub findings: Vec<DiagnoseFinding>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FindingKind {
+    /// An existing feature already covers this issue.
+    DuplicateCandidate,
+    /// An in-flight feature touches the same area; the fix should depend on it.
+    MissingDependency,
+    /// A released feature covers similar scope; the issue may be a regression.
+    ScopeOverlap,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DiagnoseFinding {
+    pub kind: FindingKind,
+    /// Slug of the existing feature the finding refers to.
+    pub feature: String,
+    pub title: String,
+    /// Title similarity in `0.0..=1.0`.
+    pub confidence: f32,
+    pub suggested_action: String,
+}
+
 // ---------------------------------------------------------------------------
 // Agent options — filesystem-only, no sdlc MCP
 // ---------------------------------------------------------------------------
@@ -116,6 +157,106 @@ fn parse_result(json: &serde_json::Value, description: &str) -> DiagnoseResult {
     }
 }
 
+// ---------------------------------------------------------------------------
+// Findings
+// ---------------------------------------------------------------------------
+
+/// Similarity at or above which an existing feature is a duplicate candidate.
+pub const DUPLICATE_THRESHOLD: f32 = 0.6;
+
+/// Similarity below which an existing feature is considered unrelated.
+const OVERLAP_THRESHOLD: f32 = 0.3;
+
+/// Words that carry no signal in a fix title.
+const STOPWORDS: &[&str] = &[
+    "a", "an", "the", "and", "or", "of", "to", "in", "on", "for", "with", "fix", "bug",
+];
+
+/// Lowercased title words with stopwords removed and a trailing plural `s`
+/// dropped, so "Fix login redirects" and "login-redirect" compare equal.
+fn title_tokens(text: &str) -> HashSet<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .map(str::to_lowercase)
+        .filter(|w| !w.is_empty() && !STOPWORDS.contains(&w.as_str()))
+        .map(|w| match w.strip_suffix('s') {
+            Some(stem) if stem.len() >= 3 && !stem.ends_with('s') => stem.to_string(),
+            _ => w,
+        })
+        .collect()
+}
+
+/// Jaccard similarity of the two token sets.
+fn similarity(a: &HashSet<String>, b: &HashSet<String>) -> f32 {
+    let union = a.union(b).count();
+    if union == 0 {
+        return 0.0;
+    }
+    let score = a.intersection(b).count() as f32 / union as f32;
+    (score * 100.0).round() / 100.0
+}
+
+/// Compare a diagnosis title against every active feature in the project.
+pub fn project_findings(root: &Path, title: &str) -> sdlc_core::Result<Vec<DiagnoseFinding>> {
+    let candidate = title_tokens(title);
+    let config = Config::load(root)?;
+    let state = sdlc_core::state::State::load(root)?;
+    let classifier = Classifier::new(default_rules());
+
+    let mut findings = Vec::new();
+    for feature in Feature::list(root)?.iter().filter(|f| !f.archived) {
+        let confidence = similarity(&candidate, &title_tokens(&feature.title));
+        let slug = &feature.slug;
+        let (kind, suggested_action) = if confidence >= DUPLICATE_THRESHOLD {
+            let directive = classifier.classify(&EvalContext::new(feature, &state, &config, root));
+            let action = if feature.phase == Phase::Released {
+                format!("Reopen work on `{slug}` instead of creating a new feature")
+            } else {
+                format!(
+                    "Continue `{slug}` instead of creating a new feature: {}",
+                    directive.next_command
+                )
+            };
+            (FindingKind::DuplicateCandidate, action)
+        } else if confidence < OVERLAP_THRESHOLD {
+            continue;
+        } else if feature.phase == Phase::Released {
+            (
+                FindingKind::ScopeOverlap,
+                format!("Check whether this is a regression in released feature `{slug}`"),
+            )
+        } else {
+            (
+                FindingKind::MissingDependency,
+                format!("Add `{slug}` as a dependency: sdlc feature update <new-slug> --depends-on {slug}"),
+            )
+        };
+        findings.push(DiagnoseFinding {
+            kind,
+            feature: slug.clone(),
+            title: feature.title.clone(),
+            confidence,
+            suggested_action,
+        });
+    }
+    findings.sort_by(|a, b| b.confidence.total_cmp(&a.confidence));
+    Ok(findings)
+}
+
+/// Attach findings to a diagnosis. Triage never fails on them: a project
+/// that cannot be read just yields no findings.
+async fn into_report(root: std::path::PathBuf, result: DiagnoseResult) -> DiagnoseReport {
+    let title = result.title.clone();
+    let findings = tokio::task::spawn_blocking(move || project_findings(&root, &title))
+        .await
+        .map_err(|e| e.to_string())
+        .and_then(|r| r.map_err(|e| e.to_string()))
+        .unwrap_or_else(|e| {
+            tracing::warn!(error = %e, "diagnose findings unavailable");
+            Vec::new()
+        });
+    DiagnoseReport { result, findings }
+}
+
 // ---------------------------------------------------------------------------
 // Handler
 // ---------------------------------------------------------------------------
@@ -129,10 +270,12 @@ const DIAGNOSE_TIMEOUT_SECS: u64 = 60;
 /// The request body is `{ "description": "..." }` — any text the user pasted
 /// (stack trace, vague description, or anything else). The agent determines
 /// what to do with it; random/non-software input yields `confidence: "none"`.
+/// The response adds `findings` from comparing the diagnosis with existing
+/// features (see [`project_findings`]).
 pub async fn diagnose(
     State(app): State<AppState>,
     Json(body): Json<DiagnoseRequest>,
-) -> Result<Json<DiagnoseResult>, AppError> {
+) -> Result<Json<DiagnoseReport>, AppError> {
     let description = body.description.trim().to_string();
     if description.is_empty() {
         return Err(AppError::bad_request("description is required"));
@@ -203,15 +346,77 @@ For vague descriptions with no file paths, still search the codebase and set con
             timeout_secs = DIAGNOSE_TIMEOUT_SECS,
             "Diagnosis agent timed out"
         );
-        return Ok(Json(fallback_result(&description)));
+        return Ok(Json(
+            into_report(app.root.clone(), fallback_result(&description)).await,
+        ));
+    }
+
+    let result = match extract_json(&result_text) {
+        Some(v) => parse_result(&v, &description),
+        None => fallback_result(&description),
+    };
+    Ok(Json(into_report(app.root.clone(), result).await))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sdlc_core::state::State;
+    use tempfile::TempDir;
+
+    fn project(features: &[(&str, &str)]) -> TempDir {
+        let dir = TempDir::new().unwrap();
+        Config::new("test").save(dir.path()).unwrap();
+        State::new("test").save(dir.path()).unwrap();
+        for (slug, title) in features {
+            Feature::create(dir.path(), *slug, *title).unwrap();
+        }
+        dir
     }
 
-    if result_text.is_empty() {
-        return Ok(Json(fallback_result(&description)));
+    #[test]
+    fn near_identical_title_is_a_duplicate_candidate() {
+        let dir = project(&[
+            ("fix-login-redirect-loop", "Fix Login Redirect Loop"),
+            ("billing-export", "Billing CSV export"),
+        ]);
+
+        let findings = project_findings(dir.path(), "login-redirect-loops").unwrap();
+        assert_eq!(findings.len(), 1, "{findings:?}");
+        let f = &findings[0];
+        assert_eq!(f.kind, FindingKind::DuplicateCandidate);
+        assert_eq!(f.feature, "fix-login-redirect-loop");
+        assert!(f.confidence >= DUPLICATE_THRESHOLD, "{}", f.confidence);
+        assert!(f.suggested_action.contains("fix-login-redirect-loop"));
+    }
+
+    #[test]
+    fn partial_overlap_classifies_by_phase() {
+        let dir = project(&[("login-sso", "Login SSO redirect")]);
+        let mut released =
+            Feature::create(dir.path(), "login-form", "Login form redirect").unwrap();
+        released.phase = Phase::Released;
+        released.save(dir.path()).unwrap();
+
+        let findings = project_findings(dir.path(), "login-redirect-timeout-error").unwrap();
+        let kinds: Vec<_> = findings
+            .iter()
+            .map(|f| (f.feature.as_str(), f.kind))
+            .collect();
+        assert!(kinds.contains(&("login-sso", FindingKind::MissingDependency)));
+        assert!(kinds.contains(&("login-form", FindingKind::ScopeOverlap)));
+        assert!(findings.iter().all(|f| f.confidence < DUPLICATE_THRESHOLD));
     }
 
-    match extract_json(&result_text) {
-        Some(v) => Ok(Json(parse_result(&v, &description))),
-        None => Ok(Json(fallback_result(&description))),
+    #[test]
+    fn report_serializes_result_fields_at_top_level() {
+        let report = DiagnoseReport {
+            result: fallback_result("the login page loops forever"),
+            findings: vec![],
+        };
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["confidence"], "low");
+        assert!(json["title"].is_string());
+        assert!(json["findings"].as_array().unwrap().is_empty());
     }
 }
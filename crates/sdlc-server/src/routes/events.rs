@@ -88,6 +88,16 @@ pub async fn sse_events(State(app): State<AppState>) -> impl axum::response::Int
             .to_string();
             Some(Ok(Event::default().event("run").data(data)))
         }
+        Ok(SseMessage::RunStatus { id, key, status }) => {
+            let data = serde_json::json!({
+                "type": "run_status",
+                "id": id,
+                "key": key,
+                "status": status,
+            })
+            .to_string();
+            Some(Ok(Event::default().event("run").data(data)))
+        }
         Ok(SseMessage::RunFinished {
             id,
             key,
@@ -315,5 +325,8 @@ pub async fn sse_events(State(app): State<AppState>) -> impl axum::response::Int
         header::HeaderName::from_static("x-accel-buffering"),
         HeaderValue::from_static("no"),
     );
-    (headers, Sse::new(padded).keep_alive(KeepAlive::default()))
+    (
+        headers,
+        Sse::new(padded).keep_alive(KeepAlive::new().interval(app.http.keep_alive())),
+    )
 }
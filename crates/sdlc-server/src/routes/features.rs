@@ -1,4 +1,4 @@
-use axum::extract::{Path, State};
+use axum::extract::{Path, Query, State};
 use axum::Json;
 
 use crate::error::AppError;
@@ -21,6 +21,7 @@ pub async fn list_features(
                     "title": f.title,
                     "description": f.description,
                     "phase": f.phase,
+                    "priority": f.priority,
                     "archived": f.archived,
                     "blocked": f.is_blocked(),
                     "task_summary": sdlc_core::task::summarize(&f.tasks),
@@ -80,6 +81,7 @@ pub async fn get_feature(
             "title": f.title,
             "description": f.description,
             "phase": f.phase,
+            "priority": f.priority,
             "archived": f.archived,
             "blocked": f.is_blocked(),
             "blockers": f.blockers,
@@ -113,12 +115,7 @@ pub async fn get_feature_directive(
         let state = sdlc_core::state::State::load(&root)?;
         let feature = sdlc_core::feature::Feature::load(&root, &slug)?;
 
-        let ctx = sdlc_core::classifier::EvalContext {
-            feature: &feature,
-            state: &state,
-            config: &config,
-            root: &root,
-        };
+        let ctx = sdlc_core::classifier::EvalContext::new(&feature, &state, &config, &root);
         let classifier = sdlc_core::classifier::Classifier::new(sdlc_core::rules::default_rules());
         Ok::<_, sdlc_core::SdlcError>(classifier.classify(&ctx))
     })
@@ -128,6 +125,19 @@ pub async fn get_feature_directive(
     Ok(Json(classification))
 }
 
+/// GET /api/next — the directive for every active feature, classified against
+/// one load of config and state.
+pub async fn get_next_all(
+    State(app): State<AppState>,
+) -> Result<Json<Vec<sdlc_core::classifier::Classification>>, AppError> {
+    let root = app.root.clone();
+    let directives = tokio::task::spawn_blocking(move || sdlc_core::classifier::next_all(&root))
+        .await
+        .map_err(|e| AppError(anyhow::anyhow!("task join error: {e}")))??;
+
+    Ok(Json(directives.into_iter().map(|(_, c)| c).collect()))
+}
+
 /// GET /api/features/:slug/next — classify next action.
 pub async fn get_feature_next(
     State(app): State<AppState>,
@@ -139,12 +149,7 @@ pub async fn get_feature_next(
         let state = sdlc_core::state::State::load(&root)?;
         let feature = sdlc_core::feature::Feature::load(&root, &slug)?;
 
-        let ctx = sdlc_core::classifier::EvalContext {
-            feature: &feature,
-            state: &state,
-            config: &config,
-            root: &root,
-        };
+        let ctx = sdlc_core::classifier::EvalContext::new(&feature, &state, &config, &root);
         let classifier = sdlc_core::classifier::Classifier::new(sdlc_core::rules::default_rules());
         let c = classifier.classify(&ctx);
 
@@ -154,6 +159,7 @@ pub async fn get_feature_next(
             "description": c.description,
             "current_phase": c.current_phase,
             "action": c.action,
+            "rationale": c.rationale,
             "message": c.message,
             "next_command": c.next_command,
             "output_path": c.output_path,
@@ -171,7 +177,9 @@ pub async fn get_feature_next(
 
 #[derive(serde::Deserialize)]
 pub struct CreateFeatureBody {
-    pub slug: String,
+    /// Derived from `title` when omitted.
+    #[serde(default)]
+    pub slug: Option<String>,
     pub title: String,
     #[serde(default)]
     pub description: Option<String>,
@@ -184,18 +192,18 @@ pub async fn create_feature(
 ) -> Result<Json<serde_json::Value>, AppError> {
     let root = app.root.clone();
     let result = tokio::task::spawn_blocking(move || {
+        let slug = body
+            .slug
+            .unwrap_or_else(|| sdlc_core::slug::derive(&body.title));
         let f = sdlc_core::feature::Feature::create_with_description(
             &root,
-            body.slug,
+            slug,
             body.title,
             body.description,
         )?;
 
         // Add to active features in state
-        if let Ok(mut state) = sdlc_core::state::State::load(&root) {
-            state.add_active_feature(&f.slug);
-            let _ = state.save(&root);
-        }
+        let _ = sdlc_core::state::State::update(&root, |state| state.add_active_feature(&f.slug));
 
         Ok::<_, sdlc_core::SdlcError>(serde_json::json!({
             "slug": f.slug,
@@ -209,10 +217,20 @@ pub async fn create_feature(
     Ok(Json(result))
 }
 
+#[derive(serde::Deserialize, Default)]
+pub struct MergeQuery {
+    /// Merge even if dependencies are not yet released.
+    #[serde(default)]
+    pub force: bool,
+}
+
 /// POST /api/features/:slug/merge — finalize the merge phase, transitioning to released.
+///
+/// Refused while any dependency is unreleased unless `?force=true`.
 pub async fn merge_feature(
     State(app): State<AppState>,
     Path(slug): Path<String>,
+    Query(query): Query<MergeQuery>,
 ) -> Result<Json<serde_json::Value>, AppError> {
     let root = app.root.clone();
     let result = tokio::task::spawn_blocking(move || {
@@ -226,18 +244,34 @@ pub async fn merge_feature(
             )));
         }
 
+        if !query.force {
+            feature.ensure_dependencies_released(&root)?;
+        }
+
+        let check = sdlc_core::git::verify_clean_merge(&root, config.base_branch())?;
+        if let Some(warning) = check.warning() {
+            tracing::warn!(slug = %slug, "{warning}");
+        }
+
         feature.transition(Phase::Released, &config)?;
         feature.save(&root)?;
 
-        let mut state = sdlc_core::state::State::load(&root)?;
-        state.record_action(&slug, ActionType::Merge, Phase::Released, "merged");
-        state.complete_directive(&slug);
-        state.save(&root)?;
+        let state = sdlc_core::state::State::update(&root, |state| {
+            state.record_action(&slug, ActionType::Merge, Phase::Released, "merged");
+            state.complete_directive(&slug);
+            state.clone()
+        })?;
+        let changelog = sdlc_core::feature::write_changelog(&root, &feature, &state)
+            .unwrap_or_else(|e| {
+                tracing::warn!(slug = %slug, error = %e, "CHANGELOG.md write failed");
+                false
+            });
 
         Ok::<_, sdlc_core::SdlcError>(serde_json::json!({
             "slug": slug,
             "phase": "released",
             "merged": true,
+            "changelog": changelog,
         }))
     })
     .await
@@ -0,0 +1,247 @@
+use std::future::Future;
+use std::time::Duration;
+
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::Json;
+use serde::Serialize;
+
+use crate::run_queue::QueuedGroup;
+use crate::state::AppState;
+
+/// Upper bound for any single check. A check that overruns is reported as
+/// `down` instead of holding up the whole report.
+const CHECK_TIMEOUT: Duration = Duration::from_secs(3);
+
+// ---------------------------------------------------------------------------
+// Types
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ComponentStatus {
+    Ok,
+    Down,
+    /// Not applicable to this server (e.g. no tunnel running).
+    Disabled,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ComponentHealth {
+    pub name: &'static str,
+    pub status: ComponentStatus,
+    /// A critical component being down turns the whole report into a 503.
+    pub critical: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HealthStatus {
+    Ok,
+    /// Only non-critical components are down; the server is still usable.
+    Degraded,
+    Down,
+}
+
+/// Agent run capacity: free slots and the runs queued for one.
+#[derive(Debug, Clone, Serialize)]
+pub struct RunSlots {
+    pub free: usize,
+    pub queued: Vec<QueuedGroup>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct HealthReport {
+    pub status: HealthStatus,
+    pub components: Vec<ComponentHealth>,
+    pub runs: RunSlots,
+}
+
+impl HealthReport {
+    fn from_components(components: Vec<ComponentHealth>, runs: RunSlots) -> Self {
+        let down = |critical: bool| {
+            components
+                .iter()
+                .any(|c| c.critical == critical && c.status == ComponentStatus::Down)
+        };
+        let status = if down(true) {
+            HealthStatus::Down
+        } else if down(false) {
+            HealthStatus::Degraded
+        } else {
+            HealthStatus::Ok
+        };
+        Self {
+            status,
+            components,
+            runs,
+        }
+    }
+
+    fn http_status(&self) -> StatusCode {
+        match self.status {
+            HealthStatus::Down => StatusCode::SERVICE_UNAVAILABLE,
+            HealthStatus::Ok | HealthStatus::Degraded => StatusCode::OK,
+        }
+    }
+}
+
+type Probe = (ComponentStatus, Option<String>);
+
+// ---------------------------------------------------------------------------
+// Checks
+// ---------------------------------------------------------------------------
+
+async fn run_check(
+    name: &'static str,
+    critical: bool,
+    limit: Duration,
+    probe: impl Future<Output = Probe>,
+) -> ComponentHealth {
+    let (status, detail) = tokio::time::timeout(limit, probe)
+        .await
+        .unwrap_or_else(|_| {
+            (
+                ComponentStatus::Down,
+                Some(format!("timed out after {}ms", limit.as_millis())),
+            )
+        });
+    ComponentHealth {
+        name,
+        status,
+        critical,
+        detail,
+    }
+}
+
+/// Run a filesystem- or process-bound probe off the async runtime.
+async fn blocking(probe: impl FnOnce() -> Probe + Send + 'static) -> Probe {
+    tokio::task::spawn_blocking(probe)
+        .await
+        .unwrap_or_else(|e| (ComponentStatus::Down, Some(format!("check failed: {e}"))))
+}
+
+/// `.sdlc/` exists and `state.yaml` parses. The hub serves no project of
+/// its own, so the check does not apply there.
+fn probe_sdlc_dir(app: &AppState) -> impl Future<Output = Probe> {
+    let root = app.root.clone();
+    let hub = app.hub_registry.is_some();
+    blocking(move || {
+        if hub {
+            return (ComponentStatus::Disabled, Some("hub mode".into()));
+        }
+        match sdlc_core::state::State::load(&root) {
+            Ok(_) => (ComponentStatus::Ok, None),
+            Err(e) => (ComponentStatus::Down, Some(e.to_string())),
+        }
+    })
+}
+
+/// The agent provider's CLI could be spawned.
+fn probe_agent_cli(app: &AppState) -> impl Future<Output = Probe> {
+    let provider = app.agent_provider.clone();
+    blocking(move || match provider.preflight() {
+        Ok(()) => (ComponentStatus::Ok, Some(provider.name().to_string())),
+        Err(e) => (ComponentStatus::Down, Some(e.to_string())),
+    })
+}
+
+/// The sdlc MCP server that agent runs connect to is launchable.
+fn probe_mcp(app: &AppState) -> impl Future<Output = Probe> {
+    let root = app.root.clone();
+    blocking(move || {
+        let opts = super::runs::sdlc_query_options(root, 1, None);
+        match opts.mcp_servers.iter().try_for_each(|s| s.validate()) {
+            Ok(()) => (ComponentStatus::Ok, None),
+            Err(e) => (ComponentStatus::Down, Some(e.to_string())),
+        }
+    })
+}
+
+async fn probe_tunnel(app: &AppState) -> Probe {
+    match app.tunnel_snapshot.read().await.url.clone() {
+        Some(url) => (ComponentStatus::Ok, Some(url)),
+        None => (ComponentStatus::Disabled, Some("not running".into())),
+    }
+}
+
+// ---------------------------------------------------------------------------
+// GET /api/health
+// ---------------------------------------------------------------------------
+
+/// GET /api/health — per-subsystem status. All checks run concurrently;
+/// 503 when a critical component (`.sdlc/`) is down, 200 otherwise.
+pub async fn health(State(app): State<AppState>) -> (StatusCode, Json<HealthReport>) {
+    let (sdlc_dir, agent_cli, mcp, tunnel) = tokio::join!(
+        run_check("sdlc_dir", true, CHECK_TIMEOUT, probe_sdlc_dir(&app)),
+        run_check("agent_cli", false, CHECK_TIMEOUT, probe_agent_cli(&app)),
+        run_check("mcp", false, CHECK_TIMEOUT, probe_mcp(&app)),
+        run_check("tunnel", false, CHECK_TIMEOUT, probe_tunnel(&app)),
+    );
+    let runs = RunSlots {
+        free: app.run_slots.available_permits(),
+        queued: app.run_queue.snapshot(),
+    };
+    let report = HealthReport::from_components(vec![sdlc_dir, agent_cli, mcp, tunnel], runs);
+    (report.http_status(), Json(report))
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn component<'a>(report: &'a HealthReport, name: &str) -> &'a ComponentHealth {
+        report.components.iter().find(|c| c.name == name).unwrap()
+    }
+
+    #[tokio::test]
+    async fn missing_sdlc_dir_is_503_naming_the_component() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let app = AppState::new(dir.path().to_path_buf());
+
+        let (status, Json(report)) = health(State(app)).await;
+
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(report.status, HealthStatus::Down);
+        let sdlc = component(&report, "sdlc_dir");
+        assert_eq!(sdlc.status, ComponentStatus::Down);
+        assert!(sdlc.detail.is_some());
+        assert_eq!(
+            component(&report, "tunnel").status,
+            ComponentStatus::Disabled
+        );
+    }
+
+    #[tokio::test]
+    async fn initialized_project_is_200() {
+        let dir = tempfile::TempDir::new().unwrap();
+        sdlc_core::io::ensure_dir(&dir.path().join(".sdlc")).unwrap();
+        sdlc_core::state::State::new("test")
+            .save(dir.path())
+            .unwrap();
+        let app = AppState::new(dir.path().to_path_buf());
+
+        let (status, Json(report)) = health(State(app)).await;
+
+        // The agent CLI is usually absent in CI; that only degrades the report.
+        assert_eq!(status, StatusCode::OK);
+        assert_ne!(report.status, HealthStatus::Down);
+        assert_eq!(component(&report, "sdlc_dir").status, ComponentStatus::Ok);
+        assert!(report.runs.free > 0);
+        assert!(report.runs.queued.is_empty());
+    }
+
+    #[tokio::test]
+    async fn slow_check_times_out_as_down() {
+        let probe = std::future::pending::<Probe>();
+        let result = run_check("slow", false, Duration::from_millis(10), probe).await;
+        assert_eq!(result.status, ComponentStatus::Down);
+        assert!(result.detail.unwrap().contains("timed out"));
+    }
+}
@@ -387,7 +387,11 @@ pub async fn hub_sse_events(State(app): State<AppState>) -> impl axum::response:
         header::HeaderName::from_static("x-accel-buffering"),
         HeaderValue::from_static("no"),
     );
-    (headers, Sse::new(padded).keep_alive(KeepAlive::default())).into_response()
+    (
+        headers,
+        Sse::new(padded).keep_alive(KeepAlive::new().interval(app.http.keep_alive())),
+    )
+        .into_response()
 }
 
 // ---------------------------------------------------------------------------
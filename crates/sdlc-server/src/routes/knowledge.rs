@@ -134,7 +134,7 @@ pub async fn create_knowledge(
     let result = tokio::task::spawn_blocking(move || {
         let slug = body
             .slug
-            .unwrap_or_else(|| slugify_title_server(&body.title));
+            .unwrap_or_else(|| sdlc_core::slug::derive(&body.title));
 
         let entry = sdlc_core::knowledge::create(&root, &slug, &body.title, &body.code)?;
 
@@ -757,26 +757,3 @@ pub async fn ask_knowledge(
     )
     .await
 }
-
-// ---------------------------------------------------------------------------
-// Internal helpers
-// ---------------------------------------------------------------------------
-
-fn slugify_title_server(title: &str) -> String {
-    let lower = title.to_lowercase();
-    let mut result = String::new();
-    let mut last_was_dash = false;
-    for c in lower.chars() {
-        if c.is_ascii_alphanumeric() {
-            result.push(c);
-            last_was_dash = false;
-        } else if !last_was_dash && !result.is_empty() {
-            result.push('-');
-            last_was_dash = true;
-        }
-    }
-    while result.ends_with('-') {
-        result.pop();
-    }
-    result.chars().take(40).collect()
-}
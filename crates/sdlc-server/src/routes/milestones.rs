@@ -82,12 +82,7 @@ pub async fn review_milestone(
             .iter()
             .filter_map(|fs| sdlc_core::feature::Feature::load(&root, fs).ok())
             .map(|f| {
-                let ctx = sdlc_core::classifier::EvalContext {
-                    feature: &f,
-                    state: &state,
-                    config: &config,
-                    root: &root,
-                };
+                let ctx = sdlc_core::classifier::EvalContext::new(&f, &state, &config, &root);
                 let c = classifier.classify(&ctx);
                 serde_json::json!({
                     "feature": c.feature,
@@ -98,9 +93,25 @@ pub async fn review_milestone(
             })
             .collect();
 
+        // The signed checklist from the last UAT run carries pass/fail marks;
+        // fall back to the unsigned acceptance test before any run.
+        let acceptance = match m.load_uat_results(&root)? {
+            Some(content) => Some(("uat_results", content)),
+            None => m
+                .load_acceptance_test(&root)?
+                .map(|content| ("acceptance_test", content)),
+        }
+        .map(|(source, content)| {
+            serde_json::json!({
+                "source": source,
+                "steps": sdlc_core::milestone::parse_acceptance_test(&content),
+            })
+        });
+
         Ok::<_, sdlc_core::SdlcError>(serde_json::json!({
             "milestone": slug,
             "features": reviews,
+            "acceptance_test": acceptance,
         }))
     })
     .await
@@ -109,9 +120,27 @@ pub async fn review_milestone(
     Ok(Json(result))
 }
 
+/// GET /api/milestones/:slug/burndown — remaining tasks and features per day.
+pub async fn get_milestone_burndown(
+    State(app): State<AppState>,
+    Path(slug): Path<String>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let root = app.root.clone();
+    let points = tokio::task::spawn_blocking(move || {
+        sdlc_core::milestone::burndown(&root, &slug)
+            .map(|points| serde_json::json!({ "milestone": slug, "points": points }))
+    })
+    .await
+    .map_err(|e| AppError(anyhow::anyhow!("task join error: {e}")))??;
+
+    Ok(Json(points))
+}
+
 #[derive(serde::Deserialize)]
 pub struct CreateMilestoneBody {
-    pub slug: String,
+    /// Derived from `title` when omitted.
+    #[serde(default)]
+    pub slug: Option<String>,
     pub title: String,
 }
 
@@ -122,11 +151,12 @@ pub async fn create_milestone(
 ) -> Result<Json<serde_json::Value>, AppError> {
     let root = app.root.clone();
     let result = tokio::task::spawn_blocking(move || {
-        let m = sdlc_core::milestone::Milestone::create(&root, body.slug, body.title)?;
+        let slug = body
+            .slug
+            .unwrap_or_else(|| sdlc_core::slug::derive(&body.title));
+        let m = sdlc_core::milestone::Milestone::create(&root, slug, body.title)?;
 
-        let mut state = sdlc_core::state::State::load(&root)?;
-        state.add_milestone(&m.slug);
-        state.save(&root)?;
+        sdlc_core::state::State::update(&root, |state| state.add_milestone(&m.slug))?;
 
         Ok::<_, sdlc_core::SdlcError>(serde_json::json!({
             "slug": m.slug,
@@ -303,6 +333,66 @@ pub async fn get_uat_run_artifact(
 mod tests {
     use super::*;
 
+    #[tokio::test]
+    async fn review_milestone_returns_parsed_acceptance_steps() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let root = dir.path();
+        std::fs::create_dir_all(root.join(".sdlc/milestones")).unwrap();
+        sdlc_core::config::Config::new("test").save(root).unwrap();
+        sdlc_core::state::State::new("test").save(root).unwrap();
+        let m = sdlc_core::milestone::Milestone::create(root, "v1", "V1").unwrap();
+        m.save_acceptance_test(root, "- [ ] Users can log in\n- [ ] Invoices render\n")
+            .unwrap();
+        let app = AppState::new_for_test(root.to_path_buf());
+
+        let Json(before) = review_milestone(State(app.clone()), Path("v1".into()))
+            .await
+            .unwrap();
+        assert_eq!(before["acceptance_test"]["source"], "acceptance_test");
+        assert_eq!(before["acceptance_test"]["steps"][0]["status"], "pending");
+
+        m.save_uat_results(
+            root,
+            "- [x] Users can log in _(2026-03-03)_\n\
+             - [ ] ~~Invoices render~~ _(✗ task billing#T1 — 500)_\n",
+        )
+        .unwrap();
+        let Json(after) = review_milestone(State(app), Path("v1".into()))
+            .await
+            .unwrap();
+        let steps = &after["acceptance_test"]["steps"];
+        assert_eq!(after["acceptance_test"]["source"], "uat_results");
+        assert_eq!(steps[0]["status"], "passed");
+        assert_eq!(steps[1]["status"], "failed");
+        assert_eq!(steps[1]["annotation"], "✗ task billing#T1 — 500");
+    }
+
+    #[tokio::test]
+    async fn burndown_counts_remaining_tasks_after_completion() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let root = dir.path();
+        std::fs::create_dir_all(root.join(".sdlc/milestones")).unwrap();
+        let mut feature = sdlc_core::feature::Feature::create(root, "auth", "Auth").unwrap();
+        sdlc_core::task::add_task(&mut feature.tasks, "Handler");
+        sdlc_core::task::add_task(&mut feature.tasks, "Tests");
+        feature.save(root).unwrap();
+        sdlc_core::task::complete_task(&mut feature.tasks, "T1").unwrap();
+        feature.save(root).unwrap();
+        let mut m = sdlc_core::milestone::Milestone::create(root, "v1", "V1").unwrap();
+        m.add_feature("auth");
+        m.save(root).unwrap();
+        let app = AppState::new_for_test(root.to_path_buf());
+
+        let Json(body) = get_milestone_burndown(State(app), Path("v1".into()))
+            .await
+            .unwrap();
+        let points = body["points"].as_array().unwrap();
+        assert_eq!(points.len(), 1);
+        assert_eq!(points[0]["remaining_tasks"], 1);
+        assert_eq!(points[0]["total_tasks"], 2);
+        assert_eq!(points[0]["remaining_features"], 1);
+    }
+
     // TC-6: MIME type detection
     #[test]
     fn mime_for_filename_png() {
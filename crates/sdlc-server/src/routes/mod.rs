@@ -14,8 +14,9 @@ pub mod diagnose;
 pub mod escalations;
 pub mod events;
 pub mod features;
-pub mod git;
 pub mod feedback;
+pub mod git;
+pub mod health;
 pub mod hub;
 pub mod init;
 pub mod investigations;
@@ -23,6 +24,7 @@ pub mod invites;
 pub mod knowledge;
 pub mod milestones;
 pub mod orchestrator;
+pub mod plan;
 pub mod prepare;
 pub mod query;
 pub mod roadmap;
@@ -0,0 +1,23 @@
+use axum::extract::State;
+use axum::Json;
+
+use crate::error::AppError;
+use crate::state::AppState;
+
+/// POST /api/plan/distribute — apply a structured milestones → features →
+/// tasks plan without an agent. Idempotent: re-posting the same plan returns
+/// a diff where everything is skipped.
+pub async fn distribute_plan(
+    State(app): State<AppState>,
+    Json(plan): Json<sdlc_core::plan::Plan>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let root = app.root.clone();
+    let result = tokio::task::spawn_blocking(move || {
+        let diff = sdlc_core::plan::apply(&root, &plan)?;
+        Ok::<_, sdlc_core::SdlcError>(serde_json::to_value(diff)?)
+    })
+    .await
+    .map_err(|e| AppError(anyhow::anyhow!("task join error: {e}")))??;
+
+    Ok(Json(result))
+}
@@ -1,9 +1,15 @@
+use std::time::Duration;
+
 use axum::extract::{Query, State};
 use axum::Json;
 
 use crate::error::AppError;
 use crate::state::AppState;
 
+/// How long a cached project summary is served before it is recomputed, even
+/// if `state.yaml` has not changed (feature manifests and escalations can).
+pub const PROJECT_SUMMARY_TTL: Duration = Duration::from_secs(2);
+
 /// GET /api/project/phase
 pub async fn get_project_phase(
     State(app): State<AppState>,
@@ -39,3 +45,66 @@ pub async fn get_prepare(
 
     Ok(Json(result))
 }
+
+/// GET /api/project/summary
+///
+/// Phase, active milestone, ready and blocked features, and open escalations
+/// in one response. Cached briefly, keyed on the `state.yaml` mtime.
+pub async fn get_project_summary(
+    State(app): State<AppState>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let summary = app
+        .project_summary_cache
+        .get_or_compute(&app.root, PROJECT_SUMMARY_TTL, || async {
+            let root = app.root.clone();
+            let summary =
+                tokio::task::spawn_blocking(move || sdlc_core::prepare::project_summary(&root))
+                    .await
+                    .map_err(|e| AppError(anyhow::anyhow!("task join error: {e}")))??;
+            Ok::<_, AppError>(summary)
+        })
+        .await?;
+    Ok(Json(serde_json::to_value(&summary)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sdlc_core::feature::Feature;
+    use sdlc_core::state::State as SdlcState;
+
+    #[tokio::test]
+    async fn summary_reflects_freshly_blocked_feature() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let root = dir.path();
+        sdlc_core::config::Config::new("test").save(root).unwrap();
+        let mut state = SdlcState::new("test");
+        state.save(root).unwrap();
+        Feature::create(root, "auth", "Auth").unwrap();
+        state.add_active_feature("auth");
+        state.save(root).unwrap();
+
+        let app = AppState::new(root.to_path_buf());
+        let before = get_project_summary(State(app.clone())).await.unwrap();
+        assert_eq!(before.0["ready"][0]["slug"], "auth");
+        assert!(before.0["blocked"].as_array().unwrap().is_empty());
+
+        let mut state = SdlcState::load(root).unwrap();
+        state.set_blocked("auth", "waiting on credentials");
+        state.save(root).unwrap();
+        // A save within the filesystem's mtime granularity keeps the old mtime.
+        std::fs::File::options()
+            .write(true)
+            .open(sdlc_core::paths::state_path(root))
+            .unwrap()
+            .set_modified(std::time::SystemTime::now() + Duration::from_secs(60))
+            .unwrap();
+
+        let after = get_project_summary(State(app)).await.unwrap();
+        let blocked = after.0["blocked"].as_array().unwrap();
+        assert_eq!(blocked.len(), 1);
+        assert_eq!(blocked[0]["slug"], "auth");
+        assert_eq!(blocked[0]["reason"], "waiting on credentials");
+        assert!(after.0["ready"].as_array().unwrap().is_empty());
+    }
+}
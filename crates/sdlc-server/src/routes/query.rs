@@ -1,67 +1,101 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::extract::rejection::QueryRejection;
 use axum::extract::{Query, State};
 use axum::Json;
+use sdlc_core::search::{EntityIndex, ENTITY_FILTER_FIELDS, TASK_FILTER_FIELDS};
 
 use crate::error::AppError;
 use crate::state::AppState;
 
 #[derive(serde::Deserialize)]
 pub struct SearchParams {
+    /// Missing is reported as empty, by [`SearchParams::validate`].
+    #[serde(default)]
     pub q: String,
     pub limit: Option<usize>,
 }
 
+/// Results returned when `limit` is not given.
+pub const DEFAULT_SEARCH_LIMIT: usize = 10;
+/// Largest `limit` a search accepts.
+pub const MAX_SEARCH_LIMIT: usize = 100;
+
+impl SearchParams {
+    /// Reject an empty query, a filter key outside `fields`, or a `limit`
+    /// outside `1..=MAX_SEARCH_LIMIT`; return the limit to search with.
+    fn validate(&self, fields: &[&str]) -> Result<usize, AppError> {
+        if self.q.trim().is_empty() {
+            return Err(AppError::invalid_param("q", "must not be empty"));
+        }
+        if let Some(key) = sdlc_core::search::filter_fields(&self.q)
+            .into_iter()
+            .find(|key| !fields.contains(key))
+        {
+            return Err(AppError::invalid_param(
+                "q",
+                format!(
+                    "unknown filter '{key}:' (expected one of: {})",
+                    fields.join(", ")
+                ),
+            ));
+        }
+        match self.limit.unwrap_or(DEFAULT_SEARCH_LIMIT) {
+            0 => Err(AppError::invalid_param("limit", "must be at least 1")),
+            n if n > MAX_SEARCH_LIMIT => Err(AppError::invalid_param(
+                "limit",
+                format!("must be at most {MAX_SEARCH_LIMIT}"),
+            )),
+            n => Ok(n),
+        }
+    }
+}
+
+/// Unwrap query params, turning a deserialization failure (e.g. a
+/// non-numeric `limit`) into a 400 that names the field.
+fn query_params<T>(params: Result<Query<T>, QueryRejection>) -> Result<T, AppError> {
+    params.map(|Query(p)| p).map_err(|rejection| {
+        let text = rejection.body_text();
+        let detail = text
+            .strip_prefix("Failed to deserialize query string: ")
+            .unwrap_or(&text);
+        match detail.split_once(": ") {
+            Some((field, msg)) => AppError::invalid_param(field, msg),
+            None => AppError::invalid_param("query", detail),
+        }
+    })
+}
+
 #[derive(serde::Deserialize)]
 pub struct ReadyParams {
     pub phase: Option<String>,
 }
 
+#[derive(serde::Deserialize)]
+pub struct StaleParams {
+    /// Idle threshold, as accepted by `sdlc_core::since::parse_since`.
+    pub older_than: Option<String>,
+}
+
+/// How long a cached search index is reused before it is rebuilt, even if
+/// `state.yaml` has not changed (comments and artifacts live elsewhere).
+pub const SEARCH_INDEX_TTL: Duration = Duration::from_secs(5);
+
 /// GET /api/query/search?q=<query>&limit=<n>
+///
+/// Ranked full-text search over features, ponders, milestones, and
+/// investigations. Accepts field filters such as `type:feature` and
+/// `phase:review`; each result carries a highlighted snippet when the match
+/// is outside the title.
 pub async fn search(
     State(app): State<AppState>,
-    Query(params): Query<SearchParams>,
+    params: Result<Query<SearchParams>, QueryRejection>,
 ) -> Result<Json<serde_json::Value>, AppError> {
-    let root = app.root.clone();
+    let params = query_params(params)?;
+    let limit = params.validate(ENTITY_FILTER_FIELDS)?;
+    let index = entity_index(&app).await?;
     let result = tokio::task::spawn_blocking(move || {
-        let limit = params.limit.unwrap_or(10);
-
-        let features = sdlc_core::feature::Feature::list(&root)?;
-
-        let ponder_entries = sdlc_core::ponder::PonderEntry::list(&root)?;
-        let ponder_artifacts: Vec<_> = ponder_entries
-            .iter()
-            .map(|e| {
-                let arts = sdlc_core::ponder::list_artifacts(&root, &e.slug).unwrap_or_default();
-                (e.clone(), arts)
-            })
-            .collect();
-
-        let milestones = sdlc_core::milestone::Milestone::list(&root)?;
-        let milestone_statuses: Vec<_> = milestones
-            .into_iter()
-            .map(|m| {
-                let status = m.compute_status(&features);
-                (m, status)
-            })
-            .collect();
-
-        let investigations = sdlc_core::investigation::list(&root)?;
-        let inv_artifacts: Vec<_> = investigations
-            .into_iter()
-            .map(|e| {
-                let arts =
-                    sdlc_core::investigation::list_artifacts(&root, &e.slug).unwrap_or_default();
-                (e, arts)
-            })
-            .collect();
-
-        let index = sdlc_core::search::EntityIndex::build(sdlc_core::search::EntitySources {
-            features: &features,
-            ponders: &ponder_artifacts,
-            milestones: &milestone_statuses,
-            investigations: &inv_artifacts,
-            root: &root,
-        })?;
-
         let results = index.search(&params.q, limit)?;
 
         Ok::<_, sdlc_core::SdlcError>(serde_json::json!({
@@ -75,14 +109,29 @@ pub async fn search(
     Ok(Json(result))
 }
 
+/// Return the shared entity index, rebuilding it if `state.yaml` changed or
+/// the cached copy is older than `SEARCH_INDEX_TTL`.
+async fn entity_index(app: &AppState) -> Result<Arc<EntityIndex>, AppError> {
+    app.search_index_cache
+        .get_or_compute(&app.root, SEARCH_INDEX_TTL, || async {
+            let root = app.root.clone();
+            let index = tokio::task::spawn_blocking(move || EntityIndex::build_for_project(&root))
+                .await
+                .map_err(|e| AppError(anyhow::anyhow!("task join error: {e}")))??;
+            Ok(Arc::new(index))
+        })
+        .await
+}
+
 /// GET /api/query/search-tasks?q=<query>&limit=<n>
 pub async fn search_tasks(
     State(app): State<AppState>,
-    Query(params): Query<SearchParams>,
+    params: Result<Query<SearchParams>, QueryRejection>,
 ) -> Result<Json<serde_json::Value>, AppError> {
+    let params = query_params(params)?;
+    let limit = params.validate(TASK_FILTER_FIELDS)?;
     let root = app.root.clone();
     let result = tokio::task::spawn_blocking(move || {
-        let limit = params.limit.unwrap_or(10);
         let features = sdlc_core::feature::Feature::list(&root)?;
         let index = sdlc_core::search::TaskIndex::build(&features)?;
         let results = index.search(&params.q, limit)?;
@@ -136,55 +185,16 @@ pub async fn blocked(State(app): State<AppState>) -> Result<Json<serde_json::Val
     Ok(Json(result))
 }
 
-/// GET /api/query/ready?phase=<optional>
+/// GET /api/query/ready?phase=<optional> — features startable now, with
+/// every dependency released.
 pub async fn ready(
     State(app): State<AppState>,
     Query(params): Query<ReadyParams>,
 ) -> Result<Json<serde_json::Value>, AppError> {
     let root = app.root.clone();
     let result = tokio::task::spawn_blocking(move || {
-        let config = sdlc_core::config::Config::load(&root)?;
-        let state = sdlc_core::state::State::load(&root)?;
-        let features = sdlc_core::feature::Feature::list(&root)?;
-        let classifier = sdlc_core::classifier::Classifier::new(sdlc_core::rules::default_rules());
-
-        let out: Vec<serde_json::Value> = features
-            .iter()
-            .filter(|f| !f.archived && !f.is_blocked())
-            .filter(|f| {
-                params
-                    .phase
-                    .as_deref()
-                    .is_none_or(|p| f.phase.to_string() == p)
-            })
-            .filter_map(|f| {
-                let ctx = sdlc_core::classifier::EvalContext {
-                    feature: f,
-                    state: &state,
-                    config: &config,
-                    root: &root,
-                };
-                let c = classifier.classify(&ctx);
-                if matches!(
-                    c.action,
-                    sdlc_core::types::ActionType::WaitForApproval
-                        | sdlc_core::types::ActionType::Done
-                        | sdlc_core::types::ActionType::UnblockDependency
-                ) {
-                    None
-                } else {
-                    Some(serde_json::json!({
-                        "slug": f.slug,
-                        "phase": f.phase.to_string(),
-                        "action": c.action.as_str(),
-                        "message": c.message,
-                        "next_command": c.next_command,
-                    }))
-                }
-            })
-            .collect();
-
-        Ok::<_, sdlc_core::SdlcError>(serde_json::json!(out))
+        let ready = sdlc_core::query::ready(&root, params.phase.as_deref())?;
+        Ok::<_, sdlc_core::SdlcError>(serde_json::to_value(ready)?)
     })
     .await
     .map_err(|e| AppError(anyhow::anyhow!("task join error: {e}")))??;
@@ -207,14 +217,9 @@ pub async fn needs_approval(
             .iter()
             .filter(|f| !f.archived)
             .filter_map(|f| {
-                let ctx = sdlc_core::classifier::EvalContext {
-                    feature: f,
-                    state: &state,
-                    config: &config,
-                    root: &root,
-                };
+                let ctx = sdlc_core::classifier::EvalContext::new(f, &state, &config, &root);
                 let c = classifier.classify(&ctx);
-                if is_approval_action(c.action) {
+                if sdlc_core::query::is_approval_action(c.action) {
                     Some(serde_json::json!({
                         "slug": f.slug,
                         "phase": f.phase.to_string(),
@@ -236,20 +241,99 @@ pub async fn needs_approval(
     Ok(Json(result))
 }
 
-/// Returns true for actions that require verification or human sign-off before the phase
-/// can advance. Includes both agent-executable approve_* steps and the WaitForApproval
-/// HITL gate — the latter surfaces features that are explicitly blocked pending human
-/// sign-off, which is a distinct consumer use-case from the agentive approve_* actions.
-fn is_approval_action(action: sdlc_core::types::ActionType) -> bool {
-    matches!(
-        action,
-        sdlc_core::types::ActionType::ApproveSpec
-            | sdlc_core::types::ActionType::ApproveDesign
-            | sdlc_core::types::ActionType::ApproveTasks
-            | sdlc_core::types::ActionType::ApproveQaPlan
-            | sdlc_core::types::ActionType::ApproveReview
-            | sdlc_core::types::ActionType::ApproveAudit
-            | sdlc_core::types::ActionType::ApproveMerge
-            | sdlc_core::types::ActionType::WaitForApproval
-    )
+/// GET /api/query/stale?older_than=<7d> — active features with no activity
+/// for longer than the threshold (default 7 days), longest idle first.
+pub async fn stale(
+    State(app): State<AppState>,
+    Query(params): Query<StaleParams>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let root = app.root.clone();
+    let result = tokio::task::spawn_blocking(move || {
+        let now = chrono::Utc::now();
+        let older_than = params.older_than.as_deref().unwrap_or("7d");
+        let cutoff = sdlc_core::since::parse_since(older_than, now)?;
+        let stale = sdlc_core::query::stale_at(&root, now - cutoff, now)?;
+        Ok::<_, sdlc_core::SdlcError>(serde_json::to_value(stale)?)
+    })
+    .await
+    .map_err(|e| AppError(anyhow::anyhow!("task join error: {e}")))??;
+
+    Ok(Json(result))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sdlc_core::feature::Feature;
+    use sdlc_core::state::State as SdlcState;
+
+    fn params(q: &str) -> Result<Query<SearchParams>, QueryRejection> {
+        Ok(Query(SearchParams {
+            q: q.to_string(),
+            limit: None,
+        }))
+    }
+
+    #[tokio::test]
+    async fn search_index_rebuilds_after_state_change() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let root = dir.path();
+        sdlc_core::config::Config::new("test").save(root).unwrap();
+        SdlcState::new("test").save(root).unwrap();
+        Feature::create(root, "auth", "Auth Login").unwrap();
+
+        let app = AppState::new(root.to_path_buf());
+        let before = search(State(app.clone()), params("billing")).await.unwrap();
+        assert!(before.0["results"].as_array().unwrap().is_empty());
+
+        Feature::create(root, "billing", "Billing Dashboard").unwrap();
+        SdlcState::update(root, |s| s.add_active_feature("billing")).unwrap();
+        // An update within the filesystem's mtime granularity keeps the old mtime.
+        std::fs::File::options()
+            .write(true)
+            .open(sdlc_core::paths::state_path(root))
+            .unwrap()
+            .set_modified(std::time::SystemTime::now() + Duration::from_secs(60))
+            .unwrap();
+
+        let after = search(State(app), params("billing type:feature"))
+            .await
+            .unwrap();
+        let results = after.0["results"].as_array().unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0]["slug"], "billing");
+    }
+
+    #[tokio::test]
+    async fn stale_route_filters_by_older_than() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let root = dir.path();
+        sdlc_core::config::Config::new("test").save(root).unwrap();
+        SdlcState::new("test").save(root).unwrap();
+        // Written directly so its creation is not logged as fresh activity.
+        let mut old = Feature::new("old", "Old");
+        old.updated_at = chrono::Utc::now() - chrono::Duration::days(10);
+        sdlc_core::io::atomic_write(
+            &sdlc_core::paths::feature_manifest(root, "old"),
+            serde_yaml::to_string(&old).unwrap().as_bytes(),
+        )
+        .unwrap();
+        Feature::create(root, "fresh", "Fresh").unwrap();
+
+        let app = AppState::new(root.to_path_buf());
+        let older_than = |v: &str| {
+            Query(StaleParams {
+                older_than: Some(v.to_string()),
+            })
+        };
+        let week = stale(State(app.clone()), older_than("1w")).await.unwrap();
+        let week = week.0.as_array().unwrap().clone();
+        assert_eq!(week.len(), 1);
+        assert_eq!(week[0]["slug"], "old");
+        assert!(week[0]["idle_seconds"].as_i64().unwrap() >= 10 * 86_400);
+
+        let fortnight = stale(State(app.clone()), older_than("2w")).await.unwrap();
+        assert!(fortnight.0.as_array().unwrap().is_empty());
+        assert!(stale(State(app), older_than("soon")).await.is_err());
+    }
 }
@@ -146,7 +146,9 @@ pub async fn get_ponder(
 
 #[derive(serde::Deserialize)]
 pub struct CreatePonderBody {
-    pub slug: String,
+    /// Derived from `title` when omitted.
+    #[serde(default)]
+    pub slug: Option<String>,
     pub title: String,
     #[serde(default)]
     pub brief: Option<String>,
@@ -159,16 +161,16 @@ pub async fn create_ponder(
 ) -> Result<Json<serde_json::Value>, AppError> {
     let root = app.root.clone();
     let result = tokio::task::spawn_blocking(move || {
-        let entry = sdlc_core::ponder::PonderEntry::create(&root, body.slug, body.title)?;
+        let slug = body
+            .slug
+            .unwrap_or_else(|| sdlc_core::slug::derive(&body.title));
+        let entry = sdlc_core::ponder::PonderEntry::create(&root, slug, body.title)?;
 
         if let Some(brief) = body.brief {
             sdlc_core::ponder::capture_content(&root, &entry.slug, "brief.md", &brief)?;
         }
 
-        if let Ok(mut state) = sdlc_core::state::State::load(&root) {
-            state.add_ponder(&entry.slug);
-            let _ = state.save(&root);
-        }
+        let _ = sdlc_core::state::State::update(&root, |state| state.add_ponder(&entry.slug));
 
         Ok::<_, sdlc_core::SdlcError>(serde_json::json!({
             "slug": entry.slug,
@@ -252,10 +254,7 @@ pub async fn update_ponder(
             entry.status,
             sdlc_core::ponder::PonderStatus::Parked | sdlc_core::ponder::PonderStatus::Committed
         ) {
-            if let Ok(mut state) = sdlc_core::state::State::load(&root) {
-                state.remove_ponder(&slug);
-                let _ = state.save(&root);
-            }
+            let _ = sdlc_core::state::State::update(&root, |state| state.remove_ponder(&slug));
         }
 
         Ok::<_, sdlc_core::SdlcError>(serde_json::json!({
@@ -287,10 +286,7 @@ pub async fn delete_ponder(
             std::fs::remove_dir_all(&dir)?;
         }
 
-        if let Ok(mut state) = sdlc_core::state::State::load(&root) {
-            state.remove_ponder(&slug);
-            let _ = state.save(&root);
-        }
+        let _ = sdlc_core::state::State::update(&root, |state| state.remove_ponder(&slug));
 
         Ok::<_, sdlc_core::SdlcError>(serde_json::json!({
             "slug": slug,
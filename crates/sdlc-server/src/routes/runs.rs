@@ -1,5 +1,5 @@
 use axum::{
-    extract::{Path, State},
+    extract::{Path, Query, State},
     http::{header, HeaderValue},
     response::{
         sse::{Event, KeepAlive, Sse},
@@ -7,10 +7,14 @@ use axum::{
     },
     Json,
 };
-use claude_agent::{query_with, types::AgentEvent, McpServerConfig, PermissionMode, QueryOptions};
+use claude_agent::{
+    query_with, types::AgentEvent, McpServerConfig, PermissionMode, QueryOptions, SessionStore,
+    TranscriptWriter,
+};
 use std::collections::HashMap;
 use std::convert::Infallible;
 use tokio::time::{timeout, Duration};
+use tokio_stream::wrappers::errors::BroadcastStreamRecvError;
 use tokio_stream::wrappers::BroadcastStream;
 use tokio_stream::StreamExt as _;
 
@@ -23,7 +27,7 @@ use crate::{
     error::AppError,
     state::{
         enforce_retention, generate_run_id, load_run_history, persist_run, persist_run_events,
-        AppState, RunRecord, SseMessage,
+        AppState, RunFrame, RunLifecycle, RunRecord, SseMessage, RUN_EVENT_BUFFER,
     },
 };
 
@@ -115,6 +119,7 @@ mod tests {
         let mut opts = QueryOptions {
             mcp_servers: vec![McpServerConfig {
                 name: "sdlc".into(),
+                transport: claude_agent::McpTransport::Stdio,
                 command: "sdlc".into(),
                 args: vec!["mcp".into()],
                 env: HashMap::new(),
@@ -138,6 +143,7 @@ mod tests {
         let mut opts = QueryOptions {
             mcp_servers: vec![McpServerConfig {
                 name: "sdlc".into(),
+                transport: claude_agent::McpTransport::Stdio,
                 command: "sdlc".into(),
                 args: vec!["mcp".into()],
                 env: {
@@ -185,6 +191,44 @@ mod tests {
         );
     }
 
+    #[test]
+    fn pricing_table_applies_config_overrides_over_builtins() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let usage = TokenUsage {
+            input_tokens: 1_000_000,
+            output_tokens: 1_000_000,
+            cache_creation_input_tokens: None,
+            cache_read_input_tokens: None,
+        };
+        // No config: built-in gpt-4.1 rates ($2 in, $8 out).
+        let cost = usage.billable_cost("gpt-4.1", &pricing_table(dir.path()));
+        assert_eq!(cost, Some(10.0));
+
+        let mut cfg = sdlc_core::config::Config::new("p");
+        cfg.pricing.insert(
+            "gpt-4.1".into(),
+            sdlc_core::config::ModelRates {
+                input: 1.0,
+                output: 4.0,
+                ..Default::default()
+            },
+        );
+        std::fs::create_dir_all(dir.path().join(".sdlc")).unwrap();
+        cfg.save(dir.path()).unwrap();
+        let table = pricing_table(dir.path());
+        assert_eq!(usage.billable_cost("gpt-4.1", &table), Some(5.0));
+        assert_eq!(usage.billable_cost("claude-sonnet-4-6", &table), Some(18.0));
+
+        // A broken config falls back to the built-ins.
+        std::fs::write(
+            sdlc_core::paths::config_path(dir.path()),
+            "pricing: [oops\n",
+        )
+        .unwrap();
+        let table = pricing_table(dir.path());
+        assert_eq!(usage.billable_cost("gpt-4.1", &table), Some(10.0));
+    }
+
     #[tokio::test]
     async fn checkout_from_pool_disabled_returns_none() {
         use crate::credential_pool::OptionalCredentialPool;
@@ -211,6 +255,830 @@ mod tests {
         );
     }
 
+    // -------------------------------------------------------------------------
+    // Scripted provider
+    // -------------------------------------------------------------------------
+
+    /// Provider the run tests script. Each spawn records its prompt, stays
+    /// inside `spawn` for `hold`, then sends `events`; a `listening` one
+    /// then waits for a user message and ends with a result answering it.
+    /// `active` and `peak` count the agents inside `spawn`, now and at most.
+    #[derive(Clone, Default)]
+    struct ScriptedProvider {
+        events: Vec<AgentEvent>,
+        hold: Duration,
+        listening: bool,
+        prompts: std::sync::Arc<std::sync::Mutex<Vec<String>>>,
+        active: std::sync::Arc<std::sync::atomic::AtomicUsize>,
+        peak: std::sync::Arc<std::sync::atomic::AtomicUsize>,
+    }
+
+    impl ScriptedProvider {
+        fn new(events: Vec<AgentEvent>) -> Self {
+            Self {
+                events,
+                ..Self::default()
+            }
+        }
+
+        /// A provider that stays busy for 300ms, then finishes with "done".
+        fn slow() -> Self {
+            Self {
+                hold: Duration::from_millis(300),
+                ..Self::new(vec![result_event(false, "done")])
+            }
+        }
+    }
+
+    impl claude_agent::AgentProvider for ScriptedProvider {
+        fn spawn(
+            &self,
+            prompt: String,
+            opts: QueryOptions,
+            tx: tokio::sync::mpsc::Sender<Result<AgentEvent, claude_agent::AgentError>>,
+        ) -> std::pin::Pin<
+            Box<dyn std::future::Future<Output = Result<(), claude_agent::AgentError>> + Send>,
+        > {
+            use std::sync::atomic::Ordering;
+            self.prompts.lock().unwrap().push(prompt);
+            let this = self.clone();
+            Box::pin(async move {
+                let now = this.active.fetch_add(1, Ordering::SeqCst) + 1;
+                this.peak.fetch_max(now, Ordering::SeqCst);
+                tokio::time::sleep(this.hold).await;
+                this.active.fetch_sub(1, Ordering::SeqCst);
+                for event in this.events {
+                    let _ = tx.send(Ok(event)).await;
+                }
+                if this.listening {
+                    let heard = match &opts.user_input {
+                        Some(input) => input.recv().await.unwrap_or_default(),
+                        None => String::new(),
+                    };
+                    let _ = tx
+                        .send(Ok(AgentEvent::Result {
+                            is_error: false,
+                            is_max_turns: false,
+                            text: format!("heard: {heard}"),
+                            cost_usd: 0.0,
+                            turns: 2,
+                            session_id: None,
+                            stop_reason: None,
+                            usage: None,
+                            timestamp: String::new(),
+                        }))
+                        .await;
+                }
+                Ok(())
+            })
+        }
+
+        fn accepts_user_input(&self) -> bool {
+            self.listening
+        }
+
+        fn name(&self) -> &'static str {
+            "scripted"
+        }
+
+        fn credential_env_var(&self) -> &'static str {
+            "SCRIPTED_TOKEN"
+        }
+    }
+
+    // -------------------------------------------------------------------------
+    // Concurrency limit
+    // -------------------------------------------------------------------------
+
+    #[tokio::test]
+    async fn runs_beyond_the_limit_queue_until_a_slot_frees() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let peak = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let mut app = AppState::new_for_test(dir.path().to_path_buf());
+        app.run_slots = std::sync::Arc::new(tokio::sync::Semaphore::new(2));
+        app.agent_provider = std::sync::Arc::new(ScriptedProvider {
+            peak: peak.clone(),
+            ..ScriptedProvider::slow()
+        });
+        let mut events = app.event_tx.subscribe();
+
+        let mut queued = 0;
+        for i in 0..5 {
+            let Json(resp) = spawn_agent_run(
+                format!("sdlc-run:f{i}"),
+                "go".into(),
+                QueryOptions::default(),
+                &app,
+                "feature",
+                "test run",
+                None,
+            )
+            .await
+            .unwrap();
+            if resp["queued"] == true {
+                queued += 1;
+            }
+        }
+        assert_eq!(queued, 3);
+
+        let (mut finished, mut statuses) = (0, Vec::new());
+        while finished < 5 {
+            match timeout(Duration::from_secs(10), events.recv()).await {
+                Ok(Ok(SseMessage::RunFinished { status, .. })) => {
+                    assert_eq!(status, "completed");
+                    finished += 1;
+                }
+                Ok(Ok(SseMessage::RunStatus { status, .. })) => statuses.push(status),
+                Ok(Ok(_)) => {}
+                other => panic!("waiting for runs to finish: {other:?}"),
+            }
+        }
+        assert_eq!(peak.load(std::sync::atomic::Ordering::SeqCst), 2);
+        assert_eq!(statuses.iter().filter(|s| *s == "queued").count(), 3);
+        assert_eq!(statuses.iter().filter(|s| *s == "running").count(), 3);
+    }
+
+    #[tokio::test]
+    async fn queued_runs_alternate_between_milestones() {
+        let dir = tempfile::TempDir::new().unwrap();
+        for (slug, features) in [("ma", ["a1", "a2", "a3"].as_slice()), ("mb", &["b1", "b2"])] {
+            let mut m = sdlc_core::milestone::Milestone::create(dir.path(), slug, slug).unwrap();
+            for f in features {
+                m.add_feature(f);
+            }
+            m.save(dir.path()).unwrap();
+        }
+        let mut app = AppState::new_for_test(dir.path().to_path_buf());
+        app.run_slots = std::sync::Arc::new(tokio::sync::Semaphore::new(0));
+        app.agent_provider = std::sync::Arc::new(ScriptedProvider::slow());
+        let mut events = app.event_tx.subscribe();
+
+        // Milestone `ma` floods the queue before `mb` submits anything.
+        for target in ["a1", "a2", "a3", "b1", "b2"] {
+            let Json(resp) = spawn_agent_run(
+                format!("sdlc-run:{target}"),
+                "go".into(),
+                QueryOptions::default(),
+                &app,
+                "feature",
+                "test run",
+                None,
+            )
+            .await
+            .unwrap();
+            assert_eq!(resp["queued"], true);
+        }
+        let queued: Vec<_> = app
+            .run_queue
+            .snapshot()
+            .into_iter()
+            .map(|g| (g.milestone.unwrap(), g.run_ids.len()))
+            .collect();
+        assert_eq!(queued, vec![("ma".to_string(), 3), ("mb".to_string(), 2)]);
+
+        app.run_slots.add_permits(1);
+        let mut started = Vec::new();
+        while started.len() < 5 {
+            match timeout(Duration::from_secs(10), events.recv()).await {
+                Ok(Ok(SseMessage::RunStatus { key, status, .. })) if status == "running" => {
+                    started.push(key.trim_start_matches("sdlc-run:").to_string());
+                }
+                Ok(Ok(_)) => {}
+                other => panic!("waiting for queued runs to start: {other:?}"),
+            }
+        }
+        assert_eq!(started, vec!["a1", "b1", "a2", "b2", "a3"]);
+    }
+
+    // -------------------------------------------------------------------------
+    // Idempotency keys
+    // -------------------------------------------------------------------------
+
+    #[tokio::test]
+    async fn repeated_idempotency_key_starts_one_run() {
+        use tower::ServiceExt;
+
+        let dir = tempfile::TempDir::new().unwrap();
+        let mut app = AppState::new_for_test(dir.path().to_path_buf());
+        let active = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        app.agent_provider = std::sync::Arc::new(ScriptedProvider {
+            active: active.clone(),
+            ..ScriptedProvider::slow()
+        });
+        let router = crate::build_router_from_state(app.clone());
+        let start = |key: &'static str| {
+            let req = axum::http::Request::post("/api/run/my-feature")
+                .header(crate::idempotency::IDEMPOTENCY_KEY_HEADER, key)
+                .body(axum::body::Body::empty())
+                .unwrap();
+            let router = router.clone();
+            async move {
+                let resp = router.oneshot(req).await.unwrap();
+                assert_eq!(resp.status(), axum::http::StatusCode::OK);
+                let replayed = resp
+                    .headers()
+                    .contains_key(crate::idempotency::REPLAYED_HEADER);
+                let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+                    .await
+                    .unwrap();
+                let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+                (json["run_id"].as_str().unwrap().to_string(), replayed)
+            }
+        };
+
+        // A concurrent retry, and one after the run has finished.
+        let ((first, a), (second, b)) = tokio::join!(start("retry-1"), start("retry-1"));
+        assert_eq!(first, second);
+        assert!(a != b, "exactly one response is a replay");
+        tokio::time::sleep(Duration::from_millis(500)).await;
+        assert_eq!(active.load(std::sync::atomic::Ordering::SeqCst), 0);
+        let (late, replayed) = start("retry-1").await;
+        assert_eq!(late, first);
+        assert!(replayed);
+
+        assert_eq!(app.run_history.lock().await.len(), 1);
+    }
+
+    // -------------------------------------------------------------------------
+    // Run event SSE
+    // -------------------------------------------------------------------------
+
+    /// Waker that counts how often the stream asks to be polled again.
+    struct CountingWaker(std::sync::atomic::AtomicUsize);
+
+    impl std::task::Wake for CountingWaker {
+        fn wake(self: std::sync::Arc<Self>) {
+            self.wake_by_ref();
+        }
+
+        fn wake_by_ref(self: &std::sync::Arc<Self>) {
+            self.0.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+        }
+    }
+
+    #[tokio::test]
+    async fn idle_run_event_stream_only_wakes_on_messages() {
+        use std::sync::atomic::Ordering;
+        use std::task::{Context, Poll};
+        use tokio_stream::Stream as _;
+
+        let (tx, rx) = tokio::sync::broadcast::channel::<RunFrame>(8);
+        let mut stream = Box::pin(run_event_stream(rx));
+        let counter = std::sync::Arc::new(CountingWaker(Default::default()));
+        let waker = std::task::Waker::from(counter.clone());
+        let mut cx = Context::from_waker(&waker);
+
+        assert!(stream.as_mut().poll_next(&mut cx).is_pending());
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        assert_eq!(counter.0.load(Ordering::SeqCst), 0, "idle stream was woken");
+
+        tx.send(RunFrame::Agent(r#"{"type":"assistant"}"#.to_string()))
+            .unwrap();
+        assert_eq!(counter.0.load(Ordering::SeqCst), 1);
+        assert!(matches!(
+            stream.as_mut().poll_next(&mut cx),
+            Poll::Ready(Some(Ok(_)))
+        ));
+        assert!(stream.as_mut().poll_next(&mut cx).is_pending());
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert_eq!(counter.0.load(Ordering::SeqCst), 1);
+    }
+
+    /// Run `events` through `/api/run/{key}/events` and return each SSE frame
+    /// as `(event name, data)`. The run is held in the queue until the
+    /// subscription is open so no frame is missed.
+    async fn run_event_frames(key: &str, events: Vec<AgentEvent>) -> Vec<(String, String)> {
+        let dir = tempfile::TempDir::new().unwrap();
+        let mut app = AppState::new_for_test(dir.path().to_path_buf());
+        app.run_slots = std::sync::Arc::new(tokio::sync::Semaphore::new(0));
+        app.agent_provider = std::sync::Arc::new(ScriptedProvider::new(events));
+        let Json(started) = spawn_agent_run(
+            key.into(),
+            "go".into(),
+            QueryOptions::default(),
+            &app,
+            "feature",
+            "lifecycle",
+            None,
+        )
+        .await
+        .unwrap();
+        assert_eq!(started["queued"], true);
+        queued_run_frames(&app, key).await
+    }
+
+    /// Subscribe to the queued run `key`'s events, release it, and return
+    /// each SSE frame as `(event name, data)` once the stream closes.
+    async fn queued_run_frames(app: &AppState, key: &str) -> Vec<(String, String)> {
+        use tower::ServiceExt;
+
+        let req = axum::http::Request::get(format!("/api/run/{key}/events"))
+            .body(axum::body::Body::empty())
+            .unwrap();
+        let resp = crate::build_router_from_state(app.clone())
+            .oneshot(req)
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), axum::http::StatusCode::OK);
+        app.run_slots.add_permits(1);
+
+        let body = timeout(
+            Duration::from_secs(10),
+            axum::body::to_bytes(resp.into_body(), usize::MAX),
+        )
+        .await
+        .expect("run event stream did not close")
+        .unwrap();
+        String::from_utf8(body.to_vec())
+            .unwrap()
+            .split("\n\n")
+            .filter(|frame| !frame.trim().is_empty())
+            .map(|frame| {
+                let field = |name: &str| {
+                    frame
+                        .lines()
+                        .find_map(|l| l.strip_prefix(name))
+                        .unwrap_or_default()
+                        .to_string()
+                };
+                (field("event: "), field("data: "))
+            })
+            .collect()
+    }
+
+    /// `(event name, lifecycle type or agent event type)` for each frame.
+    fn frame_kinds(frames: &[(String, String)]) -> Vec<(String, String)> {
+        frames
+            .iter()
+            .map(|(name, data)| {
+                let data: serde_json::Value = serde_json::from_str(data).unwrap();
+                let kind = data["type"].as_str().unwrap_or_default().to_string();
+                (name.clone(), kind)
+            })
+            .collect()
+    }
+
+    fn result_event(is_error: bool, text: &str) -> AgentEvent {
+        AgentEvent::Result {
+            is_error,
+            is_max_turns: false,
+            text: text.into(),
+            cost_usd: 0.0,
+            turns: 1,
+            session_id: None,
+            stop_reason: None,
+            usage: Some(claude_agent::TokenUsage {
+                input_tokens: 10,
+                output_tokens: 5,
+                cache_creation_input_tokens: None,
+                cache_read_input_tokens: None,
+            }),
+            timestamp: String::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn successful_run_emits_lifecycle_sequence() {
+        let frames = run_event_frames(
+            "lifecycle-ok",
+            vec![
+                AgentEvent::Assistant {
+                    text: "reading".into(),
+                    tools: vec![
+                        claude_agent::types::ToolCall {
+                            name: "Read".into(),
+                            input: serde_json::json!({}),
+                        },
+                        claude_agent::types::ToolCall {
+                            name: "Grep".into(),
+                            input: serde_json::json!({}),
+                        },
+                    ],
+                    thinking: vec![],
+                    timestamp: String::new(),
+                },
+                result_event(false, "all good"),
+            ],
+        )
+        .await;
+
+        let kinds = frame_kinds(&frames);
+        let expected = [
+            ("lifecycle", "started"),
+            ("agent", "assistant"),
+            ("lifecycle", "tool_batch"),
+            ("agent", "result"),
+            ("lifecycle", "completed"),
+            ("done", ""),
+        ];
+        assert_eq!(kinds, expected.map(|(n, k)| (n.to_string(), k.to_string())));
+        let tool_batch: serde_json::Value = serde_json::from_str(&frames[2].1).unwrap();
+        assert_eq!(tool_batch["tools"], serde_json::json!(["Read", "Grep"]));
+        let completed: serde_json::Value = serde_json::from_str(&frames[4].1).unwrap();
+        assert_eq!(completed["status"], "completed");
+        assert_eq!(completed["usage"]["output_tokens"], 5);
+    }
+
+    #[tokio::test]
+    async fn start_run_streams_mock_transport_messages() {
+        use tower::ServiceExt;
+
+        let message = |json: &str| -> claude_agent::Message { serde_json::from_str(json).unwrap() };
+        let script = vec![
+            message(
+                r#"{"type":"system","subtype":"init","session_id":"s1","model":"m","tools":[],"mcp_servers":[],"permission_mode":"default","claude_code_version":"0.0.0","cwd":"/tmp"}"#,
+            ),
+            message(
+                r#"{"type":"assistant","message":{"id":"msg_1","role":"assistant","model":"m","content":[{"type":"text","text":"Writing the spec."}],"usage":{"input_tokens":1,"output_tokens":1}},"parent_tool_use_id":null,"session_id":"s1"}"#,
+            ),
+            message(
+                r#"{"type":"result","subtype":"success","session_id":"s1","result":"spec written","duration_ms":1,"duration_api_ms":1,"is_error":false,"num_turns":1,"stop_reason":"end_turn","total_cost_usd":0.0,"usage":{"input_tokens":1,"output_tokens":1}}"#,
+            ),
+        ];
+
+        let dir = tempfile::TempDir::new().unwrap();
+        let mut app = AppState::new_for_test(dir.path().to_path_buf());
+        app.run_slots = std::sync::Arc::new(tokio::sync::Semaphore::new(0));
+        app.agent_provider = std::sync::Arc::new(claude_agent::ClaudeProvider);
+        app.agent_transport = claude_agent::Transport::Mock(
+            claude_agent::MockTransport::new().on("Drive feature 'auth'", script),
+        );
+
+        let req = axum::http::Request::post("/api/run/auth")
+            .body(axum::body::Body::empty())
+            .unwrap();
+        let resp = crate::build_router_from_state(app.clone())
+            .oneshot(req)
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), axum::http::StatusCode::OK);
+
+        let frames = queued_run_frames(&app, "auth").await;
+        let kinds = frame_kinds(&frames);
+        let expected = [
+            ("lifecycle", "started"),
+            ("agent", "init"),
+            ("agent", "assistant"),
+            ("agent", "result"),
+            ("lifecycle", "completed"),
+            ("done", ""),
+        ];
+        assert_eq!(kinds, expected.map(|(n, k)| (n.to_string(), k.to_string())));
+        let assistant: serde_json::Value = serde_json::from_str(&frames[2].1).unwrap();
+        assert_eq!(assistant["text"], "Writing the spec.");
+        let result: serde_json::Value = serde_json::from_str(&frames[3].1).unwrap();
+        assert_eq!(result["text"], "spec written");
+    }
+
+    #[tokio::test]
+    async fn failed_run_emits_failed_before_done() {
+        let frames = run_event_frames("lifecycle-err", vec![result_event(true, "boom")]).await;
+
+        let kinds = frame_kinds(&frames);
+        let expected = [
+            ("lifecycle", "started"),
+            ("agent", "result"),
+            ("lifecycle", "failed"),
+            ("done", ""),
+        ];
+        assert_eq!(kinds, expected.map(|(n, k)| (n.to_string(), k.to_string())));
+        let failed: serde_json::Value = serde_json::from_str(&frames[2].1).unwrap();
+        assert_eq!(failed["error"], "boom");
+    }
+
+    #[tokio::test]
+    async fn slow_subscriber_does_not_stall_run() {
+        use http_body_util::BodyExt;
+        use tower::ServiceExt;
+
+        let mut events: Vec<AgentEvent> = (0..RUN_EVENT_BUFFER * 2)
+            .map(|i| AgentEvent::Assistant {
+                text: format!("step {i}"),
+                tools: vec![],
+                thinking: vec![],
+                timestamp: String::new(),
+            })
+            .collect();
+        events.push(result_event(false, "done"));
+
+        let dir = tempfile::TempDir::new().unwrap();
+        let mut app = AppState::new_for_test(dir.path().to_path_buf());
+        app.run_slots = std::sync::Arc::new(tokio::sync::Semaphore::new(0));
+        app.agent_provider = std::sync::Arc::new(ScriptedProvider::new(events));
+        let Json(started) = spawn_agent_run(
+            "fan-out".into(),
+            "go".into(),
+            QueryOptions::default(),
+            &app,
+            "feature",
+            "fan out",
+            None,
+        )
+        .await
+        .unwrap();
+        assert_eq!(started["queued"], true);
+
+        let subscribe = || {
+            let router = crate::build_router_from_state(app.clone());
+            async move {
+                let req = axum::http::Request::get("/api/run/fan-out/events")
+                    .body(axum::body::Body::empty())
+                    .unwrap();
+                router.oneshot(req).await.unwrap().into_body()
+            }
+        };
+        let fast = subscribe().await;
+        let mut slow = subscribe().await;
+        let fast = tokio::spawn(axum::body::to_bytes(fast, usize::MAX));
+        app.run_slots.add_permits(1);
+
+        // The slow subscriber reads nothing until the run is over.
+        timeout(Duration::from_secs(10), async {
+            while app.agent_runs.lock().await.contains_key("fan-out") {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("run stalled behind a slow subscriber");
+
+        let fast = timeout(Duration::from_secs(10), fast)
+            .await
+            .unwrap()
+            .unwrap()
+            .unwrap();
+        assert!(String::from_utf8_lossy(&fast).contains("event: done"));
+
+        let mut body = String::new();
+        while let Some(frame) = timeout(Duration::from_secs(10), slow.frame())
+            .await
+            .unwrap()
+        {
+            if let Ok(data) = frame.unwrap().into_data() {
+                body.push_str(&String::from_utf8_lossy(&data));
+            }
+        }
+        assert!(
+            body.contains("event: lagged"),
+            "slow subscriber never lagged"
+        );
+        assert!(body.contains("event: done"));
+    }
+
+    // -------------------------------------------------------------------------
+    // Human interjections
+    // -------------------------------------------------------------------------
+
+    #[tokio::test]
+    async fn injected_message_reaches_the_agent_and_is_recorded() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let mut app = AppState::new_for_test(dir.path().to_path_buf());
+        app.agent_provider = std::sync::Arc::new(ScriptedProvider {
+            listening: true,
+            ..ScriptedProvider::new(vec![AgentEvent::Assistant {
+                text: "working on it".into(),
+                tools: vec![],
+                thinking: vec![],
+                timestamp: String::new(),
+            }])
+        });
+        let mut events = app.event_tx.subscribe();
+
+        let Json(started) = spawn_agent_run(
+            "steer".into(),
+            "go".into(),
+            QueryOptions::default(),
+            &app,
+            "feature",
+            "steer",
+            None,
+        )
+        .await
+        .unwrap();
+        let run_id = started["run_id"].as_str().unwrap().to_string();
+        let transcript_path = SessionStore::new(dir.path()).transcript_path(&run_id);
+        // Mid-run: the first turn is out and the agent is waiting.
+        for _ in 0..100 {
+            let so_far = std::fs::read_to_string(&transcript_path).unwrap_or_default();
+            if so_far.contains("working on it") {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+        let Json(resp) = inject_into_run("steer", "use the v2 endpoint", &app)
+            .await
+            .unwrap();
+        assert_eq!(resp["status"], "injected");
+
+        loop {
+            match timeout(Duration::from_secs(10), events.recv()).await {
+                Ok(Ok(SseMessage::RunFinished { status, .. })) => {
+                    assert_eq!(status, "completed");
+                    break;
+                }
+                Ok(Ok(_)) => {}
+                other => panic!("waiting for run to finish: {other:?}"),
+            }
+        }
+
+        let transcript: Vec<serde_json::Value> = SessionStore::new(dir.path())
+            .tail(&run_id, false)
+            .unwrap()
+            .map(|e| e.unwrap())
+            .collect();
+        let kinds: Vec<&str> = transcript
+            .iter()
+            .map(|e| e["type"].as_str().unwrap())
+            .collect();
+        assert_eq!(kinds, ["assistant", "interjection", "result"]);
+        assert_eq!(transcript[1]["source"], "human");
+        assert_eq!(transcript[1]["text"], "use the v2 endpoint");
+        assert_eq!(transcript[2]["text"], "heard: use the v2 endpoint");
+
+        // The run is over, so there is nothing left to steer.
+        assert!(inject_into_run("steer", "too late", &app).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn inject_rejects_runs_that_cannot_take_input() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let mut app = AppState::new_for_test(dir.path().to_path_buf());
+        app.agent_provider = std::sync::Arc::new(ScriptedProvider::slow());
+        let Json(resp) = spawn_agent_run(
+            "deaf".into(),
+            "go".into(),
+            QueryOptions::default(),
+            &app,
+            "feature",
+            "deaf",
+            None,
+        )
+        .await
+        .unwrap();
+        assert_eq!(resp["status"], "started");
+        assert!(inject_into_run("deaf", "hello", &app).await.is_err());
+        assert!(inject_into_run("deaf", "  ", &app).await.is_err());
+    }
+
+    // -------------------------------------------------------------------------
+    // Run cost and duration
+    // -------------------------------------------------------------------------
+
+    #[tokio::test]
+    async fn completed_run_record_carries_usage_model_and_duration() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let mut app = AppState::new_for_test(dir.path().to_path_buf());
+        // The provider reports a model on init and token usage on its result.
+        app.agent_provider = std::sync::Arc::new(ScriptedProvider::new(vec![
+            AgentEvent::Init {
+                model: "claude-test-1".into(),
+                tools_count: 0,
+                mcp_servers: vec![],
+                timestamp: String::new(),
+            },
+            AgentEvent::Result {
+                is_error: false,
+                is_max_turns: false,
+                text: "done".into(),
+                cost_usd: 0.25,
+                turns: 3,
+                session_id: None,
+                stop_reason: Some("end_turn".into()),
+                usage: Some(claude_agent::TokenUsage {
+                    input_tokens: 1200,
+                    output_tokens: 340,
+                    cache_creation_input_tokens: None,
+                    cache_read_input_tokens: None,
+                }),
+                timestamp: String::new(),
+            },
+        ]));
+        let mut events = app.event_tx.subscribe();
+
+        let Json(resp) = spawn_agent_run(
+            "sdlc-run:metered".into(),
+            "go".into(),
+            QueryOptions::default(),
+            &app,
+            "feature",
+            "metered run",
+            None,
+        )
+        .await
+        .unwrap();
+        assert_eq!(resp["queued"], false);
+        loop {
+            match timeout(Duration::from_secs(10), events.recv()).await {
+                Ok(Ok(SseMessage::RunFinished { .. })) => break,
+                Ok(Ok(_)) => {}
+                other => panic!("waiting for run to finish: {other:?}"),
+            }
+        }
+
+        let history = app.run_history.lock().await;
+        let rec = history
+            .iter()
+            .find(|r| r.key == "sdlc-run:metered")
+            .unwrap();
+        assert_eq!(rec.status, "completed");
+        assert_eq!(rec.model.as_deref(), Some("claude-test-1"));
+        assert_eq!(rec.cost_usd, Some(0.25));
+        let usage = rec.usage.as_ref().expect("usage recorded");
+        assert_eq!(usage.input_tokens, 1200);
+        assert_eq!(usage.output_tokens, 340);
+        assert!(rec.duration_ms.is_some());
+
+        // The same events were appended to the run's transcript.
+        let kinds: Vec<String> = SessionStore::new(dir.path())
+            .tail(&rec.id, false)
+            .unwrap()
+            .map(|e| e.unwrap()["type"].as_str().unwrap().to_string())
+            .collect();
+        assert_eq!(kinds, ["init", "result"]);
+    }
+
+    fn finished_run(id: &str, model: Option<&str>, cost: f64, duration_ms: u64) -> RunRecord {
+        serde_json::from_value(serde_json::json!({
+            "id": id,
+            "key": format!("sdlc-run:{id}"),
+            "run_type": "feature",
+            "target": id,
+            "label": id,
+            "status": "completed",
+            "started_at": "2026-01-01T00:00:00Z",
+            "completed_at": "2026-01-01T00:01:00Z",
+            "cost_usd": cost,
+            "turns": 1,
+            "error": null,
+            "model": model,
+            "usage": { "input_tokens": 100, "output_tokens": 10 },
+            "duration_ms": duration_ms,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn run_stats_sum_cost_tokens_and_average_duration() {
+        let mut running = finished_run("live", Some("opus"), 9.0, 9_000);
+        running.status = "running".into();
+        running.completed_at = None;
+        let records = vec![
+            finished_run("a", Some("opus"), 1.5, 1_000),
+            finished_run("b", Some("opus"), 0.5, 3_000),
+            finished_run("c", None, 0.25, 2_000),
+            running,
+        ];
+
+        let stats = RunStats::from_records(&records);
+        assert_eq!(stats.finished_runs, 3);
+        assert!((stats.total_cost_usd - 2.25).abs() < f64::EPSILON);
+        assert_eq!(stats.total_input_tokens, 300);
+        assert_eq!(stats.total_output_tokens, 30);
+        assert_eq!(stats.runs_per_model["opus"], 2);
+        assert_eq!(stats.runs_per_model["unknown"], 1);
+        assert_eq!(stats.average_duration_ms, Some(2_000));
+    }
+
+    #[tokio::test]
+    async fn list_runs_since_excludes_runs_started_before_cutoff() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let app = AppState::new_for_test(dir.path().to_path_buf());
+        let mut recent = finished_run("recent", None, 0.0, 0);
+        recent.started_at = (chrono::Utc::now() - chrono::Duration::minutes(30)).to_rfc3339();
+        *app.run_history.lock().await = vec![finished_run("old", None, 0.0, 0), recent];
+
+        let ids = |runs: serde_json::Value| -> Vec<String> {
+            runs.as_array()
+                .unwrap()
+                .iter()
+                .map(|r| r["id"].as_str().unwrap().to_string())
+                .collect()
+        };
+        let query = |since: Option<&str>| {
+            Query(ListRunsQuery {
+                since: since.map(str::to_string),
+            })
+        };
+
+        let Json(all) = list_runs(query(None), State(app.clone())).await.unwrap();
+        assert_eq!(ids(all), ["old", "recent"]);
+        let Json(last_hour) = list_runs(query(Some("1h")), State(app.clone()))
+            .await
+            .unwrap();
+        assert_eq!(ids(last_hour), ["recent"]);
+        let Json(since_date) = list_runs(query(Some("2026-01-01")), State(app.clone()))
+            .await
+            .unwrap();
+        assert_eq!(ids(since_date), ["old", "recent"]);
+        assert!(list_runs(query(Some("soon")), State(app)).await.is_err());
+    }
+
+    #[test]
+    fn run_stats_of_empty_history_has_no_average() {
+        let stats = RunStats::from_records(&[]);
+        assert_eq!(stats.finished_runs, 0);
+        assert_eq!(stats.average_duration_ms, None);
+    }
+
     #[test]
     fn extract_slug_from_key_parses_correctly() {
         assert_eq!(extract_slug_from_key("sdlc-run:my-feature"), "my-feature");
@@ -366,6 +1234,81 @@ mod tests {
         );
     }
 
+    // -------------------------------------------------------------------------
+    // Milestone run-wave resume
+    // -------------------------------------------------------------------------
+
+    #[tokio::test]
+    async fn run_wave_resumes_a_stopped_wave_with_unfinished_features_only() {
+        use sdlc_core::{feature::Feature, milestone::Milestone, types::Phase};
+
+        let dir = tempfile::TempDir::new().unwrap();
+        let root = dir.path();
+        std::fs::create_dir_all(root.join(".sdlc/features")).unwrap();
+        sdlc_core::config::Config::new("test").save(root).unwrap();
+        let mut state = sdlc_core::state::State::new("test");
+        let mut milestone = Milestone::create(root, "v1", "V1").unwrap();
+        for slug in ["auth", "billing", "search"] {
+            Feature::create(root, slug, slug).unwrap();
+            state.add_active_feature(slug);
+            milestone.add_feature(slug);
+        }
+        milestone.save(root).unwrap();
+        state.add_milestone("v1");
+        state.save(root).unwrap();
+
+        let provider = ScriptedProvider::default();
+        let prompts = provider.prompts.clone();
+        let mut app = AppState::new_for_test(root.to_path_buf());
+        app.agent_provider = std::sync::Arc::new(provider);
+
+        let Json(first) = start_milestone_run_wave(Path("v1".into()), State(app.clone()))
+            .await
+            .unwrap();
+        assert_eq!(first["resumed"], false);
+        assert_eq!(
+            first["wave_run"]["remaining"],
+            serde_json::json!(["auth", "billing", "search"])
+        );
+
+        // The agent ships auth, then the wave is stopped.
+        for _ in 0..100 {
+            if app.agent_runs.lock().await.is_empty() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        let mut auth = Feature::load(root, "auth").unwrap();
+        auth.phase = Phase::Released;
+        auth.save(root).unwrap();
+        let _ = stop_milestone_run_wave(Path("v1".into()), State(app.clone())).await;
+
+        let Json(resumed) = start_milestone_run_wave(Path("v1".into()), State(app.clone()))
+            .await
+            .unwrap();
+        assert_eq!(resumed["resumed"], true);
+        assert_eq!(
+            resumed["wave_run"]["completed"],
+            serde_json::json!(["auth"])
+        );
+        assert_eq!(
+            resumed["wave_run"]["remaining"],
+            serde_json::json!(["billing", "search"])
+        );
+        // The resumed run reaches the provider asynchronously.
+        for _ in 0..100 {
+            if prompts.lock().unwrap().len() == 2 {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        let prompts = prompts.lock().unwrap();
+        assert_eq!(prompts.len(), 2);
+        let prompt = &prompts[1];
+        assert!(prompt.starts_with("Resume wave 1"), "{prompt}");
+        assert!(prompt.contains("`/sdlc-run <feature-slug>`: billing, search."));
+    }
+
     /// Verify that `session_id()` and `stop_reason()` on `ResultMessage` return
     /// the values we would capture into `RunRecord`.
     #[test]
@@ -406,12 +1349,140 @@ mod tests {
             panic!("expected Message::Result");
         }
     }
+
+    // -------------------------------------------------------------------------
+    // Ponder chat dialogue flushing
+    // -------------------------------------------------------------------------
+
+    fn ponder_project() -> tempfile::TempDir {
+        let dir = tempfile::TempDir::new().unwrap();
+        std::fs::create_dir_all(dir.path().join(".sdlc/roadmap")).unwrap();
+        sdlc_core::ponder::PonderEntry::create(dir.path(), "idea", "Idea").unwrap();
+        dir
+    }
+
+    fn assistant_frame(text: &str) -> RunFrame {
+        RunFrame::Agent(
+            serde_json::json!({
+                "type": "assistant",
+                "text": text,
+                "tools": [],
+                "thinking": [],
+                "timestamp": "2026-01-01T00:00:00Z",
+            })
+            .to_string(),
+        )
+    }
+
+    #[tokio::test]
+    async fn ponder_dialogue_survives_a_mid_chat_shutdown() {
+        let dir = ponder_project();
+        let partial = sdlc_core::paths::ponder_partial_session_path(dir.path(), "idea");
+        let (tx, rx) = tokio::sync::broadcast::channel(16);
+        let flush = tokio::spawn(flush_ponder_dialogue(
+            dir.path().to_path_buf(),
+            "idea".into(),
+            1,
+            "Ada".into(),
+            Some("What if search were local?".into()),
+            rx,
+        ));
+        tx.send(assistant_frame("KAI · Architect\nStart with retrieval."))
+            .unwrap();
+        for _ in 0..100 {
+            if std::fs::read_to_string(&partial).is_ok_and(|c| c.contains("retrieval")) {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+
+        // The server goes down mid-chat: the flush task dies without ever
+        // seeing the run end.
+        flush.abort();
+        let _ = flush.await;
+        assert!(sdlc_core::ponder::list_sessions(dir.path(), "idea")
+            .unwrap()
+            .is_empty());
+
+        let recovered = sdlc_core::ponder::recover_partial_sessions(dir.path()).unwrap();
+        assert_eq!(recovered, vec![("idea".to_string(), 1)]);
+        let session = sdlc_core::ponder::read_session(dir.path(), "idea", 1).unwrap();
+        assert!(
+            session.contains("**Ada · Owner**\nWhat if search were local?\n\nKAI · Architect"),
+            "{session}"
+        );
+        assert!(!partial.exists());
+    }
+
+    #[tokio::test]
+    async fn subscribed_run_receiver_sees_every_frame() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let mut app = AppState::new_for_test(dir.path().to_path_buf());
+        app.agent_provider = std::sync::Arc::new(ScriptedProvider::default());
+        let (_, mut frames) = spawn_agent_run_subscribed(
+            "ponder:idea".into(),
+            "go".into(),
+            QueryOptions::default(),
+            &app,
+            "ponder",
+            "ponder: idea",
+            None,
+        )
+        .await
+        .unwrap();
+        // Let the run finish before anything is read.
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        assert!(matches!(
+            frames.recv().await,
+            Ok(RunFrame::Lifecycle(RunLifecycle::Started { .. }))
+        ));
+    }
+
+    #[tokio::test]
+    async fn ponder_dialogue_is_dropped_when_the_agent_logs_the_session() {
+        let dir = ponder_project();
+        let (tx, rx) = tokio::sync::broadcast::channel(16);
+        let flush = tokio::spawn(flush_ponder_dialogue(
+            dir.path().to_path_buf(),
+            "idea".into(),
+            1,
+            "Ada".into(),
+            None,
+            rx,
+        ));
+        tx.send(assistant_frame("Thinking out loud.")).unwrap();
+        sdlc_core::ponder::log_session(dir.path(), "idea", "the agent's own log").unwrap();
+        tx.send(RunFrame::Done).unwrap();
+        flush.await.unwrap();
+
+        let sessions = sdlc_core::ponder::list_sessions(dir.path(), "idea").unwrap();
+        assert_eq!(sessions.len(), 1);
+        assert!(!sdlc_core::paths::ponder_partial_session_path(dir.path(), "idea").exists());
+    }
 }
 
 // ---------------------------------------------------------------------------
 // Shared helpers
 // ---------------------------------------------------------------------------
 
+/// The milestone a run on `target` belongs to: the milestone itself, or the
+/// one that contains the feature. `None` if neither, or if milestones cannot
+/// be read.
+async fn run_milestone(root: &std::path::Path, target: &str) -> Option<String> {
+    let root = root.to_path_buf();
+    let target = target.to_string();
+    tokio::task::spawn_blocking(move || {
+        let milestones = sdlc_core::milestone::Milestone::list(&root).ok()?;
+        milestones
+            .into_iter()
+            .find(|m| m.slug == target || m.features.contains(&target))
+            .map(|m| m.slug)
+    })
+    .await
+    .ok()
+    .flatten()
+}
+
 /// Spawn a Claude agent keyed by `key`, streaming events into the broadcast map.
 /// Creates a RunRecord, persists it, and emits SSE lifecycle events.
 ///
@@ -422,12 +1493,34 @@ mod tests {
 pub(crate) async fn spawn_agent_run(
     key: String,
     prompt: String,
-    mut opts: QueryOptions,
+    opts: QueryOptions,
     app: &AppState,
     run_type: &str,
     label: &str,
     completion_event: Option<SseMessage>,
 ) -> Result<Json<serde_json::Value>, AppError> {
+    spawn_agent_run_subscribed(key, prompt, opts, app, run_type, label, completion_event)
+        .await
+        .map(|(response, _)| response)
+}
+
+/// [`spawn_agent_run`], also returning a receiver for the run's frames that
+/// was subscribed before the run started, so it sees every frame.
+pub(crate) async fn spawn_agent_run_subscribed(
+    key: String,
+    prompt: String,
+    mut opts: QueryOptions,
+    app: &AppState,
+    run_type: &str,
+    label: &str,
+    completion_event: Option<SseMessage>,
+) -> Result<
+    (
+        Json<serde_json::Value>,
+        tokio::sync::broadcast::Receiver<RunFrame>,
+    ),
+    AppError,
+> {
     tracing::debug!(key = %key, "spawn_agent_run: request received");
 
     // Duplicate check must happen BEFORE spawning the task to close the TOCTOU race window.
@@ -459,9 +1552,30 @@ pub(crate) async fn spawn_agent_run(
         }
     }
 
+    if matches!(opts.transport, claude_agent::Transport::Subprocess) {
+        opts.transport = app.agent_transport.clone();
+    }
+    opts.pricing = pricing_table(&app.root);
+
+    // Take a slot now if one is free; otherwise the task waits for one and
+    // the run is reported as `queued` until it gets it.
+    let permit = app.run_slots.clone().try_acquire_owned().ok();
+    let queued = permit.is_none();
+
     // Create the broadcast channel and build the RunRecord before taking the lock.
     let run_id = generate_run_id();
     let target = key.split(':').next_back().unwrap_or(&key).to_string();
+    // Queued runs wait their milestone's turn for a slot (see `run_queue`).
+    // Enqueue here rather than in the task so queue order is submit order.
+    let ticket = if queued {
+        let milestone = run_milestone(&app.root, &target).await;
+        Some(
+            app.run_queue
+                .enqueue(milestone, &run_id, app.run_slots.clone()),
+        )
+    } else {
+        None
+    };
     // Store a truncated prompt for display in the activity feed.
     let prompt_preview = Some(truncate_chars_with_ellipsis(
         &prompt,
@@ -473,7 +1587,7 @@ pub(crate) async fn spawn_agent_run(
         run_type: run_type.to_string(),
         target,
         label: label.to_string(),
-        status: "running".to_string(),
+        status: if queued { "queued" } else { "running" }.to_string(),
         started_at: chrono::Utc::now().to_rfc3339(),
         completed_at: None,
         cost_usd: None,
@@ -482,9 +1596,23 @@ pub(crate) async fn spawn_agent_run(
         prompt: prompt_preview,
         session_id: None,
         stop_reason: None,
+        model: None,
+        usage: None,
+        duration_ms: None,
     };
 
-    let (tx, _) = tokio::sync::broadcast::channel::<String>(512);
+    // Human interjections go through the run task, which forwards them to
+    // the agent and records them alongside its events.
+    let (interject_tx, mut interjections) = if app.agent_provider.accepts_user_input() {
+        let (input, user_input) = claude_agent::user_input_channel();
+        opts.user_input = Some(user_input);
+        let (tx, rx) = tokio::sync::mpsc::channel::<String>(16);
+        (Some(tx), Some((rx, input)))
+    } else {
+        (None, None)
+    };
+
+    let (tx, frames) = tokio::sync::broadcast::channel::<RunFrame>(RUN_EVENT_BUFFER);
     // Clone tx for the spawned task; keep the original to store in the map.
     let tx_task = tx.clone();
 
@@ -496,10 +1624,56 @@ pub(crate) async fn spawn_agent_run(
     let run_id_clone = run_id.clone();
     let telemetry_store = app.telemetry.get().cloned();
     let provider = app.agent_provider.clone();
+    // Fired once the record is in `run_history`, so the task never updates a
+    // record before it exists (a queued run going live, or a run that finishes
+    // before the spawner gets back to it).
+    let (registered_tx, registered_rx) = tokio::sync::oneshot::channel::<()>();
 
     tracing::debug!(key = %key, "spawn_agent_run: spawning agent task");
     let handle = tokio::spawn(async move {
         let tx = tx_task;
+        let mut registered = Some(registered_rx);
+        let _permit = match permit {
+            Some(permit) => Some(permit),
+            None => {
+                if let Some(rx) = registered.take() {
+                    let _ = rx.await;
+                }
+                let permit = match ticket {
+                    Some(ticket) => ticket.await.ok(),
+                    None => None,
+                };
+                if let Some(rec) = run_history
+                    .lock()
+                    .await
+                    .iter_mut()
+                    .find(|r| r.id == run_id_clone)
+                {
+                    rec.status = "running".to_string();
+                }
+                let _ = event_tx.send(SseMessage::RunStatus {
+                    id: run_id_clone.clone(),
+                    key: key_clone.clone(),
+                    status: "running".to_string(),
+                });
+                tracing::debug!(key = %key_clone, "agent run left the queue");
+                permit
+            }
+        };
+        let requested_model = opts.model.clone();
+        let run_started = std::time::Instant::now();
+        // Live transcript for `sdlc agent logs --follow`; a run without one
+        // still completes normally.
+        let mut transcript = match SessionStore::new(&root).transcript_writer(&run_id_clone) {
+            Ok(w) => Some(w),
+            Err(e) => {
+                warn!(key = %key_clone, error = %e, "could not open run transcript");
+                None
+            }
+        };
+        let _ = tx.send(RunFrame::Lifecycle(RunLifecycle::Started {
+            run_id: run_id_clone.clone(),
+        }));
         let mut stream = query_with(prompt, opts, provider.as_ref());
         let mut message_count: u64 = 0;
         let mut accumulated_events: Vec<serde_json::Value> = Vec::new();
@@ -507,21 +1681,68 @@ pub(crate) async fn spawn_agent_run(
         let mut final_turns: Option<u64> = None;
         let mut final_session_id: Option<String> = None;
         let mut final_stop_reason: Option<String> = None;
+        let mut final_usage: Option<claude_agent::TokenUsage> = None;
+        let mut final_model: Option<String> = None;
         let mut is_error = false;
         let mut is_max_turns = false;
         let mut error_msg: Option<String> = None;
 
         // Per-message timeout: prevents the task from hanging if the agent stops emitting.
         loop {
-            match timeout(AGENT_MESSAGE_TIMEOUT, stream.next()).await {
+            let next = tokio::select! {
+                Some((text, input)) = next_interjection(&mut interjections) => {
+                    if let Err(e) = input.send(text.clone()) {
+                        warn!(key = %key_clone, error = %e, "could not deliver interjection");
+                        continue;
+                    }
+                    let event = serde_json::json!({
+                        "type": "interjection",
+                        "source": "human",
+                        "text": text,
+                        "timestamp": chrono::Utc::now().to_rfc3339(),
+                    });
+                    accumulated_events.push(event.clone());
+                    append_transcript(&mut transcript, &event);
+                    let _ = tx.send(RunFrame::Agent(event.to_string()));
+                    continue;
+                }
+                next = timeout(AGENT_MESSAGE_TIMEOUT, stream.next()) => next,
+            };
+            match next {
                 Ok(Some(msg)) => match msg {
                     Ok(agent_event) => {
+                        // Deltas only drive the live typewriter view: forward
+                        // them, but keep them out of the stored event log —
+                        // the full assistant message follows.
+                        if matches!(agent_event, AgentEvent::AssistantDelta { .. }) {
+                            if let Ok(json) = serde_json::to_string(&agent_event) {
+                                let _ = tx.send(RunFrame::Agent(json));
+                            }
+                            continue;
+                        }
                         message_count += 1;
+                        // Aliases like "sonnet" resolve to a full model id,
+                        // so a containment check is the strictest fair test.
+                        if let AgentEvent::Init { ref model, .. } = agent_event {
+                            if requested_model
+                                .as_ref()
+                                .is_some_and(|m| !model.contains(m.as_str()))
+                            {
+                                warn!(
+                                    key = %key_clone,
+                                    requested = ?requested_model,
+                                    actual = %model,
+                                    "agent is running on a different model than requested"
+                                );
+                            }
+                            final_model = Some(model.clone());
+                        }
                         let event = match serde_json::to_value(&agent_event) {
                             Ok(v) => v,
                             Err(_) => continue,
                         };
                         accumulated_events.push(event.clone());
+                        append_transcript(&mut transcript, &event);
                         if let Some(store) = &telemetry_store {
                             let store = store.clone();
                             let run_id2 = run_id_clone.clone();
@@ -534,7 +1755,14 @@ pub(crate) async fn spawn_agent_run(
                             Ok(s) => s,
                             Err(_) => continue,
                         };
-                        let _ = tx.send(json);
+                        let _ = tx.send(RunFrame::Agent(json));
+                        if let AgentEvent::Assistant { ref tools, .. } = agent_event {
+                            if !tools.is_empty() {
+                                let tools = tools.iter().map(|t| t.name.clone()).collect();
+                                let _ =
+                                    tx.send(RunFrame::Lifecycle(RunLifecycle::ToolBatch { tools }));
+                            }
+                        }
 
                         if let AgentEvent::Result {
                             is_error: err,
@@ -544,6 +1772,7 @@ pub(crate) async fn spawn_agent_run(
                             turns,
                             ref session_id,
                             ref stop_reason,
+                            ref usage,
                             ..
                         } = agent_event
                         {
@@ -553,6 +1782,7 @@ pub(crate) async fn spawn_agent_run(
                             final_turns = Some(turns as u64);
                             final_session_id = session_id.clone();
                             final_stop_reason = stop_reason.clone();
+                            final_usage = usage.clone();
                             if is_error && !is_max_turns {
                                 error_msg = Some(result_text.clone());
                             }
@@ -569,12 +1799,21 @@ pub(crate) async fn spawn_agent_run(
                             "message": e.to_string()
                         });
                         accumulated_events.push(event.clone());
-                        let _ = tx.send(event.to_string());
+                        append_transcript(&mut transcript, &event);
+                        let _ = tx.send(RunFrame::Agent(event.to_string()));
                         break;
                     }
                 },
                 Ok(None) => {
-                    // Stream ended normally without a Result message.
+                    // Stream ended normally without a Result message. Close
+                    // the transcript so followers do not wait forever.
+                    append_transcript(
+                        &mut transcript,
+                        &serde_json::json!({
+                            "type": "error",
+                            "message": "agent stream ended without a result"
+                        }),
+                    );
                     break;
                 }
                 Err(_elapsed) => {
@@ -586,7 +1825,8 @@ pub(crate) async fn spawn_agent_run(
                         "message": "agent stream timed out after 10 minutes of silence"
                     });
                     accumulated_events.push(event.clone());
-                    let _ = tx.send(event.to_string());
+                    append_transcript(&mut transcript, &event);
+                    let _ = tx.send(RunFrame::Agent(event.to_string()));
                     break;
                 }
             }
@@ -602,11 +1842,31 @@ pub(crate) async fn spawn_agent_run(
             "completed"
         };
         let completed_at = chrono::Utc::now().to_rfc3339();
+        let duration_ms = Some(run_started.elapsed().as_millis() as u64);
+        if let Some(rx) = registered.take() {
+            let _ = rx.await;
+        }
 
         // Update the record AND clone it for persistence in a single lock acquisition
         // to avoid a second lock round-trip.
+        let terminal = if status == "failed" {
+            RunLifecycle::Failed {
+                error: error_msg
+                    .clone()
+                    .unwrap_or_else(|| "agent run failed".to_string()),
+            }
+        } else {
+            RunLifecycle::Completed {
+                status: status.to_string(),
+                usage: final_usage.clone(),
+            }
+        };
         let full_rec = {
             let mut history = run_history.lock().await;
+            // Sent under the history lock so it cannot race `stop_run_by_key`,
+            // which emits its own terminal frames when it marks a run stopped.
+            let _ = tx.send(RunFrame::Lifecycle(terminal));
+            let _ = tx.send(RunFrame::Done);
             if let Some(rec) = history.iter_mut().find(|r| r.id == run_id_clone) {
                 rec.status = status.to_string();
                 rec.completed_at = Some(completed_at.clone());
@@ -615,6 +1875,9 @@ pub(crate) async fn spawn_agent_run(
                 rec.error = error_msg.clone();
                 rec.session_id = final_session_id.clone();
                 rec.stop_reason = final_stop_reason.clone();
+                rec.model = final_model.clone();
+                rec.usage = final_usage.clone();
+                rec.duration_ms = duration_ms;
                 rec.clone()
             } else {
                 // Fallback: create a minimal record for persistence if it's missing.
@@ -633,6 +1896,9 @@ pub(crate) async fn spawn_agent_run(
                     prompt: None,
                     session_id: final_session_id.clone(),
                     stop_reason: final_stop_reason.clone(),
+                    model: final_model.clone(),
+                    usage: final_usage.clone(),
+                    duration_ms,
                 }
             }
         };
@@ -722,7 +1988,7 @@ pub(crate) async fn spawn_agent_run(
                 "Agent already running for '{key}'"
             )));
         }
-        runs.insert(key.clone(), (tx.clone(), abort_handle));
+        runs.insert(key.clone(), (tx.clone(), abort_handle, interject_tx));
     }
 
     // Async I/O happens after the lock is released.
@@ -743,12 +2009,52 @@ pub(crate) async fn spawn_agent_run(
         run_type: record.run_type.clone(),
         target: record.target.clone(),
     });
+    if queued {
+        info!(key = %key, "spawn_agent_run: all run slots busy, queued");
+        let _ = app.event_tx.send(SseMessage::RunStatus {
+            id: run_id.clone(),
+            key: key.clone(),
+            status: "queued".to_string(),
+        });
+    }
+    let _ = registered_tx.send(());
 
-    Ok(Json(serde_json::json!({
+    let response = Json(serde_json::json!({
         "status": "started",
+        "queued": queued,
         "message": format!("Agent started for '{key}'"),
         "run_id": run_id,
-    })))
+    }));
+    Ok((response, frames))
+}
+
+/// SSE frames for a run's broadcast channel. The stream parks on the
+/// channel and is only woken when the run task sends a message.
+///
+/// Each subscriber reads the channel at its own pace. One that falls more
+/// than [`RUN_EVENT_BUFFER`] frames behind gets a `lagged` event carrying the
+/// number of frames it missed, then resumes from the oldest buffered frame.
+fn run_event_stream(
+    rx: tokio::sync::broadcast::Receiver<RunFrame>,
+) -> impl tokio_stream::Stream<Item = Result<Event, Infallible>> {
+    BroadcastStream::new(rx).filter_map(|frame| {
+        let frame = match frame {
+            Ok(frame) => frame,
+            Err(BroadcastStreamRecvError::Lagged(skipped)) => {
+                let data = serde_json::json!({ "skipped": skipped }).to_string();
+                return Some(Ok(Event::default().event("lagged").data(data)));
+            }
+        };
+        let event = match frame {
+            RunFrame::Agent(data) => Event::default().event("agent").data(data),
+            RunFrame::Lifecycle(lifecycle) => Event::default()
+                .event("lifecycle")
+                .json_data(lifecycle)
+                .ok()?,
+            RunFrame::Done => Event::default().event("done").data("{}"),
+        };
+        Some(Ok::<Event, Infallible>(event))
+    })
 }
 
 /// Subscribe to SSE events for a given run key.
@@ -756,17 +2062,18 @@ async fn get_run_events(key: &str, app: &AppState) -> Response {
     tracing::debug!(key = %key, "get_run_events: SSE subscribe");
     let rx = {
         let runs = app.agent_runs.lock().await;
-        runs.get(key).map(|(tx, _)| tx.subscribe())
+        runs.get(key).map(|(tx, _, _)| tx.subscribe())
     };
 
     match rx {
         Some(rx) => {
-            let stream = BroadcastStream::new(rx).filter_map(|msg| {
-                msg.ok()
-                    .map(|data| Ok::<Event, Infallible>(Event::default().event("agent").data(data)))
-            });
-            let mut response = Sse::new(stream)
-                .keep_alive(KeepAlive::default())
+            // Idle streams send a keep-alive comment so proxies do not close
+            // the connection between agent messages.
+            let keep_alive = KeepAlive::new()
+                .interval(app.http.keep_alive())
+                .text("keep-alive");
+            let mut response = Sse::new(run_event_stream(rx))
+                .keep_alive(keep_alive)
                 .into_response();
             // Disable Cloudflare (and nginx) buffering so SSE events are
             // delivered immediately rather than being held until the buffer fills.
@@ -787,13 +2094,38 @@ async fn get_run_events(key: &str, app: &AppState) -> Response {
     }
 }
 
+/// Append `event` to a run transcript, dropping the writer after the first
+/// failure so one bad disk write does not log once per event.
+fn append_transcript(writer: &mut Option<TranscriptWriter>, event: &serde_json::Value) {
+    if let Some(w) = writer.as_mut() {
+        if let Err(e) = w.append(event) {
+            warn!(error = %e, "run transcript write failed; transcript closed");
+            *writer = None;
+        }
+    }
+}
+
+/// Wait for the next human interjection, paired with the agent's input
+/// channel; pends forever for runs that take no input.
+async fn next_interjection(
+    interjections: &mut Option<(
+        tokio::sync::mpsc::Receiver<String>,
+        claude_agent::UserInputSender,
+    )>,
+) -> Option<(String, &claude_agent::UserInputSender)> {
+    match interjections {
+        Some((rx, input)) => rx.recv().await.map(|text| (text, &*input)),
+        None => std::future::pending().await,
+    }
+}
+
 /// Stop a running agent by removing it from the broadcast map.
 /// Also updates the RunRecord status and emits RunFinished.
 async fn stop_run_by_key(key: &str, app: &AppState) -> Json<serde_json::Value> {
     tracing::debug!(key = %key, "stop_run_by_key: request received");
     let removed = app.agent_runs.lock().await.remove(key);
     match removed {
-        Some((_, abort_handle)) => {
+        Some((run_tx, abort_handle, _)) => {
             abort_handle.abort();
             info!(key = %key, "stop_run_by_key: agent stopped");
 
@@ -802,10 +2134,14 @@ async fn stop_run_by_key(key: &str, app: &AppState) -> Json<serde_json::Value> {
                 let mut history = app.run_history.lock().await;
                 if let Some(rec) = history
                     .iter_mut()
-                    .find(|r| r.key == key && r.status == "running")
+                    .find(|r| r.key == key && (r.status == "running" || r.status == "queued"))
                 {
                     rec.status = "stopped".to_string();
                     rec.completed_at = Some(chrono::Utc::now().to_rfc3339());
+                    let _ = run_tx.send(RunFrame::Lifecycle(RunLifecycle::Failed {
+                        error: "run stopped".to_string(),
+                    }));
+                    let _ = run_tx.send(RunFrame::Done);
                     let root = app.root.clone();
                     let rec_clone = rec.clone();
                     tokio::task::spawn_blocking(move || persist_run(&root, &rec_clone))
@@ -818,6 +2154,12 @@ async fn stop_run_by_key(key: &str, app: &AppState) -> Json<serde_json::Value> {
             };
 
             if let Some(id) = run_id {
+                // The aborted task never writes its terminal event.
+                let mut transcript = SessionStore::new(&app.root).transcript_writer(&id).ok();
+                append_transcript(
+                    &mut transcript,
+                    &serde_json::json!({ "type": "error", "message": "run stopped" }),
+                );
                 let _ = app.event_tx.send(SseMessage::RunFinished {
                     id,
                     key: key.to_string(),
@@ -894,6 +2236,29 @@ async fn checkout_from_pool(
     }
 }
 
+/// Built-in model prices with the project's `pricing:` overrides applied.
+/// A config that exists but cannot be loaded is logged and leaves the
+/// built-ins in place.
+pub(crate) fn pricing_table(root: &std::path::Path) -> claude_agent::PricingTable {
+    let overrides = match sdlc_core::config::Config::load(root) {
+        Ok(cfg) => cfg.pricing,
+        Err(sdlc_core::error::SdlcError::NotInitialized) => Default::default(),
+        Err(e) => {
+            warn!(error = %e, "ignoring pricing overrides: could not load config");
+            Default::default()
+        }
+    };
+    overrides.into_iter().fold(
+        claude_agent::PricingTable::default(),
+        |table, (model, r)| {
+            table.with_override(
+                model,
+                claude_agent::ModelPricing::new(r.input, r.output, r.cache_write, r.cache_read),
+            )
+        },
+    )
+}
+
 /// Build the standard sdlc MCP query options.
 pub(crate) fn sdlc_query_options(
     root: std::path::PathBuf,
@@ -908,6 +2273,7 @@ pub(crate) fn sdlc_query_options(
         permission_mode: PermissionMode::BypassPermissions,
         mcp_servers: vec![McpServerConfig {
             name: "sdlc".into(),
+            transport: claude_agent::McpTransport::Stdio,
             command: std::env::current_exe()
                 .unwrap_or_else(|_| std::path::PathBuf::from("sdlc"))
                 .to_string_lossy()
@@ -930,9 +2296,11 @@ pub(crate) fn sdlc_query_options(
             "mcp__sdlc__sdlc_complete_task".into(),
             "mcp__sdlc__sdlc_add_comment".into(),
             "mcp__sdlc__sdlc_merge".into(),
+            "mcp__sdlc__sdlc_query".into(),
         ],
         cwd: Some(root),
         max_turns: Some(max_turns),
+        stream_deltas: true,
         ..Default::default()
     }
 }
@@ -971,22 +2339,16 @@ pub async fn start_run(
     };
 
     let opts = sdlc_query_options(app.root.clone(), 200, None);
-    let prompt = match context.as_deref() {
-        Some(ctx) if !ctx.is_empty() => format!(
-            "Drive feature '{}' through the sdlc state machine. \
-             User context: \"{}\". Use this as the core problem statement \
-             when writing artifacts. \
-             Run `sdlc next --for {} --json` to get the next action, \
-             execute it, then loop until done or a HITL gate is reached.",
-            slug, ctx, slug
-        ),
-        _ => format!(
-            "Drive feature '{}' through the sdlc state machine. \
-             Run `sdlc next --for {} --json` to get the next action, \
-             execute it, then loop until done or a HITL gate is reached.",
-            slug, slug
-        ),
+    let mut vars = sdlc_core::prompt_template::PromptVars::new();
+    vars.set("feature_slug", &slug);
+    let template = match context.as_deref() {
+        Some(ctx) if !ctx.is_empty() => {
+            vars.set("context", ctx);
+            "run-with-context"
+        }
+        _ => "run",
     };
+    let prompt = sdlc_core::prompt_template::render_named(&app.root, template, &vars)?;
     let label = slug.clone();
     spawn_agent_run(slug, prompt, opts, &app, "feature", &label, None).await
 }
@@ -996,6 +2358,48 @@ pub async fn run_events(Path(slug): Path<String>, State(app): State<AppState>) -
     get_run_events(&slug, &app).await
 }
 
+#[derive(serde::Deserialize)]
+pub struct InjectBody {
+    pub message: String,
+}
+
+/// POST /api/run/{slug}/inject — send a human message into a live run. The
+/// agent sees it before its next turn; it appears in the run's events as an
+/// `interjection`.
+pub async fn inject_run(
+    Path(slug): Path<String>,
+    State(app): State<AppState>,
+    Json(body): Json<InjectBody>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    validate_slug(&slug)?;
+    inject_into_run(&slug, body.message.trim(), &app).await
+}
+
+async fn inject_into_run(
+    key: &str,
+    message: &str,
+    app: &AppState,
+) -> Result<Json<serde_json::Value>, AppError> {
+    if message.is_empty() {
+        return Err(AppError::bad_request("message must not be empty"));
+    }
+    let interject_tx = match app.agent_runs.lock().await.get(key) {
+        Some((_, _, interject_tx)) => interject_tx.clone(),
+        None => return Err(AppError::not_found(format!("No agent running for '{key}'"))),
+    };
+    let interject_tx = interject_tx.ok_or_else(|| {
+        AppError::conflict(format!(
+            "The agent for '{key}' does not accept messages mid-run"
+        ))
+    })?;
+    interject_tx
+        .send(message.to_string())
+        .await
+        .map_err(|_| AppError::conflict(format!("The agent run for '{key}' has finished")))?;
+    info!(key = %key, "inject_run: interjection queued");
+    Ok(Json(serde_json::json!({ "status": "injected" })))
+}
+
 /// POST /api/run/{slug}/stop — stop a running agent.
 pub async fn stop_run(
     Path(slug): Path<String>,
@@ -1023,6 +2427,7 @@ pub async fn start_milestone_uat(
     let mut opts = sdlc_query_options(app.root.clone(), 200, None);
     opts.mcp_servers.push(McpServerConfig {
         name: "playwright".into(),
+        transport: claude_agent::McpTransport::Stdio,
         command: "npx".into(),
         args: vec!["@playwright/mcp@latest".into(), "--headless".into()],
         env: HashMap::new(),
@@ -1303,25 +2708,131 @@ pub async fn stop_milestone_prepare(
 // Milestone run-wave endpoints
 // ---------------------------------------------------------------------------
 
+/// Minimum gap between `wave_run.yaml` checkpoints while a run-wave agent is
+/// streaming events.
+const WAVE_CHECKPOINT_INTERVAL: Duration = Duration::from_secs(5);
+
+fn run_wave_prompt(slug: &str, resumed: Option<&sdlc_core::wave_run::WaveRun>) -> String {
+    match resumed {
+        Some(run) => format!(
+            "Resume wave {wave} of milestone '{slug}', which was stopped part-way. \
+             These features already completed — do not run them again: {done}. \
+             For each of these remaining features that does not need a worktree, \
+             spawn a parallel Agent call running `/sdlc-run <feature-slug>`: {remaining}. \
+             Wait for all agents to complete, then run \
+             `sdlc project prepare --milestone {slug} --json` and report the updated wave plan.",
+            wave = run.wave,
+            done = run.completed().join(", "),
+            remaining = run.remaining().join(", "),
+        ),
+        None => format!(
+            "Execute the current wave of milestone '{slug}' in parallel. \
+             Run `sdlc project prepare --milestone {slug} --json` to get the live wave plan. \
+             Wave 1 of the output is the current wave. \
+             For each feature in Wave 1 that does not need a worktree, \
+             spawn a parallel Agent call running `/sdlc-run <feature-slug>`. \
+             Wait for all agents to complete, then re-run prepare and report the updated wave plan.",
+        ),
+    }
+}
+
+/// Refresh and save `wave_run.yaml` for `slug`. Progress tracking never
+/// fails the run itself, so errors are only logged.
+async fn checkpoint_wave_run(root: std::path::PathBuf, slug: String) {
+    let result =
+        tokio::task::spawn_blocking(move || sdlc_core::wave_run::checkpoint(&root, &slug)).await;
+    match result {
+        Ok(Ok(_)) => {}
+        Ok(Err(e)) => warn!(error = %e, "failed to checkpoint wave run"),
+        Err(e) => warn!(error = %e, "wave run checkpoint task failed"),
+    }
+}
+
+/// Checkpoint `wave_run.yaml` while the run under `key` executes — at most
+/// every [`WAVE_CHECKPOINT_INTERVAL`] while it streams events, and once more
+/// when it finishes or is stopped.
+async fn track_wave_run(app: &AppState, key: &str, slug: String) {
+    let Some(mut rx) = app
+        .agent_runs
+        .lock()
+        .await
+        .get(key)
+        .map(|(tx, _, _)| tx.subscribe())
+    else {
+        return;
+    };
+    let root = app.root.clone();
+    tokio::spawn(async move {
+        let mut last = tokio::time::Instant::now();
+        loop {
+            match rx.recv().await {
+                Ok(_) | Err(tokio::sync::broadcast::error::RecvError::Lagged(_)) => {
+                    if last.elapsed() < WAVE_CHECKPOINT_INTERVAL {
+                        continue;
+                    }
+                    last = tokio::time::Instant::now();
+                    checkpoint_wave_run(root.clone(), slug.clone()).await;
+                }
+                Err(tokio::sync::broadcast::error::RecvError::Closed) => {
+                    checkpoint_wave_run(root, slug).await;
+                    break;
+                }
+            }
+        }
+    });
+}
+
 /// POST /api/milestone/{slug}/run-wave — spawn a Claude agent that executes
 /// the current wave of a milestone in parallel.
+///
+/// Per-feature progress is kept in `.sdlc/milestones/<slug>/wave_run.yaml`.
+/// When an earlier run-wave was stopped before its wave finished, the new
+/// agent resumes that wave with only the features that are not yet done.
 pub async fn start_milestone_run_wave(
     Path(slug): Path<String>,
     State(app): State<AppState>,
 ) -> Result<Json<serde_json::Value>, AppError> {
     validate_slug(&slug)?;
     let key = format!("milestone-run-wave:{slug}");
+    let root = app.root.clone();
+    let milestone = slug.clone();
+    let tracked = tokio::task::spawn_blocking(move || {
+        sdlc_core::wave_run::begin_or_resume(&root, &milestone)
+    })
+    .await
+    .map_err(|e| AppError(anyhow::anyhow!("task join error: {e}")))?
+    .unwrap_or_else(|e| {
+        warn!(slug = %slug, error = %e, "wave run tracking unavailable");
+        None
+    });
+    let resumed = tracked
+        .as_ref()
+        .filter(|(_, start)| *start == sdlc_core::wave_run::WaveRunStart::Resumed)
+        .map(|(run, _)| run);
+
     let opts = sdlc_query_options(app.root.clone(), 200, None);
-    let prompt = format!(
-        "Execute the current wave of milestone '{slug}' in parallel. \
-         Run `sdlc project prepare --milestone {slug} --json` to get the live wave plan. \
-         Wave 1 of the output is the current wave. \
-         For each feature in Wave 1 that does not need a worktree, \
-         spawn a parallel Agent call running `/sdlc-run <feature-slug>`. \
-         Wait for all agents to complete, then re-run prepare and report the updated wave plan.",
-    );
+    let prompt = run_wave_prompt(&slug, resumed);
     let label = format!("run-wave: {slug}");
-    spawn_agent_run(key, prompt, opts, &app, "milestone_run_wave", &label, None).await
+    let Json(mut resp) = spawn_agent_run(
+        key.clone(),
+        prompt,
+        opts,
+        &app,
+        "milestone_run_wave",
+        &label,
+        None,
+    )
+    .await?;
+    if let Some((run, start)) = &tracked {
+        track_wave_run(&app, &key, slug.clone()).await;
+        resp["resumed"] = serde_json::json!(*start == sdlc_core::wave_run::WaveRunStart::Resumed);
+        resp["wave_run"] = serde_json::json!({
+            "wave": run.wave,
+            "completed": run.completed(),
+            "remaining": run.remaining(),
+        });
+    }
+    Ok(Json(resp))
 }
 
 /// GET /api/milestone/{slug}/run-wave/events — SSE stream of run-wave agent messages.
@@ -1339,7 +2850,9 @@ pub async fn stop_milestone_run_wave(
     State(app): State<AppState>,
 ) -> Json<serde_json::Value> {
     let key = format!("milestone-run-wave:{slug}");
-    stop_run_by_key(&key, &app).await
+    let resp = stop_run_by_key(&key, &app).await;
+    checkpoint_wave_run(app.root.clone(), slug).await;
+    resp
 }
 
 // message_to_event() has been moved into claude_agent::provider::claude::claude_message_to_event().
@@ -1494,7 +3007,7 @@ pub async fn start_ponder_chat(
         slug: slug.clone(),
         session: session_n,
     };
-    let _ = spawn_agent_run(
+    let (_, frames) = spawn_agent_run_subscribed(
         run_key,
         prompt,
         opts,
@@ -1505,6 +3018,23 @@ pub async fn start_ponder_chat(
     )
     .await?;
 
+    // Keep the dialogue on disk as it grows, so a chat cut short by a stop
+    // or a restart still leaves a session behind.
+    let seed = body
+        .message
+        .as_deref()
+        .map(str::trim)
+        .filter(|m| !m.is_empty())
+        .map(str::to_string);
+    tokio::spawn(flush_ponder_dialogue(
+        app.root.clone(),
+        slug.clone(),
+        session_n,
+        owner_name.clone(),
+        seed,
+        frames,
+    ));
+
     Ok(Json(serde_json::json!({
         "status": "started",
         "session": session_n,
@@ -1523,6 +3053,64 @@ pub async fn stop_ponder_chat(
     result
 }
 
+/// Mirror a ponder chat's dialogue into its partial session file as the run
+/// streams, and settle it when the run ends: discarded if the agent logged
+/// session `session_n` itself, logged in its place otherwise.
+///
+/// If the server dies first, the file stays behind for
+/// [`sdlc_core::ponder::recover_partial_sessions`] to log.
+async fn flush_ponder_dialogue(
+    root: std::path::PathBuf,
+    slug: String,
+    session_n: u32,
+    owner_name: String,
+    seed: Option<String>,
+    mut frames: tokio::sync::broadcast::Receiver<RunFrame>,
+) {
+    let owner_turn = |text: &str| format!("**{owner_name} · Owner**\n{text}");
+    let mut dialogue: Vec<String> = seed.as_deref().map(owner_turn).into_iter().collect();
+    loop {
+        let frame = match frames.recv().await {
+            Ok(RunFrame::Agent(json)) => json,
+            Ok(RunFrame::Done) | Err(tokio::sync::broadcast::error::RecvError::Closed) => break,
+            Ok(RunFrame::Lifecycle(_)) | Err(_) => continue,
+        };
+        let Ok(event) = serde_json::from_str::<serde_json::Value>(&frame) else {
+            continue;
+        };
+        let text = event["text"].as_str().unwrap_or_default().trim();
+        let turn = match event["type"].as_str() {
+            Some("assistant") if !text.is_empty() => text.to_string(),
+            Some("interjection") if !text.is_empty() => owner_turn(text),
+            _ => continue,
+        };
+        dialogue.push(turn);
+        let (root2, slug2, content) = (root.clone(), slug.clone(), dialogue.join("\n\n"));
+        let written = tokio::task::spawn_blocking(move || {
+            sdlc_core::ponder::write_partial_session(&root2, &slug2, &content)
+        })
+        .await;
+        if let Ok(Err(e)) = written {
+            warn!(slug = %slug, error = %e, "could not flush ponder dialogue");
+        }
+    }
+
+    let slug2 = slug.clone();
+    let settled = tokio::task::spawn_blocking(move || {
+        if sdlc_core::ponder::next_session_number(&root, &slug2)? > session_n {
+            sdlc_core::ponder::discard_partial_session(&root, &slug2).map(|_| None)
+        } else {
+            sdlc_core::ponder::finalize_partial_session(&root, &slug2)
+        }
+    })
+    .await;
+    match settled {
+        Ok(Ok(Some(n))) => info!(slug = %slug, session = n, "logged interrupted ponder session"),
+        Ok(Err(e)) => warn!(slug = %slug, error = %e, "could not settle ponder dialogue"),
+        _ => {}
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Ponder commit endpoint
 // ---------------------------------------------------------------------------
@@ -1552,11 +3140,10 @@ pub async fn commit_ponder(
             if let Err(e) = entry.save(root) {
                 warn!(slug = %slug, error = %e, "commit_ponder: failed to save committed status");
             }
-            if let Ok(mut state) = sdlc_core::state::State::load(root) {
-                state.remove_ponder(&slug);
-                if let Err(e) = state.save(root) {
-                    warn!(slug = %slug, error = %e, "commit_ponder: failed to update state.yaml");
-                }
+            if let Err(e) =
+                sdlc_core::state::State::update(root, |state| state.remove_ponder(&slug))
+            {
+                warn!(slug = %slug, error = %e, "commit_ponder: failed to update state.yaml");
             }
         }
     }
@@ -2143,10 +3730,77 @@ pub async fn start_team_recruit(
 // Run history endpoints
 // ---------------------------------------------------------------------------
 
-/// GET /api/runs — list all RunRecords (no events).
-pub async fn list_runs(State(app): State<AppState>) -> Json<serde_json::Value> {
+#[derive(serde::Deserialize)]
+pub struct ListRunsQuery {
+    /// Only runs started at or after this cutoff: a duration ago (`2h`,
+    /// `3d`) or an ISO-8601 timestamp.
+    pub since: Option<String>,
+}
+
+/// GET /api/runs — list all RunRecords (no events), optionally `?since=`.
+pub async fn list_runs(
+    Query(params): Query<ListRunsQuery>,
+    State(app): State<AppState>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let since = params
+        .since
+        .as_deref()
+        .map(|s| sdlc_core::since::parse_since(s, chrono::Utc::now()))
+        .transpose()?;
+    let history = app.run_history.lock().await;
+    let runs: Vec<&RunRecord> = history
+        .iter()
+        .filter(|r| since.is_none_or(|cutoff| started_at_or_after(r, cutoff)))
+        .collect();
+    Ok(Json(serde_json::json!(runs)))
+}
+
+/// Whether `record` started at or after `cutoff`. A record whose start time
+/// does not parse is excluded rather than guessed at.
+fn started_at_or_after(record: &RunRecord, cutoff: chrono::DateTime<chrono::Utc>) -> bool {
+    chrono::DateTime::parse_from_rfc3339(&record.started_at).is_ok_and(|t| t >= cutoff)
+}
+
+/// Aggregate cost and duration over finished runs, for the dashboard.
+#[derive(Debug, Default, serde::Serialize)]
+pub struct RunStats {
+    pub finished_runs: usize,
+    pub total_cost_usd: f64,
+    pub total_input_tokens: u64,
+    pub total_output_tokens: u64,
+    /// Finished runs keyed by model; runs that never reported one count as `unknown`.
+    pub runs_per_model: std::collections::BTreeMap<String, usize>,
+    pub average_duration_ms: Option<u64>,
+}
+
+impl RunStats {
+    /// Roll up every record with a `completed_at`; active and queued runs are skipped.
+    pub fn from_records(records: &[RunRecord]) -> Self {
+        let mut stats = RunStats::default();
+        let (mut duration_total, mut timed_runs) = (0u64, 0u64);
+        for rec in records.iter().filter(|r| r.completed_at.is_some()) {
+            stats.finished_runs += 1;
+            stats.total_cost_usd += rec.cost_usd.unwrap_or(0.0);
+            if let Some(usage) = &rec.usage {
+                stats.total_input_tokens += usage.input_tokens;
+                stats.total_output_tokens += usage.output_tokens;
+            }
+            let model = rec.model.as_deref().unwrap_or("unknown");
+            *stats.runs_per_model.entry(model.to_string()).or_default() += 1;
+            if let Some(ms) = rec.duration_ms {
+                duration_total += ms;
+                timed_runs += 1;
+            }
+        }
+        stats.average_duration_ms = duration_total.checked_div(timed_runs);
+        stats
+    }
+}
+
+/// GET /api/runs/stats — cost, token and duration rollup over run history.
+pub async fn get_run_stats(State(app): State<AppState>) -> Json<RunStats> {
     let history = app.run_history.lock().await;
-    Json(serde_json::json!(history.as_slice()))
+    Json(RunStats::from_records(&history))
 }
 
 /// GET /api/runs/{id} — single RunRecord + events (loaded from disk sidecar).
@@ -22,12 +22,7 @@ pub async fn get_state(State(app): State<AppState>) -> Result<Json<serde_json::V
         let feature_summaries: Vec<serde_json::Value> = features
             .iter()
             .map(|f| {
-                let ctx = sdlc_core::classifier::EvalContext {
-                    feature: f,
-                    state: &state,
-                    config: &config,
-                    root: &root,
-                };
+                let ctx = sdlc_core::classifier::EvalContext::new(f, &state, &config, &root);
                 let classification = classifier.classify(&ctx);
                 next_actions.insert(f.slug.clone(), classification.action);
                 serde_json::json!({
@@ -32,6 +32,65 @@ pub async fn add_task(
     Ok(Json(result))
 }
 
+/// POST /api/features/:slug/tasks/bulk — create many tasks in one write.
+///
+/// Body: a JSON array of `{ title, description? }`. Titles already present
+/// (by normalized title) are skipped, so posting the same batch twice is a
+/// no-op. Returns `{ slug, created, skipped, tasks: [{ title, task_id, status }] }`.
+pub async fn add_tasks_bulk(
+    State(app): State<AppState>,
+    Path(slug): Path<String>,
+    Json(specs): Json<Vec<sdlc_core::task::TaskSpec>>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let root = app.root.clone();
+    let result = tokio::task::spawn_blocking(move || {
+        let results = sdlc_core::task::import(&root, &slug, &specs)?;
+        let created = results
+            .iter()
+            .filter(|r| r.status == sdlc_core::task::ImportStatus::Created)
+            .count();
+
+        Ok::<_, sdlc_core::SdlcError>(serde_json::json!({
+            "slug": slug,
+            "created": created,
+            "skipped": results.len() - created,
+            "tasks": results,
+        }))
+    })
+    .await
+    .map_err(|e| AppError(anyhow::anyhow!("task join error: {e}")))??;
+
+    Ok(Json(result))
+}
+
+#[derive(serde::Deserialize)]
+pub struct ReorderTasksBody {
+    pub tasks: Vec<String>,
+}
+
+/// PUT /api/features/:slug/tasks/order — set the execution order of a feature's tasks.
+pub async fn reorder_tasks(
+    State(app): State<AppState>,
+    Path(slug): Path<String>,
+    Json(body): Json<ReorderTasksBody>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let root = app.root.clone();
+    let result = tokio::task::spawn_blocking(move || {
+        let refs: Vec<&str> = body.tasks.iter().map(|s| s.as_str()).collect();
+        sdlc_core::task::reorder(&root, &slug, &refs)?;
+        let feature = sdlc_core::feature::Feature::load(&root, &slug)?;
+
+        Ok::<_, sdlc_core::SdlcError>(serde_json::json!({
+            "slug": slug,
+            "tasks": feature.tasks,
+        }))
+    })
+    .await
+    .map_err(|e| AppError(anyhow::anyhow!("task join error: {e}")))??;
+
+    Ok(Json(result))
+}
+
 /// POST /api/features/:slug/tasks/:id/start — start a task.
 pub async fn start_task(
     State(app): State<AppState>,
@@ -1,5 +1,6 @@
 use axum::extract::{Path, Query, State};
 use axum::http::{HeaderMap, StatusCode};
+use axum::response::{IntoResponse, Response};
 use axum::Json;
 use std::collections::HashMap;
 use tokio::io::{AsyncBufReadExt, BufReader};
@@ -123,8 +124,10 @@ pub async fn get_tool_meta(
 /// The request body must be a JSON object matching the tool's `input_schema`.
 /// An empty body is treated as `{}`.
 ///
-/// For non-streaming tools: returns 200 with the full `ToolResult` JSON once
-/// execution finishes (synchronous, unchanged behavior).
+/// For non-streaming tools: returns 200 with the tool's `ToolRunResult` once
+/// execution finishes. A tool that crashes, exits non-zero, or writes
+/// something other than a result envelope still returns 200, with
+/// `ok: false` and its stderr in `error`.
 ///
 /// For streaming tools (`streaming: true` in `--meta`): returns 202 immediately
 /// with `{ "job_id": "<id>", "streaming": true }`. The tool runs in the
@@ -140,7 +143,7 @@ pub async fn run_tool(
     State(app): State<AppState>,
     Path(name): Path<String>,
     body: Option<Json<serde_json::Value>>,
-) -> Result<(StatusCode, Json<serde_json::Value>), AppError> {
+) -> Result<Response, AppError> {
     validate_tool_name(&name)?;
 
     let input = body.map(|b| b.0).unwrap_or_else(|| serde_json::json!({}));
@@ -378,7 +381,8 @@ pub async fn run_tool(
                 "job_id": interaction_id,
                 "streaming": true
             })),
-        ));
+        )
+            .into_response());
     }
 
     // --- Non-streaming (synchronous) path ---
@@ -405,40 +409,30 @@ pub async fn run_tool(
 
         let stdin_json = serde_json::to_string(&input)
             .map_err(|e| AppError(anyhow::anyhow!("failed to serialize tool input: {e}")))?;
-        let run_result = sdlc_core::tool_runner::run_tool(
+        // A tool that crashes or writes no envelope comes back as `ok: false`
+        // with its stderr, not as an error; only failing to start it is one.
+        let output = sdlc_core::tool_runner::run_tool_result(
             &script,
             "--run",
             Some(&stdin_json),
             &root,
             Some(&extra_env),
-        );
-
-        let (output, status) = match run_result {
-            Ok(stdout) => match serde_json::from_str::<serde_json::Value>(&stdout) {
-                Ok(val) => (val, "completed"),
-                Err(e) => {
-                    return Err(AppError(anyhow::anyhow!(
-                        "tool --run returned invalid JSON: {e}"
-                    )));
-                }
-            },
-            Err(e) => return Err(AppError(e.into())),
-        };
+        )?;
 
-        record.status = status.to_string();
+        record.status = if output.ok { "completed" } else { "failed" }.to_string();
         record.completed_at = Some(chrono::Utc::now().to_rfc3339());
-        record.result = Some(output.clone());
+        record.result = serde_json::to_value(&output).ok();
         if should_persist {
             let _ = sdlc_core::tool_interaction::save_interaction(&root, &record);
             sdlc_core::tool_interaction::enforce_interaction_retention(&root, &name, 200);
         }
 
-        Ok(output)
+        Ok::<_, AppError>(output)
     })
     .await
     .map_err(|e| AppError(anyhow::anyhow!("task join error: {e}")))??;
 
-    Ok((StatusCode::OK, Json(result)))
+    Ok((StatusCode::OK, Json(result)).into_response())
 }
 
 // ---------------------------------------------------------------------------
@@ -743,7 +737,7 @@ pub async fn agent_call(
     // Subscribe to the broadcast channel for this run key
     let rx = {
         let runs = app.agent_runs.lock().await;
-        runs.get(&run_key).map(|(tx, _)| tx.subscribe())
+        runs.get(&run_key).map(|(tx, _, _)| tx.subscribe())
     };
 
     let mut rx = match rx {
@@ -757,7 +751,10 @@ pub async fn agent_call(
     // Wait for a result or error event, with 10-minute timeout
     use tokio::time::{timeout, Duration};
     match timeout(Duration::from_secs(600), async {
-        while let Ok(msg) = rx.recv().await {
+        while let Ok(frame) = rx.recv().await {
+            let crate::state::RunFrame::Agent(msg) = frame else {
+                continue;
+            };
             if let Ok(val) = serde_json::from_str::<serde_json::Value>(&msg) {
                 let event_type = val.get("type").and_then(|t| t.as_str());
                 if event_type == Some("result") {
@@ -0,0 +1,242 @@
+//! Fair dispatch of queued agent runs.
+//!
+//! `spawn_agent_run` starts a run at once when one of the
+//! `max_concurrent_runs` slots is free. Otherwise the run waits here, in a
+//! queue per milestone, and freed slots are handed out round-robin across
+//! milestones — one run from each milestone with runs waiting, in the order
+//! the milestones first queued — instead of strictly first come, first
+//! served. A burst of runs for one milestone therefore cannot hold the whole
+//! agent pool while another milestone waits. Within a milestone runs keep
+//! their queue order; runs whose feature is in no milestone share one queue.
+//!
+//! While anything is queued, a single dispatcher task waits on the slot
+//! semaphore. Being its only waiter means a freed slot reaches the
+//! dispatcher rather than a newly submitted run, so new runs queue behind
+//! the ones already waiting.
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+
+use serde::Serialize;
+use tokio::sync::{oneshot, OwnedSemaphorePermit, Semaphore};
+
+/// A queued run's claim on a slot; resolves with the permit on its turn.
+/// Errors if the slot semaphore is closed.
+pub type SlotTicket = oneshot::Receiver<OwnedSemaphorePermit>;
+
+/// Runs waiting for a slot from one milestone, oldest first.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct QueuedGroup {
+    /// `None` for runs whose feature is in no milestone.
+    pub milestone: Option<String>,
+    pub run_ids: Vec<String>,
+}
+
+struct Waiter {
+    run_id: String,
+    tx: oneshot::Sender<OwnedSemaphorePermit>,
+}
+
+#[derive(Default)]
+struct Inner {
+    /// Milestones with waiting runs, in round-robin order.
+    order: VecDeque<Option<String>>,
+    waiting: HashMap<Option<String>, VecDeque<Waiter>>,
+    dispatching: bool,
+}
+
+impl Inner {
+    /// The next live waiter in round-robin order. Its milestone moves to the
+    /// back of the rotation if it has more runs waiting.
+    fn pop_next(&mut self) -> Option<Waiter> {
+        while let Some(group) = self.order.pop_front() {
+            let Some(queue) = self.waiting.get_mut(&group) else {
+                continue;
+            };
+            let waiter = queue.pop_front();
+            if queue.is_empty() {
+                self.waiting.remove(&group);
+            } else {
+                self.order.push_back(group);
+            }
+            // A run stopped while queued has dropped its ticket.
+            if let Some(w) = waiter.filter(|w| !w.tx.is_closed()) {
+                return Some(w);
+            }
+        }
+        None
+    }
+}
+
+/// Milestone-fair queue in front of the run slot semaphore.
+#[derive(Default)]
+pub struct RunQueue {
+    inner: Mutex<Inner>,
+}
+
+impl RunQueue {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Inner> {
+        self.inner.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Queue `run_id` under `milestone` for a permit from `slots`. Must be
+    /// called from within a Tokio runtime.
+    pub fn enqueue(
+        self: &Arc<Self>,
+        milestone: Option<String>,
+        run_id: &str,
+        slots: Arc<Semaphore>,
+    ) -> SlotTicket {
+        let (tx, rx) = oneshot::channel();
+        let mut inner = self.lock();
+        let queue = inner.waiting.entry(milestone.clone()).or_default();
+        let first = queue.is_empty();
+        queue.push_back(Waiter {
+            run_id: run_id.to_string(),
+            tx,
+        });
+        if first {
+            inner.order.push_back(milestone);
+        }
+        if !inner.dispatching {
+            inner.dispatching = true;
+            tokio::spawn(Arc::clone(self).dispatch(slots));
+        }
+        rx
+    }
+
+    /// Hand each freed slot to the next run in turn; exits once the queue
+    /// is empty.
+    async fn dispatch(self: Arc<Self>, slots: Arc<Semaphore>) {
+        loop {
+            let Ok(permit) = slots.clone().acquire_owned().await else {
+                // Closed: dropping the waiters fails their tickets.
+                let mut inner = self.lock();
+                *inner = Inner::default();
+                return;
+            };
+            let waiter = {
+                let mut inner = self.lock();
+                match inner.pop_next() {
+                    Some(w) => w,
+                    None => {
+                        inner.dispatching = false;
+                        return;
+                    }
+                }
+            };
+            tracing::debug!(run_id = %waiter.run_id, "run queue: dispatching");
+            // A run that stopped in the meantime returns the permit on drop.
+            let _ = waiter.tx.send(permit);
+            // Stop before taking another slot that nobody is waiting for.
+            let mut inner = self.lock();
+            if inner.order.is_empty() {
+                inner.dispatching = false;
+                return;
+            }
+        }
+    }
+
+    /// Runs still waiting, grouped by milestone in dispatch order.
+    pub fn snapshot(&self) -> Vec<QueuedGroup> {
+        let inner = self.lock();
+        inner
+            .order
+            .iter()
+            .filter_map(|group| {
+                let run_ids: Vec<String> = inner
+                    .waiting
+                    .get(group)?
+                    .iter()
+                    .filter(|w| !w.tx.is_closed())
+                    .map(|w| w.run_id.clone())
+                    .collect();
+                (!run_ids.is_empty()).then(|| QueuedGroup {
+                    milestone: group.clone(),
+                    run_ids,
+                })
+            })
+            .collect()
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn slots_alternate_between_milestones() {
+        let queue = Arc::new(RunQueue::new());
+        let slots = Arc::new(Semaphore::new(0));
+        let runs = [
+            ("a", "a1"),
+            ("a", "a2"),
+            ("a", "a3"),
+            ("b", "b1"),
+            ("b", "b2"),
+        ];
+        let mut tickets: Vec<_> = runs
+            .iter()
+            .map(|(m, id)| {
+                let ticket = queue.enqueue(Some(m.to_string()), id, slots.clone());
+                (id.to_string(), ticket)
+            })
+            .collect();
+        assert_eq!(
+            queue.snapshot(),
+            vec![
+                QueuedGroup {
+                    milestone: Some("a".into()),
+                    run_ids: vec!["a1".into(), "a2".into(), "a3".into()],
+                },
+                QueuedGroup {
+                    milestone: Some("b".into()),
+                    run_ids: vec!["b1".into(), "b2".into()],
+                },
+            ]
+        );
+
+        // Free one slot at a time, holding each permit so exactly one ticket
+        // resolves per slot.
+        let (mut order, mut held) = (Vec::new(), Vec::new());
+        for _ in 0..runs.len() {
+            slots.add_permits(1);
+            loop {
+                tokio::task::yield_now().await;
+                let granted = tickets
+                    .iter_mut()
+                    .enumerate()
+                    .find_map(|(i, (_, t))| t.try_recv().ok().map(|p| (i, p)));
+                if let Some((i, permit)) = granted {
+                    order.push(tickets.remove(i).0);
+                    held.push(permit);
+                    break;
+                }
+            }
+        }
+        assert_eq!(order, vec!["a1", "b1", "a2", "b2", "a3"]);
+        assert!(queue.snapshot().is_empty());
+    }
+
+    #[tokio::test]
+    async fn stopped_runs_are_skipped() {
+        let queue = Arc::new(RunQueue::new());
+        let slots = Arc::new(Semaphore::new(0));
+        drop(queue.enqueue(None, "gone", slots.clone()));
+        let mut kept = queue.enqueue(None, "kept", slots.clone());
+        assert_eq!(queue.snapshot()[0].run_ids, vec!["kept".to_string()]);
+
+        slots.add_permits(1);
+        let permit = (&mut kept).await.unwrap();
+        drop(permit);
+        assert_eq!(slots.available_permits(), 1);
+    }
+}
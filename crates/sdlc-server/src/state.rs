@@ -11,9 +11,56 @@ use crate::hub::{HubRegistry, HubSseMessage, ProjectStatus};
 use crate::auth::TunnelConfig;
 use crate::tunnel::Tunnel;
 
-/// Entry in the active-runs map: the broadcast sender for SSE subscribers
-/// and an abort handle to cancel the spawned task.
-pub type AgentRunEntry = (broadcast::Sender<String>, tokio::task::AbortHandle);
+/// Frames held in a run's broadcast channel. The channel is a ring buffer
+/// with a cursor per SSE subscriber: the run never waits on a subscriber,
+/// and one that falls more than this many frames behind skips the oldest and
+/// is sent a `lagged` event instead.
+pub const RUN_EVENT_BUFFER: usize = 512;
+
+/// Entry in the active-runs map: the broadcast sender for SSE subscribers,
+/// an abort handle to cancel the spawned task, and the queue for human
+/// interjections (`None` when the provider cannot take input mid-run).
+pub type AgentRunEntry = (
+    broadcast::Sender<RunFrame>,
+    tokio::task::AbortHandle,
+    Option<tokio::sync::mpsc::Sender<String>>,
+);
+
+/// One frame on a run's broadcast channel, sent to `/events` subscribers as
+/// an SSE event named `agent`, `lifecycle`, or `done` (plus `lagged`, which
+/// the stream emits itself when a subscriber skips frames).
+#[derive(Clone, Debug)]
+pub enum RunFrame {
+    /// A serialized agent event (or a synthetic `error` / `interjection`).
+    Agent(String),
+    Lifecycle(RunLifecycle),
+    /// Last frame of every run; always directly follows the terminal
+    /// `completed` or `failed` lifecycle frame.
+    Done,
+}
+
+/// Explicit lifecycle transitions of a run, so clients can drive progress
+/// without inferring it from the message stream.
+#[derive(Clone, Debug, serde::Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum RunLifecycle {
+    /// The run holds a slot and the agent is being spawned.
+    Started {
+        run_id: String,
+    },
+    /// The agent issued tool calls in one assistant turn.
+    ToolBatch {
+        tools: Vec<String>,
+    },
+    /// `status` is `completed`, or `paused` when the turn budget ran out.
+    Completed {
+        status: String,
+        usage: Option<claude_agent::TokenUsage>,
+    },
+    Failed {
+        error: String,
+    },
+}
 
 /// Owns a set of background watcher task abort handles.
 /// Calls `.abort()` on every handle when dropped, ensuring watcher tasks
@@ -56,6 +103,15 @@ pub struct RunRecord {
     pub session_id: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub stop_reason: Option<String>,
+    /// Model reported by the agent's init event.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    /// Token counts from the terminal result event.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub usage: Option<claude_agent::TokenUsage>,
+    /// Wall-clock time from the run taking a slot to its result.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duration_ms: Option<u64>,
 }
 
 /// Generate a timestamp-based run ID: "20260227-143022-abc"
@@ -103,9 +159,9 @@ pub fn load_run_history(root: &Path) -> Vec<RunRecord> {
         .filter_map(|e| {
             let data = std::fs::read_to_string(e.path()).ok()?;
             let mut rec: RunRecord = serde_json::from_str(&data).ok()?;
-            // Mark stale running records as failed — they were orphaned by a crash
-            // or restart, not stopped intentionally by the user.
-            if rec.status == "running" {
+            // Mark stale running/queued records as failed — they were orphaned
+            // by a crash or restart, not stopped intentionally by the user.
+            if rec.status == "running" || rec.status == "queued" {
                 rec.status = "failed".to_string();
                 rec.completed_at = Some(chrono::Utc::now().to_rfc3339());
                 rec.error = Some("server restarted".to_string());
@@ -187,6 +243,7 @@ pub fn enforce_retention(root: &Path, max: usize) {
         let _ = std::fs::remove_file(&path);
         let events_path = dir.join(format!("{id}.events.json"));
         let _ = std::fs::remove_file(events_path);
+        let _ = claude_agent::SessionStore::new(root).clear_transcript(&id);
     }
 }
 
@@ -219,6 +276,13 @@ pub enum SseMessage {
         run_type: String,
         target: String,
     },
+    /// A started run changed status before finishing: `queued` while it
+    /// waits for a free slot, then `running` once it gets one.
+    RunStatus {
+        id: String,
+        key: String,
+        status: String,
+    },
     /// An agent run finished (completed, failed, stopped).
     RunFinished {
         id: String,
@@ -342,6 +406,65 @@ pub struct AppTunnelSnapshot {
     pub url: Option<String>,
 }
 
+// ---------------------------------------------------------------------------
+// State-keyed cache
+// ---------------------------------------------------------------------------
+
+/// A value computed from the project — the `GET /api/project/summary`
+/// result, the search index — and reused while `state.yaml` keeps the same
+/// mtime and the value is younger than the TTL it is read with.
+pub struct StateCache<T> {
+    entry: Mutex<Option<StateCacheEntry<T>>>,
+}
+
+struct StateCacheEntry<T> {
+    state_mtime: Option<std::time::SystemTime>,
+    computed_at: std::time::Instant,
+    value: T,
+}
+
+impl<T> Default for StateCache<T> {
+    fn default() -> Self {
+        Self {
+            entry: Mutex::new(None),
+        }
+    }
+}
+
+impl<T: Clone> StateCache<T> {
+    /// The cached value if it is still valid for `root`, otherwise the
+    /// result of `compute`, cached on success. Concurrent callers wait for
+    /// one computation instead of each starting their own.
+    pub async fn get_or_compute<E, F>(
+        &self,
+        root: &Path,
+        ttl: std::time::Duration,
+        compute: impl FnOnce() -> F,
+    ) -> Result<T, E>
+    where
+        F: std::future::Future<Output = Result<T, E>>,
+    {
+        let state_mtime = std::fs::metadata(sdlc_core::paths::state_path(root))
+            .and_then(|m| m.modified())
+            .ok();
+
+        let mut entry = self.entry.lock().await;
+        if let Some(cached) = entry.as_ref() {
+            if cached.state_mtime == state_mtime && cached.computed_at.elapsed() < ttl {
+                return Ok(cached.value.clone());
+            }
+        }
+
+        let value = compute().await?;
+        *entry = Some(StateCacheEntry {
+            state_mtime,
+            computed_at: std::time::Instant::now(),
+            value: value.clone(),
+        });
+        Ok(value)
+    }
+}
+
 // ---------------------------------------------------------------------------
 // AppState
 // ---------------------------------------------------------------------------
@@ -358,6 +481,13 @@ pub struct AppState {
     pub agent_runs: Arc<Mutex<HashMap<String, AgentRunEntry>>>,
     /// Persistent run history (active + completed).
     pub run_history: Arc<Mutex<Vec<RunRecord>>>,
+    /// Execution slots for agent runs, sized from `max_concurrent_runs` in
+    /// config.yaml. `spawn_agent_run` holds a permit for the lifetime of the
+    /// agent process; runs that cannot get one wait as `queued`.
+    pub run_slots: Arc<tokio::sync::Semaphore>,
+    /// Runs waiting for a `run_slots` permit, dispatched round-robin by
+    /// milestone.
+    pub run_queue: Arc<crate::run_queue::RunQueue>,
     /// Atomic snapshot of tunnel auth config + URL.
     /// Written once on tunnel start and once on stop — never partially updated.
     pub tunnel_snapshot: Arc<RwLock<TunnelSnapshot>>,
@@ -370,6 +500,12 @@ pub struct AppState {
     pub app_tunnel_handle: Arc<Mutex<Option<Tunnel>>>,
     /// HTTP client for reverse-proxying app tunnel requests.
     pub http_client: reqwest::Client,
+    /// Retries for app-tunnel GET/HEAD requests refused by the dev server.
+    /// Read once from `app_proxy_retries` in config.yaml at startup.
+    pub app_proxy_retries: u32,
+    /// Keep-alive, body limit, and timeouts: config.yaml `http`, overridden
+    /// by `sdlc ui` flags.
+    pub http: sdlc_core::config::HttpConfig,
     /// Telemetry backend for persisting raw agent events across restarts.
     /// Populated asynchronously at startup via a background task.
     /// Uses redb (local) or PostgreSQL (cluster) depending on `DATABASE_URL`.
@@ -421,6 +557,17 @@ pub struct AppState {
     /// for all `spawn_agent_run` calls. Defaults to `ClaudeProvider`.
     /// Set via `AGENT_PROVIDER=codex` env var.
     pub agent_provider: Arc<dyn claude_agent::AgentProvider>,
+    /// Transport for runs whose options leave it at the default subprocess.
+    /// Tests set a `Transport::Mock` to drive routes with scripted agent
+    /// messages instead of a real `claude` binary.
+    pub agent_transport: claude_agent::Transport,
+    /// Short-lived cache for `GET /api/project/summary`.
+    pub project_summary_cache: Arc<StateCache<sdlc_core::prepare::ProjectSummary>>,
+    /// Shared entity index for `GET /api/query/search`.
+    pub search_index_cache: Arc<StateCache<Arc<sdlc_core::search::EntityIndex>>>,
+    /// Recent `Idempotency-Key`s on run-starting POSTs, so a retried start
+    /// replays the first run's id instead of spawning a duplicate.
+    pub run_idempotency: Arc<crate::idempotency::IdempotencyKeys>,
 }
 
 /// Generate a 32-char hex token (128-bit entropy) from the OS CSPRNG.
@@ -515,9 +662,17 @@ impl AppState {
         tracing::debug!(count = history.len(), "run history loaded");
         // Seed the app tunnel port from config.yaml so it survives restarts.
         tracing::debug!("loading config");
-        let saved_app_port = sdlc_core::config::Config::load(&root)
-            .ok()
-            .and_then(|c| c.app_port);
+        let config = sdlc_core::config::Config::load(&root).ok();
+        let saved_app_port = config.as_ref().and_then(|c| c.app_port);
+        let max_concurrent_runs = config.as_ref().map_or(
+            sdlc_core::config::DEFAULT_MAX_CONCURRENT_RUNS,
+            sdlc_core::config::Config::max_concurrent_runs,
+        );
+        let app_proxy_retries = config.as_ref().map_or(
+            sdlc_core::config::DEFAULT_APP_PROXY_RETRIES,
+            sdlc_core::config::Config::app_proxy_retries,
+        );
+        let http = config.as_ref().and_then(|c| c.http).unwrap_or_default();
         tracing::debug!("building http client");
         let http_client = reqwest::Client::builder()
             .redirect(reqwest::redirect::Policy::none())
@@ -570,6 +725,8 @@ impl AppState {
             event_tx: tx,
             agent_runs: Arc::new(Mutex::new(HashMap::new())),
             run_history: Arc::new(Mutex::new(history)),
+            run_slots: Arc::new(tokio::sync::Semaphore::new(max_concurrent_runs)),
+            run_queue: Arc::new(crate::run_queue::RunQueue::new()),
             tunnel_snapshot: Arc::new(RwLock::new(initial_tunnel_snapshot)),
             tunnel_handle: Arc::new(Mutex::new(None)),
             app_tunnel_snapshot: Arc::new(RwLock::new(AppTunnelSnapshot {
@@ -578,6 +735,8 @@ impl AppState {
             })),
             app_tunnel_handle: Arc::new(Mutex::new(None)),
             http_client,
+            app_proxy_retries,
+            http,
             telemetry,
             orchestrator,
             _watcher_handles: Arc::new(WatcherGuard(Vec::new())),
@@ -604,6 +763,10 @@ impl AppState {
             invite_store: Arc::new(OnceLock::new()),
             notify_client: None,
             agent_provider: select_agent_provider(),
+            agent_transport: claude_agent::Transport::default(),
+            project_summary_cache: Arc::default(),
+            search_index_cache: Arc::default(),
+            run_idempotency: Arc::new(crate::idempotency::IdempotencyKeys::from_env()),
             root,
         }
     }
@@ -736,6 +899,15 @@ impl AppState {
 
     pub fn new_with_port(root: PathBuf, port: u16) -> Self {
         let state = Self::build_base_state(root, port);
+        // Ponder chats cut off by a crash left their dialogue behind.
+        match sdlc_core::ponder::recover_partial_sessions(&state.root) {
+            Ok(recovered) => {
+                for (slug, session) in recovered {
+                    tracing::info!(%slug, session, "recovered interrupted ponder session");
+                }
+            }
+            Err(e) => tracing::warn!(error = %e, "could not recover ponder sessions"),
+        }
         let tx = state.event_tx.clone();
 
         // Open storage backends in a background task.
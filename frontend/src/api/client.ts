@@ -17,6 +17,7 @@ export const api = {
   getFeatures: () => request<import('@/lib/types').FeatureSummary[]>('/api/features'),
   getFeature: (slug: string) => request<import('@/lib/types').FeatureDetail>(`/api/features/${slug}`),
   getFeatureNext: (slug: string) => request<import('@/lib/types').Classification>(`/api/features/${slug}/next`),
+  getNextAll: () => request<import('@/lib/types').Classification[]>('/api/next'),
   createFeature: (body: { slug: string; title: string; description?: string }) =>
     request('/api/features', { method: 'POST', body: JSON.stringify(body) }),
   transitionFeature: (slug: string, phase: string) =>
@@ -25,6 +26,7 @@ export const api = {
   getMilestones: () => request<import('@/lib/types').MilestoneSummary[]>('/api/milestones'),
   getMilestone: (slug: string) => request<import('@/lib/types').MilestoneDetail>(`/api/milestones/${slug}`),
   reviewMilestone: (slug: string) => request<import('@/lib/types').MilestoneReview>(`/api/milestones/${slug}/review`),
+  getMilestoneBurndown: (slug: string) => request<import('@/lib/types').MilestoneBurndown>(`/api/milestones/${slug}/burndown`),
   createMilestone: (body: { slug: string; title: string }) =>
     request('/api/milestones', { method: 'POST', body: JSON.stringify(body) }),
   addFeatureToMilestone: (milestoneSlug: string, featureSlug: string) =>
@@ -39,6 +41,18 @@ export const api = {
 
   getArtifact: (slug: string, type_: string) =>
     request<import('@/lib/types').Artifact>(`/api/artifacts/${slug}/${type_}`),
+  getArtifactDetails: (slug: string, type_: string) =>
+    request<import('@/lib/types').ArtifactDetails>(`/api/artifacts/${slug}/${type_}?format=json`),
+  getArtifactHtml: async (slug: string, type_: string): Promise<string> => {
+    const res = await fetch(`${BASE}/api/artifacts/${slug}/${type_}?format=html`)
+    if (!res.ok) {
+      const body = await res.json().catch(() => ({ error: res.statusText }))
+      throw new Error(body.error || res.statusText)
+    }
+    return res.text()
+  },
+  getArtifactHistory: (slug: string, type_: string) =>
+    request<import('@/lib/types').ArtifactHistory>(`/api/artifacts/${slug}/${type_}/history`),
   approveArtifact: (slug: string, type_: string, by?: string) =>
     request(`/api/artifacts/${slug}/${type_}/approve`, { method: 'POST', body: JSON.stringify({ by }) }),
   rejectArtifact: (slug: string, type_: string, reason?: string) =>
@@ -48,6 +62,8 @@ export const api = {
 
   addTask: (slug: string, title: string) =>
     request(`/api/features/${slug}/tasks`, { method: 'POST', body: JSON.stringify({ title }) }),
+  addTasksBulk: (slug: string, tasks: { title: string; description?: string }[]) =>
+    request<import('@/lib/types').BulkTaskImport>(`/api/features/${slug}/tasks/bulk`, { method: 'POST', body: JSON.stringify(tasks) }),
   startTask: (slug: string, taskId: string) =>
     request(`/api/features/${slug}/tasks/${taskId}/start`, { method: 'POST' }),
   completeTask: (slug: string, taskId: string) =>
@@ -57,7 +73,7 @@ export const api = {
     request(`/api/features/${slug}/comments`, { method: 'POST', body: JSON.stringify({ body, flag, by }) }),
 
   diagnose: (description: string) =>
-    request<import('@/lib/types').DiagnoseResult>('/api/diagnose', {
+    request<import('@/lib/types').DiagnoseReport>('/api/diagnose', {
       method: 'POST',
       body: JSON.stringify({ description }),
     }),
@@ -69,6 +85,11 @@ export const api = {
     }),
   stopRun: (slug: string) =>
     request<{ status: string; message: string }>(`/api/run/${slug}/stop`, { method: 'POST' }),
+  injectRun: (slug: string, message: string) =>
+    request<{ status: string }>(`/api/run/${slug}/inject`, {
+      method: 'POST',
+      body: JSON.stringify({ message }),
+    }),
 
   startMilestoneUat: (slug: string) =>
     request<{ status: string; message: string }>(`/api/milestone/${encodeURIComponent(slug)}/uat`, { method: 'POST' }),
@@ -101,7 +122,7 @@ export const api = {
 
   getConfig: () => request<import('@/lib/types').ProjectConfig>('/api/config'),
   updateConfig: (body: { name?: string; description?: string }) =>
-    request<import('@/lib/types').ProjectConfig>('/api/config', { method: 'PATCH', body: JSON.stringify(body) }),
+    request<import('@/lib/types').ConfigUpdateResult>('/api/config', { method: 'PATCH', body: JSON.stringify(body) }),
 
   querySearch: (q: string, limit = 10) =>
     request<import('@/lib/types').QuerySearchResponse>(`/api/query/search?q=${encodeURIComponent(q)}&limit=${limit}`),
@@ -113,6 +134,8 @@ export const api = {
     request<import('@/lib/types').QueryReadyItem[]>(`/api/query/ready${phase ? `?phase=${encodeURIComponent(phase)}` : ''}`),
   queryNeedsApproval: () =>
     request<import('@/lib/types').QueryNeedsApprovalItem[]>('/api/query/needs-approval'),
+  queryStale: (olderThan?: string) =>
+    request<import('@/lib/types').QueryStaleItem[]>(`/api/query/stale${olderThan ? `?older_than=${encodeURIComponent(olderThan)}` : ''}`),
 
   // Project prepare
   getProjectPhase: () => request<import('@/lib/types').ProjectPhase>('/api/project/phase'),
@@ -195,7 +218,11 @@ export const api = {
     request<void>(`/api/investigation/${slug}/chat/current`, { method: 'DELETE' }),
 
   // Run history
-  getRuns: () => request<import('@/lib/types').RunRecord[]>('/api/runs'),
+  getRuns: (since?: string) =>
+    request<import('@/lib/types').RunRecord[]>(
+      since ? `/api/runs?since=${encodeURIComponent(since)}` : '/api/runs',
+    ),
+  getRunStats: () => request<import('@/lib/types').RunStats>('/api/runs/stats'),
   getRun: (id: string) => request<import('@/lib/types').RunRecord & { events: import('@/lib/types').RawRunEvent[] }>(`/api/runs/${id}`),
   getRunTelemetry: (id: string) => request<import('@/lib/types').RunTelemetry>(`/api/runs/${id}/telemetry`),
 
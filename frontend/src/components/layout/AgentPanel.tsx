@@ -1,6 +1,6 @@
 import { useState, useCallback, useRef, useEffect } from 'react'
 import { Link } from 'react-router-dom'
-import { useAgentRuns } from '@/contexts/AgentRunContext'
+import { isActiveRun, useAgentRuns } from '@/contexts/AgentRunContext'
 import { RunCard } from './RunCard'
 import { QuotaPanel } from './QuotaPanel'
 import { FullscreenModal } from '@/components/shared/FullscreenModal'
@@ -21,8 +21,8 @@ function clamp(val: number, min: number, max: number) {
 function RunList() {
   const { runs, expandedRunIds, toggleRun } = useAgentRuns()
 
-  const activeRuns = runs.filter(r => r.status === 'running')
-  const completedRuns = runs.filter(r => r.status !== 'running')
+  const activeRuns = runs.filter(isActiveRun)
+  const completedRuns = runs.filter(r => !isActiveRun(r))
 
   if (runs.length === 0) {
     return <p className="text-xs text-muted-foreground text-center py-8">No recent activity</p>
@@ -1,10 +1,10 @@
 import { useCallback, useEffect, useRef, useState } from 'react'
 import { Link } from 'react-router-dom'
-import { Loader2, CheckCircle2, XCircle, StopCircle, ChevronDown, ChevronRight, Square, ExternalLink } from 'lucide-react'
+import { Loader2, CheckCircle2, XCircle, StopCircle, ChevronDown, ChevronRight, Square, ExternalLink, Clock } from 'lucide-react'
 import { RunActivityFeed } from '@/components/runs/RunActivityFeed'
 import { ActivityTimeSeries } from '@/components/runs/ActivityTimeSeries'
 import { useRunTelemetry } from '@/hooks/useRunTelemetry'
-import { useAgentRuns } from '@/contexts/AgentRunContext'
+import { isActiveRun, useAgentRuns } from '@/contexts/AgentRunContext'
 import { runTargetRoute } from '@/lib/routing'
 import type { RawRunEvent, RunRecord, RunType } from '@/lib/types'
 
@@ -33,6 +33,8 @@ interface RunCardProps {
 
 function StatusIcon({ status }: { status: string }) {
   switch (status) {
+    case 'queued':
+      return <Clock className="w-4 h-4 text-muted-foreground shrink-0" aria-label="Queued" />
     case 'running':
       return <Loader2 className="w-4 h-4 text-primary animate-spin shrink-0" />
     case 'completed':
@@ -57,10 +59,12 @@ function formatTime(iso: string) {
 export function RunCard({ run, expanded, onToggle }: RunCardProps) {
   const { stopRun } = useAgentRuns()
   const [liveEvents, setLiveEvents] = useState<RawRunEvent[]>([])
+  // Assistant text streamed so far for the message in progress
+  const [draftText, setDraftText] = useState('')
   const [stopping, setStopping] = useState(false)
   const eventSourceRef = useRef<EventSource | null>(null)
 
-  const isActive = run.status === 'running'
+  const isActive = isActiveRun(run)
 
   // For active runs: open EventSource when expanded to show live log
   useEffect(() => {
@@ -86,6 +90,11 @@ export function RunCard({ run, expanded, onToggle }: RunCardProps) {
     es.addEventListener('agent', (e) => {
       try {
         const event = JSON.parse(e.data) as RawRunEvent
+        if (event.type === 'assistant_delta') {
+          setDraftText(prev => prev + (event.text ?? ''))
+          return
+        }
+        if (event.type === 'assistant') setDraftText('')
         setLiveEvents(prev => [...prev, event])
       } catch {
         // ignore parse errors
@@ -107,6 +116,7 @@ export function RunCard({ run, expanded, onToggle }: RunCardProps) {
   const handleToggle = useCallback(() => {
     if (expanded) {
       setLiveEvents([])
+      setDraftText('')
     }
     onToggle()
   }, [expanded, onToggle])
@@ -131,6 +141,7 @@ export function RunCard({ run, expanded, onToggle }: RunCardProps) {
             <p className="text-xs font-medium truncate">{run.label}</p>
             <div className="flex items-center gap-1.5 text-[10px] text-muted-foreground mt-0.5">
               <span>{formatTime(run.started_at)}</span>
+              {run.status === 'queued' && <span>· queued</span>}
               {run.cost_usd != null && <span>· ${run.cost_usd.toFixed(2)}</span>}
               {run.turns != null && <span>· {run.turns} turns</span>}
               {run.error && <span className="text-red-400 truncate">· {run.error.slice(0, 40)}</span>}
@@ -180,6 +191,9 @@ export function RunCard({ run, expanded, onToggle }: RunCardProps) {
             <div className="space-y-3">
               <ActivityTimeSeries events={liveEvents} isRunning={true} />
               <RunActivityFeed runId={run.id} isRunning={true} events={liveEvents} />
+              {draftText && (
+                <p className="text-xs text-muted-foreground whitespace-pre-wrap">{draftText}</p>
+              )}
             </div>
           ) : (
             <CompletedRunPanel runId={run.id} />
@@ -147,7 +147,7 @@ export function pairEvents(
       }
 
       default:
-        // skip: system, stream_event, auth_status, status
+        // skip: system, stream_event, assistant_delta, auth_status, permission_request, status
         break
     }
   }
@@ -1,9 +1,9 @@
 import { useEffect, useRef, useState } from 'react'
 import { useNavigate } from 'react-router-dom'
-import { Zap, AlertTriangle, ArrowLeft, FileCode } from 'lucide-react'
+import { Zap, AlertTriangle, ArrowLeft, FileCode, Copy, Link2, History } from 'lucide-react'
 import { api } from '@/api/client'
 import { useAgentRuns } from '@/contexts/AgentRunContext'
-import type { DiagnoseResult } from '@/lib/types'
+import type { DiagnoseFinding, DiagnoseFindingKind, DiagnoseReport } from '@/lib/types'
 
 interface FixRightAwayModalProps {
   open: boolean
@@ -40,13 +40,59 @@ function ConfidenceBadge({ confidence }: { confidence: string }) {
   )
 }
 
+const FINDING_META: Record<DiagnoseFindingKind, { label: string; icon: typeof Copy }> = {
+  duplicate_candidate: { label: 'Possible duplicate', icon: Copy },
+  missing_dependency: { label: 'Related in-flight work', icon: Link2 },
+  scope_overlap: { label: 'Overlaps shipped feature', icon: History },
+}
+
+function FindingsChecklist({
+  findings,
+  checked,
+  onToggle,
+}: {
+  findings: DiagnoseFinding[]
+  checked: Set<string>
+  onToggle: (key: string) => void
+}) {
+  return (
+    <div className="space-y-1.5">
+      <span className="text-xs font-semibold text-muted-foreground uppercase tracking-wide">Before you fix</span>
+      {findings.map(f => {
+        const key = `${f.kind}:${f.feature}`
+        const { label, icon: Icon } = FINDING_META[f.kind]
+        return (
+          <label key={key} className="flex items-start gap-2 p-2 rounded border border-border/60 bg-muted/20 cursor-pointer">
+            <input
+              type="checkbox"
+              checked={checked.has(key)}
+              onChange={() => onToggle(key)}
+              className="mt-0.5"
+            />
+            <div className="min-w-0 space-y-0.5">
+              <div className="flex items-center gap-1.5 text-xs">
+                <Icon className="w-3 h-3 text-muted-foreground shrink-0" />
+                <span className="font-medium">{label}</span>
+                <span className="font-mono text-muted-foreground truncate">{f.feature}</span>
+                <span className="ml-auto font-mono text-muted-foreground">{Math.round(f.confidence * 100)}%</span>
+              </div>
+              <p className="text-xs text-muted-foreground">{f.suggested_action}</p>
+            </div>
+          </label>
+        )
+      })}
+    </div>
+  )
+}
+
 export function FixRightAwayModal({ open, onClose, initialDescription }: FixRightAwayModalProps) {
   const navigate = useNavigate()
   const { setPanelOpen } = useAgentRuns()
 
   const [step, setStep] = useState<Step>('input')
   const [description, setDescription] = useState(initialDescription ?? '')
-  const [diagnosis, setDiagnosis] = useState<DiagnoseResult | null>(null)
+  const [diagnosis, setDiagnosis] = useState<DiagnoseReport | null>(null)
+  const [checkedFindings, setCheckedFindings] = useState<Set<string>>(new Set())
   const [title, setTitle] = useState('')
   const [error, setError] = useState<string | null>(null)
 
@@ -59,6 +105,7 @@ export function FixRightAwayModal({ open, onClose, initialDescription }: FixRigh
       setStep('input')
       setDescription(initialDescription ?? '')
       setDiagnosis(null)
+      setCheckedFindings(new Set())
       setTitle('')
       setError(null)
       setTimeout(() => textareaRef.current?.focus(), 0)
@@ -88,6 +135,7 @@ export function FixRightAwayModal({ open, onClose, initialDescription }: FixRigh
     try {
       const result = await api.diagnose(description)
       setDiagnosis(result)
+      setCheckedFindings(new Set())
       setTitle(toFixSlug(result.title))
       setStep('review')
     } catch (e) {
@@ -99,6 +147,7 @@ export function FixRightAwayModal({ open, onClose, initialDescription }: FixRigh
         root_cause: 'Diagnosis unavailable — proceeding with raw description.',
         files_affected: [],
         confidence: 'low',
+        findings: [],
       })
       setTitle(fallbackTitle)
       setStep('review')
@@ -229,6 +278,21 @@ export function FixRightAwayModal({ open, onClose, initialDescription }: FixRigh
               )}
             </div>
 
+            {diagnosis.findings.length > 0 && (
+              <FindingsChecklist
+                findings={diagnosis.findings}
+                checked={checkedFindings}
+                onToggle={key =>
+                  setCheckedFindings(prev => {
+                    const next = new Set(prev)
+                    if (next.has(key)) next.delete(key)
+                    else next.add(key)
+                    return next
+                  })
+                }
+              />
+            )}
+
             {/* Editable slug / feature title */}
             <div className="space-y-1">
               <label className="text-xs text-muted-foreground">Feature slug</label>
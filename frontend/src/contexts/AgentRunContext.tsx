@@ -29,6 +29,11 @@ interface AgentRunContextValue {
 
 const AgentRunContext = createContext<AgentRunContextValue | null>(null)
 
+/** Queued runs count as active: they hold a place and can be stopped. */
+export function isActiveRun(run: RunRecord): boolean {
+  return run.status === 'running' || run.status === 'queued'
+}
+
 const PANEL_STORAGE_KEY = 'sdlc-agent-panel-open'
 
 function isDesktop() {
@@ -74,6 +79,10 @@ export function AgentRunProvider({ children }: { children: ReactNode }) {
         }
         return [newRun, ...prev]
       })
+    } else if (event.type === 'run_status') {
+      setRuns(prev =>
+        prev.map(r => (r.id === event.id ? { ...r, status: (event.status ?? r.status) as RunStatus } : r)),
+      )
     } else if (event.type === 'run_finished') {
       setRuns(prev =>
         prev.map(r =>
@@ -91,15 +100,15 @@ export function AgentRunProvider({ children }: { children: ReactNode }) {
   const noop = useCallback(() => {}, [])
   useSSE(noop, undefined, handleRunEvent)
 
-  const activeRuns = useMemo(() => runs.filter(r => r.status === 'running'), [runs])
+  const activeRuns = useMemo(() => runs.filter(isActiveRun), [runs])
 
   const isRunning = useCallback(
-    (key: string) => runs.some(r => r.key === key && r.status === 'running'),
+    (key: string) => runs.some(r => r.key === key && isActiveRun(r)),
     [runs],
   )
 
   const getRunForKey = useCallback(
-    (key: string) => runs.find(r => r.key === key && r.status === 'running'),
+    (key: string) => runs.find(r => r.key === key && isActiveRun(r)),
     [runs],
   )
 
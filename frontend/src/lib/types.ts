@@ -89,6 +89,18 @@ export interface ProjectConfig {
   }
 }
 
+/** One field changed by `PATCH /api/config`, by dotted path. */
+export interface FieldChange {
+  path: string
+  old: unknown
+  new: unknown
+}
+
+export interface ConfigUpdateResult {
+  config: ProjectConfig
+  changes: FieldChange[]
+}
+
 // ---------------------------------------------------------------------------
 // Query types
 // ---------------------------------------------------------------------------
@@ -102,12 +114,31 @@ export interface DiagnoseResult {
   confidence: string
 }
 
+export type DiagnoseFindingKind = 'duplicate_candidate' | 'missing_dependency' | 'scope_overlap'
+
+export interface DiagnoseFinding {
+  kind: DiagnoseFindingKind
+  /** Slug of the existing feature the finding refers to */
+  feature: string
+  title: string
+  /** Title similarity, 0–1 */
+  confidence: number
+  suggested_action: string
+}
+
+export interface DiagnoseReport extends DiagnoseResult {
+  /** Highest confidence first */
+  findings: DiagnoseFinding[]
+}
+
 export interface EntitySearchResult {
   kind: 'feature' | 'ponder' | 'milestone' | 'investigation'
   slug: string
   title: string
   status: string
   score: number
+  /** Matching fragment with terms wrapped in <b>; absent for title-only matches */
+  snippet?: string
 }
 
 export interface QuerySearchResponse {
@@ -150,6 +181,14 @@ export interface QueryNeedsApprovalItem {
   next_command: string
 }
 
+export interface QueryStaleItem {
+  slug: string
+  title: string
+  phase: string
+  last_activity: string
+  idle_seconds: number
+}
+
 // ---------------------------------------------------------------------------
 // Auth token types
 // ---------------------------------------------------------------------------
@@ -223,9 +262,37 @@ export interface MilestoneFeatureReview {
   message: string
 }
 
+export type AcceptanceStepStatus = 'pending' | 'passed' | 'failed'
+
+export interface AcceptanceStep {
+  text: string
+  checked: boolean
+  annotation?: string
+  status: AcceptanceStepStatus
+}
+
 export interface MilestoneReview {
   milestone: string
   features: MilestoneFeatureReview[]
+  /** Steps from `uat_results.md` when a UAT run has signed them, else `acceptance_test.md`. */
+  acceptance_test: {
+    source: 'uat_results' | 'acceptance_test'
+    steps: AcceptanceStep[]
+  } | null
+}
+
+/** Work remaining at the end of one day, replayed from the audit log. */
+export interface BurndownPoint {
+  date: string
+  remaining_tasks: number
+  total_tasks: number
+  remaining_features: number
+  total_features: number
+}
+
+export interface MilestoneBurndown {
+  milestone: string
+  points: BurndownPoint[]
 }
 
 // ---------------------------------------------------------------------------
@@ -249,6 +316,8 @@ export interface EscalationDetail extends EscalationSummary {
   status: EscalationStatus
   resolved_at: string | null
   resolution: string | null
+  /** Feature created from this escalation, if any. */
+  spawned_feature?: string
 }
 
 export type ParallelWorkItemKind =
@@ -297,6 +366,36 @@ export interface Artifact {
   rejection_reason: string | null
   waived_at: string | null
   waive_reason: string | null
+  waive_expires_at?: string | null
+}
+
+export type ApprovalDecision = 'approved' | 'rejected' | 'waived'
+
+export interface ApprovalEntry {
+  decision: ApprovalDecision
+  actor: string
+  at: string
+  version?: string
+  reason?: string
+}
+
+export interface ArtifactDetails {
+  slug: string
+  artifact_type: string
+  phase: string
+  status: string
+  approved_at: string | null
+  approved_by: string | null
+  path: string
+  word_count: number
+  last_modified: string | null
+  content: string | null
+}
+
+export interface ArtifactHistory {
+  slug: string
+  artifact_type: string
+  history: ApprovalEntry[]
 }
 
 export interface Task {
@@ -304,6 +403,7 @@ export interface Task {
   title: string
   description: string | null
   status: TaskStatus
+  order: number
   created_at: string
   started_at: string | null
   completed_at: string | null
@@ -317,6 +417,16 @@ export interface Comment {
   flag: string | null
   target: Record<string, unknown>
   created_at: string
+  parent_id?: string
+}
+
+export type Priority = 'normal' | 'hotfix'
+
+export interface BulkTaskImport {
+  slug: string
+  created: number
+  skipped: number
+  tasks: { title: string; task_id: string; status: 'created' | 'skipped' }[]
 }
 
 export interface FeatureDetail {
@@ -324,6 +434,7 @@ export interface FeatureDetail {
   title: string
   description: string | null
   phase: Phase
+  priority: Priority
   archived: boolean
   blocked: boolean
   blockers: string[]
@@ -349,6 +460,7 @@ export interface Classification {
   description: string | null
   current_phase: Phase
   action: ActionType
+  rationale: string
   message: string
   next_command: string
   output_path: string | null
@@ -356,6 +468,12 @@ export interface Classification {
   task_id: string | null
   is_heavy: boolean
   timeout_minutes: number
+  /** Approved artifacts an upstream edit may have invalidated. */
+  stale_artifacts?: string[]
+  /** Open escalation a `wait_for_approval` directive is waiting on. */
+  blocked_by_escalation?: string
+  escalation_kind?: EscalationKind
+  escalation_title?: string
 }
 
 // ---------------------------------------------------------------------------
@@ -551,7 +669,8 @@ export interface PonderSseEvent {
 // Agent run tracking (panel)
 // ---------------------------------------------------------------------------
 
-export type RunStatus = 'running' | 'completed' | 'failed' | 'stopped'
+/** `queued` runs are waiting for a free slot (server `max_concurrent_runs`). */
+export type RunStatus = 'queued' | 'running' | 'completed' | 'failed' | 'stopped'
 export type RunType = 'feature' | 'milestone_uat' | 'milestone_prepare' | 'milestone_run_wave' | 'ponder' | 'investigation' | 'vision_align' | 'architecture_align'
 
 export interface RunRecord {
@@ -567,6 +686,26 @@ export interface RunRecord {
   turns?: number
   error?: string
   prompt?: string | null
+  model?: string
+  usage?: TokenUsage
+  duration_ms?: number
+}
+
+export interface TokenUsage {
+  input_tokens: number
+  output_tokens: number
+  cache_creation_input_tokens?: number
+  cache_read_input_tokens?: number
+}
+
+/** GET /api/runs/stats — rollup over finished runs. */
+export interface RunStats {
+  finished_runs: number
+  total_cost_usd: number
+  total_input_tokens: number
+  total_output_tokens: number
+  runs_per_model: Record<string, number>
+  average_duration_ms: number | null
 }
 
 // ---------------------------------------------------------------------------
@@ -575,7 +714,7 @@ export interface RunRecord {
 
 /** Raw event as stored in the events sidecar — matches message_to_event output */
 export interface RawRunEvent {
-  type: 'init' | 'assistant' | 'tool_progress' | 'tool_summary' | 'result' | 'error' | 'status' | 'system' | 'user' | 'stream_event' | 'auth_status' | 'subagent_started' | 'subagent_completed' | 'subagent_progress'
+  type: 'init' | 'assistant' | 'tool_progress' | 'tool_summary' | 'result' | 'error' | 'status' | 'system' | 'user' | 'stream_event' | 'assistant_delta' | 'auth_status' | 'permission_request' | 'interjection' | 'subagent_started' | 'subagent_completed' | 'subagent_progress'
   // Wall-clock timestamp (ISO-8601). Canonical field name — must match message_to_event() in
   // crates/sdlc-server/src/routes/runs.rs which writes obj["timestamp"]. Do NOT rename to "ts".
   timestamp?: string
@@ -585,8 +724,10 @@ export interface RawRunEvent {
   model?: string
   tools_count?: number
   mcp_servers?: string[]
-  // assistant
+  // assistant, assistant_delta, interjection
   text?: string
+  // interjection — who steered the run mid-flight
+  source?: 'human'
   tools?: { name: string; input: unknown }[]
   // Correlated tool ids (for pairing tool calls with results)
   tool_use_id?: string
@@ -610,6 +751,20 @@ export interface RawRunEvent {
   last_tool_name?: string
 }
 
+/** `lifecycle` SSE frame on a run's event stream. `completed` or `failed` is
+ *  always the frame right before the closing `done` event. */
+export type RunLifecycleEvent =
+  | { type: 'started'; run_id: string }
+  | { type: 'tool_batch'; tools: string[] }
+  | { type: 'completed'; status: 'completed' | 'paused'; usage: TokenUsage | null }
+  | { type: 'failed'; error: string }
+
+/** `lagged` SSE frame: this subscriber fell too far behind and missed
+ *  `skipped` frames; the stream continues from the oldest buffered one. */
+export interface RunLaggedEvent {
+  skipped: number
+}
+
 export interface RunTelemetry {
   run_id: string
   prompt?: string | null
@@ -656,7 +811,7 @@ export type PairedEvent =
   | PairedRunResult
 
 export interface RunSseEvent {
-  type: 'run_started' | 'run_finished'
+  type: 'run_started' | 'run_status' | 'run_finished'
   id: string
   key: string
   label?: string
@@ -978,7 +1133,12 @@ export interface AgentDefinition {
   name: string
   description: string
   model: string
+  color?: string
   tools: string[]
+  principles: string[]
+  codebase?: string
+  always: string[]
+  never: string[]
   content: string
 }
 